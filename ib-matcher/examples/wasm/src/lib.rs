@@ -0,0 +1 @@
+pub use ib_matcher::wasm::*;
@@ -9,10 +9,12 @@ A multilingual, flexible and fast string, glob and regex matcher. Support 拼音
   - Support characters with multiple readings (i.e. heteronyms, 多音字).
   - Support multiple pinyin notations, including [Quanpin (全拼)](https://zh.wikipedia.org/wiki/全拼), [Jianpin (简拼)](https://zh.wikipedia.org/wiki/简拼) and many [Shuangpin (双拼)](https://zh.wikipedia.org/wiki/%E5%8F%8C%E6%8B%BC) notations.
   - Support mixing multiple notations during matching.
+  - Support [fuzzy pinyin (模糊音)](pinyin::fuzzy), e.g. z/zh, n/l and in/ing.
 - [Japanese romaji](romaji) matching (ローマ字検索)
   - Support characters with multiple readings (i.e. heteronyms, 同形異音語).
   - Support [Hepburn romanization system](https://en.wikipedia.org/wiki/Hepburn_romanization)
     and its [convenient IME variant](romaji::convert::hepburn_ime).
+  - Support [Kunrei-shiki romanization system](romaji::RomanizationSystem) (feature `romaji-kunrei`).
   - Support handling of `n'`/`nn` and [`々`](romaji::kanji#handling-of-々noma).
 - [glob()-style](syntax::glob) pattern matching (i.e. `?`, `*`, `[]` and `**`)
   - Support [different anchor modes](syntax::glob#anchor-modes), [treating surrounding wildcards as anchors](syntax::glob#surrounding-wildcards-as-anchors) and [special anchors in file paths](syntax::glob#anchors-in-file-paths).
@@ -188,6 +190,7 @@ Use [`regex::cp::Regex`] if:
 //! These can improve the performance by 5~10% at most.
 //!
 //! ## Crate features
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(feature = "doc", doc = document_features::document_features!())]
 
@@ -206,6 +209,8 @@ pub mod regex;
     feature = "syntax-regex"
 ))]
 pub mod syntax;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[cfg(feature = "romaji")]
 pub use ib_romaji as romaji;
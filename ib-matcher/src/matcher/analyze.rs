@@ -1,4 +1,4 @@
-use std::cmp::min;
+use core::cmp::min;
 
 use bon::{bon, Builder};
 
@@ -30,6 +30,43 @@ impl Default for PatternAnalyzeConfig {
     }
 }
 
+/// Results of analyzing a pattern, see [`IbMatcher::analysis()`](super::IbMatcher::analysis).
+///
+/// They can be used to build prefilters in front of the matcher, e.g. skipping haystacks shorter than [`PatternAnalysis::min_haystack_len()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternAnalysis {
+    pub(crate) min_haystack_len: usize,
+    pub(crate) plain_only: bool,
+    #[cfg(feature = "pinyin")]
+    pub(crate) used_notations: PinyinNotation,
+}
+
+impl PatternAnalysis {
+    /// The minimum length of haystacks that can be matched, in bytes. Shorter haystacks never match.
+    ///
+    /// It is a lower bound, which is tighter if [`IbMatcherBuilder::analyze`](super::IbMatcherBuilder::analyze) is enabled.
+    pub fn min_haystack_len(&self) -> usize {
+        self.min_haystack_len
+    }
+
+    /// Whether no part of the pattern can be matched as pinyin or romaji, i.e. the pattern can only be matched as plain text by [`PlainMatchConfig`](super::PlainMatchConfig).
+    ///
+    /// If so, a match always contains the pattern itself (case-insensitively by default), which can be prefiltered by substring search.
+    ///
+    /// Romaji is not analyzed at the moment, so this is always `false` if romaji matching is enabled.
+    pub fn is_plain_only(&self) -> bool {
+        self.plain_only
+    }
+
+    /// Pinyin notations that can match some part of the pattern. Empty if pinyin matching is not enabled.
+    ///
+    /// Only accurate if [`IbMatcherBuilder::analyze`](super::IbMatcherBuilder::analyze) is enabled, otherwise all the enabled notations.
+    #[cfg(feature = "pinyin")]
+    pub fn used_notations(&self) -> PinyinNotation {
+        self.used_notations
+    }
+}
+
 pub(crate) struct PatternAnalyzer<'a> {
     pattern: &'a str,
     is_pattern_partial: bool,
@@ -115,7 +152,12 @@ impl<'a> PatternAnalyzer<'a> {
             self.set_min_haystack_len(ib_romaji::data::MIN_LEN);
         }
 
-        if config.traversal {
+        // Traversal does not take fuzzy pinyin into account
+        #[cfg(feature = "pinyin")]
+        let traversal = config.traversal && !self.pinyin.is_some_and(|py| !py.fuzzy.is_empty());
+        #[cfg(not(feature = "pinyin"))]
+        let traversal = config.traversal;
+        if traversal {
             #[cfg(feature = "pinyin")]
             {
                 self.pinyin_result.used_notations = PinyinNotation::empty();
@@ -142,7 +184,16 @@ impl<'a> PatternAnalyzer<'a> {
 
             // Traversal can give a better lower bound
             #[cfg(feature = "pinyin")]
-            let max_len = self.pinyin_result.used_notations.max_len();
+            let max_len = match self.pinyin.and_then(|py| py.shuangpin) {
+                // Shuangpin is 2 letters long
+                Some(_) => Some(
+                    self.pinyin_result
+                        .used_notations
+                        .max_len()
+                        .map_or(2, |len| len.max(2)),
+                ),
+                None => self.pinyin_result.used_notations.max_len(),
+            };
             #[cfg(not(feature = "pinyin"))]
             let max_len = None;
             let min_haystack_chars = {
@@ -184,58 +235,67 @@ impl<'a> PatternAnalyzer<'a> {
         let mut any_matched_single_char = false;
         #[cfg(feature = "pinyin")]
         if let Some(pinyin) = self.pinyin {
-            for notation in pinyin.notations.iter() {
-                // partial:
-                // Only pinyin > 4 bytes can make a difference on `min_haystack_len`,
-                // i.e. chong/chuai/chuan/chuang, jiong/qiong/xiong, zhong;
-                // otherwise it can't be shorter as the hanzi needs 3 bytes.
-                for (py, partial) in pinyin.data.match_pinyin_partial(
-                    notation,
-                    pattern,
-                    self.pinyin_result.partial_pattern,
-                ) {
-                    let matched = if partial { pattern } else { py };
-
-                    let mut matched_single_char = false;
-                    if matched.len() == 1 {
-                        matched_single_char = true;
-
-                        if notation == PinyinNotation::Ascii
-                            && pinyin.notations.contains(PinyinNotation::AsciiFirstLetter)
-                        {
-                            // Only let AsciiFirstLetter analyze to prune the tree
-                            continue;
-                        }
-                    } else if pinyin.notations.contains(PinyinNotation::Unicode)
-                        && matched.chars().nth(1).is_none()
+            // partial:
+            // Only pinyin > 4 bytes can make a difference on `min_haystack_len`,
+            // i.e. chong/chuai/chuan/chuang, jiong/qiong/xiong, zhong;
+            // otherwise it can't be shorter as the hanzi needs 3 bytes.
+            let partial_pattern = self.pinyin_result.partial_pattern;
+            let matches = pinyin.notations.iter().flat_map(|notation| {
+                pinyin
+                    .data
+                    .match_pinyin_partial(notation, pattern, partial_pattern)
+                    .map(move |m| (notation, m))
+            });
+            // The user-defined Shuangpin scheme is analyzed as an empty notation
+            let matches = matches.chain(pinyin.shuangpin.into_iter().flat_map(|shuangpin| {
+                shuangpin
+                    .match_keys_partial(pattern, partial_pattern)
+                    .map(|m| (PinyinNotation::empty(), m))
+            }));
+            for (notation, (py, partial)) in matches {
+                let matched = if partial { pattern } else { py };
+
+                let mut matched_single_char = false;
+                if matched.len() == 1 {
+                    matched_single_char = true;
+
+                    if notation == PinyinNotation::Ascii
+                        && pinyin.notations.contains(PinyinNotation::AsciiFirstLetter)
                     {
-                        matched_single_char = true;
-                    }
-                    any_matched_single_char |= matched_single_char;
-
-                    self.pinyin_result.used_notations |= notation;
-
-                    // `MAX_RANGE` starts from 0x3007, at least 3 bytes
-                    // `c.len_utf8()` for pruning the tree with `any_matched_single_char`
-                    let min_len = min_len
-                        + if matched_single_char {
-                            min(3, c.len_utf8())
-                        } else {
-                            3
-                        };
-
-                    #[cfg(test)]
-                    println!(
-                        "{}{py} {:X} min_len={min_len} single={matched_single_char}{}",
-                        " ".repeat(depth),
-                        notation.bits(),
-                        if partial { " partial" } else { "" }
-                    );
-
-                    self.sub_analyze(&pattern[matched.len()..], depth + 1, min_len);
-                    if self.traversal_count > Self::TRAVERSAL_LIMIT {
-                        return;
+                        // Only let AsciiFirstLetter analyze to prune the tree
+                        continue;
                     }
+                } else if pinyin
+                    .notations
+                    .intersects(PinyinNotation::Unicode | PinyinNotation::Zhuyin)
+                    && matched.chars().nth(1).is_none()
+                {
+                    matched_single_char = true;
+                }
+                any_matched_single_char |= matched_single_char;
+
+                self.pinyin_result.used_notations |= notation;
+
+                // `MAX_RANGE` starts from 0x3007, at least 3 bytes
+                // `c.len_utf8()` for pruning the tree with `any_matched_single_char`
+                let min_len = min_len
+                    + if matched_single_char {
+                        min(3, c.len_utf8())
+                    } else {
+                        3
+                    };
+
+                #[cfg(test)]
+                println!(
+                    "{}{py} {:X} min_len={min_len} single={matched_single_char}{}",
+                    " ".repeat(depth),
+                    notation.bits(),
+                    if partial { " partial" } else { "" }
+                );
+
+                self.sub_analyze(&pattern[matched.len()..], depth + 1, min_len);
+                if self.traversal_count > Self::TRAVERSAL_LIMIT {
+                    return;
                 }
             }
         }
@@ -247,7 +307,82 @@ impl<'a> PatternAnalyzer<'a> {
             #[cfg(test)]
             println!("{}{matched}", " ".repeat(depth));
             let len = matched.len_utf8();
-            self.sub_analyze(&pattern[len..], depth + 1, min_len + len);
+            // The pinyin separator may be skipped without matching anything
+            #[cfg(feature = "pinyin")]
+            let skipped = self.pinyin.is_some_and(|py| py.separator == Some(c));
+            #[cfg(not(feature = "pinyin"))]
+            let skipped = false;
+            self.sub_analyze(
+                &pattern[len..],
+                depth + 1 - skipped as usize,
+                min_len + if skipped { 0 } else { len },
+            );
+        }
+    }
+
+    /// See [`PatternAnalysis::is_plain_only()`].
+    pub fn is_plain_only(&self) -> bool {
+        #[cfg(feature = "romaji")]
+        if self.romaji.is_some() {
+            return false;
+        }
+        #[cfg(feature = "pinyin")]
+        if let Some(pinyin) = self.pinyin {
+            let partial_pattern = self.pinyin_result.partial_pattern;
+            return !self.pattern.char_indices().any(|(i, _)| {
+                let pattern = &self.pattern[i..];
+                pinyin.notations.iter().any(|notation| {
+                    pinyin
+                        .data
+                        .match_pinyin_partial(notation, pattern, partial_pattern)
+                        .next()
+                        .is_some()
+                }) || pinyin.shuangpin.is_some_and(|shuangpin| {
+                    shuangpin
+                        .match_keys_partial(pattern, partial_pattern)
+                        .next()
+                        .is_some()
+                })
+            });
+        }
+        true
+    }
+
+    /// Mark the bytes of `pattern` that may be matched as pinyin.
+    ///
+    /// `pattern` should be the lowercase one if pinyin is case insensitive.
+    #[cfg(feature = "pinyin")]
+    pub fn mark_pinyin_bytes(
+        pinyin: &PinyinMatchConfig,
+        pattern: &str,
+        partial_pattern: bool,
+        marks: &mut [bool],
+    ) {
+        for (i, c) in pattern.char_indices() {
+            let rest = &pattern[i..];
+            // Fuzzy pinyins are not in the data
+            if !pinyin.fuzzy.is_empty() && c.is_ascii_alphabetic() {
+                marks[i] = true;
+            }
+            let matches = pinyin.notations.iter().flat_map(|notation| {
+                pinyin
+                    .data
+                    .match_pinyin_partial(notation, rest, partial_pattern)
+            });
+            let matches = matches.chain(
+                pinyin
+                    .shuangpin
+                    .into_iter()
+                    .flat_map(|shuangpin| shuangpin.match_keys_partial(rest, partial_pattern)),
+            );
+            for (py, partial) in matches {
+                let mut len = if partial { rest.len() } else { py.len() };
+                // The separator after a pinyin is skipped when matched
+                if let Some(separator) = pinyin.separator.filter(|&s| rest[len..].starts_with(s)) {
+                    len += separator.len_utf8();
+                }
+                marks[i..i + len].fill(true);
+            }
         }
     }
 
@@ -342,6 +477,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn is_plain_only() {
+        let pinyin_data = PinyinData::new(PinyinNotation::all());
+        let pinyin =
+            PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+                .data(&pinyin_data)
+                .build();
+
+        assert!(PatternAnalyzer::builder("pinyin").build().is_plain_only());
+        assert!(!PatternAnalyzer::builder("pinyin")
+            .pinyin(&pinyin)
+            .build()
+            .is_plain_only());
+        assert!(!PatternAnalyzer::builder("1.p")
+            .pinyin(&pinyin)
+            .build()
+            .is_plain_only());
+        assert!(PatternAnalyzer::builder("1.2_3")
+            .pinyin(&pinyin)
+            .build()
+            .is_plain_only());
+        assert!(PatternAnalyzer::builder("拼音")
+            .pinyin(&pinyin)
+            .build()
+            .is_plain_only());
+    }
+
     #[test]
     fn min_haystack_len() {
         let pinyin_data = PinyinData::new(PinyinNotation::all());
@@ -1,4 +1,5 @@
-use std::iter;
+use alloc::vec::Vec;
+use core::iter;
 
 use aho_corasick::{automaton::Automaton, Anchored, StartKind};
 use bon::{bon, Builder};
@@ -8,9 +9,11 @@ use crate::matcher::Match;
 /// Note [`PlainMatchConfigBuilder::case_insensitive`] is `true` by default, unlike [`PinyinMatchConfigBuilder`](super::PinyinMatchConfigBuilder) and [`RomajiMatchConfigBuilder`](super::RomajiMatchConfigBuilder).
 #[derive(Builder, Clone, Debug)]
 pub struct PlainMatchConfig {
-    /// The case insensitivity of pinyin is controlled by [`PinyinMatchConfigBuilder::case_insensitive`](super::PinyinMatchConfigBuilder::case_insensitive).
-    #[builder(default = true)]
-    pub(crate) case_insensitive: bool,
+    /// Whether upper and lower case letters can match each other.
+    ///
+    /// Default: [`IbMatcherBuilder::case()`](super::IbMatcherBuilder::case) if set, otherwise `true`.
+    /// See [`IbMatcherBuilder::case()`](super::IbMatcherBuilder::case) for the other languages.
+    pub(crate) case_insensitive: Option<bool>,
 
     #[builder(default = true, setters(vis = "pub(crate)"))]
     pub(crate) maybe_ascii: bool,
@@ -19,10 +22,16 @@ pub struct PlainMatchConfig {
 impl PlainMatchConfig {
     pub(crate) fn case_insensitive(case_insensitive: bool) -> Option<Self> {
         Some(Self {
-            case_insensitive,
+            case_insensitive: Some(case_insensitive),
             maybe_ascii: true,
         })
     }
+
+    /// [`PlainMatchConfigBuilder::case_insensitive`], or its default if unset.
+    #[inline]
+    pub(crate) fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive.unwrap_or(true)
+    }
 }
 
 /// For ASCII-only haystack optimization.
@@ -101,8 +110,12 @@ impl<const CHAR_LEN: usize> AsciiMatcher<CHAR_LEN> {
         plain: Option<&PlainMatchConfig>,
         #[builder(default = false)] starts_with: bool,
         #[builder(default = false)] ends_with: bool,
+        /// Never match, e.g. if the match length would be out of bounds.
+        /// [`AsciiMatcher::find_first_or_non_ascii_byte()`] still works as the plain config.
+        #[builder(default = false)]
+        fail: bool,
     ) -> Self {
-        let imp = match plain.filter(|_| pattern.is_ascii()) {
+        let imp = match plain.filter(|_| pattern.is_ascii() && !fail) {
             Some(plain) => {
                 // regex::bytes::RegexBuilder::new(&regex_utils::escape_bytes(pattern))
                 //     .unicode(false)
@@ -125,7 +138,7 @@ impl<const CHAR_LEN: usize> AsciiMatcher<CHAR_LEN> {
                 // })
                 AcDFA(AcDfaMatcher {
                     dfa: aho_corasick::dfa::DFA::builder()
-                        .ascii_case_insensitive(plain.case_insensitive)
+                        .ascii_case_insensitive(plain.is_case_insensitive())
                         .start_kind(if starts_with {
                             StartKind::Anchored
                         } else {
@@ -135,12 +148,12 @@ impl<const CHAR_LEN: usize> AsciiMatcher<CHAR_LEN> {
                         .unwrap(),
                     starts_with,
                     ends_with,
-                    pattern: if plain.case_insensitive {
+                    pattern: if plain.is_case_insensitive() {
                         pattern.to_ascii_lowercase()
                     } else {
                         pattern.into()
                     },
-                    case_insensitive: plain.case_insensitive,
+                    case_insensitive: plain.is_case_insensitive(),
                 })
             }
             None => Fail,
@@ -149,7 +162,10 @@ impl<const CHAR_LEN: usize> AsciiMatcher<CHAR_LEN> {
         // Or FF/FE?
         // TODO: Mask?
         let b = pattern.first().copied().unwrap_or(0);
-        let first_byte = if plain.as_ref().is_some_and(|plain| plain.case_insensitive) {
+        let first_byte = if plain
+            .as_ref()
+            .is_some_and(|plain| plain.is_case_insensitive())
+        {
             // Lowercase letters occur more often
             if b.is_ascii_lowercase() {
                 (b, b.to_ascii_uppercase())
@@ -163,6 +179,12 @@ impl<const CHAR_LEN: usize> AsciiMatcher<CHAR_LEN> {
         Self { imp, first_byte }
     }
 
+    /// Never matches, e.g. the pattern is not ASCII.
+    #[inline(always)]
+    pub fn is_fail(&self) -> bool {
+        matches!(self.imp, Fail)
+    }
+
     pub fn find(&self, haystack: &[u8]) -> Option<Match> {
         match &self.imp {
             Fail => None,
@@ -255,6 +277,23 @@ impl<const CHAR_LEN: usize> AsciiMatcher<CHAR_LEN> {
         }
     }
 
+    /// The number of non-overlapping matches, without constructing them.
+    ///
+    /// Returns `None` if anchored, in which case the caller should count by [`AsciiMatcher::find()`] instead.
+    pub fn count(&self, haystack: &[u8]) -> Option<usize> {
+        match &self.imp {
+            Fail => Some(0),
+            AcDFA(ac) if !ac.starts_with && !ac.ends_with => {
+                Some(ac.dfa.try_find_iter(ac.input(haystack)).unwrap().count())
+            }
+            #[cfg(feature = "perf-plain-ac")]
+            Ac(ac) if !ac.starts_with && !ac.ends_with => {
+                Some(ac.ac.find_iter(ac.input(haystack)).count())
+            }
+            _ => None,
+        }
+    }
+
     #[inline(always)]
     pub fn test_first_byte(&self, b: u8) -> bool {
         b == self.first_byte.0 || b == self.first_byte.1
@@ -398,6 +437,29 @@ mod tests {
         assert!(!matcher.is_match(b"xyzab"));
     }
 
+    #[test]
+    fn count() {
+        let matcher = AsciiMatcher::<1>::builder(b"aa")
+            .maybe_plain(PlainMatchConfig::case_insensitive(true).as_ref())
+            .build();
+        assert_eq!(matcher.count(b""), Some(0));
+        assert_eq!(matcher.count(b"a"), Some(0));
+        assert_eq!(matcher.count(b"aAaaa"), Some(2));
+        assert_eq!(matcher.count(b"aa-aa-a"), Some(2));
+
+        let matcher = AsciiMatcher::<1>::builder(b"aa")
+            .maybe_plain(PlainMatchConfig::case_insensitive(true).as_ref())
+            .ends_with(true)
+            .build();
+        assert_eq!(matcher.count(b"aaaa"), None);
+
+        // Non-ASCII pattern
+        let matcher = AsciiMatcher::<1>::builder("行".as_bytes())
+            .maybe_plain(PlainMatchConfig::case_insensitive(true).as_ref())
+            .build();
+        assert_eq!(matcher.count(b"xing"), Some(0));
+    }
+
     #[test]
     fn starts_with() {
         let matcher = AsciiMatcher::<1>::builder(b"abc")
@@ -1,9 +1,9 @@
 #![cfg_attr(not(feature = "regex"), allow(unused))]
-use std::{
+use alloc::{sync::Arc, vec::Vec};
+use core::{
     marker::PhantomPinned,
     mem::{transmute, MaybeUninit},
     ops::Deref,
-    sync::Arc,
 };
 
 use crate::matcher::{pattern::Pattern, IbMatcher, MatchConfig};
@@ -42,6 +42,46 @@ impl<'a> IbMatcherWithConfig<'a> {
     }
 }
 
+/// [`IbMatcherWithConfig`] with multiple patterns sharing the same config.
+pub(crate) struct IbMatchersWithConfig<'a> {
+    /// Declared before `config` to be dropped before it.
+    matchers: Vec<IbMatcher<'a>>,
+    /// See [`IbMatcherWithConfig`].
+    config: MatchConfig<'a>,
+    _pin: PhantomPinned,
+}
+
+impl<'a> IbMatchersWithConfig<'a> {
+    pub fn with_config<'p, P: Into<Pattern<'p, str>>>(
+        patterns: impl IntoIterator<Item = P>,
+        config: MatchConfig<'a>,
+    ) -> Arc<Self> {
+        let mut this = Arc::new(Self {
+            matchers: Vec::new(),
+            config,
+            _pin: PhantomPinned,
+        });
+
+        // `shallow_clone()` requires `config` cannot be moved
+        let config: &MatchConfig<'static> = unsafe { transmute(&this.config) };
+        let matchers = patterns
+            .into_iter()
+            .map(|pattern| IbMatcher::with_config(pattern, config.shallow_clone()))
+            .collect();
+        unsafe { Arc::get_mut(&mut this).unwrap_unchecked() }.matchers = matchers;
+
+        this
+    }
+}
+
+impl<'a> Deref for IbMatchersWithConfig<'a> {
+    type Target = [IbMatcher<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.matchers
+    }
+}
+
 impl<'a> Deref for IbMatcherWithConfig<'a> {
     type Target = IbMatcher<'a>;
 
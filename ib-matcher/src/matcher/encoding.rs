@@ -1,8 +1,22 @@
-use std::{ops::RangeFrom, slice::SliceIndex};
+use core::{
+    ops::{Range, RangeFrom},
+    slice::SliceIndex,
+};
+
+use ib_unicode::str::RoundCharBoundaryExt;
 
 /// ## Performance
 /// Although multiple encodings are supported, UTF-8 (`str`) is most optimized.
 ///
+/// ## Unpaired surrogates
+/// UTF-16 ([`U16Str`](widestring::U16Str)) and WTF-8 ([`OsStr`](std::ffi::OsStr) on Windows) strings can contain unpaired surrogates,
+/// e.g. in file names. Since a surrogate can't form a valid scalar value on its own,
+/// it is decoded as `U+FFFD` (REPLACEMENT CHARACTER) of its own length (1 in UTF-16, 3 in WTF-8):
+/// - It can only be matched by a `U+FFFD` in the pattern, like a real `U+FFFD` in the haystack.
+/// - Chars around it are matched as usual, and match offsets are still in units of the original string.
+///
+/// Similarly, invalid code units of [`U32Str`](widestring::U32Str) and invalid bytes of `OsStr` on other platforms are decoded as `U+FFFD` one unit each.
+///
 /// TODO: Extended ASCII code pages
 /// TODO: Index/SliceIndex
 pub trait EncodedStr: Sealed {
@@ -16,15 +30,47 @@ pub trait EncodedStr: Sealed {
     fn is_ascii(&self) -> bool;
     fn as_bytes(&self) -> &[u8];
 
+    /// `self[i]` without bounds and char boundary checks.
+    ///
+    /// ## Safety
+    /// `i` must be in bounds of `self`, and its ends must be at char boundaries,
+    /// see [`floor_char_boundary()`](Self::floor_char_boundary).
     unsafe fn get_unchecked<I: SliceIndex<Self::SLICE, Output = Self::SLICE>>(&self, i: I)
         -> &Self;
+    /// [`get_unchecked()`](Self::get_unchecked) with a [`RangeFrom`].
+    ///
+    /// ## Safety
+    /// `range.start` must be in bounds of `self` and at a char boundary.
     unsafe fn get_unchecked_from(&self, range: RangeFrom<usize>) -> &Self;
+    /// [`get_unchecked()`](Self::get_unchecked) with a [`Range`].
+    ///
+    /// ## Safety
+    /// `range.start <= range.end`, and both of them must be in bounds of `self` and at char boundaries.
+    unsafe fn get_unchecked_range(&self, range: Range<usize>) -> &Self;
+    /// `self` with the `len` elements before it.
+    ///
+    /// ## Safety
+    /// There must be at least `len` elements of the same str before `self`.
+    unsafe fn extend_start_unchecked(&self, len: usize) -> &Self;
+
+    /// Finds the closest char boundary not exceeding `index`, or the length if `index` is greater.
+    fn floor_char_boundary(&self, index: usize) -> usize;
 
     fn char_index_strs(&self) -> impl Iterator<Item = (usize, char, &Self)>;
     fn char_len_next_strs(&self) -> impl Iterator<Item = (char, usize, &Self)>;
     fn chars_count(&self) -> usize {
         self.char_index_strs().count()
     }
+
+    /// Whether `index` is at an [extended grapheme cluster](https://www.unicode.org/reports/tr29/) boundary,
+    /// treating the start of `self` as the start of the text.
+    ///
+    /// Only UTF-8 is supported at the moment. For other encodings, every char boundary is treated as a grapheme cluster boundary.
+    #[cfg(feature = "unicode-segmentation")]
+    fn is_grapheme_boundary(&self, index: usize) -> bool {
+        let _ = index;
+        true
+    }
 }
 
 mod private {
@@ -37,6 +83,8 @@ impl Sealed for str {}
 impl Sealed for widestring::U16Str {}
 #[cfg(feature = "encoding")]
 impl Sealed for widestring::U32Str {}
+#[cfg(all(feature = "encoding", feature = "std"))]
+impl Sealed for std::ffi::OsStr {}
 
 impl EncodedStr for str {
     type CHAR = u8;
@@ -63,6 +111,22 @@ impl EncodedStr for str {
         self.get_unchecked(range)
     }
 
+    unsafe fn get_unchecked_range(&self, range: Range<usize>) -> &Self {
+        self.get_unchecked(range)
+    }
+
+    unsafe fn extend_start_unchecked(&self, len: usize) -> &Self {
+        // https://github.com/rust-lang/rust/issues/119206
+        core::str::from_utf8_unchecked(core::slice::from_raw_parts(
+            self.as_ptr().sub(len),
+            self.len() + len,
+        ))
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        RoundCharBoundaryExt::floor_char_boundary_ib(self, index)
+    }
+
     fn char_index_strs(&self) -> impl Iterator<Item = (usize, char, &Self)> {
         self.char_indices().map(|(i, c)| (i, c, &self[i..]))
     }
@@ -77,6 +141,13 @@ impl EncodedStr for str {
     fn chars_count(&self) -> usize {
         self.chars().count()
     }
+
+    #[cfg(feature = "unicode-segmentation")]
+    fn is_grapheme_boundary(&self, index: usize) -> bool {
+        unicode_segmentation::GraphemeCursor::new(index, self.len(), true)
+            .is_boundary(self, 0)
+            .unwrap_or(true)
+    }
 }
 
 #[cfg(feature = "encoding")]
@@ -109,6 +180,28 @@ impl EncodedStr for widestring::U16Str {
         self.get_unchecked(range)
     }
 
+    unsafe fn get_unchecked_range(&self, range: Range<usize>) -> &Self {
+        self.get_unchecked(range)
+    }
+
+    unsafe fn extend_start_unchecked(&self, len: usize) -> &Self {
+        widestring::U16Str::from_ptr(self.as_ptr().sub(len), self.len() + len)
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        if index >= self.len() {
+            self.len()
+        } else if index > 0
+            && (0xDC00..=0xDFFF).contains(&self.as_slice()[index])
+            && (0xD800..=0xDBFF).contains(&self.as_slice()[index - 1])
+        {
+            // The low surrogate of a surrogate pair
+            index - 1
+        } else {
+            index
+        }
+    }
+
     fn char_index_strs(&self) -> impl Iterator<Item = (usize, char, &Self)> {
         self.char_indices_lossy().map(|(i, c)| (i, c, &self[i..]))
     }
@@ -121,6 +214,8 @@ impl EncodedStr for widestring::U16Str {
     }
 }
 
+/// Offsets are in code points.
+/// `&[char]` can be matched as [`U32Str::from_char_slice()`](widestring::U32Str::from_char_slice) without copying.
 #[cfg(feature = "encoding")]
 impl EncodedStr for widestring::U32Str {
     type CHAR = u32;
@@ -151,6 +246,18 @@ impl EncodedStr for widestring::U32Str {
         self.get_unchecked(range)
     }
 
+    unsafe fn get_unchecked_range(&self, range: Range<usize>) -> &Self {
+        self.get_unchecked(range)
+    }
+
+    unsafe fn extend_start_unchecked(&self, len: usize) -> &Self {
+        widestring::U32Str::from_ptr(self.as_ptr().sub(len), self.len() + len)
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        index.min(self.len())
+    }
+
     fn char_index_strs(&self) -> impl Iterator<Item = (usize, char, &Self)> {
         self.char_indices_lossy().map(|(i, c)| (i, c, &self[i..]))
     }
@@ -161,6 +268,168 @@ impl EncodedStr for widestring::U32Str {
     }
 }
 
+/// [`OsStr::as_encoded_bytes()`](std::ffi::OsStr::as_encoded_bytes) is WTF-8 on Windows and arbitrary bytes (usually UTF-8) on other platforms,
+/// both of which are supersets of UTF-8. So file names can be matched without transcoding.
+///
+/// For [`Path`](std::path::Path), use [`Path::as_os_str()`](std::path::Path::as_os_str).
+///
+/// See [unpaired surrogates](EncodedStr#unpaired-surrogates) for how surrogates and invalid bytes are handled.
+///
+/// ## Example
+/// ```
+/// // cargo add ib-matcher --features pinyin,encoding
+/// use std::{ffi::OsStr, path::Path};
+/// use ib_matcher::{matcher::{IbMatcher, PinyinMatchConfig}, pinyin::PinyinNotation};
+///
+/// let matcher = IbMatcher::builder(OsStr::new("pysousuoeve"))
+///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+///     .build();
+/// assert!(matcher.is_match(Path::new("拼音搜索Everything.exe").as_os_str()));
+/// ```
+#[cfg(all(feature = "encoding", feature = "std"))]
+impl EncodedStr for std::ffi::OsStr {
+    type CHAR = u8;
+    type SLICE = [u8];
+
+    fn is_ascii(&self) -> bool {
+        self.as_encoded_bytes().is_ascii()
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.as_encoded_bytes()
+    }
+
+    unsafe fn get_unchecked<I: SliceIndex<Self::SLICE, Output = Self::SLICE>>(
+        &self,
+        i: I,
+    ) -> &Self {
+        // Only sliced at char boundaries
+        std::ffi::OsStr::from_encoded_bytes_unchecked(self.as_encoded_bytes().get_unchecked(i))
+    }
+
+    unsafe fn get_unchecked_from(&self, range: RangeFrom<usize>) -> &Self {
+        self.get_unchecked(range)
+    }
+
+    unsafe fn get_unchecked_range(&self, range: Range<usize>) -> &Self {
+        self.get_unchecked(range)
+    }
+
+    unsafe fn extend_start_unchecked(&self, len: usize) -> &Self {
+        let bytes = self.as_encoded_bytes();
+        std::ffi::OsStr::from_encoded_bytes_unchecked(core::slice::from_raw_parts(
+            bytes.as_ptr().sub(len),
+            bytes.len() + len,
+        ))
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        let bytes = self.as_encoded_bytes();
+        if index >= bytes.len() {
+            return bytes.len();
+        }
+        if !is_continuation_byte(bytes[index]) {
+            return index;
+        }
+        // A char is at most 4 bytes
+        match (index.saturating_sub(3)..index)
+            .rev()
+            .find(|&start| !is_continuation_byte(bytes[start]))
+        {
+            Some(start) if start + decode_wtf8_lossy(&bytes[start..]).1 > index => start,
+            _ => index,
+        }
+    }
+
+    fn char_index_strs(&self) -> impl Iterator<Item = (usize, char, &Self)> {
+        wtf8_char_indices_lossy(self.as_encoded_bytes())
+            .map(|(i, c, _)| (i, c, unsafe { self.get_unchecked_from(i..) }))
+    }
+
+    fn char_len_next_strs(&self) -> impl Iterator<Item = (char, usize, &Self)> {
+        wtf8_char_indices_lossy(self.as_encoded_bytes())
+            .map(|(i, c, len)| (c, len, unsafe { self.get_unchecked_from(i + len..) }))
+    }
+}
+
+#[cfg(all(feature = "encoding", feature = "std"))]
+fn is_continuation_byte(b: u8) -> bool {
+    b & 0xC0 == 0x80
+}
+
+/// Decode the first char of non-empty WTF-8 `bytes`, and return it with its length.
+///
+/// Surrogates are decoded as `U+FFFD` of 3 bytes, and invalid bytes as `U+FFFD` of 1 byte.
+#[cfg(all(feature = "encoding", feature = "std"))]
+fn decode_wtf8_lossy(bytes: &[u8]) -> (char, usize) {
+    let b = bytes[0];
+    let (len, min, mut c) = match b {
+        0x00..=0x7F => return (b as char, 1),
+        0xC2..=0xDF => (2, 0x80, (b & 0x1F) as u32),
+        0xE0..=0xEF => (3, 0x800, (b & 0x0F) as u32),
+        0xF0..=0xF4 => (4, 0x10000, (b & 0x07) as u32),
+        _ => return (char::REPLACEMENT_CHARACTER, 1),
+    };
+    let Some(tail) = bytes.get(1..len) else {
+        return (char::REPLACEMENT_CHARACTER, 1);
+    };
+    for &b in tail {
+        if !is_continuation_byte(b) {
+            return (char::REPLACEMENT_CHARACTER, 1);
+        }
+        c = c << 6 | (b & 0x3F) as u32;
+    }
+    if c < min {
+        // Overlong
+        return (char::REPLACEMENT_CHARACTER, 1);
+    }
+    match char::from_u32(c) {
+        Some(c) => (c, len),
+        // Surrogate
+        None if len == 3 => (char::REPLACEMENT_CHARACTER, len),
+        // > U+10FFFF
+        None => (char::REPLACEMENT_CHARACTER, 1),
+    }
+}
+
+/// `(index, char, len)` of each char in WTF-8 `bytes`. See [`decode_wtf8_lossy()`].
+#[cfg(all(feature = "encoding", feature = "std"))]
+fn wtf8_char_indices_lossy(bytes: &[u8]) -> impl Iterator<Item = (usize, char, usize)> + '_ {
+    let mut i = 0;
+    core::iter::from_fn(move || {
+        let rest = bytes.get(i..).filter(|rest| !rest.is_empty())?;
+        let (c, len) = decode_wtf8_lossy(rest);
+        let item = (i, c, len);
+        i += len;
+        Some(item)
+    })
+}
+
+/// The valid UTF-8 chunks of `bytes` and their offsets, with invalid byte sequences skipped.
+///
+/// Every chunk except the last one is followed by invalid bytes. Chunks can be empty, e.g. if `bytes` starts with invalid bytes.
+pub(crate) fn utf8_chunks(bytes: &[u8]) -> impl Iterator<Item = (usize, &str)> + '_ {
+    let mut i = 0;
+    core::iter::from_fn(move || {
+        let start = i;
+        let rest = bytes.get(i..)?;
+        match core::str::from_utf8(rest) {
+            Ok(chunk) => {
+                i = usize::MAX;
+                Some((start, chunk))
+            }
+            Err(e) => {
+                let valid = e.valid_up_to();
+                // An incomplete char at the end if `error_len()` is `None`
+                i += valid + e.error_len().unwrap_or(rest.len() - valid);
+                Some((start, unsafe {
+                    core::str::from_utf8_unchecked(&rest[..valid])
+                }))
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
@@ -185,4 +454,161 @@ mod tests {
         assert!(u32str!("abc").is_ascii());
         assert!(u32str!("协作").is_ascii() == false);
     }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn u16_unpaired_surrogate() {
+        use widestring::U16String;
+
+        // a, high surrogate, 行, low surrogate
+        let s = U16String::from_vec([b'a' as u16, 0xD800, 0x884C, 0xDC00]);
+        assert_eq!(
+            s.char_index_strs()
+                .map(|(i, c, _)| (i, c))
+                .collect::<Vec<_>>(),
+            [(0, 'a'), (1, '\u{FFFD}'), (2, '行'), (3, '\u{FFFD}')]
+        );
+        assert_eq!(s.floor_char_boundary(2), 2);
+        assert_eq!(s.chars_count(), 4);
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn u16_surrogate_pair() {
+        use widestring::u16str;
+
+        // 𪚥 (U+2A6A5) is a pair in UTF-16
+        let s = u16str!("a𪚥行");
+        assert_eq!(
+            s.char_index_strs()
+                .map(|(i, c, s)| (i, c, s.len()))
+                .collect::<Vec<_>>(),
+            [(0, 'a', 4), (1, '𪚥', 3), (3, '行', 1)]
+        );
+        assert_eq!(
+            s.char_len_next_strs()
+                .map(|(c, len, next)| (c, len, next.len()))
+                .collect::<Vec<_>>(),
+            [('a', 1, 3), ('𪚥', 2, 1), ('行', 1, 0)]
+        );
+        assert_eq!(s.floor_char_boundary(1), 1);
+        assert_eq!(s.floor_char_boundary(2), 1);
+        assert_eq!(s.floor_char_boundary(3), 3);
+        assert_eq!(s.chars_count(), 3);
+    }
+
+    #[cfg(all(feature = "encoding", feature = "std"))]
+    #[test]
+    fn wtf8() {
+        assert_eq!(decode_wtf8_lossy(b"a"), ('a', 1));
+        assert_eq!(decode_wtf8_lossy("行".as_bytes()), ('行', 3));
+        assert_eq!(decode_wtf8_lossy("𝄞".as_bytes()), ('𝄞', 4));
+        // Surrogates
+        assert_eq!(decode_wtf8_lossy(b"\xED\xA0\x80"), ('\u{FFFD}', 3));
+        assert_eq!(decode_wtf8_lossy(b"\xED\xBF\xBF"), ('\u{FFFD}', 3));
+        // Invalid
+        assert_eq!(decode_wtf8_lossy(b"\xFF"), ('\u{FFFD}', 1));
+        assert_eq!(decode_wtf8_lossy(b"\x80"), ('\u{FFFD}', 1));
+        assert_eq!(decode_wtf8_lossy(b"\xE8\xA1"), ('\u{FFFD}', 1));
+        assert_eq!(decode_wtf8_lossy(b"\xC0\x80"), ('\u{FFFD}', 1));
+        assert_eq!(decode_wtf8_lossy(b"\xF4\x90\x80\x80"), ('\u{FFFD}', 1));
+
+        assert_eq!(
+            wtf8_char_indices_lossy(b"a\xED\xA0\x80\xE8\xA1\x8C\xE8\xA1").collect::<Vec<_>>(),
+            [
+                (0, 'a', 1),
+                (1, '\u{FFFD}', 3),
+                (4, '行', 3),
+                (7, '\u{FFFD}', 1),
+                (8, '\u{FFFD}', 1)
+            ]
+        );
+    }
+
+    /// `a`, an unpaired high surrogate, `行`.
+    #[cfg(all(feature = "encoding", feature = "std"))]
+    fn os_str_with_surrogate() -> std::ffi::OsString {
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStringExt;
+            std::ffi::OsString::from_wide(&[b'a' as u16, 0xD800, 0x884C])
+        }
+        #[cfg(not(windows))]
+        unsafe {
+            std::ffi::OsString::from_encoded_bytes_unchecked(b"a\xED\xA0\x80\xE8\xA1\x8C".to_vec())
+        }
+    }
+
+    #[cfg(all(feature = "encoding", feature = "std"))]
+    #[test]
+    fn os_str() {
+        use std::ffi::OsStr;
+
+        use crate::matcher::IbMatcher;
+
+        assert!(OsStr::new("abc").is_ascii());
+        assert!(!OsStr::new("协作").is_ascii());
+
+        let s = os_str_with_surrogate();
+        let s = s.as_os_str();
+        assert!(!s.is_ascii());
+        assert_eq!(
+            s.char_index_strs()
+                .map(|(i, c, _)| (i, c))
+                .collect::<Vec<_>>(),
+            [(0, 'a'), (1, '\u{FFFD}'), (4, '行')]
+        );
+        assert_eq!(
+            s.char_len_next_strs()
+                .map(|(c, len, next)| (c, len, next.len()))
+                .collect::<Vec<_>>(),
+            [('a', 1, 6), ('\u{FFFD}', 3, 3), ('行', 3, 0)]
+        );
+        let boundaries: Vec<_> = (0..=8).map(|i| s.floor_char_boundary(i)).collect();
+        assert_eq!(boundaries, [0, 1, 1, 1, 4, 4, 4, 7, 7]);
+
+        let matcher = IbMatcher::builder(OsStr::new("行")).build();
+        assert_eq!(matcher.find(s).map(|m| m.range()), Some(4..7));
+        let matcher = IbMatcher::builder(OsStr::new("\u{FFFD}")).build();
+        assert_eq!(matcher.find(s).map(|m| m.range()), Some(1..4));
+        #[cfg(feature = "pinyin")]
+        {
+            use crate::{matcher::PinyinMatchConfig, pinyin::PinyinNotation};
+
+            let matcher = IbMatcher::builder(OsStr::new("xing"))
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .build();
+            assert_eq!(matcher.find(s).map(|m| m.range()), Some(4..7));
+            assert_eq!(matcher.find_at(s, 2).map(|m| m.range()), Some(4..7));
+        }
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn grapheme_boundary() {
+        // e + U+0301, man + ZWJ + woman + ZWJ + girl, CRLF
+        let s = "e\u{301}👨‍👩‍👧\r\n";
+        assert!(s.is_grapheme_boundary(0));
+        assert!(!s.is_grapheme_boundary(1));
+        assert!(s.is_grapheme_boundary(3));
+        assert!(!s.is_grapheme_boundary(7));
+        assert!(!s.is_grapheme_boundary(17));
+        assert!(s.is_grapheme_boundary(21));
+        assert!(!s.is_grapheme_boundary(22));
+        assert!(s.is_grapheme_boundary(23));
+    }
+
+    #[test]
+    fn utf8_chunks_() {
+        fn chunks(bytes: &[u8]) -> Vec<(usize, &str)> {
+            utf8_chunks(bytes).collect()
+        }
+        assert_eq!(chunks(b""), [(0, "")]);
+        assert_eq!(chunks("a拼".as_bytes()), [(0, "a拼")]);
+        assert_eq!(chunks(b"a\xffb"), [(0, "a"), (2, "b")]);
+        assert_eq!(chunks(b"\xff\xfea"), [(0, ""), (1, ""), (2, "a")]);
+        // Incomplete chars
+        assert_eq!(chunks(b"a\xe6\x8bb"), [(0, "a"), (3, "b")]);
+        assert_eq!(chunks(b"a\xe6\x8b"), [(0, "a"), (3, "")]);
+    }
 }
@@ -1,10 +1,34 @@
 //! ## Performance
 //! With default `release` profile, using `Input` instead of `&HaystackStr` is 3~5% slower (without using Bon), while with `lto = "fat"` and `codegen-units = 1` using `Input` is 3~5% faster, well...
+use core::ops::{Bound, RangeBounds};
+
 use bon::Builder;
 
 use crate::matcher::encoding::EncodedStr;
 
-#[derive(Builder, Clone)]
+/// The input of a search by [`IbMatcher`](super::IbMatcher), akin to [`regex::Input`](crate::regex::Input).
+///
+/// All the match methods of [`IbMatcher`](super::IbMatcher) accept `impl Into<Input>`, so a plain `&HaystackStr` can be passed directly.
+///
+/// ## Example
+/// ```
+/// use ib_matcher::{
+///     matcher::{IbMatcher, Input, PinyinMatchConfig},
+///     pinyin::PinyinNotation,
+/// };
+///
+/// let matcher = IbMatcher::builder("pinyin")
+///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+///     .build();
+/// let haystack = "拼音, 拼音";
+/// // The match offsets are in the whole haystack
+/// assert_eq!(matcher.find(Input::new(haystack).range(6..)).unwrap().range(), 8..14);
+/// assert!(matcher.find(Input::new(haystack).range(..3)).is_none());
+///
+/// assert!(matcher.find(Input::new(haystack).range(6..).anchored(true)).is_none());
+/// assert_eq!(matcher.find(Input::new(haystack).range(8..).anchored(true)).unwrap().range(), 8..14);
+/// ```
+#[derive(Builder)]
 pub struct Input<'h, HaystackStr = str>
 where
     HaystackStr: EncodedStr + ?Sized,
@@ -16,6 +40,104 @@ where
     /// The haystack does not include the real start of the haystack. Akin to POSIX `REG_NOTBOL` and PCRE `PCRE_NOTBOL`.
     #[builder(default = false)]
     pub(crate) no_start: bool,
+    /// See [`Input::anchored()`].
+    #[builder(default = false)]
+    pub(crate) anchored: bool,
+    /// The offset of `haystack` in the haystack given by the user, i.e. the start of [`Input::range()`].
+    #[builder(skip)]
+    pub(crate) start: usize,
+    /// The haystack given by the user and its `no_start`, which [`Input::range()`] is in.
+    #[builder(skip = (haystack, no_start))]
+    pub(crate) whole: (&'h HaystackStr, bool),
+}
+
+// `#[derive(Clone, Copy)]` would require `HaystackStr: Copy`
+impl<HaystackStr> Clone for Input<'_, HaystackStr>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<HaystackStr> Copy for Input<'_, HaystackStr> where HaystackStr: EncodedStr + ?Sized {}
+
+impl<'h, HaystackStr> Input<'h, HaystackStr>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    #[inline]
+    pub fn new(haystack: &'h HaystackStr) -> Self {
+        haystack.into()
+    }
+
+    /// Only searches `haystack[range]`, but unlike slicing the haystack:
+    /// - The match offsets are still in the whole haystack.
+    /// - If `range` doesn't start at 0, the start of the range is not treated as the start of the haystack,
+    ///   i.e. [`IbMatcherBuilder::starts_with()`](super::IbMatcherBuilder::starts_with) can only match if it starts at 0.
+    ///
+    /// `range` is in the same units as [`Match`](super::Match).
+    /// [`IbMatcherBuilder::ends_with()`](super::IbMatcherBuilder::ends_with) and [`IbMatcherBuilder::stem_only()`](super::IbMatcherBuilder::stem_only)
+    /// are relative to the end of the range.
+    ///
+    /// Like [`regex_automata::Input::range()`](https://docs.rs/regex-automata/latest/regex_automata/struct.Input.html#method.range),
+    /// `range` is always in the whole haystack, not relative to the previous range.
+    ///
+    /// ## Panics
+    /// Panics if the start of `range` is greater than its end, its end is greater than the length of the haystack,
+    /// or any of its bounds is not at a char boundary.
+    #[track_caller]
+    pub fn range(self, range: impl RangeBounds<usize>) -> Self {
+        let (whole, whole_no_start) = self.whole;
+        let len = whole.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end
+                && end <= len
+                && whole.floor_char_boundary(start) == start
+                && whole.floor_char_boundary(end) == end,
+            "invalid range {start}..{end} for haystack of length {len}"
+        );
+
+        Input {
+            haystack: unsafe { whole.get_unchecked_range(start..end) },
+            no_start: whole_no_start || start != 0,
+            start,
+            ..self
+        }
+    }
+
+    /// Only matches at the start of the range, i.e. like [`IbMatcher::test()`](super::IbMatcher::test) but for all the match methods.
+    ///
+    /// Unlike [`IbMatcherBuilder::starts_with()`](super::IbMatcherBuilder::starts_with),
+    /// the start of the range doesn't need to be the start of the haystack.
+    #[inline]
+    pub fn anchored(self, anchored: bool) -> Self {
+        Input { anchored, ..self }
+    }
+
+    /// The start of the range, see [`Input::range()`].
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The end of the range, see [`Input::range()`].
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.start + self.haystack.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE
+    }
 }
 
 impl<'h, HaystackStr> From<&'h HaystackStr> for Input<'h, HaystackStr>
@@ -28,6 +150,9 @@ where
         Input {
             haystack,
             no_start: false,
+            anchored: false,
+            start: 0,
+            whole: (haystack, false),
         }
     }
 }
@@ -41,9 +166,59 @@ impl<'h> Input<'h, str> {
     pub fn from_regex(input: &crate::regex::Input<'h>) -> Self {
         let haystack = &input.haystack()[input.get_span()];
         debug_assert!(str::from_utf8(haystack).is_ok());
+        let haystack = unsafe { core::mem::transmute(str::from_utf8_unchecked(haystack)) };
+        let no_start = input.start() != 0;
         Input {
-            haystack: unsafe { std::mem::transmute(str::from_utf8_unchecked(haystack)) },
-            no_start: input.start() != 0,
+            haystack,
+            no_start,
+            anchored: false,
+            start: 0,
+            whole: (haystack, no_start),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn range_included_max() {
+        Input::new("abc").range(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn range_excluded_max() {
+        Input::new("abc").range((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn range_not_char_boundary() {
+        Input::new("拼音").range(1..6);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn range_end_not_char_boundary() {
+        Input::new("拼音").range(..4);
+    }
+
+    #[test]
+    fn range_absolute() {
+        let input = Input::new("xxaxa").range(3..).range(1..);
+        assert_eq!((input.start(), input.end()), (1, 5));
+        assert!(input.no_start);
+        let input = Input::new("xxaxa").range(3..).range(0..2);
+        assert_eq!((input.start(), input.end()), (0, 2));
+        assert!(!input.no_start);
+        let input = Input::builder("xxaxa")
+            .no_start(true)
+            .build()
+            .range(3..)
+            .range(..);
+        assert!(input.no_start);
+    }
+}
@@ -1,6 +1,11 @@
-use std::ops::Range;
+use core::ops::Range;
 
-use crate::Sealed;
+#[cfg(feature = "pinyin")]
+use crate::pinyin::PinyinNotation;
+use crate::{
+    matcher::{encoding::EncodedStr, input::Input, IbMatcher},
+    Sealed,
+};
 
 #[derive(Clone, Debug)]
 pub struct Match {
@@ -18,10 +23,45 @@ impl Match {
         self.end
     }
 
+    /// The byte range of the match in the haystack, which can be used to slice the haystack.
+    ///
+    /// This is the same as `Range::from(m)`, but doesn't consume the match,
+    /// so [`is_pattern_partial()`](Self::is_pattern_partial) is still accessible.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::matcher::IbMatcher;
+    ///
+    /// let matcher = IbMatcher::builder("bar").build();
+    /// let haystack = "foo bar";
+    /// let m = matcher.find(haystack).unwrap();
+    /// assert_eq!(&haystack[m.range()], "bar");
+    /// assert!(!m.is_pattern_partial());
+    ///
+    /// let range: core::ops::Range<usize> = m.into();
+    /// assert_eq!(range, 4..7);
+    /// ```
     pub fn range(&self) -> Range<usize> {
         self.start..self.end
     }
 
+    /// The [`Span`](crate::regex::Span) of the match, i.e. [`range()`](Self::range)
+    /// as in [`regex_automata::Match::span()`](crate::regex::Match::span).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::matcher::IbMatcher;
+    ///
+    /// let matcher = IbMatcher::builder("bar").build();
+    /// let haystack = "foo bar";
+    /// let m = matcher.find(haystack).unwrap();
+    /// assert_eq!(&haystack[m.span()], "bar");
+    /// ```
+    #[cfg(feature = "regex-automata")]
+    pub fn span(&self) -> crate::regex::Span {
+        self.range().into()
+    }
+
     pub fn len(&self) -> usize {
         self.end - self.start
     }
@@ -55,6 +95,126 @@ impl Match {
     }
 }
 
+/// How the haystack of a match is matched by the pattern. See [`ScoredMatch`] and [`IbMatcher::stats()`].
+///
+/// The counts are saturated at [`u16::MAX`].
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct MatchStats {
+    /// Chars matched as plain chars, i.e. literally (or case-insensitively).
+    pub plain: u16,
+    /// Hanzi matched by pinyin notations other than [`PinyinNotation::AsciiFirstLetter`](crate::pinyin::PinyinNotation::AsciiFirstLetter),
+    /// including Shuangpin and Jyutping.
+    pub pinyin: u16,
+    /// Hanzi matched by [`PinyinNotation::AsciiFirstLetter`](crate::pinyin::PinyinNotation::AsciiFirstLetter).
+    pub pinyin_first_letter: u16,
+    /// Words matched by romaji. A word can be multiple kana/kanji.
+    pub romaji: u16,
+    /// Pinyin notations that matched at least one hanzi, including [`PinyinNotation::Jyutping`].
+    ///
+    /// Custom Shuangpin schemes (see [`PinyinMatchConfigBuilder::shuangpin()`](super::PinyinMatchConfigBuilder::shuangpin)) are counted in [`MatchStats::pinyin`],
+    /// but have no notation here.
+    #[cfg(feature = "pinyin")]
+    pub pinyin_notations: PinyinNotation,
+}
+
+impl MatchStats {
+    #[inline]
+    pub(crate) fn add(mut self, count: fn(&mut Self) -> &mut u16) -> Self {
+        let count = count(&mut self);
+        *count = count.saturating_add(1);
+        self
+    }
+
+    /// A hanzi matched by `notation`.
+    #[cfg(feature = "pinyin")]
+    #[inline]
+    pub(crate) fn add_pinyin(mut self, notation: PinyinNotation) -> Self {
+        self.pinyin_notations |= notation;
+        match notation {
+            PinyinNotation::AsciiFirstLetter => self.add(|s| &mut s.pinyin_first_letter),
+            _ => self.add(|s| &mut s.pinyin),
+        }
+    }
+
+    /// The coarse kind of the match. Plain chars are ignored unless nothing else is matched.
+    pub fn kind(&self) -> MatchKind {
+        let pinyin = self.pinyin != 0 || self.pinyin_first_letter != 0;
+        match (pinyin, self.romaji != 0) {
+            (false, false) => MatchKind::Plain,
+            (true, false) => MatchKind::Pinyin,
+            (false, true) => MatchKind::Romaji,
+            (true, true) => MatchKind::Mixed,
+        }
+    }
+}
+
+/// How a match is matched at a coarse level, e.g. for explaining a match in search UIs. See [`MatchStats::kind()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchKind {
+    /// Only matched as plain chars, i.e. literally (or case-insensitively).
+    Plain,
+    /// Some hanzi matched by pinyin. See [`MatchStats::pinyin_notations`] for the notations.
+    Pinyin,
+    /// Some words matched by romaji.
+    Romaji,
+    /// Both pinyin and romaji, only possible with [`IbMatcherBuilder::mix_lang()`](super::IbMatcherBuilder::mix_lang).
+    Mixed,
+}
+
+/// A [`Match`] with its [`MatchStats`] and score. Returned by [`IbMatcher::find_best()`].
+#[derive(Clone, Debug)]
+pub struct ScoredMatch {
+    pub(crate) m: Match,
+    pub(crate) stats: MatchStats,
+    pub(crate) score: i32,
+}
+
+impl ScoredMatch {
+    pub fn as_match(&self) -> &Match {
+        &self.m
+    }
+
+    pub fn into_match(self) -> Match {
+        self.m
+    }
+
+    pub fn stats(&self) -> MatchStats {
+        self.stats
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+
+    /// The default scoring of [`IbMatcher::find_best()`]:
+    ///
+    /// `plain - pinyin - romaji - 2 * pinyin_first_letter - (2 if partial)`
+    ///
+    /// That is, literal matches are preferred over pinyin/romaji, full pinyin over first letters,
+    /// and for the same kind of matching, shorter spans (i.e. fewer hanzi/words) are preferred.
+    /// For example, pattern "an" prefers "安" (an) over "安娜" (a n) in "安娜".
+    pub fn default_score(m: &Match, stats: &MatchStats) -> i32 {
+        stats.plain as i32
+            - stats.pinyin as i32
+            - stats.romaji as i32
+            - 2 * stats.pinyin_first_letter as i32
+            - if m.is_pattern_partial { 2 } else { 0 }
+    }
+}
+
+impl From<Match> for Range<usize> {
+    fn from(m: Match) -> Self {
+        m.range()
+    }
+}
+
+#[cfg(feature = "regex-automata")]
+impl From<Match> for crate::regex::Span {
+    fn from(m: Match) -> Self {
+        m.span()
+    }
+}
+
 #[cfg(feature = "regex-automata")]
 impl Into<crate::regex::Match> for Match {
     fn into(self) -> crate::regex::Match {
@@ -72,17 +232,139 @@ pub trait OptionMatchExt: Sealed + Into<Option<Match>> + Sized {
 impl Sealed for Option<Match> {}
 impl OptionMatchExt for Option<Match> {}
 
+/// An iterator over all non-overlapping matches in a haystack.
+///
+/// Created by [`IbMatcher::find_iter()`].
+pub struct FindMatches<'m, 'h, HaystackStr = str>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    pub(crate) matcher: &'m IbMatcher<'m, HaystackStr>,
+    pub(crate) haystack: &'h HaystackStr,
+    pub(crate) no_start: bool,
+    /// See [`Input::anchored()`].
+    pub(crate) anchored: bool,
+    /// See [`Input::range()`].
+    pub(crate) start: usize,
+    pub(crate) is_ascii: bool,
+    /// In units of `HaystackStr::CHAR`. `usize::MAX` if finished.
+    pub(crate) pos: usize,
+}
+
+impl<HaystackStr> Iterator for FindMatches<'_, '_, HaystackStr>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        self.next_match(false)
+    }
+}
+
+impl<HaystackStr> FindMatches<'_, '_, HaystackStr>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    /// The next match starting from `pos`.
+    ///
+    /// If `overlapping`, the next search starts from one char after the start of the match instead of its end.
+    fn next_match(&mut self, overlapping: bool) -> Option<Match> {
+        let len = self.haystack.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE;
+        if self.pos > len {
+            return None;
+        }
+
+        let no_start = self.no_start || self.pos != 0;
+        let m = if self.anchored {
+            // Can only match at the start
+            match self.pos {
+                0 => self.matcher.test_stem(Input {
+                    haystack: self.haystack,
+                    no_start,
+                    anchored: true,
+                    start: 0,
+                    whole: (self.haystack, no_start),
+                }),
+                _ => None,
+            }
+        } else if self.matcher.starts_with && no_start {
+            None
+        } else {
+            self.matcher.find_with_is_ascii(
+                Input {
+                    haystack: unsafe { self.haystack.get_unchecked_from(self.pos..) },
+                    no_start,
+                    anchored: false,
+                    start: 0,
+                    whole: (self.haystack, self.no_start),
+                },
+                // A suffix of an ASCII-only haystack is also ASCII-only
+                self.is_ascii,
+            )
+        };
+        let Some(m) = m.map(|m| m.offset(self.pos)) else {
+            self.pos = usize::MAX;
+            return None;
+        };
+
+        self.pos = if m.is_empty() || overlapping {
+            // Advance at least one char on empty matches
+            m.start
+                + unsafe { self.haystack.get_unchecked_from(m.start..) }
+                    .char_len_next_strs()
+                    .next()
+                    .map_or(1, |(_, len, _)| len)
+        } else {
+            m.end
+        };
+        Some(m.offset(self.start))
+    }
+}
+
+/// An iterator over the matches at all start positions in a haystack, including overlapping ones.
+///
+/// Created by [`IbMatcher::find_overlapping_iter()`].
+pub struct FindOverlappingMatches<'m, 'h, HaystackStr = str>(
+    pub(crate) FindMatches<'m, 'h, HaystackStr>,
+)
+where
+    HaystackStr: EncodedStr + ?Sized;
+
+impl<HaystackStr> Iterator for FindOverlappingMatches<'_, '_, HaystackStr>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        self.0.next_match(true)
+    }
+}
+
+impl<HaystackStr> core::iter::FusedIterator for FindOverlappingMatches<'_, '_, HaystackStr> where
+    HaystackStr: EncodedStr + ?Sized
+{
+}
+
+impl<HaystackStr> core::iter::FusedIterator for FindMatches<'_, '_, HaystackStr> where
+    HaystackStr: EncodedStr + ?Sized
+{
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct SubMatch {
     pub len: usize,
     pub is_pattern_partial: bool,
+    pub stats: MatchStats,
 }
 
 impl SubMatch {
-    pub fn new(len: usize, is_pattern_partial: bool) -> Self {
+    pub fn new(len: usize, is_pattern_partial: bool, stats: MatchStats) -> Self {
         Self {
             len,
             is_pattern_partial,
+            stats,
         }
     }
 }
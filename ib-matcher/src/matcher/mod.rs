@@ -55,15 +55,27 @@ assert_match!(c.matcher("shuuseipacchi").find("集成パッチ"), Some((0, 15)))
 assert_match!(c.matcher("shikanokonokonokokoshitantan").find("鹿乃子のこのこ虎視眈々"), Some((0, 33)));
 ```
 */
-use core::{fmt::Debug, marker::PhantomData, num::NonZeroU8};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+    vec,
+    vec::Vec,
+};
+use core::{fmt::Debug, iter, marker::PhantomData, mem, num::NonZeroU8, ops::Range};
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Read};
 
 use bon::{bon, Builder};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
+#[cfg(feature = "pinyin")]
+use crate::pinyin::{phrase::PhrasePinyins, FuzzyFlags, Pinyin, PinyinData, PinyinNotation};
 use crate::{
     matcher::{
         ascii::AsciiMatcher,
         encoding::EncodedStr,
-        input::Input,
         matches::SubMatch,
         pattern::{LangOnly, Pattern},
     },
@@ -75,6 +87,8 @@ pub(crate) mod config;
 pub mod encoding;
 pub mod input;
 mod matches;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
 pub mod pattern;
 #[cfg(feature = "perf-plain-regex")]
 mod regex_utils;
@@ -84,13 +98,68 @@ mod ascii;
 mod pinyin;
 #[cfg(feature = "romaji")]
 mod romaji;
+#[cfg(feature = "serde")]
+mod spec;
 
 pub use ascii::{PlainMatchConfig, PlainMatchConfigBuilder};
-pub use matches::{Match, OptionMatchExt};
+pub use input::Input;
+pub use matches::{
+    FindMatches, FindOverlappingMatches, Match, MatchKind, MatchStats, OptionMatchExt, ScoredMatch,
+};
+#[cfg(feature = "unicode-normalization")]
+pub use normalize::Normalization;
 #[cfg(feature = "pinyin")]
 pub use pinyin::*;
 #[cfg(feature = "romaji")]
 pub use romaji::*;
+#[cfg(feature = "serde")]
+pub use spec::{
+    MatchConfigSpec, MatchConfigSpecError, PinyinMatchSpec, PlainMatchSpec, RomajiMatchSpec,
+};
+
+/// See [`IbMatcher::sub_test_and_try_for_each()`].
+#[cfg(feature = "pinyin")]
+type PhraseRest<'p> = &'p [PhrasePinyins];
+#[cfg(not(feature = "pinyin"))]
+type PhraseRest<'p> = core::marker::PhantomData<&'p ()>;
+
+/// A language that haystack chars can be matched in. See [`IbMatcherBuilder::lang_priority()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum Lang {
+    /// Plain chars. See [`PlainMatchConfig`].
+    Literal,
+    /// See [`PinyinMatchConfig`].
+    Pinyin,
+    /// See [`RomajiMatchConfig`].
+    Romaji,
+}
+
+impl Lang {
+    /// The default of [`IbMatcherBuilder::lang_priority()`].
+    pub const DEFAULT_PRIORITY: &'static [Lang] = &[Lang::Literal, Lang::Romaji, Lang::Pinyin];
+}
+
+/// The case sensitivity of matching. See [`IbMatcherBuilder::case()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum Case {
+    /// Upper and lower case letters only match themselves.
+    Sensitive,
+    /// Upper and lower case letters match each other.
+    Insensitive,
+}
+
+/// What an empty pattern matches. See [`IbMatcherBuilder::empty_pattern()`].
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum EmptyBehavior {
+    /// Match every haystack with an empty match at the start, i.e. `Match` `0..0`.
+    #[default]
+    MatchAll,
+    /// Match nothing.
+    MatchNone,
+}
 
 #[derive(Builder)]
 pub struct MatchConfig<'a> {
@@ -114,10 +183,28 @@ pub struct MatchConfig<'a> {
     /// Only matches if the haystack ends with the pattern.
     #[builder(default = false)]
     ends_with: bool,
+    /// Only match the file stem of haystacks, i.e. before the last `.`, if the pattern doesn't contain `.`.
+    ///
+    /// See [`IbMatcherBuilder::stem_only()`] for details.
+    #[builder(default = false)]
+    stem_only: bool,
+    /// The minimum length of a match. See [`IbMatcherBuilder::min_match_len()`] for details.
+    #[builder(default = 0)]
+    min_match_len: usize,
+    /// The maximum length of a match. See [`IbMatcherBuilder::max_match_len()`] for details.
+    max_match_len: Option<usize>,
+    /// Return the leftmost-longest match. See [`IbMatcherBuilder::longest()`] for details.
+    #[builder(default = false)]
+    longest: bool,
+    /// What an empty pattern matches. See [`IbMatcherBuilder::empty_pattern()`] for details.
+    #[builder(default)]
+    empty_pattern: EmptyBehavior,
 
+    /// The case sensitivity of all languages, unless overridden. See [`IbMatcherBuilder::case()`] for details.
+    case: Option<Case>,
     /// `None` means not to match characters in the pattern as plain characters, i.e. match them only as pinyin/romaji, even if they are not valid pinyin/romaji characters.
     ///
-    /// Note empty pattern always match everything.
+    /// Note empty pattern always match everything, unless [`EmptyBehavior::MatchNone`] is set.
     #[builder(required, default = Some(PlainMatchConfig::builder().build()))]
     pub(crate) plain: Option<PlainMatchConfig>,
     /// Allow to match a haystack with mixed languages, i.e. pinyin and romaji, at the same time.
@@ -125,6 +212,27 @@ pub struct MatchConfig<'a> {
     /// `true` may lead to unexpected matches, especially if [`PinyinNotation::AsciiFirstLetter`] is enabled, and also lower performance.
     #[builder(default = false)]
     mix_lang: bool,
+    /// The order to try the languages of a haystack char in. See [`IbMatcherBuilder::lang_priority()`] for details.
+    #[builder(default = Lang::DEFAULT_PRIORITY)]
+    lang_priority: &'a [Lang],
+    /// Try the next languages if the rest of the pattern doesn't match. See [`IbMatcherBuilder::lang_fallback()`] for details.
+    #[builder(default = true)]
+    lang_fallback: bool,
+    /// Match full-width ASCII chars in haystacks (e.g. "２０２４") as their ASCII counterparts. See [`IbMatcherBuilder::fullwidth_ascii()`] for details.
+    #[builder(default = false)]
+    fullwidth_ascii: bool,
+    /// Restrict each match to a single line. See [`IbMatcherBuilder::line_terminator()`] for details.
+    line_terminator: Option<u8>,
+    /// Unicode normalization of the pattern and haystacks. See [`Normalization`] for details.
+    #[cfg(feature = "unicode-normalization")]
+    #[builder(default)]
+    normalize: Normalization,
+    /// Treat [extended grapheme clusters](https://www.unicode.org/reports/tr29/) as atomic units, i.e. [`Match`] boundaries never split a cluster.
+    ///
+    /// See [`IbMatcherBuilder::graphemes()`] for details.
+    #[cfg(feature = "unicode-segmentation")]
+    #[builder(default = false)]
+    graphemes: bool,
     #[cfg(feature = "pinyin")]
     pinyin: Option<PinyinMatchConfig<'a>>,
     #[cfg(feature = "romaji")]
@@ -141,6 +249,17 @@ impl Default for MatchConfig<'_> {
 }
 
 impl<'a> MatchConfig<'a> {
+    /// Whether plain chars are matched case-insensitively, with [`MatchConfigBuilder::case`] applied.
+    #[cfg(any(feature = "regex-cp", feature = "regex-lita"))]
+    pub(crate) fn is_plain_case_insensitive(&self) -> bool {
+        self.plain
+            .as_ref()
+            .is_some_and(|plain| match (plain.case_insensitive, self.case) {
+                (None, Some(case)) => case == Case::Insensitive,
+                _ => plain.is_case_insensitive(),
+            })
+    }
+
     pub fn shallow_clone(&'a self) -> Self {
         Self {
             analyze: self.analyze,
@@ -148,8 +267,22 @@ impl<'a> MatchConfig<'a> {
             is_pattern_partial: self.is_pattern_partial,
             starts_with: self.starts_with,
             ends_with: self.ends_with,
+            stem_only: self.stem_only,
+            min_match_len: self.min_match_len,
+            max_match_len: self.max_match_len,
+            longest: self.longest,
+            empty_pattern: self.empty_pattern,
+            case: self.case,
             plain: self.plain.clone(),
             mix_lang: self.mix_lang,
+            lang_priority: self.lang_priority,
+            lang_fallback: self.lang_fallback,
+            fullwidth_ascii: self.fullwidth_ascii,
+            line_terminator: self.line_terminator,
+            #[cfg(feature = "unicode-normalization")]
+            normalize: self.normalize,
+            #[cfg(feature = "unicode-segmentation")]
+            graphemes: self.graphemes,
             #[cfg(feature = "pinyin")]
             pinyin: self.pinyin.as_ref().map(|c| c.shallow_clone()),
             #[cfg(feature = "romaji")]
@@ -170,6 +303,60 @@ impl<'a> MatchConfig<'a> {
     }
 }
 
+#[cfg(all(feature = "std", any(feature = "pinyin", feature = "romaji")))]
+impl MatchConfig<'static> {
+    /// Match pinyin ([`PinyinNotation::Ascii`] and [`PinyinNotation::AsciiFirstLetter`]) and romaji with the default configs,
+    /// i.e. `MatchConfig::builder().pinyin(Default::default()).romaji(Default::default()).build()`,
+    /// but the pinyin data and the romanizer are lazily initialized once and shared by all the configs,
+    /// so repeated construction is cheap.
+    ///
+    /// Only the languages enabled by features are matched.
+    /// Use [`MatchConfig::cjk_isolated()`] if the configs shouldn't share the data.
+    ///
+    /// ## Example
+    /// ```
+    /// // cargo add ib-matcher --features pinyin,romaji
+    /// use ib_matcher::matcher::MatchConfig;
+    ///
+    /// let config = MatchConfig::cjk();
+    /// assert!(config.matcher("pysousuo").is_match("拼音搜索"));
+    /// assert!(config.matcher("konosuba").is_match("この素晴らしい世界に祝福を"));
+    /// ```
+    pub fn cjk() -> Self {
+        let builder = MatchConfig::builder();
+        #[cfg(feature = "pinyin")]
+        let builder = builder.pinyin({
+            static PINYIN_DATA: std::sync::OnceLock<PinyinData> = std::sync::OnceLock::new();
+            let notations = PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter;
+            PinyinMatchConfig::builder(notations)
+                .data(PINYIN_DATA.get_or_init(|| PinyinData::new(notations)))
+                .build()
+        });
+        #[cfg(feature = "romaji")]
+        let builder = builder.romaji({
+            static ROMANIZER: std::sync::OnceLock<crate::romaji::HepburnRomanizer> =
+                std::sync::OnceLock::new();
+            RomajiMatchConfig::builder()
+                .romanizer(ROMANIZER.get_or_init(Default::default))
+                .build()
+        });
+        builder.build()
+    }
+
+    /// [`MatchConfig::cjk()`], but with its own pinyin data and romanizer,
+    /// e.g. to modify them without affecting other configs.
+    ///
+    /// This is as slow as building the data and the romanizer from scratch.
+    pub fn cjk_isolated() -> Self {
+        let builder = MatchConfig::builder();
+        #[cfg(feature = "pinyin")]
+        let builder = builder.pinyin(Default::default());
+        #[cfg(feature = "romaji")]
+        let builder = builder.romaji(Default::default());
+        builder.build()
+    }
+}
+
 #[derive(Debug)]
 struct PatternChar<'a> {
     c: char,
@@ -178,6 +365,26 @@ struct PatternChar<'a> {
     s_lowercase: &'a str,
 }
 
+impl<'a> PatternChar<'a> {
+    /// `pattern[..len]`, with `s` and `s_lowercase` also truncated.
+    ///
+    /// `s` and `s_lowercase` are the rest of the whole pattern,
+    /// so slicing the pattern alone does not drop the chars after `len`.
+    fn truncate(pattern: &[Self], len: usize) -> Vec<Self> {
+        let (s_end, s_lowercase_end) = pattern
+            .get(len)
+            .map_or((0, 0), |c| (c.s.len(), c.s_lowercase.len()));
+        pattern[..len]
+            .iter()
+            .map(|c| PatternChar {
+                s: &c.s[..c.s.len() - s_end],
+                s_lowercase: &c.s_lowercase[..c.s_lowercase.len() - s_lowercase_end],
+                ..*c
+            })
+            .collect()
+    }
+}
+
 /**
 ## Example
 ```
@@ -208,6 +415,32 @@ assert!(matcher.is_match("『この素晴らしい世界に祝福を』"));
 // Matching is unanchored by default, you can set `b.starts_with(true)` for anchored one.
 ```
 */
+/// ## Mixed text
+/// Plain chars and pinyin/romaji can be mixed in the same match.
+/// For example, [`PinyinNotation::AsciiFirstLetter`] (简拼) "py" can be directly followed by plain "search" to match "拼音search" as a whole:
+/// ```
+/// use ib_matcher::{
+///     matcher::{IbMatcher, PinyinMatchConfig},
+///     pinyin::PinyinNotation,
+/// };
+///
+/// let matcher = IbMatcher::builder("pysearch")
+///     .pinyin(PinyinMatchConfig::notations(
+///         PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+///     ))
+///     .build();
+/// assert_eq!(matcher.find("拼音search").unwrap().range(), 0..12);
+/// assert_eq!(matcher.find("x拼音searchx").unwrap().range(), 1..13);
+/// // The plain chars must be adjacent to the hanzi
+/// assert!(!matcher.is_match("拼音 search"));
+///
+/// let matcher = IbMatcher::builder("searchpy")
+///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiFirstLetter))
+///     .build();
+/// assert_eq!(matcher.find("search拼音").unwrap().range(), 0..12);
+/// ```
+/// But pinyin and romaji can only be mixed with [`IbMatcherBuilder::mix_lang()`].
+///
 /// ## Design
 /// API follows [`regex::Regex`](https://docs.rs/regex/latest/regex/struct.Regex.html).
 ///
@@ -215,12 +448,10 @@ assert!(matcher.is_match("『この素晴らしい世界に祝福を』"));
 /// - If you need to build [`IbMatcher`] multiple times, pass [`PinyinMatchConfigBuilder::data`] to the builder or use [`PinyinMatchConfig::shallow_clone()`] to avoid re-initializing the pinyin data every time. Same for [`RomajiMatchConfig`].
 /// - For matching more than 1000 strings, enable [`IbMatcherBuilder::analyze`] to optimize the pattern further. (The analysis costs ~65us, equivalent to about 220~1100 matches.)
 /// - If you only need to call [`IbMatcher::test`] (or [`IbMatcher::test_and_try_for_each`]), set `starts_with` to improve performance.
+/// - For matching a large number of haystacks on multiple threads, see [`IbMatcher::par_filter()`] (feature `rayon`).
 ///
 /// TODO: No-pinyin pattern optimization
-/// TODO: Anchors, `*_at`
-/// TODO: Unicode normalization
 /// TODO: No-hanzi haystack optimization (0.2/0.9%)
-/// TODO: If pattern doesn't contain `.`, only match before `.` in the haystack
 pub struct IbMatcher<'a, HaystackStr = str>
 where
     HaystackStr: EncodedStr + ?Sized,
@@ -235,12 +466,27 @@ where
     _pattern_string: String,
     _pattern_string_lowercase: String,
 
-    min_haystack_len: usize,
+    analysis: analyze::PatternAnalysis,
     starts_with: bool,
     ends_with: bool,
+    /// `false` if the pattern contains `.`.
+    stem_only: bool,
+    min_match_len: usize,
+    /// `usize::MAX` if unbounded.
+    max_match_len: usize,
+    longest: bool,
+    empty_pattern: EmptyBehavior,
 
     plain: Option<PlainMatchConfig>,
     mix_lang: bool,
+    lang_priority: &'a [Lang],
+    lang_fallback: bool,
+    fullwidth_ascii: bool,
+    line_terminator: Option<u8>,
+    #[cfg(feature = "unicode-normalization")]
+    normalize: Normalization,
+    #[cfg(feature = "unicode-segmentation")]
+    graphemes: bool,
     #[cfg(feature = "pinyin")]
     pinyin: Option<PinyinMatcher<'a>>,
     #[cfg(feature = "romaji")]
@@ -250,17 +496,46 @@ where
 }
 
 impl Debug for IbMatcher<'_, str> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("IbMatcher")
             .field("pattern", &self._pattern_string)
             .field("starts_with", &self.starts_with)
             .field("ends_with", &self.ends_with)
+            .field("stem_only", &self.stem_only)
             .field("plain", &self.plain)
             .field("mix_lang", &self.mix_lang)
+            .field("lang_priority", &self.lang_priority)
+            .field("lang_fallback", &self.lang_fallback)
+            .field("fullwidth_ascii", &self.fullwidth_ascii)
+            .field("line_terminator", &self.line_terminator)
             .finish()
     }
 }
 
+#[cfg(all(feature = "std", any(feature = "pinyin", feature = "romaji")))]
+impl<HaystackStr> IbMatcher<'static, HaystackStr>
+where
+    HaystackStr: EncodedStr + ?Sized,
+{
+    /// Match `pattern` as pinyin and romaji with the default configs,
+    /// sharing the pinyin data and the romanizer across calls. See [`MatchConfig::cjk()`] for details.
+    ///
+    /// ## Example
+    /// ```
+    /// // cargo add ib-matcher --features pinyin,romaji
+    /// use ib_matcher::matcher::IbMatcher;
+    ///
+    /// assert!(IbMatcher::cjk("pysousuoeve").is_match("拼音搜索Everything"));
+    /// assert!(IbMatcher::cjk("konosuba").is_match("『この素晴らしい世界に祝福を』"));
+    /// ```
+    pub fn cjk<'p>(pattern: impl Into<Pattern<'p, HaystackStr>>) -> Self
+    where
+        HaystackStr: 'p,
+    {
+        Self::with_config(pattern, MatchConfig::cjk())
+    }
+}
+
 #[bon]
 impl<'a, HaystackStr> IbMatcher<'a, HaystackStr>
 where
@@ -280,8 +555,22 @@ where
             .is_pattern_partial(config.is_pattern_partial)
             .starts_with(config.starts_with)
             .ends_with(config.ends_with)
+            .stem_only(config.stem_only)
+            .min_match_len(config.min_match_len)
+            .maybe_max_match_len(config.max_match_len)
+            .longest(config.longest)
+            .empty_pattern(config.empty_pattern)
+            .maybe_case(config.case)
             .plain(config.plain)
-            .mix_lang(config.mix_lang);
+            .mix_lang(config.mix_lang)
+            .lang_priority(config.lang_priority)
+            .lang_fallback(config.lang_fallback)
+            .fullwidth_ascii(config.fullwidth_ascii)
+            .maybe_line_terminator(config.line_terminator);
+        #[cfg(feature = "unicode-normalization")]
+        let builder = builder.normalize(config.normalize);
+        #[cfg(feature = "unicode-segmentation")]
+        let builder = builder.graphemes(config.graphemes);
         #[cfg(feature = "pinyin")]
         let builder = builder.maybe_pinyin(config.pinyin);
         #[cfg(feature = "romaji")]
@@ -311,10 +600,167 @@ where
         /// Only matches if the haystack ends with the pattern.
         #[builder(default = false)]
         ends_with: bool,
+        /// If the pattern doesn't contain `.`, only match the file stem of haystacks, i.e. the part before the last `.`.
+        ///
+        /// For file name matching, the pattern is usually meant to match the stem instead of the extension,
+        /// e.g. pattern "pdf" shouldn't match "报告.pdf". This also skips the extension when searching.
+        ///
+        /// The stem is determined like [`std::path::Path::file_stem()`]:
+        /// - Only the `.` after the last path separator (`/` or `\`) counts,
+        ///   i.e. dots in directory names (e.g. `v1.0/readme`) are ignored and the whole haystack is matched.
+        /// - A leading `.` of the file name (e.g. `.gitignore`) doesn't start an extension.
+        /// - Only the last extension is excluded, e.g. the stem of `a.tar.gz` is `a.tar`.
+        ///
+        /// [`IbMatcherBuilder::ends_with()`] then means ending with the stem.
+        /// Match offsets are still in the whole haystack.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::{
+        ///     matcher::{IbMatcher, PinyinMatchConfig},
+        ///     pinyin::PinyinNotation,
+        /// };
+        ///
+        /// let matcher = IbMatcher::builder("bg")
+        ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiFirstLetter))
+        ///     .stem_only(true)
+        ///     .ends_with(true)
+        ///     .build();
+        /// assert!(matcher.is_match("报告.pdf"));
+        /// assert!(matcher.is_match("v1.0/报告"));
+        /// assert!(!matcher.is_match("文档.bg"));
+        ///
+        /// // The pattern contains `.`
+        /// let matcher = IbMatcher::builder("g.p").stem_only(true).build();
+        /// assert!(matcher.is_match("bg.pdf"));
+        /// ```
+        #[builder(default = false)]
+        stem_only: bool,
+        /// Only matches if the match is at least this long, in the same units as [`Match`] (i.e. bytes for UTF-8).
+        ///
+        /// The length is checked on each match candidate during matching, so a too-short candidate can still be replaced by a longer one,
+        /// e.g. from another pinyin notation or reading. It is not the same as filtering the results of [`IbMatcher::find()`].
+        ///
+        /// Partial matches (see [`IbMatcherBuilder::is_pattern_partial()`]) are bounded in the same way,
+        /// by the length of the haystack they cover, including the whole hanzi/kana whose pinyin/romaji is only partially matched.
+        ///
+        /// Not applied to empty patterns, which always match with length 0.
+        #[builder(default = 0)]
+        min_match_len: usize,
+        /// Only matches if the match is at most this long, in the same units as [`Match`] (i.e. bytes for UTF-8).
+        ///
+        /// For example, a short pattern of pinyin first letters can match a long run of hanzi. `max_match_len(4 * 3)` limits `"pyss"` to at most 4 (BMP) hanzi.
+        ///
+        /// Match candidates are pruned as soon as they exceed the length, which is also faster than filtering the results.
+        /// See [`IbMatcherBuilder::min_match_len()`] for the interaction with partial matches.
+        ///
+        /// With [`IbMatcherBuilder::normalize()`], the lengths are of the normalized haystack.
+        max_match_len: Option<usize>,
+        /// Make [`IbMatcher::find()`] (and [`IbMatcher::find_iter()`], [`IbMatcher::find_at()`]) return the leftmost-longest match,
+        /// and [`IbMatcher::test()`] the longest match.
+        ///
+        /// By default, the first match found is returned, which prefers longer matches but is not guaranteed to be the longest one,
+        /// and may change across versions as the search order changes.
+        /// With this enabled, all the match candidates at the leftmost start are explored and the longest one is kept,
+        /// so the result is deterministic. If multiple candidates have the same length, a non-partial one is preferred, then the first one found.
+        ///
+        /// ## Performance
+        /// Instead of stopping at the first candidate, every candidate is explored,
+        /// whose number can be exponential in the pattern length in the worst case,
+        /// e.g. when every pattern char can match as either a pinyin letter or a first letter.
+        /// ASCII-only haystacks are not affected, since they have only one candidate.
+        ///
+        /// [`IbMatcher::is_match()`] and [`IbMatcher::test_and_try_for_each()`] are not affected either.
+        /// To score the candidates in other ways, see [`IbMatcher::find_best_by()`].
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::{
+        ///     matcher::{IbMatcher, PinyinMatchConfig},
+        ///     pinyin::PinyinNotation,
+        /// };
+        ///
+        /// let matcher = IbMatcher::builder("an")
+        ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+        ///     .longest(true)
+        ///     .build();
+        /// // "a n" is longer than "an"
+        /// assert_eq!(matcher.find("安娜").unwrap().range(), 0..6);
+        /// assert_eq!(matcher.test("安娜").unwrap().range(), 0..6);
+        /// ```
+        #[builder(default = false)]
+        longest: bool,
+        /// What an empty pattern matches, e.g. a search box may want an empty query to match nothing instead of everything.
+        ///
+        /// Defaults to [`EmptyBehavior::MatchAll`], i.e. [`IbMatcher::find()`] and [`IbMatcher::test()`] return an empty match at the start of any haystack,
+        /// and [`IbMatcher::is_match()`] returns `true`.
+        /// With [`EmptyBehavior::MatchNone`], they return `None` and `false` instead.
+        ///
+        /// Non-empty patterns are not affected.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::matcher::{EmptyBehavior, IbMatcher};
+        ///
+        /// let matcher = IbMatcher::builder("").build();
+        /// assert_eq!(matcher.find("abc").unwrap().range(), 0..0);
+        /// assert!(matcher.is_match(""));
+        ///
+        /// let matcher = IbMatcher::builder("")
+        ///     .empty_pattern(EmptyBehavior::MatchNone)
+        ///     .build();
+        /// assert!(matcher.find("abc").is_none());
+        /// assert!(matcher.test("abc").is_none());
+        /// assert!(!matcher.is_match(""));
+        /// ```
+        #[builder(default)]
+        empty_pattern: EmptyBehavior,
+
+        /// The case sensitivity of all languages, i.e. whether upper and lower case letters can match each other.
+        ///
+        /// Each language can override it in its own config, and has its own default if neither is set:
+        ///
+        /// | Language | Override | Default |
+        /// | -------- | -------- | ------- |
+        /// | Literal  | [`PlainMatchConfigBuilder::case_insensitive`]  | [`Case::Insensitive`] |
+        /// | Pinyin   | [`PinyinMatchConfigBuilder::case_insensitive`] | [`Case::Sensitive`] |
+        /// | Romaji   | [`RomajiMatchConfigBuilder::case_insensitive`] | [`Case::Sensitive`] |
+        ///
+        /// Pinyins and romajis are lower case, so a case-sensitive language only matches the lower case letters in the pattern,
+        /// e.g. pattern "XING" can match "xing" but not "行" by default.
+        /// [`IbMatcherBuilder::case_insensitive()`] is a shortcut for the literal override only.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::{
+        ///     matcher::{Case, IbMatcher, PinyinMatchConfig, RomajiMatchConfig},
+        ///     pinyin::PinyinNotation,
+        /// };
+        ///
+        /// let romanizer = Default::default();
+        /// let builder = |pattern| {
+        ///     IbMatcher::builder(pattern)
+        ///         .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+        ///         .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+        /// };
+        /// assert!(!builder("XING").build().is_match("行"));
+        /// assert!(builder("XING").case(Case::Insensitive).build().is_match("行"));
+        /// assert!(builder("KONO").case(Case::Insensitive).build().is_match("この"));
+        ///
+        /// // Only literals are case-sensitive
+        /// let matcher = builder("XiNG")
+        ///     .case(Case::Insensitive)
+        ///     .case_insensitive(false)
+        ///     .build();
+        /// assert!(matcher.is_match("行"));
+        /// assert!(matcher.is_match("XiNG"));
+        /// assert!(!matcher.is_match("xing"));
+        /// ```
+        case: Option<Case>,
 
         /// `None` means not to match characters in the pattern as plain characters, i.e. match them only as pinyin/romaji, even if they are not valid pinyin/romaji characters.
         ///
-        /// Note empty pattern always match everything.
+        /// Note empty pattern always match everything, unless [`EmptyBehavior::MatchNone`] is set.
         #[builder(required, default = Some(PlainMatchConfig::builder().build()))]
         mut plain: Option<PlainMatchConfig>,
         /// Allow to match a haystack with mixed languages, i.e. pinyin and romaji, at the same time.
@@ -322,6 +768,123 @@ where
         /// `true` may lead to unexpected matches, especially if [`PinyinNotation::AsciiFirstLetter`] is enabled, and also lower performance.
         #[builder(default = false)]
         mix_lang: bool,
+        /// The order to try the languages of each haystack char in, which decides the match found first.
+        ///
+        /// Defaults to [`Lang::DEFAULT_PRIORITY`], i.e. literal, romaji, then pinyin.
+        /// Languages that are not in the list are not matched, even if they are enabled.
+        ///
+        /// A literal match is always kept without trying the other languages,
+        /// since a char equal to the pattern char can't be matched by pinyin or romaji at the same time.
+        /// Also see [`IbMatcherBuilder::lang_fallback()`].
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::{
+        ///     matcher::{IbMatcher, Lang, PinyinMatchConfig, RomajiMatchConfig},
+        ///     pinyin::PinyinNotation,
+        /// };
+        ///
+        /// let romanizer = Default::default();
+        /// let builder = |lang_priority| {
+        ///     IbMatcher::builder("hatsuneodxyy")
+        ///         .pinyin(PinyinMatchConfig::notations(
+        ///             PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+        ///         ))
+        ///         .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+        ///         .mix_lang(true)
+        ///         .is_pattern_partial(true)
+        ///         .lang_priority(lang_priority)
+        ///         .build()
+        /// };
+        /// // "y" is matched as a partial romaji of "羊" (you) first
+        /// let m = builder(Lang::DEFAULT_PRIORITY).find("初音殴打喜羊羊").unwrap();
+        /// assert!(m.is_pattern_partial());
+        ///
+        /// let m = builder(&[Lang::Literal, Lang::Pinyin, Lang::Romaji]).find("初音殴打喜羊羊").unwrap();
+        /// assert!(!m.is_pattern_partial());
+        /// ```
+        #[builder(default = Lang::DEFAULT_PRIORITY)]
+        lang_priority: &'a [Lang],
+        /// If a haystack char is matched in a language but the rest of the pattern doesn't match,
+        /// try the next languages in [`IbMatcherBuilder::lang_priority()`].
+        ///
+        /// `false` stops at the first language that matches the char,
+        /// which is faster but may miss some matches. A literal match is never fallen back from.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::{
+        ///     matcher::{IbMatcher, PinyinMatchConfig, RomajiMatchConfig},
+        ///     pinyin::PinyinNotation,
+        /// };
+        ///
+        /// let romanizer = Default::default();
+        /// let builder = || {
+        ///     IbMatcher::builder("shilai")
+        ///         .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+        ///         .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+        /// };
+        /// // "史" is "shi" in both romaji and pinyin, but "萊" is only "lai" in pinyin
+        /// assert!(builder().build().is_match("史萊"));
+        /// assert!(!builder().lang_fallback(false).build().is_match("史萊"));
+        /// ```
+        #[builder(default = true)]
+        lang_fallback: bool,
+        /// Match full-width ASCII chars (U+FF01..=U+FF5E) in haystacks as their ASCII counterparts,
+        /// e.g. "2024" matches "２０２４" and "abc" matches "ＡＢＣ" (if [`PlainMatchConfigBuilder::case_insensitive`] is `true`).
+        ///
+        /// Only literal chars are affected, and the pattern itself is not converted,
+        /// i.e. "２０" still matches "２０" but not "20".
+        /// [`Match`] ranges are still in the original haystack.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::matcher::IbMatcher;
+        ///
+        /// assert!(!IbMatcher::builder("2024").build().is_match("２０２４年"));
+        ///
+        /// let matcher = IbMatcher::builder("2024").fullwidth_ascii(true).build();
+        /// assert_eq!(matcher.find("令和６年（２０２４年）").unwrap().range(), 15..27);
+        /// assert!(matcher.is_match("2024"));
+        /// ```
+        #[builder(default = false)]
+        fullwidth_ascii: bool,
+        /// Restrict each match to a single line, i.e. never match across the char of this byte (e.g. `b'\n'`) in haystacks.
+        ///
+        /// Defaults to `None`, i.e. matches can span multiple lines.
+        ///
+        /// `b'\n'` also works for CRLF (`\r\n`) line endings, since `\r` can only be matched by a literal `\r` in the pattern.
+        /// Pinyin and romaji never match ASCII chars, so in practice this only affects patterns containing the terminator itself,
+        /// which then never match, including in the ASCII-only haystack fast path.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_matcher::matcher::IbMatcher;
+        ///
+        /// let matcher = IbMatcher::builder("foo\nbar").build();
+        /// assert!(matcher.is_match("foo\nbar"));
+        ///
+        /// let matcher = IbMatcher::builder("foo\nbar").line_terminator(b'\n').build();
+        /// assert!(!matcher.is_match("foo\nbar"));
+        ///
+        /// let matcher = IbMatcher::builder("bar").line_terminator(b'\n').build();
+        /// assert_eq!(matcher.find("foo\nbar").unwrap().range(), 4..7);
+        /// ```
+        line_terminator: Option<u8>,
+        /// Unicode normalization of the pattern and haystacks. See [`Normalization`] for details.
+        #[cfg(feature = "unicode-normalization")]
+        #[builder(default)]
+        mut normalize: Normalization,
+        /// Treat [extended grapheme clusters](https://www.unicode.org/reports/tr29/) as atomic units when advancing the haystack,
+        /// i.e. [`Match`] boundaries (and [`IbMatcher::highlights()`]) never split a cluster,
+        /// like an emoji ZWJ sequence (e.g. "👨‍👩‍👧") or a letter with combining marks (e.g. "e\u{301}").
+        ///
+        /// For example, pattern "e" doesn't match "e\u{301}" (é) if this is enabled.
+        ///
+        /// Only UTF-8 is supported at the moment. See [`EncodedStr::is_grapheme_boundary()`].
+        #[cfg(feature = "unicode-segmentation")]
+        #[builder(default = false)]
+        graphemes: bool,
         #[cfg(feature = "pinyin")] mut pinyin: Option<PinyinMatchConfig<'a>>,
         #[cfg(feature = "romaji")] mut romaji: Option<RomajiMatchConfig<'a>>,
     ) -> Self {
@@ -338,18 +901,60 @@ where
                 romaji = None;
             }
         }
+        if !lang_priority.contains(&Lang::Literal) {
+            plain = None;
+        }
+        if let Some(case) = case {
+            let case_insensitive = case == Case::Insensitive;
+            if let Some(plain) = &mut plain {
+                plain.case_insensitive.get_or_insert(case_insensitive);
+            }
+            #[cfg(feature = "pinyin")]
+            if let Some(pinyin) = &mut pinyin {
+                pinyin.case_insensitive.get_or_insert(case_insensitive);
+            }
+            #[cfg(feature = "romaji")]
+            if let Some(romaji) = &mut romaji {
+                romaji.case_insensitive.get_or_insert(case_insensitive);
+            }
+        }
+        #[cfg(feature = "pinyin")]
+        if !lang_priority.contains(&Lang::Pinyin) {
+            pinyin = None;
+        }
+        #[cfg(feature = "romaji")]
+        if !lang_priority.contains(&Lang::Romaji) {
+            romaji = None;
+        }
 
         let pattern = pattern.pattern;
         let pattern_bytes = pattern.as_bytes().to_owned();
         let pattern: String = pattern.char_index_strs().map(|(_, c, _)| c).collect();
 
+        #[cfg(feature = "unicode-normalization")]
+        let (pattern, pattern_bytes) = {
+            if !HaystackStr::UTF8 {
+                normalize = Normalization::None;
+            }
+            match normalize.normalize(&pattern) {
+                Some(normalized) => {
+                    let pattern_bytes = normalized.s.as_bytes().to_owned();
+                    (normalized.s, pattern_bytes)
+                }
+                None => (pattern, pattern_bytes),
+            }
+        };
+
+        let stem_only = stem_only && !pattern.contains('.');
+
         let pattern_string = pattern;
         let pattern_s: &str = pattern_string.as_str();
-        let pattern_s: &'static str = unsafe { std::mem::transmute(pattern_s) };
+        let pattern_s: &'static str = unsafe { core::mem::transmute(pattern_s) };
 
         let pattern_string_lowercase = pattern_string.to_simple_or_ascii_fold_case();
         let pattern_s_lowercase: &str = pattern_string_lowercase.as_str();
-        let pattern_s_lowercase: &'static str = unsafe { std::mem::transmute(pattern_s_lowercase) };
+        let pattern_s_lowercase: &'static str =
+            unsafe { core::mem::transmute(pattern_s_lowercase) };
 
         let pattern = pattern_string
             .char_indices()
@@ -398,6 +1003,18 @@ where
                 len
             }
         };
+        let min_haystack_len = match pattern.is_empty() {
+            true => min_haystack_len,
+            false => min_haystack_len.max(min_match_len * HaystackStr::ELEMENT_LEN_BYTE),
+        };
+        let max_match_len = max_match_len.unwrap_or(usize::MAX);
+
+        let analysis = analyze::PatternAnalysis {
+            min_haystack_len,
+            plain_only: analyzer.is_plain_only(),
+            #[cfg(feature = "pinyin")]
+            used_notations: analyzer.pinyin().used_notations,
+        };
 
         #[cfg(feature = "pinyin")]
         let pinyin_analyze = analyzer.pinyin().clone();
@@ -417,14 +1034,27 @@ where
             .maybe_plain(plain.as_ref())
             .starts_with(starts_with)
             .ends_with(ends_with)
+            // Matches in ASCII-only haystacks are always as long as the pattern
+            .fail(
+                !pattern_bytes.is_empty()
+                    && (!(min_match_len..=max_match_len)
+                        .contains(&(pattern_bytes.len() / HaystackStr::ELEMENT_LEN_BYTE))
+                        || line_terminator
+                            .is_some_and(|t| pattern.iter().any(|c| c.c == t as char))),
+            )
             .build();
 
         Self {
             ascii,
 
-            min_haystack_len,
+            analysis,
             starts_with,
             ends_with,
+            stem_only,
+            min_match_len,
+            max_match_len,
+            longest,
+            empty_pattern,
 
             pattern,
             _pattern_string: pattern_string,
@@ -433,6 +1063,16 @@ where
             plain,
 
             mix_lang,
+            lang_priority,
+            lang_fallback,
+            fullwidth_ascii,
+            line_terminator,
+
+            #[cfg(feature = "unicode-normalization")]
+            normalize,
+
+            #[cfg(feature = "unicode-segmentation")]
+            graphemes,
 
             #[cfg(feature = "pinyin")]
             pinyin,
@@ -451,8 +1091,16 @@ where
     where
         HaystackStr: 'h,
     {
-        let input = input.into();
+        let input = self.stem_input(input.into());
+        let start = input.start;
+        self.find_stem(input).map(|m| m.offset(start))
+    }
 
+    /// [`IbMatcher::find()`] with [`IbMatcher::stem_input()`] already applied.
+    fn find_stem(&self, input: Input<HaystackStr>) -> Option<Match> {
+        if input.anchored {
+            return self.test_stem(input);
+        }
         if self.starts_with && input.no_start {
             return None;
         }
@@ -461,23 +1109,255 @@ where
         self.find_with_is_ascii(input, is_ascii)
     }
 
+    /// [`IbMatcher::find()`], but starts searching at `at` in `haystack`, e.g. for resuming a search.
+    ///
+    /// `at` is in the same units as [`Match`].
+    /// If `at` is not at a char boundary, it is rounded down to one.
+    /// If `at` is greater than the length of `haystack`, it is clamped to the length.
+    ///
+    /// The haystack before `at` is not searched, but `at` is not treated as the start of the haystack,
+    /// i.e. [`IbMatcherBuilder::starts_with()`] can only match if `at` is 0.
+    ///
+    /// ## Returns
+    /// The match offsets are in `haystack`, not relative to `at`.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// let haystack = "拼音, 拼音";
+    /// assert_eq!(matcher.find_at(haystack, 0).unwrap().range(), 0..6);
+    /// assert_eq!(matcher.find_at(haystack, 6).unwrap().range(), 8..14);
+    /// ```
+    pub fn find_at(&'a self, haystack: &HaystackStr, at: usize) -> Option<Match> {
+        let (input, at) = self.stem_input_at(haystack, at);
+        self.find_stem(input).map(|m| m.offset(at))
+    }
+
+    /// The input of `haystack[at..]`, with `at` rounded down to a char boundary.
+    ///
+    /// See [`IbMatcher::find_at()`].
+    fn stem_input_at<'h>(
+        &self,
+        haystack: &'h HaystackStr,
+        at: usize,
+    ) -> (Input<'h, HaystackStr>, usize) {
+        // The stem of the whole haystack, not of the suffix
+        let haystack = self.stem_input(haystack.into()).haystack;
+        let at = haystack.floor_char_boundary(at);
+        (
+            Input {
+                haystack: unsafe { haystack.get_unchecked_from(at..) },
+                no_start: at != 0,
+                anchored: false,
+                start: 0,
+                whole: (haystack, false),
+            },
+            at,
+        )
+    }
+
+    /// Truncate the haystack of `input` to its file stem if [`IbMatcherBuilder::stem_only()`] is applied.
+    ///
+    /// Only called by the public match methods, since internal calls may be on a suffix of the haystack.
+    #[inline]
+    fn stem_input<'h>(&self, input: Input<'h, HaystackStr>) -> Input<'h, HaystackStr> {
+        if !self.stem_only {
+            return input;
+        }
+        let Input {
+            haystack, no_start, ..
+        } = input;
+
+        // The last `.`, `/` or `\`, and whether the char before it is a separator
+        let mut last = None;
+        if HaystackStr::UTF8 {
+            last = Self::utf8_last_path_char(haystack.as_bytes(), no_start);
+        } else {
+            let mut after_sep = !no_start;
+            for (i, c, _) in haystack.char_index_strs() {
+                if matches!(c, '.' | '/' | '\\') {
+                    last = Some((i, c as u8, after_sep));
+                }
+                after_sep = matches!(c, '/' | '\\');
+            }
+        }
+
+        match last {
+            Some((i, b'.', false)) => Input {
+                haystack: unsafe { haystack.get_unchecked_range(0..i) },
+                ..input
+            },
+            _ => input,
+        }
+    }
+
+    /// The last `.`, `/` or `\` in UTF-8 `bytes`, and whether the char before it is a separator. See [`IbMatcher::stem_input()`].
+    ///
+    /// Invalid UTF-8 is allowed, since they are never in multi-byte chars.
+    fn utf8_last_path_char(bytes: &[u8], no_start: bool) -> Option<(usize, u8, bool)> {
+        let i = bytes
+            .iter()
+            .rposition(|&b| matches!(b, b'.' | b'/' | b'\\'))?;
+        let after_sep = match i {
+            0 => !no_start,
+            i => matches!(bytes[i - 1], b'/' | b'\\'),
+        };
+        Some((i, bytes[i], after_sep))
+    }
+
+    /// ASCII-only haystacks can only be matched as plain text, since pinyin and romaji only match non-ASCII chars.
+    /// So they never match if [`AsciiMatcher`] fails, e.g. the pattern is not ASCII, plain matching is disabled or the match length is out of range.
+    ///
+    /// Shared by all the match methods to reject such haystacks before any other checks.
+    #[inline(always)]
+    fn rejects_ascii_haystack(&self, is_ascii: bool) -> bool {
+        // Empty patterns are left to `IbMatcher::empty_match()`
+        is_ascii && self.ascii.is_fail() && !self.pattern.is_empty()
+    }
+
+    /// The match of an empty pattern at the start of the haystack, or `None` if [`EmptyBehavior::MatchNone`].
+    #[inline]
+    fn empty_match(&self) -> Option<Match> {
+        match self.empty_pattern {
+            EmptyBehavior::MatchAll => Some(Match {
+                start: 0,
+                end: 0,
+                is_pattern_partial: false,
+            }),
+            EmptyBehavior::MatchNone => None,
+        }
+    }
+
     fn find_with_is_ascii<'h>(
         &self,
         input: Input<'h, HaystackStr>,
         is_ascii: bool,
     ) -> Option<Match> {
+        if self.rejects_ascii_haystack(is_ascii) {
+            return None;
+        }
+
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalized) = self.normalize_haystack(input.haystack, is_ascii) {
+            let haystack = unsafe { normalized.as_haystack::<HaystackStr>() };
+            return self
+                .find_normalized(Input { haystack, ..input }, haystack.is_ascii())
+                .map(|m| normalized.map(m));
+        }
+        self.find_normalized(input, is_ascii)
+    }
+
+    /// Normalize `haystack` if [`IbMatcherBuilder::normalize()`] is set and it is not normalized yet.
+    #[cfg(feature = "unicode-normalization")]
+    fn normalize_haystack(
+        &self,
+        haystack: &HaystackStr,
+        is_ascii: bool,
+    ) -> Option<normalize::NormalizedStr> {
+        // `normalize` is always `None` if not HaystackStr::UTF8
+        if self.normalize == Normalization::None || is_ascii {
+            return None;
+        }
+        self.normalize
+            .normalize(unsafe { core::str::from_utf8_unchecked(haystack.as_bytes()) })
+    }
+
+    #[inline]
+    fn graphemes(&self) -> bool {
+        #[cfg(feature = "unicode-segmentation")]
+        return self.graphemes;
+        #[cfg(not(feature = "unicode-segmentation"))]
+        false
+    }
+
+    /// Whether `index` can be a match boundary in `haystack` under [`IbMatcherBuilder::graphemes()`].
+    #[inline]
+    fn is_boundary(&self, haystack: &HaystackStr, index: usize) -> bool {
+        #[cfg(feature = "unicode-segmentation")]
+        return !self.graphemes || haystack.is_grapheme_boundary(index);
+        #[cfg(not(feature = "unicode-segmentation"))]
+        {
+            let _ = (haystack, index);
+            true
+        }
+    }
+
+    /// Whether [`IbMatcherBuilder::graphemes()`] needs to be checked for `haystack`.
+    #[inline]
+    fn check_graphemes(&self, haystack: &HaystackStr, is_ascii: bool) -> bool {
+        // CRLF is the only multi-char grapheme cluster in ASCII
+        self.graphemes()
+            && (!is_ascii
+                || HaystackStr::UTF8 && haystack.as_bytes().windows(2).any(|w| w == b"\r\n"))
+    }
+
+    /// [`IbMatcher::sub_test()`] with the whole pattern, respecting [`IbMatcherBuilder::graphemes()`] and [`IbMatcherBuilder::min_match_len()`].
+    fn sub_test_whole(&self, haystack: &HaystackStr) -> Option<SubMatch> {
+        if self.longest {
+            let mut longest: Option<SubMatch> = None;
+            self.sub_test_and_try_for_each::<0xFF, ()>(
+                &self.pattern,
+                haystack,
+                0,
+                MatchStats::default(),
+                None,
+                Default::default(),
+                &mut |submatch| {
+                    if submatch.len >= self.min_match_len
+                        && self.is_boundary(haystack, submatch.len)
+                        && longest.as_ref().map_or(true, |longest| {
+                            Self::is_longer(
+                                (submatch.len, submatch.is_pattern_partial),
+                                (longest.len, longest.is_pattern_partial),
+                            )
+                        })
+                    {
+                        longest = Some(submatch);
+                    }
+                    None
+                },
+            );
+            return longest;
+        }
+        if self.graphemes() || self.min_match_len != 0 {
+            return self.sub_test_and_try_for_each::<0xFF, SubMatch>(
+                &self.pattern,
+                haystack,
+                0,
+                MatchStats::default(),
+                None,
+                Default::default(),
+                &mut |submatch| {
+                    (submatch.len >= self.min_match_len && self.is_boundary(haystack, submatch.len))
+                        .then_some(submatch)
+                },
+            );
+        }
+        self.sub_test::<0xFF>(&self.pattern, haystack, 0)
+    }
+
+    /// For [`IbMatcherBuilder::longest()`], whether `(len, is_pattern_partial)` of a candidate is preferred over another one found before.
+    #[inline]
+    fn is_longer(candidate: (usize, bool), than: (usize, bool)) -> bool {
+        candidate.0 > than.0 || candidate.0 == than.0 && than.1 && !candidate.1
+    }
+
+    fn find_normalized<'h>(&self, input: Input<'h, HaystackStr>, is_ascii: bool) -> Option<Match> {
         debug_assert!(!(self.starts_with && input.no_start));
 
         if self.pattern.is_empty() {
-            return Some(Match {
-                start: 0,
-                end: 0,
-                is_pattern_partial: false,
-            });
+            return self.empty_match();
         }
 
         let haystack = input.haystack;
-        if is_ascii {
+        if is_ascii && !self.check_graphemes(haystack, is_ascii) {
             return self.ascii.find(haystack.as_bytes()).div(HaystackStr::CHAR);
         }
 
@@ -487,13 +1367,11 @@ where
                 return None;
             }
             if self.starts_with {
-                return self
-                    .sub_test::<0xFF>(&self.pattern, haystack, 0)
-                    .map(|submatch| Match {
-                        start: 0,
-                        end: submatch.len,
-                        is_pattern_partial: submatch.is_pattern_partial,
-                    });
+                return self.sub_test_whole(haystack).map(|submatch| Match {
+                    start: 0,
+                    end: submatch.len,
+                    is_pattern_partial: submatch.is_pattern_partial,
+                });
             }
 
             // ASCII prefilter, -30% for matcher find_ascii_25
@@ -508,12 +1386,14 @@ where
                 if self.is_haystack_too_short(str) {
                     break;
                 }
-                if let Some(submatch) = self.sub_test::<0xFF>(&self.pattern, str, 0) {
-                    return Some(Match {
-                        start: i,
-                        end: i + submatch.len,
-                        is_pattern_partial: submatch.is_pattern_partial,
-                    });
+                if self.is_boundary(haystack, i) {
+                    if let Some(submatch) = self.sub_test_whole(str) {
+                        return Some(Match {
+                            start: i,
+                            end: i + submatch.len,
+                            is_pattern_partial: submatch.is_pattern_partial,
+                        });
+                    }
                 }
 
                 let s = unsafe { str::from_utf8_unchecked(&haystack.as_bytes()[i..]) };
@@ -524,7 +1404,7 @@ where
                 if self.is_haystack_too_short(str) {
                     break;
                 }
-                if let Some(submatch) = self.sub_test::<0xFF>(&self.pattern, str, 0) {
+                if let Some(submatch) = self.sub_test_whole(str) {
                     return Some(Match {
                         start: i,
                         end: i + submatch.len,
@@ -540,44 +1420,682 @@ where
         None
     }
 
-    /// Returns true if and only if there is a match for the pattern anywhere in the haystack given.
+    /// This routine searches for the last match of this pattern in the haystack given, and if found, returns a [`Match`], e.g. for jumping to the last occurrence.
     ///
-    /// It is recommended to use this method if all you need to do is test whether a match exists, since the underlying matching engine may be able to do less work.
-    pub fn is_match<'h>(&self, input: impl Into<Input<'h, HaystackStr>>) -> bool
+    /// "Last" means the match with the greatest start offset. If there are multiple matches at that start,
+    /// the one preferred by [`IbMatcher::find()`] is returned, e.g. the longest one with [`IbMatcherBuilder::longest()`].
+    ///
+    /// This is not always the last match of [`IbMatcher::find_iter()`], since its matches are non-overlapping:
+    /// a match that overlaps the previous one is skipped by [`IbMatcher::find_iter()`], but can still be the last match here.
+    /// An empty pattern matches at the end of the haystack.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// assert_eq!(matcher.rfind("拼音, pinyin, 拼音").unwrap().range(), 16..22);
+    ///
+    /// // Overlapping matches
+    /// let matcher = IbMatcher::builder("aa").build();
+    /// assert_eq!(matcher.rfind("aaa").unwrap().range(), 1..3);
+    /// assert_eq!(matcher.find_iter("aaa").last().unwrap().range(), 0..2);
+    /// ```
+    pub fn rfind<'h>(&self, input: impl Into<Input<'h, HaystackStr>>) -> Option<Match>
     where
         HaystackStr: 'h,
     {
-        let input = input.into();
-
+        let input = self.stem_input(input.into());
+        let start = input.start;
+        if input.anchored {
+            return self.test_stem(input).map(|m| m.offset(start));
+        }
         if self.starts_with && input.no_start {
-            return false;
+            return None;
         }
 
-        let haystack = input.haystack;
-        if haystack.is_ascii() {
-            return self.ascii.is_match(haystack.as_bytes());
+        let is_ascii = input.haystack.is_ascii();
+        if self.rejects_ascii_haystack(is_ascii) {
+            return None;
         }
 
-        self.find_with_is_ascii(input, false).is_some()
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalized) = self.normalize_haystack(input.haystack, is_ascii) {
+            let haystack = unsafe { normalized.as_haystack::<HaystackStr>() };
+            return self
+                .rfind_normalized(Input { haystack, ..input }, haystack.is_ascii())
+                .map(|m| normalized.map(m).offset(start));
+        }
+        self.rfind_normalized(input, is_ascii)
+            .map(|m| m.offset(start))
     }
 
-    /// This routine tests if this pattern matches the haystack at the start, and if found, returns a [`Match`]. The [`Match`] provides access to both the byte offsets of the match and [`Match::is_pattern_partial()`].
-    ///
-    /// ## Returns
-    /// - `Match.start()` is guaranteed to be 0.
-    /// - If there are multiple possible matches, the longer ones are preferred. But the result is not guaranteed to be the longest one.
+    fn rfind_normalized(&self, input: Input<HaystackStr>, is_ascii: bool) -> Option<Match> {
+        let haystack = input.haystack;
+        let len = haystack.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE;
+        if self.pattern.is_empty() {
+            return self.empty_match().map(|m| m.offset(len));
+        }
+        if self.starts_with {
+            // Can only match at the start
+            return self.find_normalized(input, is_ascii);
+        }
+
+        // All matches in ASCII-only haystacks are plain matches
+        let ascii = is_ascii && !self.check_graphemes(haystack, is_ascii);
+        // Scan the char boundaries backwards
+        let mut i = len;
+        while i != 0 {
+            i = haystack.floor_char_boundary(i - 1);
+
+            let str = unsafe { haystack.get_unchecked_from(i..) };
+            if ascii {
+                if let Some(m) = self.ascii.test(str.as_bytes()).div(HaystackStr::CHAR) {
+                    return Some(m.offset(i));
+                }
+                continue;
+            }
+            if self.is_haystack_too_short(str) || !self.is_boundary(haystack, i) {
+                continue;
+            }
+            if let Some(submatch) = self.sub_test_whole(str) {
+                return Some(Match {
+                    start: i,
+                    end: i + submatch.len,
+                    is_pattern_partial: submatch.is_pattern_partial,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Returns an iterator over all non-overlapping matches in the haystack given.
+    ///
+    /// Each search starts from the end of the previous match.
+    /// Empty matches (only possible with an empty pattern) advance the search by one char, like [`regex`](https://docs.rs/regex/).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pysousuo")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// let matches: Vec<_> = matcher
+    ///     .find_iter("拼音搜索, pysousuo, 拼音搜索")
+    ///     .map(|m| m.range())
+    ///     .collect();
+    /// assert_eq!(matches, [0..12, 14..22, 24..36]);
+    /// ```
+    pub fn find_iter<'m, 'h>(
+        &'m self,
+        input: impl Into<Input<'h, HaystackStr>>,
+    ) -> FindMatches<'m, 'h, HaystackStr>
+    where
+        'a: 'm,
+    {
+        let input = self.stem_input(input.into());
+        FindMatches {
+            matcher: self,
+            haystack: input.haystack,
+            no_start: input.no_start,
+            anchored: input.anchored,
+            start: input.start,
+            is_ascii: input.haystack.is_ascii(),
+            pos: 0,
+        }
+    }
+
+    /// Returns an iterator over the matches at all start positions in the haystack given, including overlapping ones,
+    /// e.g. for dense highlighting or counting potential anchors.
+    ///
+    /// At each start position, the match is the one [`IbMatcher::find()`] would return, i.e. each search starts from one char after the start of the previous match.
+    /// For example, pattern "aa" matches "aaa" at `0..2` and `1..3`.
+    /// An empty pattern matches at every char boundary (including the end), the same as [`IbMatcher::find_iter()`].
+    ///
+    /// ## Performance
+    /// Every char can be the start of a search, so this is O(n·m) in the worst case for non-ASCII haystacks,
+    /// where n is the length of the haystack and m is the length of the pattern.
+    /// Use the non-overlapping [`IbMatcher::find_iter()`] if possible.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("xx")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// let matches: Vec<_> = matcher
+    ///     .find_overlapping_iter("星星相惜")
+    ///     .map(|m| m.range())
+    ///     .collect();
+    /// assert_eq!(matches, [0..6, 3..9, 6..12]);
+    /// assert_eq!(matcher.find_iter("星星相惜").count(), 2);
+    /// ```
+    pub fn find_overlapping_iter<'m, 'h>(
+        &'m self,
+        input: impl Into<Input<'h, HaystackStr>>,
+    ) -> FindOverlappingMatches<'m, 'h, HaystackStr>
+    where
+        'a: 'm,
+    {
+        FindOverlappingMatches(self.find_iter(input))
+    }
+
+    /// Returns the number of non-overlapping matches in the haystack given.
+    ///
+    /// The matches are the same as [`IbMatcher::find_iter()`]'s, i.e. each search starts from the end of the previous match,
+    /// and an empty pattern matches at every char boundary (including the end).
+    /// But for ASCII-only haystacks, the matches are counted without constructing [`Match`] values, which is faster than `find_iter().count()`.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("xing")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// assert_eq!(matcher.count("xingxing"), 2);
+    /// assert_eq!(matcher.count("行不行, xing"), 3);
+    ///
+    /// let matcher = IbMatcher::builder("aa").build();
+    /// assert_eq!(matcher.count("aaaaa"), 2);
+    /// ```
+    pub fn count<'h>(&self, input: impl Into<Input<'h, HaystackStr>>) -> usize
+    where
+        HaystackStr: 'h,
+    {
+        let input = self.stem_input(input.into());
+        let haystack = input.haystack;
+        let is_ascii = haystack.is_ascii();
+        if self.rejects_ascii_haystack(is_ascii) {
+            return 0;
+        }
+
+        // Anchored matchers can match at most once, leave them to `FindMatches`
+        if is_ascii
+            && !self.starts_with
+            && !input.anchored
+            && !self.pattern.is_empty()
+            && !self.check_graphemes(haystack, is_ascii)
+        {
+            if let Some(count) = self.ascii.count(haystack.as_bytes()) {
+                return count;
+            }
+        }
+
+        FindMatches {
+            matcher: self,
+            haystack,
+            no_start: input.no_start,
+            anchored: input.anchored,
+            start: input.start,
+            is_ascii,
+            pos: 0,
+        }
+        .count()
+    }
+
+    /// [`IbMatcher::find()`], but returns the best of all the possible matches at the leftmost start,
+    /// scored by [`ScoredMatch::default_score()`].
+    ///
+    /// [`IbMatcher::find()`] returns the first match found, which is not guaranteed to be the longest or the best one.
+    /// This method enumerates all the candidates at the leftmost start instead, so it is slower.
+    /// If multiple candidates have the same score, the longer one is returned, then the first one found.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("an")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// // "an" instead of "a n"
+    /// let m = matcher.find_best("安娜").unwrap();
+    /// assert_eq!(m.as_match().range(), 0..3);
+    /// assert_eq!(m.stats().pinyin, 1);
+    ///
+    /// // Prefer longer matches
+    /// let m = matcher.find_best_by("安娜", |m, _| m.len() as i32).unwrap();
+    /// assert_eq!(m.as_match().range(), 0..6);
+    /// assert_eq!(m.stats().pinyin_first_letter, 2);
+    /// ```
+    pub fn find_best<'h>(&self, input: impl Into<Input<'h, HaystackStr>>) -> Option<ScoredMatch>
+    where
+        HaystackStr: 'h,
+    {
+        self.find_best_by(input, ScoredMatch::default_score)
+    }
+
+    /// [`IbMatcher::find_best()`] with a custom scoring function. Higher scores are better.
+    pub fn find_best_by<'h>(
+        &self,
+        input: impl Into<Input<'h, HaystackStr>>,
+        mut score: impl FnMut(&Match, &MatchStats) -> i32,
+    ) -> Option<ScoredMatch>
+    where
+        HaystackStr: 'h,
+    {
+        let input = self.stem_input(input.into());
+        if self.starts_with && input.no_start {
+            return None;
+        }
+
+        let haystack = input.haystack;
+        let start = match input.anchored {
+            true => 0,
+            false => self.find_with_is_ascii(input, haystack.is_ascii())?.start,
+        };
+
+        let mut best: Option<ScoredMatch> = None;
+        self.test_and_try_for_each_stats::<false, ()>(
+            Input {
+                haystack: unsafe { haystack.get_unchecked_from(start..) },
+                no_start: input.no_start || start != 0,
+                ..input
+            },
+            &mut |m, stats| {
+                let m = m.offset(input.start + start);
+                let score = score(&m, &stats);
+                if best
+                    .as_ref()
+                    .map_or(true, |best| (score, m.len()) > (best.score, best.m.len()))
+                {
+                    best = Some(ScoredMatch { m, stats, score });
+                }
+                None
+            },
+        );
+        best
+    }
+
+    /// [`IbMatcher::find()`] with [`IbMatcher::highlights()`] of the match.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// let (m, highlights) = matcher.find_highlights("拼音搜索").unwrap();
+    /// assert_eq!(m.range(), 0..6);
+    /// assert_eq!(highlights, [0..3, 0..3, 0..3, 3..6, 3..6, 3..6]);
+    /// ```
+    pub fn find_highlights<'h>(
+        &'a self,
+        input: impl Into<Input<'h, HaystackStr>>,
+    ) -> Option<(Match, Vec<Range<usize>>)>
+    where
+        HaystackStr: 'h,
+    {
+        let input = input.into();
+        let (haystack, start) = (input.haystack, input.start);
+        self.find(Input { start: 0, ..input }).map(|m| {
+            let mut highlights = self.highlights(haystack, &m);
+            for range in &mut highlights {
+                *range = range.start + start..range.end + start;
+            }
+            (m.offset(start), highlights)
+        })
+    }
+
+    /// Returns the haystack range consumed by each pattern char of the match `m`, e.g. for highlighting the matched portion.
+    ///
+    /// `m` must be a match in `haystack` returned by this matcher, e.g. by [`IbMatcher::find()`] or [`IbMatcher::find_iter()`].
+    ///
+    /// ## Returns
+    /// A range for each pattern char, in the same units as [`Match`].
+    /// - If multiple pattern chars match a haystack char (e.g. "pin" matches "拼"), they share the same range.
+    /// - If some pattern chars can only match multiple haystack chars as a whole (e.g. romaji "kyou" matches the word "今日"), they all get the range of the whole.
+    ///
+    /// ## Performance
+    /// The matched haystack is re-tested char by char, which is much slower than [`IbMatcher::find()`].
+    /// Only call it on the matches to be displayed.
+    pub fn highlights(&self, haystack: &HaystackStr, m: &Match) -> Vec<Range<usize>> {
+        #[cfg(feature = "unicode-normalization")]
+        {
+            let matched = unsafe { haystack.get_unchecked_range(m.start..m.end) };
+            if let Some(normalized) = self.normalize_haystack(matched, matched.is_ascii()) {
+                let m_normalized = Match {
+                    start: 0,
+                    end: normalized.s.len(),
+                    is_pattern_partial: m.is_pattern_partial,
+                };
+                return self
+                    .highlights_normalized(unsafe { normalized.as_haystack() }, &m_normalized)
+                    .into_iter()
+                    .map(|range| {
+                        let range = normalized.map_range(range);
+                        m.start + range.start..m.start + range.end
+                    })
+                    .collect();
+            }
+        }
+        self.highlights_normalized(haystack, m)
+    }
+
+    /// Returns how the match `m` is matched, e.g. for explaining "matched by pinyin" in search UIs.
+    ///
+    /// `m` must be a match in `haystack` returned by this matcher, e.g. by [`IbMatcher::find()`] or [`IbMatcher::find_iter()`].
+    /// If the matched haystack can be matched in multiple ways, the stats of the first one found are returned.
+    ///
+    /// ## Performance
+    /// The matched haystack is re-tested, so the match methods are not slowed down by this.
+    /// Only call it on the matches to be displayed.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, MatchKind, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pysousuo")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// let haystack = "拼音搜索";
+    /// let m = matcher.find(haystack).unwrap();
+    /// let stats = matcher.stats(haystack, &m);
+    /// assert_eq!(stats.kind(), MatchKind::Pinyin);
+    /// assert_eq!(stats.pinyin_first_letter, 2);
+    /// assert_eq!(stats.pinyin, 2);
+    /// assert_eq!(
+    ///     stats.pinyin_notations,
+    ///     PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter
+    /// );
+    /// ```
+    pub fn stats(&self, haystack: &HaystackStr, m: &Match) -> MatchStats {
+        let matched = unsafe { haystack.get_unchecked_range(m.start..m.end) };
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalized) = self.normalize_haystack(matched, matched.is_ascii()) {
+            return self.stats_normalized(unsafe { normalized.as_haystack() }, m);
+        }
+        self.stats_normalized(matched, m)
+    }
+
+    fn stats_normalized(&self, matched: &HaystackStr, m: &Match) -> MatchStats {
+        if self.pattern.is_empty() {
+            return MatchStats::default();
+        }
+        let len = matched.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE;
+        self.sub_test_and_try_for_each::<0xFF, MatchStats>(
+            &self.pattern,
+            matched,
+            0,
+            MatchStats::default(),
+            None,
+            Default::default(),
+            &mut |sub| {
+                (sub.len == len && sub.is_pattern_partial == m.is_pattern_partial)
+                    .then_some(sub.stats)
+            },
+        )
+        .unwrap_or_default()
+    }
+
+    fn highlights_normalized(&self, haystack: &HaystackStr, m: &Match) -> Vec<Range<usize>> {
+        let pattern_len = self.pattern.len();
+        let mut highlights = Vec::with_capacity(pattern_len);
+        if pattern_len == 0 {
+            return highlights;
+        }
+
+        let haystack = unsafe { haystack.get_unchecked_range(m.start..m.end) };
+        let is_exact_match = |pattern: &[PatternChar], haystack: &HaystackStr, partial: bool| {
+            let len = haystack.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE;
+            self.sub_test_and_try_for_each::<0xFF, ()>(
+                pattern,
+                haystack,
+                0,
+                MatchStats::default(),
+                None,
+                Default::default(),
+                &mut |sub| (sub.len == len && (partial || !sub.is_pattern_partial)).then_some(()),
+            )
+            .is_some()
+        };
+
+        // The current segment is haystack[start..] and pattern[i..]
+        let mut start = 0;
+        let mut i = 0;
+        for (end, _c, rest) in haystack.char_index_strs().skip(1) {
+            if !self.is_boundary(haystack, end) {
+                continue;
+            }
+            let segment = unsafe { haystack.get_unchecked_range(start..end) };
+            // Split the segment if both sides can match
+            if let Some(j) = (i + 1..pattern_len).find(|&j| {
+                is_exact_match(
+                    &PatternChar::truncate(&self.pattern[i..], j - i),
+                    segment,
+                    false,
+                ) && is_exact_match(&self.pattern[j..], rest, true)
+            }) {
+                highlights.extend(iter::repeat(m.start + start..m.start + end).take(j - i));
+                start = end;
+                i = j;
+            }
+        }
+        highlights.extend(iter::repeat(m.start + start..m.end).take(pattern_len - i));
+        highlights
+    }
+
+    /// Returns true if and only if there is a match for the pattern anywhere in the haystack given.
+    ///
+    /// It is recommended to use this method if all you need to do is test whether a match exists, since the underlying matching engine may be able to do less work.
+    pub fn is_match<'h>(&self, input: impl Into<Input<'h, HaystackStr>>) -> bool
+    where
+        HaystackStr: 'h,
+    {
+        let input = self.stem_input(input.into());
+        if input.anchored {
+            return self
+                .test_and_try_for_each_opt::<false, ()>(input, &mut |_| Some(()))
+                .is_some();
+        }
+
+        if self.starts_with && input.no_start {
+            return false;
+        }
+
+        let haystack = input.haystack;
+        let is_ascii = haystack.is_ascii();
+        if self.rejects_ascii_haystack(is_ascii) {
+            return false;
+        }
+        if is_ascii {
+            // `AsciiMatcher` fails on empty pattern if plain matching is disabled
+            return match self.pattern.is_empty() {
+                true => self.empty_pattern == EmptyBehavior::MatchAll,
+                false => self.ascii.is_match(haystack.as_bytes()),
+            };
+        }
+
+        self.find_with_is_ascii(input, false).is_some()
+    }
+
+    /// Whether this pattern matches `haystack` at `at`, i.e. [`IbMatcher::test_at()`] without the [`Match`].
+    ///
+    /// `at` is handled the same as [`IbMatcher::find_at()`].
+    pub fn is_match_at(&self, haystack: &HaystackStr, at: usize) -> bool {
+        let (input, _) = self.stem_input_at(haystack, at);
+        self.test_and_try_for_each_opt::<false, ()>(input, &mut |_| Some(()))
+            .is_some()
+    }
+
+    /// Whether this pattern may match some haystack starting with `haystack` at the start,
+    /// e.g. for pruning the subtrees of a trie or a sorted name index during indexed search.
+    ///
+    /// Returns `true` if either:
+    /// - The pattern matches at the start of `haystack`, i.e. [`IbMatcher::test()`] returns a match.
+    ///   Every haystack starting with `haystack` then matches too,
+    ///   unless [`IbMatcherBuilder::ends_with()`] or [`IbMatcherBuilder::stem_only()`] is set.
+    /// - The whole `haystack` is matched by a strict prefix of the pattern,
+    ///   i.e. the rest of the pattern may match the chars following `haystack`.
+    ///   For example, `"拼"` and `"拼音s"` are matched by the prefixes `"pin"` and `"pinyins"` of pattern `"pinyinsousuo"`.
+    ///   An empty `haystack` is always matched by the empty prefix.
+    ///
+    /// So `false` means no haystack starting with `haystack` can be matched at the start, and the subtree can be pruned.
+    /// But `true` in the second case doesn't guarantee that any of them matches, since the rest of the pattern can still fail.
+    ///
+    /// In the second case, [`IbMatcherBuilder::ends_with()`], [`IbMatcherBuilder::stem_only()`] and [`IbMatcherBuilder::min_match_len()`] are not applied,
+    /// since they depend on the chars following `haystack`. [`IbMatcherBuilder::max_match_len()`] still is.
+    /// An empty pattern is never a strict prefix, so it only depends on [`IbMatcherBuilder::empty_pattern()`].
+    ///
+    /// ## Performance
+    /// The second case tests every strict prefix of the pattern against `haystack`, so it is only meant for short haystacks like trie keys.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pinyinsousuo")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// // Matched
+    /// assert!(matcher.is_match_prefix("拼音搜索"));
+    /// assert!(matcher.is_match_prefix("拼音搜索引擎"));
+    /// // Matched by a strict prefix of the pattern
+    /// assert!(matcher.is_match_prefix(""));
+    /// assert!(matcher.is_match_prefix("拼"));
+    /// assert!(matcher.is_match_prefix("拼音s"));
+    /// // Can be pruned
+    /// assert!(!matcher.is_match_prefix("拼写"));
+    /// assert!(!matcher.is_match_prefix("音"));
+    /// ```
+    pub fn is_match_prefix(&self, haystack: &HaystackStr) -> bool {
+        if self.test(haystack).is_some() {
+            return true;
+        }
+        if self.pattern.is_empty() {
+            return false;
+        }
+
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalized) = self.normalize_haystack(haystack, haystack.is_ascii()) {
+            return self.is_matched_by_strict_prefix(unsafe { normalized.as_haystack() });
+        }
+        self.is_matched_by_strict_prefix(haystack)
+    }
+
+    /// Whether the whole `haystack` is matched by a strict prefix of the pattern. See [`IbMatcher::is_match_prefix()`].
+    fn is_matched_by_strict_prefix(&self, haystack: &HaystackStr) -> bool {
+        let len = haystack.as_bytes().len() / HaystackStr::ELEMENT_LEN_BYTE;
+        if len == 0 {
+            return true;
+        }
+        (1..self.pattern.len()).any(|prefix_len| {
+            self.sub_test_and_try_for_each::<0xFF, ()>(
+                &PatternChar::truncate(&self.pattern, prefix_len),
+                haystack,
+                0,
+                MatchStats::default(),
+                None,
+                Default::default(),
+                &mut |sub| (sub.len == len).then_some(()),
+            )
+            .is_some()
+        })
+    }
+
+    /// This routine tests if this pattern matches the haystack at the start, and if found, returns a [`Match`]. The [`Match`] provides access to both the byte offsets of the match and [`Match::is_pattern_partial()`].
+    ///
+    /// ## Returns
+    /// - `Match.start()` is guaranteed to be 0, or [`Input::start()`] if [`Input::range()`] is set.
+    /// - If there are multiple possible matches, the longer ones are preferred. But the result is not guaranteed to be the longest one,
+    ///   unless [`IbMatcherBuilder::longest()`] is enabled.
     pub fn test<'h>(&self, input: impl Into<Input<'h, HaystackStr>>) -> Option<Match>
     where
         HaystackStr: 'h,
     {
-        self.test_and_try_for_each(input, &mut Some)
+        let input = self.stem_input(input.into());
+        let start = input.start;
+        self.test_stem(input).map(|m| m.offset(start))
+    }
+
+    /// [`IbMatcher::test()`], but tests if this pattern matches `haystack` at `at` instead of the start,
+    /// e.g. for verifying the candidate positions found by another scanner without searching.
+    ///
+    /// `at` is handled the same as [`IbMatcher::find_at()`], i.e. rounded down to a char boundary and clamped to the length,
+    /// and [`IbMatcherBuilder::starts_with()`] can only match if `at` is 0.
+    ///
+    /// ## Returns
+    /// The match offsets are in `haystack`, i.e. `Match.start()` is `at` after rounding.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// let haystack = "拼音, pinyin";
+    /// assert_eq!(matcher.test_at(haystack, 0).unwrap().range(), 0..6);
+    /// assert_eq!(matcher.test_at(haystack, 8).unwrap().range(), 8..14);
+    /// assert!(matcher.test_at(haystack, 3).is_none());
+    /// // Rounded down to the start of "拼"
+    /// assert_eq!(matcher.test_at(haystack, 1).unwrap().range(), 0..6);
+    /// assert!(matcher.is_match_at(haystack, 8));
+    /// assert!(!matcher.is_match_at(haystack, 9));
+    /// ```
+    pub fn test_at(&self, haystack: &HaystackStr, at: usize) -> Option<Match> {
+        let (input, at) = self.stem_input_at(haystack, at);
+        self.test_stem(input).map(|m| m.offset(at))
+    }
+
+    /// [`IbMatcher::test()`] with [`IbMatcher::stem_input()`] already applied.
+    fn test_stem(&self, input: Input<HaystackStr>) -> Option<Match> {
+        if self.longest {
+            let mut longest: Option<Match> = None;
+            self.test_and_try_for_each_opt::<false, ()>(input, &mut |m| {
+                if longest.as_ref().map_or(true, |longest| {
+                    Self::is_longer(
+                        (m.len(), m.is_pattern_partial),
+                        (longest.len(), longest.is_pattern_partial),
+                    )
+                }) {
+                    longest = Some(m);
+                }
+                None::<()>
+            });
+            return longest;
+        }
+        self.test_and_try_for_each_opt::<false, Match>(input, &mut Some)
     }
 
     /// This routine tests if this pattern matches the haystack at the start, and if found, calls `f`, and returns a [`T`] if it returns `Some`.
     ///
     /// ## Arguments
     /// - `f`: The [`Match`] provides access to both the byte offsets of the match and [`Match::is_pattern_partial()`].
-    ///   - `Match.start()` is guaranteed to be 0.
+    ///   - `Match.start()` is guaranteed to be 0, or [`Input::start()`] if [`Input::range()`] is set.
     pub fn test_and_try_for_each<'h, T>(
         &self,
         input: impl Into<Input<'h, HaystackStr>>,
@@ -586,7 +2104,9 @@ where
     where
         HaystackStr: 'h,
     {
-        self.test_and_try_for_each_opt::<false, T>(input, f)
+        let input = self.stem_input(input.into());
+        let start = input.start;
+        self.test_and_try_for_each_opt::<false, T>(input, &mut |m| f(m.offset(start)))
     }
 
     pub(crate) fn test_and_try_for_each_opt<'h, const CONF_MAYBE_ASCII: bool, T>(
@@ -597,18 +2117,41 @@ where
     where
         HaystackStr: 'h,
     {
-        let input = input.into();
+        self.test_and_try_for_each_stats::<CONF_MAYBE_ASCII, T>(input.into(), &mut |m, _| f(m))
+    }
+
+    /// [`IbMatcher::test_and_try_for_each_opt()`] with [`MatchStats`].
+    fn test_and_try_for_each_stats<'h, const CONF_MAYBE_ASCII: bool, T>(
+        &self,
+        input: Input<'h, HaystackStr>,
+        f: &mut impl FnMut(Match, MatchStats) -> Option<T>,
+    ) -> Option<T> {
+        // Before the length check, since normalization can change the length
+        #[cfg(feature = "unicode-normalization")]
+        if let Some(normalized) = self.normalize_haystack(input.haystack, input.haystack.is_ascii())
+        {
+            return self.test_and_try_for_each_normalized::<CONF_MAYBE_ASCII, T>(
+                Input {
+                    haystack: unsafe { normalized.as_haystack() },
+                    ..input
+                },
+                &mut |m, stats| f(normalized.map(m), stats),
+            );
+        }
+        self.test_and_try_for_each_normalized::<CONF_MAYBE_ASCII, T>(input, f)
+    }
+
+    fn test_and_try_for_each_normalized<'h, const CONF_MAYBE_ASCII: bool, T>(
+        &self,
+        input: Input<'h, HaystackStr>,
+        f: &mut impl FnMut(Match, MatchStats) -> Option<T>,
+    ) -> Option<T> {
         let haystack = input.haystack;
         if self.is_haystack_too_short(haystack) || self.starts_with && input.no_start {
             return None;
         } else {
             if self.pattern.is_empty() {
-                return Some(Match {
-                    start: 0,
-                    end: 0,
-                    is_pattern_partial: false,
-                })
-                .and_then(f);
+                return self.empty_match().and_then(|m| f(m, MatchStats::default()));
             }
         }
 
@@ -629,28 +2172,71 @@ where
             || CONF_MAYBE_ASCII && self.plain.as_ref().is_some_and(|p| p.maybe_ascii))
             && haystack.is_ascii()
         {
-            return self
-                .ascii
-                .test(haystack.as_bytes())
-                .div(HaystackStr::CHAR)
-                .and_then(f);
+            if self.rejects_ascii_haystack(true) {
+                return None;
+            }
+            if !self.check_graphemes(haystack, true) {
+                // All chars are matched as plain chars
+                let stats = MatchStats {
+                    plain: self.pattern.len().try_into().unwrap_or(u16::MAX),
+                    ..Default::default()
+                };
+                return self
+                    .ascii
+                    .test(haystack.as_bytes())
+                    .div(HaystackStr::CHAR)
+                    .and_then(|m| f(m, stats));
+            }
         }
 
         self.sub_test_and_try_for_each::<0xFF, T>(
             &self.pattern,
             haystack,
             0,
+            MatchStats::default(),
             None,
+            Default::default(),
             &mut |submatch| {
-                f(Match {
-                    start: 0,
-                    end: submatch.len,
-                    is_pattern_partial: submatch.is_pattern_partial,
-                })
+                if submatch.len < self.min_match_len || !self.is_boundary(haystack, submatch.len) {
+                    return None;
+                }
+                f(
+                    Match {
+                        start: 0,
+                        end: submatch.len,
+                        is_pattern_partial: submatch.is_pattern_partial,
+                    },
+                    submatch.stats,
+                )
             },
         )
     }
 
+    /// Whether `c` may have readings in the enabled languages. Chars that don't are never matched by pinyin or romaji.
+    ///
+    /// Must agree with the data, otherwise chars with readings would be silently skipped.
+    #[cfg(any(feature = "pinyin", feature = "romaji"))]
+    #[inline(always)]
+    fn may_have_readings<const LANG: u8>(&self, c: char) -> bool {
+        #[cfg(feature = "romaji")]
+        if const { LANG & 2 != 0 } && self.romaji.is_some() {
+            // Kanas and punctuation are also romanized
+            return !c.is_ascii();
+        }
+        // Jyutpings are in the same ranges
+        #[cfg(feature = "pinyin")]
+        let may_cover = PinyinData::may_cover(c)
+            || self.pinyin.as_ref().is_some_and(|pinyin| {
+                pinyin
+                    .config
+                    .readings
+                    .is_some_and(|readings| readings.get(c).is_some())
+            });
+        #[cfg(not(feature = "pinyin"))]
+        let may_cover = false;
+        may_cover
+    }
+
     fn sub_test<const LANG: u8>(
         &self,
         pattern: &[PatternChar],
@@ -661,7 +2247,9 @@ where
             pattern,
             haystack,
             matched_len,
+            MatchStats::default(),
             None,
+            Default::default(),
             &mut Some,
         )
     }
@@ -671,14 +2259,19 @@ where
     /// - `pattern`: Not empty.
     /// - `haystack`
     /// - `matched_len`: For tail-call optimization.
+    /// - `stats`: Of the matched part.
+    /// - `_phrase`: The pinyins of the rest of the matched pinyin phrase, starting from the first char of `haystack`.
     /// - `f`
     ///   - TODO: Use coroutine when stable
+    #[allow(clippy::too_many_arguments)]
     fn sub_test_and_try_for_each<const LANG: u8, T>(
         &self,
         pattern: &[PatternChar],
         haystack: &HaystackStr,
         matched_len: usize,
+        stats: MatchStats,
         _last_romaji_c: Option<NonZeroU8>,
+        _phrase: PhraseRest,
         f: &mut impl FnMut(SubMatch) -> Option<T>,
     ) -> Option<T> {
         debug_assert!(!pattern.is_empty());
@@ -699,204 +2292,498 @@ where
             }
         };
         let matched_len_next = matched_len + haystack_c_len;
+        if matched_len_next > self.max_match_len
+            || self
+                .line_terminator
+                .is_some_and(|t| haystack_c == t as char)
+        {
+            return None;
+        }
 
         let (pattern_c, pattern_next) = pattern.split_first().unwrap();
 
-        if let Some(plain) = &self.plain {
-            if match plain.case_insensitive {
-                true => haystack_c.to_simple_or_ascii_fold_case() == pattern_c.c_lowercase,
-                false => haystack_c == pattern_c.c,
-            } {
-                // If haystack_c == pattern_c, then it is impossible that pattern_c is a pinyin letter and haystack_c is a hanzi.
-                return if pattern_next.is_empty() {
-                    Some(SubMatch::new(matched_len_next, false))
-                        .filter(|_| !self.ends_with || haystack_next.as_bytes().is_empty())
-                        .and_then(f)
-                } else {
-                    self.sub_test_and_try_for_each::<0xFF, T>(
-                        pattern_next,
-                        haystack_next,
-                        matched_len_next,
-                        None,
-                        f,
-                    )
-                };
+        for &lang in self.lang_priority {
+            let (lang_matched, submatch) = match lang {
+                Lang::Literal => self.sub_test_literal::<T>(
+                    pattern_c,
+                    pattern_next,
+                    haystack_c,
+                    haystack_next,
+                    matched_len_next,
+                    stats,
+                    f,
+                ),
+                // Fast fail optimization
+                #[cfg(feature = "romaji")]
+                Lang::Romaji if const { LANG & 2 != 0 } => match &self.romaji {
+                    Some(romaji) if self.may_have_readings::<LANG>(haystack_c) => self
+                        .sub_test_romaji::<T>(
+                            romaji,
+                            pattern,
+                            haystack,
+                            matched_len,
+                            stats,
+                            _last_romaji_c,
+                            f,
+                        ),
+                    _ => continue,
+                },
+                #[cfg(feature = "pinyin")]
+                Lang::Pinyin if const { LANG & 1 != 0 } => match &self.pinyin {
+                    Some(matcher) if self.may_have_readings::<LANG>(haystack_c) => self
+                        .sub_test_pinyins::<T>(
+                            matcher,
+                            pattern,
+                            haystack_c,
+                            haystack_next,
+                            matched_len_next,
+                            stats,
+                            _phrase,
+                            f,
+                        ),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            // If haystack_c == pattern_c, then it is impossible that pattern_c is a pinyin letter and haystack_c is a hanzi,
+            // so a matched literal char is always committed.
+            if submatch.is_some() || lang_matched && (lang == Lang::Literal || !self.lang_fallback)
+            {
+                return submatch;
             }
         }
 
-        // Fast fail optimization
-        #[cfg(any(feature = "pinyin", feature = "romaji"))]
-        if haystack_c.is_ascii() {
-            return None;
-        }
+        None
+    }
 
-        #[cfg(feature = "romaji")]
-        if let Some(romaji) = self.romaji.as_ref().filter(|_| const { LANG & 2 != 0 }) {
-            use ib_romaji::HepburnRomanizer as R;
-            // const {
-            //     assert!(
-            //         HaystackStr::ELEMENT_LEN_BYTE == 1,
-            //         "non-UTF-8 romaji match is not yet supported"
-            //     );
-            // }
-            debug_assert_eq!(
-                HaystackStr::ELEMENT_LEN_BYTE,
-                1,
-                "non-UTF-8 romaji match is not yet supported"
-            );
-            if let Some(m) = romaji.config.romanizer.romanize_and_try_for_each(
-                // unsafe { str::from_utf8_unchecked(haystack.as_bytes()) },
-                // TODO: Ideally, IbMatcher should accept Input with start/span.
-                ib_romaji::Input::new(
-                    unsafe {
-                        let b = haystack.as_bytes();
-                        /*
-                        // https://github.com/rust-lang/rust/issues/119206
-                        core::str::from_raw_parts(
-                            b.as_ptr().sub(matched_len),
-                            b.len() + matched_len,
-                        )
-                        */
-                        str::from_utf8_unchecked(core::slice::from_raw_parts(
-                            b.as_ptr().sub(matched_len),
-                            b.len() + matched_len,
-                        ))
-                    },
-                    matched_len,
-                ),
-                |len, romaji| {
+    /// Match `haystack_c` as a plain char.
+    ///
+    /// ## Returns
+    /// (lang_matched, submatch), like [`Self::sub_test_pinyin`].
+    #[inline(always)]
+    #[allow(clippy::too_many_arguments)]
+    fn sub_test_literal<T>(
+        &self,
+        pattern_c: &PatternChar,
+        pattern_next: &[PatternChar],
+        haystack_c: char,
+        haystack_next: &HaystackStr,
+        matched_len_next: usize,
+        stats: MatchStats,
+        f: &mut impl FnMut(SubMatch) -> Option<T>,
+    ) -> (bool, Option<T>) {
+        let Some(plain) = &self.plain else {
+            return (false, None);
+        };
+        let eq = |haystack_c: char| match plain.is_case_insensitive() {
+            true => haystack_c.to_simple_or_ascii_fold_case() == pattern_c.c_lowercase,
+            false => haystack_c == pattern_c.c,
+        };
+        if !(eq(haystack_c)
+            || match haystack_c {
+                // The full-width ASCII block is at a fixed offset from ASCII
+                '！'..='～' if self.fullwidth_ascii => {
+                    eq(unsafe { char::from_u32_unchecked(haystack_c as u32 - 0xFEE0) })
+                }
+                _ => false,
+            })
+        {
+            return (false, None);
+        }
+        let stats = stats.add(|s| &mut s.plain);
+        (
+            true,
+            if pattern_next.is_empty() {
+                Some(SubMatch::new(matched_len_next, false, stats))
+                    .filter(|_| !self.ends_with || haystack_next.as_bytes().is_empty())
+                    .and_then(f)
+            } else {
+                self.sub_test_and_try_for_each::<0xFF, T>(
+                    pattern_next,
+                    haystack_next,
+                    matched_len_next,
+                    stats,
+                    None,
+                    Default::default(),
+                    f,
+                )
+            },
+        )
+    }
+
+    /// Match the words starting from the first char of `haystack` as romajis.
+    ///
+    /// ## Returns
+    /// (lang_matched, submatch), like [`Self::sub_test_pinyin`].
+    #[cfg(feature = "romaji")]
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn sub_test_romaji<T>(
+        &self,
+        romaji: &RomajiMatcher,
+        pattern: &[PatternChar],
+        haystack: &HaystackStr,
+        matched_len: usize,
+        stats: MatchStats,
+        _last_romaji_c: Option<NonZeroU8>,
+        f: &mut impl FnMut(SubMatch) -> Option<T>,
+    ) -> (bool, Option<T>) {
+        let (pattern_c, pattern_next) = pattern.split_first().unwrap();
+        let mut lang_matched = false;
+        use ib_romaji::HepburnRomanizer as R;
+        // Non-UTF-8 haystacks are transcoded for the romanizer,
+        // and the romanized lengths are mapped back by `ends`.
+        let mut buf = String::new();
+        let mut ends = Vec::new();
+        let (input, start) = if HaystackStr::UTF8 {
+            // TODO: Ideally, IbMatcher should accept Input with start/span.
+            (
+                unsafe {
+                    str::from_utf8_unchecked(
+                        haystack.extend_start_unchecked(matched_len).as_bytes(),
+                    )
+                },
+                matched_len,
+            )
+        } else {
+            let start = romaji.transcode_window(haystack, matched_len, &mut buf, &mut ends);
+            (buf.as_str(), start)
+        };
+        let script = romaji.config.script;
+        let case_insensitive = romaji.config.is_case_insensitive();
+        let lenient_gemination = romaji.config.lenient_gemination;
+        let rendaku = romaji.config.rendaku;
+        if let Some(m) = romaji.config.romanizer.romanize_readings_and_try_for_each(
+            ib_romaji::Input::new(input, start),
+            romaji.readings(),
+            |len, romaji| {
+                #[cfg(false)]
+                eprintln!("romaji={romaji}, len={len}");
+                // The kana automaton doesn't distinguish scripts
+                if !script.allows(&input[start..start + len]) {
+                    return None;
+                }
+                let voiced = (rendaku && &input[start..start + len] == ib_romaji::kanji::NOMA_STR)
+                    .then(|| ib_romaji::kanji::rendaku(romaji))
+                    .flatten();
+                let len = if HaystackStr::UTF8 {
+                    len
+                } else {
+                    ends[ends.binary_search_by_key(&len, |&(end, _)| end).unwrap()].1
+                };
+                /*
+                if matched_len > 0 {
+                    // This is cursed
+                    let last_c = unsafe { (*pattern.as_ptr().sub(1)).c_lowercase };
+                }
+                */
+                let mut pattern = pattern;
+                let r = if let Some(last_romaji_c) = _last_romaji_c {
+                    let need_apostrophe = R::need_apostrophe_c(last_romaji_c.get() as char, romaji);
                     #[cfg(false)]
-                    eprintln!("romaji={romaji}, len={len}");
-                    /*
-                    if matched_len > 0 {
-                        // This is cursed
-                        let last_c = unsafe { (*pattern.as_ptr().sub(1)).c_lowercase };
-                    }
-                    */
-                    let mut pattern = pattern;
-                    let r = if let Some(last_romaji_c) = _last_romaji_c {
-                        let need_apostrophe =
-                            R::need_apostrophe_c(last_romaji_c.get() as char, romaji);
-                        #[cfg(false)]
-                        dbg!(pattern_c.s, romaji, need_apostrophe);
-                        if need_apostrophe {
-                            if pattern_c.c == R::APOSTROPHE
-                                || pattern_c.c == ib_romaji::convert::hepburn_ime::APOSTROPHE_ALT
-                            {
-                                // Unfortunately, sub_test_pinyin() requires non-empty pattern,
-                                // but APOSTROPHE may be the last char, i.e. pattern ends with needed n apostrophe.
-                                // e.g. c.matcher("nn").find("ンヰ世界")
-                                // TODO: Analyze ahead?
-                                if pattern_next.is_empty() {
-                                    // Not matched_len_next
-                                    return Some(SubMatch::new(matched_len, false))
-                                        .filter(|_| {
-                                            // No need for `|| haystack_next.as_bytes().is_empty()`
-                                            !self.ends_with
-                                        })
-                                        .and_then(|m| f(m));
-                                }
-                                pattern = pattern_next;
-                                true
-                            } else {
-                                false
+                    dbg!(pattern_c.s, romaji, need_apostrophe);
+                    if need_apostrophe {
+                        let c = match case_insensitive {
+                            true => pattern_c.c_lowercase,
+                            false => pattern_c.c,
+                        };
+                        if c == R::APOSTROPHE
+                            || c == ib_romaji::convert::hepburn_ime::APOSTROPHE_ALT
+                        {
+                            // Unfortunately, sub_test_pinyin() requires non-empty pattern,
+                            // but APOSTROPHE may be the last char, i.e. pattern ends with needed n apostrophe.
+                            // e.g. c.matcher("nn").find("ンヰ世界")
+                            // TODO: Analyze ahead?
+                            if pattern_next.is_empty() {
+                                lang_matched = true;
+                                // Not matched_len_next
+                                return Some(SubMatch::new(matched_len, false, stats))
+                                    .filter(|_| {
+                                        // No need for `|| haystack_next.as_bytes().is_empty()`
+                                        !self.ends_with
+                                    })
+                                    .and_then(|m| f(m));
                             }
-                        } else {
+                            pattern = pattern_next;
                             true
+                        } else {
+                            false
                         }
                     } else {
                         true
-                    };
-                    if r {
-                        let match_len_next = matched_len + len;
+                    }
+                } else {
+                    true
+                };
+                let match_len_next = matched_len + len;
+                if r && match_len_next <= self.max_match_len {
+                    let degeminated = lenient_gemination
+                        .then(|| RomajiMatcher::degeminate(romaji))
+                        .flatten();
+                    for romaji in iter::once(romaji)
+                        .chain(degeminated.as_deref())
+                        .chain(voiced.as_deref())
+                    {
                         match self.sub_test_pinyin::<2, T>(
                             pattern,
                             unsafe { haystack.get_unchecked_from(len..) },
                             match_len_next,
+                            stats.add(|s| &mut s.romaji),
                             romaji,
+                            Default::default(),
                             f,
                         ) {
                             (true, Some(submatch)) => return Some(submatch),
-                            (true, None) => (),
+                            (true, None) => lang_matched = true,
                             (false, None) => (),
                             (false, Some(_)) => unreachable!(),
                         }
                     }
-                    None
-                },
-            ) {
-                return Some(m);
+                }
+                None
+            },
+        ) {
+            return (true, Some(m));
+        }
+        (lang_matched, None)
+    }
+
+    /// Match `haystack_c` as pinyins, including the readings of the phrase starting from it.
+    ///
+    /// ## Returns
+    /// (lang_matched, submatch), like [`Self::sub_test_pinyin`].
+    #[cfg(feature = "pinyin")]
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn sub_test_pinyins<T>(
+        &self,
+        matcher: &PinyinMatcher,
+        pattern: &[PatternChar],
+        haystack_c: char,
+        haystack_next: &HaystackStr,
+        matched_len_next: usize,
+        stats: MatchStats,
+        phrase: PhraseRest,
+        f: &mut impl FnMut(SubMatch) -> Option<T>,
+    ) -> (bool, Option<T>) {
+        let mut lang_matched = false;
+        // for pinyin in self.pinyin_data.get_pinyins(haystack_c) {
+        //     for &notation in self.pinyin.notations_prefix_group.iter() {
+        //         let pinyin = pinyin.notation(notation).unwrap();
+        //         match self.sub_test_pinyin(pattern, haystack_next, matched_len, pinyin) {
+        //             (true, Some(submatch)) => return Some(submatch),
+        //             (true, None) => (),
+        //             (false, None) => break,
+        //             (false, Some(_)) => unreachable!(),
+        //         }
+        //     }
+        //     for &notation in self.pinyin.notations.iter() {
+        //         let pinyin = pinyin.notation(notation).unwrap();
+        //         match self.sub_test_pinyin(pattern, haystack_next, matched_len, pinyin) {
+        //             (true, Some(submatch)) => return Some(submatch),
+        //             (true, None) => (),
+        //             (false, None) => (),
+        //             (false, Some(_)) => unreachable!(),
+        //         }
+        //     }
+        // }
+        // None
+
+        // The readings of a phrase take precedence over the ones of its hanzi
+        let phrase = match phrase {
+            [] => matcher
+                .config
+                .phrases
+                .and_then(|phrases| {
+                    phrases.find(haystack_c, |rest| {
+                        haystack_next
+                            .char_len_next_strs()
+                            .map(|(c, _, _)| c)
+                            .take(rest.len())
+                            .eq(rest.iter().copied())
+                    })
+                })
+                .unwrap_or_default(),
+            phrase => phrase,
+        };
+        let (phrase_pinyins, phrase_next) = match phrase.split_first() {
+            Some((pinyins, next)) => (Some(pinyins), next),
+            None => (None, Default::default()),
+        };
+
+        let mut try_pinyin = |pinyin: Pinyin| {
+            for &notation in matcher.notations_prefix_group.iter() {
+                let pinyin = pinyin.notation(notation).unwrap();
+                match self.sub_test_pinyin_fuzzy::<T>(
+                    matcher,
+                    notation,
+                    pattern,
+                    haystack_next,
+                    matched_len_next,
+                    stats,
+                    pinyin,
+                    phrase_next,
+                    f,
+                ) {
+                    (true, Some(submatch)) => return Some(submatch),
+                    (true, None) => lang_matched = true,
+                    (false, None) => break,
+                    (false, Some(_)) => unreachable!(),
+                }
+            }
+            for &notation in matcher.notations.iter() {
+                // e.g. Wade-Giles without the apostrophe
+                for pinyin in pinyin.notation_variants(notation) {
+                    match self.sub_test_pinyin_fuzzy::<T>(
+                        matcher,
+                        notation,
+                        pattern,
+                        haystack_next,
+                        matched_len_next,
+                        stats,
+                        pinyin,
+                        phrase_next,
+                        f,
+                    ) {
+                        (true, Some(submatch)) => return Some(submatch),
+                        (true, None) => lang_matched = true,
+                        (false, None) => (),
+                        (false, Some(_)) => unreachable!(),
+                    }
+                }
+            }
+            if let Some(shuangpin) = matcher.config.shuangpin {
+                let (matched, submatch) = self.sub_test_pinyin::<1, T>(
+                    pattern,
+                    haystack_next,
+                    matched_len_next,
+                    stats.add(|s| &mut s.pinyin),
+                    shuangpin.keys(&pinyin),
+                    phrase_next,
+                    f,
+                );
+                lang_matched |= matched;
+                if submatch.is_some() {
+                    return submatch;
+                }
             }
+            None
+        };
+        // Reduce total time by 45~65% compared to using `get_pinyins()`
+        if let Some(m) = match phrase_pinyins {
+            Some(pinyins) => pinyins
+                .iter()
+                .find_map(|&i| try_pinyin(matcher.config.data.pinyin(i))),
+            // User readings take precedence over the built-in ones
+            None => match matcher
+                .config
+                .readings
+                .and_then(|readings| readings.get(haystack_c))
+            {
+                Some(readings) => readings
+                    .pinyins
+                    .iter()
+                    .find_map(|&i| try_pinyin(matcher.config.data.pinyin(i)))
+                    .or_else(|| {
+                        if readings.replace {
+                            return None;
+                        }
+                        matcher
+                            .config
+                            .data
+                            .get_pinyins_and_try_for_each(haystack_c, &mut try_pinyin)
+                    }),
+                None => matcher
+                    .config
+                    .data
+                    .get_pinyins_and_try_for_each(haystack_c, &mut try_pinyin),
+            },
+        } {
+            return (true, Some(m));
         }
 
-        #[cfg(feature = "pinyin")]
-        if let Some(matcher) = self.pinyin.as_ref().filter(|_| const { LANG & 1 != 0 }) {
-            // for pinyin in self.pinyin_data.get_pinyins(haystack_c) {
-            //     for &notation in self.pinyin.notations_prefix_group.iter() {
-            //         let pinyin = pinyin.notation(notation).unwrap();
-            //         match self.sub_test_pinyin(pattern, haystack_next, matched_len, pinyin) {
-            //             (true, Some(submatch)) => return Some(submatch),
-            //             (true, None) => (),
-            //             (false, None) => break,
-            //             (false, Some(_)) => unreachable!(),
-            //         }
-            //     }
-            //     for &notation in self.pinyin.notations.iter() {
-            //         let pinyin = pinyin.notation(notation).unwrap();
-            //         match self.sub_test_pinyin(pattern, haystack_next, matched_len, pinyin) {
-            //             (true, Some(submatch)) => return Some(submatch),
-            //             (true, None) => (),
-            //             (false, None) => (),
-            //             (false, Some(_)) => unreachable!(),
-            //         }
-            //     }
-            // }
-            // None
-
-            // Reduce total time by 45~65% compared to using `get_pinyins()`
+        #[cfg(feature = "pinyin-jyutping")]
+        if matcher.jyutping {
             if let Some(m) =
                 matcher
                     .config
                     .data
-                    .get_pinyins_and_try_for_each(haystack_c, |pinyin| {
-                        for &notation in matcher.notations_prefix_group.iter() {
-                            let pinyin = pinyin.notation(notation).unwrap();
-                            match self.sub_test_pinyin::<1, T>(
-                                pattern,
-                                haystack_next,
-                                matched_len_next,
-                                pinyin,
-                                f,
-                            ) {
-                                (true, Some(submatch)) => return Some(submatch),
-                                (true, None) => (),
-                                (false, None) => break,
-                                (false, Some(_)) => unreachable!(),
-                            }
-                        }
-                        for &notation in matcher.notations.iter() {
-                            let pinyin = pinyin.notation(notation).unwrap();
-                            match self.sub_test_pinyin::<1, T>(
-                                pattern,
-                                haystack_next,
-                                matched_len_next,
-                                pinyin,
-                                f,
-                            ) {
-                                (true, Some(submatch)) => return Some(submatch),
-                                (true, None) => (),
-                                (false, None) => (),
-                                (false, Some(_)) => unreachable!(),
-                            }
-                        }
-                        None
+                    .get_jyutpings_and_try_for_each(haystack_c, |jyutping| {
+                        let (matched, submatch) = self.sub_test_pinyin::<1, T>(
+                            pattern,
+                            haystack_next,
+                            matched_len_next,
+                            stats.add_pinyin(PinyinNotation::Jyutping),
+                            jyutping,
+                            Default::default(),
+                            f,
+                        );
+                        lang_matched |= matched;
+                        submatch
                     })
             {
-                return Some(m);
+                return (true, Some(m));
             }
         }
+        (lang_matched, None)
+    }
 
-        None
+    /// [`Self::sub_test_pinyin`] with the pinyin and its fuzzy variants.
+    ///
+    /// ## Returns
+    /// (pinyin_matched, submatch), where `pinyin_matched` is `true` if any variant matched.
+    #[cfg(feature = "pinyin")]
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn sub_test_pinyin_fuzzy<T>(
+        &self,
+        matcher: &PinyinMatcher,
+        notation: PinyinNotation,
+        pattern: &[PatternChar],
+        haystack_next: &HaystackStr,
+        matched_len_next: usize,
+        stats: MatchStats,
+        pinyin: &str,
+        phrase_next: PhraseRest,
+        f: &mut impl FnMut(SubMatch) -> Option<T>,
+    ) -> (bool, Option<T>) {
+        let stats = stats.add_pinyin(notation);
+        let r = self.sub_test_pinyin::<1, T>(
+            pattern,
+            haystack_next,
+            matched_len_next,
+            stats,
+            pinyin,
+            phrase_next,
+            f,
+        );
+        let fuzzy = matcher.config.fuzzy;
+        if fuzzy.is_empty() || r.1.is_some() || !FuzzyFlags::NOTATIONS.contains(notation) {
+            return r;
+        }
+
+        let mut matched = r.0;
+        fuzzy
+            .try_for_each_variant(pinyin, |variant| {
+                match self.sub_test_pinyin::<1, T>(
+                    pattern,
+                    haystack_next,
+                    matched_len_next,
+                    stats,
+                    variant,
+                    phrase_next,
+                    f,
+                ) {
+                    (true, Some(submatch)) => Some(submatch),
+                    (true, None) => {
+                        matched = true;
+                        None
+                    }
+                    (false, None) => None,
+                    (false, Some(_)) => unreachable!(),
+                }
+            })
+            .map_or((matched, None), |submatch| (true, Some(submatch)))
     }
 
     /// ## Arguments
@@ -904,16 +2791,21 @@ where
     /// - `pattern`: Not empty.
     /// - `haystack`
     /// - `matched_len`: For tail-call optimization.
+    /// - `stats`: Including the current hanzi/word.
+    /// - `phrase_next`: See [`Self::sub_test_and_try_for_each`].
     ///
     /// ## Returns
     /// (pinyin_matched, submatch)
     #[cfg(any(feature = "pinyin", feature = "romaji"))]
+    #[allow(clippy::too_many_arguments)]
     fn sub_test_pinyin<const LANG: u8, T>(
         &self,
         pattern: &[PatternChar],
         haystack_next: &HaystackStr,
         matched_len_next: usize,
+        stats: MatchStats,
         pinyin: &str,
+        phrase_next: PhraseRest,
         f: &mut impl FnMut(SubMatch) -> Option<T>,
     ) -> (bool, Option<T>) {
         #[cfg(false)]
@@ -926,17 +2818,13 @@ where
 
         let pattern_s = match match LANG {
             #[cfg(feature = "pinyin")]
-            1 => {
-                unsafe { self.pinyin.as_ref().unwrap_unchecked() }
-                    .config
-                    .case_insensitive
-            }
+            1 => unsafe { self.pinyin.as_ref().unwrap_unchecked() }
+                .config
+                .is_case_insensitive(),
             #[cfg(feature = "romaji")]
-            2 => {
-                unsafe { self.romaji.as_ref().unwrap_unchecked() }
-                    .config
-                    .case_insensitive
-            }
+            2 => unsafe { self.romaji.as_ref().unwrap_unchecked() }
+                .config
+                .is_case_insensitive(),
             _ => unreachable!(),
         } {
             true => pattern[0].s_lowercase,
@@ -963,9 +2851,7 @@ where
                 2 => {
                     let romaji = unsafe { self.romaji.as_ref().unwrap_unchecked() };
                     romaji.partial_pattern
-                        && ib_romaji::convert::hepburn_ime::romaji_starts_with_ignore_hepburn_ime(
-                            pinyin, pattern_s,
-                        )
+                        && romaji.romaji_starts_with(pinyin, pattern_s)
                         && (romaji.partial_kana
                             || ib_romaji::HepburnRomanizer::is_romaji_kana_boundary(
                                 pinyin,
@@ -977,21 +2863,37 @@ where
                 return (
                     true,
                     // TODO: partial_word/kana
-                    Some(SubMatch::new(matched_len_next, true))
+                    Some(SubMatch::new(matched_len_next, true, stats))
                         .filter(|_| !self.ends_with || haystack_next.as_bytes().is_empty())
                         .and_then(f),
                 );
             }
         } else if match LANG {
             #[cfg(feature = "romaji")]
-            2 => ib_romaji::convert::hepburn_ime::starts_with_ignore_hepburn_ime(pattern_s, pinyin),
+            2 => unsafe { self.romaji.as_ref().unwrap_unchecked() }
+                .pattern_starts_with(pattern_s, pinyin),
             #[cfg(feature = "pinyin")]
             _ => pattern_s.starts_with(pinyin),
         } {
-            if pattern_s.len() == pinyin.len() {
+            // Not pinyin.chars().count(), the pattern may use different chars of the same length,
+            // e.g. romaji long vowels.
+            let mut pattern_next = &pattern[pattern_s[..pinyin.len()].chars().count()..];
+            #[cfg(feature = "pinyin")]
+            if LANG == 1 {
+                // Skip the syllable separator
+                let separator = unsafe { self.pinyin.as_ref().unwrap_unchecked() }
+                    .config
+                    .separator;
+                if let Some((c, next)) = pattern_next.split_first() {
+                    if Some(c.c) == separator {
+                        pattern_next = next;
+                    }
+                }
+            }
+            if pattern_next.is_empty() {
                 return (
                     true,
-                    Some(SubMatch::new(matched_len_next, false))
+                    Some(SubMatch::new(matched_len_next, false, stats))
                         .filter(|_| !self.ends_with || haystack_next.as_bytes().is_empty())
                         .and_then(f),
                 );
@@ -1003,12 +2905,14 @@ where
                 Self::sub_test_and_try_for_each::<LANG, T>
             }(
                 self,
-                &pattern[pinyin.chars().count()..],
+                pattern_next,
                 haystack_next,
                 matched_len_next,
+                stats,
                 Some(unsafe {
                     NonZeroU8::new_unchecked(*pinyin.as_bytes().last().unwrap_unchecked())
                 }),
+                phrase_next,
                 f,
             ) {
                 return (true, Some(submatch));
@@ -1039,14 +2943,461 @@ where
     /// Already tested in match methods.
     pub fn is_haystack_too_short(&self, haystack: &HaystackStr) -> bool {
         // Self::is_haystack_too_short_with_pattern(&self.pattern, haystack)
-        haystack.as_bytes().len() < self.min_haystack_len
+        haystack.as_bytes().len() < self.analysis.min_haystack_len
+    }
+
+    /// The results of analyzing the pattern, e.g. for building prefilters.
+    ///
+    /// ```
+    /// use ib_matcher::matcher::{IbMatcher, PinyinMatchConfig};
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::default())
+    ///     .analyze(true)
+    ///     .build();
+    /// let analysis = matcher.analysis();
+    /// // "拼音"
+    /// assert_eq!(analysis.min_haystack_len(), 6);
+    /// assert!(!analysis.is_plain_only());
+    ///
+    /// let matcher = IbMatcher::builder("1.2")
+    ///     .pinyin(PinyinMatchConfig::default())
+    ///     .build();
+    /// assert!(matcher.analysis().is_plain_only());
+    /// ```
+    pub fn analysis(&self) -> &analyze::PatternAnalysis {
+        &self.analysis
+    }
+
+    /// Literal byte strings that any matched haystack must contain, e.g. for prefiltering haystacks with an inverted index.
+    ///
+    /// They are the runs of pattern chars that can only be matched as plain text.
+    /// If [`PlainMatchConfigBuilder::case_insensitive`] (the default), the runs are also split at chars that have other cases,
+    /// since they may be matched in any case.
+    /// If [`IbMatcherBuilder::fullwidth_ascii`], the runs are also split at ASCII chars other than space,
+    /// since they may be matched in full-width.
+    ///
+    /// An empty `Vec` means no guarantee, which is the case if:
+    /// - The whole pattern may be matched as pinyin.
+    /// - Romaji matching is enabled, which is not analyzed at the moment.
+    /// - [`IbMatcherBuilder::normalize`] is enabled or the haystacks are not UTF-8.
+    /// - [`IbMatcherBuilder::plain`] is `None`.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::matcher::{IbMatcher, PinyinMatchConfig};
+    ///
+    /// let matcher = IbMatcher::builder("py.Xss")
+    ///     .pinyin(PinyinMatchConfig::default())
+    ///     .build();
+    /// assert_eq!(matcher.prefilter_literals(), [b"."]);
+    ///
+    /// let matcher = IbMatcher::builder("py.Xss")
+    ///     .pinyin(PinyinMatchConfig::default())
+    ///     .case_insensitive(false)
+    ///     .build();
+    /// assert_eq!(matcher.prefilter_literals(), [b".X"]);
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::default())
+    ///     .build();
+    /// assert!(matcher.prefilter_literals().is_empty());
+    /// ```
+    pub fn prefilter_literals(&self) -> Vec<Vec<u8>> {
+        let Some(plain) = &self.plain else {
+            return Vec::new();
+        };
+        #[cfg(feature = "romaji")]
+        if self.romaji.is_some() {
+            return Vec::new();
+        }
+        #[cfg(feature = "unicode-normalization")]
+        if self.normalize != Normalization::None {
+            return Vec::new();
+        }
+        if !HaystackStr::UTF8 {
+            return Vec::new();
+        }
+
+        let pattern = self._pattern_string.as_str();
+        #[cfg_attr(not(feature = "pinyin"), allow(unused_mut))]
+        let mut pinyin_bytes = vec![false; pattern.len()];
+        #[cfg(feature = "pinyin")]
+        if let Some(pinyin) = &self.pinyin {
+            analyze::PatternAnalyzer::mark_pinyin_bytes(
+                &pinyin.config,
+                match pinyin.config.is_case_insensitive() {
+                    true => &self._pattern_string_lowercase,
+                    false => pattern,
+                },
+                pinyin.partial_pattern,
+                &mut pinyin_bytes,
+            );
+        }
+
+        let mut literals = Vec::new();
+        let mut literal = Vec::new();
+        for (i, c) in pattern.char_indices() {
+            if pinyin_bytes[i]
+                || (self.fullwidth_ascii && matches!(c, '!'..='~'))
+                || (plain.is_case_insensitive() && !c.to_lowercase().eq(c.to_uppercase()))
+            {
+                if !literal.is_empty() {
+                    literals.push(mem::take(&mut literal));
+                }
+                continue;
+            }
+            literal.extend_from_slice(&pattern.as_bytes()[i..i + c.len_utf8()]);
+        }
+        if !literal.is_empty() {
+            literals.push(literal);
+        }
+        literals
+    }
+}
+
+/// Parallel matching of multiple haystacks.
+///
+/// ## Thread safety
+/// The matcher is shared by reference across the threads of rayon's global pool,
+/// which requires `IbMatcher` to be [`Sync`].
+/// It is as long as the referenced configs are, which is the case for all of [`PinyinData`](crate::pinyin::PinyinData) (including with feature `inmut-data`), [`HepburnRomanizer`](crate::romaji::HepburnRomanizer) and the other configs of this crate.
+/// The haystacks are only read, so they only need to be [`Sync`] as well.
+#[cfg(feature = "rayon")]
+impl<'a, HaystackStr> IbMatcher<'a, HaystackStr>
+where
+    HaystackStr: EncodedStr + Sync + ?Sized,
+    Self: Sync,
+{
+    /// The minimum number of haystacks processed by one task.
+    ///
+    /// A single match only takes hundreds of nanoseconds, so splitting the work further costs more than it saves.
+    const PAR_MIN_LEN: usize = 256;
+
+    /// Returns the haystacks that match the pattern, i.e. [`IbMatcher::is_match()`] in parallel.
+    ///
+    /// The order of `haystacks` is preserved.
+    /// See [`IbMatcher::par_filter_indices()`] for slices, which also chunks the haystacks more efficiently.
+    ///
+    /// ## Example
+    /// ```
+    /// // cargo add ib-matcher --features pinyin,rayon
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pysousuo")
+    ///     .pinyin(PinyinMatchConfig::notations(
+    ///         PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+    ///     ))
+    ///     .analyze(true)
+    ///     .build();
+    /// let haystacks = vec!["拼音搜索", "Everything", "拼音搜索Everything"];
+    /// assert_eq!(matcher.par_filter(haystacks), ["拼音搜索", "拼音搜索Everything"]);
+    /// ```
+    pub fn par_filter<'h, I>(&self, haystacks: I) -> Vec<&'h HaystackStr>
+    where
+        I: IntoParallelIterator<Item = &'h HaystackStr>,
+        HaystackStr: 'h,
+    {
+        haystacks
+            .into_par_iter()
+            .filter(|&haystack| self.is_match(haystack))
+            .collect()
+    }
+
+    /// Returns the indices of the haystacks that match the pattern, in ascending order.
+    ///
+    /// Each task processes at least a few hundred haystacks, so this is more efficient than [`IbMatcher::par_filter()`] for a large number of short haystacks, e.g. file names.
+    ///
+    /// ## Example
+    /// ```
+    /// // cargo add ib-matcher --features rayon
+    /// use ib_matcher::matcher::IbMatcher;
+    ///
+    /// let matcher = IbMatcher::builder("ev").build();
+    /// let haystacks: Vec<String> = (0..1000).map(|i| format!("{i}.{}", ["txt", "ev"][i % 2])).collect();
+    /// let indices = matcher.par_filter_indices(&haystacks);
+    /// assert_eq!(indices.len(), 500);
+    /// assert_eq!(indices[..3], [1, 3, 5]);
+    /// ```
+    pub fn par_filter_indices<H>(&self, haystacks: &[H]) -> Vec<usize>
+    where
+        H: AsRef<HaystackStr> + Sync,
+    {
+        haystacks
+            .par_iter()
+            .with_min_len(Self::PAR_MIN_LEN)
+            .enumerate()
+            .filter(|(_, haystack)| self.is_match(haystack.as_ref()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl<'a> IbMatcher<'a, str> {
+    /// [`IbMatcher::find()`] on a byte haystack that is mostly UTF-8 but may contain invalid byte sequences,
+    /// e.g. for searching arbitrary files.
+    ///
+    /// Invalid byte sequences are treated as separators that never match: a match can only be in a valid UTF-8 region,
+    /// and never spans or includes invalid bytes. Each region is searched like a haystack of its own,
+    /// except that [`IbMatcherBuilder::starts_with()`] can only match at the start of the whole haystack,
+    /// and [`IbMatcherBuilder::ends_with()`] and [`IbMatcherBuilder::stem_only()`] are relative to its end.
+    ///
+    /// Note this is different from the lossy decoding of [`OsStr`](std::ffi::OsStr) haystacks (see [`EncodedStr`](encoding::EncodedStr)),
+    /// where invalid bytes are decoded as `U+FFFD`.
+    ///
+    /// Never panics on arbitrary input.
+    ///
+    /// ## Returns
+    /// The match offsets are in bytes of `haystack`.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pinyin")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+    ///     .build();
+    /// let haystack = b"\xff\xfe\xe6\x8b\xbc\xe9\x9f\xb3"; // "\xff\xfe拼音"
+    /// assert_eq!(matcher.find_bytes(haystack).unwrap().range(), 2..8);
+    /// // "拼\xff音"
+    /// assert!(matcher.find_bytes(b"\xe6\x8b\xbc\xff\xe9\x9f\xb3").is_none());
+    /// ```
+    pub fn find_bytes(&self, haystack: &[u8]) -> Option<Match> {
+        let haystack = match self
+            .stem_only
+            .then(|| Self::utf8_last_path_char(haystack, false))
+            .flatten()
+        {
+            Some((i, b'.', false)) => &haystack[..i],
+            _ => haystack,
+        };
+        if self.pattern.is_empty() {
+            return self.empty_match();
+        }
+
+        for (start, chunk) in encoding::utf8_chunks(haystack) {
+            // Invalid bytes after the chunk
+            if self.ends_with && start + chunk.len() != haystack.len() {
+                continue;
+            }
+            let input = Input {
+                haystack: chunk,
+                no_start: start != 0,
+                anchored: false,
+                start: 0,
+                whole: (chunk, start != 0),
+            };
+            if let Some(m) = self.find_stem(input) {
+                return Some(m.offset(start));
+            }
+        }
+        None
+    }
+
+    /// Replaces the first match in `haystack` with `rep`.
+    ///
+    /// Returns [`Cow::Borrowed`] without allocation if there is no match.
+    /// Unlike [`regex`](https://docs.rs/regex/), `$` in `rep` has no special meaning.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pys")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// assert_eq!(matcher.replace("拼音搜索, 拼音搜索", "*"), "*索, 拼音搜索");
+    /// ```
+    pub fn replace<'h>(&self, haystack: &'h str, rep: &str) -> Cow<'h, str> {
+        self.replace_with(haystack, 1, |_, _| rep)
+    }
+
+    /// Replaces all non-overlapping matches in `haystack` with `rep`.
+    ///
+    /// See [`IbMatcher::find_iter()`] for how matches are found,
+    /// and [`IbMatcher::replace()`] for details.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pys")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// assert_eq!(matcher.replace_all("拼音搜索, 拼音搜索", "*"), "*索, *索");
+    /// ```
+    pub fn replace_all<'h>(&self, haystack: &'h str, rep: &str) -> Cow<'h, str> {
+        self.replace_with(haystack, 0, |_, _| rep)
+    }
+
+    /// Replaces at most `limit` non-overlapping matches in `haystack` with the return value of `f`.
+    /// If `limit` is 0, all matches are replaced.
+    ///
+    /// `f` is called with each match and the matched text, e.g. for inserting highlight tags.
+    ///
+    /// Returns [`Cow::Borrowed`] without allocation if there is no match.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pysousuo")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// assert_eq!(
+    ///     matcher.replace_with("拼音搜索, pysousuo", 0, |_, s| format!("<b>{s}</b>")),
+    ///     "<b>拼音搜索</b>, <b>pysousuo</b>"
+    /// );
+    /// ```
+    pub fn replace_with<'h, R: AsRef<str>>(
+        &self,
+        haystack: &'h str,
+        limit: usize,
+        mut f: impl FnMut(&Match, &str) -> R,
+    ) -> Cow<'h, str> {
+        let mut new: Option<String> = None;
+        let mut last_end = 0;
+        for (i, m) in self.find_iter(haystack).enumerate() {
+            if limit != 0 && i == limit {
+                break;
+            }
+            let new = new.get_or_insert_with(|| String::with_capacity(haystack.len()));
+            new.push_str(&haystack[last_end..m.start()]);
+            new.push_str(f(&m, &haystack[m.range()]).as_ref());
+            last_end = m.end();
+        }
+        match new {
+            Some(mut new) => {
+                new.push_str(&haystack[last_end..]);
+                Cow::Owned(new)
+            }
+            None => Cow::Borrowed(haystack),
+        }
+    }
+
+    /// Searches `reader` line by line without loading the whole content into memory,
+    /// and calls `f` with the line number (starting from 1) and each non-overlapping match in the line.
+    ///
+    /// `f` can return `Some(_)` to stop the iteration, or `None` to continue.
+    ///
+    /// - The match offsets are in bytes from the start of `reader`, not the line.
+    /// - Lines are split by `\n`, and a trailing `\r` is also excluded from the line, like [`BufRead::lines()`].
+    ///   Matches never span multiple lines.
+    /// - Lines longer than `max_line_len` bytes are searched in chunks of at most `max_line_len` bytes
+    ///   (split at char boundaries) to keep the memory bounded, and matches straddling two chunks are not found.
+    ///   If `max_line_len` is 0, lines are never split. Otherwise, it should be at least 4 (the maximum length of a char).
+    ///
+    /// ## Errors
+    /// Returns the I/O errors of `reader`, or [`io::ErrorKind::InvalidData`] if a line is not valid UTF-8.
+    /// Matches before the error have already been passed to `f`.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let matcher = IbMatcher::builder("pysousuo")
+    ///     .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter))
+    ///     .build();
+    /// let mut matches = Vec::new();
+    /// matcher
+    ///     .matches_reader("拼音搜索\nabc\r\npysousuo, 拼音搜索".as_bytes(), 0, |line_no, m| {
+    ///         matches.push((line_no, m.range()));
+    ///         None::<()>
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(matches, [(1, 0..12), (3, 18..26), (3, 28..40)]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn matches_reader<T>(
+        &self,
+        mut reader: impl BufRead,
+        max_line_len: usize,
+        mut f: impl FnMut(usize, Match) -> Option<T>,
+    ) -> io::Result<Option<T>> {
+        let max_line_len = match max_line_len {
+            0 => u64::MAX,
+            len => len.max(4) as u64,
+        };
+        let invalid_data = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )
+        };
+
+        // May start with an incomplete char of the previous chunk
+        let mut buf = Vec::new();
+        let mut line_no = 1;
+        // Offset of `buf` in `reader`
+        let mut offset = 0;
+        loop {
+            let limit = max_line_len - buf.len() as u64;
+            let eof = (&mut reader).take(limit).read_until(b'\n', &mut buf)? == 0;
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            let newline = buf.last() == Some(&b'\n');
+
+            let chunk = match core::str::from_utf8(&buf) {
+                Ok(chunk) => chunk,
+                // A long line is split in the middle of a char
+                Err(e) if !newline && !eof && e.error_len().is_none() => unsafe {
+                    core::str::from_utf8_unchecked(&buf[..e.valid_up_to()])
+                },
+                Err(_) => return Err(invalid_data()),
+            };
+            let chunk_len = chunk.len();
+            let line = match chunk.strip_suffix('\n') {
+                Some(line) => line.strip_suffix('\r').unwrap_or(line),
+                None => chunk,
+            };
+
+            for m in self.find_iter(line) {
+                let m = Match {
+                    start: offset + m.start,
+                    end: offset + m.end,
+                    ..m
+                };
+                if let Some(result) = f(line_no, m) {
+                    return Ok(Some(result));
+                }
+            }
+
+            buf.drain(..chunk_len);
+            offset += chunk_len;
+            if newline {
+                line_no += 1;
+            }
+            if eof && buf.is_empty() {
+                return Ok(None);
+            }
+        }
     }
 }
 
 impl<'a, S: match_config_builder::State> MatchConfigBuilder<'a, S> {
     /// A convenient setter for [`PlainMatchConfigBuilder::case_insensitive`].
     ///
-    /// The case insensitivity of pinyin is controlled by [`PinyinMatchConfigBuilder::case_insensitive`].
+    /// The case insensitivity of pinyin and romaji is controlled by [`PinyinMatchConfigBuilder::case_insensitive`] and [`RomajiMatchConfigBuilder::case_insensitive`].
+    /// To set all of them at once, see [`IbMatcherBuilder::case()`].
     pub fn case_insensitive(
         self,
         case_insensitive: bool,
@@ -1064,7 +3415,8 @@ where
 {
     /// A convenient setter for [`PlainMatchConfigBuilder::case_insensitive`].
     ///
-    /// The case insensitivity of pinyin is controlled by [`PinyinMatchConfigBuilder::case_insensitive`].
+    /// The case insensitivity of pinyin and romaji is controlled by [`PinyinMatchConfigBuilder::case_insensitive`] and [`RomajiMatchConfigBuilder::case_insensitive`].
+    /// To set all of them at once, see [`IbMatcherBuilder::case()`].
     pub fn case_insensitive(
         self,
         case_insensitive: bool,
@@ -1088,13 +3440,92 @@ mod test {
     }
 
     #[test]
-    fn is_haystack_too_short() {
-        // assert!(IbMatcher::is_haystack_too_short_with_pattern(&[], "") == false);
-        // assert!(IbMatcher::is_haystack_too_short_with_pattern(&[], "a") == false);
-
-        let matcher = IbMatcher::builder("pysseve")
-            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
-            .analyze(true)
+    fn mixed_first_letter() {
+        let matcher = IbMatcher::builder("pysearch")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        for haystack in ["拼音search", "拼音Search"] {
+            assert_match(matcher.find(haystack), Some((0, 12)));
+            assert_match(matcher.test(haystack), Some((0, 12)));
+            assert!(matcher.is_match(haystack));
+            assert_eq!(matcher.count(haystack), 1);
+            assert_eq!(
+                matcher.find_highlights(haystack).unwrap().1,
+                [0..3, 3..6, 6..7, 7..8, 8..9, 9..10, 10..11, 11..12]
+            );
+        }
+        assert_match(matcher.find("x拼音searchx"), Some((1, 12)));
+        assert!(matcher.test("x拼音search").is_none());
+        assert_eq!(
+            matcher
+                .find_iter("拼音search拼音search")
+                .map(|m| m.range())
+                .collect::<Vec<_>>(),
+            [0..12, 12..24]
+        );
+        assert!(!matcher.is_match("拼音 search"));
+        assert!(!matcher.is_match("拼search"));
+        let m = matcher.find_best("拼音search").unwrap();
+        assert_eq!((m.stats().plain, m.stats().pinyin_first_letter), (6, 2));
+
+        // Anchors
+        let matcher = IbMatcher::builder("pysearch")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .starts_with(true)
+            .ends_with(true)
+            .build();
+        assert_match(matcher.find("拼音search"), Some((0, 12)));
+        assert!(!matcher.is_match("拼音searchx"));
+
+        // Full pinyin and first letters
+        let matcher = IbMatcher::builder("pyinsearchss")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .analyze(true)
+            .build();
+        assert_match(matcher.find("拼音search搜索"), Some((0, 18)));
+
+        // Before hanzi, and other ASCII chars
+        let matcher = IbMatcher::builder("v2py")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.find("V2拼音"), Some((0, 8)));
+
+        // Case insensitive pinyin
+        let matcher = IbMatcher::builder("PYSearch")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::AsciiFirstLetter)
+                    .case_insensitive(true)
+                    .build(),
+            )
+            .build();
+        assert_match(matcher.find("拼音SEARCH"), Some((0, 12)));
+
+        // Without plain matching
+        let matcher = IbMatcher::builder("pysearch")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .plain(None)
+            .build();
+        assert!(!matcher.is_match("拼音search"));
+    }
+
+    #[test]
+    fn is_haystack_too_short() {
+        // assert!(IbMatcher::is_haystack_too_short_with_pattern(&[], "") == false);
+        // assert!(IbMatcher::is_haystack_too_short_with_pattern(&[], "a") == false);
+
+        let matcher = IbMatcher::builder("pysseve")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .analyze(true)
             .build();
         assert!(matcher.is_haystack_too_short(""));
         assert!(matcher.is_haystack_too_short("a"));
@@ -1105,6 +3536,262 @@ mod test {
         assert!(matcher.is_haystack_too_short("拼音搜") == false);
     }
 
+    #[test]
+    fn prefilter_literals() {
+        let literals = |pattern, pinyin| {
+            IbMatcher::builder(pattern)
+                .pinyin(pinyin)
+                .build()
+                .prefilter_literals()
+        };
+        let ascii = || PinyinMatchConfig::notations(PinyinNotation::Ascii);
+
+        assert_eq!(literals("拼音 1.2", ascii()), ["拼音 1.2".as_bytes()]);
+        assert_eq!(literals("pinyin 1.2", ascii()), [b" 1.2"]);
+        // Part of a pinyin
+        assert_eq!(literals("xing", ascii()), [b""; 0]);
+        assert_eq!(literals("1a2b", ascii()), [b"1", b"2"]);
+        assert_eq!(
+            literals(
+                "1a2b",
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .case_insensitive(true)
+                    .build()
+            ),
+            [b"1", b"2"]
+        );
+        assert_eq!(
+            literals(
+                "1zon2",
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .fuzzy(FuzzyFlags::all())
+                    .build()
+            ),
+            [b"1", b"2"]
+        );
+
+        let matcher = IbMatcher::builder("1A2a")
+            .pinyin(ascii())
+            .case_insensitive(false)
+            .build();
+        assert_eq!(matcher.prefilter_literals(), [b"1A2"]);
+
+        let matcher = IbMatcher::builder("1A2b")
+            .plain(None)
+            .pinyin(ascii())
+            .build();
+        assert_eq!(matcher.prefilter_literals(), [b""; 0]);
+
+        // Without pinyin
+        let matcher = IbMatcher::builder("1A2b").case_insensitive(false).build();
+        assert_eq!(matcher.prefilter_literals(), [b"1A2b"]);
+
+        // Full-width ASCII
+        let matcher = IbMatcher::builder("2024年").fullwidth_ascii(true).build();
+        assert_eq!(matcher.prefilter_literals(), ["年".as_bytes()]);
+        assert_match(matcher.find("２０２４年"), Some((0, 15)));
+        let matcher = IbMatcher::builder("1 2").fullwidth_ascii(true).build();
+        assert_eq!(matcher.prefilter_literals(), [b" "]);
+        assert_match(matcher.find("１ ２"), Some((0, 7)));
+    }
+
+    #[test]
+    fn ascii_haystack_non_ascii_pattern() {
+        for matcher in [
+            IbMatcher::builder("拼音")
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .build(),
+            IbMatcher::builder("拼音")
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .graphemes(true)
+                .build(),
+            IbMatcher::builder("pinyin")
+                .plain(None)
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .build(),
+        ] {
+            for haystack in ["pinyin", "PinYin 1", "a\r\nb"] {
+                assert!(matcher.find(haystack).is_none(), "{haystack:?}");
+                assert!(matcher.test(haystack).is_none(), "{haystack:?}");
+                assert!(!matcher.is_match(haystack), "{haystack:?}");
+                assert_eq!(matcher.count(haystack), 0, "{haystack:?}");
+            }
+            // Non-ASCII haystacks are still matched
+            assert!(matcher.is_match("拼音"));
+        }
+
+        // Empty pattern always match
+        let matcher = IbMatcher::builder("")
+            .plain(None)
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert!(matcher.is_match("pinyin"));
+        assert_match(matcher.find("pinyin"), Some((0, 0)));
+    }
+
+    #[test]
+    fn is_match_prefix() {
+        let matcher = IbMatcher::builder("pinyinsousuo")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        for haystack in [
+            "",
+            "p",
+            "PIN",
+            "拼",
+            "拼音",
+            "拼y",
+            "拼音s",
+            "拼音搜",
+            "拼音搜索",
+            "拼音搜索引擎",
+            "拼音sousuo",
+        ] {
+            assert!(matcher.is_match_prefix(haystack), "{haystack}");
+        }
+        for haystack in ["x", "拼x", "拼写", "音", "pinyinx", "拼音搜x"] {
+            assert!(!matcher.is_match_prefix(haystack), "{haystack}");
+        }
+
+        // Mid-syllable
+        let matcher = IbMatcher::builder("xian")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert!(matcher.is_match_prefix("西"));
+        assert!(matcher.is_match_prefix("先"));
+        assert!(!matcher.is_match_prefix("西x"));
+
+        // Matching requires the end, but a prefix doesn't
+        let matcher = IbMatcher::builder("pinyin")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .ends_with(true)
+            .build();
+        assert!(matcher.is_match_prefix("拼"));
+        assert!(matcher.is_match_prefix("拼音"));
+        assert!(!matcher.is_match_prefix("拼音x"));
+
+        let matcher = IbMatcher::builder("pinyin")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .min_match_len(3)
+            .max_match_len(3)
+            .build();
+        assert!(matcher.is_match_prefix("拼"));
+        assert!(!matcher.is_match_prefix("拼音"));
+
+        // Empty pattern
+        let matcher = IbMatcher::builder("").build();
+        assert!(matcher.is_match_prefix(""));
+        assert!(matcher.is_match_prefix("abc"));
+        let matcher = IbMatcher::builder("")
+            .empty_pattern(EmptyBehavior::MatchNone)
+            .build();
+        assert!(!matcher.is_match_prefix(""));
+        assert!(!matcher.is_match_prefix("abc"));
+    }
+
+    #[test]
+    fn empty_pattern() {
+        let matcher = IbMatcher::builder("")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        for haystack in ["", "abc", "拼音"] {
+            assert!(matcher.is_match(haystack));
+            assert_match(matcher.find(haystack), Some((0, 0)));
+            assert_match(matcher.test(haystack), Some((0, 0)));
+        }
+        assert_eq!(matcher.count("a行"), 3);
+
+        let matcher = IbMatcher::builder("")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .empty_pattern(EmptyBehavior::MatchNone)
+            .build();
+        for haystack in ["", "abc", "拼音"] {
+            assert!(!matcher.is_match(haystack));
+            assert_match(matcher.find(haystack), None);
+            assert_match(matcher.test(haystack), None);
+            assert!(matcher.test_at(haystack, 0).is_none());
+            assert!(matcher.find_at(haystack, 0).is_none());
+        }
+        assert_eq!(matcher.count("a行"), 0);
+        assert_eq!(matcher.find_iter("a行").count(), 0);
+
+        // Also applies to plain-disabled and `MatchConfig`
+        let matcher = IbMatcher::builder("")
+            .plain(None)
+            .empty_pattern(EmptyBehavior::MatchNone)
+            .build();
+        assert!(!matcher.is_match("abc"));
+        let config = MatchConfig::builder()
+            .empty_pattern(EmptyBehavior::MatchNone)
+            .build();
+        assert!(!config.matcher("").is_match("abc"));
+
+        // Non-empty patterns are not affected
+        let matcher = IbMatcher::builder("py")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .empty_pattern(EmptyBehavior::MatchNone)
+            .build();
+        assert_match(matcher.find("拼音"), Some((0, 6)));
+    }
+
+    #[test]
+    fn stem_only() {
+        let matcher = IbMatcher::builder("pdf")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .stem_only(true)
+            .build();
+        assert!(!matcher.is_match("a.pdf"));
+        assert_match(matcher.find("pdf.pdf"), Some((0, 3)));
+        assert_match(matcher.find("a拼到放.pdf"), Some((1, 9)));
+        assert_eq!(matcher.count("pdfpdf.pdf"), 2);
+        assert_eq!(matcher.find_iter("pdf.pdf").count(), 1);
+        assert_match(matcher.test("pdf.pdf"), Some((0, 3)));
+        assert!(matcher.test(".pdf").is_none());
+        assert!(matcher.find_at("pdf.pdf", 1).is_none());
+        assert!(matcher.find_at("a.pdf", 2).is_none());
+        // No extension
+        assert!(matcher.is_match("pdf"));
+        assert!(matcher.is_match("pdf."));
+        // Leading dot
+        assert!(matcher.is_match(".pdf"));
+        assert!(matcher.is_match("a/.pdf"));
+        assert!(!matcher.is_match("a/.x.pdf"));
+        // Dots in directory names
+        assert!(matcher.is_match("a.pdf/pdf"));
+        assert!(matcher.is_match(r"a.b\pdf"));
+        assert!(!matcher.is_match("x.a/b.pdf"));
+        // Only the last extension
+        assert!(matcher.is_match("a.pdf.gz"));
+
+        let matcher = IbMatcher::builder("pdf")
+            .stem_only(true)
+            .ends_with(true)
+            .build();
+        assert!(matcher.is_match("apdf.txt"));
+        assert!(!matcher.is_match("apdfa.txt"));
+
+        // The pattern contains `.`
+        let matcher = IbMatcher::builder("a.p").stem_only(true).build();
+        assert!(matcher.is_match("a.pdf"));
+
+        #[cfg(feature = "encoding")]
+        {
+            use widestring::u16str;
+
+            let matcher = IbMatcher::builder(u16str!("pdf")).stem_only(true).build();
+            assert!(!matcher.is_match(u16str!("a.pdf")));
+            assert_match(matcher.find(u16str!("报pdf.pdf")), Some((1, 3)));
+            assert!(matcher.is_match(u16str!("a.b/pdf")));
+            assert!(matcher.is_match(u16str!(".pdf")));
+        }
+    }
+
     #[test]
     fn test() {
         let matcher = IbMatcher::builder("xing")
@@ -1150,7 +3837,7 @@ mod test {
         let matcher = IbMatcher::builder(u16str!("xing"))
             .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .build();
-        // assert_match(matcher.test(u16str!("")), None);
+        assert_match(matcher.test(u16str!("")), None);
 
         assert_match(matcher.test(u16str!("xing")), Some((0, 4)));
 
@@ -1170,6 +3857,18 @@ mod test {
             .build();
         assert_match(matcher.test(u16str!("")), Some((0, 0)));
         assert_match(matcher.test(u16str!("abc")), Some((0, 0)));
+        assert_match(matcher.find(u16str!("拼音")), Some((0, 0)));
+        assert!(matcher.is_match(u16str!("")));
+
+        let matcher = IbMatcher::builder(u16str!(""))
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .empty_pattern(EmptyBehavior::MatchNone)
+            .build();
+        assert_match(matcher.test(u16str!("")), None);
+        assert_match(matcher.test(u16str!("abc")), None);
+        assert_match(matcher.find(u16str!("拼音")), None);
+        assert!(!matcher.is_match(u16str!("")));
+        assert!(!matcher.is_match(u16str!("abc")));
 
         let matcher = IbMatcher::builder(u16str!("ke"))
             .pinyin(PinyinMatchConfig::notations(
@@ -1182,6 +3881,198 @@ mod test {
         assert_match(matcher.test(u16str!("凯尔")), Some((0, 2)));
         // AsciiFirstLetter is preferred
         assert_match(matcher.test(u16str!("柯尔")), Some((0, 2)));
+
+        // Not at a char boundary
+        assert_match(matcher.find_at(u16str!("𝄞凯尔"), 1), Some((2, 2)));
+        assert_match(matcher.find_at(u16str!("凯尔"), 1), None);
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn test_u16_surrogate_pair() {
+        use widestring::{u16str, U16String};
+
+        // 𪚥 (zhe) and 𤭢 (cei) are in CJK Extension B, i.e. surrogate pairs in UTF-16
+        let matcher = IbMatcher::builder(u16str!("zhe"))
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test(u16str!("𪚥")), Some((0, 2)));
+        assert_match(matcher.find(u16str!("a𪚥b")), Some((1, 2)));
+        assert_match(matcher.find(u16str!("𤭢𪚥")), Some((2, 2)));
+        // Not at a char boundary, rounded down to the start of the pair
+        assert_match(matcher.find_at(u16str!("𪚥𪚥"), 1), Some((0, 2)));
+        assert_match(matcher.find_at(u16str!("𪚥𪚥"), 2), Some((2, 2)));
+        // After an unpaired surrogate
+        let mut s = U16String::from_vec([0xD800]);
+        s.push(u16str!("𪚥"));
+        assert_match(matcher.find(s.as_ustr()), Some((1, 2)));
+
+        let matcher = IbMatcher::builder(u16str!("zc"))
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.find(u16str!("a𪚥𤭢")), Some((1, 4)));
+        let matcher = IbMatcher::builder(u16str!("zhecei"))
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.find(u16str!("a𪚥𤭢")), Some((1, 4)));
+        assert_match(matcher.find(u16str!("a𪚥b𤭢")), None);
+
+        // Literal
+        let matcher = IbMatcher::builder(u16str!("𝄞𪚥")).build();
+        assert_match(matcher.find(u16str!("a𝄞𪚥")), Some((1, 4)));
+        let matcher = IbMatcher::builder(u16str!("z𝄞"))
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.find(u16str!("𪚥𝄞")), Some((0, 4)));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn test_u32() {
+        use widestring::{u32str, U32Str};
+
+        let matcher = IbMatcher::builder(u32str!("xing"))
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test(u32str!("xing")), Some((0, 4)));
+        assert_match(matcher.test(u32str!("XiNG")), Some((0, 4)));
+        assert_match(matcher.test(u32str!("行")), Some((0, 1)));
+        assert_match(matcher.find(u32str!("𝄞行")), Some((1, 1)));
+        assert!(matcher.is_match(u32str!("银行")));
+        assert!(!matcher.is_match(u32str!("银")));
+
+        let matcher = IbMatcher::builder(u32str!("ke"))
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test(u32str!("ke")), Some((0, 2)));
+        assert_match(matcher.test(u32str!("科")), Some((0, 1)));
+        assert_match(matcher.test(u32str!("k鹅")), Some((0, 2)));
+        assert_match(matcher.test(u32str!("凯尔")), None);
+
+        let matcher = IbMatcher::builder(u32str!(""))
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test(u32str!("")), Some((0, 0)));
+        assert_match(matcher.test(u32str!("abc")), Some((0, 0)));
+
+        let matcher = IbMatcher::builder(u32str!("ke"))
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.test(u32str!("ke")), Some((0, 2)));
+        assert_match(matcher.test(u32str!("科")), Some((0, 1)));
+        assert_match(matcher.test(u32str!("k鹅")), Some((0, 2)));
+        assert_match(matcher.test(u32str!("凯尔")), Some((0, 2)));
+        // AsciiFirstLetter is preferred
+        assert_match(matcher.test(u32str!("柯尔")), Some((0, 2)));
+        assert_match(matcher.find_at(u32str!("𝄞凯尔"), 1), Some((1, 2)));
+        assert_match(matcher.find_at(u32str!("凯尔"), 1), None);
+
+        // From chars
+        let chars: Vec<char> = "拼音搜索Everything".chars().collect();
+        let matcher = IbMatcher::builder(u32str!("pysousuoeve"))
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.find(U32Str::from_char_slice(&chars)), Some((0, 7)));
+    }
+
+    #[test]
+    fn longest() {
+        let builder = || {
+            IbMatcher::builder("an").pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+        };
+        let matcher = builder().longest(true).build();
+        // "a n" instead of "an"
+        assert_match(matcher.find("安娜"), Some((0, 6)));
+        assert_match(matcher.test("安娜"), Some((0, 6)));
+        assert_match(matcher.find("是安娜"), Some((3, 6)));
+        assert_match(matcher.find_at("安娜安娜", 3), Some((6, 6)));
+        assert_eq!(
+            matcher
+                .find_iter("安娜安")
+                .map(|m| m.range())
+                .collect::<Vec<_>>(),
+            [0..6, 6..9]
+        );
+        // Not affected
+        assert!(matcher.is_match("安娜"));
+        assert_match(matcher.find("an"), Some((0, 2)));
+        assert_match(matcher.find("安"), Some((0, 3)));
+
+        // Bounded by max_match_len
+        let matcher = builder().longest(true).max_match_len(3).build();
+        assert_match(matcher.find("安娜"), Some((0, 3)));
+        // With starts_with
+        let matcher = builder().longest(true).starts_with(true).build();
+        assert_match(matcher.find("安娜"), Some((0, 6)));
+        assert_match(matcher.find("是安娜"), None);
+
+        // Multiple readings and notations
+        let builder = |pattern| {
+            IbMatcher::builder(pattern)
+                .pinyin(PinyinMatchConfig::notations(
+                    PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                ))
+                .is_pattern_partial(true)
+                .longest(true)
+                .build()
+        };
+        // "xi a n" instead of "xi an"
+        assert_match(builder("xian").find("西安娜"), Some((0, 9)));
+        assert_match(builder("xian").find("先"), Some((0, 3)));
+        assert_match(builder("xia").find("西安"), Some((0, 6)));
+        // Heteronyms: "xi an" instead of "xian"
+        assert_match(builder("xian").find("洗安"), Some((0, 6)));
+        // "m" instead of the partial "m(ing)"
+        assert_match(builder("m").find("明"), Some((0, 3)));
+        crate::assert_match!(builder("mi").find("明"), Some((0, 3)), partial);
+
+        // The same as the first match found
+        for (pattern, haystack) in [
+            ("an", "安娜"),
+            ("xian", "西安娜"),
+            ("xian", "洗安"),
+            ("m", "明"),
+        ] {
+            let matcher = IbMatcher::builder(pattern)
+                .pinyin(PinyinMatchConfig::notations(
+                    PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                ))
+                .is_pattern_partial(true)
+                .build();
+            assert_eq!(
+                matcher.find(haystack).map(|m| m.range()),
+                builder(pattern).find(haystack).map(|m| m.range()),
+                "{pattern} {haystack}"
+            );
+        }
+
+        // Non-partial is preferred
+        #[cfg(feature = "romaji")]
+        {
+            let romanizer = Default::default();
+            let builder = || {
+                IbMatcher::builder("da")
+                    .pinyin(PinyinMatchConfig::notations(
+                        PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                    ))
+                    .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+                    .mix_lang(true)
+            };
+            // Romaji "da(i)" is found first
+            crate::assert_match!(builder().build().find("大人"), Some((0, 3)), partial);
+            // Pinyin "da"
+            assert_match(builder().longest(true).build().find("大人"), Some((0, 3)));
+        }
     }
 
     #[cfg(feature = "unicode")]
@@ -1245,140 +4136,1004 @@ mod test {
         assert_match(matcher.test("xing"), None);
         assert_match(matcher.test("XiNG"), Some((0, 4)));
         assert_match(matcher.test("行"), Some((0, 3)));
+
+        // Case
+        let matcher = IbMatcher::builder("XING")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .case(Case::Sensitive)
+            .build();
+        assert_match(matcher.test("xing"), None);
+        assert_match(matcher.test("XING"), Some((0, 4)));
+        assert_match(matcher.test("行"), None);
+
+        let matcher = IbMatcher::builder("XING")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .case(Case::Insensitive)
+            .build();
+        assert_match(matcher.test("xing"), Some((0, 4)));
+        assert_match(matcher.test("行"), Some((0, 3)));
+
+        // Overrides
+        let matcher = IbMatcher::builder("XING")
+            .case_insensitive(false)
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .case(Case::Insensitive)
+            .build();
+        assert_match(matcher.test("xing"), None);
+        assert_match(matcher.test("行"), Some((0, 3)));
+
+        let config = MatchConfig::builder()
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .case_insensitive(false)
+                    .build(),
+            )
+            .case(Case::Insensitive)
+            .build();
+        assert_match(config.matcher("XING").test("xing"), Some((0, 4)));
+        assert_match(config.matcher("XING").test("行"), None);
+        #[cfg(any(feature = "regex-cp", feature = "regex-lita"))]
+        assert!(config.is_plain_case_insensitive());
+    }
+
+    #[test]
+    fn test_no_plain() {
+        let matcher = IbMatcher::builder("xing")
+            .plain(None)
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test(""), None);
+        assert_match(matcher.test("xing"), None);
+        assert_match(matcher.test("XiNG"), None);
+        assert_match(matcher.test("行"), Some((0, 3)));
+
+        let matcher = IbMatcher::builder("ke")
+            .plain(None)
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test("ke"), None);
+        assert_match(matcher.test("科"), Some((0, 3)));
+        assert_match(matcher.test("k鹅"), None);
+        assert_match(matcher.test("凯尔"), None);
+
+        let matcher = IbMatcher::builder("")
+            .plain(None)
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.test(""), Some((0, 0)));
+        assert_match(matcher.test("abc"), Some((0, 0)));
+
+        let matcher = IbMatcher::builder("ke")
+            .plain(None)
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.test("ke"), None);
+        assert_match(matcher.test("科"), Some((0, 3)));
+        assert_match(matcher.test("k鹅"), None);
+        assert_match(matcher.test("凯尔"), Some((0, 6)));
+        // AsciiFirstLetter is preferred
+        assert_match(matcher.test("柯尔"), Some((0, 6)));
+    }
+
+    #[test]
+    fn mix_lang() {
+        let pinyin =
+            PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter);
+        let romanizer = Default::default();
+        let romaji = RomajiMatchConfig::builder().romanizer(&romanizer).build();
+
+        let matcher = IbMatcher::builder("burua")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .romaji(romaji.clone())
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(matcher.find("让这个世界变得更好"), None);
+        let matcher = IbMatcher::builder("burua")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .romaji(romaji.clone())
+            .is_pattern_partial(true)
+            .mix_lang(true)
+            .build();
+        // b uru a(ra)
+        assert_match!(matcher.find("让这个世界变得更好"), Some((15, 9)), partial);
+
+        let matcher = IbMatcher::builder("shiraimu")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .romaji(romaji.clone())
+            .build();
+        assert_match!(matcher.find("持續狩獵史萊姆三百年"), None);
+        let matcher = IbMatcher::builder("shiraimu")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .romaji(romaji.clone())
+            .mix_lang(true)
+            .build();
+        // shi rai mu
+        assert_match!(matcher.find("持續狩獵史萊姆三百年"), Some((12, 9)));
+
+        let matcher = IbMatcher::builder("hatsuneouda")
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .analyze(true)
+            .build();
+        // hatsune ou da (ki yo yo)
+        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 12)));
+        let matcher = IbMatcher::builder("hatsuneoudaxi")
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .build();
+        assert_match!(matcher.find("初音殴打喜羊羊.gif"), None);
+        let matcher = IbMatcher::builder("hatsuneodxyy")
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .mix_lang(true)
+            .analyze(true)
+            .build();
+        // hatsune odxyy
+        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 21)));
+
+        // If set is_pattern_partial, the match will be partial as romaji is matched first.
+        // See lang_priority() for trying pinyin first.
+        let matcher = IbMatcher::builder("hatsuneodxyy")
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .mix_lang(true)
+            .analyze(true)
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 21)), partial);
+    }
+
+    #[test]
+    fn lang_priority() {
+        let pinyin =
+            PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter);
+        let romanizer = Default::default();
+        let romaji = RomajiMatchConfig::builder().romanizer(&romanizer).build();
+
+        let matcher = IbMatcher::builder("hatsuneodxyy")
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .mix_lang(true)
+            .is_pattern_partial(true)
+            .lang_priority(&[Lang::Literal, Lang::Pinyin, Lang::Romaji])
+            .build();
+        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 21)));
+
+        // Languages not in the list are not matched
+        let matcher = IbMatcher::builder("hatsune")
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .lang_priority(&[Lang::Literal, Lang::Pinyin])
+            .build();
+        assert_match!(matcher.find("初音"), None);
+        let matcher = IbMatcher::builder("py")
+            .pinyin(pinyin.shallow_clone())
+            .lang_priority(&[Lang::Pinyin])
+            .build();
+        assert_match!(matcher.find("拼音"), Some((0, 6)));
+        assert_match!(matcher.find("py"), None);
+
+        // Romaji is tried before pinyin by default
+        let config = MatchConfig::builder()
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .mix_lang(true)
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(
+            config.matcher("hatsuneodxyy").find("初音殴打喜羊羊.gif"),
+            Some((0, 21)),
+            partial
+        );
+        let config = MatchConfig::builder()
+            .pinyin(pinyin.shallow_clone())
+            .romaji(romaji.shallow_clone())
+            .mix_lang(true)
+            .is_pattern_partial(true)
+            .lang_priority(&[Lang::Pinyin, Lang::Romaji, Lang::Literal])
+            .build();
+        assert_match!(
+            config.matcher("hatsuneodxyy").find("初音殴打喜羊羊.gif"),
+            Some((0, 21))
+        );
+    }
+
+    #[test]
+    fn lang_fallback() {
+        let romanizer = Default::default();
+        let romaji = RomajiMatchConfig::builder().romanizer(&romanizer).build();
+        let builder = |lang_priority| {
+            IbMatcher::builder("shilai")
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .romaji(romaji.shallow_clone())
+                .lang_priority(lang_priority)
+        };
+
+        // shi lai (pinyin), after "shi" (romaji) fails
+        assert_match!(
+            builder(Lang::DEFAULT_PRIORITY).build().find("史萊"),
+            Some((0, 6))
+        );
+        assert_match!(
+            builder(Lang::DEFAULT_PRIORITY)
+                .lang_fallback(false)
+                .build()
+                .find("史萊"),
+            None
+        );
+        assert_match!(
+            builder(&[Lang::Pinyin, Lang::Romaji])
+                .lang_fallback(false)
+                .build()
+                .find("史萊"),
+            Some((0, 6))
+        );
+    }
+
+    #[test]
+    fn find() {
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.find(""), None);
+        assert_match(matcher.find("buxing"), Some((2, 4)));
+        assert_match(matcher.find("BuXiNG"), Some((2, 4)));
+        assert_match(matcher.find("不行"), Some((3, 3)));
+
+        let matcher = IbMatcher::builder("")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.find(""), Some((0, 0)));
+        assert_match(matcher.find("abc"), Some((0, 0)));
+    }
+
+    #[test]
+    fn rfind() {
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match(matcher.rfind(""), None);
+        assert_match(matcher.rfind("buxing"), Some((2, 4)));
+        assert_match(matcher.rfind("xing行不行"), Some((10, 3)));
+        assert_match(matcher.rfind("行xingx"), Some((3, 4)));
+        assert_match(matcher.rfind("不"), None);
+
+        // Overlapping candidates: the greatest start wins, not the last match of `find_iter()`
+        let matcher = IbMatcher::builder("aa").build();
+        assert_match(matcher.rfind("aaa"), Some((1, 2)));
+        assert_match(matcher.find_iter("aaa").last(), Some((0, 2)));
+        assert_match(matcher.rfind("aa的aaa"), Some((6, 2)));
+        assert_match(matcher.rfind("a"), None);
+
+        let matcher = IbMatcher::builder("pyin")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_match(matcher.rfind("拼音音"), Some((0, 6)));
+        assert_match(matcher.rfind("拼音拼音"), Some((6, 6)));
+
+        // Longer matches at the same start are still preferred
+        let matcher = IbMatcher::builder("an")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .longest(true)
+            .build();
+        assert_match(matcher.rfind("安娜是安娜"), Some((9, 6)));
+        assert_match(matcher.rfind("安娜是安"), Some((9, 3)));
+
+        // starts_with and ends_with
+        let matcher = IbMatcher::builder("aa").starts_with(true).build();
+        assert_match(matcher.rfind("aaa"), Some((0, 2)));
+        assert_match(matcher.rfind("baa"), None);
+        let matcher = IbMatcher::builder("aa").ends_with(true).build();
+        assert_match(matcher.rfind("aaab"), None);
+        assert_match(matcher.rfind("aa的aa"), Some((5, 2)));
+
+        let matcher = IbMatcher::builder("").build();
+        assert_match(matcher.rfind(""), Some((0, 0)));
+        assert_match(matcher.rfind("abc"), Some((3, 0)));
+        assert_match(matcher.rfind("拼音"), Some((6, 0)));
+    }
+
+    #[test]
+    fn find_bytes() {
+        let matcher = IbMatcher::builder("pinyin")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        let pinyin = "拼音".as_bytes();
+        let bytes = |parts: &[&[u8]]| parts.concat();
+        assert_match(matcher.find_bytes(pinyin), Some((0, 6)));
+        assert_match(matcher.find_bytes(&bytes(&[b"\xff", pinyin])), Some((1, 6)));
+        assert_match(
+            matcher.find_bytes(&bytes(&[b"a\xe6\x8b", pinyin, b"\xff"])),
+            Some((3, 6)),
+        );
+        assert_match(matcher.find_bytes(b"pin\xffyin"), None);
+        assert_match(matcher.find_bytes(b"pin\xe6\x8byin"), None);
+        assert_match(
+            matcher.find_bytes(&bytes(&[b"pin\xff", pinyin])),
+            Some((4, 6)),
+        );
+        // Not matched as U+FFFD
+        let matcher = IbMatcher::builder("\u{FFFD}").build();
+        assert_match(matcher.find_bytes(b"\xff"), None);
+        assert_match(matcher.find_bytes("\u{FFFD}".as_bytes()), Some((0, 3)));
+
+        let matcher = IbMatcher::builder("ab").starts_with(true).build();
+        assert_match(matcher.find_bytes(b"ab\xff"), Some((0, 2)));
+        assert_match(matcher.find_bytes(b"\xffab"), None);
+        let matcher = IbMatcher::builder("ab").ends_with(true).build();
+        assert_match(matcher.find_bytes(b"\xffab"), Some((1, 2)));
+        assert_match(matcher.find_bytes(b"ab\xff"), None);
+        assert_match(matcher.find_bytes(b"ab\xffab"), Some((3, 2)));
+        let matcher = IbMatcher::builder("ab").stem_only(true).build();
+        assert_match(matcher.find_bytes(b"\xff.ab"), None);
+        assert_match(matcher.find_bytes(b"ab\xff.txt"), Some((0, 2)));
+        assert_match(matcher.find_bytes(b"\xffab.ab"), Some((1, 2)));
+        assert_match(matcher.find_bytes(b"\xffa.ab"), None);
+
+        let matcher = IbMatcher::builder("").build();
+        assert_match(matcher.find_bytes(b"\xff"), Some((0, 0)));
+
+        // No panics
+        let matchers = [
+            IbMatcher::builder("a").build(),
+            IbMatcher::builder("zhong")
+                .pinyin(PinyinMatchConfig::notations(
+                    PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                ))
+                .is_pattern_partial(true)
+                .stem_only(true)
+                .build(),
+            IbMatcher::builder("z.")
+                .pinyin(PinyinMatchConfig::notations(
+                    PinyinNotation::AsciiFirstLetter,
+                ))
+                .ends_with(true)
+                .build(),
+        ];
+        let units: &[u8] = &[
+            b'a', b'.', b'/', 0x80, 0xbf, 0xc0, 0xe4, 0xb8, 0xad, 0xf0, 0xff,
+        ];
+        for &a in units {
+            for &b in units {
+                for &c in units {
+                    let haystack = [a, b, c, 0xe4, 0xb8, 0xad];
+                    for matcher in &matchers {
+                        if let Some(m) = matcher.find_bytes(&haystack) {
+                            assert!(str::from_utf8(&haystack[m.range()]).is_ok());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn find_iter() {
+        let ranges = |matcher: &IbMatcher, haystack: &str| {
+            matcher
+                .find_iter(haystack)
+                .map(|m| (m.start(), m.end()))
+                .collect::<Vec<_>>()
+        };
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_eq!(ranges(&matcher, ""), [(0, 0); 0]);
+        // ASCII-only
+        assert_eq!(
+            ranges(&matcher, "xingXINGbuxing"),
+            [(0, 4), (4, 8), (10, 14)]
+        );
+        assert_eq!(ranges(&matcher, "xinxing"), [(3, 7)]);
+        assert_eq!(ranges(&matcher, "行不行, xing"), [(0, 3), (6, 9), (11, 15)]);
+        assert_eq!(ranges(&matcher, "不可"), [(0, 0); 0]);
+
+        // Non-overlapping
+        let matcher = IbMatcher::builder("aa").build();
+        assert_eq!(ranges(&matcher, "aaaaa"), [(0, 2), (2, 4)]);
+        assert_eq!(ranges(&matcher, "aaaaa哈"), [(0, 2), (2, 4)]);
+
+        // Empty matches
+        let matcher = IbMatcher::builder("")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_eq!(ranges(&matcher, ""), [(0, 0)]);
+        assert_eq!(ranges(&matcher, "ab"), [(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(ranges(&matcher, "a行"), [(0, 0), (1, 1), (4, 4)]);
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .starts_with(true)
+            .build();
+        assert_eq!(ranges(&matcher, "xingxing"), [(0, 4)]);
+        assert_eq!(ranges(&matcher, "行行"), [(0, 3)]);
+        assert_eq!(ranges(&matcher, "不行"), [(0, 0); 0]);
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .ends_with(true)
+            .build();
+        assert_eq!(ranges(&matcher, "xingxing"), [(4, 8)]);
+        assert_eq!(ranges(&matcher, "行行"), [(3, 6)]);
+    }
+
+    #[test]
+    fn find_overlapping_iter() {
+        let ranges = |matcher: &IbMatcher, haystack: &str| {
+            matcher
+                .find_overlapping_iter(haystack)
+                .map(|m| (m.start(), m.end()))
+                .collect::<Vec<_>>()
+        };
+
+        let matcher = IbMatcher::builder("aa").build();
+        assert_eq!(ranges(&matcher, "aaa"), [(0, 2), (1, 3)]);
+        assert_eq!(
+            ranges(&matcher, "aaaa哈aa"),
+            [(0, 2), (1, 3), (2, 4), (7, 9)]
+        );
+        assert_eq!(ranges(&matcher, "a"), [(0, 0); 0]);
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_eq!(ranges(&matcher, "xingxing"), [(0, 4), (4, 8)]);
+        assert_eq!(ranges(&matcher, "行星ing"), [(0, 3), (3, 9)]);
+        assert_eq!(ranges(&matcher, "行ing"), [(0, 6)]);
+
+        let matcher = IbMatcher::builder("")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_eq!(ranges(&matcher, "a行"), [(0, 0), (1, 1), (4, 4)]);
+
+        let matcher = IbMatcher::builder("aa").starts_with(true).build();
+        assert_eq!(ranges(&matcher, "aaa"), [(0, 2)]);
+        let matcher = IbMatcher::builder("aa").ends_with(true).build();
+        assert_eq!(ranges(&matcher, "aaa"), [(1, 3)]);
+
+        #[cfg(feature = "encoding")]
+        {
+            use widestring::u16str;
+
+            let matcher = IbMatcher::builder(u16str!("aa")).build();
+            assert_eq!(
+                matcher
+                    .find_overlapping_iter(u16str!("aa哈aaa"))
+                    .map(|m| m.range())
+                    .collect::<Vec<_>>(),
+                [0..2, 3..5, 4..6]
+            );
+        }
     }
 
     #[test]
-    fn test_no_plain() {
+    fn count() {
+        let counts = |matcher: &IbMatcher, haystack: &str| {
+            (matcher.count(haystack), matcher.find_iter(haystack).count())
+        };
+
         let matcher = IbMatcher::builder("xing")
-            .plain(None)
             .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .build();
-        assert_match(matcher.test(""), None);
-        assert_match(matcher.test("xing"), None);
-        assert_match(matcher.test("XiNG"), None);
-        assert_match(matcher.test("行"), Some((0, 3)));
+        assert_eq!(counts(&matcher, ""), (0, 0));
+        assert_eq!(counts(&matcher, "xingXINGxin"), (2, 2));
+        assert_eq!(counts(&matcher, "行不行, xing"), (3, 3));
 
-        let matcher = IbMatcher::builder("ke")
-            .plain(None)
+        // Non-overlapping
+        let matcher = IbMatcher::builder("aa").build();
+        assert_eq!(counts(&matcher, "aaaaa"), (2, 2));
+        assert_eq!(counts(&matcher, "aaaaa哈"), (2, 2));
+
+        // Non-ASCII pattern on ASCII haystacks
+        let matcher = IbMatcher::builder("行").build();
+        assert_eq!(counts(&matcher, "xing"), (0, 0));
+
+        // Empty matches
+        let matcher = IbMatcher::builder("").build();
+        assert_eq!(counts(&matcher, ""), (1, 1));
+        assert_eq!(counts(&matcher, "a行"), (3, 3));
+
+        let matcher = IbMatcher::builder("xing")
             .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .starts_with(true)
             .build();
-        assert_match(matcher.test("ke"), None);
-        assert_match(matcher.test("科"), Some((0, 3)));
-        assert_match(matcher.test("k鹅"), None);
-        assert_match(matcher.test("凯尔"), None);
+        assert_eq!(counts(&matcher, "xingxing"), (1, 1));
+        assert_eq!(counts(&matcher, "不行"), (0, 0));
+        assert_eq!(
+            matcher.count(Input::builder("xing").no_start(true).build()),
+            0
+        );
 
-        let matcher = IbMatcher::builder("")
-            .plain(None)
+        let matcher = IbMatcher::builder("xing")
             .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .ends_with(true)
             .build();
-        assert_match(matcher.test(""), Some((0, 0)));
-        assert_match(matcher.test("abc"), Some((0, 0)));
+        assert_eq!(counts(&matcher, "xingxing"), (1, 1));
+        assert_eq!(counts(&matcher, "xingx"), (0, 0));
+    }
 
-        let matcher = IbMatcher::builder("ke")
-            .plain(None)
+    #[test]
+    fn match_len() {
+        let matcher = |min, max| {
+            IbMatcher::builder("an")
+                .pinyin(PinyinMatchConfig::notations(
+                    PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                ))
+                .min_match_len(min)
+                .maybe_max_match_len(max)
+                .build()
+        };
+        // an / a n
+        assert_match(matcher(0, Some(3)).find("安娜"), Some((0, 3)));
+        assert_match(matcher(4, None).find("安娜"), Some((0, 6)));
+        assert_match(matcher(4, Some(5)).find("安娜"), None);
+        assert_match(matcher(4, None).test("安娜"), Some((0, 6)));
+
+        // ASCII-only haystacks
+        assert_match(matcher(3, None).find("an"), None);
+        assert!(!matcher(3, None).is_match("an"));
+        assert_eq!(matcher(0, Some(1)).count("an an"), 0);
+        assert_match(matcher(3, None).find("an安"), Some((2, 3)));
+        assert!(matcher(0, Some(2)).is_match("an"));
+
+        let matcher = IbMatcher::builder("pyss")
             .pinyin(PinyinMatchConfig::notations(
-                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                PinyinNotation::AsciiFirstLetter,
             ))
+            .max_match_len(3 * 3)
             .build();
-        assert_match(matcher.test("ke"), None);
-        assert_match(matcher.test("科"), Some((0, 3)));
-        assert_match(matcher.test("k鹅"), None);
-        assert_match(matcher.test("凯尔"), Some((0, 6)));
-        // AsciiFirstLetter is preferred
-        assert_match(matcher.test("柯尔"), Some((0, 6)));
+        assert_match(matcher.find("拼音搜索"), None);
+        assert_match(matcher.find("拼音搜索, pyss"), Some((14, 4)));
+
+        // Partial matches
+        let matcher = |max| {
+            IbMatcher::builder("pinyi")
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .is_pattern_partial(true)
+                .max_match_len(max)
+                .build()
+        };
+        assert_match!(matcher(6).find("拼音"), Some((0, 6)), partial);
+        assert_match(matcher(5).find("拼音"), None);
+
+        // Empty pattern
+        let matcher = IbMatcher::builder("").min_match_len(1).build();
+        assert_match(matcher.find("abc"), Some((0, 0)));
+        assert!(matcher.is_match("abc"));
     }
 
     #[test]
-    fn mix_lang() {
-        let pinyin =
-            PinyinMatchConfig::notations(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter);
-        let romanizer = Default::default();
-        let romaji = RomajiMatchConfig::builder().romanizer(&romanizer).build();
-
-        let matcher = IbMatcher::builder("burua")
+    fn find_best() {
+        let matcher = IbMatcher::builder("an")
             .pinyin(PinyinMatchConfig::notations(
                 PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
             ))
-            .romaji(romaji.clone())
+            .build();
+        let best = |haystack| {
+            matcher
+                .find_best(haystack)
+                .map(|m| (m.as_match().range(), m.stats(), m.score()))
+        };
+        let notation = |count, notation| match count {
+            0 => PinyinNotation::empty(),
+            _ => notation,
+        };
+        let stats = |plain, pinyin, pinyin_first_letter| MatchStats {
+            plain,
+            pinyin,
+            pinyin_first_letter,
+            romaji: 0,
+            pinyin_notations: notation(pinyin, PinyinNotation::Ascii)
+                | notation(pinyin_first_letter, PinyinNotation::AsciiFirstLetter),
+        };
+        assert_eq!(best(""), None);
+        assert_eq!(best("安娜"), Some((0..3, stats(0, 1, 0), -1)));
+        assert_eq!(best("娜安娜"), Some((3..6, stats(0, 1, 0), -1)));
+        assert_eq!(best("AN"), Some((0..2, stats(2, 0, 0), 2)));
+        assert_eq!(best("a娜"), Some((0..4, stats(1, 0, 1), -1)));
+        assert_eq!(best("bn"), None);
+
+        let m = matcher
+            .find_best_by("安娜", |m, stats| m.len() as i32 + stats.pinyin as i32)
+            .unwrap();
+        assert_eq!(m.as_match().range(), 0..6);
+        assert_eq!(m.stats(), stats(0, 0, 2));
+        assert_eq!(m.score(), 6);
+
+        // Partial
+        let matcher = IbMatcher::builder("pinyi")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .is_pattern_partial(true)
             .build();
-        assert_match!(matcher.find("让这个世界变得更好"), None);
-        let matcher = IbMatcher::builder("burua")
+        let m = matcher.find_best("拼音").unwrap();
+        assert_match!(Some(m.as_match().clone()), Some((0, 6)), partial);
+        assert_eq!(m.score(), -4);
+
+        // Romaji
+        let matcher = IbMatcher::builder("konosuba")
+            .romaji(RomajiMatchConfig::default())
+            .build();
+        let m = matcher.find_best("『この素晴らしい世界に祝福を』").unwrap();
+        assert_eq!(m.as_match().start(), 3);
+        assert_eq!(m.stats().plain, 0);
+        assert!(m.stats().romaji >= 3);
+    }
+
+    #[test]
+    fn stats() {
+        let stats = |matcher: &IbMatcher, haystack: &str| {
+            let m = matcher.find(haystack).unwrap();
+            matcher.stats(haystack, &m)
+        };
+
+        let matcher = IbMatcher::builder("pyss")
             .pinyin(PinyinMatchConfig::notations(
                 PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
             ))
-            .romaji(romaji.clone())
+            .build();
+        let s = stats(&matcher, "pyss");
+        assert_eq!(s.kind(), MatchKind::Plain);
+        assert_eq!(s.plain, 4);
+        assert_eq!(s.pinyin_notations, PinyinNotation::empty());
+        let s = stats(&matcher, "拼音搜索");
+        assert_eq!(s.kind(), MatchKind::Pinyin);
+        assert_eq!(s.pinyin_first_letter, 4);
+        assert_eq!(s.pinyin_notations, PinyinNotation::AsciiFirstLetter);
+        let s = stats(&matcher, "拼yss");
+        assert_eq!(s.kind(), MatchKind::Pinyin);
+        assert_eq!((s.plain, s.pinyin_first_letter), (3, 1));
+
+        // Partial pattern
+        let matcher = IbMatcher::builder("pinyi")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .is_pattern_partial(true)
-            .mix_lang(true)
             .build();
-        // b uru a(ra)
-        assert_match!(matcher.find("让这个世界变得更好"), Some((15, 9)), partial);
+        let s = stats(&matcher, "拼音");
+        assert_eq!(s.pinyin, 2);
+        assert_eq!(s.pinyin_notations, PinyinNotation::Ascii);
 
-        let matcher = IbMatcher::builder("shiraimu")
-            .pinyin(PinyinMatchConfig::notations(
-                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
-            ))
-            .romaji(romaji.clone())
+        // Empty pattern
+        let matcher = IbMatcher::builder("").build();
+        assert_eq!(stats(&matcher, "abc"), MatchStats::default());
+
+        #[cfg(feature = "pinyin-jyutping")]
+        {
+            let matcher = IbMatcher::builder("jam")
+                .pinyin(PinyinMatchConfig::notations(
+                    PinyinNotation::Ascii | PinyinNotation::Jyutping,
+                ))
+                .build();
+            let s = stats(&matcher, "音");
+            assert_eq!(s.kind(), MatchKind::Pinyin);
+            assert_eq!(s.pinyin_notations, PinyinNotation::Jyutping);
+        }
+
+        // Romaji
+        let matcher = IbMatcher::builder("konosuba")
+            .romaji(RomajiMatchConfig::default())
             .build();
-        assert_match!(matcher.find("持續狩獵史萊姆三百年"), None);
-        let matcher = IbMatcher::builder("shiraimu")
-            .pinyin(PinyinMatchConfig::notations(
-                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
-            ))
-            .romaji(romaji.clone())
-            .mix_lang(true)
+        let s = stats(&matcher, "『この素晴らしい世界に祝福を』");
+        assert_eq!(s.kind(), MatchKind::Romaji);
+        assert_eq!(s.plain, 0);
+    }
+
+    #[test]
+    fn find_at() {
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .build();
-        // shi rai mu
-        assert_match!(matcher.find("持續狩獵史萊姆三百年"), Some((12, 9)));
+        assert_match(matcher.find_at("行xing", 0), Some((0, 3)));
+        assert_match(matcher.find_at("行xing", 3), Some((3, 4)));
+        // Not at a char boundary
+        assert_match(matcher.find_at("行xing", 1), Some((0, 3)));
+        assert_match(matcher.find_at("行xing", 4), None);
+        assert_match(matcher.find_at("行xing", 100), None);
+        // ASCII-only suffix
+        assert_match(matcher.find_at("行 xing", 3), Some((4, 4)));
 
-        let matcher = IbMatcher::builder("hatsuneouda")
-            .pinyin(pinyin.shallow_clone())
-            .romaji(romaji.shallow_clone())
-            .analyze(true)
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .starts_with(true)
             .build();
-        // hatsune ou da (ki yo yo)
-        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 12)));
-        let matcher = IbMatcher::builder("hatsuneoudaxi")
-            .pinyin(pinyin.shallow_clone())
-            .romaji(romaji.shallow_clone())
+        assert_match(matcher.find_at("行xing", 0), Some((0, 3)));
+        assert_match(matcher.find_at("行xing", 3), None);
+
+        let matcher = IbMatcher::builder("").build();
+        assert_match(matcher.find_at("ab", 1), Some((1, 0)));
+        assert_match(matcher.find_at("ab", 2), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_at() {
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .build();
-        assert_match!(matcher.find("初音殴打喜羊羊.gif"), None);
-        let matcher = IbMatcher::builder("hatsuneodxyy")
-            .pinyin(pinyin.shallow_clone())
-            .romaji(romaji.shallow_clone())
-            .mix_lang(true)
-            .analyze(true)
+        assert_match(matcher.test_at("行xing", 0), Some((0, 3)));
+        assert_match(matcher.test_at("行xing", 3), Some((3, 4)));
+        // Not at a char boundary
+        assert_match(matcher.test_at("行xing", 1), Some((0, 3)));
+        assert_match(matcher.test_at("行xing", 4), None);
+        assert_match(matcher.test_at("行xing", 100), None);
+        assert_match(matcher.test_at("不行", 0), None);
+        assert!(matcher.is_match_at("不行", 3));
+        assert!(!matcher.is_match_at("不行", 0));
+        // ASCII-only suffix, which is anchored too
+        assert_match(matcher.test_at("行 xing", 4), Some((4, 4)));
+        assert_match(matcher.test_at("行 xing", 3), None);
+        assert!(!matcher.is_match_at("行 xing", 3));
+        assert_match(matcher.test_at("xing行", 0), Some((0, 4)));
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .starts_with(true)
             .build();
-        // hatsune odxyy
-        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 21)));
+        assert_match(matcher.test_at("行xing", 0), Some((0, 3)));
+        assert_match(matcher.test_at("行xing", 3), None);
+        assert!(!matcher.is_match_at("行xing", 3));
 
-        // If set is_pattern_partial, the match will be partial as romaji is matched first.
-        // TODO: A option to try all cases?
-        let matcher = IbMatcher::builder("hatsuneodxyy")
-            .pinyin(pinyin.shallow_clone())
-            .romaji(romaji.shallow_clone())
-            .mix_lang(true)
-            .analyze(true)
-            .is_pattern_partial(true)
+        // The stem of the whole haystack
+        let matcher = IbMatcher::builder("b")
+            .stem_only(true)
+            .ends_with(true)
             .build();
-        assert_match!(matcher.find("初音殴打喜羊羊.gif"), Some((0, 21)), partial);
+        assert_match(matcher.test_at("a.b.c", 2), Some((2, 1)));
+        assert!(matcher.is_match_at("a.b.c", 2));
+        assert_match(matcher.test_at("a.b.c", 4), None);
+
+        let matcher = IbMatcher::builder("").build();
+        assert_match(matcher.test_at("ab", 1), Some((1, 0)));
+        assert!(matcher.is_match_at("ab", 2));
     }
 
     #[test]
-    fn find() {
+    fn input() {
         let matcher = IbMatcher::builder("xing")
             .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
             .build();
-        assert_match(matcher.find(""), None);
-        assert_match(matcher.find("buxing"), Some((2, 4)));
-        assert_match(matcher.find("BuXiNG"), Some((2, 4)));
-        assert_match(matcher.find("不行"), Some((3, 3)));
+        let input = || Input::new("行xing行");
+        assert_match(matcher.find(input().range(3..)), Some((3, 4)));
+        assert_match(matcher.find(input().range(4..7)), None);
+        assert_match(matcher.rfind(input().range(..7)), Some((3, 4)));
+        assert_match(matcher.test(input().range(7..)), Some((7, 3)));
+        assert!(!matcher.is_match(input().range(4..7)));
+        // In the whole haystack, not relative to the previous range
+        assert_match(matcher.find(input().range(3..).range(4..)), Some((7, 3)));
+        assert_match(matcher.find(input().range(7..).range(3..)), Some((3, 4)));
+        assert_eq!(input().range(3..).range(4..).start(), 4);
+        assert_eq!(input().range(3..7).end(), 7);
+        let a = IbMatcher::builder("a").build();
+        assert_match(
+            a.find(Input::new("xxaxa").range(3..).range(1..)),
+            Some((2, 1)),
+        );
 
-        let matcher = IbMatcher::builder("")
+        let matches: Vec<_> = matcher
+            .find_iter(Input::new("行行行").range(3..))
+            .map(|m| m.range())
+            .collect();
+        assert_eq!(matches, [3..6, 6..9]);
+        assert_eq!(matcher.count(Input::new("行行行").range(3..6)), 1);
+        assert_eq!(matcher.count(Input::new("xingxing").range(1..)), 1);
+
+        let m = matcher.find_best(Input::new("不行").range(3..)).unwrap();
+        assert_eq!(m.as_match().range(), 3..6);
+        let (m, highlights) = matcher
+            .find_highlights(Input::new("不行").range(3..))
+            .unwrap();
+        assert_eq!(m.range(), 3..6);
+        assert_eq!(highlights, [3..6, 3..6, 3..6, 3..6]);
+        let mut matches = Vec::new();
+        matcher.test_and_try_for_each(input().range(3..), &mut |m| {
+            matches.push((m.start(), m.end()));
+            None::<()>
+        });
+        assert_eq!(matches, [(3, 7)]);
+
+        // Anchored
+        let input = |haystack| Input::new(haystack).anchored(true);
+        assert_match(matcher.find(input("不行")), None);
+        assert!(!matcher.is_match(input("不行")));
+        assert_match(matcher.find(input("不行").range(3..)), Some((3, 3)));
+        assert!(matcher.is_match(input("不行").range(3..)));
+        assert_match(matcher.rfind(input("行行")), Some((0, 3)));
+        assert_eq!(matcher.find_iter(input("行行")).count(), 1);
+        assert_eq!(matcher.count(input("xingxing")), 1);
+        assert_eq!(matcher.count(input("a xing")), 0);
+        assert!(matcher.find_best(input("不行")).is_none());
+        let m = matcher.find_best(input("不行").range(3..)).unwrap();
+        assert_eq!(m.as_match().range(), 3..6);
+
+        // The start of the range is not the start of the haystack
+        let matcher = IbMatcher::builder("xing")
             .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .starts_with(true)
             .build();
-        assert_match(matcher.find(""), Some((0, 0)));
-        assert_match(matcher.find("abc"), Some((0, 0)));
+        assert_match(matcher.find(Input::new("行行").range(..3)), Some((0, 3)));
+        assert_match(matcher.find(Input::new("行行").range(3..)), None);
+        assert_match(matcher.find(input("行行").range(3..)), None);
+    }
+
+    #[test]
+    fn replace() {
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert!(matches!(
+            matcher.replace_all("abc", "*"),
+            Cow::Borrowed("abc")
+        ));
+        assert_eq!(matcher.replace("不行xing", "*"), "不*xing");
+        assert_eq!(matcher.replace_all("不行xing", "*"), "不**");
+        assert_eq!(matcher.replace_all("行", ""), "");
+        assert_eq!(
+            matcher.replace_with("行行行", 2, |m, s| format!("[{}:{s}]", m.start())),
+            "[0:行][3:行]行"
+        );
+
+        // Empty matches
+        let matcher = IbMatcher::builder("").build();
+        assert_eq!(matcher.replace_all("", "*"), "*");
+        assert_eq!(matcher.replace_all("a行", "*"), "*a*行*");
+        assert_eq!(matcher.replace("a行", "*"), "*a行");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn matches_reader() {
+        use std::io::BufReader;
+
+        let matches = |matcher: &IbMatcher, s: &str, max_line_len| {
+            let mut matches = Vec::new();
+            matcher
+                .matches_reader(
+                    BufReader::with_capacity(2, s.as_bytes()),
+                    max_line_len,
+                    |line_no, m| {
+                        matches.push((line_no, m.start(), m.end()));
+                        None::<()>
+                    },
+                )
+                .map(|_| matches)
+        };
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_eq!(matches(&matcher, "", 0).unwrap(), []);
+        assert_eq!(
+            matches(&matcher, "不行\n\nxing\r\n行行", 0).unwrap(),
+            [(1, 3, 6), (3, 8, 12), (4, 14, 17), (4, 17, 20)]
+        );
+        assert_eq!(
+            matches(&matcher, "不行\n\nxing\r\n行行\n", 0).unwrap(),
+            [(1, 3, 6), (3, 8, 12), (4, 14, 17), (4, 17, 20)]
+        );
+
+        // Stop early
+        let mut count = 0;
+        let result = matcher.matches_reader("行\n行\n行".as_bytes(), 0, |line_no, _| {
+            count += 1;
+            (line_no == 2).then_some(line_no)
+        });
+        assert_eq!(result.unwrap(), Some(2));
+        assert_eq!(count, 2);
+
+        // Long lines
+        let matcher = IbMatcher::builder("xingxing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_eq!(
+            matches(&matcher, "a行行\n行行", 0).unwrap(),
+            [(1, 1, 7), (2, 8, 14)]
+        );
+        assert_eq!(
+            matches(&matcher, "a行行\n行行", 7).unwrap(),
+            [(1, 1, 7), (2, 8, 14)]
+        );
+        assert_eq!(matches(&matcher, "a行行\n行行", 4).unwrap(), []);
+
+        // Invalid UTF-8
+        let mut lines = b"\xff\n".as_slice();
+        let result = matcher.matches_reader(&mut lines, 0, |_, _| Some(()));
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn highlights() {
+        let highlights = |matcher: &IbMatcher, haystack: &str| {
+            matcher
+                .find_highlights(haystack)
+                .map(|(_, highlights)| {
+                    highlights
+                        .into_iter()
+                        .map(|r| (r.start, r.end))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap()
+        };
+
+        let matcher = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_eq!(
+            highlights(&matcher, "buxing"),
+            [(2, 3), (3, 4), (4, 5), (5, 6)]
+        );
+        assert_eq!(highlights(&matcher, "不行"), [(3, 6); 4]);
+
+        let matcher = IbMatcher::builder("pysousuo")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        assert_eq!(
+            highlights(&matcher, "拼音搜索"),
+            [
+                (0, 3),
+                (3, 6),
+                (6, 9),
+                (6, 9),
+                (6, 9),
+                (9, 12),
+                (9, 12),
+                (9, 12)
+            ]
+        );
+        // Mixed with literal
+        assert_eq!(
+            highlights(&matcher, "拼y搜suo"),
+            [
+                (0, 3),
+                (3, 4),
+                (4, 7),
+                (4, 7),
+                (4, 7),
+                (7, 8),
+                (8, 9),
+                (9, 10)
+            ]
+        );
+
+        // Partial
+        let matcher = IbMatcher::builder("pinyi")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .is_pattern_partial(true)
+            .build();
+        assert_eq!(
+            highlights(&matcher, "1拼音"),
+            [(1, 4), (1, 4), (1, 4), (4, 7), (4, 7)]
+        );
+
+        // Romaji word
+        let romanizer = Default::default();
+        let matcher = IbMatcher::builder("kyouga")
+            .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+            .build();
+        assert_eq!(
+            highlights(&matcher, "今日が"),
+            [(0, 6), (0, 6), (0, 6), (0, 6), (6, 9), (6, 9)]
+        );
+
+        // Empty pattern
+        let matcher = IbMatcher::builder("").build();
+        assert_eq!(highlights(&matcher, "abc"), [(0, 0); 0]);
     }
 
     #[test]
@@ -1606,4 +5361,246 @@ mod test {
         // AsciiFirstLetter is preferred
         assert_match!(matcher.find("1柯尔"), None);
     }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn normalize() {
+        // か + U+3099 (combining voiced sound mark)
+        let matcher = IbMatcher::builder("が").build();
+        assert_match!(matcher.find("かか\u{3099}"), None);
+        let matcher = IbMatcher::builder("が")
+            .normalize(Normalization::Nfc)
+            .build();
+        assert_match!(matcher.find("かか\u{3099}"), Some((3, 6)));
+        assert_match!(matcher.test("か\u{3099}か"), Some((0, 6)));
+        assert_match!(matcher.find("がか"), Some((0, 3)));
+        assert!(matcher.is_match("か\u{3099}"));
+        assert!(!matcher.is_match("か"));
+
+        // The pattern is also normalized
+        let matcher = IbMatcher::builder("か\u{3099}")
+            .normalize(Normalization::Nfc)
+            .build();
+        assert_match!(matcher.find("aが"), Some((1, 3)));
+
+        // Compatibility
+        let matcher = IbMatcher::builder("abc")
+            .normalize(Normalization::Nfc)
+            .build();
+        assert_match!(matcher.find("ＡＢＣ"), None);
+        let matcher = IbMatcher::builder("abc")
+            .normalize(Normalization::Nfkc)
+            .build();
+        assert_match!(matcher.find("1ＡＢＣ"), Some((1, 9)));
+        let matcher = IbMatcher::builder("ＡＢＣ")
+            .normalize(Normalization::Nfkc)
+            .build();
+        assert_match!(matcher.find("1abc"), Some((1, 3)));
+        // Longer after normalization
+        let matcher = IbMatcher::builder("株式会社")
+            .normalize(Normalization::Nfkc)
+            .starts_with(true)
+            .build();
+        assert_match!(matcher.test("㍿"), Some((0, 3)));
+
+        // Pinyin and romaji
+        let matcher = IbMatcher::builder("pinyin")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .normalize(Normalization::Nfkc)
+            .build();
+        assert_match!(matcher.find("ＰＩＮ音"), Some((0, 12)));
+        let romanizer = Default::default();
+        let matcher = IbMatcher::builder("gakkou")
+            .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+            .normalize(Normalization::Nfc)
+            .build();
+        assert_match!(matcher.find("1か\u{3099}っこう"), Some((1, 15)));
+
+        let highlights: Vec<_> = matcher.find_highlights("か\u{3099}っこう").unwrap().1;
+        assert_eq!(highlights, [0..6, 0..6, 6..12, 6..12, 6..12, 12..15]);
+
+        // Matches in the middle of a normalized char are extended
+        let matcher = IbMatcher::builder("fi")
+            .normalize(Normalization::Nfkc)
+            .build();
+        assert_match!(matcher.find("aﬁ"), Some((1, 3)));
+        let matcher = IbMatcher::builder("f")
+            .normalize(Normalization::Nfkc)
+            .build();
+        assert_match!(matcher.find("aﬁ"), Some((1, 3)));
+        assert_eq!(
+            matcher
+                .find_iter("ﬁﬀ")
+                .map(|m| m.range())
+                .collect::<Vec<_>>(),
+            [0..3, 3..6]
+        );
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn graphemes() {
+        // e + U+0301 (combining acute accent)
+        let matcher = IbMatcher::builder("e").build();
+        assert_match!(matcher.find("e\u{301}x"), Some((0, 1)));
+        assert_match!(matcher.test("e\u{301}"), Some((0, 1)));
+        let matcher = IbMatcher::builder("e").graphemes(true).build();
+        assert_match!(matcher.find("e\u{301}x"), None);
+        assert_match!(matcher.find("e\u{301}e"), Some((3, 1)));
+        assert_match!(matcher.test("e\u{301}"), None);
+        assert!(!matcher.is_match("e\u{301}"));
+        let matcher = IbMatcher::builder("e\u{301}").graphemes(true).build();
+        assert_match!(matcher.find("xe\u{301}"), Some((1, 3)));
+        // Starts in the middle of a cluster
+        let matcher = IbMatcher::builder("\u{301}").graphemes(true).build();
+        assert_match!(matcher.find("e\u{301}"), None);
+
+        // Emoji ZWJ sequence
+        let matcher = IbMatcher::builder("👨").build();
+        assert_match!(matcher.find("👨‍👩‍👧"), Some((0, 4)));
+        let matcher = IbMatcher::builder("👨").graphemes(true).build();
+        assert_match!(matcher.find("👨‍👩‍👧"), None);
+        assert_match!(matcher.find("👨‍👩‍👧👨"), Some((18, 4)));
+
+        // CRLF
+        let matcher = IbMatcher::builder("a\r").graphemes(true).build();
+        assert_match!(matcher.find("a\r\n"), None);
+        assert_match!(matcher.find("a\ra"), Some((0, 2)));
+        let matcher = IbMatcher::builder("\na").graphemes(true).build();
+        assert_match!(matcher.find("\r\na"), None);
+        assert_match!(matcher.test("\r\na"), None);
+
+        // Highlights
+        let matcher = IbMatcher::builder("e\u{301}x").graphemes(true).build();
+        let highlights: Vec<_> = matcher.find_highlights("e\u{301}x").unwrap().1;
+        assert_eq!(highlights, [0..3, 0..3, 3..4]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_filter() {
+        fn assert_sync<T: Sync>(_: &T) {}
+
+        let matcher = IbMatcher::builder("pyss")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::AsciiFirstLetter,
+            ))
+            .romaji(RomajiMatchConfig::default())
+            .build();
+        assert_sync(&matcher);
+
+        let haystacks: Vec<String> = (0..10000)
+            .map(|i| match i % 3 {
+                0 => format!("{i}拼音搜索"),
+                1 => format!("{i}pyss"),
+                _ => format!("{i}拼音"),
+            })
+            .collect();
+        let expected: Vec<usize> = (0..haystacks.len()).filter(|i| i % 3 != 2).collect();
+
+        let indices = matcher.par_filter_indices(&haystacks);
+        assert_eq!(indices, expected);
+
+        let filtered = matcher.par_filter(haystacks.par_iter().map(|h| h.as_str()));
+        assert_eq!(
+            filtered,
+            expected
+                .iter()
+                .map(|&i| haystacks[i].as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(matcher.par_filter(vec![]), [""; 0]);
+    }
+
+    #[cfg(all(feature = "pinyin", feature = "romaji"))]
+    #[test]
+    fn cjk() {
+        assert_match(IbMatcher::cjk("pysousuo").find("拼音搜索"), Some((0, 12)));
+        assert_match(IbMatcher::cjk("sekai").find("この世界"), Some((6, 6)));
+        assert_match(IbMatcher::cjk("kono").find("この"), Some((0, 6)));
+        assert_match(IbMatcher::cjk("abc").find("xABC"), Some((1, 3)));
+
+        // Shared
+        let (a, b) = (MatchConfig::cjk(), MatchConfig::cjk());
+        let (a_py, b_py) = (a.pinyin.as_ref().unwrap(), b.pinyin.as_ref().unwrap());
+        assert!(core::ptr::eq(&*a_py.data, &*b_py.data));
+        let (a_romaji, b_romaji) = (a.romaji.as_ref().unwrap(), b.romaji.as_ref().unwrap());
+        assert!(core::ptr::eq(&*a_romaji.romanizer, &*b_romaji.romanizer));
+
+        // Isolated
+        let c = MatchConfig::cjk_isolated();
+        assert!(!core::ptr::eq(
+            &*a_py.data,
+            &*c.pinyin.as_ref().unwrap().data
+        ));
+        assert!(!core::ptr::eq(
+            &*a_romaji.romanizer,
+            &*c.romaji.as_ref().unwrap().romanizer
+        ));
+        assert!(c.matcher("pysousuo").is_match("拼音搜索"));
+    }
+
+    #[test]
+    fn fullwidth_ascii() {
+        let matcher = IbMatcher::builder("2024").build();
+        assert_match(matcher.find("２０２４"), None);
+
+        let matcher = IbMatcher::builder("2024").fullwidth_ascii(true).build();
+        assert_match(matcher.find("２０２４"), Some((0, 12)));
+        assert_match(matcher.find("2024"), Some((0, 4)));
+        assert_match(matcher.find("x２０24"), Some((1, 8)));
+        assert_match(matcher.find("２０２３"), None);
+
+        let matcher = IbMatcher::builder("abc!").fullwidth_ascii(true).build();
+        assert_match(matcher.find("ＡＢＣ！"), Some((0, 12)));
+        assert_match(matcher.find("ａｂｃ！"), Some((0, 12)));
+        let matcher = IbMatcher::builder("abc")
+            .fullwidth_ascii(true)
+            .case(Case::Sensitive)
+            .build();
+        assert_match(matcher.find("ＡＢＣａｂｃ"), Some((9, 9)));
+
+        // The pattern is not converted
+        let matcher = IbMatcher::builder("２０").fullwidth_ascii(true).build();
+        assert_match(matcher.find("２０"), Some((0, 6)));
+        assert_match(matcher.find("20"), None);
+
+        // Not full-width ASCII
+        let matcher = IbMatcher::builder(" ").fullwidth_ascii(true).build();
+        assert_match(matcher.find("\u{3000}"), None);
+        assert_match(matcher.find("\u{FF5F}"), None);
+    }
+
+    #[cfg(feature = "pinyin")]
+    #[test]
+    fn line_terminator() {
+        let builder = |pattern| {
+            IbMatcher::builder(pattern).pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+        };
+
+        let matcher = builder("py\nss").build();
+        assert_match(matcher.find("拼音\n搜索"), Some((0, 13)));
+        assert_match(matcher.find("py\nss"), Some((0, 5)));
+
+        let matcher = builder("py\nss").line_terminator(b'\n').build();
+        assert_match(matcher.find("拼音\n搜索"), None);
+        assert_match(matcher.find("py\nss"), None);
+
+        let matcher = builder("pyss").line_terminator(b'\n').build();
+        assert_match(matcher.find("拼音\n搜索"), None);
+        assert_match(matcher.find("拼音\n拼音搜索"), Some((7, 12)));
+        assert_eq!(
+            matcher
+                .find_iter("拼音搜索\r\n拼音搜索")
+                .map(|m| m.range())
+                .collect::<Vec<_>>(),
+            [0..12, 14..26]
+        );
+
+        // Empty pattern
+        let matcher = builder("").line_terminator(b'\n').build();
+        assert_match(matcher.find("\n"), Some((0, 0)));
+    }
 }
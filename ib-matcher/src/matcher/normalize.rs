@@ -0,0 +1,188 @@
+/*!
+Unicode normalization of patterns and haystacks. See [`Normalization`].
+*/
+use alloc::{string::String, vec::Vec};
+use core::{iter, mem::transmute_copy, ops::Range};
+
+use unicode_normalization::{
+    char::{canonical_combining_class, decompose_canonical, decompose_compatible},
+    is_nfc_quick, is_nfkc_quick, IsNormalized, UnicodeNormalization,
+};
+
+use crate::matcher::{encoding::EncodedStr, Match};
+
+/// [Unicode normalization form](https://unicode.org/reports/tr15/) applied to both the pattern and the haystack before matching.
+///
+/// - [`Normalization::Nfc`] composes canonically equivalent sequences,
+///   e.g. "か" + U+3099 (combining voiced sound mark) to "が".
+/// - [`Normalization::Nfkc`] additionally folds compatibility characters,
+///   e.g. full-width "ＡＢＣ" to "ABC", half-width "ｶﾞ" to "ガ" and "ﬁ" to "fi".
+///
+/// The match offsets are still in the original haystack.
+/// If a match starts or ends in the middle of a normalized sequence (e.g. a composed char), it is extended to the whole sequence.
+///
+/// Only UTF-8 haystacks are supported at the moment. For other encodings, this is ignored.
+///
+/// ## Example
+/// ```
+/// // cargo add ib-matcher --features unicode-normalization
+/// use ib_matcher::matcher::{IbMatcher, Normalization};
+///
+/// let matcher = IbMatcher::builder("が")
+///     .normalize(Normalization::Nfc)
+///     .build();
+/// // か + U+3099
+/// assert_eq!(matcher.find("かか\u{3099}").unwrap().range(), 3..9);
+///
+/// let matcher = IbMatcher::builder("abc")
+///     .normalize(Normalization::Nfkc)
+///     .build();
+/// assert_eq!(matcher.find("1ＡＢＣ").unwrap().range(), 1..10);
+/// ```
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum Normalization {
+    /// No normalization.
+    #[default]
+    None,
+    /// Normalization Form C (canonical composition).
+    Nfc,
+    /// Normalization Form KC (compatibility composition).
+    Nfkc,
+}
+
+impl Normalization {
+    /// Normalize `s`, or return `None` if `s` is already normalized.
+    pub(crate) fn normalize(self, s: &str) -> Option<NormalizedStr> {
+        let quick = match self {
+            Normalization::None => return None,
+            Normalization::Nfc => is_nfc_quick(s.chars()),
+            Normalization::Nfkc => is_nfkc_quick(s.chars()),
+        };
+        if quick == IsNormalized::Yes {
+            return None;
+        }
+
+        // Split `s` into segments that can be normalized independently,
+        // so that offsets can be mapped back per segment.
+        let mut normalized = NormalizedStr {
+            s: String::with_capacity(s.len()),
+            ends: Vec::new(),
+        };
+        let mut start = 0;
+        for (i, c) in s.char_indices().skip(1).chain(iter::once((s.len(), '\0'))) {
+            if i != s.len() && !self.is_segment_start(c) {
+                continue;
+            }
+            let segment = &s[start..i];
+            match self {
+                Normalization::Nfc => normalized.s.extend(segment.nfc()),
+                _ => normalized.s.extend(segment.nfkc()),
+            }
+            normalized.ends.push((normalized.s.len(), i));
+            start = i;
+        }
+
+        (normalized.s != s).then_some(normalized)
+    }
+
+    /// Whether `c` can neither be reordered nor composed with the chars before it.
+    fn is_segment_start(self, c: char) -> bool {
+        // The first char of the full decomposition, e.g. `ﾞ` is decomposed to U+3099
+        let mut first = None;
+        let mut f = |d| {
+            first.get_or_insert(d);
+        };
+        match self {
+            Normalization::Nfc => decompose_canonical(c, &mut f),
+            _ => decompose_compatible(c, &mut f),
+        }
+        let first = first.unwrap_or(c);
+        canonical_combining_class(first) == 0
+            && is_nfc_quick(iter::once(first)) == IsNormalized::Yes
+    }
+}
+
+/// A normalized string with its offsets mapping to the original one.
+pub(crate) struct NormalizedStr {
+    pub s: String,
+    /// `(end in s, end in the original)` of each independently normalized segment.
+    ends: Vec<(usize, usize)>,
+}
+
+impl NormalizedStr {
+    /// ## Safety
+    /// `HaystackStr` must be [`str`].
+    pub unsafe fn as_haystack<HaystackStr: EncodedStr + ?Sized>(&self) -> &HaystackStr {
+        debug_assert!(HaystackStr::UTF8);
+        transmute_copy(&self.s.as_str())
+    }
+
+    /// Map a range in [`NormalizedStr::s`] to the original string,
+    /// with `start` rounded down and `end` rounded up to the segment boundaries.
+    pub fn map_range(&self, range: Range<usize>) -> Range<usize> {
+        let segment_start = |i: usize| match i {
+            0 => 0,
+            i => self.ends[i - 1].1,
+        };
+        let start = segment_start(self.ends.partition_point(|&(end, _)| end <= range.start));
+        let end = match self.ends.partition_point(|&(end, _)| end < range.end) {
+            _ if range.end == 0 => 0,
+            i => self.ends[i].1,
+        };
+        start..end
+    }
+
+    /// [`NormalizedStr::map_range()`] for [`Match`].
+    pub fn map(&self, m: Match) -> Match {
+        let Range { start, end } = self.map_range(m.range());
+        Match { start, end, ..m }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(form: Normalization, s: &str) -> Option<(String, Vec<(usize, usize)>)> {
+        form.normalize(s).map(|n| (n.s, n.ends))
+    }
+
+    #[test]
+    fn normalize_ends() {
+        assert_eq!(normalize(Normalization::None, "か\u{3099}"), None);
+        assert_eq!(normalize(Normalization::Nfc, "が"), None);
+        assert_eq!(normalize(Normalization::Nfkc, "abc"), None);
+        assert_eq!(normalize(Normalization::Nfc, "ＡＢ"), None);
+
+        assert_eq!(
+            normalize(Normalization::Nfc, "aか\u{3099}b"),
+            Some(("aがb".into(), vec![(1, 1), (4, 7), (5, 8)]))
+        );
+        assert_eq!(
+            normalize(Normalization::Nfkc, "ｶﾞｯｺ"),
+            Some(("ガッコ".into(), vec![(3, 6), (6, 9), (9, 12)]))
+        );
+        assert_eq!(
+            normalize(Normalization::Nfkc, "ＡＢ"),
+            Some(("AB".into(), vec![(1, 3), (2, 6)]))
+        );
+        // Hangul jamo
+        assert_eq!(
+            normalize(Normalization::Nfc, "\u{1100}\u{1161}"),
+            Some(("가".into(), vec![(3, 6)]))
+        );
+    }
+
+    #[test]
+    fn map() {
+        let n = Normalization::Nfc.normalize("aか\u{3099}b").unwrap();
+        let map = |start, end| n.map_range(start..end);
+        assert_eq!(map(0, 0), 0..0);
+        assert_eq!(map(0, 1), 0..1);
+        assert_eq!(map(1, 4), 1..7);
+        assert_eq!(map(1, 2), 1..7);
+        assert_eq!(map(4, 5), 7..8);
+        assert_eq!(map(5, 5), 8..8);
+    }
+}
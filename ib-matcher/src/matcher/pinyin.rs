@@ -1,8 +1,11 @@
-use std::borrow::Cow;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::ops::Deref;
 
 use bon::{bon, builder, Builder};
 
-use crate::pinyin::{PinyinData, PinyinNotation};
+use crate::pinyin::{
+    FuzzyFlags, PinyinData, PinyinNotation, PinyinPhrases, PinyinReadings, ShuangpinScheme,
+};
 
 /// ## Performance
 /// To avoid initialization cost, you should share one `data` across all configs by either passing `&data`:
@@ -20,24 +23,93 @@ use crate::pinyin::{PinyinData, PinyinNotation};
 /// let config = PinyinMatchConfig::notations(PinyinNotation::Ascii);
 /// let config2 = config.shallow_clone();
 /// ```
+/// Or passing an [`Arc<PinyinData>`](PinyinData::shared), which doesn't borrow anything, e.g. to build many matchers with different patterns in a server:
+/// ```
+/// use ib_matcher::{
+///     matcher::{IbMatcher, PinyinMatchConfig},
+///     pinyin::{PinyinData, PinyinNotation},
+/// };
+///
+/// let notations = PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter;
+/// let data = PinyinData::shared(notations);
+/// let matchers: Vec<IbMatcher<'static>> = (0..500)
+///     .map(|i| {
+///         IbMatcher::builder(format!("pinyin{i}").as_str())
+///             .pinyin(PinyinMatchConfig::builder(notations).data(data.clone()).build())
+///             .build()
+///     })
+///     .collect();
+/// assert!(matchers[42].is_match("拼音42"));
+/// ```
 #[derive(Builder, Clone)]
 pub struct PinyinMatchConfig<'a> {
     #[builder(start_fn)]
     pub(crate) notations: PinyinNotation,
 
+    /// Either `&PinyinData` or `Arc<PinyinData>`, see [`PinyinDataRef`].
+    ///
     /// Default: `new()` on [`PinyinMatchConfigBuilder::build()`]
     ///
     /// Must be inited with required notations if `inmut-data` feature is not enabled.
-    #[builder(default = Cow::Owned(PinyinData::new(notations)))]
-    #[builder(with = |data: &'a PinyinData| Cow::Borrowed(data))]
-    pub(crate) data: Cow<'a, PinyinData>,
+    #[builder(default = PinyinDataRef::Shared(PinyinData::shared(notations)))]
+    #[builder(with = |data: impl Into<PinyinDataRef<'a>>| data.into())]
+    pub(crate) data: PinyinDataRef<'a>,
 
     /// Whether upper case letters can match pinyins.
-    #[builder(default = false)]
-    pub(crate) case_insensitive: bool,
+    ///
+    /// Default: [`IbMatcherBuilder::case()`](super::IbMatcherBuilder::case) if set, otherwise `false`.
+    pub(crate) case_insensitive: Option<bool>,
 
     #[builder(default = true)]
     pub(crate) allow_partial_pattern: bool,
+
+    /// Treat the given pairs of initials/finals as equivalent, e.g. "zong" can match "种" (zhǒng) with [`FuzzyFlags::ZZh`].
+    ///
+    /// See [`fuzzy`](crate::pinyin::fuzzy) for the supported pairs.
+    ///
+    /// Pattern analysis is disabled if any pair is enabled.
+    #[builder(default = FuzzyFlags::empty())]
+    pub(crate) fuzzy: FuzzyFlags,
+
+    /// A user-defined Shuangpin scheme, matched in addition to `notations`.
+    ///
+    /// See [`shuangpin`](crate::pinyin::shuangpin) for details.
+    pub(crate) shuangpin: Option<&'a ShuangpinScheme>,
+
+    /// Readings of phrases that take precedence over the readings of their hanzi,
+    /// e.g. "重庆" only matching "chongqing" but not "zhongqing".
+    ///
+    /// See [`phrase`](crate::pinyin::phrase) for details.
+    pub(crate) phrases: Option<&'a PinyinPhrases>,
+
+    /// Readings of hanzi that are added to or replace their built-in readings,
+    /// e.g. "行" only matching "hang" in banking.
+    ///
+    /// See [`reading`](crate::pinyin::reading) for details.
+    pub(crate) readings: Option<&'a PinyinReadings>,
+
+    /// A char that can be typed in the pattern to separate syllables, e.g. `'` in "xi'an",
+    /// which matches "西安" but not "先" (xian).
+    ///
+    /// The separator is optional, i.e. "xian" still matches both.
+    /// It is skipped only after a syllable, otherwise it is matched as a plain char.
+    ///
+    /// Default: `None`, i.e. no separator.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{matcher::{IbMatcher, PinyinMatchConfig}, pinyin::PinyinNotation};
+    ///
+    /// let config = PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+    ///     .separator('\'')
+    ///     .build();
+    /// let matcher = IbMatcher::builder("xi'an").pinyin(config.shallow_clone()).build();
+    /// assert!(matcher.is_match("西安"));
+    /// assert!(!matcher.is_match("先"));
+    /// let matcher = IbMatcher::builder("xian").pinyin(config).build();
+    /// assert!(matcher.is_match("先"));
+    /// ```
+    pub(crate) separator: Option<char>,
 }
 
 impl Default for PinyinMatchConfig<'_> {
@@ -52,17 +124,60 @@ impl<'a> PinyinMatchConfig<'a> {
         Self::builder(notations).build()
     }
 
+    /// [`PinyinMatchConfigBuilder::case_insensitive`], or its default if unset.
+    #[inline]
+    pub(crate) fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive.unwrap_or(false)
+    }
+
     /// See [`PinyinMatchConfig`].
     pub fn shallow_clone(&'a self) -> Self {
         Self {
             notations: self.notations,
-            data: Cow::Borrowed(self.data.as_ref()),
+            data: PinyinDataRef::Borrowed(&self.data),
             case_insensitive: self.case_insensitive,
             allow_partial_pattern: self.allow_partial_pattern,
+            fuzzy: self.fuzzy,
+            shuangpin: self.shuangpin,
+            phrases: self.phrases,
+            readings: self.readings,
+            separator: self.separator,
+        }
+    }
+}
+
+/// [`PinyinData`] used by [`PinyinMatchConfig`], either borrowed or shared.
+///
+/// Both `&PinyinData` and `Arc<PinyinData>` can be converted into it.
+#[derive(Clone)]
+pub enum PinyinDataRef<'a> {
+    Borrowed(&'a PinyinData),
+    Shared(Arc<PinyinData>),
+}
+
+impl Deref for PinyinDataRef<'_> {
+    type Target = PinyinData;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            PinyinDataRef::Borrowed(data) => data,
+            PinyinDataRef::Shared(data) => data,
         }
     }
 }
 
+impl<'a> From<&'a PinyinData> for PinyinDataRef<'a> {
+    fn from(data: &'a PinyinData) -> Self {
+        PinyinDataRef::Borrowed(data)
+    }
+}
+
+impl From<Arc<PinyinData>> for PinyinDataRef<'_> {
+    fn from(data: Arc<PinyinData>) -> Self {
+        PinyinDataRef::Shared(data)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct PinyinAnalyzeResult {
     /// - If [`PinyinNotation::Ascii`] and [`PinyinNotation::AsciiFirstLetter`] are both enabled, [`PinyinNotation::Ascii`] is only considered used if the pattern uses any non-single-letter pinyin from [`PinyinNotation::Ascii`].
@@ -83,12 +198,17 @@ pub(crate) struct PinyinMatcher<'a> {
     pub config: PinyinMatchConfig<'a>,
     pub notations_prefix_group: Box<[PinyinNotation]>,
     pub notations: Box<[PinyinNotation]>,
+    /// Jyutping is matched separately from [`PinyinMatcher::notations`], since its readings are not notations of the Mandarin pinyins.
+    #[cfg(feature = "pinyin-jyutping")]
+    pub jyutping: bool,
     pub partial_pattern: bool,
 }
 
 #[bon]
 impl<'a> PinyinMatcher<'a> {
-    pub const ORDERED_PINYIN_NOTATIONS: [PinyinNotation; 10] = [
+    pub const ORDERED_PINYIN_NOTATIONS: [PinyinNotation;
+        11 + cfg!(feature = "pinyin-jyutping") as usize
+            + cfg!(feature = "pinyin-wade-giles") as usize] = [
         PinyinNotation::AsciiFirstLetter,
         PinyinNotation::Ascii,
         PinyinNotation::AsciiTone,
@@ -99,6 +219,11 @@ impl<'a> PinyinMatcher<'a> {
         PinyinNotation::DiletterThunisoft,
         PinyinNotation::DiletterXiaohe,
         PinyinNotation::DiletterZrm,
+        PinyinNotation::Zhuyin,
+        #[cfg(feature = "pinyin-wade-giles")]
+        PinyinNotation::WadeGiles,
+        #[cfg(feature = "pinyin-jyutping")]
+        PinyinNotation::Jyutping,
     ];
 
     #[builder]
@@ -139,6 +264,8 @@ impl<'a> PinyinMatcher<'a> {
             }
             _ => (Vec::new(), used_notations),
         };
+        #[cfg(feature = "pinyin-jyutping")]
+        let unprefixable_notations = unprefixable_notations.difference(PinyinNotation::Jyutping);
         let mut notations = Vec::with_capacity(unprefixable_notations.bits().count_ones() as usize);
         for notation in Self::ORDERED_PINYIN_NOTATIONS {
             if unprefixable_notations.contains(notation) {
@@ -150,6 +277,8 @@ impl<'a> PinyinMatcher<'a> {
             partial_pattern: analyze.partial_pattern,
             notations_prefix_group: notations_prefix_group.into_boxed_slice(),
             notations: notations.into_boxed_slice(),
+            #[cfg(feature = "pinyin-jyutping")]
+            jyutping: used_notations.contains(PinyinNotation::Jyutping),
             config,
         }
     }
@@ -199,4 +328,535 @@ mod tests {
             .build();
         assert_match!(m.test("Event.SelectFirstWhenEnterFolder.js"), Some((0, 35)));
     }
+
+    #[test]
+    fn fuzzy() {
+        let m = IbMatcher::builder("zong")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match!(m.find("种"), None);
+
+        let m = IbMatcher::builder("zong")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .fuzzy(FuzzyFlags::ZZh)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("种"), Some((0, 3)));
+        assert_match!(m.find("宗"), Some((0, 3)));
+
+        // Opt-in per pair
+        let m = IbMatcher::builder("zong")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .fuzzy(FuzzyFlags::SSh | FuzzyFlags::InIng)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("种"), None);
+
+        let m = IbMatcher::builder("jingtian")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .fuzzy(FuzzyFlags::InIng)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("今天"), Some((0, 6)));
+
+        let m = IbMatcher::builder("nanren")
+            .pinyin(
+                PinyinMatchConfig::builder(
+                    PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                )
+                .fuzzy(FuzzyFlags::LN)
+                .build(),
+            )
+            .analyze(true)
+            .build();
+        assert_match!(m.find("蓝人"), Some((0, 6)));
+
+        // AsciiFirstLetter
+        let m = IbMatcher::builder("nr")
+            .pinyin(
+                PinyinMatchConfig::builder(
+                    PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+                )
+                .fuzzy(FuzzyFlags::LN)
+                .build(),
+            )
+            .analyze(true)
+            .build();
+        assert_match!(m.find("蓝人"), Some((0, 6)));
+
+        // AsciiTone
+        let m = IbMatcher::builder("zong3")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::AsciiTone)
+                    .fuzzy(FuzzyFlags::Retroflex)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("种"), Some((0, 3)));
+
+        // Partial pattern
+        let m = IbMatcher::builder("zo")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .fuzzy(FuzzyFlags::ZZh)
+                    .build(),
+            )
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(m.find("种"), Some((0, 3)), partial);
+    }
+
+    #[test]
+    fn tone() {
+        let m = IbMatcher::builder("pin1yin1")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiTone))
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+        let m = IbMatcher::builder("pin4yin1")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiTone))
+            .build();
+        assert_match!(m.find("拼音"), None);
+
+        let m = IbMatcher::builder("pīnyīn")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::UnicodeTone))
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+        let m = IbMatcher::builder("pinyin")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::UnicodeTone))
+            .build();
+        assert_match!(m.find("拼音"), None);
+
+        // Neutral tone
+        let m = IbMatcher::builder("wo3de5")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiTone))
+            .build();
+        assert_match!(m.find("我的"), Some((0, 6)));
+        let m = IbMatcher::builder("wǒde")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::UnicodeTone))
+            .build();
+        assert_match!(m.find("我的"), Some((0, 6)));
+
+        // ü
+        let m = IbMatcher::builder("lv4se4")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::AsciiTone))
+            .build();
+        assert_match!(m.find("绿色"), Some((0, 6)));
+        let m = IbMatcher::builder("lǜsè")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::UnicodeTone))
+            .build();
+        assert_match!(m.find("绿色"), Some((0, 6)));
+
+        // Mixed with toneless
+        let m = IbMatcher::builder("pin1yin")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiTone,
+            ))
+            .analyze(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+    }
+
+    #[test]
+    fn zhuyin() {
+        let m = IbMatcher::builder("ㄆㄧㄣㄧㄣ")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Zhuyin))
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+        assert_match!(m.find("协作拼音"), Some((6, 6)));
+        assert_match!(m.find("pinyin"), None);
+
+        let m = IbMatcher::builder("ㄆㄧ")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Zhuyin))
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 3)), partial);
+
+        let m = IbMatcher::builder("pinㄧㄣ")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::Zhuyin,
+            ))
+            .analyze(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+    }
+
+    #[cfg(feature = "pinyin-wade-giles")]
+    #[test]
+    fn wade_giles() {
+        let m = IbMatcher::builder("k'ungtzu")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::WadeGiles))
+            .build();
+        assert_match!(m.find("孔子"), Some((0, 6)));
+        // The apostrophe is optional
+        let m = IbMatcher::builder("kungtzu")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::WadeGiles))
+            .build();
+        assert_match!(m.find("孔子"), Some((0, 6)));
+        // But can't be added to unaspirated initials
+        let m = IbMatcher::builder("k'ungtz'u")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::WadeGiles))
+            .build();
+        assert_match!(m.find("孔子"), None);
+
+        let m = IbMatcher::builder("maotsetung")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::WadeGiles))
+            .analyze(true)
+            .build();
+        assert_match!(m.find("毛泽东"), Some((0, 9)));
+        // Pinyin is not matched
+        assert_match!(
+            IbMatcher::builder("maozedong")
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::WadeGiles))
+                .build()
+                .find("毛泽东"),
+            None
+        );
+
+        let m = IbMatcher::builder("ch'ingh")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::WadeGiles))
+            .is_pattern_partial(true)
+            .analyze(true)
+            .build();
+        assert_match!(m.find("清华"), Some((0, 6)), partial);
+
+        let m = IbMatcher::builder("beiching")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::WadeGiles,
+            ))
+            .analyze(true)
+            .build();
+        assert_match!(m.find("北京"), Some((0, 6)));
+    }
+
+    #[test]
+    fn extension() {
+        let m = IbMatcher::builder("dayuansu")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        // U+2B7FC, CJK Unified Ideographs Extension E
+        assert_match!(m.find("𫟼元素"), Some((0, 10)));
+
+        #[cfg(feature = "romaji")]
+        {
+            let romanizer = Default::default();
+            let m = IbMatcher::builder("dayuansu")
+                .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+                .romaji(
+                    crate::matcher::RomajiMatchConfig::builder()
+                        .romanizer(&romanizer)
+                        .build(),
+                )
+                .build();
+            assert_match!(m.find("𫟼元素"), Some((0, 10)));
+        }
+    }
+
+    #[cfg(feature = "pinyin-jyutping")]
+    #[test]
+    fn jyutping() {
+        let m = IbMatcher::builder("bingjam")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Jyutping))
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+        assert_match!(m.find("協作拼音"), Some((6, 6)));
+        // Heteronyms
+        let m = IbMatcher::builder("pingjam")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Jyutping))
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+        // Mandarin pinyin is not matched
+        let m = IbMatcher::builder("pinyin")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Jyutping))
+            .build();
+        assert_match!(m.find("拼音"), None);
+
+        let m = IbMatcher::builder("hoenggong")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Jyutping))
+            .analyze(true)
+            .build();
+        assert_match!(m.find("香港"), Some((0, 6)));
+
+        let m = IbMatcher::builder("hoenggo")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Jyutping))
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(m.find("香港"), Some((0, 6)), partial);
+
+        // Mixed with Mandarin pinyin
+        let m = IbMatcher::builder("pinjam")
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::Jyutping,
+            ))
+            .analyze(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+    }
+
+    #[test]
+    fn shuangpin() {
+        use crate::pinyin::ShuangpinScheme;
+
+        // 小鹤双拼, but with "zh" and "sh" swapped
+        #[rustfmt::skip]
+        let scheme = ShuangpinScheme::builder()
+            .initials(&[("zh", 'u'), ("ch", 'i'), ("sh", 'v')])
+            .finals(&[
+                ("i", 'i'), ("u", 'u'), ("v", 'v'),
+                ("e", 'e'), ("ie", 'p'), ("o", 'o'), ("uo", 'o'), ("ue", 't'), ("ve", 't'),
+                ("a", 'a'), ("ia", 'x'), ("ua", 'x'),
+                ("ei", 'w'), ("ui", 'v'),
+                ("ai", 'd'), ("uai", 'k'),
+                ("ou", 'z'), ("iu", 'q'),
+                ("ao", 'c'), ("iao", 'n'),
+                ("in", 'b'), ("un", 'y'), ("vn", 'y'),
+                ("en", 'f'),
+                ("an", 'j'), ("ian", 'm'), ("uan", 'r'), ("van", 'r'),
+                ("ing", 'k'),
+                ("ong", 's'), ("iong", 's'),
+                ("eng", 'g'),
+                ("ang", 'h'), ("iang", 'l'), ("uang", 'l'),
+            ])
+            .zero_initials(&[("a", "aa"), ("e", "ee"), ("o", "oo"), ("eng", "eg"), ("ang", "ah")])
+            .build()
+            .unwrap();
+        let config = || {
+            PinyinMatchConfig::builder(PinyinNotation::empty())
+                .shuangpin(&scheme)
+                .build()
+        };
+
+        let m = IbMatcher::builder("usvu").pinyin(config()).build();
+        assert_match!(m.find("中书"), Some((0, 6)));
+        assert_match!(m.find("协作中书"), Some((6, 6)));
+        let m = IbMatcher::builder("vsuu").pinyin(config()).build();
+        assert_match!(m.find("中书"), None);
+
+        let m = IbMatcher::builder("pbyb")
+            .pinyin(config())
+            .analyze(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+
+        let m = IbMatcher::builder("pby")
+            .pinyin(config())
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)), partial);
+
+        // Mixed with built-in notations
+        let m = IbMatcher::builder("pinyb")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .shuangpin(&scheme)
+                    .build(),
+            )
+            .analyze(true)
+            .build();
+        assert_match!(m.find("拼音"), Some((0, 6)));
+    }
+
+    #[test]
+    fn phrases() {
+        let phrases = PinyinPhrases::builtin();
+        let config = || {
+            PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+                .phrases(&phrases)
+                .build()
+        };
+
+        let m = IbMatcher::builder("chongqing").pinyin(config()).build();
+        assert_match!(m.find("重庆"), Some((0, 6)));
+        assert_match!(m.find("去重庆"), Some((3, 6)));
+        let m = IbMatcher::builder("zhongqing").pinyin(config()).build();
+        assert_match!(m.find("重庆"), None);
+        // Without phrases
+        let m = IbMatcher::builder("zhongqing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match!(m.find("重庆"), Some((0, 6)));
+
+        // Not in a phrase
+        let m = IbMatcher::builder("zhong").pinyin(config()).build();
+        assert_match!(m.find("重要"), Some((0, 3)));
+        assert_match!(m.find("重庆"), None);
+
+        // Mixed notations
+        let m = IbMatcher::builder("cq")
+            .pinyin(config())
+            .analyze(true)
+            .build();
+        assert_match!(m.find("重庆"), Some((0, 6)));
+        let m = IbMatcher::builder("chongq")
+            .pinyin(config())
+            .analyze(true)
+            .build();
+        assert_match!(m.find("重庆"), Some((0, 6)));
+
+        // Partial pattern
+        let m = IbMatcher::builder("chongqi")
+            .pinyin(config())
+            .is_pattern_partial(true)
+            .build();
+        assert_match!(m.find("重庆"), Some((0, 6)), partial);
+
+        // Starting in the middle of a phrase
+        let m = IbMatcher::builder("sunwukong").pinyin(config()).build();
+        assert_match!(m.find("长孙悟空"), Some((3, 9)));
+
+        // Fuzzy pinyin
+        let m = IbMatcher::builder("congqing")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .phrases(&phrases)
+                    .fuzzy(FuzzyFlags::CCh)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("重庆"), Some((0, 6)));
+
+        #[cfg(feature = "encoding")]
+        {
+            use widestring::u16str;
+
+            let m = IbMatcher::builder(u16str!("chongqing"))
+                .pinyin(config())
+                .build();
+            assert!(m.is_match(u16str!("去重庆")));
+            let m = IbMatcher::builder(u16str!("zhongqing"))
+                .pinyin(config())
+                .build();
+            assert!(!m.is_match(u16str!("去重庆")));
+        }
+
+        // User-defined
+        let phrases = PinyinPhrases::new([("银行", "yin hang")]).unwrap();
+        let m = IbMatcher::builder("yinxing")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .phrases(&phrases)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("银行"), None);
+        assert_match!(m.find("银杏"), Some((0, 6)));
+    }
+
+    #[test]
+    fn separator() {
+        let config = || {
+            PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+                .separator('\'')
+                .build()
+        };
+        let matcher = |pattern| {
+            IbMatcher::builder(pattern)
+                .pinyin(config())
+                .analyze(true)
+                .build()
+        };
+
+        assert_match!(matcher("xi'an").find("西安"), Some((0, 6)));
+        assert_match!(matcher("xi'an").find("先"), None);
+        assert_match!(matcher("xi'an").find("仙人"), None);
+        assert_match!(matcher("x'a").find("西安"), Some((0, 6)));
+        assert_match!(matcher("xi'a").find("西安"), Some((0, 6)));
+        // Optional
+        assert_match!(matcher("xian").find("西安"), Some((0, 6)));
+        assert_match!(matcher("xian").find("先"), Some((0, 3)));
+        // Trailing
+        assert_match!(matcher("xi'").find("西"), Some((0, 3)));
+        assert_match!(matcher("pinyin'").find("拼音"), Some((0, 6)));
+        // Not after a syllable
+        assert_match!(matcher("'an").find("安"), None);
+        assert_match!(matcher("'an").find("'安"), Some((0, 4)));
+        assert_match!(matcher("a'b").find("a'b"), Some((0, 3)));
+
+        // Prefilter
+        assert_eq!(matcher("xi'an").prefilter_literals(), [b""; 0]);
+        assert_eq!(matcher("pinyin'").prefilter_literals(), [b""; 0]);
+        assert_eq!(matcher("'an").prefilter_literals(), [b"'"]);
+
+        // Off by default
+        let m = IbMatcher::builder("xi'an")
+            .pinyin(PinyinMatchConfig::default())
+            .build();
+        assert_match!(m.find("西安"), None);
+    }
+
+    #[test]
+    fn readings() {
+        let readings = PinyinReadings::new()
+            .replace('行', "hang2")
+            .unwrap()
+            .add('重', "tong2")
+            .unwrap()
+            // Not a hanzi
+            .add('℃', "du4")
+            .unwrap();
+        let config = || {
+            PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+                .readings(&readings)
+                .build()
+        };
+
+        // Replaced
+        let m = IbMatcher::builder("yinhang").pinyin(config()).build();
+        assert_match!(m.find("去银行"), Some((3, 6)));
+        let m = IbMatcher::builder("xing").pinyin(config()).build();
+        assert_match!(m.find("行"), None);
+        let m = IbMatcher::builder("xing")
+            .pinyin(PinyinMatchConfig::notations(PinyinNotation::Ascii))
+            .build();
+        assert_match!(m.find("行"), Some((0, 3)));
+
+        // Added
+        let m = IbMatcher::builder("tong").pinyin(config()).build();
+        assert_match!(m.find("重"), Some((0, 3)));
+        let m = IbMatcher::builder("zhongyao").pinyin(config()).build();
+        assert_match!(m.find("重要"), Some((0, 6)));
+        let m = IbMatcher::builder("36du").pinyin(config()).build();
+        assert_match!(m.find("36℃"), Some((0, 5)));
+
+        // Notations
+        let m = IbMatcher::builder("yh").pinyin(config()).build();
+        assert_match!(m.find("银行"), Some((0, 6)));
+        let m = IbMatcher::builder("yínháng")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Unicode)
+                    .readings(&readings)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("银行"), Some((0, 6)));
+
+        // Phrases take precedence
+        let phrases = PinyinPhrases::new([("行不行", "xing bu xing")]).unwrap();
+        let m = IbMatcher::builder("xingbuxing")
+            .pinyin(
+                PinyinMatchConfig::builder(PinyinNotation::Ascii)
+                    .phrases(&phrases)
+                    .readings(&readings)
+                    .build(),
+            )
+            .build();
+        assert_match!(m.find("行不行"), Some((0, 9)));
+
+        #[cfg(feature = "encoding")]
+        {
+            use widestring::u16str;
+
+            let m = IbMatcher::builder(u16str!("yinhang"))
+                .pinyin(config())
+                .build();
+            assert!(m.is_match(u16str!("去银行")));
+            let m = IbMatcher::builder(u16str!("xing")).pinyin(config()).build();
+            assert!(!m.is_match(u16str!("行")));
+        }
+    }
 }
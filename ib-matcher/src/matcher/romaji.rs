@@ -1,7 +1,14 @@
-use std::borrow::Cow;
+use alloc::{borrow::Cow, string::String, vec::Vec};
 
 use bon::Builder;
-use ib_romaji::HepburnRomanizer;
+use ib_romaji::{
+    convert::long_vowel::LongVowelStyle,
+    kana::{self, KanaClass},
+    kanji::{ReadingFilter, ReadingKind},
+    HepburnRomanizer,
+};
+
+use crate::matcher::encoding::EncodedStr;
 
 /**
 ## Partial matches
@@ -26,6 +33,13 @@ So unlike pinyin, there are three partial matching options in romaji matching:
 
   To use this option, set [`IbMatcherBuilder::is_pattern_partial(true)`](super::IbMatcherBuilder::is_pattern_partial),
   which also works the same for pinyin matching.
+
+## UTF-16 and UTF-32
+Romaji matching also works on UTF-16 and UTF-32 haystacks (`encoding` feature),
+but the haystack has to be transcoded to UTF-8 for romanization, which is slower than UTF-8 haystacks.
+
+A surrogate pair is romanized as one char, e.g. a kanji outside the BMP.
+Unpaired surrogates never match as romaji.
 */
 /// ## Performance
 /// To avoid initialization cost, you should share one `romanizer` across all configs by either passing `&romanizer`:
@@ -51,8 +65,9 @@ pub struct RomajiMatchConfig<'a> {
     pub(crate) romanizer: Cow<'a, HepburnRomanizer>,
 
     /// Whether upper case letters can match Japanese words.
-    #[builder(default = false)]
-    pub(crate) case_insensitive: bool,
+    ///
+    /// Default: [`IbMatcherBuilder::case()`](super::IbMatcherBuilder::case) if set, otherwise `false`.
+    pub(crate) case_insensitive: Option<bool>,
 
     /// Allow partially match a Japanese word.
     ///
@@ -62,6 +77,135 @@ pub struct RomajiMatchConfig<'a> {
 
     #[builder(default = true)]
     pub(crate) allow_partial_pattern: bool,
+
+    /// Also allow long vowels in the pattern to be written in this style,
+    /// e.g. both `kou` and `kō` can match `校` with [`LongVowelStyle::Macron`].
+    ///
+    /// Default: the romanizer's [`long_vowel()`](HepburnRomanizer::long_vowel)
+    pub(crate) long_vowel: Option<LongVowelStyle>,
+
+    /// Only match kanji readings of the given kinds, e.g. only kun'yomi for Japanese personal names.
+    ///
+    /// See [`ib_romaji::kanji`](ib_romaji::kanji#onyomi-and-kunyomi) for details.
+    #[builder(default)]
+    pub(crate) readings: ReadingKind,
+
+    /// Only match the top `max_readings` most common readings (of [`readings`](RomajiMatchConfigBuilder::readings)) of each kanji,
+    /// e.g. to skip rare nanori readings when matching ordinary text.
+    ///
+    /// Readings of kanji that are not ranked are never limited, see [`ib_romaji::kanji`](ib_romaji::kanji#untagged-kanji).
+    ///
+    /// Default: all readings. See [`ib_romaji::kanji`](ib_romaji::kanji#reading-ranks) for details.
+    pub(crate) max_readings: Option<usize>,
+
+    /// Whether to match nanori (名乗り), i.e. kanji readings only used in names, e.g. `kusa` for 日.
+    ///
+    /// They are essential when searching person or place names, but can cause odd matches in ordinary text.
+    /// Only nanori of a subset of common kanji are tagged at the moment,
+    /// see [`ib_romaji::kanji`](ib_romaji::kanji#nanori) for details.
+    #[builder(default = false)]
+    pub(crate) include_nanori: bool,
+
+    /// Only match kanas of the given script, e.g. only hiragana to distinguish function words from katakana loanwords.
+    ///
+    /// Kanji readings are script-neutral, so a kanji is always matched,
+    /// and a word mixing kanji and kanas (e.g. `お茶`) is matched if its kanas are of the script.
+    /// The long vowel mark `ー` is script-neutral too.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::matcher::{IbMatcher, RomajiMatchConfig, ScriptFilter};
+    ///
+    /// let matcher = IbMatcher::builder("kana")
+    ///     .romaji(RomajiMatchConfig::builder().script(ScriptFilter::HiraganaOnly).build())
+    ///     .build();
+    /// assert!(matcher.is_match("かな"));
+    /// assert!(!matcher.is_match("カナ"));
+    /// ```
+    #[builder(default)]
+    pub(crate) script: ScriptFilter,
+
+    /// Also allow sokuon (`っ`) geminates to be typed without doubling, e.g. `choto` for `ちょっと`, a common typo.
+    ///
+    /// Exactly these leniencies are applied to the romajis of kanas and kanji readings:
+    /// - A doubled consonant can also be matched as a single one, e.g. `kipu` for `切符` (`kippu`) and `isho` for `一緒` (`issho`).
+    ///   `n` is never treated as a geminate, since `nn` is written for `ん` followed by `n*`, e.g. `konna` for `こんな`.
+    /// - `tch` and `cch` (`っち`, see [`HepburnRomanizer::sokuon_before_ch()`]) can also be matched as `ch`, e.g. `macha` for `まっちゃ`.
+    ///
+    /// The doubled forms always match too. A lone `っ` not followed by a kana (`tsu`) and long vowels are not affected,
+    /// see [`long_vowel`](RomajiMatchConfigBuilder::long_vowel) for the latter.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::matcher::{IbMatcher, RomajiMatchConfig};
+    ///
+    /// let matcher = IbMatcher::builder("choto")
+    ///     .romaji(RomajiMatchConfig::builder().lenient_gemination(true).build())
+    ///     .build();
+    /// assert!(matcher.is_match("ちょっと"));
+    ///
+    /// let matcher = IbMatcher::builder("choto")
+    ///     .romaji(RomajiMatchConfig::default())
+    ///     .build();
+    /// assert!(!matcher.is_match("ちょっと"));
+    /// ```
+    #[builder(default = false)]
+    pub(crate) lenient_gemination: bool,
+
+    /// Also match the voiced readings of 々 (連濁, rendaku), e.g. `teradera` for `寺々`.
+    ///
+    /// Common rendaku words (e.g. `hitobito` for `人々`) are always matched via the word dictionary.
+    /// This tries the [`rendaku()`](ib_romaji::kanji::rendaku) of each reading of the repeated kanji,
+    /// and the unvoiced readings always match too.
+    /// See [`ib_romaji::kanji`](ib_romaji::kanji#rendaku) for details.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::matcher::{IbMatcher, RomajiMatchConfig};
+    ///
+    /// let matcher = IbMatcher::builder("teradera")
+    ///     .romaji(RomajiMatchConfig::builder().rendaku(true).build())
+    ///     .build();
+    /// assert!(matcher.is_match("寺々"));
+    ///
+    /// let matcher = IbMatcher::builder("teradera")
+    ///     .romaji(RomajiMatchConfig::default())
+    ///     .build();
+    /// assert!(!matcher.is_match("寺々"));
+    /// ```
+    #[builder(default = false)]
+    pub(crate) rendaku: bool,
+}
+
+/// Which kana scripts can be matched as romajis. See [`RomajiMatchConfigBuilder::script()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum ScriptFilter {
+    /// Both hiragana and katakana.
+    #[default]
+    Both,
+    /// Only hiragana.
+    HiraganaOnly,
+    /// Only katakana, including half-width katakana.
+    KatakanaOnly,
+}
+
+impl ScriptFilter {
+    /// Whether the kanas in `s`, the source text of a romaji, are all of the script.
+    pub(crate) fn allows(self, s: &str) -> bool {
+        if self == ScriptFilter::Both {
+            return true;
+        }
+        s.chars()
+            .filter(|&c| !matches!(c, 'ー' | 'ｰ'))
+            .all(|c| match kana::classify(c) {
+                KanaClass::Hiragana => self == ScriptFilter::HiraganaOnly,
+                KanaClass::Katakana | KanaClass::HalfWidthKana => {
+                    self == ScriptFilter::KatakanaOnly
+                }
+                _ => true,
+            })
+    }
 }
 
 impl Default for RomajiMatchConfig<'_> {
@@ -72,6 +216,12 @@ impl Default for RomajiMatchConfig<'_> {
 }
 
 impl<'a> RomajiMatchConfig<'a> {
+    /// [`RomajiMatchConfigBuilder::case_insensitive`], or its default if unset.
+    #[inline]
+    pub(crate) fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive.unwrap_or(false)
+    }
+
     /// See [`RomajiMatchConfig`].
     pub fn shallow_clone(&'a self) -> RomajiMatchConfig<'a> {
         Self {
@@ -79,6 +229,13 @@ impl<'a> RomajiMatchConfig<'a> {
             case_insensitive: self.case_insensitive,
             partial_word: self.partial_word,
             allow_partial_pattern: self.allow_partial_pattern,
+            long_vowel: self.long_vowel,
+            readings: self.readings,
+            max_readings: self.max_readings,
+            include_nanori: self.include_nanori,
+            script: self.script,
+            lenient_gemination: self.lenient_gemination,
+            rendaku: self.rendaku,
         }
     }
 }
@@ -87,6 +244,7 @@ pub(crate) struct RomajiMatcher<'a> {
     pub config: RomajiMatchConfig<'a>,
     pub partial_pattern: bool,
     pub partial_kana: bool,
+    pub long_vowel: LongVowelStyle,
 }
 
 impl<'a> RomajiMatcher<'a> {
@@ -95,16 +253,122 @@ impl<'a> RomajiMatcher<'a> {
         Self {
             partial_pattern: config.partial_word || partial_kana,
             partial_kana,
+            long_vowel: config
+                .long_vowel
+                .unwrap_or_else(|| config.romanizer.long_vowel()),
             config,
         }
     }
+
+    pub fn readings(&self) -> ReadingFilter {
+        ReadingFilter {
+            kind: self.config.readings,
+            max: self.config.max_readings,
+            nanori: self.config.include_nanori,
+        }
+    }
+
+    /// Transcode the start of a non-UTF-8 `haystack` to UTF-8 into `buf`,
+    /// until it is long enough for [`HepburnRomanizer::romanize_and_try_for_each()`].
+    ///
+    /// The char before `haystack` (if `matched_len > 0`) is also transcoded as the context,
+    /// e.g. for `々`.
+    ///
+    /// For each transcoded char of `haystack`, `(end in buf after the context, end in haystack)`
+    /// is pushed to `ends`.
+    ///
+    /// Surrogate pairs are transcoded as one char, and unpaired surrogates as `U+FFFD`.
+    ///
+    /// Returns the start of `haystack` in `buf`.
+    pub fn transcode_window<HaystackStr: EncodedStr + ?Sized>(
+        &self,
+        haystack: &HaystackStr,
+        matched_len: usize,
+        buf: &mut String,
+        ends: &mut Vec<(usize, usize)>,
+    ) -> usize {
+        // A char is at most 4 bytes
+        let context_len = matched_len.min(4 / HaystackStr::ELEMENT_LEN_BYTE);
+        if let Some((_, c, _)) = unsafe { haystack.extend_start_unchecked(context_len) }
+            .char_index_strs()
+            .take_while(|&(i, _, _)| i < context_len)
+            .last()
+        {
+            buf.push(c);
+        }
+        let start = buf.len();
+
+        let max_len = self.config.romanizer.max_word_len();
+        let mut end = 0;
+        for (c, len, _) in haystack.char_len_next_strs() {
+            if buf.len() - start >= max_len {
+                break;
+            }
+            buf.push(c);
+            end += len;
+            ends.push((buf.len() - start, end));
+        }
+        start
+    }
+
+    /// `romaji` with each sokuon geminate collapsed into a single consonant, e.g. `chotto` to `choto` and `matcha` to `macha`.
+    /// See [`RomajiMatchConfigBuilder::lenient_gemination()`].
+    ///
+    /// Returns `None` if there is no geminate.
+    pub fn degeminate(romaji: &str) -> Option<String> {
+        let bytes = romaji.as_bytes();
+        // Whether the consonant at `i` is the first one of a geminate
+        let is_geminate = |i: usize| match bytes[i..] {
+            [b't' | b'c', b'c', b'h', ..] => true,
+            [a, b, ..] => a == b && b"bcdfghjkprstvwyz".contains(&a),
+            _ => false,
+        };
+        if !(0..bytes.len()).any(is_geminate) {
+            return None;
+        }
+        Some(
+            romaji
+                .char_indices()
+                .filter(|&(i, _)| !is_geminate(i))
+                .map(|(_, c)| c)
+                .collect(),
+        )
+    }
+
+    /// Whether `pattern` starts with `romaji`.
+    #[inline]
+    pub fn pattern_starts_with(&self, pattern: &str, romaji: &str) -> bool {
+        match self.long_vowel {
+            LongVowelStyle::Spelled => {
+                ib_romaji::convert::hepburn_ime::starts_with_ignore_hepburn_ime(pattern, romaji)
+            }
+            style => ib_romaji::convert::long_vowel::starts_with_ignore_long_vowel(
+                pattern, romaji, style,
+            ),
+        }
+    }
+
+    /// Whether `romaji` starts with `pattern`.
+    #[inline]
+    pub fn romaji_starts_with(&self, romaji: &str, pattern: &str) -> bool {
+        match self.long_vowel {
+            LongVowelStyle::Spelled => {
+                ib_romaji::convert::hepburn_ime::romaji_starts_with_ignore_hepburn_ime(
+                    romaji, pattern,
+                )
+            }
+            style => ib_romaji::convert::long_vowel::romaji_starts_with_ignore_long_vowel(
+                romaji, pattern, style,
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
         assert_match,
-        matcher::{IbMatcher, MatchConfig},
+        matcher::{Case, IbMatcher, MatchConfig, PlainMatchConfig},
     };
 
     use super::*;
@@ -207,6 +471,68 @@ mod tests {
         assert_match!(m.find("ボタン雪"), Some((0, 12)));
     }
 
+    #[test]
+    fn case_insensitive() {
+        let romanizer = Default::default();
+        let builder = |pattern, plain, romaji| {
+            IbMatcher::builder(pattern)
+                .plain(PlainMatchConfig::case_insensitive(plain))
+                .romaji(
+                    RomajiMatchConfig::builder()
+                        .romanizer(&romanizer)
+                        .case_insensitive(romaji)
+                        .build(),
+                )
+                .build()
+        };
+        let haystack = "この素晴らしい世界に祝福を";
+
+        let matcher = builder("konosuba", false, false);
+        assert_match!(matcher.test("konosuba"), Some((0, 8)));
+        assert_match!(matcher.test("KONOSUBA"), None);
+        assert_match!(matcher.test(haystack), Some((0, 21)), partial);
+
+        let matcher = builder("KONOSUBA", true, false);
+        assert_match!(matcher.test("konosuba"), Some((0, 8)));
+        assert_match!(matcher.test("KoNoSuba"), Some((0, 8)));
+        assert_match!(matcher.test(haystack), None);
+
+        let matcher = builder("KONOSUBA", true, true);
+        assert_match!(matcher.test("konosuba"), Some((0, 8)));
+        assert_match!(matcher.test("KoNoSuba"), Some((0, 8)));
+        assert_match!(matcher.test(haystack), Some((0, 21)), partial);
+
+        let matcher = builder("KoNoSuba", false, true);
+        assert_match!(matcher.test("konosuba"), None);
+        assert_match!(matcher.test("KoNoSuba"), Some((0, 8)));
+        assert_match!(matcher.test(haystack), Some((0, 21)), partial);
+
+        // n apostrophe
+        let matcher = builder("KANN", true, true);
+        assert_match!(matcher.test("かんん"), Some((0, 9)));
+        assert_match!(matcher.test("かんヰ"), Some((0, 6)));
+        let matcher = builder("KANN", true, false);
+        assert_match!(matcher.test("かんん"), None);
+
+        // Case
+        let matcher = IbMatcher::builder("KONOSUBA")
+            .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+            .case(Case::Insensitive)
+            .build();
+        assert_match!(matcher.test(haystack), Some((0, 21)), partial);
+        let matcher = IbMatcher::builder("KONOSUBA")
+            .romaji(
+                RomajiMatchConfig::builder()
+                    .romanizer(&romanizer)
+                    .case_insensitive(false)
+                    .build(),
+            )
+            .case(Case::Insensitive)
+            .build();
+        assert_match!(matcher.test(haystack), None);
+        assert_match!(matcher.test("konosuba"), Some((0, 8)));
+    }
+
     /// Without pattern_next.is_empty() check,
     /// matcher will panic if pattern ends with needed n apostrophe.
     #[test]
@@ -278,6 +604,72 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn u16() {
+        use widestring::u16str;
+
+        let romanizer = Default::default();
+        let romaji = RomajiMatchConfig::builder().romanizer(&romanizer).build();
+
+        let matcher = IbMatcher::builder(u16str!("ohayo"))
+            .romaji(romaji.clone())
+            .build();
+        assert_match!(matcher.find(u16str!("おはよう")), Some((0, 3)));
+
+        let matcher = IbMatcher::builder(u16str!("jojo"))
+            .romaji(romaji.clone())
+            .build();
+        assert_match!(matcher.find(u16str!("おはよジョジョ")), Some((3, 4)));
+        // Surrogate pair
+        assert_match!(matcher.find(u16str!("𝄞ジョジョ")), Some((2, 4)));
+
+        let matcher = IbMatcher::builder(u16str!("konosubarashiisekaini"))
+            .romaji(romaji.clone())
+            .build();
+        assert_match!(
+            matcher.find(u16str!("この素晴らしい世界に祝福を")),
+            Some((0, 10))
+        );
+
+        // Context
+        let matcher = IbMatcher::builder(u16str!("mizukinana"))
+            .romaji(romaji.clone())
+            .starts_with(true)
+            .build();
+        assert_match!(matcher.find(u16str!("水樹奈々")), Some((0, 4)));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn u32() {
+        use widestring::u32str;
+
+        let romanizer = Default::default();
+        let romaji = RomajiMatchConfig::builder().romanizer(&romanizer).build();
+
+        let matcher = IbMatcher::builder(u32str!("jojo"))
+            .romaji(romaji.clone())
+            .build();
+        assert_match!(matcher.find(u32str!("おはよジョジョ")), Some((3, 4)));
+        assert_match!(matcher.find(u32str!("𝄞ジョジョ")), Some((1, 4)));
+
+        let matcher = IbMatcher::builder(u32str!("konosubarashiisekaini"))
+            .romaji(romaji.clone())
+            .build();
+        assert_match!(
+            matcher.find(u32str!("この素晴らしい世界に祝福を")),
+            Some((0, 10))
+        );
+
+        // Context
+        let matcher = IbMatcher::builder(u32str!("mizukinana"))
+            .romaji(romaji.clone())
+            .starts_with(true)
+            .build();
+        assert_match!(matcher.find(u32str!("水樹奈々")), Some((0, 4)));
+    }
+
     #[test]
     fn convert_hepburn_ime() {
         let c = MatchConfig::builder().romaji(Default::default()).build();
@@ -301,6 +693,294 @@ mod tests {
         assert_match!(c.matcher("shuuseipatchi").find("修正パッチ"), Some((0, 15)));
         assert_match!(c.matcher("shuuseipacchi").find("集成パッチ"), Some((0, 15)));
         assert_match!(c.matcher("shuuseipacchi").find("終生パッチ"), Some((0, 15)));
+
+        // Sokuon before ch
+        assert_match!(c.matcher("matcha").find("まっちゃ"), Some((0, 12)));
+        assert_match!(c.matcher("maccha").find("まっちゃ"), Some((0, 12)));
+        assert_match!(c.matcher("maccha").find("抹茶"), Some((0, 6)));
+        assert_match!(c.matcher("mattya").find("まっちゃ"), None);
+
+        // The output style of the romanizer doesn't affect matching
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .sokuon_before_ch(ib_romaji::convert::hepburn_ime::TchStyle::Cch)
+            .build();
+        let c = MatchConfig::builder()
+            .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+            .build();
+        assert_match!(c.matcher("matcha").find("まっちゃ"), Some((0, 12)));
+        assert_match!(c.matcher("maccha").find("まっちゃ"), Some((0, 12)));
+    }
+
+    #[cfg(feature = "romaji-kunrei")]
+    #[test]
+    fn kunrei() {
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .system(ib_romaji::RomanizationSystem::Kunrei)
+            .build();
+        let c = MatchConfig::builder()
+            .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+            .build();
+        assert_match!(c.matcher("tuki").find("月"), Some((0, 3)));
+        assert_match!(c.matcher("tsuki").find("月"), None);
+        assert_match!(c.matcher("si").find("し"), Some((0, 3)));
+        assert_match!(c.matcher("sinzyuku").find("しんじゅく"), Some((0, 15)));
+        assert_match!(c.matcher("huzisan").find("ふじさん"), Some((0, 12)));
+        assert_match!(c.matcher("mattya").find("まっちゃ"), Some((0, 12)));
+    }
+
+    #[test]
+    fn long_vowel() {
+        let c = MatchConfig::builder()
+            .romaji(
+                RomajiMatchConfig::builder()
+                    .long_vowel(LongVowelStyle::Macron)
+                    .build(),
+            )
+            .build();
+        assert_match!(c.matcher("kou").find("校"), Some((0, 3)));
+        assert_match!(c.matcher("kō").find("校"), Some((0, 3)));
+        assert_match!(c.matcher("kô").find("校"), None);
+        assert_match!(c.matcher("tōkyō").find("東京"), Some((0, 6)));
+        assert_match!(c.matcher("toukyō").find("東京"), Some((0, 6)));
+        assert_match!(c.matcher("kyō").find("今日"), Some((0, 6)));
+        // Partial
+        assert_match!(c.matcher("tō").find("東京"), Some((0, 3)));
+        assert_match!(c.matcher("tōkyo").find("東京"), Some((0, 6)), partial);
+        // ー
+        assert_match!(c.matcher("kā").find("カー"), Some((0, 6)));
+        assert_match!(c.matcher("furīren").find("フリーレン"), Some((0, 15)));
+        assert_match!(c.matcher("furiiren").find("フリーレン"), Some((0, 15)));
+
+        let c = MatchConfig::builder()
+            .romaji(
+                RomajiMatchConfig::builder()
+                    .long_vowel(LongVowelStyle::Circumflex)
+                    .build(),
+            )
+            .build();
+        assert_match!(c.matcher("kô").find("校"), Some((0, 3)));
+        assert_match!(c.matcher("kō").find("校"), None);
+
+        let c = MatchConfig::builder().romaji(Default::default()).build();
+        assert_match!(c.matcher("kō").find("校"), None);
+        assert_match!(c.matcher("kaa").find("カー"), Some((0, 6)));
+        assert_match!(c.matcher("furiiren").find("フリーレン"), Some((0, 15)));
+        assert_match!(c.matcher("furi-ren").find("フリーレン"), Some((0, 15)));
+        assert_match!(c.matcher("furīren").find("フリーレン"), None);
+    }
+
+    #[test]
+    fn readings() {
+        let romanizer = Default::default();
+        let config = |readings| {
+            MatchConfig::builder()
+                .romaji(
+                    RomajiMatchConfig::builder()
+                        .romanizer(&romanizer)
+                        .readings(readings)
+                        .build(),
+                )
+                .build()
+        };
+
+        let c = config(ReadingKind::All);
+        assert_match!(c.matcher("morikawa").find("森川"), Some((0, 6)));
+        assert_match!(c.matcher("shinsen").find("森川"), Some((0, 6)));
+
+        let c = config(ReadingKind::KunOnly);
+        assert_match!(c.matcher("morikawa").find("森川"), Some((0, 6)));
+        assert_match!(c.matcher("shinsen").find("森川"), None);
+        assert_match!(c.matcher("morisen").find("森川"), None);
+
+        let c = config(ReadingKind::OnOnly);
+        assert_match!(c.matcher("morikawa").find("森川"), None);
+        assert_match!(c.matcher("shinsen").find("森川"), Some((0, 6)));
+        // Kanas are not filtered
+        assert_match!(c.matcher("morikawa").find("もりかわ"), Some((0, 12)));
+    }
+
+    #[test]
+    fn max_readings() {
+        let romanizer = Default::default();
+        let c = MatchConfig::builder()
+            .romaji(
+                RomajiMatchConfig::builder()
+                    .romanizer(&romanizer)
+                    .max_readings(3)
+                    .include_nanori(true)
+                    .build(),
+            )
+            .build();
+        assert_match!(c.matcher("nichi").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("hi").find("日"), Some((0, 3)));
+        // Nanori
+        assert_match!(c.matcher("kusa").find("日"), None);
+        assert_match!(c.matcher("tachi").find("日"), None);
+
+        let c = MatchConfig::builder()
+            .romaji(
+                RomajiMatchConfig::builder()
+                    .romanizer(&romanizer)
+                    .readings(ReadingKind::KunOnly)
+                    .max_readings(1)
+                    .build(),
+            )
+            .build();
+        assert_match!(c.matcher("hi").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("nichi").find("日"), None);
+        assert_match!(c.matcher("ka").find("日"), None);
+    }
+
+    #[test]
+    fn include_nanori() {
+        let romanizer = Default::default();
+        let c = MatchConfig::builder()
+            .romaji(RomajiMatchConfig::builder().romanizer(&romanizer).build())
+            .build();
+        assert_match!(c.matcher("hi").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("kusa").find("日"), None);
+        assert_match!(c.matcher("manabu").find("学"), Some((0, 3)));
+        assert_match!(c.matcher("nori").find("学"), None);
+        // Untagged kanji are never filtered
+        assert_match!(c.matcher("kan").find("漢"), Some((0, 3)));
+
+        let c = MatchConfig::builder()
+            .romaji(
+                RomajiMatchConfig::builder()
+                    .romanizer(&romanizer)
+                    .include_nanori(true)
+                    .build(),
+            )
+            .build();
+        assert_match!(c.matcher("hi").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("kusa").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("nori").find("学"), Some((0, 3)));
+    }
+
+    #[test]
+    fn script() {
+        let romanizer = Default::default();
+        let config = |script| {
+            MatchConfig::builder()
+                .romaji(
+                    RomajiMatchConfig::builder()
+                        .romanizer(&romanizer)
+                        .script(script)
+                        .build(),
+                )
+                .build()
+        };
+
+        let c = config(ScriptFilter::Both);
+        assert_match!(c.matcher("kana").find("かな"), Some((0, 6)));
+        assert_match!(c.matcher("kana").find("カナ"), Some((0, 6)));
+
+        let c = config(ScriptFilter::HiraganaOnly);
+        assert_match!(c.matcher("kana").find("かな"), Some((0, 6)));
+        assert_match!(c.matcher("kana").find("カナ"), None);
+        assert_match!(c.matcher("kana").find("かナ"), None);
+        assert_match!(c.matcher("kana").find("ｶﾅかな"), Some((6, 6)));
+        // Kanji and mixed words
+        assert_match!(c.matcher("hi").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("ocha").find("お茶"), Some((0, 6)));
+        assert_match!(c.matcher("sugooi").find("すごーい"), Some((0, 12)));
+
+        let c = config(ScriptFilter::KatakanaOnly);
+        assert_match!(c.matcher("kana").find("かな"), None);
+        assert_match!(c.matcher("kana").find("カナ"), Some((0, 6)));
+        assert_match!(c.matcher("kana").find("ｶﾅ"), Some((0, 6)));
+        assert_match!(c.matcher("hi").find("日"), Some((0, 3)));
+        assert_match!(c.matcher("ocha").find("お茶"), None);
+        assert_match!(c.matcher("koohii").find("コーヒー"), Some((0, 12)));
+    }
+
+    #[test]
+    fn degeminate() {
+        assert_eq!(
+            RomajiMatcher::degeminate("chotto").as_deref(),
+            Some("choto")
+        );
+        assert_eq!(RomajiMatcher::degeminate("tto").as_deref(), Some("to"));
+        assert_eq!(RomajiMatcher::degeminate("ssha").as_deref(), Some("sha"));
+        assert_eq!(
+            RomajiMatcher::degeminate("matcha").as_deref(),
+            Some("macha")
+        );
+        assert_eq!(
+            RomajiMatcher::degeminate("maccha").as_deref(),
+            Some("macha")
+        );
+        assert_eq!(
+            RomajiMatcher::degeminate("gakkou").as_deref(),
+            Some("gakou")
+        );
+        assert_eq!(RomajiMatcher::degeminate("konna"), None);
+        assert_eq!(RomajiMatcher::degeminate("koohii"), None);
+        assert_eq!(RomajiMatcher::degeminate("chi"), None);
+        assert_eq!(RomajiMatcher::degeminate(""), None);
+    }
+
+    #[test]
+    fn lenient_gemination() {
+        let romanizer = Default::default();
+        let config = |lenient_gemination| {
+            MatchConfig::builder()
+                .romaji(
+                    RomajiMatchConfig::builder()
+                        .romanizer(&romanizer)
+                        .lenient_gemination(lenient_gemination)
+                        .build(),
+                )
+                .build()
+        };
+
+        let c = config(false);
+        assert_match!(c.matcher("chotto").find("ちょっと"), Some((0, 12)));
+        assert_match!(c.matcher("choto").find("ちょっと"), None);
+
+        let c = config(true);
+        assert_match!(c.matcher("chotto").find("ちょっと"), Some((0, 12)));
+        assert_match!(c.matcher("choto").find("ちょっと"), Some((0, 12)));
+        assert_match!(c.matcher("chotomate").find("ちょっとまって"), Some((0, 21)));
+        assert_match!(c.matcher("macha").find("まっちゃ"), Some((0, 12)));
+        assert_match!(c.matcher("kipu").find("切符"), Some((0, 6)));
+        assert_match!(c.matcher("isho").find("一緒"), Some((0, 6)));
+        assert_match!(c.matcher("gakou").find("学校"), Some((0, 6)));
+        // Not a geminate
+        assert_match!(c.matcher("kona").find("こんな"), None);
+    }
+
+    #[test]
+    fn rendaku() {
+        let romanizer = Default::default();
+        let config = |rendaku| {
+            MatchConfig::builder()
+                .romaji(
+                    RomajiMatchConfig::builder()
+                        .romanizer(&romanizer)
+                        .rendaku(rendaku)
+                        .build(),
+                )
+                .build()
+        };
+
+        let c = config(false);
+        assert_match!(c.matcher("teratera").find("寺々"), Some((0, 6)));
+        assert_match!(c.matcher("teradera").find("寺々"), None);
+
+        let c = config(true);
+        assert_match!(c.matcher("teratera").find("寺々"), Some((0, 6)));
+        assert_match!(c.matcher("teradera").find("寺々"), Some((0, 6)));
+        // Only 々 is voiced
+        assert_match!(c.matcher("deradera").find("寺々"), None);
+        // `noma` can't be voiced
+        assert_match!(c.matcher("noma").find("々"), Some((0, 3)));
     }
 
     #[test]
@@ -0,0 +1,472 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use serde::Deserialize;
+
+#[cfg(feature = "unicode-normalization")]
+use super::Normalization;
+#[cfg(feature = "pinyin")]
+use super::PinyinMatchConfig;
+use super::{Case, EmptyBehavior, Lang, MatchConfig, PlainMatchConfig};
+#[cfg(feature = "romaji")]
+use super::{RomajiMatchConfig, ScriptFilter};
+#[cfg(feature = "romaji")]
+use crate::romaji::HepburnRomanizer;
+
+/// Deserializable settings of a [`MatchConfig`], e.g. users' search settings stored as JSON or TOML.
+///
+/// The fields are named and behave like the options of [`MatchConfigBuilder`](super::MatchConfigBuilder).
+/// All of them are optional and default to the defaults of the builder,
+/// and unknown fields are reported as errors by the deserializer.
+/// Enums are deserialized from their variant names, e.g. `"Insensitive"` for [`Case::Insensitive`].
+///
+/// Pinyin and romaji matching are enabled by the presence of [`pinyin`](MatchConfigSpec::pinyin) and [`romaji`](MatchConfigSpec::romaji),
+/// e.g. `"romaji": {}` enables romaji matching with the default settings.
+/// Plain matching can be disabled by leaving [`Lang::Literal`] out of [`lang_priority`](MatchConfigSpec::lang_priority).
+///
+/// Use [`MatchConfig::from_spec()`] to convert it to a [`MatchConfig`], which also validates the flag names.
+///
+/// ## Example
+/// ```
+/// use ib_matcher::matcher::{MatchConfig, MatchConfigSpec};
+///
+/// let spec: MatchConfigSpec = serde_json::from_str(
+///     r#"{
+///         "case": "Insensitive",
+///         "mix_lang": true,
+///         "pinyin": { "notations": "Ascii | AsciiFirstLetter" },
+///         "romaji": { "partial_word": false }
+///     }"#,
+/// )
+/// .unwrap();
+/// let config = MatchConfig::from_spec(&spec).unwrap();
+/// assert!(config.matcher("PYSS").is_match("拼音搜索"));
+/// assert!(config.matcher("kono").is_match("この"));
+///
+/// // Unknown fields
+/// assert!(serde_json::from_str::<MatchConfigSpec>(r#"{ "mixlang": true }"#).is_err());
+/// ```
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatchConfigSpec {
+    pub analyze: bool,
+    pub is_pattern_partial: bool,
+    pub starts_with: bool,
+    pub ends_with: bool,
+    pub stem_only: bool,
+    pub min_match_len: usize,
+    pub max_match_len: Option<usize>,
+    pub longest: bool,
+    pub empty_pattern: EmptyBehavior,
+    pub case: Option<Case>,
+    pub plain: PlainMatchSpec,
+    pub mix_lang: bool,
+    /// Default: [`Lang::DEFAULT_PRIORITY`]
+    pub lang_priority: Option<Vec<Lang>>,
+    pub lang_fallback: bool,
+    pub fullwidth_ascii: bool,
+    pub line_terminator: Option<u8>,
+    #[cfg(feature = "unicode-normalization")]
+    pub normalize: Normalization,
+    #[cfg(feature = "unicode-segmentation")]
+    pub graphemes: bool,
+    /// `None` disables pinyin matching.
+    ///
+    /// Available without the `pinyin` feature, but [`MatchConfig::from_spec()`] returns [`MatchConfigSpecError::LangNotEnabled`] if it is set.
+    pub pinyin: Option<PinyinMatchSpec>,
+    /// `None` disables romaji matching.
+    ///
+    /// Available without the `romaji` feature, but [`MatchConfig::from_spec()`] returns [`MatchConfigSpecError::LangNotEnabled`] if it is set.
+    pub romaji: Option<RomajiMatchSpec>,
+}
+
+impl Default for MatchConfigSpec {
+    fn default() -> Self {
+        Self {
+            analyze: false,
+            is_pattern_partial: false,
+            starts_with: false,
+            ends_with: false,
+            stem_only: false,
+            min_match_len: 0,
+            max_match_len: None,
+            longest: false,
+            empty_pattern: Default::default(),
+            case: None,
+            plain: Default::default(),
+            mix_lang: false,
+            lang_priority: None,
+            lang_fallback: true,
+            fullwidth_ascii: false,
+            line_terminator: None,
+            #[cfg(feature = "unicode-normalization")]
+            normalize: Default::default(),
+            #[cfg(feature = "unicode-segmentation")]
+            graphemes: false,
+            pinyin: None,
+            romaji: None,
+        }
+    }
+}
+
+/// See [`MatchConfigSpec::plain`] and [`PlainMatchConfigBuilder`](super::PlainMatchConfigBuilder).
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlainMatchSpec {
+    pub case_insensitive: Option<bool>,
+}
+
+/// See [`MatchConfigSpec::pinyin`] and [`PinyinMatchConfigBuilder`](super::PinyinMatchConfigBuilder).
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct PinyinMatchSpec {
+    /// [`PinyinNotation`](crate::pinyin::PinyinNotation) names separated by `|`, e.g. `"Ascii | AsciiFirstLetter"`.
+    ///
+    /// Default: `"Ascii | AsciiFirstLetter"`, like [`PinyinMatchConfig::default()`](super::PinyinMatchConfig::default).
+    pub notations: String,
+    pub case_insensitive: Option<bool>,
+    pub allow_partial_pattern: bool,
+    /// [`FuzzyFlags`](crate::pinyin::FuzzyFlags) names separated by `|`, e.g. `"ZZh | CCh"`.
+    pub fuzzy: String,
+    /// See [`PinyinMatchConfigBuilder::separator()`](super::PinyinMatchConfigBuilder::separator).
+    pub separator: Option<char>,
+}
+
+impl Default for PinyinMatchSpec {
+    fn default() -> Self {
+        Self {
+            notations: "Ascii | AsciiFirstLetter".into(),
+            case_insensitive: None,
+            allow_partial_pattern: true,
+            fuzzy: String::new(),
+            separator: None,
+        }
+    }
+}
+
+#[cfg(feature = "pinyin")]
+impl PinyinMatchSpec {
+    fn to_config(&self) -> Result<PinyinMatchConfig<'static>, MatchConfigSpecError> {
+        let notations = bitflags::parser::from_str(&self.notations)
+            .map_err(|_| MatchConfigSpecError::InvalidPinyinNotations(self.notations.clone()))?;
+        let fuzzy = bitflags::parser::from_str(&self.fuzzy)
+            .map_err(|_| MatchConfigSpecError::InvalidFuzzyFlags(self.fuzzy.clone()))?;
+        Ok(PinyinMatchConfig::builder(notations)
+            .maybe_case_insensitive(self.case_insensitive)
+            .allow_partial_pattern(self.allow_partial_pattern)
+            .fuzzy(fuzzy)
+            .maybe_separator(self.separator)
+            .build())
+    }
+}
+
+/// See [`MatchConfigSpec::romaji`] and [`RomajiMatchConfigBuilder`](super::RomajiMatchConfigBuilder).
+///
+/// Each [`MatchConfig`] created from it owns a new romanizer, so create the config once and share it with [`MatchConfig::matcher()`].
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct RomajiMatchSpec {
+    pub case_insensitive: Option<bool>,
+    pub partial_word: bool,
+    pub allow_partial_pattern: bool,
+    pub max_readings: Option<usize>,
+    pub include_nanori: bool,
+    #[cfg(feature = "romaji")]
+    pub script: ScriptFilter,
+    pub lenient_gemination: bool,
+    pub rendaku: bool,
+}
+
+impl Default for RomajiMatchSpec {
+    fn default() -> Self {
+        Self {
+            case_insensitive: None,
+            partial_word: true,
+            allow_partial_pattern: true,
+            max_readings: None,
+            include_nanori: false,
+            #[cfg(feature = "romaji")]
+            script: Default::default(),
+            lenient_gemination: false,
+            rendaku: false,
+        }
+    }
+}
+
+#[cfg(feature = "romaji")]
+impl RomajiMatchSpec {
+    /// - `romanizer`: Share it instead of creating a new one.
+    fn to_config<'a>(&self, romanizer: Option<&'a HepburnRomanizer>) -> RomajiMatchConfig<'a> {
+        RomajiMatchConfig::builder()
+            .maybe_romanizer(romanizer)
+            .maybe_case_insensitive(self.case_insensitive)
+            .partial_word(self.partial_word)
+            .allow_partial_pattern(self.allow_partial_pattern)
+            .maybe_max_readings(self.max_readings)
+            .include_nanori(self.include_nanori)
+            .script(self.script)
+            .lenient_gemination(self.lenient_gemination)
+            .rendaku(self.rendaku)
+            .build()
+    }
+}
+
+/// Error of [`MatchConfig::from_spec()`].
+///
+/// Unknown fields and invalid enum values are reported by the deserializer instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MatchConfigSpecError {
+    /// Unknown or malformed pinyin notation names.
+    InvalidPinyinNotations(String),
+    /// Unknown or malformed fuzzy pinyin flag names.
+    InvalidFuzzyFlags(String),
+    /// The language is not enabled by the crate features, e.g. [`MatchConfigSpec::pinyin`] without the `pinyin` feature.
+    LangNotEnabled(Lang),
+    /// `min_match_len` is greater than `max_match_len`.
+    InvalidMatchLen { min: usize, max: usize },
+}
+
+impl fmt::Display for MatchConfigSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchConfigSpecError::InvalidPinyinNotations(notations) => {
+                write!(f, "invalid pinyin notations: {notations:?}")
+            }
+            MatchConfigSpecError::InvalidFuzzyFlags(fuzzy) => {
+                write!(f, "invalid fuzzy pinyin flags: {fuzzy:?}")
+            }
+            MatchConfigSpecError::LangNotEnabled(lang) => {
+                write!(f, "{lang:?} matching is not enabled by the crate features")
+            }
+            MatchConfigSpecError::InvalidMatchLen { min, max } => {
+                write!(f, "min_match_len {min} is greater than max_match_len {max}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MatchConfigSpecError {}
+
+impl<'a> MatchConfig<'a> {
+    /// Create a config from deserialized settings. See [`MatchConfigSpec`] for details.
+    pub fn from_spec(spec: &'a MatchConfigSpec) -> Result<Self, MatchConfigSpecError> {
+        Self::from_spec_with(
+            spec,
+            #[cfg(feature = "romaji")]
+            None,
+        )
+    }
+
+    /// [`MatchConfig::from_spec()`], but sharing `romanizer` instead of creating a new one if set.
+    pub(crate) fn from_spec_with(
+        spec: &'a MatchConfigSpec,
+        #[cfg(feature = "romaji")] romanizer: Option<&'a HepburnRomanizer>,
+    ) -> Result<Self, MatchConfigSpecError> {
+        if let Some(max) = spec.max_match_len {
+            if spec.min_match_len > max {
+                return Err(MatchConfigSpecError::InvalidMatchLen {
+                    min: spec.min_match_len,
+                    max,
+                });
+            }
+        }
+
+        let builder = MatchConfig::builder()
+            .analyze(spec.analyze)
+            .is_pattern_partial(spec.is_pattern_partial)
+            .starts_with(spec.starts_with)
+            .ends_with(spec.ends_with)
+            .stem_only(spec.stem_only)
+            .min_match_len(spec.min_match_len)
+            .maybe_max_match_len(spec.max_match_len)
+            .longest(spec.longest)
+            .empty_pattern(spec.empty_pattern)
+            .maybe_case(spec.case)
+            .plain(Some(
+                PlainMatchConfig::builder()
+                    .maybe_case_insensitive(spec.plain.case_insensitive)
+                    .build(),
+            ))
+            .mix_lang(spec.mix_lang)
+            .lang_priority(
+                spec.lang_priority
+                    .as_deref()
+                    .unwrap_or(Lang::DEFAULT_PRIORITY),
+            )
+            .lang_fallback(spec.lang_fallback)
+            .fullwidth_ascii(spec.fullwidth_ascii)
+            .maybe_line_terminator(spec.line_terminator);
+        #[cfg(feature = "unicode-normalization")]
+        let builder = builder.normalize(spec.normalize);
+        #[cfg(feature = "unicode-segmentation")]
+        let builder = builder.graphemes(spec.graphemes);
+
+        #[cfg(feature = "pinyin")]
+        let builder = builder.maybe_pinyin(
+            spec.pinyin
+                .as_ref()
+                .map(PinyinMatchSpec::to_config)
+                .transpose()?,
+        );
+        #[cfg(not(feature = "pinyin"))]
+        if spec.pinyin.is_some() {
+            return Err(MatchConfigSpecError::LangNotEnabled(Lang::Pinyin));
+        }
+
+        #[cfg(feature = "romaji")]
+        let builder = builder.maybe_romaji(spec.romaji.as_ref().map(|r| r.to_config(romanizer)));
+        #[cfg(not(feature = "romaji"))]
+        if spec.romaji.is_some() {
+            return Err(MatchConfigSpecError::LangNotEnabled(Lang::Romaji));
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> MatchConfigSpec {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn default() {
+        let spec = parse("{}");
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(config.matcher("ab").is_match("aB"));
+        assert!(!config.matcher("pinyin").is_match("拼音"));
+        assert!(config.matcher("").is_match(""));
+    }
+
+    #[test]
+    fn options() {
+        let spec = parse(
+            r#"{
+                "starts_with": true,
+                "max_match_len": 2,
+                "empty_pattern": "MatchNone",
+                "plain": { "case_insensitive": false }
+            }"#,
+        );
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(config.matcher("ab").is_match("abc"));
+        assert!(!config.matcher("ab").is_match("cab"));
+        assert!(!config.matcher("ab").is_match("AB"));
+        assert!(!config.matcher("abc").is_match("abc"));
+        assert!(!config.matcher("").is_match(""));
+
+        let spec = parse(r#"{ "case": "Sensitive", "lang_priority": ["Pinyin"] }"#);
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(!config.matcher("ab").is_match("ab"));
+    }
+
+    #[cfg(feature = "pinyin")]
+    #[test]
+    fn pinyin() {
+        let spec = parse(r#"{ "pinyin": {} }"#);
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(config.matcher("pinyin").is_match("拼音"));
+        assert!(config.matcher("py").is_match("拼音"));
+        assert!(!config.matcher("PY").is_match("拼音"));
+
+        let spec = parse(
+            r#"{
+                "case": "Insensitive",
+                "pinyin": { "notations": "AsciiFirstLetter", "fuzzy": "ZZh" }
+            }"#,
+        );
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(config.matcher("PY").is_match("拼音"));
+        assert!(!config.matcher("pinyin").is_match("拼音"));
+        assert!(config.matcher("zg").is_match("中国"));
+
+        let spec = parse(
+            r#"{
+                "case": "Insensitive",
+                "pinyin": { "notations": "Ascii", "case_insensitive": false }
+            }"#,
+        );
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(!config.matcher("PINYIN").is_match("拼音"));
+        assert!(config.matcher("PINYIN").is_match("pinyin"));
+
+        let spec = parse(r#"{ "pinyin": { "separator": "'" } }"#);
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(config.matcher("xi'an").is_match("西安"));
+        assert!(!config.matcher("xi'an").is_match("先"));
+    }
+
+    #[cfg(feature = "romaji")]
+    #[test]
+    fn romaji() {
+        let spec = parse(r#"{ "romaji": {} }"#);
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(config.matcher("kono").is_match("この"));
+        assert!(config.matcher("konosuba").is_match("この素晴らしい"));
+
+        let spec = parse(r#"{ "romaji": { "partial_word": false, "script": "KatakanaOnly" } }"#);
+        let config = MatchConfig::from_spec(&spec).unwrap();
+        assert!(!config.matcher("kono").is_match("この"));
+        assert!(config.matcher("kono").is_match("コノ"));
+        assert!(!config.matcher("konosuba").is_match("この素晴らしい"));
+    }
+
+    #[test]
+    fn errors() {
+        // Unknown fields
+        for json in [
+            r#"{ "unknown": true }"#,
+            r#"{ "plain": { "unknown": true } }"#,
+            r#"{ "pinyin": { "notation": "Ascii" } }"#,
+            r#"{ "romaji": { "unknown": true } }"#,
+            r#"{ "case": "Unknown" }"#,
+            r#"{ "lang_priority": ["English"] }"#,
+        ] {
+            assert!(
+                serde_json::from_str::<MatchConfigSpec>(json).is_err(),
+                "{json}"
+            );
+        }
+
+        let error = |json| MatchConfig::from_spec(&parse(json)).err();
+        assert_eq!(
+            error(r#"{ "min_match_len": 3, "max_match_len": 2 }"#),
+            Some(MatchConfigSpecError::InvalidMatchLen { min: 3, max: 2 })
+        );
+
+        #[cfg(feature = "pinyin")]
+        {
+            assert_eq!(
+                error(r#"{ "pinyin": { "notations": "Ascii | Unknown" } }"#),
+                Some(MatchConfigSpecError::InvalidPinyinNotations(
+                    "Ascii | Unknown".into()
+                ))
+            );
+            assert_eq!(
+                error(r#"{ "pinyin": { "notations": "Ascii |" } }"#),
+                Some(MatchConfigSpecError::InvalidPinyinNotations(
+                    "Ascii |".into()
+                ))
+            );
+            assert_eq!(
+                error(r#"{ "pinyin": { "fuzzy": "ZZ" } }"#),
+                Some(MatchConfigSpecError::InvalidFuzzyFlags("ZZ".into()))
+            );
+        }
+        #[cfg(not(feature = "pinyin"))]
+        assert_eq!(
+            error(r#"{ "pinyin": {} }"#),
+            Some(MatchConfigSpecError::LangNotEnabled(Lang::Pinyin))
+        );
+        #[cfg(not(feature = "romaji"))]
+        assert_eq!(
+            error(r#"{ "romaji": {} }"#),
+            Some(MatchConfigSpecError::LangNotEnabled(Lang::Romaji))
+        );
+    }
+}
@@ -5,7 +5,10 @@ pub struct MatcherFlags(u32);
 
 bitflags::bitflags! {
     impl MatcherFlags: u32 {
+        /// 带声调符号全拼
+        ///
         /// e.g. "pīn", "yīn"
+        #[doc(alias = "带声调符号全拼")]
         const Unicode = 0x8;
 
         /// 全拼
@@ -68,6 +71,14 @@ bitflags::bitflags! {
         #[doc(alias = "自然码双拼")]
         const DiletterZrm = 0x200;
 
+        /// 注音符号（不带声调）
+        ///
+        /// e.g. "ㄆㄧㄣ", "ㄧㄣ"
+        ///
+        /// See [Bopomofo](https://en.wikipedia.org/wiki/Bopomofo) for details.
+        #[doc(alias("注音", "注音符号", "Bopomofo"))]
+        const Zhuyin = 0x400;
+
         const PinyinNotationMask = 0xFFF;
 
         /// 允许部分拼音匹配
@@ -77,6 +88,12 @@ bitflags::bitflags! {
     }
 }
 
+impl MatcherFlags {
+    /// Alias of [`MatcherFlags::Unicode`].
+    #[allow(non_upper_case_globals)]
+    pub const UnicodeTone: Self = Self::Unicode;
+}
+
 impl From<PinyinNotation> for MatcherFlags {
     fn from(value: PinyinNotation) -> Self {
         Self(value.bits())
@@ -0,0 +1,193 @@
+/*!
+Fuzzy pinyin (模糊音).
+
+Many speakers of southern Chinese dialects can't reliably distinguish some initials and finals,
+e.g. "zh" and "z", "in" and "ing". With fuzzy pinyin, these pairs are treated as equivalent,
+so "zong" can match "种" (zhǒng) and "jing" can match "今" (jīn).
+
+Fuzzy pinyin only applies to [`PinyinNotation::Ascii`], [`PinyinNotation::AsciiTone`] and
+[`PinyinNotation::AsciiFirstLetter`] (single-letter initials only, i.e. l ↔ n, f ↔ h and l ↔ r).
+
+## Supported pairs
+| Flag | Pair |
+| --- | --- |
+| [`FuzzyFlags::ZZh`] | z ↔ zh |
+| [`FuzzyFlags::CCh`] | c ↔ ch |
+| [`FuzzyFlags::SSh`] | s ↔ sh |
+| [`FuzzyFlags::LN`] | l ↔ n |
+| [`FuzzyFlags::FH`] | f ↔ h |
+| [`FuzzyFlags::LR`] | l ↔ r |
+| [`FuzzyFlags::AnAng`] | an ↔ ang |
+| [`FuzzyFlags::EnEng`] | en ↔ eng |
+| [`FuzzyFlags::InIng`] | in ↔ ing |
+| [`FuzzyFlags::IanIang`] | ian ↔ iang |
+| [`FuzzyFlags::UanUang`] | uan ↔ uang |
+
+The finals must match as a whole, e.g. [`FuzzyFlags::AnAng`] does not make "ian" match "iang".
+*/
+use super::{PinyinNotation, PinyinString};
+
+bitflags::bitflags! {
+    /// See [`fuzzy`](super::fuzzy) for details.
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+    pub struct FuzzyFlags: u32 {
+        /// z ↔ zh
+        const ZZh = 0x1;
+        /// c ↔ ch
+        const CCh = 0x2;
+        /// s ↔ sh
+        const SSh = 0x4;
+        /// l ↔ n
+        const LN = 0x8;
+        /// f ↔ h
+        const FH = 0x10;
+        /// l ↔ r
+        const LR = 0x20;
+
+        /// an ↔ ang
+        const AnAng = 0x100;
+        /// en ↔ eng
+        const EnEng = 0x200;
+        /// in ↔ ing
+        const InIng = 0x400;
+        /// ian ↔ iang
+        const IanIang = 0x800;
+        /// uan ↔ uang
+        const UanUang = 0x1000;
+
+        /// z ↔ zh, c ↔ ch, s ↔ sh
+        const Retroflex = Self::ZZh.bits() | Self::CCh.bits() | Self::SSh.bits();
+        /// an ↔ ang, en ↔ eng, in ↔ ing, ian ↔ iang, uan ↔ uang
+        const Nasal = Self::AnAng.bits()
+            | Self::EnEng.bits()
+            | Self::InIng.bits()
+            | Self::IanIang.bits()
+            | Self::UanUang.bits();
+    }
+}
+
+impl FuzzyFlags {
+    /// Notations that fuzzy pinyin applies to.
+    pub(crate) const NOTATIONS: PinyinNotation = PinyinNotation::Ascii
+        .union(PinyinNotation::AsciiTone)
+        .union(PinyinNotation::AsciiFirstLetter);
+
+    #[rustfmt::skip]
+    const INITIALS: [(FuzzyFlags, &'static str, &'static str); 6] = [
+        (FuzzyFlags::ZZh, "z", "zh"),
+        (FuzzyFlags::CCh, "c", "ch"),
+        (FuzzyFlags::SSh, "s", "sh"),
+        (FuzzyFlags::LN, "l", "n"),
+        (FuzzyFlags::FH, "f", "h"),
+        (FuzzyFlags::LR, "l", "r"),
+    ];
+
+    #[rustfmt::skip]
+    const FINALS: [(FuzzyFlags, &'static str, &'static str); 5] = [
+        (FuzzyFlags::AnAng, "an", "ang"),
+        (FuzzyFlags::EnEng, "en", "eng"),
+        (FuzzyFlags::InIng, "in", "ing"),
+        (FuzzyFlags::IanIang, "ian", "iang"),
+        (FuzzyFlags::UanUang, "uan", "uang"),
+    ];
+
+    fn alternatives<'a>(
+        self,
+        pairs: &'a [(FuzzyFlags, &'static str, &'static str)],
+        s: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        pairs
+            .iter()
+            .filter(move |(flag, _, _)| self.contains(*flag))
+            .filter_map(move |&(_, a, b)| match s {
+                _ if s == a => Some(b),
+                _ if s == b => Some(a),
+                _ => None,
+            })
+    }
+
+    /// Call `f` with each fuzzy variant of an ASCII pinyin, excluding the pinyin itself.
+    ///
+    /// `ascii` can be in [`PinyinNotation::Ascii`], [`PinyinNotation::AsciiTone`] or [`PinyinNotation::AsciiFirstLetter`].
+    pub(crate) fn try_for_each_variant<T>(
+        self,
+        ascii: &str,
+        mut f: impl FnMut(&str) -> Option<T>,
+    ) -> Option<T> {
+        debug_assert!(ascii.is_ascii());
+
+        let (body, tone) = match ascii.as_bytes().last() {
+            Some(b'1'..=b'5') => ascii.split_at(ascii.len() - 1),
+            _ => (ascii, ""),
+        };
+        // Not Pinyin::split_initial_final(), which requires a full pinyin
+        let (initial, final_) = body.split_at(match body.as_bytes() {
+            [b'z' | b'c' | b's', b'h', ..] => 2,
+            [b'a' | b'e' | b'i' | b'o' | b'u' | b'v', ..] => 0,
+            _ => 1.min(body.len()),
+        });
+
+        let initials = [initial].into_iter().chain(
+            self.alternatives(&Self::INITIALS, initial)
+                // AsciiFirstLetter is a single letter
+                .filter(|alt| !final_.is_empty() || alt.len() == 1),
+        );
+        for (i, initial) in initials.enumerate() {
+            let finals = [final_]
+                .into_iter()
+                .chain(self.alternatives(&Self::FINALS, final_));
+            for (j, final_) in finals.enumerate() {
+                if i == 0 && j == 0 {
+                    continue;
+                }
+                let mut variant = PinyinString::new();
+                variant.extend([initial, final_, tone]);
+                if let Some(v) = f(&variant) {
+                    return Some(v);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(fuzzy: FuzzyFlags, ascii: &str) -> Vec<String> {
+        let mut variants = Vec::new();
+        fuzzy.try_for_each_variant(ascii, |variant| {
+            variants.push(variant.to_string());
+            None::<()>
+        });
+        variants
+    }
+
+    #[test]
+    fn variant() {
+        assert_eq!(variants(FuzzyFlags::empty(), "zhong"), [""; 0]);
+        assert_eq!(variants(FuzzyFlags::ZZh, "zhong"), ["zong"]);
+        assert_eq!(variants(FuzzyFlags::ZZh, "zong"), ["zhong"]);
+        assert_eq!(variants(FuzzyFlags::CCh, "zhong"), [""; 0]);
+        assert_eq!(
+            variants(FuzzyFlags::SSh | FuzzyFlags::InIng, "shin"),
+            ["shing", "sin", "sing"]
+        );
+        assert_eq!(
+            variants(FuzzyFlags::LN | FuzzyFlags::LR, "lan"),
+            ["nan", "ran"]
+        );
+        assert_eq!(variants(FuzzyFlags::AnAng, "jian"), [""; 0]);
+        assert_eq!(variants(FuzzyFlags::IanIang, "jian"), ["jiang"]);
+
+        // AsciiTone
+        assert_eq!(variants(FuzzyFlags::ZZh, "zhong4"), ["zong4"]);
+        assert_eq!(variants(FuzzyFlags::InIng, "jin1"), ["jing1"]);
+
+        // AsciiFirstLetter
+        assert_eq!(variants(FuzzyFlags::LN, "l"), ["n"]);
+        assert_eq!(variants(FuzzyFlags::ZZh, "z"), [""; 0]);
+        assert_eq!(variants(FuzzyFlags::all(), "a"), [""; 0]);
+    }
+}
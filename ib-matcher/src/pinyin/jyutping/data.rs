@@ -0,0 +1,16 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+use super::JyutpingCombination;
+
+const F: u16 = u16::MAX;
+
+pub(super) const JYUTPINGS: [&str; 162] = [
+"baak","baat","bak","bat","bin","bing","biu","bou","bun","caa","caan","cam","can","cat","ce","ceot","cin","cit","coeng","cung","daai","dei","di","dik","dim","din","ding","dok","dong","dou","dung","faan","fo","gaa","gaai","gaak","gaan","gau","ge","gei","geoi","gin","go","gok","gong","gou","gung","gwaan","gwok","gwong","haa","haang","hai","hak","hang","hau","hek","heoi","ho","hoeng","hoi","hok","hong","hou","hung","jam","jan","jap","jat","jau","je","ji","jing","joeng","jung","jyu","jyun","jyut","keoi","kui","kuk","lai","lam","lau","lei","ling","liu","lo","loeng","loi","lok","long","lou","luk","lung","m","maa","maan","man","mat","me","meng","ming","mou","mun","naa","naam","nei","ng","ngo","ngok","nin","nou","oi","on","pin","ping","saa","saam","saan","saang","sai","sam","san","sang","sap","sau","sei","seoi","si","sik","siu","soeng","sok","syu","taa","tai","teng","tin","tit","tou","tung","tyun","waa","waan","wo","wong","wu","wui","zaam","zaau","zau","ze","zeoi","zi","zim","ziu","zo","zoeng","zoi","zok","zung"];
+
+pub(super) const JYUTPING_COMBINATION_LEN: usize = 3;
+
+pub(super) static JYUTPING_COMBINATIONS: [JyutpingCombination; 10] = [
+[14,40,F],[18,158,F],[19,161,F],[29,27,F],[54,62,51],[90,110,F],[101,102,F],[116,5,F],[120,124,F],[148,79,F]];
+
+pub(super) static JYUTPING_TABLE: [(char, u16); 237] = [
+('\u{4E00}',68),('\u{4E03}',13),('\u{4E07}',97),('\u{4E09}',118),('\u{4E0A}',132),('\u{4E0B}',50),('\u{4E0D}',3),('\u{4E1C}',30),('\u{4E2A}',42),('\u{4E2D}',161),('\u{4E50}',167),('\u{4E5C}',99),('\u{4E5D}',37),('\u{4E66}',134),('\u{4E8C}',71),('\u{4E94}',108),('\u{4EBA}',66),('\u{4ED6}',135),('\u{4EEC}',104),('\u{4EF6}',41),('\u{4F1A}',171),('\u{4F55}',58),('\u{4F5C}',160),('\u{4F60}',107),('\u{4F62}',78),('\u{4F86}',89),('\u{4FC2}',52),('\u{500B}',42),('\u{5011}',104),('\u{5143}',76),('\u{5165}',67),('\u{516B}',1),('\u{516C}',46),('\u{516D}',93),('\u{5173}',47),('\u{5187}',103),('\u{51FA}',15),('\u{5218}',83),('\u{5289}',83),('\u{5317}',2),('\u{5341}',125),('\u{5343}',16),('\u{5357}',106),('\u{5385}',137),('\u{53BB}',57),('\u{53E3}',55),('\u{53F8}',129),('\u{5403}',56),('\u{540D}',168),('\u{5433}',108),('\u{5434}',108),('\u{544A}',45),('\u{5468}',151),('\u{5480}',153),('\u{548C}',145),('\u{5497}',157),('\u{54A9}',100),('\u{5514}',95),('\u{5572}',22),('\u{55BA}',52),('\u{5605}',38),('\u{5622}',70),('\u{569F}',81),('\u{56DB}',127),('\u{56FD}',48),('\u{56FE}',140),('\u{570B}',48),('\u{5716}',140),('\u{5728}',159),('\u{5730}',21),('\u{5831}',7),('\u{5927}',20),('\u{5929}',138),('\u{5979}',135),('\u{597D}',63),('\u{5B57}',154),('\u{5B66}',61),('\u{5B78}',61),('\u{5B9A}',26),('\u{5BB6}',33),('\u{5BFB}',11),('\u{5C0B}',11),('\u{5C0F}',131),('\u{5C16}',155),('\u{5C6F}',142),('\u{5C71}',119),('\u{5C9B}',29),('\u{5CF6}',29),('\u{5DDE}',151),('\u{5DE5}',46),('\u{5E74}',111),('\u{5E7F}',49),('\u{5E97}',24),('\u{5EA6}',165),('\u{5EE3}',49),('\u{5EF3}',137),('\u{5F00}',60),('\u{5F20}',158),('\u{5F35}',158),('\u{5F71}',72),('\u{5FC3}',122),('\u{611B}',113),('\u{6211}',109),('\u{6236}',147),('\u{6237}',147),('\u{624B}',126),('\u{627E}',150),('\u{62A5}',7),('\u{62FC}',169),('\u{641C}',126),('\u{6587}',98),('\u{6599}',86),('\u{65B0}',123),('\u{65E5}',68),('\u{65E7}',37),('\u{65F6}',129),('\u{65FA}',146),('\u{660E}',102),('\u{662F}',129),('\u{6642}',129),('\u{66F2}',80),('\u{66F8}',134),('\u{6703}',171),('\u{6708}',77),('\u{6709}',69),('\u{6717}',91),('\u{672C}',8),('\u{673A}',39),('\u{674E}',84),('\u{6765}',89),('\u{6771}',30),('\u{6797}',82),('\u{6837}',73),('\u{683C}',35),('\u{6848}',114),('\u{6863}',28),('\u{6881}',88),('\u{697C}',83),('\u{6A02}',167),('\u{6A13}',83),('\u{6A23}',73),('\u{6A5F}',39),('\u{6A94}',28),('\u{6B4C}',42),('\u{6C34}',128),('\u{6C99}',117),('\u{6E2F}',44),('\u{6E7E}',144),('\u{7063}',144),('\u{706B}',32),('\u{70B9}',24),('\u{7167}',156),('\u{7231}',113),('\u{7247}',115),('\u{738B}',146),('\u{73AF}',144),('\u{74B0}',144),('\u{751F}',170),('\u{7528}',74),('\u{7530}',138),('\u{7535}',25),('\u{767D}',0),('\u{767E}',0),('\u{7684}',23),('\u{76F8}',132),('\u{7747}',136),('\u{7AD9}',149),('\u{7CA4}',77),('\u{7CB5}',77),('\u{7D05}',64),('\u{7D22}',133),('\u{7EA2}',64),('\u{8111}',112),('\u{8166}',112),('\u{820A}',37),('\u{8336}',9),('\u{842C}',97),('\u{884C}',166),('\u{8857}',34),('\u{8868}',6),('\u{897F}',121),('\u{89D2}',43),('\u{8A2D}',17),('\u{8A71}',143),('\u{8A9E}',75),('\u{8B1B}',44),('\u{8B1D}',152),('\u{8BB2}',44),('\u{8BBE}',17),('\u{8BDD}',143),('\u{8BED}',75),('\u{8C22}',152),('\u{8CC7}',154),('\u{8D44}',154),('\u{8DEF}',92),('\u{8ECA}',162),('\u{8F66}',162),('\u{8FB9}',4),('\u{8FD9}',152),('\u{9019}',152),('\u{9053}',29),('\u{908A}',4),('\u{90A3}',105),('\u{9152}',151),('\u{91CD}',164),('\u{9285}',141),('\u{9435}',139),('\u{947C}',87),('\u{94C1}',139),('\u{94DC}',141),('\u{9523}',87),('\u{9577}',163),('\u{957F}',163),('\u{9580}',104),('\u{958B}',60),('\u{9593}',36),('\u{95DC}',47),('\u{95E8}',104),('\u{95F4}',36),('\u{9648}',12),('\u{9673}',12),('\u{96F6}',85),('\u{96FB}',25),('\u{97F3}',65),('\u{98DF}',130),('\u{98EF}',31),('\u{9910}',10),('\u{996D}',31),('\u{9999}',59),('\u{99AC}',96),('\u{9A6C}',96),('\u{9EC3}',146),('\u{9EC4}',146),('\u{9ED1}',53),('\u{9EDE}',24),('\u{9F8D}',94),('\u{9F99}',94)];
@@ -0,0 +1,92 @@
+/*!
+Cantonese [Jyutping](https://en.wikipedia.org/wiki/Jyutping) (粵拼) readings of hanzi. See [`PinyinNotation::Jyutping`](super::PinyinNotation::Jyutping).
+
+The readings are generated from the `kCantonese` field of [Unihan](https://www.unicode.org/charts/unihan.html) by `ib-pinyin/data/generate_jyutping.py`,
+with tones removed.
+
+**Experimental and incomplete:** at the moment only a subset of about 240 common hanzi is included,
+other hanzi have no jyutpings and are never matched by them.
+
+## Memory usage
+The table takes 8 bytes per hanzi, plus the readings and their combinations.
+*/
+
+mod data;
+
+pub(super) type JyutpingCombination = [u16; data::JYUTPING_COMBINATION_LEN];
+
+fn get_jyutping_index(c: char) -> Option<u16> {
+    data::JYUTPING_TABLE
+        .binary_search_by_key(&c, |&(c, _)| c)
+        .ok()
+        .map(|i| data::JYUTPING_TABLE[i].1)
+}
+
+fn jyutping_combination(index: u16) -> impl Iterator<Item = &'static u16> {
+    data::JYUTPING_COMBINATIONS[index as usize]
+        .iter()
+        .take_while(|&&i| i != u16::MAX)
+}
+
+/// All jyutpings, sorted and deduplicated.
+// `copied()` can't shorten the lifetime to be the same as other notations
+#[allow(clippy::map_clone)]
+pub(super) fn iter<'a>() -> impl Iterator<Item = &'a str> {
+    data::JYUTPINGS
+        .iter()
+        .map(|&jyutping| -> &'a str { jyutping })
+}
+
+pub(super) fn get_jyutpings_and_try_for_each<T>(
+    c: char,
+    mut f: impl FnMut(&'static str) -> Option<T>,
+) -> Option<T> {
+    let i = get_jyutping_index(c)?;
+    if i < data::JYUTPINGS.len() as u16 {
+        f(data::JYUTPINGS[i as usize])
+    } else {
+        let i = i - data::JYUTPINGS.len() as u16;
+        jyutping_combination(i).find_map(|&i| f(data::JYUTPINGS[i as usize]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jyutpings(c: char) -> Vec<&'static str> {
+        let mut jyutpings = Vec::new();
+        get_jyutpings_and_try_for_each(c, |jyutping| {
+            jyutpings.push(jyutping);
+            None::<()>
+        });
+        jyutpings
+    }
+
+    #[test]
+    fn data() {
+        assert!(data::JYUTPINGS.windows(2).all(|w| w[0] < w[1]));
+        assert!(data::JYUTPING_TABLE.windows(2).all(|w| w[0].0 < w[1].0));
+        for jyutping in data::JYUTPINGS {
+            assert!(jyutping.len() <= 6, "{jyutping}");
+            assert!(
+                jyutping.bytes().all(|b| b.is_ascii_lowercase()),
+                "{jyutping}"
+            );
+        }
+        // Also used to fast fail
+        for &(c, _) in data::JYUTPING_TABLE.iter() {
+            assert!(super::super::PinyinData::may_cover(c), "{c}");
+        }
+    }
+
+    #[test]
+    fn get_jyutpings() {
+        assert_eq!(jyutpings('拼'), ["ping", "bing"]);
+        assert_eq!(jyutpings('音'), ["jam"]);
+        // Tones are removed
+        assert_eq!(jyutpings('中'), ["zung"]);
+        assert_eq!(jyutpings('行'), ["hang", "hong", "haang"]);
+        assert_eq!(jyutpings('a'), [""; 0]);
+    }
+}
@@ -13,14 +13,27 @@ See [Pinyin](https://en.wikipedia.org/wiki/Pinyin) for what is pinyin.
   - 华宇双拼（紫光双拼）
   - 小鹤双拼
   - 自然码双拼
-- 支持多音字。
+  - [自定义双拼](shuangpin)
+  - 注音符号（“ㄆㄧㄣㄧㄣ”）
+  - 粤拼（“pingjam”，需启用 `pinyin-jyutping` feature）
+  - 威妥玛拼音（“p'inyin”，撇号可省略，需启用 `pinyin-wade-giles` feature）
+- 支持多音字，以及[词语读音](phrase)（“重庆” 只匹配 “chongqing”）。
+- 支持[自定义汉字读音](reading)。
+- 支持[模糊音](fuzzy)（“zong” 匹配 “种”）。
 - 支持 Unicode 辅助平面汉字。
 */
 
-use std::ops::RangeInclusive;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::ops::RangeInclusive;
 
 mod data;
+pub mod fuzzy;
+#[cfg(feature = "pinyin-jyutping")]
+mod jyutping;
 mod notation;
+pub mod phrase;
+pub mod reading;
+pub mod shuangpin;
 
 pub(super) type PinyinCombination = [u16; data::PINYIN_COMBINATION_LEN];
 
@@ -38,20 +51,30 @@ impl PinyinRangeTable {
     }
 }
 
+pub use fuzzy::FuzzyFlags;
 use itertools::Itertools;
 pub use notation::PinyinNotation;
+pub use phrase::{PinyinPhrases, PinyinPhrasesError};
+pub use reading::{PinyinReadings, PinyinReadingsError};
+pub use shuangpin::{ShuangpinScheme, ShuangpinSchemeError, ShuangpinSchemeInfo};
 
 type PinyinString = arraystring::ArrayString<arraystring::typenum::U7>;
+/// Zhuyin needs 3 * 3 bytes, a separate type to not increase the memory usage of other notations.
+type ZhuyinString = arraystring::ArrayString<arraystring::typenum::U9>;
 
 #[cfg(not(feature = "inmut-data"))]
-type OptionalPinyinStringArray = Option<Box<[PinyinString]>>;
+type OptionalStringArray<S> = Option<Box<[S]>>;
 #[cfg(feature = "inmut-data")]
-type OptionalPinyinStringArray = std::sync::OnceLock<Box<[PinyinString]>>;
+type OptionalStringArray<S> = std::sync::OnceLock<Box<[S]>>;
+
+type OptionalPinyinStringArray = OptionalStringArray<PinyinString>;
 
 /// ## Memory usage
 /// Per pinyin notation: 8 * 1514 ≈ 11.8 KiB.
 /// - `Unicode` does not require extra memory.
 /// - `AsciiFirstLetter` uses the same storage as `Ascii`.
+/// - `Zhuyin`: 10 * 1514 ≈ 14.8 KiB.
+/// - `WadeGiles`: 2 * 8 * 1514 ≈ 23.7 KiB, with and without the apostrophes.
 ///
 /// ## Others
 /// TODO: Optionally generate pinyin notation data at build time.
@@ -79,6 +102,12 @@ pub struct PinyinData {
     diletter_thunisoft: OptionalPinyinStringArray,
     diletter_xiaohe: OptionalPinyinStringArray,
     diletter_zrm: OptionalPinyinStringArray,
+    zhuyin: OptionalStringArray<ZhuyinString>,
+    #[cfg(feature = "pinyin-wade-giles")]
+    wade_giles: OptionalPinyinStringArray,
+    /// For optional apostrophes in patterns.
+    #[cfg(feature = "pinyin-wade-giles")]
+    wade_giles_without_apostrophe: OptionalPinyinStringArray,
 }
 
 impl PinyinData {
@@ -95,12 +124,28 @@ impl PinyinData {
             diletter_thunisoft: Default::default(),
             diletter_xiaohe: Default::default(),
             diletter_zrm: Default::default(),
+            zhuyin: Default::default(),
+            #[cfg(feature = "pinyin-wade-giles")]
+            wade_giles: Default::default(),
+            #[cfg(feature = "pinyin-wade-giles")]
+            wade_giles_without_apostrophe: Default::default(),
         };
 
         pinyin_data.init_notations(notations);
         pinyin_data
     }
 
+    /// [`PinyinData::new()`] wrapped in [`Arc`], which can be shared across threads and any number of matchers
+    /// via [`PinyinMatchConfigBuilder::data()`](crate::matcher::PinyinMatchConfigBuilder::data),
+    /// without borrowing it.
+    ///
+    /// Without the `inmut-data` feature, the data can't be changed once shared, so `notations` must include all the notations used by the matchers.
+    /// With `inmut-data`, missing notations are inited on demand when building matchers,
+    /// at the cost of an atomic load on every access of a notation.
+    pub fn shared(notations: PinyinNotation) -> Arc<Self> {
+        Arc::new(Self::new(notations))
+    }
+
     const fn notation(&self, notation: PinyinNotation) -> &OptionalPinyinStringArray {
         match notation {
             PinyinNotation::Unicode => unreachable!(),
@@ -113,6 +158,8 @@ impl PinyinData {
             PinyinNotation::DiletterThunisoft => &self.diletter_thunisoft,
             PinyinNotation::DiletterXiaohe => &self.diletter_xiaohe,
             PinyinNotation::DiletterZrm => &self.diletter_zrm,
+            #[cfg(feature = "pinyin-wade-giles")]
+            PinyinNotation::WadeGiles => &self.wade_giles,
             _ => unreachable!(),
         }
     }
@@ -136,6 +183,9 @@ impl PinyinData {
         for notation in notations.iter() {
             match notation {
                 PinyinNotation::Unicode => (),
+                // Static data
+                #[cfg(feature = "pinyin-jyutping")]
+                PinyinNotation::Jyutping => (),
                 PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter => {
                     let init = || {
                         data::PINYINS
@@ -162,6 +212,59 @@ impl PinyinData {
                     #[cfg(feature = "inmut-data")]
                     this.ascii_tone.get_or_init(init);
                 }
+                PinyinNotation::Zhuyin => {
+                    this.init_notations(PinyinNotation::Ascii);
+
+                    let init = || {
+                        #[cfg(not(feature = "inmut-data"))]
+                        let ascii = this.ascii.as_ref().unwrap();
+                        #[cfg(feature = "inmut-data")]
+                        let ascii = this.ascii.get().unwrap();
+
+                        ascii
+                            .iter()
+                            .map(|py| notation::ascii_to_zhuyin(py))
+                            .collect::<Vec<_>>()
+                            .into_boxed_slice()
+                    };
+                    #[cfg(not(feature = "inmut-data"))]
+                    this.zhuyin.get_or_insert_with(init);
+                    #[cfg(feature = "inmut-data")]
+                    this.zhuyin.get_or_init(init);
+                }
+                #[cfg(feature = "pinyin-wade-giles")]
+                PinyinNotation::WadeGiles => {
+                    this.init_notations(PinyinNotation::Ascii);
+
+                    let init = || {
+                        #[cfg(not(feature = "inmut-data"))]
+                        let ascii = this.ascii.as_ref().unwrap();
+                        #[cfg(feature = "inmut-data")]
+                        let ascii = this.ascii.get().unwrap();
+
+                        ascii
+                            .iter()
+                            .map(|py| notation::ascii_to_wade_giles(py))
+                            .collect::<Vec<_>>()
+                            .into_boxed_slice()
+                    };
+                    #[cfg(not(feature = "inmut-data"))]
+                    let wade_giles = this.wade_giles.get_or_insert_with(init);
+                    #[cfg(feature = "inmut-data")]
+                    let wade_giles = this.wade_giles.get_or_init(init);
+
+                    let init = || {
+                        wade_giles
+                            .iter()
+                            .map(|py| notation::wade_giles_without_apostrophe(py))
+                            .collect::<Vec<_>>()
+                            .into_boxed_slice()
+                    };
+                    #[cfg(not(feature = "inmut-data"))]
+                    this.wade_giles_without_apostrophe.get_or_insert_with(init);
+                    #[cfg(feature = "inmut-data")]
+                    this.wade_giles_without_apostrophe.get_or_init(init);
+                }
                 _ => {
                     this.init_notations(PinyinNotation::Ascii);
 
@@ -171,10 +274,10 @@ impl PinyinData {
                         #[cfg(feature = "inmut-data")]
                         let ascii = this.ascii.get().unwrap();
 
-                        let map = notation::ascii_map_fn(notation);
+                        let scheme = notation.shuangpin_scheme().unwrap();
                         ascii
                             .iter()
-                            .map(|py| map(py))
+                            .map(|py| scheme.keys(py))
                             .collect::<Vec<_>>()
                             .into_boxed_slice()
                     };
@@ -204,8 +307,39 @@ impl PinyinData {
         self.inited_notations.clone().into()
     }
 
+    /// The Unicode ranges of the hanzi that may have pinyins, i.e. chars out of them never have any.
+    ///
+    /// They include the CJK Unified Ideographs and Extension A to G (e.g. rare surnames in Extension B),
+    /// as well as some compatibility ideographs and PUA chars.
+    /// The ranges are not sorted, and a char in them may still have no pinyin.
+    ///
+    /// Jyutpings (with feature `pinyin-jyutping`) are also in these ranges.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::pinyin::PinyinData;
+    ///
+    /// assert!(PinyinData::covered_ranges().any(|range| range.contains(&'中')));
+    /// // Extension B
+    /// assert!(PinyinData::covered_ranges().any(|range| range.contains(&'𠀀')));
+    /// assert!(!PinyinData::covered_ranges().any(|range| range.contains(&'a')));
+    /// ```
+    pub fn covered_ranges() -> impl Iterator<Item = RangeInclusive<char>> + Clone {
+        data::PINYIN_RANGE_TABLES.iter().map(|range| {
+            char::from_u32(*range.range.start()).unwrap()
+                ..=char::from_u32(*range.range.end()).unwrap()
+        })
+    }
+
+    /// Whether `c` may be in [`PinyinData::covered_ranges()`].
+    /// Faster than checking the ranges, for fast fail.
+    #[inline(always)]
+    pub(crate) fn may_cover(c: char) -> bool {
+        PinyinRangeTable::MAX_RANGE.contains(&(c as u32))
+    }
+
     fn get_pinyin_index(c: char) -> Option<u16> {
-        if PinyinRangeTable::MAX_RANGE.contains(&(c as u32)) {
+        if Self::may_cover(c) {
             for range in &data::PINYIN_RANGE_TABLES {
                 if range.range.contains(&(c as u32)) {
                     return match range.table[(c as u32 - range.range.start()) as usize] {
@@ -224,7 +358,7 @@ impl PinyinData {
             .take_while(|&&i| i != u16::MAX)
     }
 
-    fn pinyin(&self, index: u16) -> Pinyin<'_> {
+    pub(crate) fn pinyin(&self, index: u16) -> Pinyin<'_> {
         Pinyin { data: self, index }
     }
 
@@ -282,6 +416,41 @@ impl PinyinData {
         }
     }
 
+    /// Cantonese readings of `c` in [`PinyinNotation::Jyutping`], which are independent of the Mandarin ones from [`PinyinData::get_pinyins()`].
+    #[cfg(feature = "pinyin-jyutping")]
+    pub fn get_jyutpings_and_for_each(&self, c: char, mut f: impl FnMut(&'static str)) {
+        jyutping::get_jyutpings_and_try_for_each(c, |jyutping| {
+            f(jyutping);
+            None::<()>
+        });
+    }
+
+    /// See [`PinyinData::get_jyutpings_and_for_each()`].
+    #[cfg(feature = "pinyin-jyutping")]
+    pub fn get_jyutpings_and_try_for_each<T>(
+        &self,
+        c: char,
+        f: impl FnMut(&'static str) -> Option<T>,
+    ) -> Option<T> {
+        jyutping::get_jyutpings_and_try_for_each(c, f)
+    }
+
+    /// All pinyins of the given notation and their variants, with adjacent duplicates.
+    fn notation_iter(&self, notation: PinyinNotation) -> impl Iterator<Item = &str> {
+        #[cfg(feature = "pinyin-jyutping")]
+        if notation == PinyinNotation::Jyutping {
+            return itertools::Either::Left(jyutping::iter());
+        }
+        #[cfg(feature = "pinyin-jyutping")]
+        return itertools::Either::Right(
+            self.iter()
+                .flat_map(move |pinyin| pinyin.notation_variants(notation)),
+        );
+        #[cfg(not(feature = "pinyin-jyutping"))]
+        self.iter()
+            .flat_map(move |pinyin| pinyin.notation_variants(notation))
+    }
+
     /// Match pinyin of the given notation in haystack.
     pub fn match_pinyin<'a: 'h, 'h>(
         &'a self,
@@ -291,8 +460,7 @@ impl PinyinData {
         debug_assert_eq!(notation.bits().count_ones(), 1);
         debug_assert!(self.inited_notations().contains(notation));
 
-        self.iter()
-            .map(move |pinyin| pinyin.notation(notation).unwrap())
+        self.notation_iter(notation)
             .filter(move |py| haystack.starts_with(py))
             .dedup()
     }
@@ -311,19 +479,16 @@ impl PinyinData {
         debug_assert_eq!(notation.bits().count_ones(), 1);
         debug_assert!(self.inited_notations().contains(notation));
 
-        self.iter()
-            .map(move |pinyin| pinyin.notation(notation).unwrap())
-            .dedup()
-            .filter_map(move |py| {
-                if haystack.starts_with(py) {
-                    Some((py, false))
-                } else if partial && py.starts_with(haystack) {
-                    debug_assert!(haystack.len() < py.len());
-                    Some((py, true))
-                } else {
-                    None
-                }
-            })
+        self.notation_iter(notation).dedup().filter_map(move |py| {
+            if haystack.starts_with(py) {
+                Some((py, false))
+            } else if partial && py.starts_with(haystack) {
+                debug_assert!(haystack.len() < py.len());
+                Some((py, true))
+            } else {
+                None
+            }
+        })
     }
 }
 
@@ -333,28 +498,57 @@ pub struct Pinyin<'a> {
 }
 
 impl<'a> Pinyin<'a> {
+    /// `None` if the notation is not inited,
+    /// or is [`PinyinNotation::Jyutping`], see [`PinyinData::get_jyutpings_and_for_each()`] instead.
     pub fn notation(&self, notation: PinyinNotation) -> Option<&'a str> {
         debug_assert_eq!(notation.bits().count_ones(), 1);
 
         let i = self.index as usize;
 
-        let get = |pinyins: &'a OptionalPinyinStringArray| {
-            #[cfg(not(feature = "inmut-data"))]
-            let notation = pinyins.as_ref().map(|pinyins| pinyins[i].as_str());
-            #[cfg(feature = "inmut-data")]
-            let notation = pinyins.get().map(|pinyins| pinyins[i].as_str());
-            notation
-        };
-
         match notation {
             PinyinNotation::Unicode => Some(data::PINYINS[i]),
             PinyinNotation::AsciiFirstLetter => {
-                get(&self.data.ascii).map(|ascii| unsafe { ascii.get_unchecked(..1) })
+                Self::get(&self.data.ascii, i).map(|ascii| unsafe { ascii.get_unchecked(..1) })
             }
-            _ => get(self.data.notation(notation)),
+            PinyinNotation::Zhuyin => Self::get(&self.data.zhuyin, i),
+            #[cfg(feature = "pinyin-jyutping")]
+            PinyinNotation::Jyutping => None,
+            _ => Self::get(self.data.notation(notation), i),
         }
     }
 
+    /// [`Pinyin::notation()`] followed by its variants that should also be matched,
+    /// i.e. the one without the apostrophe for [`PinyinNotation::WadeGiles`].
+    pub(crate) fn notation_variants(
+        &self,
+        notation: PinyinNotation,
+    ) -> impl Iterator<Item = &'a str> {
+        let py = self.notation(notation);
+        #[cfg(feature = "pinyin-wade-giles")]
+        let variant = match notation {
+            PinyinNotation::WadeGiles => Self::get(
+                &self.data.wade_giles_without_apostrophe,
+                self.index as usize,
+            )
+            .filter(|&variant| Some(variant) != py),
+            _ => None,
+        };
+        #[cfg(not(feature = "pinyin-wade-giles"))]
+        let variant = None;
+        py.into_iter().chain(variant)
+    }
+
+    fn get<S: core::ops::Deref<Target = str>>(
+        pinyins: &'a OptionalStringArray<S>,
+        i: usize,
+    ) -> Option<&'a str> {
+        #[cfg(not(feature = "inmut-data"))]
+        let notation = pinyins.as_ref().map(|pinyins| &*pinyins[i]);
+        #[cfg(feature = "inmut-data")]
+        let notation = pinyins.get().map(|pinyins| &*pinyins[i]);
+        notation
+    }
+
     /// Require `PinyinNotation::Ascii`.
     pub fn initial_final(&self) -> Option<(&str, &str)> {
         self.notation(PinyinNotation::Ascii)
@@ -429,16 +623,39 @@ mod tests {
         assert_eq!(min_start..=max_end, PinyinRangeTable::MAX_RANGE)
     }
 
+    #[test]
+    fn covered_ranges() {
+        let covered = |c: char| PinyinData::covered_ranges().any(|range| range.contains(&c));
+        for c in ['〇', '中', '㐀', '𠀀', '𫟼', '𰻞'] {
+            assert!(covered(c), "{c}");
+            assert!(PinyinData::may_cover(c), "{c}");
+            assert!(PinyinData::get_pinyin_index(c).is_some(), "{c}");
+        }
+        for c in ['a', '、', 'あ', 'ア', 'Ａ', '😀'] {
+            assert!(!covered(c), "{c}");
+            assert!(PinyinData::get_pinyin_index(c).is_none(), "{c}");
+        }
+        for range in PinyinData::covered_ranges() {
+            assert!(PinyinData::may_cover(*range.start()));
+            assert!(PinyinData::may_cover(*range.end()));
+        }
+    }
+
     #[test]
     fn get_pinyins() {
         let data = PinyinData::new(PinyinNotation::all());
 
         assert_eq!(data.get_pinyins('中').count(), 2);
 
+        // Jyutping is not a notation of Mandarin pinyins
+        #[cfg(feature = "pinyin-jyutping")]
+        let notations = PinyinNotation::all().difference(PinyinNotation::Jyutping);
+        #[cfg(not(feature = "pinyin-jyutping"))]
+        let notations = PinyinNotation::all();
         for pinyin in data.get_pinyins('中') {
             println!("{:?}", pinyin);
 
-            for notation in PinyinNotation::all().iter() {
+            for notation in notations.iter() {
                 assert!(pinyin.notation(notation).is_some_and(|py| !py.is_empty()));
             }
         }
@@ -1,14 +1,31 @@
-use super::{Pinyin, PinyinString};
+use alloc::format;
+use core::fmt;
+
+use super::{shuangpin, Pinyin, PinyinString, ShuangpinSchemeInfo, ZhuyinString};
 
 bitflags::bitflags! {
     /// - All pinyin notations are in lower case (`py.to_lowercase() == py`).
-    /// - All pinyin notations are no more than 7 characters long (`py.len() <= 7`).
+    /// - All pinyin notations are no more than 7 characters long (`py.len() <= 7`),
+    ///   except [`PinyinNotation::Zhuyin`], which is no more than 3 chars / 9 bytes long.
+    ///
+    /// ## Presets
+    /// - [`PinyinNotation::common()`]: [`PinyinNotation::Ascii`] and [`PinyinNotation::AsciiFirstLetter`].
+    /// - [`PinyinNotation::all()`]: All the notations enabled by the features,
+    ///   e.g. [`PinyinNotation::Jyutping`] is only included with feature `pinyin-jyutping`.
     ///
     /// ## Others
     /// TODO: doc alias does not work
-    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    #[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
     pub struct PinyinNotation: u32 {
+        /// 带声调符号全拼
+        ///
         /// e.g. "pīn", "yīn"
+        ///
+        /// The neutral tone (tone 5) has no mark, e.g. "de" (的).
+        /// "ü" is not spelled as "v", e.g. "lǜ" (绿).
+        ///
+        /// See [tones](https://en.wikipedia.org/wiki/Pinyin#Tones) for details.
+        #[doc(alias = "带声调符号全拼")]
         const Unicode = 0x8;
 
         /// 全拼
@@ -21,7 +38,10 @@ bitflags::bitflags! {
 
         /// 带声调全拼
         ///
-        /// The tone digit is in `1..=5`. See [tones](https://en.wikipedia.org/wiki/Pinyin#Tones) for details.
+        /// The tone digit is in `1..=5`, where 5 is the neutral tone, e.g. "de5" (的).
+        /// See [tones](https://en.wikipedia.org/wiki/Pinyin#Tones) for details.
+        ///
+        /// "ü" is spelled as "v", e.g. "lv4" (绿).
         ///
         /// e.g. "pin1", "yin1"
         #[doc(alias = "带声调全拼")]
@@ -70,10 +90,71 @@ bitflags::bitflags! {
         /// See [自然码](https://zh.wikipedia.org/zh-cn/自然码) for details.
         #[doc(alias = "自然码双拼")]
         const DiletterZrm = 0x200;
+
+        /// 注音符号（不带声调）
+        ///
+        /// e.g. "ㄆㄧㄣ", "ㄧㄣ"
+        ///
+        /// See [Bopomofo](https://en.wikipedia.org/wiki/Bopomofo) for details.
+        #[doc(alias("注音", "注音符号", "Bopomofo"))]
+        const Zhuyin = 0x400;
+
+        /// 粤拼（不带声调）
+        ///
+        /// e.g. "ping" or "bing", "jam" (拼音 in Cantonese)
+        ///
+        /// Unlike other notations, the readings are Cantonese ones, independent of the Mandarin pinyins.
+        /// Heteronyms are supported as well. See [`PinyinData::get_jyutpings_and_for_each()`](super::PinyinData::get_jyutpings_and_for_each).
+        ///
+        /// Experimental and incomplete, only a subset of common hanzi have jyutpings at the moment.
+        ///
+        /// See [Jyutping](https://en.wikipedia.org/wiki/Jyutping) for details.
+        #[cfg(feature = "pinyin-jyutping")]
+        #[doc(alias("粤拼", "粵拼"))]
+        const Jyutping = 0x800;
+
+        /// 威妥玛拼音（不带声调）
+        ///
+        /// e.g. "p'in", "yin"; "chung" (中), "hsüeh" is spelled as "hsueh" (学)
+        ///
+        /// Diacritics are omitted, i.e. "ü", "ê" and "ŭ" are spelled as "u", "e" and "u".
+        /// The apostrophe of aspirated initials (p', t', k', ch', ts', tz') is kept,
+        /// but it is optional in patterns, e.g. both "k'ung" and "kung" match "孔".
+        ///
+        /// See [Wade–Giles](https://en.wikipedia.org/wiki/Wade%E2%80%93Giles) for details.
+        #[cfg(feature = "pinyin-wade-giles")]
+        #[doc(alias("威妥玛拼音", "威妥瑪拼音", "Wade–Giles"))]
+        const WadeGiles = 0x1000;
     }
 }
 
 impl PinyinNotation {
+    /// Alias of [`PinyinNotation::Unicode`].
+    #[allow(non_upper_case_globals)]
+    pub const UnicodeTone: Self = Self::Unicode;
+
+    /// [`PinyinNotation::Ascii`] and [`PinyinNotation::AsciiFirstLetter`], the most commonly used combination.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// let builder = |pattern| {
+    ///     IbMatcher::builder(pattern)
+    ///         .pinyin(PinyinMatchConfig::notations(PinyinNotation::common()))
+    ///         .build()
+    /// };
+    /// assert!(builder("pinyin").is_match("拼音"));
+    /// assert!(builder("py").is_match("拼音"));
+    /// assert!(builder("piny").is_match("拼音"));
+    /// ```
+    pub const fn common() -> Self {
+        Self::Ascii.union(Self::AsciiFirstLetter)
+    }
+
     pub fn contains_diletter(&self) -> bool {
         self.intersects(
             PinyinNotation::DiletterAbc
@@ -85,14 +166,71 @@ impl PinyinNotation {
         )
     }
 
+    /// The built-in 双拼 (Shuangpin) schemes, i.e. the diletter notations,
+    /// with their ids, names and key layouts, e.g. for listing them in settings.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{IbMatcher, PinyinMatchConfig},
+    ///     pinyin::PinyinNotation,
+    /// };
+    ///
+    /// for scheme in PinyinNotation::shuangpin_schemes() {
+    ///     println!("{}: {}", scheme.id, scheme.name);
+    /// }
+    ///
+    /// // e.g. the id saved in settings
+    /// let notation = PinyinNotation::from_shuangpin_id("xiaohe").unwrap();
+    /// assert_eq!(notation, PinyinNotation::DiletterXiaohe);
+    /// assert_eq!(notation.shuangpin_scheme().unwrap().name, "小鹤双拼");
+    ///
+    /// let matcher = IbMatcher::builder("pbyb")
+    ///     .pinyin(PinyinMatchConfig::notations(notation))
+    ///     .build();
+    /// assert!(matcher.is_match("拼音"));
+    /// ```
+    pub fn shuangpin_schemes() -> &'static [ShuangpinSchemeInfo] {
+        &shuangpin::BUILTIN_SCHEMES
+    }
+
+    /// The built-in 双拼 (Shuangpin) scheme of this notation.
+    ///
+    /// `None` if this is not a single diletter notation.
+    pub fn shuangpin_scheme(&self) -> Option<&'static ShuangpinSchemeInfo> {
+        Self::shuangpin_schemes()
+            .iter()
+            .find(|scheme| scheme.notation == *self)
+    }
+
+    /// The diletter notation of the built-in 双拼 (Shuangpin) scheme with [`ShuangpinSchemeInfo::id`] `id`,
+    /// e.g. `"xiaohe"` for [`PinyinNotation::DiletterXiaohe`].
+    pub fn from_shuangpin_id(id: &str) -> Option<Self> {
+        Self::shuangpin_schemes()
+            .iter()
+            .find(|scheme| scheme.id == id)
+            .map(|scheme| scheme.notation)
+    }
+
     /// `None` if no notation is set.
     pub fn max_len(&self) -> Option<usize> {
+        if self.contains(PinyinNotation::Zhuyin) {
+            return Some(9);
+        }
         if self.intersects(PinyinNotation::Unicode | PinyinNotation::AsciiTone) {
             return Some(7);
         }
+        #[cfg(feature = "pinyin-wade-giles")]
+        if self.contains(PinyinNotation::WadeGiles) {
+            return Some(7);
+        }
         if self.contains(PinyinNotation::Ascii) {
             return Some(6);
         }
+        #[cfg(feature = "pinyin-jyutping")]
+        if self.contains(PinyinNotation::Jyutping) {
+            return Some(6);
+        }
         if self.contains_diletter() {
             return Some(2);
         }
@@ -107,7 +245,7 @@ impl PinyinNotation {
 pub(super) use atomic::*;
 #[cfg(feature = "inmut-data")]
 mod atomic {
-    use std::sync::atomic::{AtomicU32, Ordering};
+    use core::sync::atomic::{AtomicU32, Ordering};
 
     use super::*;
 
@@ -209,15 +347,18 @@ fn unicode_tone(unicode: &str) -> u8 {
     5
 }
 
-pub(super) fn ascii_map_fn(notation: PinyinNotation) -> fn(&str) -> PinyinString {
-    match notation {
-        PinyinNotation::DiletterAbc => ascii_to_diletter_abc,
-        PinyinNotation::DiletterJiajia => ascii_to_diletter_jiajia,
-        PinyinNotation::DiletterMicrosoft => ascii_to_diletter_microsoft,
-        PinyinNotation::DiletterThunisoft => ascii_to_diletter_thunisoft,
-        PinyinNotation::DiletterXiaohe => ascii_to_diletter_xiaohe,
-        PinyinNotation::DiletterZrm => ascii_to_diletter_zrm,
-        _ => unreachable!(),
+/// Map the syllables that diletter schemes can't spell to similar ones.
+pub(super) fn diletter_syllable(ascii: &str) -> &str {
+    match ascii {
+        // 噷
+        "hm" => "hen",
+        // 哼
+        "hng" => "heng",
+        // 呒呣嘸
+        "m" => "mu",
+        // 唔嗯 㕶 𠮾
+        "n" | "ng" => "en",
+        _ => ascii,
     }
 }
 
@@ -229,23 +370,13 @@ pub(super) fn ascii_map_fn(notation: PinyinNotation) -> fn(&str) -> PinyinString
 ///   See [initials](https://en.wikipedia.org/wiki/Pinyin#Initials) for details.
 ///
 /// - `final_map`: See [finals](https://en.wikipedia.org/wiki/Pinyin#Finals) for details.
-fn ascii_to_diletter<'a>(
+pub(super) fn ascii_to_diletter<'a, K: fmt::Display>(
     ascii: &str,
-    map_pinyin: impl Fn(&str) -> Option<&str>,
-    map_initial: impl Fn(&str) -> Option<&str>,
-    map_final: impl Fn(&str) -> Option<&str>,
+    map_pinyin: impl Fn(&str) -> Option<&'a str>,
+    map_initial: impl Fn(&str) -> Option<K>,
+    map_final: impl Fn(&str) -> Option<K>,
 ) -> PinyinString {
-    let ascii = match ascii {
-        // 噷
-        "hm" => "hen",
-        // 哼
-        "hng" => "heng",
-        // 呒呣嘸
-        "m" => "mu",
-        // 唔嗯 㕶 𠮾
-        "n" | "ng" => "en",
-        _ => ascii,
-    };
+    let ascii = diletter_syllable(ascii);
 
     if let Some(py) = map_pinyin(ascii) {
         return py.into();
@@ -253,274 +384,176 @@ fn ascii_to_diletter<'a>(
 
     match Pinyin::split_initial_final(ascii) {
         ("", final_) => final_.into(),
-        (initial, final_) => format!(
-            "{}{}",
-            map_initial(initial).unwrap_or_else(|| {
-                debug_assert_eq!(initial.len(), 1);
-                initial
-            }),
-            map_final(final_).unwrap()
-        )
-        .as_str()
-        .into(),
+        (initial, final_) => {
+            let final_ = map_final(final_).unwrap();
+            match map_initial(initial) {
+                Some(initial) => format!("{initial}{final_}"),
+                None => {
+                    debug_assert_eq!(initial.len(), 1);
+                    format!("{initial}{final_}")
+                }
+            }
+            .as_str()
+            .into()
+        }
     }
 }
 
+/// See [Bopomofo](https://en.wikipedia.org/wiki/Bopomofo#Zhuyin_and_pinyin_correspondence) for details.
 #[rustfmt::skip]
-fn ascii_to_diletter_abc(ascii: &str) -> PinyinString {
-    ascii_to_diletter(
-        ascii,
-        |pinyin| Some(match pinyin {
-            "e" => "oe", "o" => "oo",
-            "a" => "oa",
-            "ei" => "oq",
-            "ai" => "ol",
-            "ou" => "ob",
-            "ao" => "ok",
-            "en" => "of",
-            "an" => "oj",
-            "eng" => "og",
-            "ang" => "oh",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |initial| Some(match initial {
-            "zh" => "a", "ch" => "e", "sh" => "v",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |final_| Some(match final_ {
-            "i" => "i", "u" => "u", "v" => "v",
-            "e" => "e", "ie" => "x", "o" => "o", "uo" => "o", "ue" => "m", "ve" => "m",
-            "a" => "a", "ia" => "d", "ua" => "d",
-            "ei" => "q", "ui" => "m",
-            "ai" => "l", "uai" => "c",
-            "ou" => "b", "iu" => "r",
-            "ao" => "k", "iao" => "z",
-            "in" => "c", "un" => "n", "vn" => "n",
-            "en" => "f",
-            "an" => "j", "ian" => "w", "uan" => "p", "van" => "p",
-            "ing" => "y",
-            "ong" => "s", "iong" => "s",
-            "eng" => "g",
-            "ang" => "h", "iang" => "t", "uang" => "t",
-            "er" => "or",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-    )
-}
-
-#[rustfmt::skip]
-fn ascii_to_diletter_jiajia(ascii: &str) -> PinyinString {
-    ascii_to_diletter(
-        ascii,
-        |pinyin| Some(match pinyin {
-            "e" => "ee", "o" => "oo",
-            "a" => "aa",
-            "ei" => "ew",
-            "ai" => "as",
-            "ou" => "op",
-            "ao" => "ad",
-            "en" => "er",
-            "an" => "af",
-            "eng" => "et",
-            "ang" => "ag",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |initial| Some(match initial {
-            "zh" => "v", "ch" => "u", "sh" => "i",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |final_| Some(match final_ {
-            "i" => "i", "u" => "u", "v" => "v",
-            "e" => "e", "ie" => "m", "o" => "o", "uo" => "o", "ue" => "x", "ve" => "t",
-            "a" => "a", "ia" => "b", "ua" => "b",
-            "ei" => "w", "ui" => "v",
-            "ai" => "s", "uai" => "x",
-            "ou" => "p", "iu" => "n",
-            "ao" => "d", "iao" => "k",
-            "in" => "l", "un" => "z", "vn" => "z",
-            "en" => "r",
-            "an" => "f", "ian" => "j", "uan" => "c", "van" => "c",
-            "ing" => "q",
-            "ong" => "y", "iong" => "y",
-            "eng" => "t",
-            "ang" => "g", "iang" => "h", "uang" => "h",
-            "er" => "eq",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-    )
-}
+pub(super) fn ascii_to_zhuyin(ascii: &str) -> ZhuyinString {
+    let map_initial = |initial| match initial {
+        "b" => "ㄅ", "p" => "ㄆ", "m" => "ㄇ", "f" => "ㄈ",
+        "d" => "ㄉ", "t" => "ㄊ", "n" => "ㄋ", "l" => "ㄌ",
+        "g" => "ㄍ", "k" => "ㄎ", "h" => "ㄏ",
+        "j" => "ㄐ", "q" => "ㄑ", "x" => "ㄒ",
+        "zh" => "ㄓ", "ch" => "ㄔ", "sh" => "ㄕ", "r" => "ㄖ",
+        "z" => "ㄗ", "c" => "ㄘ", "s" => "ㄙ",
+        // "y" and "w" are handled by finals
+        _ => "",
+    };
+    let map_final = |final_| match final_ {
+        "" => "",
+        "a" => "ㄚ", "o" => "ㄛ", "e" => "ㄜ",
+        "ai" => "ㄞ", "ei" => "ㄟ", "ao" => "ㄠ", "ou" => "ㄡ",
+        "an" => "ㄢ", "en" => "ㄣ", "ang" => "ㄤ", "eng" => "ㄥ", "ong" => "ㄨㄥ",
+        "er" => "ㄦ",
+        "i" => "ㄧ", "ia" => "ㄧㄚ", "io" => "ㄧㄛ", "ie" => "ㄧㄝ", "iai" => "ㄧㄞ",
+        "iao" => "ㄧㄠ", "iu" => "ㄧㄡ",
+        "ian" => "ㄧㄢ", "in" => "ㄧㄣ", "iang" => "ㄧㄤ", "ing" => "ㄧㄥ", "iong" => "ㄩㄥ",
+        "u" => "ㄨ", "ua" => "ㄨㄚ", "uo" => "ㄨㄛ", "uai" => "ㄨㄞ", "ui" => "ㄨㄟ",
+        "uan" => "ㄨㄢ", "un" => "ㄨㄣ", "uang" => "ㄨㄤ", "ueng" => "ㄨㄥ",
+        "v" => "ㄩ", "ve" | "ue" => "ㄩㄝ", "van" => "ㄩㄢ", "vn" => "ㄩㄣ",
+        _ => unreachable!("{final_}"),
+    };
 
-#[rustfmt::skip]
-fn ascii_to_diletter_microsoft(ascii: &str) -> PinyinString {
-    ascii_to_diletter(
-        ascii,
-        |pinyin| Some(match pinyin {
-            "e" => "oe", "o" => "oo",
-            "a" => "oa",
-            "ei" => "oz",
-            "ai" => "ol",
-            "ou" => "ob",
-            "ao" => "ok",
-            "en" => "of",
-            "an" => "oj",
-            "eng" => "og",
-            "ang" => "oh",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |initial| Some(match initial {
-            "zh" => "v", "ch" => "i", "sh" => "u",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |final_| Some(match final_ {
-            "i" => "i", "u" => "u", "v" => "y",
-            "e" => "e", "ie" => "x", "o" => "o", "uo" => "o", "ue" => "t", "ve" => "v",
-            "a" => "a", "ia" => "w", "ua" => "w",
-            "ei" => "z", "ui" => "v",
-            "ai" => "l", "uai" => "y",
-            "ou" => "b", "iu" => "q",
-            "ao" => "k", "iao" => "c",
-            "in" => "n", "un" => "p", "vn" => "p",
-            "en" => "f",
-            "an" => "j", "ian" => "m", "uan" => "r", "van" => "r",
-            "ing" => ";",
-            "ong" => "s", "iong" => "s",
-            "eng" => "g",
-            "ang" => "h", "iang" => "d", "uang" => "d",
-            "er" => "or",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-    )
+    let (initial, final_) = match ascii {
+        // 噷
+        "hm" => return "ㄏㄇ".into(),
+        // 哼
+        "hng" => return "ㄏㄫ".into(),
+        // 呒呣嘸
+        "m" => return "ㄇ".into(),
+        // 唔嗯 㕶 𠮾
+        "n" => return "ㄣ".into(),
+        "ng" => return "ㄫ".into(),
+        _ => Pinyin::split_initial_final(ascii),
+    };
+    let final_: &str = &match (initial, final_) {
+        // yi, yin, ying
+        ("y", "i" | "in" | "ing") => final_.into(),
+        // yu, yue, yuan, yun
+        ("y" | "j" | "q" | "x", "u" | "ue" | "uan" | "un") => format!("v{}", &final_[1..]),
+        ("y", "ong") => "iong".into(),
+        ("y", "ou") => "iu".into(),
+        ("y", _) => format!("i{final_}"),
+        ("w", "u") => final_.into(),
+        ("w", "ei") => "ui".into(),
+        ("w", "en") => "un".into(),
+        ("w", "eng" | "ong") => "ueng".into(),
+        ("w", _) => format!("u{final_}"),
+        // zhi, chi, shi, ri, zi, ci, si
+        ("zh" | "ch" | "sh" | "r" | "z" | "c" | "s", "i") => "".into(),
+        _ => final_.into(),
+    };
+    let mut zhuyin = ZhuyinString::new();
+    zhuyin.extend([map_initial(initial), map_final(final_)]);
+    zhuyin
 }
 
+/// Diacritics are omitted, and the apostrophe of aspirated initials is kept.
+///
+/// See [Wade–Giles](https://en.wikipedia.org/wiki/Wade%E2%80%93Giles#Chart) for details.
+#[cfg(feature = "pinyin-wade-giles")]
 #[rustfmt::skip]
-fn ascii_to_diletter_thunisoft(ascii: &str) -> PinyinString {
-    ascii_to_diletter(
-        ascii,
-        |pinyin| Some(match pinyin {
-            "e" => "oe", "o" => "oo",
-            "a" => "oa",
-            "ei" => "ok",
-            "ai" => "op",
-            "ou" => "oz",
-            "ao" => "oq",
-            "en" => "ow",
-            "an" => "or",
-            "eng" => "ot",
-            "ang" => "os",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |initial| Some(match initial {
-            "zh" => "u", "ch" => "a", "sh" => "i",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |final_| Some(match final_ {
-            "i" => "i", "u" => "u", "v" => "v",
-            "e" => "e", "ie" => "d", "o" => "o", "uo" => "o", "ue" => "n", "ve" => "n",
-            "a" => "a", "ia" => "x", "ua" => "x",
-            "ei" => "k", "ui" => "n",
-            "ai" => "p", "uai" => "y",
-            "ou" => "z", "iu" => "j",
-            "ao" => "q", "iao" => "b",
-            "in" => "y", "un" => "m", "vn" => "y",
-            "en" => "w",
-            "an" => "r", "ian" => "f", "uan" => "l", "van" => "l",
-            "ing" => ";",
-            "ong" => "h", "iong" => "h",
-            "eng" => "t",
-            "ang" => "s", "iang" => "g", "uang" => "g",
-            "er" => "oj",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-    )
-}
+pub(super) fn ascii_to_wade_giles(ascii: &str) -> PinyinString {
+    let wade_giles = match ascii {
+        "zhi" => "chih", "chi" => "ch'ih", "shi" => "shih", "ri" => "jih",
+        "zi" => "tzu", "ci" => "tz'u", "si" => "ssu",
+        "e" => "o", "ge" => "ko", "ke" => "k'o", "he" => "ho",
+        "er" => "erh",
+        "yi" => "i", "ye" => "yeh", "yan" => "yen", "you" => "yu",
+        // 噷, 哼, 呒呣嘸, 唔嗯
+        "hm" | "hng" | "m" | "n" | "ng" => ascii,
+        _ => "",
+    };
+    if !wade_giles.is_empty() {
+        return wade_giles.into();
+    }
 
-#[rustfmt::skip]
-fn ascii_to_diletter_xiaohe(ascii: &str) -> PinyinString {
-    ascii_to_diletter(
-        ascii,
-        |pinyin| Some(match pinyin {
-            "e" => "ee", "o" => "oo",
-            "a" => "aa",
-            "ei" => "ei",
-            "ai" => "ai",
-            "ou" => "ou",
-            "ao" => "ao",
-            "en" => "en",
-            "an" => "an",
-            "eng" => "eg",
-            "ang" => "ah",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |initial| Some(match initial {
-            "zh" => "v", "ch" => "i", "sh" => "u",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |final_| Some(match final_ {
-            "i" => "i", "u" => "u", "v" => "v",
-            "e" => "e", "ie" => "p", "o" => "o", "uo" => "o", "ue" => "t", "ve" => "t",
-            "a" => "a", "ia" => "x", "ua" => "x",
-            "ei" => "w", "ui" => "v",
-            "ai" => "d", "uai" => "k",
-            "ou" => "z", "iu" => "q",
-            "ao" => "c", "iao" => "n",
-            "in" => "b", "un" => "y", "vn" => "y",
-            "en" => "f",
-            "an" => "j", "ian" => "m", "uan" => "r", "van" => "r",
-            "ing" => "k",
-            "ong" => "s", "iong" => "s",
-            "eng" => "g",
-            "ang" => "h", "iang" => "l", "uang" => "l",
-            "er" => "er",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-    )
+    let (initial, final_) = Pinyin::split_initial_final(ascii);
+    let final_ = match (initial, final_) {
+        (_, "ong") => "ung",
+        (_, "iong") => "iung",
+        (_, "ie") => "ieh",
+        (_, "ue" | "ve") => "ueh",
+        (_, "ian") => "ien",
+        (_, "v") => "u",
+        ("g" | "k" | "h" | "sh", "uo") => "uo",
+        (_, "uo") => "o",
+        ("g" | "k", "ui") => "uei",
+        _ => final_,
+    };
+    let initial = match initial {
+        "b" => "p", "p" => "p'",
+        "d" => "t", "t" => "t'",
+        "g" => "k", "k" => "k'",
+        "j" | "zh" => "ch", "q" | "ch" => "ch'",
+        "x" => "hs",
+        "r" => "j",
+        "z" => "ts", "c" => "ts'",
+        _ => initial,
+    };
+    let mut wade_giles = PinyinString::new();
+    wade_giles.extend([initial, final_]);
+    wade_giles
 }
 
-#[rustfmt::skip]
-fn ascii_to_diletter_zrm(ascii: &str) -> PinyinString {
-    ascii_to_diletter(
-        ascii,
-        |pinyin| Some(match pinyin {
-            "e" => "ee", "o" => "oo",
-            "a" => "aa",
-            "ei" => "ei",
-            "ai" => "ai",
-            "ou" => "ou",
-            "ao" => "ao",
-            "en" => "en",
-            "an" => "an",
-            "eng" => "eg",
-            "ang" => "ah",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |initial| Some(match initial {
-            "zh" => "v", "ch" => "i", "sh" => "u",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-        |final_| Some(match final_ {
-            "i" => "i", "u" => "u", "v" => "v",
-            "e" => "e", "ie" => "x", "o" => "o", "uo" => "o", "ue" => "t", "ve" => "t",
-            "a" => "a", "ia" => "w", "ua" => "w",
-            "ei" => "z", "ui" => "v",
-            "ai" => "l", "uai" => "y",
-            "ou" => "b", "iu" => "q",
-            "ao" => "k", "iao" => "c",
-            "in" => "n", "un" => "p", "vn" => "p",
-            "en" => "f",
-            "an" => "j", "ian" => "m", "uan" => "r", "van" => "r",
-            "ing" => ";",
-            "ong" => "s", "iong" => "s",
-            "eng" => "g",
-            "ang" => "h", "iang" => "d", "uang" => "d",
-            "er" => "er",
-            _ => "",
-        }).filter(|s| !s.is_empty()),
-    )
+/// [`ascii_to_wade_giles()`] without the apostrophe.
+#[cfg(feature = "pinyin-wade-giles")]
+pub(super) fn wade_giles_without_apostrophe(wade_giles: &str) -> PinyinString {
+    let mut without = PinyinString::new();
+    without.extend(wade_giles.split('\''));
+    without
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{super::data, *};
+    use super::{
+        super::{data, PinyinData},
+        *,
+    };
+
+    fn diletter(notation: PinyinNotation, ascii: &str) -> PinyinString {
+        notation.shuangpin_scheme().unwrap().keys(ascii)
+    }
+
+    #[test]
+    fn presets() {
+        assert_eq!(
+            PinyinNotation::common(),
+            PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter
+        );
+        assert!(PinyinNotation::all().contains(PinyinNotation::common()));
+
+        // `all()` can be initialized, one by one or at once
+        for notation in PinyinNotation::all().iter() {
+            let data = PinyinData::new(notation);
+            assert!(data.inited_notations().contains(notation), "{notation:?}");
+        }
+        let data = PinyinData::new(PinyinNotation::all());
+        assert_eq!(data.inited_notations(), PinyinNotation::all());
+
+        #[cfg(feature = "pinyin-jyutping")]
+        assert!(PinyinNotation::all().contains(PinyinNotation::Jyutping));
+        #[cfg(feature = "pinyin-wade-giles")]
+        assert!(PinyinNotation::all().contains(PinyinNotation::WadeGiles));
+        assert_eq!(
+            PinyinNotation::all().iter().count(),
+            11 + cfg!(feature = "pinyin-jyutping") as usize
+                + cfg!(feature = "pinyin-wade-giles") as usize
+        );
+    }
 
     #[test]
     fn lowercase() {
@@ -538,12 +571,12 @@ mod tests {
             assert!(unicode.len() <= 7, "{}", unicode.len());
             assert!(ascii.len() <= 6);
 
-            assert_eq!(ascii_to_diletter_abc(&ascii).len(), 2);
-            assert_eq!(ascii_to_diletter_jiajia(&ascii).len(), 2);
-            assert_eq!(ascii_to_diletter_microsoft(&ascii).len(), 2);
-            assert_eq!(ascii_to_diletter_thunisoft(&ascii).len(), 2);
-            assert_eq!(ascii_to_diletter_xiaohe(&ascii).len(), 2);
-            assert_eq!(ascii_to_diletter_zrm(&ascii).len(), 2);
+            assert_eq!(diletter(PinyinNotation::DiletterAbc, &ascii).len(), 2);
+            assert_eq!(diletter(PinyinNotation::DiletterJiajia, &ascii).len(), 2);
+            assert_eq!(diletter(PinyinNotation::DiletterMicrosoft, &ascii).len(), 2);
+            assert_eq!(diletter(PinyinNotation::DiletterThunisoft, &ascii).len(), 2);
+            assert_eq!(diletter(PinyinNotation::DiletterXiaohe, &ascii).len(), 2);
+            assert_eq!(diletter(PinyinNotation::DiletterZrm, &ascii).len(), 2);
         }
     }
 
@@ -557,15 +590,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn unicode_to_ascii_tone_() {
+        assert_eq!(&unicode_to_ascii_tone("pīn"), "pin1");
+        assert_eq!(&unicode_to_ascii_tone("lǜ"), "lv4");
+        assert_eq!(&unicode_to_ascii_tone("lüè"), "lve4");
+        // Neutral tone
+        assert_eq!(&unicode_to_ascii_tone("de"), "de5");
+        assert_eq!(&unicode_to_ascii_tone("ê̄"), "e1");
+        assert_eq!(&unicode_to_ascii_tone("m̀"), "m4");
+        assert_eq!(&unicode_to_ascii_tone("ḿ"), "m2");
+    }
+
+    #[test]
+    fn ascii_to_zhuyin_() {
+        for unicode in data::PINYINS {
+            let zhuyin = ascii_to_zhuyin(&unicode_to_ascii(unicode));
+            println!("{}: {}", unicode, zhuyin);
+
+            assert!(!zhuyin.is_empty());
+            assert!(zhuyin.chars().count() <= 3);
+            assert!(zhuyin.chars().all(|c| matches!(c, 'ㄅ'..='ㄯ')));
+        }
+
+        assert_eq!(&ascii_to_zhuyin("pin"), "ㄆㄧㄣ");
+        assert_eq!(&ascii_to_zhuyin("yin"), "ㄧㄣ");
+        assert_eq!(&ascii_to_zhuyin("zhong"), "ㄓㄨㄥ");
+        assert_eq!(&ascii_to_zhuyin("shi"), "ㄕ");
+        assert_eq!(&ascii_to_zhuyin("xue"), "ㄒㄩㄝ");
+        assert_eq!(&ascii_to_zhuyin("lve"), "ㄌㄩㄝ");
+        assert_eq!(&ascii_to_zhuyin("you"), "ㄧㄡ");
+        assert_eq!(&ascii_to_zhuyin("yong"), "ㄩㄥ");
+        assert_eq!(&ascii_to_zhuyin("wei"), "ㄨㄟ");
+        assert_eq!(&ascii_to_zhuyin("er"), "ㄦ");
+    }
+
+    #[cfg(feature = "pinyin-wade-giles")]
+    #[test]
+    fn ascii_to_wade_giles_() {
+        for unicode in data::PINYINS {
+            let wade_giles = ascii_to_wade_giles(&unicode_to_ascii(unicode));
+            println!("{}: {}", unicode, wade_giles);
+
+            assert!(!wade_giles.is_empty());
+            assert!(wade_giles.len() <= 7);
+            assert!(wade_giles.chars().all(|c| matches!(c, 'a'..='z' | '\'')));
+        }
+
+        assert_eq!(&ascii_to_wade_giles("pin"), "p'in");
+        assert_eq!(&ascii_to_wade_giles("yin"), "yin");
+        assert_eq!(&ascii_to_wade_giles("bei"), "pei");
+        assert_eq!(&ascii_to_wade_giles("jing"), "ching");
+        assert_eq!(&ascii_to_wade_giles("qing"), "ch'ing");
+        assert_eq!(&ascii_to_wade_giles("zhong"), "chung");
+        assert_eq!(&ascii_to_wade_giles("chuang"), "ch'uang");
+        assert_eq!(&ascii_to_wade_giles("qiong"), "ch'iung");
+        assert_eq!(&ascii_to_wade_giles("xue"), "hsueh");
+        assert_eq!(&ascii_to_wade_giles("lve"), "lueh");
+        assert_eq!(&ascii_to_wade_giles("shi"), "shih");
+        assert_eq!(&ascii_to_wade_giles("zi"), "tzu");
+        assert_eq!(&ascii_to_wade_giles("ci"), "tz'u");
+        assert_eq!(&ascii_to_wade_giles("ze"), "tse");
+        assert_eq!(&ascii_to_wade_giles("ge"), "ko");
+        assert_eq!(&ascii_to_wade_giles("guo"), "kuo");
+        assert_eq!(&ascii_to_wade_giles("duo"), "to");
+        assert_eq!(&ascii_to_wade_giles("gui"), "kuei");
+        assert_eq!(&ascii_to_wade_giles("ri"), "jih");
+        assert_eq!(&ascii_to_wade_giles("tian"), "t'ien");
+        assert_eq!(&ascii_to_wade_giles("you"), "yu");
+        assert_eq!(&ascii_to_wade_giles("yong"), "yung");
+        assert_eq!(&ascii_to_wade_giles("er"), "erh");
+
+        assert_eq!(&wade_giles_without_apostrophe("ch'uang"), "chuang");
+        assert_eq!(&wade_giles_without_apostrophe("yin"), "yin");
+    }
+
     #[test]
     fn ascii_to_diletter_microsoft_() {
-        assert_eq!(&ascii_to_diletter_microsoft("pin"), "pn");
-        assert_eq!(&ascii_to_diletter_microsoft("ying"), "y;");
+        assert_eq!(&diletter(PinyinNotation::DiletterMicrosoft, "pin"), "pn");
+        assert_eq!(&diletter(PinyinNotation::DiletterMicrosoft, "ying"), "y;");
     }
 
     #[test]
     fn ascii_to_diletter_xiaohe_() {
-        assert_eq!(&ascii_to_diletter_xiaohe("pin"), "pb");
-        assert_eq!(&ascii_to_diletter_xiaohe("yin"), "yb");
+        assert_eq!(&diletter(PinyinNotation::DiletterXiaohe, "pin"), "pb");
+        assert_eq!(&diletter(PinyinNotation::DiletterXiaohe, "yin"), "yb");
     }
 }
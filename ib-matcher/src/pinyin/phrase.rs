@@ -0,0 +1,313 @@
+/*!
+Phrase-level pinyin overrides.
+
+Pinyin is matched per hanzi, so every reading of a heteronym (多音字) can be matched,
+e.g. "zhongqing" can match "重庆" (Chóngqìng) since "重" can also be read as "zhòng".
+A [`PinyinPhrases`] table gives the known readings of phrases, which take precedence over
+the readings of their hanzi, and can be passed to [`PinyinMatchConfigBuilder::phrases()`](crate::matcher::PinyinMatchConfigBuilder::phrases).
+
+[`BUILTIN_PHRASES`] contains some compound surnames (复姓) and place names with special readings.
+User-defined phrases can be used with or without them.
+
+## Example
+```
+// cargo add ib-matcher --features pinyin
+use ib_matcher::{
+    matcher::{IbMatcher, PinyinMatchConfig},
+    pinyin::{phrase::BUILTIN_PHRASES, PinyinNotation, PinyinPhrases},
+};
+
+let phrases = PinyinPhrases::new(
+    BUILTIN_PHRASES
+        .iter()
+        .copied()
+        .chain([("银行", "yin hang")]),
+)
+.unwrap();
+let config = PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+    .phrases(&phrases)
+    .build();
+
+let matcher = IbMatcher::builder("chongqing").pinyin(config.shallow_clone()).build();
+assert!(matcher.is_match("重庆"));
+let matcher = IbMatcher::builder("zhongqing").pinyin(config.shallow_clone()).build();
+assert!(!matcher.is_match("重庆"));
+// Not a phrase
+let matcher = IbMatcher::builder("zhongyao").pinyin(config.shallow_clone()).build();
+assert!(matcher.is_match("重要"));
+
+let matcher = IbMatcher::builder("yinh").pinyin(config.shallow_clone()).build();
+assert!(matcher.is_match("银行"));
+assert!(!IbMatcher::builder("yinx").pinyin(config).build().is_match("银行"));
+```
+
+## Matching
+- A phrase is only recognized when the match reaches its first hanzi, i.e. a match starting in the middle of a phrase uses the readings of the hanzi.
+- If multiple phrases start at the same hanzi, the longest one is used.
+- All the pinyin notations and [fuzzy pinyin](super::fuzzy) are still applied to the readings of phrases.
+- [`PinyinNotation::Jyutping`](super::PinyinNotation::Jyutping) is not affected by phrases.
+*/
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+
+use super::{data, notation, PinyinData};
+
+/// Compound surnames (复姓) and place names whose readings are different from the most common ones of their hanzi.
+pub static BUILTIN_PHRASES: &[(&str, &str)] = &[
+    // Compound surnames
+    ("长孙", "zhang3 sun1"),
+    ("尉迟", "yu4 chi2"),
+    ("单于", "chan2 yu2"),
+    ("万俟", "mo4 qi2"),
+    ("澹台", "tan2 tai2"),
+    ("令狐", "ling2 hu2"),
+    // Place names
+    ("重庆", "chong2 qing4"),
+    ("厦门", "xia4 men2"),
+    ("长沙", "chang2 sha1"),
+    ("六安", "lu4 an1"),
+    ("蚌埠", "beng4 bu4"),
+    ("番禺", "pan1 yu2"),
+    ("乐山", "le4 shan1"),
+    ("东阿", "dong1 e1"),
+];
+
+/// Error when building [`PinyinPhrases`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinyinPhrasesError {
+    /// The phrase is empty.
+    Empty,
+    /// The number of pinyins is not the same as the number of hanzi in the phrase.
+    LengthMismatch { phrase: String, pinyins: String },
+    /// The pinyin is not a reading of the hanzi, or not a valid pinyin.
+    UnknownPinyin { hanzi: char, pinyin: String },
+}
+
+impl fmt::Display for PinyinPhrasesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PinyinPhrasesError::Empty => write!(f, "empty phrase"),
+            PinyinPhrasesError::LengthMismatch { phrase, pinyins } => {
+                write!(
+                    f,
+                    "the pinyins {pinyins:?} don't match the hanzi of {phrase}"
+                )
+            }
+            PinyinPhrasesError::UnknownPinyin { hanzi, pinyin } => {
+                write!(f, "{pinyin} is not a reading of {hanzi}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PinyinPhrasesError {}
+
+/// Pinyin indices of a hanzi in a phrase.
+pub(crate) type PhrasePinyins = Box<[u16]>;
+
+#[derive(Clone, Debug)]
+struct Phrase {
+    /// The hanzi after the first one.
+    rest: Box<[char]>,
+    /// Of each hanzi, including the first one.
+    pinyins: Box<[PhrasePinyins]>,
+}
+
+/// A table of phrase pinyins. See [`phrase`](super::phrase) for details.
+#[derive(Clone, Debug, Default)]
+pub struct PinyinPhrases {
+    /// The first hanzi -> phrases starting with it, the longest first.
+    phrases: BTreeMap<char, Vec<Phrase>>,
+}
+
+impl PinyinPhrases {
+    /// `phrases` are pairs of phrases and their pinyins separated by spaces, e.g. `("重庆", "chong2 qing4")`.
+    ///
+    /// Each pinyin can be in ASCII (`chong`), ASCII with a tone number (`chong2`, `5` for the neutral tone) or Unicode (`chóng`),
+    /// with "ü" spelled as "v" in ASCII. A pinyin without tone allows all tones of it.
+    ///
+    /// If a phrase is given more than once, the last one is used.
+    ///
+    /// ## Errors
+    /// See [`PinyinPhrasesError`].
+    pub fn new(
+        phrases: impl IntoIterator<Item = (impl AsRef<str>, impl AsRef<str>)>,
+    ) -> Result<Self, PinyinPhrasesError> {
+        let mut table: BTreeMap<char, Vec<Phrase>> = BTreeMap::new();
+        for (phrase, pinyins) in phrases {
+            let (phrase, pinyins) = (phrase.as_ref(), pinyins.as_ref());
+            let mut hanzi = phrase.chars();
+            let first = hanzi.next().ok_or(PinyinPhrasesError::Empty)?;
+            let length_mismatch = || PinyinPhrasesError::LengthMismatch {
+                phrase: phrase.into(),
+                pinyins: pinyins.into(),
+            };
+            if pinyins.split_whitespace().count() != phrase.chars().count() {
+                return Err(length_mismatch());
+            }
+            let pinyins = phrase
+                .chars()
+                .zip(pinyins.split_whitespace())
+                .map(|(hanzi, pinyin)| Self::resolve(hanzi, pinyin))
+                .collect::<Result<_, _>>()?;
+
+            let phrase = Phrase {
+                rest: hanzi.collect(),
+                pinyins,
+            };
+            let phrases = table.entry(first).or_default();
+            match phrases.iter_mut().find(|p| p.rest == phrase.rest) {
+                Some(p) => *p = phrase,
+                None => phrases.push(phrase),
+            }
+        }
+        for phrases in table.values_mut() {
+            phrases.sort_by_key(|p| core::cmp::Reverse(p.rest.len()));
+        }
+        Ok(Self { phrases: table })
+    }
+
+    /// [`PinyinPhrases::new()`] with [`BUILTIN_PHRASES`].
+    pub fn builtin() -> Self {
+        Self::new(BUILTIN_PHRASES.iter().copied()).unwrap()
+    }
+
+    /// The number of phrases.
+    pub fn len(&self) -> usize {
+        self.phrases.values().map(|phrases| phrases.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+
+    /// Pinyin indices of `hanzi` that are spelled as `pinyin`.
+    fn resolve(hanzi: char, pinyin: &str) -> Result<PhrasePinyins, PinyinPhrasesError> {
+        let indices: PhrasePinyins = match PinyinData::get_pinyin_index(hanzi) {
+            Some(i) if i < data::PINYINS.len() as u16 => Box::new([i]),
+            Some(i) => PinyinData::pinyin_combination(i - data::PINYINS.len() as u16)
+                .copied()
+                .collect(),
+            None => Box::new([]),
+        };
+        let toned = pinyin.ends_with(|c: char| c.is_ascii_digit());
+        let indices: PhrasePinyins = indices
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let unicode = data::PINYINS[i as usize];
+                unicode == pinyin
+                    || if toned {
+                        notation::unicode_to_ascii_tone(unicode).as_str() == pinyin
+                    } else {
+                        notation::unicode_to_ascii(unicode).as_str() == pinyin
+                    }
+            })
+            .collect();
+        if indices.is_empty() {
+            return Err(PinyinPhrasesError::UnknownPinyin {
+                hanzi,
+                pinyin: pinyin.into(),
+            });
+        }
+        Ok(indices)
+    }
+
+    /// Find the longest phrase starting with `first`, where `starts_with` tests if the rest of the phrase follows.
+    ///
+    /// Returns the pinyins of each hanzi of the phrase.
+    pub(crate) fn find(
+        &self,
+        first: char,
+        mut starts_with: impl FnMut(&[char]) -> bool,
+    ) -> Option<&[PhrasePinyins]> {
+        self.phrases
+            .get(&first)?
+            .iter()
+            .find(|phrase| starts_with(&phrase.rest))
+            .map(|phrase| &*phrase.pinyins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(phrases: &'a PinyinPhrases, s: &str) -> Option<&'a [PhrasePinyins]> {
+        let mut chars = s.chars();
+        let first = chars.next().unwrap();
+        let rest: Vec<char> = chars.collect();
+        phrases.find(first, |phrase| rest.starts_with(phrase))
+    }
+
+    fn unicodes(pinyins: &[PhrasePinyins]) -> Vec<Vec<&'static str>> {
+        pinyins
+            .iter()
+            .map(|indices| indices.iter().map(|&i| data::PINYINS[i as usize]).collect())
+            .collect()
+    }
+
+    #[test]
+    fn builtin() {
+        let phrases = PinyinPhrases::builtin();
+        assert_eq!(phrases.len(), BUILTIN_PHRASES.len());
+        assert_eq!(
+            unicodes(find(&phrases, "重庆市").unwrap()),
+            [["chóng"], ["qìng"]]
+        );
+        assert_eq!(find(&phrases, "重要"), None);
+        assert_eq!(find(&phrases, "重"), None);
+    }
+
+    #[test]
+    fn new() {
+        let phrases = PinyinPhrases::new([
+            ("重庆", "chong qing"),
+            ("重庆", "chóng qìng"),
+            ("重", "zhong4"),
+            ("重庆市", "chong2 qing4 shi4"),
+        ])
+        .unwrap();
+        assert_eq!(phrases.len(), 3);
+        assert_eq!(
+            unicodes(find(&phrases, "重庆").unwrap()),
+            [["chóng"], ["qìng"]]
+        );
+        assert_eq!(find(&phrases, "重庆市").unwrap().len(), 3);
+        assert_eq!(unicodes(find(&phrases, "重新").unwrap()), [["zhòng"]]);
+
+        // Without tone
+        let phrases = PinyinPhrases::new([("长长", "chang zhang")]).unwrap();
+        assert_eq!(
+            unicodes(find(&phrases, "长长").unwrap()),
+            [["cháng"], ["zhǎng"]]
+        );
+
+        assert_eq!(
+            PinyinPhrases::new([("", "")]).unwrap_err(),
+            PinyinPhrasesError::Empty
+        );
+        assert_eq!(
+            PinyinPhrases::new([("重庆", "chong")]).unwrap_err(),
+            PinyinPhrasesError::LengthMismatch {
+                phrase: "重庆".into(),
+                pinyins: "chong".into()
+            }
+        );
+        assert_eq!(
+            PinyinPhrases::new([("重庆", "chong qin")]).unwrap_err(),
+            PinyinPhrasesError::UnknownPinyin {
+                hanzi: '庆',
+                pinyin: "qin".into()
+            }
+        );
+        assert_eq!(
+            PinyinPhrases::new([("重庆", "chong1 qing")]).unwrap_err(),
+            PinyinPhrasesError::UnknownPinyin {
+                hanzi: '重',
+                pinyin: "chong1".into()
+            }
+        );
+    }
+}
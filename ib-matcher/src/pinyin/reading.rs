@@ -0,0 +1,257 @@
+/*!
+Per-hanzi pinyin overrides.
+
+The built-in pinyin data covers the common readings of hanzi, but specialized corpora may need different ones,
+e.g. only the domain-specific reading of a heteronym (多音字), or a reading missing from the data.
+A [`PinyinReadings`] table adds readings to hanzi or replaces their readings,
+and can be passed to [`PinyinMatchConfigBuilder::readings()`](crate::matcher::PinyinMatchConfigBuilder::readings).
+
+## Example
+```
+// cargo add ib-matcher --features pinyin
+use ib_matcher::{
+    matcher::{IbMatcher, PinyinMatchConfig},
+    pinyin::{PinyinNotation, PinyinReadings},
+};
+
+let readings = PinyinReadings::new()
+    // Only "hang" in banking
+    .replace('行', "hang2")
+    .unwrap()
+    // In addition to "zhong" and "chong"
+    .add('重', "tong")
+    .unwrap();
+let config = PinyinMatchConfig::builder(PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter)
+    .readings(&readings)
+    .build();
+
+let matcher = IbMatcher::builder("yinhang").pinyin(config.shallow_clone()).build();
+assert!(matcher.is_match("银行"));
+let matcher = IbMatcher::builder("xing").pinyin(config.shallow_clone()).build();
+assert!(!matcher.is_match("行"));
+let matcher = IbMatcher::builder("t").pinyin(config.shallow_clone()).build();
+assert!(matcher.is_match("重"));
+assert!(IbMatcher::builder("zhong").pinyin(config).build().is_match("重"));
+```
+
+## Matching
+- The readings of a hanzi are the built-in ones plus the [added](PinyinReadings::add) ones, or only the [replaced](PinyinReadings::replace) ones.
+- All the pinyin notations, [Shuangpin](super::shuangpin) and [fuzzy pinyin](super::fuzzy) are applied to the readings,
+  i.e. the readings decide which syllables a hanzi has, and the notations decide how the syllables can be spelled.
+- [Phrase readings](super::phrase) take precedence over the readings here.
+- [`PinyinNotation::Jyutping`](super::PinyinNotation::Jyutping) is not affected.
+*/
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+
+use super::{data, notation};
+
+/// Error when building [`PinyinReadings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PinyinReadingsError {
+    /// ASCII chars can only be matched as plain chars.
+    Ascii(char),
+    /// The pinyin is not a valid pinyin.
+    UnknownPinyin { hanzi: char, pinyin: String },
+}
+
+impl fmt::Display for PinyinReadingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PinyinReadingsError::Ascii(c) => {
+                write!(f, "ASCII char {c:?} cannot have pinyin readings")
+            }
+            PinyinReadingsError::UnknownPinyin { hanzi, pinyin } => {
+                write!(f, "{pinyin} of {hanzi} is not a valid pinyin")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PinyinReadingsError {}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Readings {
+    /// Whether to replace the built-in readings instead of adding to them.
+    pub replace: bool,
+    /// Indices into `data::PINYINS`.
+    pub pinyins: Box<[u16]>,
+}
+
+/// A table of per-hanzi pinyin readings. See [`reading`](super::reading) for details.
+#[derive(Clone, Debug, Default)]
+pub struct PinyinReadings {
+    readings: BTreeMap<char, Readings>,
+}
+
+impl PinyinReadings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `pinyins` to the readings of `hanzi`.
+    ///
+    /// `pinyins` are separated by spaces, e.g. `"chong2 zhong4"`.
+    /// Each pinyin can be in ASCII (`chong`), ASCII with a tone number (`chong2`, `5` for the neutral tone) or Unicode (`chóng`),
+    /// with "ü" spelled as "v" in ASCII. A pinyin without tone adds all tones of it.
+    ///
+    /// If `hanzi` is given more than once, the pinyins are merged.
+    /// If its readings are [replaced](PinyinReadings::replace), they are added to the replaced ones.
+    ///
+    /// ## Errors
+    /// See [`PinyinReadingsError`].
+    pub fn add(self, hanzi: char, pinyins: &str) -> Result<Self, PinyinReadingsError> {
+        self.insert(hanzi, pinyins, false)
+    }
+
+    /// Replace the readings of `hanzi` with `pinyins`, e.g. to force a particular reading of a heteronym.
+    ///
+    /// `pinyins` are in the same format as [`PinyinReadings::add()`].
+    /// Empty `pinyins` remove all the readings of `hanzi`, i.e. it can only be matched as a plain char.
+    ///
+    /// If `hanzi` is given more than once, the last one is used.
+    ///
+    /// ## Errors
+    /// See [`PinyinReadingsError`].
+    pub fn replace(self, hanzi: char, pinyins: &str) -> Result<Self, PinyinReadingsError> {
+        self.insert(hanzi, pinyins, true)
+    }
+
+    fn insert(
+        mut self,
+        hanzi: char,
+        pinyins: &str,
+        replace: bool,
+    ) -> Result<Self, PinyinReadingsError> {
+        if hanzi.is_ascii() {
+            return Err(PinyinReadingsError::Ascii(hanzi));
+        }
+        let mut indices = Vec::new();
+        for pinyin in pinyins.split_whitespace() {
+            let len = indices.len();
+            indices.extend(Self::resolve(pinyin));
+            if indices.len() == len {
+                return Err(PinyinReadingsError::UnknownPinyin {
+                    hanzi,
+                    pinyin: pinyin.into(),
+                });
+            }
+        }
+
+        match self.readings.get_mut(&hanzi) {
+            Some(readings) if !replace => {
+                indices.extend_from_slice(&readings.pinyins);
+                indices.sort_unstable();
+                indices.dedup();
+                readings.pinyins = indices.into();
+            }
+            _ => {
+                indices.sort_unstable();
+                indices.dedup();
+                self.readings.insert(
+                    hanzi,
+                    Readings {
+                        replace,
+                        pinyins: indices.into(),
+                    },
+                );
+            }
+        }
+        Ok(self)
+    }
+
+    /// The number of hanzi with readings.
+    pub fn len(&self) -> usize {
+        self.readings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.readings.is_empty()
+    }
+
+    /// Pinyin indices spelled as `pinyin`.
+    fn resolve(pinyin: &str) -> impl Iterator<Item = u16> + '_ {
+        let toned = pinyin.ends_with(|c: char| c.is_ascii_digit());
+        (0..data::PINYINS.len() as u16).filter(move |&i| {
+            let unicode = data::PINYINS[i as usize];
+            unicode == pinyin
+                || if toned {
+                    notation::unicode_to_ascii_tone(unicode).as_str() == pinyin
+                } else {
+                    notation::unicode_to_ascii(unicode).as_str() == pinyin
+                }
+        })
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, hanzi: char) -> Option<&Readings> {
+        self.readings.get(&hanzi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unicodes(readings: &PinyinReadings, hanzi: char) -> (bool, Vec<&'static str>) {
+        let readings = readings.get(hanzi).unwrap();
+        (
+            readings.replace,
+            readings
+                .pinyins
+                .iter()
+                .map(|&i| data::PINYINS[i as usize])
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn new() {
+        let readings = PinyinReadings::new()
+            .replace('行', "hang2")
+            .unwrap()
+            .add('重', "tong2 zhòng")
+            .unwrap()
+            .add('重', "zhong4")
+            .unwrap();
+        assert_eq!(readings.len(), 2);
+        assert_eq!(unicodes(&readings, '行'), (true, vec!["háng"]));
+        assert_eq!(unicodes(&readings, '重').1.len(), 2);
+        assert!(readings.get('中').is_none());
+
+        // Adding to replaced readings
+        let readings = readings.add('行', "xing2").unwrap();
+        assert_eq!(unicodes(&readings, '行'), (true, vec!["háng", "xíng"]));
+        // Replacing again
+        let readings = readings.replace('行', "heng2").unwrap();
+        assert_eq!(unicodes(&readings, '行'), (true, vec!["héng"]));
+
+        // Without tone
+        let readings = PinyinReadings::new().add('〇', "ling").unwrap();
+        assert!(unicodes(&readings, '〇').1.len() > 1);
+
+        // Removing all readings
+        let readings = PinyinReadings::new().replace('行', "").unwrap();
+        assert_eq!(unicodes(&readings, '行'), (true, vec![]));
+
+        assert_eq!(
+            PinyinReadings::new().add('a', "a").unwrap_err(),
+            PinyinReadingsError::Ascii('a')
+        );
+        assert_eq!(
+            PinyinReadings::new().add('行', "xing6").unwrap_err(),
+            PinyinReadingsError::UnknownPinyin {
+                hanzi: '行',
+                pinyin: "xing6".into()
+            }
+        );
+        assert_eq!(
+            PinyinReadings::new().replace('行', "hang xyz").unwrap_err(),
+            PinyinReadingsError::UnknownPinyin {
+                hanzi: '行',
+                pinyin: "xyz".into()
+            }
+        );
+    }
+}
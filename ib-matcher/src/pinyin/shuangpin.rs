@@ -0,0 +1,777 @@
+/*!
+User-defined 双拼 (Shuangpin) schemes.
+
+The built-in schemes are available as diletter notations, e.g. [`PinyinNotation::DiletterXiaohe`](super::PinyinNotation::DiletterXiaohe),
+and listed with their names and key layouts by [`PinyinNotation::shuangpin_schemes()`](super::PinyinNotation::shuangpin_schemes).
+For other layouts, a [`ShuangpinScheme`] can be defined and passed to [`PinyinMatchConfigBuilder::shuangpin()`](crate::matcher::PinyinMatchConfigBuilder::shuangpin).
+
+## Example
+```
+// cargo add ib-matcher --features pinyin
+use ib_matcher::{
+    matcher::{IbMatcher, PinyinMatchConfig},
+    pinyin::{PinyinNotation, ShuangpinScheme},
+};
+
+// 小鹤双拼
+let scheme = ShuangpinScheme::builder()
+    .initials(&[("zh", 'v'), ("ch", 'i'), ("sh", 'u')])
+    .finals(&[
+        ("i", 'i'), ("u", 'u'), ("v", 'v'),
+        ("e", 'e'), ("ie", 'p'), ("o", 'o'), ("uo", 'o'), ("ue", 't'), ("ve", 't'),
+        ("a", 'a'), ("ia", 'x'), ("ua", 'x'),
+        ("ei", 'w'), ("ui", 'v'),
+        ("ai", 'd'), ("uai", 'k'),
+        ("ou", 'z'), ("iu", 'q'),
+        ("ao", 'c'), ("iao", 'n'),
+        ("in", 'b'), ("un", 'y'), ("vn", 'y'),
+        ("en", 'f'),
+        ("an", 'j'), ("ian", 'm'), ("uan", 'r'), ("van", 'r'),
+        ("ing", 'k'),
+        ("ong", 's'), ("iong", 's'),
+        ("eng", 'g'),
+        ("ang", 'h'), ("iang", 'l'), ("uang", 'l'),
+    ])
+    .zero_initials(&[("a", "aa"), ("e", "ee"), ("o", "oo"), ("eng", "eg"), ("ang", "ah")])
+    .build()
+    .unwrap();
+
+let matcher = IbMatcher::builder("pbyb")
+    .pinyin(
+        PinyinMatchConfig::builder(PinyinNotation::empty())
+            .shuangpin(&scheme)
+            .build(),
+    )
+    .build();
+assert!(matcher.is_match("拼音"));
+```
+*/
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use bon::bon;
+
+use super::{
+    data,
+    notation::{self, ascii_to_diletter, diletter_syllable},
+    Pinyin, PinyinNotation, PinyinString,
+};
+
+/// Error when building a [`ShuangpinScheme`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShuangpinSchemeError {
+    /// The name is not an initial, a final or a zero-initial syllable.
+    Unknown(String),
+    /// The initial, final or zero-initial syllable is mapped more than once.
+    Duplicate(String),
+    /// The keys are not one (or two for zero-initial syllables) lowercase ASCII letters or punctuation characters.
+    InvalidKeys(String),
+    /// "zh", "ch" or "sh" is not mapped.
+    UnmappedInitial(&'static str),
+    /// The final is not mapped.
+    UnmappedFinal(String),
+    /// The zero-initial syllable is not mapped, and its final is not 2 letters long.
+    UnmappedZeroInitial(String),
+    /// Two syllables are spelled as the same keys,
+    /// and they are not just two finals sharing a key after the same initial.
+    Conflict {
+        keys: String,
+        syllables: (String, String),
+    },
+}
+
+impl fmt::Display for ShuangpinSchemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShuangpinSchemeError::Unknown(name) => {
+                write!(f, "unknown initial, final or syllable: {name}")
+            }
+            ShuangpinSchemeError::Duplicate(name) => write!(f, "{name} is mapped more than once"),
+            ShuangpinSchemeError::InvalidKeys(keys) => write!(f, "invalid keys: {keys}"),
+            ShuangpinSchemeError::UnmappedInitial(initial) => {
+                write!(f, "initial {initial} is not mapped")
+            }
+            ShuangpinSchemeError::UnmappedFinal(final_) => {
+                write!(f, "final {final_} is not mapped")
+            }
+            ShuangpinSchemeError::UnmappedZeroInitial(syllable) => {
+                write!(f, "zero-initial syllable {syllable} is not mapped")
+            }
+            ShuangpinSchemeError::Conflict {
+                keys,
+                syllables: (a, b),
+            } => write!(f, "{a} and {b} are both spelled as {keys}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ShuangpinSchemeError {}
+
+/// A user-defined 双拼 (Shuangpin) scheme. See [`shuangpin`](super::shuangpin) for details.
+///
+/// Like the built-in schemes, the syllables that can't be spelled are mapped to similar ones, e.g. "ng" (嗯) to "en".
+#[derive(Clone, Debug)]
+pub struct ShuangpinScheme {
+    /// Indexed by pinyin indices.
+    codes: Box<[PinyinString]>,
+    /// Sorted and deduplicated.
+    unique_codes: Box<[PinyinString]>,
+}
+
+#[bon]
+impl ShuangpinScheme {
+    /// ## Errors
+    /// See [`ShuangpinSchemeError`].
+    #[builder]
+    pub fn new(
+        /// Keys of initials, e.g. `("zh", 'v')`.
+        ///
+        /// "zh", "ch" and "sh" must be mapped. Other initials are their own keys by default.
+        initials: &[(&str, char)],
+        /// Keys of finals, e.g. `("iang", 'l')`. Every final must be mapped.
+        ///
+        /// "ü" is spelled as "v", e.g. "ve" and "van".
+        /// Finals can share a key if they don't follow the same initial (mostly), e.g. "ong" and "iong".
+        finals: &[(&str, char)],
+        /// Keys of syllables without initials, e.g. `("ang", "ah")`.
+        ///
+        /// A syllable not mapped is spelled as its final itself, which must be 2 letters long, e.g. "ai".
+        #[builder(default)]
+        zero_initials: &[(&str, &str)],
+    ) -> Result<Self, ShuangpinSchemeError> {
+        let asciis: Vec<PinyinString> = data::PINYINS
+            .iter()
+            .map(|py| notation::unicode_to_ascii(py))
+            .collect();
+
+        // Names of all initials, finals and zero-initial syllables
+        let mut all_initials = BTreeMap::new();
+        let mut all_finals = BTreeMap::new();
+        let mut all_zero_initials = BTreeMap::new();
+        for ascii in &asciis {
+            let syllable = diletter_syllable(ascii);
+            match Pinyin::split_initial_final(syllable) {
+                ("", final_) => all_zero_initials.insert(final_, ()),
+                (initial, final_) => {
+                    all_initials.insert(initial, ());
+                    all_finals.insert(final_, ())
+                }
+            };
+        }
+
+        fn collect<'s>(
+            all: &BTreeMap<&str, ()>,
+            map: impl Iterator<Item = (&'s str, String)>,
+            keys_len: usize,
+        ) -> Result<BTreeMap<&'s str, String>, ShuangpinSchemeError> {
+            let mut result = BTreeMap::new();
+            for (name, keys) in map {
+                if !all.contains_key(name) {
+                    return Err(ShuangpinSchemeError::Unknown(name.into()));
+                }
+                if keys.chars().count() != keys_len
+                    || !keys
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_punctuation())
+                {
+                    return Err(ShuangpinSchemeError::InvalidKeys(keys));
+                }
+                if result.insert(name, keys).is_some() {
+                    return Err(ShuangpinSchemeError::Duplicate(name.into()));
+                }
+            }
+            Ok(result)
+        }
+        let initials = collect(
+            &all_initials,
+            initials.iter().map(|&(name, c)| (name, c.to_string())),
+            1,
+        )?;
+        // "ü" finals after "j", "q", "x" and "y" are spelled as "u" in the data, e.g. "jun" and "juan",
+        // but schemes still have keys for them
+        let known_finals: BTreeMap<&str, ()> = all_finals
+            .keys()
+            .copied()
+            .chain(["vn", "van"])
+            .map(|final_| (final_, ()))
+            .collect();
+        let finals = collect(
+            &known_finals,
+            finals.iter().map(|&(name, c)| (name, c.to_string())),
+            1,
+        )?;
+        let zero_initials = collect(
+            &all_zero_initials,
+            zero_initials
+                .iter()
+                .map(|&(name, keys)| (name, keys.to_string())),
+            2,
+        )?;
+
+        for initial in ["zh", "ch", "sh"] {
+            if !initials.contains_key(initial) {
+                return Err(ShuangpinSchemeError::UnmappedInitial(initial));
+            }
+        }
+        if let Some(&final_) = all_finals.keys().find(|f| !finals.contains_key(*f)) {
+            return Err(ShuangpinSchemeError::UnmappedFinal(final_.into()));
+        }
+        if let Some(&syllable) = all_zero_initials
+            .keys()
+            .find(|s| !zero_initials.contains_key(*s) && s.len() != 2)
+        {
+            return Err(ShuangpinSchemeError::UnmappedZeroInitial(syllable.into()));
+        }
+
+        let codes: Box<[PinyinString]> = asciis
+            .iter()
+            .map(|ascii| {
+                ascii_to_diletter(
+                    ascii,
+                    |syllable| zero_initials.get(syllable).map(String::as_str),
+                    |initial| initials.get(initial).map(String::as_str),
+                    |final_| finals.get(final_).map(String::as_str),
+                )
+            })
+            .collect();
+
+        // Check conflicts
+        let mut syllables: BTreeMap<&str, &str> = BTreeMap::new();
+        for (code, ascii) in codes.iter().zip(&asciis) {
+            let syllable = diletter_syllable(ascii);
+            match syllables.insert(code, syllable) {
+                Some(other) if other != syllable => {
+                    let initial = Pinyin::split_initial_final(syllable).0;
+                    if initial.is_empty() || initial != Pinyin::split_initial_final(other).0 {
+                        return Err(ShuangpinSchemeError::Conflict {
+                            keys: code.to_string(),
+                            syllables: (other.into(), syllable.into()),
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let mut unique_codes = codes.to_vec();
+        unique_codes.sort_unstable();
+        unique_codes.dedup();
+
+        Ok(Self {
+            codes,
+            unique_codes: unique_codes.into_boxed_slice(),
+        })
+    }
+
+    /// The keys of a pinyin in this scheme.
+    pub fn keys<'a>(&'a self, pinyin: &Pinyin) -> &'a str {
+        &self.codes[pinyin.index as usize]
+    }
+
+    /// Match keys in haystack, optionally allowing partial matches.
+    ///
+    /// See [`PinyinData::match_pinyin_partial()`](super::PinyinData::match_pinyin_partial).
+    pub(crate) fn match_keys_partial<'a: 'h, 'h>(
+        &'a self,
+        haystack: &'h str,
+        partial: bool,
+    ) -> impl Iterator<Item = (&'a str, bool)> + 'h {
+        self.unique_codes.iter().filter_map(move |keys| {
+            let keys: &str = keys;
+            if haystack.starts_with(keys) {
+                Some((keys, false))
+            } else if partial && keys.starts_with(haystack) {
+                Some((keys, true))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// A built-in 双拼 (Shuangpin) scheme, i.e. a diletter [`PinyinNotation`].
+/// See [`PinyinNotation::shuangpin_schemes()`].
+///
+/// The key layout is in the format of [`ShuangpinScheme::builder()`],
+/// so it can also be used as a template of user-defined schemes.
+/// The only exception is [`PinyinNotation::DiletterJiajia`], which spells both "en" and "er" as "er",
+/// a [`ShuangpinSchemeError::Conflict`] for user-defined schemes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ShuangpinSchemeInfo {
+    /// A stable id for saving in settings, e.g. `"xiaohe"`.
+    /// See [`PinyinNotation::from_shuangpin_id()`].
+    pub id: &'static str,
+    /// e.g. `"小鹤双拼"`.
+    pub name: &'static str,
+    /// e.g. [`PinyinNotation::DiletterXiaohe`].
+    pub notation: PinyinNotation,
+    /// Keys of initials, e.g. `("zh", 'v')`. Other initials are their own keys.
+    pub initials: &'static [(&'static str, char)],
+    /// Keys of finals, e.g. `("iang", 'l')`.
+    pub finals: &'static [(&'static str, char)],
+    /// Keys of syllables without initials, e.g. `("ang", "ah")`.
+    /// A syllable not listed is spelled as itself.
+    pub zero_initials: &'static [(&'static str, &'static str)],
+}
+
+impl ShuangpinSchemeInfo {
+    /// The keys of an ASCII pinyin, e.g. "pb" for "pin" in 小鹤双拼.
+    pub(super) fn keys(&self, ascii: &str) -> PinyinString {
+        fn get<K: Copy>(map: &[(&str, K)], name: &str) -> Option<K> {
+            map.iter().find(|(n, _)| *n == name).map(|&(_, keys)| keys)
+        }
+        ascii_to_diletter(
+            ascii,
+            |syllable| get(self.zero_initials, syllable),
+            |initial| get(self.initials, initial),
+            |final_| get(self.finals, final_),
+        )
+    }
+}
+
+pub(super) static BUILTIN_SCHEMES: [ShuangpinSchemeInfo; 6] = [
+    ShuangpinSchemeInfo {
+        id: "abc",
+        name: "智能ABC双拼",
+        notation: PinyinNotation::DiletterAbc,
+        initials: ABC_INITIALS,
+        finals: ABC_FINALS,
+        zero_initials: ABC_ZERO_INITIALS,
+    },
+    ShuangpinSchemeInfo {
+        id: "jiajia",
+        name: "拼音加加双拼",
+        notation: PinyinNotation::DiletterJiajia,
+        initials: JIAJIA_INITIALS,
+        finals: JIAJIA_FINALS,
+        zero_initials: JIAJIA_ZERO_INITIALS,
+    },
+    ShuangpinSchemeInfo {
+        id: "microsoft",
+        name: "微软双拼",
+        notation: PinyinNotation::DiletterMicrosoft,
+        initials: MICROSOFT_INITIALS,
+        finals: MICROSOFT_FINALS,
+        zero_initials: MICROSOFT_ZERO_INITIALS,
+    },
+    ShuangpinSchemeInfo {
+        id: "thunisoft",
+        name: "华宇双拼",
+        notation: PinyinNotation::DiletterThunisoft,
+        initials: THUNISOFT_INITIALS,
+        finals: THUNISOFT_FINALS,
+        zero_initials: THUNISOFT_ZERO_INITIALS,
+    },
+    ShuangpinSchemeInfo {
+        id: "xiaohe",
+        name: "小鹤双拼",
+        notation: PinyinNotation::DiletterXiaohe,
+        initials: XIAOHE_INITIALS,
+        finals: XIAOHE_FINALS,
+        zero_initials: XIAOHE_ZERO_INITIALS,
+    },
+    ShuangpinSchemeInfo {
+        id: "zrm",
+        name: "自然码双拼",
+        notation: PinyinNotation::DiletterZrm,
+        initials: ZRM_INITIALS,
+        finals: ZRM_FINALS,
+        zero_initials: ZRM_ZERO_INITIALS,
+    },
+];
+
+const ABC_INITIALS: &[(&str, char)] = &[("zh", 'a'), ("ch", 'e'), ("sh", 'v')];
+#[rustfmt::skip]
+const ABC_FINALS: &[(&str, char)] = &[
+    ("i", 'i'), ("u", 'u'), ("v", 'v'),
+    ("e", 'e'), ("ie", 'x'), ("o", 'o'), ("uo", 'o'), ("ue", 'm'), ("ve", 'm'),
+    ("a", 'a'), ("ia", 'd'), ("ua", 'd'),
+    ("ei", 'q'), ("ui", 'm'),
+    ("ai", 'l'), ("uai", 'c'),
+    ("ou", 'b'), ("iu", 'r'),
+    ("ao", 'k'), ("iao", 'z'),
+    ("in", 'c'), ("un", 'n'), ("vn", 'n'),
+    ("en", 'f'),
+    ("an", 'j'), ("ian", 'w'), ("uan", 'p'), ("van", 'p'),
+    ("ing", 'y'),
+    ("ong", 's'), ("iong", 's'),
+    ("eng", 'g'),
+    ("ang", 'h'), ("iang", 't'), ("uang", 't'),
+];
+#[rustfmt::skip]
+const ABC_ZERO_INITIALS: &[(&str, &str)] = &[
+    ("e", "oe"), ("o", "oo"),
+    ("a", "oa"),
+    ("ei", "oq"),
+    ("ai", "ol"),
+    ("ou", "ob"),
+    ("ao", "ok"),
+    ("en", "of"),
+    ("an", "oj"),
+    ("eng", "og"),
+    ("ang", "oh"),
+];
+
+const JIAJIA_INITIALS: &[(&str, char)] = &[("zh", 'v'), ("ch", 'u'), ("sh", 'i')];
+#[rustfmt::skip]
+const JIAJIA_FINALS: &[(&str, char)] = &[
+    ("i", 'i'), ("u", 'u'), ("v", 'v'),
+    ("e", 'e'), ("ie", 'm'), ("o", 'o'), ("uo", 'o'), ("ue", 'x'), ("ve", 't'),
+    ("a", 'a'), ("ia", 'b'), ("ua", 'b'),
+    ("ei", 'w'), ("ui", 'v'),
+    ("ai", 's'), ("uai", 'x'),
+    ("ou", 'p'), ("iu", 'n'),
+    ("ao", 'd'), ("iao", 'k'),
+    ("in", 'l'), ("un", 'z'), ("vn", 'z'),
+    ("en", 'r'),
+    ("an", 'f'), ("ian", 'j'), ("uan", 'c'), ("van", 'c'),
+    ("ing", 'q'),
+    ("ong", 'y'), ("iong", 'y'),
+    ("eng", 't'),
+    ("ang", 'g'), ("iang", 'h'), ("uang", 'h'),
+];
+#[rustfmt::skip]
+const JIAJIA_ZERO_INITIALS: &[(&str, &str)] = &[
+    ("e", "ee"), ("o", "oo"),
+    ("a", "aa"),
+    ("ei", "ew"),
+    ("ai", "as"),
+    ("ou", "op"),
+    ("ao", "ad"),
+    // Also the keys of "er", which is spelled as itself
+    ("en", "er"),
+    ("an", "af"),
+    ("eng", "et"),
+    ("ang", "ag"),
+];
+
+const MICROSOFT_INITIALS: &[(&str, char)] = &[("zh", 'v'), ("ch", 'i'), ("sh", 'u')];
+#[rustfmt::skip]
+const MICROSOFT_FINALS: &[(&str, char)] = &[
+    ("i", 'i'), ("u", 'u'), ("v", 'y'),
+    ("e", 'e'), ("ie", 'x'), ("o", 'o'), ("uo", 'o'), ("ue", 't'), ("ve", 'v'),
+    ("a", 'a'), ("ia", 'w'), ("ua", 'w'),
+    ("ei", 'z'), ("ui", 'v'),
+    ("ai", 'l'), ("uai", 'y'),
+    ("ou", 'b'), ("iu", 'q'),
+    ("ao", 'k'), ("iao", 'c'),
+    ("in", 'n'), ("un", 'p'), ("vn", 'p'),
+    ("en", 'f'),
+    ("an", 'j'), ("ian", 'm'), ("uan", 'r'), ("van", 'r'),
+    ("ing", ';'),
+    ("ong", 's'), ("iong", 's'),
+    ("eng", 'g'),
+    ("ang", 'h'), ("iang", 'd'), ("uang", 'd'),
+];
+#[rustfmt::skip]
+const MICROSOFT_ZERO_INITIALS: &[(&str, &str)] = &[
+    ("e", "oe"), ("o", "oo"),
+    ("a", "oa"),
+    ("ei", "oz"),
+    ("ai", "ol"),
+    ("ou", "ob"),
+    ("ao", "ok"),
+    ("en", "of"),
+    ("an", "oj"),
+    ("eng", "og"),
+    ("ang", "oh"),
+];
+
+const THUNISOFT_INITIALS: &[(&str, char)] = &[("zh", 'u'), ("ch", 'a'), ("sh", 'i')];
+#[rustfmt::skip]
+const THUNISOFT_FINALS: &[(&str, char)] = &[
+    ("i", 'i'), ("u", 'u'), ("v", 'v'),
+    ("e", 'e'), ("ie", 'd'), ("o", 'o'), ("uo", 'o'), ("ue", 'n'), ("ve", 'n'),
+    ("a", 'a'), ("ia", 'x'), ("ua", 'x'),
+    ("ei", 'k'), ("ui", 'n'),
+    ("ai", 'p'), ("uai", 'y'),
+    ("ou", 'z'), ("iu", 'j'),
+    ("ao", 'q'), ("iao", 'b'),
+    ("in", 'y'), ("un", 'm'), ("vn", 'y'),
+    ("en", 'w'),
+    ("an", 'r'), ("ian", 'f'), ("uan", 'l'), ("van", 'l'),
+    ("ing", ';'),
+    ("ong", 'h'), ("iong", 'h'),
+    ("eng", 't'),
+    ("ang", 's'), ("iang", 'g'), ("uang", 'g'),
+];
+#[rustfmt::skip]
+const THUNISOFT_ZERO_INITIALS: &[(&str, &str)] = &[
+    ("e", "oe"), ("o", "oo"),
+    ("a", "oa"),
+    ("ei", "ok"),
+    ("ai", "op"),
+    ("ou", "oz"),
+    ("ao", "oq"),
+    ("en", "ow"),
+    ("an", "or"),
+    ("eng", "ot"),
+    ("ang", "os"),
+];
+
+const XIAOHE_INITIALS: &[(&str, char)] = &[("zh", 'v'), ("ch", 'i'), ("sh", 'u')];
+#[rustfmt::skip]
+const XIAOHE_FINALS: &[(&str, char)] = &[
+    ("i", 'i'), ("u", 'u'), ("v", 'v'),
+    ("e", 'e'), ("ie", 'p'), ("o", 'o'), ("uo", 'o'), ("ue", 't'), ("ve", 't'),
+    ("a", 'a'), ("ia", 'x'), ("ua", 'x'),
+    ("ei", 'w'), ("ui", 'v'),
+    ("ai", 'd'), ("uai", 'k'),
+    ("ou", 'z'), ("iu", 'q'),
+    ("ao", 'c'), ("iao", 'n'),
+    ("in", 'b'), ("un", 'y'), ("vn", 'y'),
+    ("en", 'f'),
+    ("an", 'j'), ("ian", 'm'), ("uan", 'r'), ("van", 'r'),
+    ("ing", 'k'),
+    ("ong", 's'), ("iong", 's'),
+    ("eng", 'g'),
+    ("ang", 'h'), ("iang", 'l'), ("uang", 'l'),
+];
+#[rustfmt::skip]
+const XIAOHE_ZERO_INITIALS: &[(&str, &str)] = &[
+    ("e", "ee"), ("o", "oo"),
+    ("a", "aa"),
+    ("ei", "ei"),
+    ("ai", "ai"),
+    ("ou", "ou"),
+    ("ao", "ao"),
+    ("en", "en"),
+    ("an", "an"),
+    ("eng", "eg"),
+    ("ang", "ah"),
+    ("er", "er"),
+];
+
+const ZRM_INITIALS: &[(&str, char)] = &[("zh", 'v'), ("ch", 'i'), ("sh", 'u')];
+#[rustfmt::skip]
+const ZRM_FINALS: &[(&str, char)] = &[
+    ("i", 'i'), ("u", 'u'), ("v", 'v'),
+    ("e", 'e'), ("ie", 'x'), ("o", 'o'), ("uo", 'o'), ("ue", 't'), ("ve", 't'),
+    ("a", 'a'), ("ia", 'w'), ("ua", 'w'),
+    ("ei", 'z'), ("ui", 'v'),
+    ("ai", 'l'), ("uai", 'y'),
+    ("ou", 'b'), ("iu", 'q'),
+    ("ao", 'k'), ("iao", 'c'),
+    ("in", 'n'), ("un", 'p'), ("vn", 'p'),
+    ("en", 'f'),
+    ("an", 'j'), ("ian", 'm'), ("uan", 'r'), ("van", 'r'),
+    ("ing", ';'),
+    ("ong", 's'), ("iong", 's'),
+    ("eng", 'g'),
+    ("ang", 'h'), ("iang", 'd'), ("uang", 'd'),
+];
+#[rustfmt::skip]
+const ZRM_ZERO_INITIALS: &[(&str, &str)] = &[
+    ("e", "ee"), ("o", "oo"),
+    ("a", "aa"),
+    ("ei", "ei"),
+    ("ai", "ai"),
+    ("ou", "ou"),
+    ("ao", "ao"),
+    ("en", "en"),
+    ("an", "an"),
+    ("eng", "eg"),
+    ("ang", "ah"),
+    ("er", "er"),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::pinyin::{PinyinData, PinyinNotation};
+
+    use super::*;
+
+    #[rustfmt::skip]
+    const XIAOHE_FINALS: &[(&str, char)] = &[
+        ("i", 'i'), ("u", 'u'), ("v", 'v'),
+        ("e", 'e'), ("ie", 'p'), ("o", 'o'), ("uo", 'o'), ("ue", 't'), ("ve", 't'),
+        ("a", 'a'), ("ia", 'x'), ("ua", 'x'),
+        ("ei", 'w'), ("ui", 'v'),
+        ("ai", 'd'), ("uai", 'k'),
+        ("ou", 'z'), ("iu", 'q'),
+        ("ao", 'c'), ("iao", 'n'),
+        ("in", 'b'), ("un", 'y'), ("vn", 'y'),
+        ("en", 'f'),
+        ("an", 'j'), ("ian", 'm'), ("uan", 'r'), ("van", 'r'),
+        ("ing", 'k'),
+        ("ong", 's'), ("iong", 's'),
+        ("eng", 'g'),
+        ("ang", 'h'), ("iang", 'l'), ("uang", 'l'),
+    ];
+    const XIAOHE_INITIALS: &[(&str, char)] = &[("zh", 'v'), ("ch", 'i'), ("sh", 'u')];
+    const XIAOHE_ZERO_INITIALS: &[(&str, &str)] = &[
+        ("a", "aa"),
+        ("e", "ee"),
+        ("o", "oo"),
+        ("eng", "eg"),
+        ("ang", "ah"),
+    ];
+
+    fn xiaohe() -> Result<ShuangpinScheme, ShuangpinSchemeError> {
+        ShuangpinScheme::builder()
+            .initials(XIAOHE_INITIALS)
+            .finals(XIAOHE_FINALS)
+            .zero_initials(XIAOHE_ZERO_INITIALS)
+            .build()
+    }
+
+    #[test]
+    fn same_as_builtin() {
+        let scheme = xiaohe().unwrap();
+        let data = PinyinData::new(PinyinNotation::DiletterXiaohe);
+        for pinyin in data.iter() {
+            assert_eq!(
+                scheme.keys(&pinyin),
+                pinyin.notation(PinyinNotation::DiletterXiaohe).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn builtin_schemes() {
+        let data = PinyinData::new(PinyinNotation::all());
+        for info in PinyinNotation::shuangpin_schemes() {
+            assert_eq!(
+                PinyinNotation::from_shuangpin_id(info.id),
+                Some(info.notation)
+            );
+            assert_eq!(info.notation.shuangpin_scheme(), Some(info));
+
+            // The layouts are valid user-defined schemes
+            let scheme = match ShuangpinScheme::builder()
+                .initials(info.initials)
+                .finals(info.finals)
+                .zero_initials(info.zero_initials)
+                .build()
+            {
+                Ok(scheme) => scheme,
+                Err(e) => {
+                    // 拼音加加 spells both "en" and "er" as "er"
+                    assert_eq!(info.notation, PinyinNotation::DiletterJiajia);
+                    assert_eq!(
+                        e,
+                        ShuangpinSchemeError::Conflict {
+                            keys: "er".into(),
+                            syllables: ("en".into(), "er".into())
+                        }
+                    );
+                    continue;
+                }
+            };
+            for pinyin in data.iter() {
+                assert_eq!(
+                    scheme.keys(&pinyin),
+                    pinyin.notation(info.notation).unwrap()
+                );
+            }
+        }
+        assert_eq!(
+            PinyinNotation::shuangpin_schemes().len(),
+            PinyinNotation::all()
+                .iter()
+                .filter(|n| n.contains_diletter())
+                .count()
+        );
+        assert_eq!(PinyinNotation::from_shuangpin_id("unknown"), None);
+        assert_eq!(PinyinNotation::Ascii.shuangpin_scheme(), None);
+    }
+
+    #[test]
+    fn builtin_keys() {
+        let syllables = [
+            "a", "o", "e", "ai", "ei", "ao", "ou", "an", "en", "ang", "eng", "er", "zhuang", "chi",
+            "shuo", "lve", "jun", "xiong", "hm", "n", "m", "yuan", "wo",
+        ];
+        #[rustfmt::skip]
+        let golden = [
+            (PinyinNotation::DiletterAbc, ["oa", "oo", "oe", "ol", "oq", "ok", "ob", "oj", "of", "oh", "og", "er", "at", "ei", "vo", "lm", "jn", "xs", "hf", "of", "mu", "yp", "wo"]),
+            (PinyinNotation::DiletterJiajia, ["aa", "oo", "ee", "as", "ew", "ad", "op", "af", "er", "ag", "et", "er", "vh", "ui", "io", "lt", "jz", "xy", "hr", "er", "mu", "yc", "wo"]),
+            (PinyinNotation::DiletterMicrosoft, ["oa", "oo", "oe", "ol", "oz", "ok", "ob", "oj", "of", "oh", "og", "er", "vd", "ii", "uo", "lv", "jp", "xs", "hf", "of", "mu", "yr", "wo"]),
+            (PinyinNotation::DiletterThunisoft, ["oa", "oo", "oe", "op", "ok", "oq", "oz", "or", "ow", "os", "ot", "er", "ug", "ai", "io", "ln", "jm", "xh", "hw", "ow", "mu", "yl", "wo"]),
+            (PinyinNotation::DiletterXiaohe, ["aa", "oo", "ee", "ai", "ei", "ao", "ou", "an", "en", "ah", "eg", "er", "vl", "ii", "uo", "lt", "jy", "xs", "hf", "en", "mu", "yr", "wo"]),
+            (PinyinNotation::DiletterZrm, ["aa", "oo", "ee", "ai", "ei", "ao", "ou", "an", "en", "ah", "eg", "er", "vd", "ii", "uo", "lt", "jp", "xs", "hf", "en", "mu", "yr", "wo"]),
+        ];
+        for (notation, keys) in golden {
+            let info = notation.shuangpin_scheme().unwrap();
+            for (syllable, keys) in syllables.iter().zip(keys) {
+                assert_eq!(info.keys(syllable).as_str(), keys, "{} {syllable}", info.id);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let build = |initials, finals, zero_initials| {
+            ShuangpinScheme::builder()
+                .initials(initials)
+                .finals(finals)
+                .zero_initials(zero_initials)
+                .build()
+                .unwrap_err()
+        };
+
+        assert_eq!(
+            build(
+                &[("zh", 'v'), ("ch", 'i')],
+                XIAOHE_FINALS,
+                XIAOHE_ZERO_INITIALS
+            ),
+            ShuangpinSchemeError::UnmappedInitial("sh")
+        );
+        assert_eq!(
+            build(XIAOHE_INITIALS, &XIAOHE_FINALS[1..], XIAOHE_ZERO_INITIALS),
+            ShuangpinSchemeError::UnmappedFinal("i".into())
+        );
+        assert_eq!(
+            build(XIAOHE_INITIALS, XIAOHE_FINALS, &XIAOHE_ZERO_INITIALS[1..]),
+            ShuangpinSchemeError::UnmappedZeroInitial("a".into())
+        );
+        assert_eq!(
+            build(&[("zhh", 'v')], XIAOHE_FINALS, XIAOHE_ZERO_INITIALS),
+            ShuangpinSchemeError::Unknown("zhh".into())
+        );
+        assert_eq!(
+            build(
+                &[("zh", 'v'), ("ch", 'i'), ("sh", 'u'), ("zh", 'a')],
+                XIAOHE_FINALS,
+                XIAOHE_ZERO_INITIALS
+            ),
+            ShuangpinSchemeError::Duplicate("zh".into())
+        );
+        assert_eq!(
+            build(&[("zh", 'V')], XIAOHE_FINALS, XIAOHE_ZERO_INITIALS),
+            ShuangpinSchemeError::InvalidKeys("V".into())
+        );
+        assert_eq!(
+            build(XIAOHE_INITIALS, XIAOHE_FINALS, &[("a", "a")]),
+            ShuangpinSchemeError::InvalidKeys("a".into())
+        );
+
+        // "zh" and "z" share a key
+        assert_eq!(
+            build(
+                &[("zh", 'z'), ("ch", 'i'), ("sh", 'u')],
+                XIAOHE_FINALS,
+                XIAOHE_ZERO_INITIALS
+            ),
+            ShuangpinSchemeError::Conflict {
+                keys: "za".into(),
+                syllables: ("za".into(), "zha".into())
+            }
+        );
+        // "ang" is spelled as "ba" like "ba"
+        let mut zero_initials = XIAOHE_ZERO_INITIALS.to_vec();
+        zero_initials[4] = ("ang", "ba");
+        assert!(matches!(
+            build(XIAOHE_INITIALS, XIAOHE_FINALS, &zero_initials),
+            ShuangpinSchemeError::Conflict { keys, .. } if keys == "ba"
+        ));
+    }
+}
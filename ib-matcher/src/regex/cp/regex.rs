@@ -1,9 +1,12 @@
-use std::{
+#[cfg(feature = "regex-callback")]
+use alloc::string::String;
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::{
     cell::UnsafeCell,
+    fmt,
     marker::PhantomPinned,
     mem::{transmute, MaybeUninit},
     ops::Deref,
-    sync::Arc,
 };
 
 use bon::bon;
@@ -26,11 +29,58 @@ use crate::{
     syntax::regex::hir,
 };
 
-pub use crate::regex::nfa::{
-    backtrack::{Cache, Config, TryCapturesMatches, TryFindMatches},
-    thompson::BuildError,
+pub use crate::regex::nfa::backtrack::{
+    Cache, Config, TryCapturesMatches, TryFindMatches,
 };
 
+/// An error that occurred while building a [`Regex`].
+#[derive(Clone, Debug)]
+pub enum BuildError {
+    /// An error from parsing the pattern or compiling it.
+    Nfa(thompson::BuildError),
+    /// The capture group of a [backreference](Regex#backreferences) doesn't exist,
+    /// or is optional or in a repetition.
+    #[cfg(feature = "regex-callback")]
+    InvalidBackreference { literal: String, group_index: u32 },
+    /// The pattern has too many literals to add the [backreferences](Regex#backreferences).
+    #[cfg(feature = "regex-callback")]
+    TooManyLiterals,
+}
+
+impl From<thompson::BuildError> for BuildError {
+    fn from(err: thompson::BuildError) -> Self {
+        BuildError::Nfa(err)
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Nfa(err) => err.fmt(f),
+            #[cfg(feature = "regex-callback")]
+            BuildError::InvalidBackreference { literal, group_index } => write!(
+                f,
+                "backreference {literal:?} requires group {group_index} to match exactly once"
+            ),
+            #[cfg(feature = "regex-callback")]
+            BuildError::TooManyLiterals => {
+                write!(f, "too many literals for backreferences")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Nfa(err) => Some(err),
+            #[cfg(feature = "regex-callback")]
+            _ => None,
+        }
+    }
+}
+
 /// A compiled regular expression for searching Unicode haystacks.
 ///
 /// A `Regex` can be used to search haystacks, split haystacks into substrings
@@ -124,7 +174,20 @@ assert_eq!(re.find("葬送のフリーレン"), Some(Match::must(0, 0..24)));
 /// ```
 /// Note that enabling `syntax.case_insensitive` will make `ib` (i.e. pinyin and romaji match) doesn't work at the moment. You should only set [`MatchConfigBuilder::case_insensitive`](crate::matcher::MatchConfigBuilder::case_insensitive) ([`PlainMatchConfigBuilder::case_insensitive`](crate::matcher::PlainMatchConfigBuilder::case_insensitive)).
 ///
-/// If you need case insensitive character classes, you need to write `(?i:[a-z])` instead at the moment.
+/// Character classes follow `case_insensitive` too, i.e. `[a-z]` is parsed as `(?i:[a-z])`, unless the `i` flag is set explicitly (e.g. `(?-i:[a-z])`):
+/// ```
+/// use ib_matcher::{matcher::{PinyinMatchConfig, MatchConfig}, regex::cp::Regex};
+///
+/// let re = Regex::builder()
+///     .ib(MatchConfig::builder()
+///         .case_insensitive(true)
+///         .pinyin(PinyinMatchConfig::default())
+///         .build())
+///     .build("[a-c]+pyss")
+///     .unwrap();
+/// assert!(re.is_match("ABC拼音搜索"));
+/// ```
+/// This only applies to patterns parsed by [`Builder::build()`] and [`Builder::build_many()`]. `Hir` given to other build methods is used as is.
 ///
 /**
 # Custom matching callbacks
@@ -203,6 +266,30 @@ assert_eq!(
 );
 ```
 (In this simple example, just using `'([^'\\]+?|\\')*'` is actually enough, but there are more complex cases where balancing groups (or recursion/subroutines) are necessary.)
+
+## Backreferences
+[`Builder::backreference()`] registers the callbacks for simple backreferences:
+```
+use ib_matcher::regex::cp::Regex;
+
+let re = Regex::builder()
+    .backreference("same_word", 1)
+    .build(r"^(\w+) (same_word)\b")
+    .unwrap();
+assert_eq!(re.find("hello hello world").unwrap().span(), 0..11);
+assert!(re.is_match("hello help") == false);
+```
+The backreference compares the bytes of the group exactly, even if the group is matched by pinyin or romaji.
+
+Only non-recursive reuse of an earlier group is supported:
+- The group must match before the backreference, and must not be optional or in a repetition,
+  otherwise [`BuildError::InvalidBackreference`] is returned.
+- The pattern can have at most 256 literals, including two for each backreference,
+  otherwise [`BuildError::TooManyLiterals`] is returned.
+
+The span of the group is kept in the search [`Cache`], so a `Regex` with backreferences can be shared like any other one.
+
+Backreferences also disable some memoization of the backtracker, so the search may be slower.
 */
 /// # Synchronization and cloning
 ///
@@ -384,16 +471,18 @@ impl<'a> Regex<'a> {
     }))]
     pub fn builder(
         #[builder(field)] syntax: Option<util::syntax::Config>,
+        #[builder(field = MatchConfig::builder().case_insensitive(false).build())]
+        ib: MatchConfig<'a>,
         #[cfg(feature = "regex-callback")]
         #[builder(field)]
         callbacks: Vec<(String, Callback)>,
+        #[cfg(feature = "regex-callback")]
+        #[builder(field)]
+        backreferences: Vec<(String, u32)>,
         #[builder(finish_fn)] hirs: Vec<Hir>,
         /// Thompson NFA config. Named `configure` to be compatible with [`regex_automata::meta::Builder`]. Although some fields are not supported and `utf8_empty` is named as `utf8` instead.
         #[builder(default)]
         configure: thompson::Config,
-        /// [`IbMatcher`] config.
-        #[builder(default = MatchConfig::builder().case_insensitive(false).build())]
-        ib: MatchConfig<'a>,
         /// `IbMatcher` pattern parser.
         ///
         /// ### Example
@@ -417,24 +506,57 @@ impl<'a> Regex<'a> {
         #[cfg(test)]
         dbg!(&hirs);
 
-        let mut imp = Arc::new(RegexI {
-            re: MaybeUninit::uninit(),
-            config: {
-                let mut config = ib;
-                config.starts_with = true;
-                config
-            },
-            _pin: PhantomPinned,
-        });
-
-        let case_insensitive =
-            imp.config.plain.as_ref().is_some_and(|p| p.case_insensitive);
+        let case_insensitive = ib.is_plain_case_insensitive();
         #[cfg(feature = "perf-literal-substring")]
         #[allow(unused_mut)]
         let mut first_byte = hir::literal::extract_first_byte(&hirs);
 
         // Copy-and-patch NFA
         let (hirs, literals) = hir::fold::fold_literal_utf8(hirs.into_iter());
+        #[cfg(feature = "regex-callback")]
+        let (hirs, markers) = {
+            let mut hirs = hirs;
+            let mut markers = Vec::new();
+            for (literal, group_index) in &backreferences {
+                let once = hirs
+                    .iter()
+                    .filter_map(|hir| {
+                        hir::capture::capture_matches_once(hir, *group_index)
+                    })
+                    .reduce(|a, b| a && b);
+                if once != Some(true) {
+                    return Err(BuildError::InvalidBackreference {
+                        literal: literal.clone(),
+                        group_index: *group_index,
+                    });
+                }
+
+                // Zero-width markers around the group, after the folded literals
+                let open = literals.len() + markers.len();
+                if open >= u8::MAX as usize {
+                    return Err(BuildError::TooManyLiterals);
+                }
+                let (open, close) = (open as u8, open as u8 + 1);
+                hirs = hirs
+                    .into_iter()
+                    .map(|hir| {
+                        hir::capture::map_capture(
+                            hir,
+                            *group_index,
+                            &mut |sub| {
+                                Hir::concat(vec![
+                                    Hir::literal([open]),
+                                    sub,
+                                    Hir::literal([close]),
+                                ])
+                            },
+                        )
+                    })
+                    .collect();
+                markers.extend([open, close]);
+            }
+            (hirs, markers)
+        };
         let mut nfa: NFA = thompson::Compiler::new()
             .configure(configure)
             .build_many_from_hir(&hirs)?
@@ -457,8 +579,43 @@ impl<'a> Regex<'a> {
                     count -= 1;
                 }
             }
+            // The span of the i-th backreference group is in the slots 2i and 2i+1 of the cache
+            for (i, (literal, _)) in backreferences.iter().enumerate() {
+                for b in literals.iter().positions(|l| l == literal) {
+                    #[cfg(feature = "perf-literal-substring")]
+                    if first_byte == Some(literal.as_bytes()[0]) {
+                        first_byte = None;
+                    }
+
+                    nfa.patch_first_byte(b as u8, |next| {
+                        crate::regex::nfa::State::Backref { slot: 2 * i, next }
+                    });
+                    count -= 1;
+                }
+            }
+            for (slot, byte) in markers.into_iter().enumerate() {
+                // Once in each pattern with the group
+                while nfa
+                    .patch_first_byte(byte, |next| {
+                        crate::regex::nfa::State::BackrefSlot { slot, next }
+                    })
+                    .is_some()
+                {}
+            }
             count
         };
+
+        // Dropping `imp` requires `re` to be initialized, so no errors can be returned after this
+        // (`build_from_nfa()` never fails)
+        let mut imp = Arc::new(RegexI {
+            re: MaybeUninit::uninit(),
+            config: {
+                let mut config = ib;
+                config.starts_with = true;
+                config
+            },
+            _pin: PhantomPinned,
+        });
         nfa.patch_bytes_to_matchers(literals.len() as u8, count, |b| {
             let pattern = literals[b as usize].as_str();
             let pattern = if let Some(ib_parser) = ib_parser.as_mut() {
@@ -491,6 +648,17 @@ impl<'a> Regex<'a> {
 }
 
 impl<'a, S: builder::State> Builder<'a, '_, S> {
+    /// [`IbMatcher`] config.
+    pub fn ib(mut self, ib: MatchConfig<'a>) -> Self {
+        self.ib = ib;
+        self
+    }
+
+    /// See [case insensitivity](Regex#case-insensitivity).
+    fn class_case_insensitive(&self) -> bool {
+        self.ib.is_plain_case_insensitive()
+    }
+
     /// Configure the syntax options when parsing a pattern string while
     /// building a `Regex`.
     ///
@@ -528,6 +696,19 @@ impl<'a, S: builder::State> Builder<'a, '_, S> {
         self
     }
 
+    /// Add a [backreference](Regex#backreferences) `literal` to the capture group `group_index`.
+    ///
+    /// `literal` matches the same text as the last match of the group, like `\1` in other regex engines.
+    #[cfg(feature = "regex-callback")]
+    pub fn backreference(
+        mut self,
+        literal: impl Into<String>,
+        group_index: u32,
+    ) -> Self {
+        self.backreferences.push((literal.into(), group_index));
+        self
+    }
+
     /// Builds a `Regex` from a single pattern string.
     ///
     /// If there was a problem parsing the pattern or a problem turning it into
@@ -586,6 +767,7 @@ impl<'a, S: builder::State> Builder<'a, '_, S> {
         // Bypass case_fold_char()
         // case_insensitive class and (?i) will be broken
         // .case_insensitive(false)
+        let class_case_insensitive = self.class_case_insensitive();
         let syntax = self.syntax.unwrap_or_else(util::syntax::config_auto);
 
         // Parse
@@ -593,14 +775,18 @@ impl<'a, S: builder::State> Builder<'a, '_, S> {
             .into_iter()
             .map(|pattern| {
                 let pattern = pattern.as_ref();
-                regex_automata::util::syntax::parse_with(pattern, &syntax)
-                    .map_err(|_| {
-                        // Shit
-                        thompson::Compiler::new()
-                            .syntax(syntax)
-                            .build(pattern)
-                            .unwrap_err()
-                    })
+                util::syntax::parse_with_class_case(
+                    pattern,
+                    &syntax,
+                    class_case_insensitive,
+                )
+                .map_err(|_| {
+                    // Shit
+                    thompson::Compiler::new()
+                        .syntax(syntax)
+                        .build(pattern)
+                        .unwrap_err()
+                })
             })
             .try_collect()?;
         self.build_many_from_hir(hirs)
@@ -1132,4 +1318,88 @@ mod tests {
             vec![",this4", "me1"]
         );
     }
+
+    #[cfg(feature = "regex-callback")]
+    #[test]
+    fn backreference() {
+        let re = Regex::builder()
+            .backreference("same", 1)
+            .build(r"^(\w+) (same)\b")
+            .unwrap();
+        assert_eq!(re.find("hello hello world"), Some(Match::must(0, 0..11)));
+        assert_eq!(re.find("hello help"), None);
+        assert_eq!(re.find("hello hellooo"), None);
+
+        // Unanchored
+        let re = Regex::builder()
+            .backreference("same", 1)
+            .build(r"(\w+) (same)\b")
+            .unwrap();
+        assert_eq!(re.find("ab b"), Some(Match::must(0, 1..4)));
+        assert_eq!(re.find("a bc bc"), Some(Match::must(0, 2..7)));
+
+        // Backtracking into the group
+        let re = Regex::builder()
+            .backreference("same", 1)
+            .build(r"^(a+)a*(same)$")
+            .unwrap();
+        let mut caps = re.create_captures();
+        re.captures("aaaa", &mut caps).unwrap();
+        assert_eq!(caps.get_group(1).unwrap().range(), 0..2);
+        assert_eq!(re.find("aaaaa"), Some(Match::must(0, 0..5)));
+
+        // Multiple backreferences
+        let re = Regex::builder()
+            .backreference("x", 1)
+            .backreference("y", 2)
+            .build(r"^(\d+)-(\w+):(y)-(x)$")
+            .unwrap();
+        assert!(re.is_match("12-ab:ab-12"));
+        assert!(!re.is_match("12-ab:12-ab"));
+
+        // With pinyin
+        let re = Regex::builder()
+            .ib(MatchConfig::builder().pinyin(Default::default()).build())
+            .backreference("same", 1)
+            .build(r"^(pin)(same)")
+            .unwrap();
+        assert!(re.is_match("拼拼音"));
+        assert!(!re.is_match("拼品"));
+
+        // The span of the group doesn't leak between searches
+        let re = Regex::builder()
+            .backreference("same", 1)
+            .build(r"^(\w+) (same)")
+            .unwrap();
+        let mut cache = re.create_cache();
+        assert!(re.try_find(&mut cache, "ab ab").unwrap().is_some());
+        assert_eq!(re.try_find(&mut cache, "cd ab").unwrap(), None);
+        assert_eq!(
+            re.try_find(&mut cache, "cd cd").unwrap(),
+            Some(Match::must(0, 0..5))
+        );
+    }
+
+    #[test]
+    fn backreference_invalid() {
+        for pattern in
+            [r"^(\w+)? (same)", r"^(\w)+ (same)", r"^(?:(a)|b) (same)"]
+        {
+            let re = Regex::builder().backreference("same", 1).build(pattern);
+            assert!(
+                matches!(re, Err(BuildError::InvalidBackreference { .. })),
+                "{pattern}"
+            );
+        }
+        // No such group
+        let re =
+            Regex::builder().backreference("same", 3).build(r"^(\w+) (same)");
+        assert!(matches!(re, Err(BuildError::InvalidBackreference { .. })));
+
+        let pattern = (0..300).map(|i| format!("w{i}")).join(r"\s");
+        let re = Regex::builder()
+            .backreference("same", 1)
+            .build(&format!("^(a) {pattern} (same)"));
+        assert!(matches!(re, Err(BuildError::TooManyLiterals)));
+    }
 }
@@ -10,7 +10,6 @@ A typical use case that meets the above conditions is matching file names and pa
 It has the following limitations though:
 - UTF-8 only. The pattern and haystack must be valid UTF-8, otherwise the engine may panic.
 - No `find_iter()` and `captures_iter()` at the moment.
-- No `build_many()`.
 - No custom matching callback support.
 
 The primary type in this module is [`Regex`].
@@ -19,7 +18,9 @@ The primary type in this module is [`Regex`].
 When the pattern is a literal string, [`cp::Regex`](crate::regex::cp::Regex) is much slower than [`IbMatcher`](crate::matcher::IbMatcher). This engine uses enum dispatch to utilize the performance of [`IbMatcher`](crate::matcher::IbMatcher) if the pattern is a literal string, and fall back to [`cp::Regex`](crate::regex::cp::Regex) for other patterns.
 
 And if the haystack is ASCII-only, this engine will try to use a dense DFA first.
+
+Multiple patterns can be built with [`Builder::build_many()`]. If all of them are literal strings, they are matched by one [`IbMatcher`](crate::matcher::IbMatcher) each, otherwise they are matched together as with a single pattern.
 */
 mod regex;
 
-pub use regex::{BuildError, Builder, Config, Regex};
+pub use regex::{BuildError, Builder, Config, DeserializeError, Regex};
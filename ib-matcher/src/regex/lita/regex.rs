@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use alloc::{sync::Arc, vec, vec::Vec};
 
 use bon::bon;
 use regex_automata::{
@@ -10,7 +10,10 @@ use regex_syntax::hir::{Hir, HirKind};
 
 use crate::{
     matcher::{
-        self, config::IbMatcherWithConfig, pattern::Pattern, MatchConfig,
+        self,
+        config::{IbMatcherWithConfig, IbMatchersWithConfig},
+        pattern::Pattern,
+        MatchConfig,
     },
     regex::{
         cp,
@@ -21,7 +24,8 @@ use crate::{
     syntax::regex::hir,
 };
 
-pub use crate::regex::nfa::{backtrack::Config, thompson::BuildError};
+pub use crate::regex::{cp::BuildError, nfa::backtrack::Config};
+pub use regex_automata::util::wire::DeserializeError;
 
 /// A compiled regular expression for searching Unicode haystacks.
 ///
@@ -116,7 +120,20 @@ assert_eq!(re.find("葬送のフリーレン"), Some(Match::must(0, 0..24)));
 /// ```
 /// Note that enabling `syntax.case_insensitive` will make `ib` (i.e. pinyin and romaji match) doesn't work at the moment. You should only set [`MatchConfigBuilder::case_insensitive`](crate::matcher::MatchConfigBuilder::case_insensitive) ([`PlainMatchConfigBuilder::case_insensitive`](crate::matcher::PlainMatchConfigBuilder::case_insensitive)).
 ///
-/// If you need case insensitive character classes, you need to write `(?i:[a-z])` instead at the moment.
+/// Character classes follow `case_insensitive` too, i.e. `[a-z]` is parsed as `(?i:[a-z])`, unless the `i` flag is set explicitly (e.g. `(?-i:[a-z])`):
+/// ```
+/// use ib_matcher::{matcher::{PinyinMatchConfig, MatchConfig}, regex::lita::Regex};
+///
+/// let re = Regex::builder()
+///     .ib(MatchConfig::builder()
+///         .case_insensitive(true)
+///         .pinyin(PinyinMatchConfig::default())
+///         .build())
+///     .build("[a-c]+pyss")
+///     .unwrap();
+/// assert!(re.is_match("ABC拼音搜索"));
+/// ```
+/// This only applies to patterns parsed by [`Builder::build()`] and [`Builder::build_many()`]. `Hir` given to other build methods is used as is.
 ///
 /// # Synchronization and cloning
 ///
@@ -176,6 +193,7 @@ pub struct Regex<'a> {
 #[derive(Clone)]
 enum RegexI<'a> {
     Ib(Arc<IbMatcherWithConfig<'a>>),
+    IbMany(Arc<IbMatchersWithConfig<'a>>),
     Cp { dfa: dfa::regex::Regex, cp: cp::Regex<'a> },
 }
 
@@ -213,51 +231,51 @@ impl<'a> Regex<'a> {
     ///
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    #[builder(builder_type = Builder, finish_fn(name = build_from_hir, doc {
-    /// Builds a `Regex` directly from an `Hir` expression.
+    #[builder(builder_type = Builder, finish_fn(name = build_many_from_hir, doc {
+    /// Builds a `Regex` directly from many `Hir` expressions.
     ///
-    /// This is useful if you needed to parse a pattern string into an `Hir`
-    /// for other reasons (such as analysis or transformations). This routine
-    /// permits building a `Regex` directly from the `Hir` expression instead
-    /// of first converting the `Hir` back to a pattern string.
+    /// This is useful if you needed to parse pattern strings into `Hir`
+    /// expressions for other reasons (such as analysis or transformations).
+    /// This routine permits building a `Regex` directly from the `Hir`
+    /// expressions instead of first converting the `Hir` expressions back to
+    /// pattern strings.
     ///
     /// When using this method, any options set via [`Builder::syntax`] are
     /// ignored. Namely, the syntax options only apply when parsing a pattern
     /// string, which isn't relevant here.
     ///
     /// If there was a problem building the underlying regex matcher for the
-    /// given `Hir`, then an error is returned.
+    /// given `Hir` expressions, then an error is returned.
     ///
-    /// # Example
+    /// See [`Builder::build_many`] for how multiple patterns are matched.
     ///
-    /// This example shows how one can hand-construct an `Hir` expression and
-    /// build a regex from it without doing any parsing at all.
+    /// # Example
     ///
     /// ```
     /// use ib_matcher::{
     ///     regex::{lita::Regex, Match},
-    ///     syntax::regex::hir::{Hir, Look},
+    ///     syntax::regex::hir::Hir,
     /// };
     ///
-    /// // (?Rm)^foo$
-    /// let hir = Hir::concat(vec![
-    ///     Hir::look(Look::StartCRLF),
+    /// let re = Regex::builder().build_many_from_hir(vec![
     ///     Hir::literal("foo".as_bytes()),
-    ///     Hir::look(Look::EndCRLF),
-    /// ]);
-    /// let re = Regex::builder()
-    ///     .build_from_hir(hir)?;
-    /// let hay = "\r\nfoo\r\n";
-    /// assert_eq!(Some(Match::must(0, 2..5)), re.find(hay));
+    ///     Hir::literal("bar".as_bytes()),
+    /// ])?;
+    /// assert_eq!(Some(Match::must(1, 0..3)), re.find("barfoo"));
     ///
     /// Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     }))]
     pub fn builder(
         #[builder(field)] syntax: Option<util::syntax::Config>,
-        #[builder(finish_fn)] hir: Hir,
+        #[builder(field = MatchConfig::builder().case_insensitive(false).build())]
+        mut ib: MatchConfig<'a>,
+        #[builder(field)] prebuilt_dfa: Option<dfa::regex::Regex>,
+        #[builder(finish_fn)] hirs: Vec<Hir>,
         /// If the provided `hir` is Unicode-aware, providing a ASCII-aware-only `Hir` as `hir_ascii` can improve performance.
         ///
+        /// Only supported if there is only one pattern.
+        ///
         /// The second `bool` is whether the provided `hir_ascii` is case insensitive:
         /// - If it's `false` but `ib.case_insensitive` is `true`, then `hir_ascii` will be converted to case insensitive. (Used by glob)
         /// - If it's `true` but `ib.case_insensitive` is `false`, `build()` will panic.
@@ -266,9 +284,6 @@ impl<'a> Regex<'a> {
         /// Thompson NFA config. Named `configure` to be compatible with [`regex_automata::meta::Builder`]. Although some fields are not supported and `utf8_empty` is named as `utf8` instead.
         #[builder(default)]
         thompson: thompson::Config,
-        /// [`IbMatcher`] config.
-        #[builder(default = MatchConfig::builder().case_insensitive(false).build())]
-        mut ib: MatchConfig<'a>,
         /// `IbMatcher` pattern parser.
         ///
         /// ### Example
@@ -290,21 +305,38 @@ impl<'a> Regex<'a> {
     ) -> Result<Self, BuildError> {
         _ = syntax;
         #[cfg(test)]
-        dbg!(&hir);
+        dbg!(&hirs);
+        assert!(
+            hir_ascii.is_none() || hirs.len() == 1,
+            "hir_ascii is only supported with one pattern"
+        );
 
-        let imp = match hir.kind() {
+        let literals: Option<Vec<Pattern<str>>> = hirs
+            .iter()
+            .map(|hir| match hir.kind() {
+                HirKind::Literal(literal) => {
+                    let pattern = str::from_utf8(&literal.0).unwrap();
+                    Some(if let Some(ib_parser) = ib_parser.as_mut() {
+                        ib_parser(pattern)
+                    } else {
+                        pattern.into()
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        let imp = match literals {
             // TODO: Look::{Start,End} optimization
-            HirKind::Literal(literal) => {
-                let pattern = str::from_utf8(&literal.0).unwrap();
-                let pattern = if let Some(ib_parser) = ib_parser.as_mut() {
-                    ib_parser(pattern)
-                } else {
-                    pattern.into()
-                };
-                RegexI::Ib(IbMatcherWithConfig::with_config(pattern, ib))
+            Some(mut patterns) if patterns.len() == 1 => RegexI::Ib(
+                IbMatcherWithConfig::with_config(patterns.pop().unwrap(), ib),
+            ),
+            Some(patterns) => {
+                RegexI::IbMany(IbMatchersWithConfig::with_config(patterns, ib))
             }
-            _ => {
-                let dfa = {
+            None => {
+                let dfa = if let Some(dfa) = prebuilt_dfa {
+                    dfa
+                } else {
                     // We can always forcefully disable captures because DFAs do not
                     // support them.
                     let thompson = thompson
@@ -312,26 +344,29 @@ impl<'a> Regex<'a> {
                         .which_captures(thompson::WhichCaptures::None);
 
                     let mut compiler = thompson::Compiler::new();
-                    let hir_buf;
-                    let (mut hir, hir_case_insensitive) = hir_ascii
+                    let hirs_buf: Vec<Hir>;
+                    let (mut hirs, hir_case_insensitive) = hir_ascii
                         .as_ref()
-                        .map(|(hir, case)| (hir, *case))
-                        .unwrap_or((&hir, false));
-                    if let Some(plain) = &ib.plain {
+                        .map(|(hir, case)| (core::slice::from_ref(hir), *case))
+                        .unwrap_or((&hirs, false));
+                    if ib.plain.is_some() {
+                        let case_insensitive = ib.is_plain_case_insensitive();
                         debug_assert!(
-                            !(hir_case_insensitive && !plain.case_insensitive)
+                            !hir_case_insensitive || case_insensitive
                         );
-                        if !hir_case_insensitive && plain.case_insensitive {
-                            hir_buf = hir::case::hir_to_ascii_case_insensitive(
-                                hir.clone(),
-                            );
-                            hir = &hir_buf;
+                        if !hir_case_insensitive && case_insensitive {
+                            hirs_buf = hirs
+                                .iter()
+                                .cloned()
+                                .map(hir::case::hir_to_ascii_case_insensitive)
+                                .collect();
+                            hirs = &hirs_buf;
                         }
                     }
 
                     let forward_nfa = compiler
                         .configure(thompson.clone())
-                        .build_from_hir(hir)?;
+                        .build_many_from_hir(hirs)?;
                     // TODO: prefilter
                     // TODO: minimize?
                     // TODO: quit vs is_ascii?
@@ -342,7 +377,7 @@ impl<'a> Regex<'a> {
 
                     let reverse_nfa = compiler
                         .configure(thompson.reverse(true))
-                        .build_from_hir(hir)?;
+                        .build_many_from_hir(hirs)?;
                     let reverse = dense::Builder::new()
                         .configure(
                             dfa_dense
@@ -362,13 +397,13 @@ impl<'a> Regex<'a> {
                     plain.maybe_ascii = false;
                 }
                 let cp = cp::Regex::builder()
-                    // Unrelated to build_from_hir()
+                    // Unrelated to build_many_from_hir()
                     // .syntax(syntax)
                     .configure(thompson)
                     .ib(ib)
                     .maybe_ib_parser(ib_parser)
                     .backtrack(backtrack)
-                    .build_from_hir(hir)?;
+                    .build_many_from_hir(hirs)?;
                 RegexI::Cp { dfa, cp }
             }
         };
@@ -388,13 +423,90 @@ impl<'a> Regex<'a> {
     /// during a search, and thus might make it faster.
     pub fn create_captures(&self) -> Captures {
         match &self.imp {
-            RegexI::Ib(_) => Captures::matches(GroupInfo::empty()),
+            RegexI::Ib(_) => Captures::matches(
+                GroupInfo::new([[None::<&str>]]).unwrap(),
+            ),
+            RegexI::IbMany(matchers) => Captures::matches(
+                GroupInfo::new(vec![[None::<&str>]; matchers.len()]).unwrap(),
+            ),
             RegexI::Cp { dfa: _, cp } => cp.create_captures(),
         }
     }
+
+    /// Serialize the DFAs of this regex, so that they can be cached and
+    /// deserialized by [`Builder::dfa_from_bytes`] to skip building them.
+    ///
+    /// The DFAs are serialized in little endian.
+    ///
+    /// Returns `None` if the pattern is a literal string, which is matched by
+    /// [`IbMatcher`](crate::matcher::IbMatcher) without any DFA, i.e. there
+    /// is nothing worth caching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ib_matcher::regex::{lita::Regex, Match};
+    ///
+    /// let re = Regex::new("foo[0-9]+")?;
+    /// let bytes = re.to_dfa_bytes().unwrap();
+    ///
+    /// let re = Regex::builder().dfa_from_bytes(&bytes)?.build("foo[0-9]+")?;
+    /// assert_eq!(Some(Match::must(0, 0..8)), re.find("foo12345"));
+    ///
+    /// assert!(Regex::new("foo")?.to_dfa_bytes().is_none());
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_dfa_bytes(&self) -> Option<Vec<u8>> {
+        match &self.imp {
+            RegexI::Ib(_) | RegexI::IbMany(_) => None,
+            RegexI::Cp { dfa, cp: _ } => {
+                let (forward, forward_pad) =
+                    dfa.forward().to_bytes_little_endian();
+                let (reverse, reverse_pad) =
+                    dfa.reverse().to_bytes_little_endian();
+                let forward = &forward[forward_pad..];
+                let reverse = &reverse[reverse_pad..];
+
+                let mut bytes =
+                    Vec::with_capacity(8 + forward.len() + reverse.len());
+                bytes.extend_from_slice(&(forward.len() as u64).to_le_bytes());
+                bytes.extend_from_slice(forward);
+                bytes.extend_from_slice(reverse);
+                Some(bytes)
+            }
+        }
+    }
+}
+
+/// [`dense::DFA::from_bytes`], but `bytes` does not need to be aligned.
+fn dense_dfa_from_bytes(
+    bytes: &[u8],
+) -> Result<dense::DFA<Vec<u32>>, DeserializeError> {
+    let mut buf = vec![0u32; bytes.len().div_ceil(4)];
+    let buf = unsafe {
+        core::slice::from_raw_parts_mut(
+            buf.as_mut_ptr() as *mut u8,
+            bytes.len(),
+        )
+    };
+    buf.copy_from_slice(bytes);
+    let (dfa, _) = dense::DFA::from_bytes(buf)?;
+    Ok(dfa.to_owned())
 }
 
 impl<'a, S: builder::State> Builder<'a, '_, S> {
+    /// [`IbMatcher`] config.
+    pub fn ib(mut self, ib: MatchConfig<'a>) -> Self {
+        self.ib = ib;
+        self
+    }
+
+    /// See [case insensitivity](Regex#case-insensitivity).
+    fn class_case_insensitive(&self) -> bool {
+        self.ib.is_plain_case_insensitive()
+    }
+
     /// Configure the syntax options when parsing a pattern string while
     /// building a `Regex`.
     ///
@@ -421,6 +533,35 @@ impl<'a, S: builder::State> Builder<'a, '_, S> {
         self
     }
 
+    /// Use the DFAs serialized by [`Regex::to_dfa_bytes`] instead of
+    /// building them, which is the most expensive part of building a `Regex`.
+    ///
+    /// The DFAs must be serialized from a `Regex` built with the same pattern
+    /// and config (except [`Builder::ib`] and [`Builder::ib_parser`]),
+    /// otherwise the search results on ASCII haystacks are unspecified.
+    /// The pattern is still needed for matching non-ASCII haystacks.
+    ///
+    /// The DFAs are ignored if the pattern is a literal string.
+    ///
+    /// An error is returned if `bytes` is not valid, e.g. it is corrupted or
+    /// serialized by an incompatible version.
+    ///
+    /// See [`Regex::to_dfa_bytes`] for an example.
+    pub fn dfa_from_bytes(
+        mut self,
+        bytes: &[u8],
+    ) -> Result<Self, DeserializeError> {
+        let (len, bytes) = bytes.split_at(bytes.len().min(8));
+        let len = u64::from_le_bytes(len.try_into().unwrap_or_default());
+        let (forward, reverse) =
+            bytes.split_at(bytes.len().min(len as usize));
+        let forward = dense_dfa_from_bytes(forward)?;
+        let reverse = dense_dfa_from_bytes(reverse)?;
+        self.prebuilt_dfa =
+            Some(dfa::regex::Regex::builder().build_from_dfas(forward, reverse));
+        Ok(self)
+    }
+
     /// Builds a `Regex` from a single pattern string.
     ///
     /// If there was a problem parsing the pattern or a problem turning it into
@@ -445,21 +586,24 @@ impl<'a, S: builder::State> Builder<'a, '_, S> {
     where
         S::HirAscii: builder::IsUnset,
     {
-        let syntax =
-            self.syntax.unwrap_or_else(util::syntax::config_auto);
+        let class_case_insensitive = self.class_case_insensitive();
+        let syntax = self.syntax.unwrap_or_else(util::syntax::config_auto);
 
         // Parse
         let pattern = pattern.as_ref();
         let parse_with = |syntax| {
-            regex_automata::util::syntax::parse_with(pattern, &syntax).map_err(
-                |_| {
-                    // Shit
-                    thompson::Compiler::new()
-                        .syntax(syntax)
-                        .build(pattern)
-                        .unwrap_err()
-                },
+            util::syntax::parse_with_class_case(
+                pattern,
+                &syntax,
+                class_case_insensitive,
             )
+            .map_err(|_| {
+                // Shit
+                thompson::Compiler::new()
+                    .syntax(syntax)
+                    .build(pattern)
+                    .unwrap_err()
+            })
         };
         let hir_ascii = parse_with(
             syntax
@@ -471,6 +615,113 @@ impl<'a, S: builder::State> Builder<'a, '_, S> {
         let hir = parse_with(syntax)?;
         self.hir_ascii((hir_ascii, false)).build_from_hir(hir)
     }
+
+    /// Builds a `Regex` from many pattern strings.
+    ///
+    /// If there was a problem parsing any of the patterns or a problem turning
+    /// them into a regex matcher, then an error is returned.
+    ///
+    /// The returned [`Match`] carries the [`PatternID`] of the matched
+    /// pattern. If multiple patterns match at the same leftmost position,
+    /// the first one is preferred.
+    ///
+    /// If all the patterns are literal strings, each of them is matched by
+    /// an [`IbMatcher`](crate::matcher::IbMatcher), i.e. the haystack is
+    /// searched once per pattern. Otherwise, the patterns are matched by
+    /// the same DFA and [`cp::Regex`] as a single pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ib_matcher::{
+    ///     matcher::{MatchConfig, PinyinMatchConfig},
+    ///     regex::{lita::Regex, Match},
+    /// };
+    ///
+    /// let re = Regex::builder()
+    ///     .ib(MatchConfig::builder().pinyin(PinyinMatchConfig::default()).build())
+    ///     .build_many(&["sousuo", "pinyin"])?;
+    /// assert_eq!(Some(Match::must(1, 0..6)), re.find("拼音搜索"));
+    /// assert_eq!(Some(Match::must(0, 3..9)), re.find("音搜索"));
+    /// assert_eq!(None, re.find("拼"));
+    ///
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn build_many<P: AsRef<str>>(
+        self,
+        patterns: &[P],
+    ) -> Result<Regex<'a>, BuildError>
+    where
+        S::HirAscii: builder::IsUnset,
+    {
+        let class_case_insensitive = self.class_case_insensitive();
+        let syntax = self.syntax.unwrap_or_else(util::syntax::config_auto);
+
+        // Parse
+        let hirs = patterns
+            .iter()
+            .map(|pattern| {
+                let pattern = pattern.as_ref();
+                util::syntax::parse_with_class_case(
+                    pattern,
+                    &syntax,
+                    class_case_insensitive,
+                )
+                .map_err(|_| {
+                    // Shit
+                    thompson::Compiler::new()
+                        .syntax(syntax)
+                        .build(pattern)
+                        .unwrap_err()
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        self.build_many_from_hir(hirs)
+    }
+
+    /// Builds a `Regex` directly from an `Hir` expression.
+    ///
+    /// This is useful if you needed to parse a pattern string into an `Hir`
+    /// for other reasons (such as analysis or transformations). This routine
+    /// permits building a `Regex` directly from the `Hir` expression instead
+    /// of first converting the `Hir` back to a pattern string.
+    ///
+    /// When using this method, any options set via [`Builder::syntax`] are
+    /// ignored. Namely, the syntax options only apply when parsing a pattern
+    /// string, which isn't relevant here.
+    ///
+    /// If there was a problem building the underlying regex matcher for the
+    /// given `Hir`, then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// This example shows how one can hand-construct an `Hir` expression and
+    /// build a regex from it without doing any parsing at all.
+    ///
+    /// ```
+    /// use ib_matcher::{
+    ///     regex::{lita::Regex, Match},
+    ///     syntax::regex::hir::{Hir, Look},
+    /// };
+    ///
+    /// // (?Rm)^foo$
+    /// let hir = Hir::concat(vec![
+    ///     Hir::look(Look::StartCRLF),
+    ///     Hir::literal("foo".as_bytes()),
+    ///     Hir::look(Look::EndCRLF),
+    /// ]);
+    /// let re = Regex::builder()
+    ///     .build_from_hir(hir)?;
+    /// let hay = "\r\nfoo\r\n";
+    /// assert_eq!(Some(Match::must(0, 2..5)), re.find(hay));
+    ///
+    /// Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[allow(clippy::result_large_err)]
+    pub fn build_from_hir(self, hir: Hir) -> Result<Regex<'a>, BuildError> {
+        self.build_many_from_hir(vec![hir])
+    }
 }
 
 /// High level convenience routines for using a regex to search a haystack.
@@ -557,6 +808,9 @@ impl<'a> Regex<'a> {
             RegexI::Ib(matcher) => {
                 matcher.is_match(matcher::input::Input::from_regex(&input))
             }
+            RegexI::IbMany(matchers) => matchers.iter().any(|matcher| {
+                matcher.is_match(matcher::input::Input::from_regex(&input))
+            }),
             RegexI::Cp { dfa, cp } => {
                 if input.haystack().is_ascii() {
                     dfa.is_match(input)
@@ -587,6 +841,7 @@ impl<'a> Regex<'a> {
             RegexI::Ib(matcher) => matcher
                 .find(matcher::input::Input::from_regex(&input))
                 .map(|m| m.offset(input.start()).into()),
+            RegexI::IbMany(matchers) => find_many(matchers, &input),
             RegexI::Cp { dfa, cp } => {
                 if input.haystack().is_ascii() {
                     dfa.find(input)
@@ -640,6 +895,19 @@ impl<'a> Regex<'a> {
                 }
                 Ok(())
             }
+            RegexI::IbMany(matchers) => {
+                let slots = caps.slots_mut();
+                slots.fill(None);
+                if let Some(m) = find_many(matchers, &input) {
+                    let i = m.pattern().as_usize() * 2;
+                    slots[i] = NonMaxUsize::new(m.start());
+                    slots[i + 1] = NonMaxUsize::new(m.end());
+                    caps.set_pattern(Some(m.pattern()));
+                } else {
+                    caps.set_pattern(None);
+                }
+                Ok(())
+            }
             RegexI::Cp { dfa, cp } => {
                 if input.haystack().is_ascii() && !dfa.is_match(input.clone())
                 {
@@ -652,6 +920,23 @@ impl<'a> Regex<'a> {
     }
 }
 
+/// Leftmost match of all the matchers, preferring the first one on ties.
+fn find_many(matchers: &[matcher::IbMatcher], input: &Input) -> Option<Match> {
+    let mut found: Option<Match> = None;
+    for (i, matcher) in matchers.iter().enumerate() {
+        let Some(m) = matcher.find(matcher::input::Input::from_regex(input))
+        else {
+            continue;
+        };
+        let m = m.offset(input.start());
+        match found {
+            Some(found) if found.start() <= m.start() => (),
+            _ => found = Some(Match::new(PatternID::must(i), m.range())),
+        }
+    }
+    found
+}
+
 #[cfg(test)]
 mod tests {
     use regex_automata::Match;
@@ -803,6 +1088,92 @@ mod tests {
         assert_eq!(re.find("拼a音b搜c索d"), Some(Match::must(0, 0..15)));
     }
 
+    #[test]
+    fn dfa_bytes() {
+        let config = MatchConfig::builder()
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+        let re = Regex::builder()
+            .ib(config.shallow_clone())
+            .build("p.*y.*s.*s")
+            .unwrap();
+        let bytes = re.to_dfa_bytes().unwrap();
+
+        let re = Regex::builder()
+            .ib(config.shallow_clone())
+            .dfa_from_bytes(&bytes)
+            .unwrap()
+            .build("p.*y.*s.*s")
+            .unwrap();
+        assert_eq!(re.find("pinyin sousuo"), Some(Match::must(0, 0..11)));
+        assert_eq!(re.find("pinyin"), None);
+        assert_eq!(re.find("拼a音b搜c索d"), Some(Match::must(0, 0..15)));
+
+        // Unaligned
+        let mut unaligned = vec![0];
+        unaligned.extend_from_slice(&bytes);
+        assert!(Regex::builder().dfa_from_bytes(&unaligned[1..]).is_ok());
+
+        // Invalid
+        assert!(Regex::builder().dfa_from_bytes(&[]).is_err());
+        assert!(Regex::builder().dfa_from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        assert!(Regex::builder().dfa_from_bytes(&bytes[8..]).is_err());
+
+        // Literal
+        let re = Regex::builder()
+            .ib(config.shallow_clone())
+            .build("pyss")
+            .unwrap();
+        assert!(re.to_dfa_bytes().is_none());
+    }
+
+    #[test]
+    fn build_many() {
+        let config = MatchConfig::builder()
+            .pinyin(PinyinMatchConfig::notations(
+                PinyinNotation::Ascii | PinyinNotation::AsciiFirstLetter,
+            ))
+            .build();
+
+        // Literal
+        let re = Regex::builder()
+            .ib(config.shallow_clone())
+            .build_many(&["ss", "pinyin", "py"])
+            .unwrap();
+        assert_eq!(re.find("拼音搜索"), Some(Match::must(1, 0..6)));
+        assert_eq!(re.find("音搜索"), Some(Match::must(0, 3..9)));
+        assert_eq!(re.find("pinyin"), Some(Match::must(1, 0..6)));
+        assert_eq!(re.find("搜"), None);
+        assert!(re.is_match("搜索"));
+        assert!(!re.is_match("搜"));
+        assert!(re.to_dfa_bytes().is_none());
+
+        let mut caps = re.create_captures();
+        re.captures("音搜索", &mut caps).unwrap();
+        assert_eq!(caps.pattern(), Some(PatternID::must(0)));
+        assert_eq!(caps.get_match(), Some(Match::must(0, 3..9)));
+        re.captures("拼音搜索", &mut caps).unwrap();
+        assert_eq!(caps.get_match(), Some(Match::must(1, 0..6)));
+        re.captures("搜", &mut caps).unwrap();
+        assert!(!caps.is_match());
+
+        // Non-literal
+        let re = Regex::builder()
+            .ib(config.shallow_clone())
+            .build_many(&["s.*s", "pinyin"])
+            .unwrap();
+        assert_eq!(re.find("拼音搜索"), Some(Match::must(1, 0..6)));
+        assert_eq!(re.find("音搜a索"), Some(Match::must(0, 3..10)));
+        assert_eq!(re.find("pinyin sousuo"), Some(Match::must(1, 0..6)));
+        assert_eq!(re.find("sousuo"), Some(Match::must(0, 0..4)));
+
+        // Empty
+        let re = Regex::builder().build_many::<&str>(&[]).unwrap();
+        assert_eq!(re.find("pyss"), None);
+    }
+
     #[test]
     fn mix_lang() {
         let pinyin = PinyinMatchConfig::notations(
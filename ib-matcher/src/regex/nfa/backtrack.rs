@@ -1475,6 +1475,10 @@ impl BoundedBacktracker {
                 Frame::RestoreCapture { slot, offset } => {
                     slots[slot] = offset;
                 }
+                #[cfg(feature = "regex-callback")]
+                Frame::RestoreBackref { slot, offset } => {
+                    cache.backref_slots[slot as usize] = offset;
+                }
             }
         }
         None
@@ -1646,6 +1650,33 @@ impl BoundedBacktracker {
                         return None;
                     }
                 }
+                #[cfg(feature = "regex-callback")]
+                super::State::BackrefSlot { slot, next } => {
+                    if cache.backref_slots.len() <= slot {
+                        cache.backref_slots.resize(slot + 1, None);
+                    }
+                    cache.stack.push(Frame::RestoreBackref {
+                        slot: slot as u32,
+                        offset: cache.backref_slots[slot],
+                    });
+                    cache.backref_slots[slot] = NonMaxUsize::new(at);
+                    // Memoized failures depend on the slots and are no longer valid
+                    cache.visited.clear();
+                    sid = next;
+                }
+                #[cfg(feature = "regex-callback")]
+                super::State::Backref { slot, next } => {
+                    let span = cache.backref_slots.get(slot..slot + 2);
+                    let Some(&[Some(start), Some(end)]) = span else {
+                        return None;
+                    };
+                    let group = &input.haystack()[start.get()..end.get()];
+                    if !input.haystack()[at..input.end()].starts_with(group) {
+                        return None;
+                    }
+                    sid = next;
+                    at += group.len();
+                }
             }
         }
     }
@@ -1757,6 +1788,10 @@ pub struct Cache {
     /// what "bounds" the backtracking and prevents it from having worst case
     /// exponential time.
     visited: Visited,
+    /// The spans of the capture groups of backreferences in the current search,
+    /// see [`State::BackrefSlot`](super::State::BackrefSlot).
+    #[cfg(feature = "regex-callback")]
+    backref_slots: Vec<Option<NonMaxUsize>>,
 }
 
 impl Cache {
@@ -1770,7 +1805,12 @@ impl Cache {
     /// `BoundedBacktracker`, then you must call [`Cache::reset`] with the
     /// desired `BoundedBacktracker`.
     pub fn new(re: &BoundedBacktracker) -> Cache {
-        Cache { stack: vec![], visited: Visited::new(re) }
+        Cache {
+            stack: vec![],
+            visited: Visited::new(re),
+            #[cfg(feature = "regex-callback")]
+            backref_slots: vec![],
+        }
     }
 
     /// Reset this cache such that it can be used for searching with different
@@ -1823,8 +1863,13 @@ impl Cache {
     /// This does **not** include the stack size used up by this cache. To
     /// compute that, use `std::mem::size_of::<Cache>()`.
     pub fn memory_usage(&self) -> usize {
-        self.stack.len() * core::mem::size_of::<Frame>()
-            + self.visited.memory_usage()
+        let len = self.stack.len() * core::mem::size_of::<Frame>()
+            + self.visited.memory_usage();
+        #[cfg(feature = "regex-callback")]
+        let len = len
+            + self.backref_slots.len()
+                * core::mem::size_of::<Option<NonMaxUsize>>();
+        len
     }
 
     /// Clears this cache. This should be called at the start of every search
@@ -1844,6 +1889,8 @@ impl Cache {
     ) -> Result<(), MatchError> {
         self.stack.clear();
         self.visited.setup_search(re, input)?;
+        #[cfg(feature = "regex-callback")]
+        self.backref_slots.clear();
         Ok(())
     }
 }
@@ -1866,6 +1913,9 @@ enum Frame {
     /// all), then this "restore capture" frame will cause the offset to get
     /// reset.
     RestoreCapture { slot: SmallIndex, offset: Option<NonMaxUsize> },
+    /// Reset the given backreference `slot` to the given `offset`, like `RestoreCapture`.
+    #[cfg(feature = "regex-callback")]
+    RestoreBackref { slot: u32, offset: Option<NonMaxUsize> },
 }
 
 /// A bitset that keeps track of whether a particular (StateID, offset) has
@@ -1925,6 +1975,14 @@ impl Visited {
         true
     }
 
+    /// Remove all (StateID, offset) pairs from this set, keeping its size.
+    #[cfg(feature = "regex-callback")]
+    fn clear(&mut self) {
+        for block in self.bitset.iter_mut() {
+            *block = 0;
+        }
+    }
+
     /// Reset this visited set to work with the given bounded backtracker.
     fn reset(&mut self, _: &BoundedBacktracker) {
         self.bitset.truncate(0);
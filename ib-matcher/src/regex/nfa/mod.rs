@@ -1,5 +1,5 @@
-use core::ops::Deref;
-use std::{fmt::Debug, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{fmt::Debug, ops::Deref};
 
 use itertools::Itertools;
 #[cfg(feature = "syntax-regex")]
@@ -407,7 +407,7 @@ pub type Callback = Arc<dyn Fn(&Input, usize, &mut dyn FnMut(usize))>;
 pub enum State {
     Nfa(thompson::State),
     IbMatcher {
-        matcher: IbMatcher<'static>,
+        matcher: Box<IbMatcher<'static>>,
         next: StateID,
     },
     #[cfg(feature = "regex-callback")]
@@ -415,6 +415,18 @@ pub enum State {
         callback: Callback,
         next: StateID,
     },
+    /// Record the current position in the backreference slot `slot` of the search cache.
+    #[cfg(feature = "regex-callback")]
+    BackrefSlot {
+        slot: usize,
+        next: StateID,
+    },
+    /// Match the same bytes as the span between the backreference slots `slot` and `slot + 1`.
+    #[cfg(feature = "regex-callback")]
+    Backref {
+        slot: usize,
+        next: StateID,
+    },
 }
 
 impl From<thompson::State> for State {
@@ -424,7 +436,7 @@ impl From<thompson::State> for State {
 }
 
 impl Debug for State {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             State::Nfa(state) => write!(f, "Nfa({:?})", state),
             State::IbMatcher { matcher, next } => {
@@ -434,6 +446,14 @@ impl Debug for State {
             State::Callback { next, .. } => {
                 write!(f, "Callback({:?})", next)
             }
+            #[cfg(feature = "regex-callback")]
+            State::BackrefSlot { slot, next } => {
+                write!(f, "BackrefSlot({:?}, {:?})", slot, next)
+            }
+            #[cfg(feature = "regex-callback")]
+            State::Backref { slot, next } => {
+                write!(f, "Backref({:?}, {:?})", slot, next)
+            }
         }
     }
 }
@@ -443,22 +463,24 @@ impl NFA {
         &mut Arc::get_mut(&mut self.0).unwrap().states
     }
 
+    /// Returns the ID of the patched state, if any.
     pub fn patch_first_byte(
         &mut self,
         byte: u8,
         state: impl FnOnce(StateID) -> State,
-    ) {
-        for s in self.states_mut() {
+    ) -> Option<StateID> {
+        for (i, s) in self.states_mut().iter_mut().enumerate() {
             match *s {
                 State::Nfa(thompson::State::ByteRange {
                     trans: thompson::Transition { start, end, next },
                 }) if start == byte && end == byte => {
                     *s = state(next);
-                    break;
+                    return StateID::new(i).ok();
                 }
                 _ => (),
             }
         }
+        None
     }
 
     #[cfg(test)]
@@ -467,7 +489,10 @@ impl NFA {
         byte: u8,
         matcher: IbMatcher<'static>,
     ) {
-        self.patch_first_byte(byte, |next| State::IbMatcher { matcher, next })
+        self.patch_first_byte(byte, |next| State::IbMatcher {
+            matcher: Box::new(matcher),
+            next,
+        });
     }
 
     pub(crate) fn count_bytes(&self, lt: u8) -> usize {
@@ -499,7 +524,10 @@ impl NFA {
                 State::Nfa(thompson::State::ByteRange {
                     trans: thompson::Transition { start, end, next },
                 }) if start == end && start < lt => {
-                    *s = State::IbMatcher { matcher: matcher(start), next };
+                    *s = State::IbMatcher {
+                        matcher: Box::new(matcher(start)),
+                        next,
+                    };
                 }
                 _ => (),
             }
@@ -575,7 +603,7 @@ mod tests {
         let hir = parser.parse("δ").unwrap();
 
         let (mut hirs, literals) =
-            hir::fold::fold_literal_utf8(std::iter::once(hir));
+            hir::fold::fold_literal_utf8(core::iter::once(hir));
         let hir = hirs.pop().unwrap();
 
         let mut nfa: NFA =
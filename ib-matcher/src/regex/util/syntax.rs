@@ -1,3 +1,10 @@
+use alloc::{boxed::Box, vec};
+
+use regex_syntax::{
+    ast::{self, Ast},
+    hir::{self, Hir},
+};
+
 pub use regex_automata::util::syntax::*;
 
 /// - When `regex-unicode` feature is disabled, `unicode` will default to `false` to
@@ -9,3 +16,111 @@ pub fn config_auto() -> Config {
     let c = Config::new().unicode(false);
     c
 }
+
+/// [`parse_with()`], but if `class_case_insensitive`, character classes (`[...]`) are
+/// parsed as if they were `(?i:[...])`.
+///
+/// This lets classes follow [`MatchConfigBuilder::case_insensitive`](crate::matcher::MatchConfigBuilder::case_insensitive)
+/// while literals are left case sensitive to be matched by `IbMatcher`.
+/// Classes whose case insensitivity is set explicitly, e.g. `(?-i:[a-z])`, are not changed.
+#[allow(clippy::result_large_err)]
+pub(crate) fn parse_with_class_case(
+    pattern: &str,
+    config: &Config,
+    class_case_insensitive: bool,
+) -> Result<Hir, regex_syntax::Error> {
+    if !class_case_insensitive {
+        return parse_with(pattern, config);
+    }
+    let mut ast = ast::parse::ParserBuilder::new()
+        .ignore_whitespace(config.get_ignore_whitespace())
+        .nest_limit(config.get_nest_limit())
+        .octal(config.get_octal())
+        .build()
+        .parse(pattern)?;
+    class_to_case_insensitive(&mut ast, &mut false);
+    let hir = hir::translate::TranslatorBuilder::new()
+        .unicode(config.get_unicode())
+        .case_insensitive(config.get_case_insensitive())
+        .multi_line(config.get_multi_line())
+        .crlf(config.get_crlf())
+        .dot_matches_new_line(config.get_dot_matches_new_line())
+        .line_terminator(config.get_line_terminator())
+        .swap_greed(config.get_swap_greed())
+        .utf8(config.get_utf8())
+        .build()
+        .translate(pattern, &ast)?;
+    Ok(hir)
+}
+
+/// - `explicit`: Whether the `i` flag is set explicitly in the current group.
+fn class_to_case_insensitive(ast: &mut Ast, explicit: &mut bool) {
+    let sets_i = |flags: &ast::Flags| {
+        flags.flag_state(ast::Flag::CaseInsensitive).is_some()
+    };
+    match ast {
+        Ast::ClassBracketed(class) if !*explicit => {
+            let span = class.span;
+            let class = core::mem::replace(ast, Ast::empty(span));
+            *ast = Ast::group(ast::Group {
+                span,
+                kind: ast::GroupKind::NonCapturing(ast::Flags {
+                    span,
+                    items: vec![ast::FlagsItem {
+                        span,
+                        kind: ast::FlagsItemKind::Flag(
+                            ast::Flag::CaseInsensitive,
+                        ),
+                    }],
+                }),
+                ast: Box::new(class),
+            });
+        }
+        Ast::Flags(flags) => *explicit |= sets_i(&flags.flags),
+        Ast::Group(group) => {
+            let mut explicit = *explicit || group.flags().is_some_and(sets_i);
+            class_to_case_insensitive(&mut group.ast, &mut explicit);
+        }
+        Ast::Repetition(repetition) => {
+            class_to_case_insensitive(&mut repetition.ast, explicit)
+        }
+        // Flags set in an alternative also apply to the following ones
+        Ast::Alternation(alternation) => {
+            for ast in &mut alternation.asts {
+                class_to_case_insensitive(ast, explicit);
+            }
+        }
+        Ast::Concat(concat) => {
+            for ast in &mut concat.asts {
+                class_to_case_insensitive(ast, explicit);
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_case() {
+        let config = Config::new();
+        let parse =
+            |pattern| parse_with_class_case(pattern, &config, true).unwrap();
+        let parse_i = |pattern| parse_with(pattern, &config).unwrap();
+
+        assert_eq!(parse("[a-c]"), parse_i("(?i:[a-c])"));
+        assert_eq!(parse("[^a]"), parse_i("(?i:[^a])"));
+        assert_eq!(parse("ab[c]+"), parse_i("ab(?i:[c])+"));
+        assert_eq!(parse("(a|[b])"), parse_i("(a|(?i:[b]))"));
+        // Explicit flags
+        assert_eq!(parse("(?-i:[a])"), parse_i("[a]"));
+        assert_eq!(parse("(?-i)[a]|[b]"), parse_i("[a]|[b]"));
+        assert_eq!(parse("((?-i)[a])[b]"), parse_i("([a])(?i:[b])"));
+        assert_eq!(
+            parse_with_class_case("[a]", &config, false).unwrap(),
+            parse_i("[a]")
+        );
+    }
+}
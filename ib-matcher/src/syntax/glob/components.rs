@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::PathSeparator;
+
+/// The byte ranges of the components of `path`, e.g. `usr` and `bin` in `/usr/bin/`.
+///
+/// Separators are not part of any component, and empty components (e.g. in `a//b`) are skipped.
+///
+/// If `separator` [is Windows or any](PathSeparator::is_windows_or_any), the path prefix is a single leading component, like [`std::path::Component::Prefix`]:
+/// - Drive letters: `C:` in `C:\Windows` and `C:Windows`.
+/// - UNC paths: `\\server\share` in `\\server\share\dir`.
+/// - Verbatim and device paths: `\\?\C:`, `\\?\UNC\server\share` and `\\.\COM1`.
+///
+/// ## Examples
+/// ```
+/// use ib_matcher::syntax::glob::{path_components, PathSeparator};
+///
+/// let components = |p| path_components(p, PathSeparator::Windows).collect::<Vec<_>>();
+/// assert_eq!(components(r"C:\Windows\System32"), [0..2, 3..10, 11..19]);
+/// assert_eq!(components(r"\\server\share\dir"), [0..14, 15..18]);
+/// ```
+pub fn path_components<'p, P: ?Sized + AsRef<[u8]>>(
+    path: &'p P,
+    separator: PathSeparator,
+) -> impl Iterator<Item = Range<usize>> + 'p {
+    let path = path.as_ref();
+    let separator = separator.desugar();
+    let is_sep = move |b: u8| separator.is_separator(b);
+
+    let prefix = if separator.is_windows_or_any() {
+        windows_prefix_len(path, is_sep)
+    } else {
+        0
+    };
+
+    let mut i = prefix;
+    let rest = core::iter::from_fn(move || {
+        while i < path.len() && is_sep(path[i]) {
+            i += 1;
+        }
+        if i == path.len() {
+            return None;
+        }
+        let start = i;
+        while i < path.len() && !is_sep(path[i]) {
+            i += 1;
+        }
+        Some(start..i)
+    });
+    (prefix != 0).then_some(0..prefix).into_iter().chain(rest)
+}
+
+/// The byte ranges of the components of `path` that overlap `span`, in order.
+///
+/// This can be used to highlight the matched path components, e.g. with the `span` of a [`Match`](crate::regex::Match) of a glob pattern. See [`path_components()`] for how the path is split.
+///
+/// An empty `span` overlaps no components.
+///
+/// ## Examples
+/// ```
+/// use ib_matcher::{
+///     regex::lita::Regex,
+///     syntax::glob::{matched_path_components, parse_glob_path, PathSeparator},
+/// };
+///
+/// let path = r"C:\Users\Alice\Documents\report.docx";
+/// let re = Regex::builder()
+///     .build_from_hir(
+///         parse_glob_path()
+///             .separator(PathSeparator::Windows)
+///             .call(r"ice\Doc"),
+///     )
+///     .unwrap();
+/// let m = re.find(path).unwrap();
+/// let components = matched_path_components(path, m.range(), PathSeparator::Windows);
+/// assert_eq!(components, [9..14, 15..24]);
+/// assert_eq!(&path[components[0].clone()], "Alice");
+/// assert_eq!(&path[components[1].clone()], "Documents");
+/// ```
+pub fn matched_path_components<P: ?Sized + AsRef<[u8]>>(
+    path: &P,
+    span: Range<usize>,
+    separator: PathSeparator,
+) -> Vec<Range<usize>> {
+    if span.is_empty() {
+        return Vec::new();
+    }
+    path_components(path, separator)
+        .skip_while(|c| c.end <= span.start)
+        .take_while(|c| c.start < span.end)
+        .collect()
+}
+
+/// The length of the Windows path prefix of `path`, or 0 if there is none.
+fn windows_prefix_len(path: &[u8], is_sep: impl Fn(u8) -> bool) -> usize {
+    let component_end = |start: usize| {
+        path[start..]
+            .iter()
+            .position(|&b| is_sep(b))
+            .map_or(path.len(), |i| start + i)
+    };
+    // `server\share`, or only `server` if there is no share
+    let server_share = |start: usize| {
+        let server_end = component_end(start);
+        if server_end + 1 < path.len() {
+            component_end(server_end + 1)
+        } else {
+            server_end
+        }
+    };
+
+    match path {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => 2,
+        // `\\?\...` and `\\.\...`
+        [a, b, c @ (b'?' | b'.'), d, ..] if is_sep(*a) && is_sep(*b) && is_sep(*d) => {
+            let end = component_end(4);
+            if *c == b'?' && path[4..end].eq_ignore_ascii_case(b"UNC") && end < path.len() {
+                server_share(end + 1)
+            } else {
+                end
+            }
+        }
+        [a, b, c, ..] if is_sep(*a) && is_sep(*b) && !is_sep(*c) => server_share(2),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components(path: &str, separator: PathSeparator) -> Vec<&str> {
+        path_components(path, separator).map(|c| &path[c]).collect()
+    }
+
+    fn matched(path: &str, span: Range<usize>, separator: PathSeparator) -> Vec<&str> {
+        matched_path_components(path, span, separator)
+            .into_iter()
+            .map(|c| &path[c])
+            .collect()
+    }
+
+    #[test]
+    fn unix() {
+        let sep = PathSeparator::Unix;
+        assert_eq!(components("/usr/bin/ls", sep), ["usr", "bin", "ls"]);
+        assert_eq!(components("usr//bin/", sep), ["usr", "bin"]);
+        assert_eq!(components("/", sep), [] as [&str; 0]);
+        assert_eq!(components("", sep), [] as [&str; 0]);
+        // No prefixes
+        assert_eq!(components("C:/a", sep), ["C:", "a"]);
+        assert_eq!(components("//server/share", sep), ["server", "share"]);
+        assert_eq!(components(r"a\b", sep), [r"a\b"]);
+
+        let path = "/usr/local/bin";
+        assert_eq!(matched(path, 0..1, sep), [] as [&str; 0]);
+        assert_eq!(matched(path, 0..2, sep), ["usr"]);
+        assert_eq!(matched(path, 3..6, sep), ["usr", "local"]);
+        assert_eq!(matched(path, 4..10, sep), ["local"]);
+        assert_eq!(matched(path, 9..12, sep), ["local", "bin"]);
+        assert_eq!(matched(path, 0..path.len(), sep), ["usr", "local", "bin"]);
+        assert_eq!(matched(path, 5..5, sep), [] as [&str; 0]);
+    }
+
+    #[test]
+    fn windows() {
+        let sep = PathSeparator::Windows;
+        assert_eq!(
+            components(r"C:\Windows\System32", sep),
+            ["C:", "Windows", "System32"]
+        );
+        assert_eq!(components(r"C:Windows", sep), ["C:", "Windows"]);
+        assert_eq!(components(r"C:\", sep), ["C:"]);
+        assert_eq!(components(r"\Windows\", sep), ["Windows"]);
+        assert_eq!(components(r"a/b\c", sep), ["a/b", "c"]);
+
+        // UNC
+        assert_eq!(
+            components(r"\\server\share\dir\a.txt", sep),
+            [r"\\server\share", "dir", "a.txt"]
+        );
+        assert_eq!(components(r"\\server", sep), [r"\\server"]);
+        assert_eq!(components(r"\\server\", sep), [r"\\server"]);
+
+        // Verbatim and device
+        assert_eq!(components(r"\\?\C:\dir", sep), [r"\\?\C:", "dir"]);
+        assert_eq!(
+            components(r"\\?\UNC\server\share\dir", sep),
+            [r"\\?\UNC\server\share", "dir"]
+        );
+        assert_eq!(components(r"\\?\unc\server", sep), [r"\\?\unc\server"]);
+        assert_eq!(components(r"\\?\UNC", sep), [r"\\?\UNC"]);
+        assert_eq!(components(r"\\.\COM1", sep), [r"\\.\COM1"]);
+        assert_eq!(components(r"\\.\pipe\name", sep), [r"\\.\pipe", "name"]);
+
+        let path = r"C:\Users\Alice";
+        assert_eq!(matched(path, 0..1, sep), ["C:"]);
+        assert_eq!(matched(path, 2..3, sep), [] as [&str; 0]);
+        assert_eq!(matched(path, 1..4, sep), ["C:", "Users"]);
+        assert_eq!(matched(path, 8..14, sep), ["Alice"]);
+
+        let path = r"\\server\share\dir";
+        assert_eq!(matched(path, 3..5, sep), [r"\\server\share"]);
+        assert_eq!(matched(path, 12..16, sep), [r"\\server\share", "dir"]);
+    }
+
+    #[test]
+    fn any() {
+        let sep = PathSeparator::Any;
+        assert_eq!(
+            components(r"C:/Users\Alice/a.txt", sep),
+            ["C:", "Users", "Alice", "a.txt"]
+        );
+        assert_eq!(
+            components(r"//server\share/dir", sep),
+            [r"//server\share", "dir"]
+        );
+        assert_eq!(components("/usr/bin", sep), ["usr", "bin"]);
+        assert_eq!(matched(r"a/b\c", 2..5, sep), ["b", "c"]);
+    }
+}
@@ -0,0 +1,272 @@
+use alloc::{borrow::ToOwned, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::ops::Range;
+
+use logos::{Lexer, Logos};
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, Hir, Repetition};
+
+use super::{
+    lex_class, literal, util::SurroundingWildcardHandler, GlobParseError, GlobParseErrorKind,
+    GlobPathToken, GlobPathTranslator,
+};
+
+/// See [`parse_glob_path`](super::parse_glob_path), used if [`extglob`](super::ParseGlobPathBuilder::extglob) is enabled.
+#[derive(Logos, Clone, Copy, Debug, PartialEq)]
+pub enum ExtGlobPathToken {
+    /// Equivalent to `[^/]` on Unix and `[^\\]` on Windows.
+    #[token("?")]
+    Any,
+
+    /// Equivalent to `[^/]*` on Unix and `[^\\]*` on Windows.
+    #[token("*")]
+    Star,
+
+    /// `[...]`, see [`character classes`](super::super::glob#character-classes).
+    #[token("[", lex_class)]
+    Class,
+
+    /// Equivalent to `.*`.
+    #[token("**")]
+    GlobStar,
+
+    #[token("/")]
+    SepUnix,
+
+    #[token(r"\")]
+    SepWin,
+
+    /// `?(`, `*(`, `+(`, `@(` or `!(`.
+    #[regex(r"[?*+@!]\(")]
+    GroupStart,
+
+    /// `|` in a group.
+    #[token("|")]
+    GroupSep,
+
+    /// `)` of a group.
+    #[token(")")]
+    GroupEnd,
+
+    /// Plain text.
+    #[regex(r"[^*?\[\]/\\()|@+!]+")]
+    #[regex(r"[(@+!]")]
+    Text,
+}
+
+impl ExtGlobPathToken {
+    fn glob(self) -> GlobPathToken {
+        match self {
+            ExtGlobPathToken::Any => GlobPathToken::Any,
+            ExtGlobPathToken::Star => GlobPathToken::Star,
+            ExtGlobPathToken::Class => GlobPathToken::Class,
+            ExtGlobPathToken::GlobStar => GlobPathToken::GlobStar,
+            ExtGlobPathToken::SepUnix => GlobPathToken::SepUnix,
+            ExtGlobPathToken::SepWin => GlobPathToken::SepWin,
+            ExtGlobPathToken::GroupStart
+            | ExtGlobPathToken::GroupSep
+            | ExtGlobPathToken::GroupEnd
+            | ExtGlobPathToken::Text => GlobPathToken::Text,
+        }
+    }
+}
+
+/// - `strict`: Return an error on `!(...)` that can't be expressed instead of treating it as literal characters.
+pub(super) fn parse(
+    pattern: &str,
+    translator: &mut GlobPathTranslator,
+    mut surrounding_handler: Option<SurroundingWildcardHandler>,
+    strict: bool,
+) -> Result<Hir, GlobParseError> {
+    let mut lex = ExtGlobPathToken::lexer(pattern);
+    let mut hirs = Vec::new();
+    while let Some(token) = lex.next() {
+        let token = token.unwrap_or(ExtGlobPathToken::Text);
+        if let Some(h) = &mut surrounding_handler {
+            if h.skip(token, &mut hirs, &lex) {
+                continue;
+            }
+        }
+
+        hirs.push(match token {
+            ExtGlobPathToken::GroupStart => group_or_literal(&mut lex, translator, strict)?,
+            _ => translator.translate(token.glob(), lex.slice()),
+        });
+    }
+
+    if let Some(h) = surrounding_handler {
+        h.insert_anchors(&mut hirs);
+    }
+
+    Ok(Hir::concat(hirs))
+}
+
+/// Why a group failed to parse.
+enum GroupError {
+    /// Not a group, e.g. unclosed.
+    Invalid,
+    /// `!(...)` with a non-literal alternative, with the span of the group.
+    NonLiteralNegation(Range<usize>),
+}
+
+/// Parse a group after [`ExtGlobPathToken::GroupStart`], or treat the group start as literal characters if failed.
+///
+/// - `strict`: Return an error on `!(...)` that can't be expressed instead.
+fn group_or_literal(
+    lex: &mut Lexer<ExtGlobPathToken>,
+    translator: &mut GlobPathTranslator,
+    strict: bool,
+) -> Result<Hir, GlobParseError> {
+    let start = lex.slice();
+    let mut group_lex = lex.clone();
+    Ok(match group(&mut group_lex, translator) {
+        Ok(hir) => {
+            *lex = group_lex;
+            hir
+        }
+        Err(GroupError::NonLiteralNegation(span)) if strict => {
+            return Err(GlobParseError {
+                span,
+                kind: GlobParseErrorKind::NonLiteralNegation,
+            })
+        }
+        Err(_) => {
+            let (op, paren) = start.split_at(1);
+            let op = match op {
+                "?" => translator.translate(GlobPathToken::Any, op),
+                "*" => translator.translate(GlobPathToken::Star, op),
+                _ => literal(op, translator.case_insensitive),
+            };
+            Hir::concat(vec![op, literal(paren, translator.case_insensitive)])
+        }
+    })
+}
+
+fn group(
+    lex: &mut Lexer<ExtGlobPathToken>,
+    translator: &mut GlobPathTranslator,
+) -> Result<Hir, GroupError> {
+    let start = lex.span().start;
+    let op = lex.slice().as_bytes()[0];
+
+    // (hirs, literal)
+    let mut alternatives: Vec<(Vec<Hir>, Option<String>)> = vec![(Vec::new(), Some(String::new()))];
+    loop {
+        let (hirs, text) = alternatives.last_mut().unwrap();
+        match lex.next().ok_or(GroupError::Invalid)? {
+            Ok(ExtGlobPathToken::GroupSep) => {
+                alternatives.push((Vec::new(), Some(String::new())));
+                continue;
+            }
+            Ok(ExtGlobPathToken::GroupEnd) => break,
+            Ok(ExtGlobPathToken::GroupStart) => {
+                hirs.push(group(lex, translator)?);
+                *text = None;
+            }
+            Ok(token @ ExtGlobPathToken::Text) => {
+                hirs.push(translator.translate(token.glob(), lex.slice()));
+                if let Some(text) = text {
+                    text.push_str(lex.slice());
+                }
+            }
+            Ok(token) => {
+                hirs.push(translator.translate(token.glob(), lex.slice()));
+                *text = None;
+            }
+            Err(()) => return Err(GroupError::Invalid),
+        }
+    }
+
+    let sub = || {
+        let subs = alternatives
+            .iter()
+            .map(|(hirs, _)| Hir::concat(hirs.clone()))
+            .collect();
+        Hir::alternation(subs)
+    };
+    let repetition = |min, max| {
+        Hir::repetition(Repetition {
+            min,
+            max,
+            greedy: true,
+            sub: sub().into(),
+        })
+    };
+    Ok(match op {
+        b'@' => sub(),
+        b'?' => repetition(0, Some(1)),
+        b'*' => repetition(0, None),
+        b'+' => repetition(1, None),
+        b'!' => {
+            // Only the complement of literals can be expressed
+            let mut texts = alternatives
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect::<Option<Vec<String>>>()
+                .ok_or(GroupError::NonLiteralNegation(start..lex.span().end))?;
+            if translator.case_insensitive {
+                texts
+                    .iter_mut()
+                    .for_each(|text| text.make_ascii_lowercase());
+            }
+            complement(&texts, translator)
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Any path component text that doesn't equal to one of `texts`.
+///
+/// i.e. for the trie of `texts`, at each node either stop if the node isn't a text,
+/// or go to a char that isn't a child and then match anything,
+/// or go to a child and repeat.
+fn complement(texts: &[String], translator: &mut GlobPathTranslator) -> Hir {
+    let case_insensitive = translator.case_insensitive;
+    let seps: &[char] = match translator.separator.desugar() {
+        super::PathSeparator::Unix => &['/'],
+        super::PathSeparator::Windows => &['\\'],
+        _ => &['/', '\\'],
+    };
+
+    let mut is_text = false;
+    let mut children: BTreeMap<char, Vec<String>> = BTreeMap::new();
+    for text in texts {
+        let mut chars = text.chars();
+        match chars.next() {
+            Some(c) => children
+                .entry(c)
+                .or_default()
+                .push(chars.as_str().to_owned()),
+            None => is_text = true,
+        }
+    }
+
+    let mut excluded = ClassUnicode::new(
+        seps.iter()
+            .chain(children.keys())
+            .map(|&c| ClassUnicodeRange::new(c, c)),
+    );
+    if case_insensitive {
+        let upper = ClassUnicode::new(
+            children
+                .keys()
+                .map(|c| c.to_ascii_uppercase())
+                .map(|c| ClassUnicodeRange::new(c, c)),
+        );
+        excluded.union(&upper);
+    }
+    excluded.negate();
+
+    let mut alternatives = vec![Hir::concat(vec![
+        Hir::class(Class::Unicode(excluded)),
+        translator.translate(GlobPathToken::Star, "*"),
+    ])];
+    for (c, tails) in children {
+        alternatives.push(Hir::concat(vec![
+            literal(c.encode_utf8(&mut [0; 4]), case_insensitive),
+            complement(&tails, translator),
+        ]));
+    }
+    if !is_text {
+        alternatives.push(Hir::empty());
+    }
+    Hir::alternation(alternatives)
+}
@@ -0,0 +1,305 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use bon::builder;
+use logos::Logos;
+use regex_syntax::{
+    hir::{Dot, Hir, Look, Repetition},
+    ParserBuilder,
+};
+
+use super::{GlobPathToken, GlobPathTranslator, PathSeparator};
+
+/// A parsed pattern of [`parse_gitignore`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GitignorePattern {
+    /// Matches the whole path (relative to the directory of the `.gitignore` file) that the pattern applies to.
+    pub hir: Hir,
+    /// The pattern is prefixed with `!`, i.e. matched paths should be re-included.
+    pub negated: bool,
+    /// The pattern is suffixed with `/`, i.e. it should only match directories.
+    pub dir_only: bool,
+}
+
+/// [`.gitignore`](https://git-scm.com/docs/gitignore#_pattern_format) pattern syntax flavor.
+///
+/// Returns `None` if the pattern is a blank line or a comment.
+///
+/// The returned [`GitignorePattern::hir`] matches the whole path relative to the directory of the `.gitignore` file, like `src/main.rs`:
+/// - A leading `!` negates the pattern. See [`GitignorePattern::negated`].
+/// - A trailing `/` makes the pattern only match directories. See [`GitignorePattern::dir_only`].
+/// - A pattern with a `/` at the beginning or middle is anchored to the directory of the `.gitignore` file,
+///   otherwise it can match at any level below the directory.
+/// - `*`, `?` and `[]` never match `/`.
+/// - A leading `**/` matches in all directories, a trailing `/**` matches everything inside,
+///   and `/**/` matches zero or more directories. Other `**` are the same as `*`.
+/// - A backslash escapes the following character, including trailing spaces, which are ignored otherwise.
+///
+/// Like git, a path is not matched if only its parent directory is matched, e.g. `foo/` doesn't match `foo/bar`.
+/// And the precedence of multiple patterns (the last matched one wins) is left to the caller.
+///
+/// [Surrounding wildcards](super::glob#surrounding-wildcards-as-anchors) are not special, since the whole path is always matched.
+///
+/// ## Example
+/// ```
+/// // cargo add ib-matcher --features syntax-glob,regex
+/// use ib_matcher::{regex::lita::Regex, syntax::glob::{parse_gitignore, PathSeparator}};
+///
+/// let pattern = parse_gitignore()
+///     .separator(PathSeparator::Unix)
+///     .call("/target/**/*.d")
+///     .unwrap();
+/// assert!(!pattern.negated && !pattern.dir_only);
+/// let re = Regex::builder().build_from_hir(pattern.hir).unwrap();
+/// assert!(re.is_match("target/debug/main.d"));
+/// assert!(re.is_match("target/main.d"));
+/// assert!(re.is_match("src/target/main.d") == false);
+///
+/// let pattern = parse_gitignore()
+///     .separator(PathSeparator::Unix)
+///     .call("!*.log")
+///     .unwrap();
+/// assert!(pattern.negated);
+/// let re = Regex::builder().build_from_hir(pattern.hir).unwrap();
+/// assert!(re.is_match("debug.log"));
+/// assert!(re.is_match("logs/debug.log"));
+/// assert!(re.is_match("debug.log.txt") == false);
+///
+/// assert_eq!(parse_gitignore().separator(PathSeparator::Unix).call("# comment"), None);
+/// ```
+#[builder]
+pub fn parse_gitignore(
+    #[builder(finish_fn)] pattern: &str,
+    /// The path separator used in the haystacks to be matched.
+    ///
+    /// The separator used in the pattern is always `/`.
+    separator: PathSeparator,
+    /// See [`case insensitivity`](super::glob#case-insensitivity), i.e. `core.ignoreCase`.
+    #[builder(default)]
+    case_insensitive: bool,
+) -> Option<GitignorePattern> {
+    let mut pattern = pattern.strip_suffix('\r').unwrap_or(pattern);
+
+    // Trailing spaces are ignored unless they are escaped
+    while let Some(p) = pattern.strip_suffix(' ') {
+        if p.ends_with('\\') && !p.ends_with(r"\\") {
+            break;
+        }
+        pattern = p;
+    }
+
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return None;
+    }
+    let negated = match pattern.strip_prefix('!') {
+        Some(p) => {
+            pattern = p;
+            true
+        }
+        None => false,
+    };
+    let dir_only = match pattern.strip_suffix('/') {
+        Some(p) => {
+            pattern = p;
+            true
+        }
+        None => false,
+    };
+    let anchored = pattern.contains('/');
+    pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+    let pattern = unescape(pattern);
+
+    let mut translator = GlobPathTranslator {
+        separator,
+        pattern_separator: PathSeparator::Unix,
+        case_insensitive,
+        max_globstar_depth: None,
+        parser: ParserBuilder::new()
+            .unicode(false)
+            .utf8(false)
+            .case_insensitive(case_insensitive)
+            .build(),
+    };
+    let tokens: Vec<_> = GlobPathToken::lexer(&pattern)
+        .spanned()
+        // e.g. `]`
+        .map(|(token, span)| (token.unwrap_or(GlobPathToken::Text), &pattern[span]))
+        .collect();
+
+    // `(.*/)?`
+    let any_dirs = || {
+        Hir::repetition(Repetition {
+            min: 0,
+            max: Some(1),
+            greedy: true,
+            sub: Hir::concat(vec![
+                Hir::repetition(Repetition {
+                    min: 0,
+                    max: None,
+                    greedy: true,
+                    sub: Hir::dot(Dot::AnyByte).into(),
+                }),
+                separator.literal(),
+            ])
+            .into(),
+        })
+    };
+
+    let mut hirs = vec![Hir::look(Look::Start)];
+    if !anchored {
+        hirs.push(any_dirs());
+    }
+    let is_sep = |i: usize| matches!(tokens.get(i), Some((GlobPathToken::SepUnix, _)));
+    let mut i = 0;
+    while let Some(&(token, s)) = tokens.get(i) {
+        hirs.push(match token {
+            GlobPathToken::GlobStar if (i == 0 || is_sep(i - 1)) && is_sep(i + 1) => {
+                // Skip the separator
+                i += 1;
+                any_dirs()
+            }
+            GlobPathToken::GlobStar if i != 0 && is_sep(i - 1) && i + 1 == tokens.len() => {
+                Hir::repetition(Repetition {
+                    min: 1,
+                    max: None,
+                    greedy: true,
+                    sub: Hir::dot(Dot::AnyByte).into(),
+                })
+            }
+            GlobPathToken::GlobStar => translator.translate(GlobPathToken::Star, "*"),
+            _ => translator.translate(token, s),
+        });
+        i += 1;
+    }
+    hirs.push(Hir::look(Look::End));
+
+    Some(GitignorePattern {
+        hir: Hir::concat(hirs),
+        negated,
+        dir_only,
+    })
+}
+
+/// Unescape `\c`, with metacharacters escaped by `[]`.
+fn unescape(pattern: &str) -> String {
+    let mut s = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(c @ ('*' | '?' | '[' | ']' | '\\' | '/')) => {
+                    s.push('[');
+                    s.push(c);
+                    s.push(']');
+                }
+                Some(c) => s.push(c),
+                None => (),
+            },
+            c => s.push(c),
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::lita::Regex;
+
+    use super::*;
+
+    #[test]
+    fn gitignore() {
+        let parse = |p| parse_gitignore().separator(PathSeparator::Unix).call(p);
+        let is_match = |p, h| {
+            Regex::builder()
+                .build_from_hir(parse(p).unwrap().hir)
+                .unwrap()
+                .is_match(h)
+        };
+
+        // Blank lines and comments
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("  "), None);
+        assert_eq!(parse("# foo"), None);
+        assert!(is_match(r"\#foo", "#foo"));
+
+        // Negation
+        let p = parse("!foo").unwrap();
+        assert!(p.negated);
+        assert!(!p.dir_only);
+        assert!(is_match("!foo", "foo"));
+        assert!(!parse(r"\!foo").unwrap().negated);
+        assert!(is_match(r"\!foo", "!foo"));
+
+        // Directory only
+        let p = parse("foo/").unwrap();
+        assert!(p.dir_only);
+        assert!(is_match("foo/", "foo"));
+        assert!(is_match("foo/", "a/foo"));
+        assert!(!is_match("foo/", "foo/bar"));
+
+        // Anchoring
+        assert!(is_match("foo", "foo"));
+        assert!(is_match("foo", "a/b/foo"));
+        assert!(!is_match("foo", "afoo"));
+        assert!(!is_match("foo", "foo/bar"));
+        assert!(is_match("/foo", "foo"));
+        assert!(!is_match("/foo", "a/foo"));
+        assert!(is_match("a/foo", "a/foo"));
+        assert!(!is_match("a/foo", "b/a/foo"));
+
+        // Wildcards
+        assert!(is_match("*.log", "a/b.log"));
+        assert!(!is_match("a/*.log", "a/b/c.log"));
+        assert!(is_match("a/?.log", "a/b.log"));
+        assert!(!is_match("a/?.log", "a//.log"));
+        assert!(is_match("a/[bc].log", "a/c.log"));
+        assert!(is_match("a/[!bc].log", "a/d.log"));
+
+        // **
+        assert!(is_match("**/foo", "foo"));
+        assert!(is_match("**/foo", "a/b/foo"));
+        assert!(is_match("**/foo/bar", "a/foo/bar"));
+        assert!(is_match("abc/**", "abc/a/b"));
+        assert!(!is_match("abc/**", "abc"));
+        assert!(is_match("a/**/b", "a/b"));
+        assert!(is_match("a/**/b", "a/x/y/b"));
+        assert!(!is_match("a/**/b", "x/a/b"));
+        assert!(is_match("a**b", "axxb"));
+        assert!(!is_match("a**b", "ax/xb"));
+
+        // Escapes and trailing spaces
+        assert!(is_match("foo  ", "foo"));
+        assert!(is_match(r"foo\ ", "foo "));
+        assert!(is_match(r"a\*b", "a*b"));
+        assert!(!is_match(r"a\*b", "axb"));
+        assert!(is_match(r"a\[b]", "a[b]"));
+        assert!(is_match(r"a\\b", r"a\b"));
+
+        // Separator
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_gitignore()
+                    .separator(PathSeparator::Windows)
+                    .call("a/**/*.d")
+                    .unwrap()
+                    .hir,
+            )
+            .unwrap();
+        assert!(re.is_match(r"a\b\c.d"));
+        assert!(!re.is_match("a/b/c.d"));
+
+        // Case insensitive
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_gitignore()
+                    .separator(PathSeparator::Unix)
+                    .case_insensitive(true)
+                    .call("/README.md")
+                    .unwrap()
+                    .hir,
+            )
+            .unwrap();
+        assert!(re.is_match("readme.MD"));
+    }
+}
@@ -10,10 +10,14 @@ Supported syntax:
 
   Used by voidtools' Everything, etc.
 
+- [`parse_ev`]: [`parse_wildcard_path`] with [`GlobExtConfig::new_ev()`], i.e. the flavor used by IbEverythingExt.
+
 - [`parse_glob_path`]: `?`, `*`, `[]` and `**`, optionally with [`GlobExtConfig`].
-  - Parsing of `[]` is [fallible](#error-behavior).
+  - Parsing of `[]` is [fallible](#error-behavior), see [`try_parse_glob_path`] for reporting errors.
   - Not Windows file name safe: `[]` may disturb the matching of literal `[]` in file names.
 
+- [`parse_gitignore`]: [`.gitignore`](https://git-scm.com/docs/gitignore#_pattern_format) patterns, including `!`, `?`, `*`, `[]` and `**`.
+
 */
 //! - [`GlobExtConfig`]: Two seperators (`//`) or a complement separator (`\`) as a glob star (`*/**`).
 /*!
@@ -70,9 +74,33 @@ There are four possible anchor modes:
 - Matching to the end of the string. Rarely used besides matching file extensions.
 - Matching the whole string (from the start to the end). Used by [voidtools' Everything](https://github.com/Chaoses-Ib/IbEverythingExt/issues/98).
 
-This module will match from anywhere in the string by default. For other modes:
+This module will match from anywhere in the string by default. Other modes can be set by [`anchor`](ParseWildcardPathBuilder::anchor), see [`AnchorMode`]:
+```
+use ib_matcher::{
+    regex::lita::Regex,
+    syntax::glob::{parse_wildcard_path, AnchorMode, PathSeparator}
+};
+
+let re = Regex::builder()
+    .build_from_hir(
+        parse_wildcard_path()
+            .separator(PathSeparator::Windows)
+            .anchor(AnchorMode::Whole)
+            .call(r"C:\**.exe"),
+    )
+    .unwrap();
+assert!(re.is_match(r"C:\Windows\System32
+otepad.exe"));
+assert!(re.is_match(r"D:\C:
+otepad.exe") == false);
+assert!(re.is_match(r"C:
+otepad.exe.lnk") == false);
+```
+
+Wildcards on the anchored sides are matched as is, e.g. `*.mp4` with [`AnchorMode::Whole`] matches `v.mp4` but not `v.mp4_0.webp`, and `foo*` with [`AnchorMode::Start`] matches `foobar`.
+
+Without setting the anchor mode, some patterns can also be anchored:
 - To match from the start of the string only, you can append a `*` to the pattern (like `foo*`), which will then be consider as an anchor (by [`surrounding_wildcard_as_anchor`](ParseWildcardPathBuilder::surrounding_wildcard_as_anchor)).
-- To match the whole string only, you can combine the above one with checking the returned match length at the moment.
 - If you want to match to the end of the string, prepend a `*`, like `*.mp4`.
 
 ### Surrounding wildcards as anchors
@@ -138,19 +166,128 @@ assert!(re.is_match(r"C\DC\foobar\9") == false);
 
 The latter behavior is used by voidtools' Everything.
 
+For [`PathSeparator::Any`], a look matcher can't match both `/` and `\` since it only supports one line terminator, so the anchors are translated to `(?:^|[/\\])` and `(?:$|[/\\])` instead. They always match path components, with or without setting `look_matcher`, and the separators they match are included in the match:
+```
+use ib_matcher::{
+    regex::lita::Regex,
+    syntax::glob::{parse_wildcard_path, PathSeparator}
+};
+
+let re = Regex::builder()
+    .build_from_hir(
+        parse_wildcard_path()
+            .separator(PathSeparator::Any)
+            .call(r"?\foo*\"),
+    )
+    .unwrap();
+assert!(re.is_match(r"C\foobar\9"));
+assert!(re.is_match(r"D/C\foobar/9"));
+assert!(re.is_match(r"DC/foobar/9") == false);
+
+let re = Regex::builder()
+    .build_from_hir(parse_wildcard_path().separator(PathSeparator::Any).call("foo*"))
+    .unwrap();
+assert_eq!(re.find(r"C:\foobar").unwrap().range(), 2..6);
+assert_eq!(re.find("foobar").unwrap().range(), 0..3);
+assert!(re.is_match("C:/barfoo") == false);
+```
+
 Related issue: [IbEverythingExt #99](https://github.com/Chaoses-Ib/IbEverythingExt/issues/99)
 
+## Globstar depth
+`**` matches across any number of path components by default. [`max_globstar_depth`](ParseWildcardPathBuilder::max_globstar_depth) can limit the number of separators it matches, i.e. how many directory levels it can go down:
+```
+use ib_matcher::{
+    regex::lita::Regex,
+    syntax::glob::{parse_wildcard_path, PathSeparator}
+};
+
+let re = Regex::builder()
+    .build_from_hir(
+        parse_wildcard_path()
+            .separator(PathSeparator::Windows)
+            .max_globstar_depth(1)
+            .call(r"src\**.rs"),
+    )
+    .unwrap();
+assert!(re.is_match(r"src\lib.rs"));
+assert!(re.is_match(r"src\glob\mod.rs"));
+assert!(re.is_match(r"src\syntax\glob\mod.rs") == false);
+```
+`**` with a depth of `n` matches at most `n` separators, so a depth of 0 makes `**` the same as `*`.
+
+The limit also applies to the `**` desugared by [`GlobExtConfig`], but not the separators around it. For example, with [`GlobStar::ToChild`], `//` can match at most `n + 1` separators.
+
+Leading and trailing `**` are treated as [anchors](#surrounding-wildcards-as-anchors) rather than wildcards by default, so they are not limited. [Path anchors](#anchors-in-file-paths) are not affected.
+
+## Tilde expansion
+[`parse_wildcard_path`] can expand a leading `~` or `~user` to a home directory with [`expand_tilde`](ParseWildcardPathBuilder::expand_tilde), like shells and file pickers.
+
+The home directory is provided by the given function instead of being read from the environment, which takes the user name (empty for `~`) and returns the home directory in the form of the haystacks. If it returns `None`, `~` is treated as a literal. A `~` not at the start of the pattern is always a literal.
+
+The expansion happens at parse time, i.e. the returned `Hir` already contains the home directory as a literal:
+```
+use ib_matcher::{
+    regex::lita::Regex,
+    syntax::glob::{parse_wildcard_path, AnchorMode, PathSeparator}
+};
+
+let home_dir = |user: &str| match user {
+    "" => Some("/home/me".to_string()),
+    "alice" => Some("/home/alice".to_string()),
+    _ => None,
+};
+let parse = |pattern| {
+    parse_wildcard_path()
+        .separator(PathSeparator::Unix)
+        .anchor(AnchorMode::Start)
+        .expand_tilde(&home_dir)
+        .call(pattern)
+};
+
+let re = Regex::builder().build_from_hir(parse("~/README*")).unwrap();
+assert!(re.is_match("/home/me/README.md"));
+assert!(re.is_match("/home/alice/README.md") == false);
+
+let re = Regex::builder().build_from_hir(parse("~alice/README*")).unwrap();
+assert!(re.is_match("/home/alice/README.md"));
+
+let re = Regex::builder().build_from_hir(parse("~bob/README*")).unwrap();
+assert!(re.is_match("~bob/README.md"));
+```
+
 ## Character classes
 <!-- Support the same syntax as in [`regex`](crate::syntax::regex#character-classes), with `^` replaced by `!`. -->
 
 Support patterns like `[abc]`, `[a-z]`, `[!a-z]` and `[[:ascii:]]`.
 
+`[!...]` and `[^...]` are the same negated class. `!` and `^` are only special right after `[`, e.g. `[a!]` matches `a` or `!`, and `[![:digit:]]` matches any character except digits.
+
+All POSIX classes are supported: `alnum`, `alpha`, `ascii`, `blank`, `cntrl`, `digit`, `graph`, `lower`, `print`, `punct`, `space`, `upper`, `word` and `xdigit`. They can be negated with `^` (e.g. `[[:^alpha:]]`) and combined with other items (e.g. `[[:alpha:][:digit:]_]`). Like other characters in classes, they only match ASCII characters.
+
 Character classes can be used to escape the metacharacter: `[?]`, `[*]`, `[[]`, `[]]` match the literal characters `?`, `*`, `[`, `]` respectively.
 
 ### Error behavior
 Parsing of `[]` is fallible: patterns like `a[b` are invalid.
 
-At the moment related characters will be treated as literal characters if parsing fails.
+[`parse_glob_path`] treats the related characters as literal characters if parsing fails,
+while [`try_parse_glob_path`] returns a [`GlobParseError`] with the byte span of the invalid class,
+e.g. to tell users their `[` is unclosed:
+```
+use ib_matcher::syntax::glob::{try_parse_glob_path, GlobParseErrorKind, PathSeparator};
+
+let parse = |p| try_parse_glob_path().separator(PathSeparator::Windows).call(p);
+assert!(parse("a[b]z").is_ok());
+
+let e = parse("a[bz").unwrap_err();
+assert_eq!(e.kind, GlobParseErrorKind::UnclosedClass);
+assert_eq!(e.span, 1..2);
+
+let e = parse("a[z-b]").unwrap_err();
+assert_eq!(e.kind, GlobParseErrorKind::InvalidRange);
+assert_eq!(e.span, 1..6);
+```
+A `]` without `[` is always a literal character, not an error.
 
 ### Examples
 ```
@@ -175,6 +312,9 @@ assert!(is_match("a[!b]z", "acz"));
 
 // ASCII character class
 assert!(is_match("a[[:space:]]z", "a z"));
+assert!(is_match("a[[:^space:]]z", "a z") == false);
+assert!(is_match("a[![:digit:]]z", "a1z") == false);
+assert!(is_match("a[[:alpha:][:digit:]]z", "a1z"));
 
 // Escape
 assert!(is_match("a[?]z", "a?z"));
@@ -189,21 +329,116 @@ assert!(is_match("a[b", "a[bz"));
 assert!(is_match("a[[b]z", "a[[b]z"));
 assert!(is_match("a[!]z", "a[!]z"));
 ```
+
+## Extended glob
+With [`extglob(true)`](ParseGlobPathBuilder::extglob), Bash's extended glob groups are supported:
+`?(a|b)`, `*(a|b)`, `+(a|b)` and `@(a|b)` match zero or one, zero or more, one or more and exactly one of the alternatives,
+and `!(a|b)` matches any path component text other than the alternatives.
+
+Only the negation of literal alternatives can be expressed, e.g. `!(foo|bar)` but not `!(*.txt)`.
+Like invalid `[]`, [`parse_glob_path`] treats such a group (and unclosed groups) as literal characters,
+while [`try_parse_glob_path`] returns a [`GlobParseError`] with [`GlobParseErrorKind::NonLiteralNegation`]:
+```
+use ib_matcher::{
+    regex::cp::Regex,
+    syntax::glob::{parse_glob_path, try_parse_glob_path, GlobParseErrorKind, PathSeparator},
+};
+
+let re = Regex::builder()
+    .build_from_hir(
+        parse_glob_path()
+            .separator(PathSeparator::Windows)
+            .extglob(true)
+            .call(r"*\!(foo|bar).txt"),
+    )
+    .unwrap();
+assert!(re.is_match(r"C:\baz.txt"));
+assert!(re.is_match(r"C:\foo.txt") == false);
+
+let e = try_parse_glob_path()
+    .separator(PathSeparator::Windows)
+    .extglob(true)
+    .call("a!(*.txt)")
+    .unwrap_err();
+assert_eq!(e.kind, GlobParseErrorKind::NonLiteralNegation);
+assert_eq!(e.span, 1..9);
+```
+
+## Case insensitivity
+By default, the parsed `Hir` is case-sensitive. With `case_insensitive(true)`, ASCII letters in both literals and character classes are case-folded, e.g. `README` matches `readme` and `[a-c]` matches `B`:
+```
+# use ib_matcher::{syntax::glob::{parse_glob_path, PathSeparator}, regex::cp::Regex};
+# let is_match = |p, h| {
+#     Regex::builder()
+#         .build_from_hir(
+#             parse_glob_path()
+#                 .separator(PathSeparator::Windows)
+#                 .case_insensitive(true)
+#                 .call(p),
+#         )
+#         .unwrap()
+#         .is_match(h)
+# };
+assert!(is_match("README.*", "readme.md"));
+assert!(is_match("a[b-d]z", "ACZ"));
+assert!(is_match("a[!b]z", "aBz") == false);
+// Non-ASCII letters are not folded
+assert!(is_match("ä", "Ä") == false);
+assert!(is_match("[ä]", "Ä") == false);
+```
+
+Only ASCII letters are folded, including those in character classes. Character classes are parsed in ASCII mode, so non-ASCII characters in classes (e.g. `[ä]`) are neither case-folded nor Unicode-aware.
+
+Case-folded literals are no longer literal `Hir`s, so they won't be matched by [`IbMatcher`](crate::matcher::IbMatcher) (i.e. pinyin and romaji). To use case insensitivity with pinyin or romaji matching, leave `case_insensitive` unset and set [`MatchConfigBuilder::case_insensitive`](crate::matcher::MatchConfigBuilder::case_insensitive) instead, which makes the regex engines match literals case-insensitively. Character classes are not affected by it at the moment.
+```
+use ib_matcher::{
+    matcher::MatchConfig,
+    regex::lita::Regex,
+    syntax::glob::{parse_glob_path, PathSeparator}
+};
+
+let re = Regex::builder()
+    .ib(MatchConfig::builder()
+        .case_insensitive(true)
+        .pinyin(Default::default())
+        .build())
+    .build_from_hir(
+        parse_glob_path()
+            .separator(PathSeparator::Windows)
+            .call(r"readme*pyss"),
+    )
+    .unwrap();
+assert!(re.is_match("README 拼音搜索"));
+assert!(re.is_match("ReadMe.pyss"));
+```
 */
-use std::{borrow::Cow, path::MAIN_SEPARATOR};
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+use core::{fmt, ops::Range};
+#[cfg(feature = "std")]
+use std::path::MAIN_SEPARATOR;
 
 use bon::{builder, Builder};
 use logos::Logos;
 use regex_automata::{nfa::thompson, util::look::LookMatcher};
 use regex_syntax::{
     hir::{
-        Class, ClassBytes, ClassBytesRange, ClassUnicode, ClassUnicodeRange, Dot, Hir, Repetition,
+        Class, ClassBytes, ClassBytesRange, ClassUnicode, ClassUnicodeRange, Dot, Hir, Look,
+        Repetition,
     },
     ParserBuilder,
 };
 
 use util::SurroundingWildcardHandler;
 
+pub use components::{matched_path_components, path_components};
+pub use extglob::ExtGlobPathToken;
+pub use gitignore::{parse_gitignore, GitignorePattern, ParseGitignoreBuilder};
+
+use crate::syntax::regex::hir::case::literal_to_ascii_case_insensitive;
+
+mod components;
+mod extglob;
+mod gitignore;
 mod util;
 
 /// See [`parse_wildcard`].
@@ -226,14 +461,20 @@ pub enum WildcardToken {
 #[builder]
 pub fn parse_wildcard(
     #[builder(finish_fn)] pattern: &str,
+    /// See [`anchor modes`](super::glob#anchor-modes).
+    #[builder(default)]
+    anchor: AnchorMode,
     /// See [`surrounding wildcards as anchors`](super::glob#surrounding-wildcards-as-anchors).
     #[builder(default = true)]
     surrounding_wildcard_as_anchor: bool,
+    /// See [`case insensitivity`](super::glob#case-insensitivity).
+    #[builder(default)]
+    case_insensitive: bool,
 ) -> Hir {
     let mut lex = WildcardToken::lexer(&pattern);
     let mut hirs = Vec::new();
-    let mut surrounding_handler =
-        surrounding_wildcard_as_anchor.then(|| SurroundingWildcardHandler::new(PathSeparator::Any));
+    let mut surrounding_handler = surrounding_wildcard_as_anchor
+        .then(|| SurroundingWildcardHandler::new(PathSeparator::Any, None, anchor));
     while let Some(Ok(token)) = lex.next() {
         if let Some(h) = &mut surrounding_handler {
             if h.skip(token, &mut hirs, &lex) {
@@ -249,7 +490,7 @@ pub fn parse_wildcard(
                 greedy: true,
                 sub: Hir::dot(Dot::AnyByte).into(),
             }),
-            WildcardToken::Text => Hir::literal(lex.slice().as_bytes()),
+            WildcardToken::Text => literal(lex.slice(), case_insensitive),
         });
     }
 
@@ -257,7 +498,42 @@ pub fn parse_wildcard(
         h.insert_anchors(&mut hirs);
     }
 
-    Hir::concat(hirs)
+    anchor.anchor(Hir::concat(hirs))
+}
+
+/// See [anchor modes](super::glob#anchor-modes).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnchorMode {
+    /// Match from anywhere in the string.
+    #[default]
+    Anywhere,
+    /// Match from the start of the string, i.e. `\A`.
+    Start,
+    /// Match to the end of the string, i.e. `\z`.
+    End,
+    /// Match the whole string, i.e. `\A` and `\z`.
+    Whole,
+}
+
+impl AnchorMode {
+    fn is_start(self) -> bool {
+        matches!(self, AnchorMode::Start | AnchorMode::Whole)
+    }
+
+    fn is_end(self) -> bool {
+        matches!(self, AnchorMode::End | AnchorMode::Whole)
+    }
+
+    fn anchor(self, hir: Hir) -> Hir {
+        match self {
+            AnchorMode::Anywhere => hir,
+            AnchorMode::Start => Hir::concat(vec![Hir::look(Look::Start), hir]),
+            AnchorMode::End => Hir::concat(vec![hir, Hir::look(Look::End)]),
+            AnchorMode::Whole => {
+                Hir::concat(vec![Hir::look(Look::Start), hir, Hir::look(Look::End)])
+            }
+        }
+    }
 }
 
 /// Defaults to [`PathSeparator::Os`], i.e. `/` on Unix and `\` on Windows.
@@ -274,6 +550,10 @@ pub enum PathSeparator {
     Any,
 }
 
+/// [`std::path::MAIN_SEPARATOR`] without `std`.
+#[cfg(not(feature = "std"))]
+const MAIN_SEPARATOR: char = if cfg!(windows) { '\\' } else { '/' };
+
 impl PathSeparator {
     fn os_desugar() -> Self {
         if MAIN_SEPARATOR == '\\' {
@@ -298,6 +578,15 @@ impl PathSeparator {
         matches!(self.desugar(), PathSeparator::Windows | PathSeparator::Any)
     }
 
+    fn is_separator(self, b: u8) -> bool {
+        match self.desugar() {
+            PathSeparator::Os => unreachable!(),
+            PathSeparator::Unix => b == b'/',
+            PathSeparator::Windows => b == b'\\',
+            PathSeparator::Any => b == b'/' || b == b'\\',
+        }
+    }
+
     fn literal(&self) -> Hir {
         match self.desugar() {
             PathSeparator::Os => unreachable!(),
@@ -340,16 +629,16 @@ impl PathSeparator {
         }
     }
 
-    /// Does not support `PathSeparator::Any` yet.
+    /// A look matcher whose line terminator is the separator, i.e. `StartLF` and `EndLF` match path components.
+    ///
+    /// A look matcher only supports one line terminator, so for [`PathSeparator::Any`] the parsers use explicit `(?:^|[/\\])` and `(?:$|[/\\])` instead, which always match path components, see [anchors in file paths](super::glob#anchors-in-file-paths). The returned look matcher uses `/` in this case.
     pub fn look_matcher(&self) -> LookMatcher {
-        debug_assert!(!matches!(self, PathSeparator::Any));
-
         let mut lookm = LookMatcher::new();
         lookm.set_line_terminator(if self.is_unix_or_any() { b'/' } else { b'\\' });
         lookm
     }
 
-    /// Does not support `PathSeparator::Any` yet.
+    /// See [`PathSeparator::look_matcher()`].
     pub fn look_matcher_config(&self) -> thompson::Config {
         thompson::Config::new().look_matcher(self.look_matcher())
     }
@@ -582,6 +871,7 @@ pub enum WildcardPathToken {
 ///
 /// Used by voidtools' Everything, etc.
 #[builder]
+#[allow(clippy::type_complexity)]
 pub fn parse_wildcard_path(
     #[builder(finish_fn)] pattern: &str,
     /// The separator used in the pattern. Can be different from the one used in the haystacks to be matched.
@@ -592,20 +882,45 @@ pub fn parse_wildcard_path(
     ///
     /// Only have effect on `?` and `*`.
     separator: PathSeparator,
+    /// See [`anchor modes`](super::glob#anchor-modes).
+    #[builder(default)]
+    anchor: AnchorMode,
     /// See [`surrounding wildcards as anchors`](super::glob#surrounding-wildcards-as-anchors).
     #[builder(default = true)]
     surrounding_wildcard_as_anchor: bool,
+    /// See [`case insensitivity`](super::glob#case-insensitivity).
+    #[builder(default)]
+    case_insensitive: bool,
+    /// The maximum number of separators `**` can match. Unlimited by default.
+    ///
+    /// See [`globstar depth`](super::glob#globstar-depth).
+    max_globstar_depth: Option<usize>,
+    /// Expand a leading `~` or `~user` to the home directory returned by the function, which takes the user name (empty for `~`).
+    ///
+    /// See [`tilde expansion`](super::glob#tilde-expansion).
+    expand_tilde: Option<&dyn Fn(&str) -> Option<String>>,
     #[builder(default)] ext: GlobExtConfig,
 ) -> Hir {
     let pattern_separator = pattern_separator.unwrap_or(separator);
 
+    let mut hirs = Vec::new();
+    let pattern = match expand_tilde {
+        Some(home_dir) => match split_tilde(pattern, pattern_separator, home_dir) {
+            Some((home, pattern)) => {
+                hirs.push(literal(&home, case_insensitive));
+                pattern
+            }
+            None => pattern,
+        },
+        None => pattern,
+    };
+
     // Desugar
     let pattern = ext.desugar(pattern, pattern_separator);
 
     let mut lex = WildcardPathToken::lexer(&pattern);
-    let mut hirs = Vec::new();
-    let mut surrounding_handler =
-        surrounding_wildcard_as_anchor.then(|| SurroundingWildcardHandler::new(pattern_separator));
+    let mut surrounding_handler = surrounding_wildcard_as_anchor
+        .then(|| SurroundingWildcardHandler::new(pattern_separator, Some(separator), anchor));
     while let Some(Ok(token)) = lex.next() {
         if let Some(h) = &mut surrounding_handler {
             if h.skip(token, &mut hirs, &lex) {
@@ -621,18 +936,13 @@ pub fn parse_wildcard_path(
                 greedy: true,
                 sub: separator.any_byte_except().into(),
             }),
-            WildcardPathToken::GlobStar => Hir::repetition(Repetition {
-                min: 0,
-                max: None,
-                greedy: true,
-                sub: Hir::dot(Dot::AnyByte).into(),
-            }),
+            WildcardPathToken::GlobStar => glob_star(separator, max_globstar_depth),
             WildcardPathToken::SepUnix if pattern_separator.is_unix_or_any() => separator.literal(),
             WildcardPathToken::SepWin if pattern_separator.is_windows_or_any() => {
                 separator.literal()
             }
             WildcardPathToken::Text | WildcardPathToken::SepUnix | WildcardPathToken::SepWin => {
-                Hir::literal(lex.slice().as_bytes())
+                literal(lex.slice(), case_insensitive)
             }
         });
     }
@@ -641,7 +951,57 @@ pub fn parse_wildcard_path(
         h.insert_anchors(&mut hirs);
     }
 
-    Hir::concat(hirs)
+    anchor.anchor(Hir::concat(hirs))
+}
+
+/// Wildcard path glob syntax flavor used by [IbEverythingExt](https://github.com/Chaoses-Ib/IbEverythingExt),
+/// i.e. [`parse_wildcard_path`] with [`GlobExtConfig::new_ev()`] for the current OS.
+///
+/// Equivalent to:
+/// ```ignore
+/// parse_wildcard_path()
+///     .separator(PathSeparator::Os)
+///     .ext(GlobExtConfig::new_ev())
+///     .call(pattern)
+/// ```
+/// [`GlobExtConfig::new_ev()`] treats the [complement separator](PathSeparator::os_complement()) as a glob star,
+/// so it is only consistent with [`PathSeparator::Os`] as both the pattern and haystack separators,
+/// which is easy to get wrong when assembling the pieces,
+/// e.g. with `separator` set to [`PathSeparator::Windows`] on Unix, every `\` in the pattern would be desugared to `**\`.
+/// [Surrounding wildcards](super::glob#surrounding-wildcards-as-anchors) are treated as anchors,
+/// and the pattern is case-sensitive, leaving case insensitivity to the matcher, e.g. [`IbMatcher`](crate::matcher::IbMatcher).
+///
+/// See [anchors in file paths](super::glob#anchors-in-file-paths) for the look matcher the regex engine may need.
+///
+/// ## Example
+/// ```
+/// use std::path::MAIN_SEPARATOR;
+/// use ib_matcher::{regex::lita::Regex, syntax::glob::parse_ev};
+///
+/// let complement = if MAIN_SEPARATOR == '/' { '\\' } else { '/' };
+/// let path = format!("foo{MAIN_SEPARATOR}bar{MAIN_SEPARATOR}baz.txt");
+///
+/// // `//` (or `\\`) as `*/**`
+/// let re = Regex::builder().build_from_hir(parse_ev("foo//baz")).unwrap();
+/// assert!(re.is_match(&path));
+/// assert!(re.is_match("foobaz") == false);
+///
+/// // The complement separator as `**/`
+/// let re = Regex::builder()
+///     .build_from_hir(parse_ev(&format!("foo{complement}ba")))
+///     .unwrap();
+/// assert!(re.is_match(&path));
+///
+/// // Surrounding wildcards as anchors
+/// let re = Regex::builder().build_from_hir(parse_ev("*.txt")).unwrap();
+/// assert!(re.is_match(&path));
+/// assert!(re.is_match(&format!("{path}.bak")) == false);
+/// ```
+pub fn parse_ev(pattern: &str) -> Hir {
+    parse_wildcard_path()
+        .separator(PathSeparator::Os)
+        .ext(GlobExtConfig::new_ev())
+        .call(pattern)
 }
 
 /// See [`parse_glob_path`].
@@ -655,8 +1015,8 @@ pub enum GlobPathToken {
     #[token("*")]
     Star,
 
-    /// `[...]`.
-    #[regex(r"\[[^\]]+\]\]?")]
+    /// `[...]`, see [`character classes`](super::glob#character-classes).
+    #[token("[", lex_class)]
     Class,
 
     /// Equivalent to `.*`.
@@ -675,6 +1035,8 @@ pub enum GlobPathToken {
 }
 
 /// glob path syntax flavor, including `?`, `*`, `[]` and `**`.
+///
+/// Invalid `[]` are treated as literal characters, see [error behavior](super::glob#error-behavior).
 #[builder]
 pub fn parse_glob_path(
     #[builder(finish_fn)] pattern: &str,
@@ -686,29 +1048,166 @@ pub fn parse_glob_path(
     ///
     /// Only have effect on `?` and `*`.
     separator: PathSeparator,
+    /// See [`anchor modes`](super::glob#anchor-modes).
+    #[builder(default)]
+    anchor: AnchorMode,
     /// See [`surrounding wildcards as anchors`](super::glob#surrounding-wildcards-as-anchors).
     #[builder(default = true)]
     surrounding_wildcard_as_anchor: bool,
+    /// See [`case insensitivity`](super::glob#case-insensitivity).
+    #[builder(default)]
+    case_insensitive: bool,
+    /// The maximum number of separators `**` can match. Unlimited by default.
+    ///
+    /// See [`globstar depth`](super::glob#globstar-depth).
+    max_globstar_depth: Option<usize>,
+    /// See [`extended glob`](super::glob#extended-glob).
+    #[builder(default)]
+    extglob: bool,
     #[builder(default)] ext: GlobExtConfig,
 ) -> Hir {
+    match glob_path(
+        pattern,
+        pattern_separator,
+        separator,
+        anchor,
+        surrounding_wildcard_as_anchor,
+        case_insensitive,
+        max_globstar_depth,
+        extglob,
+        ext,
+        false,
+    ) {
+        Ok(hir) => hir,
+        Err(_) => unreachable!(),
+    }
+}
+
+/// [`parse_glob_path`], but returns an error if any `[]` is invalid (or any `!(...)` can't be expressed with [`extglob`](TryParseGlobPathBuilder::extglob))
+/// instead of treating it as literal characters.
+///
+/// See [error behavior](super::glob#error-behavior).
+#[builder]
+pub fn try_parse_glob_path(
+    #[builder(finish_fn)] pattern: &str,
+    /// The separator used in the pattern. Can be different from the one used in the haystacks to be matched.
+    ///
+    /// Defaults to the same as `separator`. You may want to use [`PathSeparator::Any`] instead.
+    pattern_separator: Option<PathSeparator>,
+    /// The path separator used in the haystacks to be matched.
+    ///
+    /// Only have effect on `?` and `*`.
+    separator: PathSeparator,
+    /// See [`anchor modes`](super::glob#anchor-modes).
+    #[builder(default)]
+    anchor: AnchorMode,
+    /// See [`surrounding wildcards as anchors`](super::glob#surrounding-wildcards-as-anchors).
+    #[builder(default = true)]
+    surrounding_wildcard_as_anchor: bool,
+    /// See [`case insensitivity`](super::glob#case-insensitivity).
+    #[builder(default)]
+    case_insensitive: bool,
+    /// The maximum number of separators `**` can match. Unlimited by default.
+    ///
+    /// See [`globstar depth`](super::glob#globstar-depth).
+    max_globstar_depth: Option<usize>,
+    /// See [`extended glob`](super::glob#extended-glob).
+    #[builder(default)]
+    extglob: bool,
+    #[builder(default)] ext: GlobExtConfig,
+) -> Result<Hir, GlobParseError> {
+    glob_path(
+        pattern,
+        pattern_separator,
+        separator,
+        anchor,
+        surrounding_wildcard_as_anchor,
+        case_insensitive,
+        max_globstar_depth,
+        extglob,
+        ext,
+        true,
+    )
+}
+
+/// - `strict`: Return an error on invalid `[]` and inexpressible `!(...)` instead of treating them as literal characters.
+#[allow(clippy::too_many_arguments)]
+fn glob_path(
+    pattern: &str,
+    pattern_separator: Option<PathSeparator>,
+    separator: PathSeparator,
+    anchor: AnchorMode,
+    surrounding_wildcard_as_anchor: bool,
+    case_insensitive: bool,
+    max_globstar_depth: Option<usize>,
+    extglob: bool,
+    ext: GlobExtConfig,
+    strict: bool,
+) -> Result<Hir, GlobParseError> {
     let pattern_separator = pattern_separator.unwrap_or(separator);
 
     // Desugar
     let pattern = ext.desugar(pattern, pattern_separator);
 
+    let mut surrounding_handler = surrounding_wildcard_as_anchor
+        .then(|| SurroundingWildcardHandler::new(pattern_separator, Some(separator), anchor));
+    let mut translator = GlobPathTranslator {
+        separator,
+        pattern_separator,
+        case_insensitive,
+        max_globstar_depth,
+        parser: ParserBuilder::new()
+            .unicode(false)
+            .utf8(false)
+            .case_insensitive(case_insensitive)
+            .build(),
+    };
+    if strict {
+        translator.validate_classes(&pattern)?;
+    }
+    if extglob {
+        return Ok(anchor.anchor(extglob::parse(
+            &pattern,
+            &mut translator,
+            surrounding_handler,
+            strict,
+        )?));
+    }
+
     let mut lex = GlobPathToken::lexer(&pattern);
     let mut hirs = Vec::new();
-    let mut surrounding_handler =
-        surrounding_wildcard_as_anchor.then(|| SurroundingWildcardHandler::new(pattern_separator));
-    let mut parser = ParserBuilder::new().unicode(false).utf8(false).build();
-    while let Some(Ok(token)) = lex.next() {
+    while let Some(token) = lex.next() {
+        // e.g. `]` and invalid `[`
+        let token = token.unwrap_or(GlobPathToken::Text);
         if let Some(h) = &mut surrounding_handler {
             if h.skip(token, &mut hirs, &lex) {
                 continue;
             }
         }
 
-        hirs.push(match token {
+        hirs.push(translator.translate(token, lex.slice()));
+    }
+
+    if let Some(h) = surrounding_handler {
+        h.insert_anchors(&mut hirs);
+    }
+
+    Ok(anchor.anchor(Hir::concat(hirs)))
+}
+
+struct GlobPathTranslator {
+    separator: PathSeparator,
+    pattern_separator: PathSeparator,
+    case_insensitive: bool,
+    max_globstar_depth: Option<usize>,
+    parser: regex_syntax::Parser,
+}
+
+impl GlobPathTranslator {
+    fn translate(&mut self, token: GlobPathToken, s: &str) -> Hir {
+        let separator = self.separator;
+        let pattern_separator = self.pattern_separator;
+        match token {
             GlobPathToken::Any => separator.any_char_except(),
             GlobPathToken::Star => Hir::repetition(Repetition {
                 min: 0,
@@ -716,43 +1215,243 @@ pub fn parse_glob_path(
                 greedy: true,
                 sub: separator.any_byte_except().into(),
             }),
-            GlobPathToken::GlobStar => Hir::repetition(Repetition {
-                min: 0,
-                max: None,
-                greedy: true,
-                sub: Hir::dot(Dot::AnyByte).into(),
-            }),
-            GlobPathToken::Class => {
-                let s = lex.slice();
-                match s {
-                    "[[]" => Hir::literal("[".as_bytes()),
-                    // "[!]" => Hir::literal("!".as_bytes()),
-                    _ => {
-                        // Life is short
-                        match parser.parse(&s.replace("[!", "[^").replace(r"\", r"\\")) {
-                            Ok(hir) => hir,
-                            Err(_e) => {
-                                #[cfg(test)]
-                                println!("{_e}");
-                                Hir::literal(s.as_bytes())
-                            }
-                        }
-                    }
-                }
-            }
+            GlobPathToken::GlobStar => glob_star(separator, self.max_globstar_depth),
+            GlobPathToken::Class => self
+                .class(s)
+                .unwrap_or_else(|_| literal(s, self.case_insensitive)),
             GlobPathToken::SepUnix if pattern_separator.is_unix_or_any() => separator.literal(),
             GlobPathToken::SepWin if pattern_separator.is_windows_or_any() => separator.literal(),
             GlobPathToken::Text | GlobPathToken::SepUnix | GlobPathToken::SepWin => {
-                Hir::literal(lex.slice().as_bytes())
+                literal(s, self.case_insensitive)
             }
-        });
+        }
     }
 
-    if let Some(h) = surrounding_handler {
-        h.insert_anchors(&mut hirs);
+    fn class(&self, s: &str) -> Result<Hir, GlobParseErrorKind> {
+        match s {
+            "[[]" => Ok(Hir::literal("[".as_bytes())),
+            // "[!]" => Hir::literal("!".as_bytes()),
+            // A parser can't be reused after parsing
+            _ => self
+                .parser
+                .clone()
+                .parse(&class_to_regex(s))
+                .map_err(GlobParseErrorKind::from_regex),
+        }
+    }
+
+    /// Find the first invalid `[]` in `pattern`.
+    ///
+    /// Classes are lexed the same with or without [`extglob`](ParseGlobPathBuilder::extglob),
+    /// since `[` can only start a class.
+    fn validate_classes(&self, pattern: &str) -> Result<(), GlobParseError> {
+        let mut lex = GlobPathToken::lexer(pattern);
+        while let Some(token) = lex.next() {
+            let kind = match token {
+                Ok(GlobPathToken::Class) => match self.class(lex.slice()) {
+                    Ok(_) => continue,
+                    Err(kind) => kind,
+                },
+                Err(()) if lex.slice().starts_with('[') => GlobParseErrorKind::UnclosedClass,
+                // `]`
+                _ => continue,
+            };
+            return Err(GlobParseError {
+                span: lex.span(),
+                kind,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Error of [`try_parse_glob_path`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlobParseError {
+    /// The byte span of the invalid `[...]`, of the unclosed `[`, or of the inexpressible `!(...)`.
+    ///
+    /// If [`ext`](TryParseGlobPathBuilder::ext) is set, the span is in the desugared pattern.
+    pub span: Range<usize>,
+    pub kind: GlobParseErrorKind,
+}
+
+impl fmt::Display for GlobParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {:?}", self.kind, self.span)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GlobParseError {}
+
+/// See [`GlobParseError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GlobParseErrorKind {
+    /// `[` without a closing `]`, e.g. `a[b` and `a[!]`.
+    UnclosedClass,
+    /// A range whose start is greater than its end, or whose bound is a POSIX class, e.g. `[z-a]`.
+    InvalidRange,
+    /// `[` in a class that doesn't start a POSIX class, e.g. `[[b]`.
+    NestedClass,
+    /// Other invalid classes.
+    InvalidClass,
+    /// `!(...)` with a non-literal alternative in [extended glob](super::glob#extended-glob), e.g. `!(*.txt)`.
+    NonLiteralNegation,
+}
+
+impl GlobParseErrorKind {
+    fn from_regex(e: regex_syntax::Error) -> Self {
+        use regex_syntax::ast::ErrorKind;
+
+        match e {
+            regex_syntax::Error::Parse(e) => match e.kind() {
+                ErrorKind::ClassRangeInvalid | ErrorKind::ClassRangeLiteral => Self::InvalidRange,
+                ErrorKind::ClassUnclosed => Self::NestedClass,
+                _ => Self::InvalidClass,
+            },
+            _ => Self::InvalidClass,
+        }
+    }
+}
+
+impl fmt::Display for GlobParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnclosedClass => "unclosed character class",
+            Self::InvalidRange => "invalid range in character class",
+            Self::NestedClass => "nested character class",
+            Self::InvalidClass => "invalid character class",
+            Self::NonLiteralNegation => "negation of non-literal pattern",
+        })
+    }
+}
+
+/// Lex the rest of `[...]` after `[`. Fails if it is not closed.
+///
+/// `]` right after `[`, `[!` or `[^` and `]` in POSIX classes like `[:alpha:]` don't close it.
+fn lex_class<'s, T: Logos<'s, Source = str>>(lex: &mut logos::Lexer<'s, T>) -> bool {
+    let rest = lex.remainder().as_bytes();
+    let mut i = 0;
+    if matches!(rest.first(), Some(b'!' | b'^')) {
+        i += 1;
+    }
+    if rest.get(i) == Some(&b']') {
+        i += 1;
+    }
+    while i < rest.len() {
+        match rest[i] {
+            b']' => {
+                lex.bump(i + 1);
+                return true;
+            }
+            b'[' if rest.get(i + 1) == Some(&b':') => {
+                i += match lex.remainder()[i + 2..].find(":]") {
+                    Some(end) => end + 4,
+                    None => 1,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    false
+}
+
+/// Translate a glob `[...]` to a regex class.
+///
+/// - `[!...]` and `[^...]` are both negated classes, e.g. `[![:digit:]]` is `[^[:digit:]]`.
+/// - `]` right after `[`, `[!` or `[^` is literal.
+/// - POSIX classes like `[:alpha:]` and `[:^alpha:]` are kept as is.
+/// - Other `[` are kept as is too, which makes the class invalid like in regex.
+/// - Other metacharacters except `-` are escaped, e.g. `[\d]` matches `\` or `d`.
+fn class_to_regex(s: &str) -> String {
+    let mut inner = &s[1..s.len() - 1];
+    let mut regex = String::with_capacity(s.len() + 4);
+    regex.push('[');
+    if let Some(rest) = inner.strip_prefix(|c| c == '!' || c == '^') {
+        regex.push('^');
+        inner = rest;
+    }
+    if let Some(rest) = inner.strip_prefix(']') {
+        regex.push_str(r"\]");
+        inner = rest;
+    }
+    while let Some(c) = inner.chars().next() {
+        if inner.starts_with("[:") {
+            if let Some(end) = inner[2..].find(":]") {
+                regex.push_str(&inner[..end + 4]);
+                inner = &inner[end + 4..];
+                continue;
+            }
+        }
+        match c {
+            '[' | '-' => regex.push(c),
+            c if regex_syntax::is_meta_character(c) => {
+                regex.push('\\');
+                regex.push(c);
+            }
+            c => regex.push(c),
+        }
+        inner = &inner[c.len_utf8()..];
+    }
+    regex.push(']');
+    regex
+}
+
+/// `**`, i.e. `.*`, or `([^/]*/){0,max_depth}[^/]*` if `max_depth` is set.
+fn glob_star(separator: PathSeparator, max_depth: Option<usize>) -> Hir {
+    let Some(max_depth) = max_depth else {
+        return Hir::repetition(Repetition {
+            min: 0,
+            max: None,
+            greedy: true,
+            sub: Hir::dot(Dot::AnyByte).into(),
+        });
+    };
+    let component = Hir::repetition(Repetition {
+        min: 0,
+        max: None,
+        greedy: true,
+        sub: separator.any_byte_except().into(),
+    });
+    // Nested `(...)?` instead of `{0,max_depth}`,
+    // so that each separator literal is compiled only once and can be folded
+    let mut hir = Hir::empty();
+    for _ in 0..max_depth {
+        hir = Hir::repetition(Repetition {
+            min: 0,
+            max: Some(1),
+            greedy: true,
+            sub: Hir::concat(vec![component.clone(), separator.literal(), hir]).into(),
+        });
     }
+    Hir::concat(vec![hir, component])
+}
+
+/// Split the leading `~user` from `pattern` and resolve it to the home directory.
+fn split_tilde<'p>(
+    pattern: &'p str,
+    pattern_separator: PathSeparator,
+    home_dir: &dyn Fn(&str) -> Option<String>,
+) -> Option<(String, &'p str)> {
+    let rest = pattern.strip_prefix('~')?;
+    let end = rest
+        .find(|c| match c {
+            '/' => pattern_separator.is_unix_or_any(),
+            '\\' => pattern_separator.is_windows_or_any(),
+            _ => false,
+        })
+        .unwrap_or(rest.len());
+    let home = home_dir(&rest[..end])?;
+    Some((home, &rest[end..]))
+}
 
-    Hir::concat(hirs)
+fn literal(s: &str, case_insensitive: bool) -> Hir {
+    if case_insensitive {
+        literal_to_ascii_case_insensitive(s.as_bytes())
+    } else {
+        Hir::literal(s.as_bytes())
+    }
 }
 
 #[cfg(test)]
@@ -882,6 +1581,11 @@ mod tests {
 
         // ASCII character class
         assert!(is_match("a[[:space:]]z", "a z"));
+        assert!(is_match("a[^b]z", "acz"));
+        assert!(is_match("a[b!^]z", "a!z"));
+        assert!(is_match("a[b!^]z", "a^z"));
+        assert!(!is_match("a[!]]z", "a]z"));
+        assert!(is_match("a[!]]z", "abz"));
 
         // Escape
         assert!(is_match("a[?]z", "a?z"));
@@ -895,6 +1599,304 @@ mod tests {
         assert!(is_match("a[b", "a[bz"));
         assert!(is_match("a[[b]z", "a[[b]z"));
         assert!(is_match("a[!]z", "a[!]z"));
+        assert!(is_match("a[[b]z[c]", "a[[b]zc"));
+
+        // Multiple classes
+        assert!(is_match("a[b]z[c]", "abzc"));
+    }
+
+    #[test]
+    fn try_glob_path() {
+        let parse = |p| {
+            try_parse_glob_path()
+                .separator(PathSeparator::Windows)
+                .call(p)
+        };
+        let err = |p| parse(p).map(|_| ()).unwrap_err();
+        let error = |span, kind| GlobParseError { span, kind };
+
+        assert_eq!(
+            parse("a[b-z]z").unwrap(),
+            parse_glob_path()
+                .separator(PathSeparator::Windows)
+                .call("a[b-z]z")
+        );
+        assert!(parse("a[]]z").is_ok());
+        assert!(parse("a[[]z").is_ok());
+        assert!(parse("a]z").is_ok());
+        assert!(parse("a[[:alpha:]]z").is_ok());
+        // Multiple classes
+        assert!(parse("a[b]z[c]").is_ok());
+
+        assert_eq!(err("a[b"), error(1..2, GlobParseErrorKind::UnclosedClass));
+        assert_eq!(err("a[!]z"), error(1..2, GlobParseErrorKind::UnclosedClass));
+        assert_eq!(
+            err("a*[b]["),
+            error(5..6, GlobParseErrorKind::UnclosedClass)
+        );
+        assert_eq!(err("a[z-b]"), error(1..6, GlobParseErrorKind::InvalidRange));
+        assert_eq!(err("a[[b]z"), error(1..5, GlobParseErrorKind::NestedClass));
+        assert_eq!(
+            err("a[b]z[z-b]").to_string(),
+            "invalid range in character class at 5..10"
+        );
+
+        // extglob
+        let parse = |p| {
+            try_parse_glob_path()
+                .separator(PathSeparator::Windows)
+                .extglob(true)
+                .call(p)
+        };
+        let err = |p| parse(p).unwrap_err();
+        assert_eq!(
+            err("@(a|[b)"),
+            error(4..5, GlobParseErrorKind::UnclosedClass)
+        );
+        assert_eq!(
+            err("a!(*.txt)"),
+            error(1..9, GlobParseErrorKind::NonLiteralNegation)
+        );
+        assert_eq!(
+            err("@(a|!(b*))"),
+            error(4..9, GlobParseErrorKind::NonLiteralNegation)
+        );
+        assert!(parse("!(foo|bar)").is_ok());
+        // Unclosed groups are still literal
+        assert_eq!(
+            parse("!(a*").unwrap(),
+            parse_glob_path()
+                .separator(PathSeparator::Windows)
+                .extglob(true)
+                .call("!(a*")
+        );
+    }
+
+    #[test]
+    fn any_separator_anchors() {
+        let is_match = |p, h| {
+            Regex::builder()
+                .build_from_hir(parse_wildcard_path().separator(PathSeparator::Any).call(p))
+                .unwrap()
+                .is_match(h)
+        };
+
+        // Leading
+        assert!(is_match("*.mp4", "a/v.mp4"));
+        assert!(is_match("*.mp4", r"a\v.mp4"));
+        assert!(is_match("*.mp4", r"a\v.mp4/b"));
+        assert!(is_match("*.mp4", r"a/v.mp4\b"));
+        assert!(!is_match("*.mp4", "a/v.mp4_0.webp"));
+
+        // Trailing
+        assert!(is_match("foo*", r"a\foobar"));
+        assert!(is_match("foo*", "a/foobar"));
+        assert!(!is_match("foo*", "a/barfoo"));
+
+        // Both
+        assert!(is_match("?foo?", r"a/xfooy\b"));
+        assert!(is_match("?foo?", r"a\xfooy/b"));
+        assert!(!is_match("?foo?", "axfooy"));
+        assert!(is_match(r"?\foo*\", r"D/C\foobar/9"));
+        assert!(!is_match(r"?\foo*\", r"DC/foobar/9"));
+
+        // The same Hir with a look matcher
+        let re = Regex::builder()
+            .thompson(PathSeparator::Any.look_matcher_config())
+            .build_from_hir(
+                parse_wildcard_path()
+                    .separator(PathSeparator::Any)
+                    .call("foo*"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"a\foobar"));
+        assert!(re.is_match("a/foobar"));
+        assert!(!re.is_match("a/barfoo"));
+    }
+
+    #[test]
+    fn glob_path_posix_class() {
+        let is_match = |p: &str, h: &str| {
+            Regex::builder()
+                .build_from_hir(parse_glob_path().separator(PathSeparator::Windows).call(p))
+                .unwrap()
+                .is_match(h)
+        };
+
+        // (class, matched, unmatched)
+        for (class, yes, no) in [
+            ("alnum", "7", "_"),
+            ("alpha", "q", "7"),
+            ("ascii", "~", "ä"),
+            ("blank", "\t", "\n"),
+            ("cntrl", "\x07", "a"),
+            ("digit", "0", "a"),
+            ("graph", "!", " "),
+            ("lower", "q", "Q"),
+            ("print", " ", "\t"),
+            ("punct", ".", "a"),
+            ("space", "\n", "a"),
+            ("upper", "Q", "q"),
+            ("word", "_", "-"),
+            ("xdigit", "F", "g"),
+        ] {
+            let h = |c: &str| format!("a{c}z");
+            for p in [format!("a[[:{class}:]]z"), format!("a[x[:{class}:]]z")] {
+                assert!(is_match(&p, &h(yes)), "{p}");
+                assert!(!is_match(&p, &h(no)), "{p}");
+            }
+            for p in [
+                format!("a[[:^{class}:]]z"),
+                format!("a[![:{class}:]]z"),
+                format!("a[^[:{class}:]]z"),
+            ] {
+                assert!(!is_match(&p, &h(yes)), "{p}");
+                // Negated classes match a single byte
+                assert_eq!(is_match(&p, &h(no)), no.is_ascii(), "{p}");
+            }
+        }
+
+        assert!(is_match("a[[:alpha:][:digit:]]z", "a1z"));
+        assert!(!is_match("a[[:alpha:][:digit:]]z", "a-z"));
+    }
+
+    #[test]
+    fn extglob() {
+        let is_match = |p, h| {
+            Regex::builder()
+                .build_from_hir(
+                    parse_glob_path()
+                        .separator(PathSeparator::Windows)
+                        .extglob(true)
+                        .call(p),
+                )
+                .unwrap()
+                .is_match(h)
+        };
+
+        // Groups
+        assert!(is_match("*.@(jpg|png)", "a.png"));
+        assert!(!is_match("*.@(jpg|png)", "a.png.txt"));
+        assert!(is_match("a?(b|c)d", "ad"));
+        assert!(is_match("a?(b|c)d", "abd"));
+        assert!(!is_match("a?(b|c)d", "abcd"));
+        assert!(is_match("a*(b|c)d", "abccbd"));
+        assert!(!is_match("a+(b|c)d", "ad"));
+        assert!(is_match("a+(b|c)d", "acbd"));
+
+        // Wildcards and nested groups
+        assert!(!is_match("a@(b*|c[0-9])d", "a\\bxxd"));
+        assert!(is_match("a@(b*|c[0-9])d", "abxxd"));
+        assert!(is_match("a@(b*|c[0-9])d", "ac5d"));
+        assert!(is_match("a@(b|+(c))d", "acccd"));
+
+        // Complement
+        assert!(is_match("*\\!(foo|bar).txt", r"C:\baz.txt"));
+        assert!(is_match("*\\!(foo|bar).txt", r"C:\fo.txt"));
+        assert!(!is_match("*\\!(foo|bar).txt", r"C:\foo.txt"));
+        assert!(!is_match("*\\!(foo|bar).txt", r"C:\bar.txt"));
+        assert!(is_match("*\\!(foo|bar).txt", r"C:\foobar.txt"));
+        assert!(is_match("*\\!(foo|bar).txt", r"C:\.txt"));
+        assert!(is_match("*\\!(foo|bar).txt", r"C:\foo\x.txt"));
+        assert!(!is_match("*\\!(foo|bar).txt", r"C:\x\foo.txt"));
+        assert!(is_match("*\\!(拼音).txt", r"C:\拼.txt"));
+        assert!(!is_match("*\\!(拼音).txt", r"C:\拼音.txt"));
+
+        // Invalid groups
+        assert!(is_match("@(a|b", "@(a|b"));
+        assert!(!is_match("@(a|b", "a"));
+        assert!(is_match("a?(b", "ax(b"));
+        assert!(is_match("!(a*)", "!(a*)"));
+        assert!(!is_match("!(a*)", "a"));
+
+        // Not special without extglob
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_glob_path()
+                    .separator(PathSeparator::Windows)
+                    .call("@(a|b)"),
+            )
+            .unwrap();
+        assert!(re.is_match("@(a|b)"));
+        assert!(!re.is_match("a"));
+
+        // Case insensitive
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_glob_path()
+                    .separator(PathSeparator::Windows)
+                    .extglob(true)
+                    .case_insensitive(true)
+                    .call("*\\!(foo).TXT"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"C:\bar.txt"));
+        assert!(!re.is_match(r"C:\FOO.txt"));
+        assert!(!re.is_match(r"C:\Foo.txt"));
+
+        // Pinyin
+        let re = Regex::builder()
+            .ib(MatchConfig::builder().pinyin(Default::default()).build())
+            .build_from_hir(
+                parse_glob_path()
+                    .separator(PathSeparator::Windows)
+                    .extglob(true)
+                    .call("@(pinyin|sousuo).txt"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"C:\拼音.txt"));
+        assert!(re.is_match(r"C:\搜索.txt"));
+        assert!(!re.is_match(r"C:\拼音搜.txt"));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let is_match = |p, h| {
+            Regex::builder()
+                .build_from_hir(
+                    parse_glob_path()
+                        .separator(PathSeparator::Windows)
+                        .case_insensitive(true)
+                        .call(p),
+                )
+                .unwrap()
+                .is_match(h)
+        };
+        assert!(is_match("README", "readme"));
+        assert!(is_match("readme", r"C:\README.md"));
+        assert!(is_match("a[b]z", "aBz"));
+        assert!(is_match("a[B-D]z", "acz"));
+        assert!(!is_match("a[!b]z", "aBz"));
+        assert!(is_match("a[!b]z", "acz"));
+        assert!(is_match("a[b", "A[Bz"));
+        assert!(!is_match("é", "É"));
+
+        let re = Regex::builder()
+            .build_from_hir(parse_wildcard().case_insensitive(true).call("*.MP4"))
+            .unwrap();
+        assert!(re.is_match("v.mp4"));
+        assert!(!re.is_match("v.mp4_0.webp"));
+
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_wildcard_path()
+                    .separator(PathSeparator::Windows)
+                    .case_insensitive(true)
+                    .call(r"win*\*.EXE"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"C:\Windows\notepad.exe"));
+        assert!(re.is_match(r"C:\Windows\记事本.exe"));
+
+        // Case sensitive by default
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_wildcard_path()
+                    .separator(PathSeparator::Windows)
+                    .call(r"win*\*.EXE"),
+            )
+            .unwrap();
+        assert!(!re.is_match(r"C:\Windows\notepad.exe"));
     }
 
     #[test]
@@ -956,6 +1958,49 @@ mod tests {
         assert!(re.is_match(r"C:\学习\Xxzl\sj\8yhj"));
     }
 
+    #[test]
+    fn ev() {
+        let sep = MAIN_SEPARATOR;
+        let complement = if sep == '/' { '\\' } else { '/' };
+
+        let patterns = [
+            "foo//baz".to_string(),
+            format!("foo{complement}{complement}baz"),
+            format!("foo{complement}ba"),
+            format!("foo{sep}bar{sep}*.txt"),
+            "*.txt".to_string(),
+            "bar*".to_string(),
+        ];
+        for p in &patterns {
+            assert_eq!(
+                parse_ev(p),
+                parse_wildcard_path()
+                    .separator(PathSeparator::Os)
+                    .ext(GlobExtConfig::new_ev())
+                    .call(p)
+            );
+        }
+
+        let path = format!("foo{sep}bar{sep}baz.txt");
+        for p in &patterns[..5] {
+            let re = Regex::builder().build_from_hir(parse_ev(p)).unwrap();
+            assert!(re.is_match(&path), "{p}");
+        }
+        let re = Regex::builder()
+            .build_from_hir(parse_ev("foo//baz"))
+            .unwrap();
+        assert!(!re.is_match("foobaz"));
+        let re = Regex::builder().build_from_hir(parse_ev("bar*")).unwrap();
+        assert!(!re.is_match(&path));
+
+        let re = Regex::builder()
+            .ib(MatchConfig::builder().pinyin(Default::default()).build())
+            .build_from_hir(parse_ev(&format!("xx{complement}hj")))
+            .unwrap();
+        assert!(re.is_match(&format!("学习资料{sep}时间{sep}合集7月")));
+        assert!(!re.is_match(&format!("学习资料{sep}时间{sep}7月合集")));
+    }
+
     #[test]
     fn surrounding_wildcard_as_anchor() {
         // Leading *
@@ -1069,6 +2114,178 @@ mod tests {
         assert!(re.is_match(r"D:\DC:\$RECYCLE.BIN\9") == false);
     }
 
+    #[test]
+    fn anchor_mode() {
+        let is_match = |anchor, pattern, haystack| {
+            Regex::builder()
+                .thompson(PathSeparator::Windows.look_matcher_config())
+                .build_from_hir(
+                    parse_wildcard_path()
+                        .separator(PathSeparator::Windows)
+                        .anchor(anchor)
+                        .call(pattern),
+                )
+                .unwrap()
+                .is_match(haystack)
+        };
+
+        assert!(is_match(AnchorMode::Anywhere, "foo", r"a\xfoox"));
+        assert!(is_match(AnchorMode::Start, "foo", r"foox"));
+        assert!(!is_match(AnchorMode::Start, "foo", r"a\foo"));
+        assert!(is_match(AnchorMode::End, "foo", r"a\xfoo"));
+        assert!(!is_match(AnchorMode::End, "foo", r"foo\a"));
+        assert!(is_match(AnchorMode::Whole, "foo", r"foo"));
+        assert!(!is_match(AnchorMode::Whole, "foo", r"foox"));
+        assert!(!is_match(AnchorMode::Whole, "foo", r"a\foo"));
+
+        // Wildcards on the anchored sides are not anchors
+        assert!(is_match(AnchorMode::Start, "*.mp4", r"v.mp4_0.webp"));
+        assert!(!is_match(AnchorMode::Start, "*.mp4", r"a\v.mp4"));
+        assert!(is_match(AnchorMode::Start, "foo*", r"foobar\a"));
+        assert!(is_match(AnchorMode::End, "foo*", r"a\foobar"));
+        assert!(!is_match(AnchorMode::End, "foo*", r"foobar\a"));
+        assert!(is_match(AnchorMode::Whole, "*.mp4", r"v.mp4"));
+        assert!(!is_match(AnchorMode::Whole, "*.mp4", r"v.mp4_0.webp"));
+        assert!(!is_match(AnchorMode::Whole, "?.mp4", r"vv.mp4"));
+
+        // Component anchors still work on the other sides
+        assert!(is_match(AnchorMode::End, r"?:\foo", r"D:\C:\foo"));
+        assert!(!is_match(AnchorMode::End, r"?:\foo", r"DC:\foo"));
+        assert!(is_match(AnchorMode::Start, r"foo\?", r"foo\a\b"));
+        assert!(!is_match(AnchorMode::Start, r"foo\?", r"foo\ab"));
+
+        // Whole
+        assert!(is_match(AnchorMode::Whole, r"?:\foo*\", r"C:\foobar\"));
+        assert!(!is_match(AnchorMode::Whole, r"?:\foo*\", r"D:\C:\foobar\"));
+        assert!(!is_match(AnchorMode::Whole, r"?:\foo*\", r"C:\foobar\a"));
+
+        // Extended glob
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_glob_path()
+                    .separator(PathSeparator::Unix)
+                    .extglob(true)
+                    .anchor(AnchorMode::Whole)
+                    .call("a/@(b|c)"),
+            )
+            .unwrap();
+        assert!(re.is_match("a/c"));
+        assert!(!re.is_match("a/cd"));
+        assert!(!re.is_match("x/a/c"));
+    }
+
+    #[test]
+    fn expand_tilde() {
+        let home_dir = |user: &str| match user {
+            "" => Some(r"C:\Users\me".to_string()),
+            "alice" => Some(r"C:\Users\alice".to_string()),
+            _ => None,
+        };
+        let is_match = |pattern, haystack| {
+            Regex::builder()
+                .build_from_hir(
+                    parse_wildcard_path()
+                        .separator(PathSeparator::Windows)
+                        .expand_tilde(&home_dir)
+                        .call(pattern),
+                )
+                .unwrap()
+                .is_match(haystack)
+        };
+
+        assert!(is_match(r"~\*.md", r"C:\Users\me\README.md"));
+        assert!(!is_match(r"~\*.md", r"~\README.md"));
+        assert!(is_match(r"~", r"C:\Users\me"));
+        assert!(is_match(r"~alice\*.md", r"C:\Users\alice\README.md"));
+        assert!(!is_match(r"~alice\*.md", r"C:\Users\me\README.md"));
+
+        // Unresolved
+        assert!(is_match(r"~bob\*.md", r"D:\~bob\README.md"));
+
+        // Not at the start
+        assert!(is_match(r"a\~\*.md", r"a\~\README.md"));
+        assert!(!is_match(r"a\~\*.md", r"a\C:\Users\me\README.md"));
+
+        // Disabled by default
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_wildcard_path()
+                    .separator(PathSeparator::Windows)
+                    .call(r"~\*.md"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"~\README.md"));
+    }
+
+    #[test]
+    fn max_globstar_depth() {
+        let is_match = |depth, pattern, haystack| {
+            Regex::builder()
+                .build_from_hir(
+                    parse_wildcard_path()
+                        .separator(PathSeparator::Windows)
+                        .maybe_max_globstar_depth(depth)
+                        .call(pattern),
+                )
+                .unwrap()
+                .is_match(haystack)
+        };
+
+        assert!(is_match(None, r"a\**b", r"a\x\y\z\b"));
+        assert!(is_match(Some(0), r"a\**b", r"a\xb"));
+        assert!(!is_match(Some(0), r"a\**b", r"a\x\b"));
+        assert!(is_match(Some(2), r"a\**b", r"a\x\y\b"));
+        assert!(!is_match(Some(2), r"a\**b", r"a\x\y\z\b"));
+        // Leading and trailing `**` are anchors
+        assert!(is_match(Some(0), r"**\b", r"a\x\y\b"));
+
+        // ToChild
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_wildcard_path()
+                    .separator(PathSeparator::Windows)
+                    .max_globstar_depth(1)
+                    .ext(
+                        GlobExtConfig::builder()
+                            .two_separator_as_star(PathSeparator::Any, GlobStar::ToChild)
+                            .build(),
+                    )
+                    .call(r"a//b"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"ax\b"));
+        assert!(re.is_match(r"ax\y\zb"));
+        assert!(!re.is_match(r"ax\y\z\b"));
+        assert!(!re.is_match(r"ab"));
+
+        // With path anchors
+        let re = Regex::builder()
+            .thompson(PathSeparator::Windows.look_matcher_config())
+            .build_from_hir(
+                parse_wildcard_path()
+                    .separator(PathSeparator::Windows)
+                    .max_globstar_depth(1)
+                    .call(r"?:\**\foo*"),
+            )
+            .unwrap();
+        assert!(re.is_match(r"C:\x\foobar"));
+        assert!(re.is_match(r"D:\C:\x\foobar"));
+        assert!(re.is_match(r"C:\x\y\foobar"));
+        assert!(!re.is_match(r"C:\x\y\z\foobar"));
+
+        // glob
+        let re = Regex::builder()
+            .build_from_hir(
+                parse_glob_path()
+                    .separator(PathSeparator::Unix)
+                    .max_globstar_depth(1)
+                    .call("a/**[0-9]"),
+            )
+            .unwrap();
+        assert!(re.is_match("a/x/1"));
+        assert!(!re.is_match("a/x/y/1"));
+    }
+
     #[test]
     fn backtrack_step_original_at() {
         // https://github.com/Chaoses-Ib/IbEverythingExt/blob/a6d1e5aa106eb5595299dd0ffa263157b3cdd25e/plugin/src/search/mod.rs#L185-L230
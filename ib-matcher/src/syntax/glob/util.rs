@@ -1,6 +1,10 @@
+use alloc::{vec, vec::Vec};
+
 use regex_syntax::hir::{Hir, Look};
 
-use crate::syntax::glob::{GlobPathToken, PathSeparator, WildcardPathToken, WildcardToken};
+use crate::syntax::glob::{
+    AnchorMode, ExtGlobPathToken, GlobPathToken, PathSeparator, WildcardPathToken, WildcardToken,
+};
 
 pub(crate) enum SurroundingHandleToken {
     Any,
@@ -44,25 +48,52 @@ impl From<GlobPathToken> for SurroundingHandleToken {
     }
 }
 
+impl From<ExtGlobPathToken> for SurroundingHandleToken {
+    fn from(token: ExtGlobPathToken) -> Self {
+        match token {
+            ExtGlobPathToken::Any => Self::Any,
+            ExtGlobPathToken::Star | ExtGlobPathToken::GlobStar => Self::Star,
+            ExtGlobPathToken::SepUnix => Self::SepUnix,
+            ExtGlobPathToken::SepWin => Self::SepWin,
+            ExtGlobPathToken::Text
+            | ExtGlobPathToken::Class
+            | ExtGlobPathToken::GroupStart
+            | ExtGlobPathToken::GroupSep
+            | ExtGlobPathToken::GroupEnd => Self::Unwild,
+        }
+    }
+}
+
 pub struct SurroundingWildcardHandler {
     leading_wildcard: bool,
     leading_star: bool,
     trailing_wildcards: usize,
     trailing_star: bool,
     sep: PathSeparator,
+    /// The path separator used in the haystacks, if any.
+    separator: Option<PathSeparator>,
     seped: bool,
+    /// Wildcards on the anchored sides are not surrounding wildcards.
+    anchor: AnchorMode,
 }
 
 impl SurroundingWildcardHandler {
     /// - `pattern_separator`: No effect if no `Sep` token
-    pub fn new(pattern_separator: PathSeparator) -> Self {
+    /// - `separator`: The path separator used in the haystacks, `None` if not matching paths
+    pub fn new(
+        pattern_separator: PathSeparator,
+        separator: Option<PathSeparator>,
+        anchor: AnchorMode,
+    ) -> Self {
         Self {
             leading_wildcard: false,
             leading_star: false,
             trailing_wildcards: 0,
             trailing_star: false,
             sep: pattern_separator,
+            separator,
             seped: false,
+            anchor,
         }
     }
 }
@@ -74,10 +105,11 @@ impl SurroundingWildcardHandler {
         hirs: &mut Vec<Hir>,
         lex: &logos::Lexer<'p, impl logos::Logos<'p, Source = str>>,
     ) -> bool {
+        let separator = self.separator;
         let mut sep = || {
             // Insert StartLF if leading_wildcard
             if !self.leading_star && self.leading_wildcard {
-                hirs.insert(0, Hir::look(Look::StartLF));
+                hirs.insert(0, Self::start(separator));
                 // leading_wildcard will never be true again if hirs is not empty
             }
             self.leading_wildcard = false;
@@ -88,17 +120,21 @@ impl SurroundingWildcardHandler {
         match token.into() {
             SurroundingHandleToken::Any => {
                 // `?` is also treated as anchor, but not skipped
-                if hirs.is_empty() {
+                if hirs.is_empty() && !self.anchor.is_start() {
                     self.leading_wildcard = true;
                 }
-                self.trailing_wildcards = 1;
+                self.trailing_wildcards = if self.anchor.is_end() { 0 } else { 1 };
             }
             SurroundingHandleToken::Star => {
-                if hirs.is_empty() {
+                if hirs.is_empty() && !self.anchor.is_start() {
                     self.leading_wildcard = true;
                     self.leading_star = true;
                     return true;
                 }
+                if self.anchor.is_end() {
+                    self.trailing_wildcards = 0;
+                    return false;
+                }
                 self.trailing_wildcards += 1;
                 if lex.remainder().is_empty() {
                     self.trailing_star = true;
@@ -114,9 +150,41 @@ impl SurroundingWildcardHandler {
         false
     }
 
+    /// `StartLF`, or `(?:^|[/\\])` for [`PathSeparator::Any`], since a look matcher only supports one line terminator.
+    fn start(separator: Option<PathSeparator>) -> Hir {
+        match separator {
+            Some(PathSeparator::Any) => {
+                Hir::alternation(vec![Hir::look(Look::Start), PathSeparator::Any.literal()])
+            }
+            _ => Hir::look(Look::StartLF),
+        }
+    }
+
+    /// `EndLF`, or `(?:$|[/\\])` for [`PathSeparator::Any`].
+    fn end(separator: Option<PathSeparator>) -> Hir {
+        match separator {
+            Some(PathSeparator::Any) => {
+                Hir::alternation(vec![Hir::look(Look::End), PathSeparator::Any.literal()])
+            }
+            _ => Hir::look(Look::EndLF),
+        }
+    }
+
     fn insert_anchors_common(&self, hirs: &mut Vec<Hir>, sep: bool) {
-        let start = || Hir::look(if sep { Look::StartLF } else { Look::Start });
-        let end = || Hir::look(if sep { Look::EndLF } else { Look::End });
+        let start = || {
+            if sep {
+                Self::start(self.separator)
+            } else {
+                Hir::look(Look::Start)
+            }
+        };
+        let end = || {
+            if sep {
+                Self::end(self.separator)
+            } else {
+                Hir::look(Look::End)
+            }
+        };
 
         // Unanchored search has implicit leading and trailing star.
         // We cancel them by anchors.
@@ -0,0 +1,113 @@
+use regex_syntax::hir::{Hir, HirKind};
+
+/// Replace the sub-expression of the capture group `index` with `f(sub)`.
+///
+/// If there is no such group, `hir` is returned as is.
+pub fn map_capture(hir: Hir, index: u32, f: &mut impl FnMut(Hir) -> Hir) -> Hir {
+    if hir.properties().explicit_captures_len() == 0 {
+        return hir;
+    }
+    match hir.kind() {
+        HirKind::Empty | HirKind::Literal(_) | HirKind::Class(_) | HirKind::Look(_) => hir,
+        HirKind::Repetition(_) => {
+            let mut repetition = match hir.into_kind() {
+                HirKind::Repetition(repetition) => repetition,
+                _ => unreachable!(),
+            };
+            repetition.sub = map_capture(*repetition.sub, index, f).into();
+            Hir::repetition(repetition)
+        }
+        HirKind::Capture(_) => {
+            let mut capture = match hir.into_kind() {
+                HirKind::Capture(capture) => capture,
+                _ => unreachable!(),
+            };
+            capture.sub = if capture.index == index {
+                f(*capture.sub)
+            } else {
+                map_capture(*capture.sub, index, f)
+            }
+            .into();
+            Hir::capture(capture)
+        }
+        HirKind::Concat(_) => {
+            let subs = match hir.into_kind() {
+                HirKind::Concat(subs) => subs,
+                _ => unreachable!(),
+            }
+            .into_iter()
+            .map(|sub| map_capture(sub, index, f))
+            .collect();
+            Hir::concat(subs)
+        }
+        HirKind::Alternation(_) => {
+            let subs = match hir.into_kind() {
+                HirKind::Alternation(subs) => subs,
+                _ => unreachable!(),
+            }
+            .into_iter()
+            .map(|sub| map_capture(sub, index, f))
+            .collect();
+            Hir::alternation(subs)
+        }
+    }
+}
+
+/// Whether the capture group `index` matches exactly once whenever `hir` matches,
+/// i.e. it is not optional (e.g. in an alternation) or in a repetition.
+///
+/// Returns `None` if there is no such group.
+pub fn capture_matches_once(hir: &Hir, index: u32) -> Option<bool> {
+    if hir.properties().explicit_captures_len() == 0 {
+        return None;
+    }
+    match hir.kind() {
+        HirKind::Empty | HirKind::Literal(_) | HirKind::Class(_) | HirKind::Look(_) => None,
+        HirKind::Repetition(repetition) => capture_matches_once(&repetition.sub, index)
+            .map(|once| once && repetition.min == 1 && repetition.max == Some(1)),
+        HirKind::Capture(capture) if capture.index == index => Some(true),
+        HirKind::Capture(capture) => capture_matches_once(&capture.sub, index),
+        HirKind::Concat(subs) => subs.iter().find_map(|sub| capture_matches_once(sub, index)),
+        HirKind::Alternation(subs) => subs
+            .iter()
+            .find_map(|sub| capture_matches_once(sub, index))
+            .map(|once| once && subs.len() == 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex_syntax::{hir::Look, parse};
+
+    use super::*;
+
+    #[test]
+    fn map_capture_test() {
+        let mut f = |sub| Hir::concat(vec![Hir::look(Look::Start), sub]);
+        assert_eq!(
+            map_capture(parse(r"a(b)(c)").unwrap(), 2, &mut f),
+            parse(r"a(b)(^c)").unwrap()
+        );
+        assert_eq!(
+            map_capture(parse(r"a(b(c))").unwrap(), 2, &mut f),
+            parse(r"a(b(^c))").unwrap()
+        );
+        assert_eq!(
+            map_capture(parse(r"a(b)").unwrap(), 2, &mut f),
+            parse(r"a(b)").unwrap()
+        );
+    }
+
+    #[test]
+    fn capture_matches_once_test() {
+        let once = |pattern, index| capture_matches_once(&parse(pattern).unwrap(), index);
+        assert_eq!(once(r"a(b)(c)", 2), Some(true));
+        assert_eq!(once(r"a(b(c))", 2), Some(true));
+        assert_eq!(once(r"a(b)", 2), None);
+        assert_eq!(once(r"a(b)?", 1), Some(false));
+        assert_eq!(once(r"a(b)*", 1), Some(false));
+        assert_eq!(once(r"(?:a(b))+", 1), Some(false));
+        assert_eq!(once(r"a(b)|c", 1), Some(false));
+        assert_eq!(once(r"(?:a|c)(b)", 1), Some(true));
+    }
+}
@@ -1,4 +1,5 @@
-use std::iter;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::iter;
 
 use regex_syntax::{
     hir::{Hir, HirKind},
@@ -1,5 +1,6 @@
 pub use regex_syntax::hir::*;
 
+pub mod capture;
 pub mod case;
 pub mod fold;
 pub mod literal;
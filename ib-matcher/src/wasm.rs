@@ -0,0 +1,182 @@
+/*!
+# WebAssembly bindings
+
+[wasm-bindgen](https://docs.rs/wasm-bindgen) bindings for matching JavaScript strings, enabled by the `wasm` feature.
+
+JavaScript strings are UTF-16, so haystacks are matched as [`U16Str`] and
+all offsets are in UTF-16 code units, i.e. they can be passed to `String.prototype.slice()` directly.
+Unpaired surrogates are replaced with U+FFFD when passed to Rust, which is still one code unit,
+so offsets are not affected by them.
+
+## Example
+```js
+import init, { new_matcher } from "./pkg/ib_matcher_wasm.js";
+
+await init();
+const matcher = new_matcher("pysousuo", JSON.stringify({ pinyin: { notations: "Ascii | AsciiFirstLetter" } }));
+const haystack = "😀拼音搜索";
+console.log(matcher.is_match(haystack)); // true
+const m = matcher.find(haystack);
+console.log(m.start, m.end, haystack.slice(m.start, m.end)); // 2 6 "拼音搜索"
+matcher.free();
+```
+
+See [`examples/wasm`](https://github.com/Chaoses-Ib/ib-matcher/tree/master/ib-matcher/examples/wasm) for a complete crate.
+
+## Options
+The options of [`new_matcher()`] are a [`MatchConfigSpec`] in JSON, i.e. the same settings as in Rust, e.g.
+`{ "starts_with": true, "pinyin": { "notations": "Ascii | AsciiFirstLetter" }, "romaji": {} }`.
+Pinyin and romaji matching require the `pinyin` and `romaji` features.
+
+An empty string is the same as `{}`, i.e. plain case-insensitive matching only.
+Unlike [`MatchConfig::from_spec()`], the romanizer is created once and shared by all the matchers.
+
+## Binary size
+Only the languages enabled by features are compiled in, so depend on ib-matcher with
+`default-features = false` and pick the features you need, e.g. `features = ["wasm", "pinyin"]`.
+
+The romaji dictionary is the largest part of the binary (several MiB even compressed),
+so leave out `romaji` unless you need it.
+Note that `romaji-compress-*` require `std` and decompress the dictionary at runtime,
+which trades startup time for size.
+
+The usual size options also apply, e.g. `opt-level = "z"`, `lto = true` and
+[`wasm-opt`](https://github.com/WebAssembly/binaryen).
+*/
+#[cfg(feature = "romaji")]
+use std::sync::OnceLock;
+
+use wasm_bindgen::prelude::*;
+use widestring::{U16Str, U16String};
+
+use crate::matcher::{IbMatcher, MatchConfig, MatchConfigSpec};
+#[cfg(feature = "romaji")]
+use crate::romaji::HepburnRomanizer;
+
+#[cfg(feature = "romaji")]
+fn romanizer() -> &'static HepburnRomanizer {
+    static ROMANIZER: OnceLock<HepburnRomanizer> = OnceLock::new();
+    ROMANIZER.get_or_init(HepburnRomanizer::default)
+}
+
+/// [`new_matcher()`] without [`JsError`], which can only be created on wasm targets.
+fn build_matcher(pattern: &str, options_json: &str) -> Result<MatcherHandle, String> {
+    let spec: Box<MatchConfigSpec> = match options_json.trim() {
+        "" => Default::default(),
+        json => serde_json::from_str(json).map_err(|e| format!("invalid options: {e}"))?,
+    };
+
+    // The config borrows from `spec`, which is boxed and kept alive by the handle
+    let spec_ref: &'static MatchConfigSpec = unsafe { &*(&*spec as *const MatchConfigSpec) };
+    let config = MatchConfig::from_spec_with(
+        spec_ref,
+        #[cfg(feature = "romaji")]
+        Some(romanizer()),
+    )
+    .map_err(|e| format!("invalid options: {e}"))?;
+
+    let pattern = U16String::from_str(pattern);
+    Ok(MatcherHandle {
+        matcher: IbMatcher::with_config(pattern.as_ustr(), config),
+        _spec: spec,
+    })
+}
+
+/// Create a matcher of `pattern`. See [options](self#options) for `options_json`.
+#[wasm_bindgen]
+pub fn new_matcher(pattern: &str, options_json: &str) -> Result<MatcherHandle, JsError> {
+    build_matcher(pattern, options_json).map_err(|e| JsError::new(&e))
+}
+
+/// A matcher created by [`new_matcher()`].
+#[wasm_bindgen]
+pub struct MatcherHandle {
+    /// Declared before `_spec` to be dropped before it.
+    matcher: IbMatcher<'static, U16Str>,
+    /// [`IbMatcher`] may have references to it, see [`MatchConfig::from_spec()`].
+    _spec: Box<MatchConfigSpec>,
+}
+
+#[wasm_bindgen]
+impl MatcherHandle {
+    pub fn is_match(&self, haystack: &str) -> bool {
+        self.matcher
+            .is_match(U16String::from_str(haystack).as_ustr())
+    }
+
+    /// Returns the first match in UTF-16 code units.
+    pub fn find(&self, haystack: &str) -> Option<MatchRange> {
+        self.matcher
+            .find(U16String::from_str(haystack).as_ustr())
+            .map(|m| MatchRange {
+                start: m.start(),
+                end: m.end(),
+            })
+    }
+}
+
+/// A match in UTF-16 code units, i.e. `haystack.slice(start, end)` in JavaScript.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, options_json: &str, haystack: &str) -> Option<MatchRange> {
+        build_matcher(pattern, options_json).unwrap().find(haystack)
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(
+            find("ab", "", "😀aB"),
+            Some(MatchRange { start: 2, end: 4 })
+        );
+        assert_eq!(find("ab", "{}", "ba"), None);
+        let matcher = build_matcher("ab", r#"{"starts_with": true}"#).unwrap();
+        assert!(matcher.is_match("abc"));
+        assert!(!matcher.is_match("cab"));
+    }
+
+    #[cfg(feature = "pinyin")]
+    #[test]
+    fn pinyin() {
+        let options = r#"{"pinyin": {"notations": "Ascii | AsciiFirstLetter"}}"#;
+        assert_eq!(
+            find("pinyin", options, "a拼音"),
+            Some(MatchRange { start: 1, end: 3 })
+        );
+        assert_eq!(
+            find("py", options, "😀拼音"),
+            Some(MatchRange { start: 2, end: 4 })
+        );
+        assert_eq!(find("pinyin", "", "拼音"), None);
+    }
+
+    #[cfg(feature = "romaji")]
+    #[test]
+    fn romaji() {
+        assert_eq!(
+            find("sushi", r#"{"romaji": {}}"#, "😀すし"),
+            Some(MatchRange { start: 2, end: 4 })
+        );
+    }
+
+    #[test]
+    fn options() {
+        assert!(build_matcher("a", "{").is_err());
+        assert!(build_matcher("a", r#"{"unknown": true}"#).is_err());
+        // The same names as `MatchConfigSpec`
+        assert!(build_matcher("a", r#"{"startsWith": true}"#).is_err());
+        assert!(build_matcher("a", r#"{"mix_lang": true, "fullwidth_ascii": true}"#).is_ok());
+        #[cfg(feature = "pinyin")]
+        assert!(build_matcher("a", r#"{"pinyin": {"notations": "Unknown"}}"#).is_err());
+        #[cfg(not(feature = "romaji"))]
+        assert!(build_matcher("a", r#"{"romaji": {}}"#).is_err());
+    }
+}
@@ -56,4 +56,9 @@ typedef uint32_t PinyinNotation;
 /// 自然码双拼
 ///
 /// See [自然码](https://zh.wikipedia.org/zh-cn/自然码) for details.
-#define PINYIN_NOTATION_DiletterZrm 0x200
\ No newline at end of file
+#define PINYIN_NOTATION_DiletterZrm 0x200
+
+/// 注音符号（不带声调）
+///
+/// See [Bopomofo](https://en.wikipedia.org/wiki/Bopomofo) for details.
+#define PINYIN_NOTATION_Zhuyin 0x400
\ No newline at end of file
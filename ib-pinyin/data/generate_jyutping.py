@@ -0,0 +1,60 @@
+import re
+
+# char -> toneless jyutpings, in the order of Unihan
+jyutping_tables = {}
+
+def read_unihan(f):
+    while line := f.readline():
+        if not line.startswith('U+'):
+            continue
+        code, field, value = line[:-1].split('\t')
+        if field != 'kCantonese':
+            continue
+        code = int(code[2:], 16)
+        # `MAX_RANGE` of pinyin starts from 0x3007
+        if code < 0x3007:
+            continue
+        jyutpings = []
+        for jyutping in value.split(' '):
+            jyutping = re.sub('[1-6]$', '', jyutping)
+            if jyutping not in jyutpings:
+                jyutpings.append(jyutping)
+        jyutping_tables[code] = jyutpings
+
+def output():
+    jyutpings = sorted({jp for jps in jyutping_tables.values() for jp in jps})
+    index = {jp: i for i, jp in enumerate(jyutpings)}
+
+    jyutping_code = f'pub(super) const JYUTPINGS: [&str; {len(jyutpings)}] = [\n'
+    jyutping_code += ','.join(f'"{jp}"' for jp in jyutpings)
+    jyutping_code += '];\n'
+
+    combinations = sorted({tuple(index[jp] for jp in jps) for jps in jyutping_tables.values() if len(jps) > 1})
+    comb_index = {comb: len(jyutpings) + i for i, comb in enumerate(combinations)}
+    max_comb = max(len(comb) for comb in combinations)
+    comb_code = f'''pub(super) const JYUTPING_COMBINATION_LEN: usize = {max_comb};
+
+pub(super) static JYUTPING_COMBINATIONS: [JyutpingCombination; {len(combinations)}] = [\n'''
+    comb_code += ','.join(f'[{",".join(map(str, comb))}{",F" * (max_comb - len(comb))}]' for comb in combinations)
+    comb_code += '];\n'
+
+    table_code = f'pub(super) static JYUTPING_TABLE: [(char, u16); {len(jyutping_tables)}] = [\n'
+    table_code += ','.join(
+        f"('\\u{{{code:X}}}',{index[jps[0]] if len(jps) == 1 else comb_index[tuple(index[jp] for jp in jps)]})"
+        for code, jps in sorted(jyutping_tables.items()))
+    table_code += '];\n'
+
+    return f'''#![cfg_attr(rustfmt, rustfmt_skip)]
+
+use super::JyutpingCombination;
+
+const F: u16 = u16::MAX;
+
+{jyutping_code}
+{comb_code}
+{table_code}'''
+
+with open('data/Unihan_Readings.txt', encoding='utf-8') as f:
+    read_unihan(f)
+    with open('src/pinyin/jyutping/data.rs', 'w', encoding='utf-8') as f:
+        f.write(output())
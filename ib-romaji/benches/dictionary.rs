@@ -0,0 +1,59 @@
+//! Build time and throughput of romanizers with different word lists.
+//!
+//! See [`ib_romaji::bench`] for the environment variables:
+//! - `IB_ROMAJI_BENCH_WORDS`: A word list in the format of [`ib_romaji::bench::parse_words()`],
+//!   benchmarked as the `custom` dictionary in addition to the embedded one and no words.
+//! - `IB_ROMAJI_BENCH_CORPUS`: The text to romanize. Default: a built-in sample.
+use std::{env, fs, hint::black_box};
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use ib_romaji::{HepburnRomanizer, bench};
+
+static SAMPLE_CORPUS: &str = "\
+日本語の文章をローマ字に変換します。今日は良い天気ですね。\
+この素晴らしい世界に祝福を！東京都渋谷区で待ち合わせ。\
+カタカナのコンピューターとひらがなのことば、それから漢字。";
+
+fn read_env(name: &str) -> Option<String> {
+    let path = env::var_os(name)?;
+    Some(fs::read_to_string(&path).unwrap_or_else(|e| panic!("{name}={path:?}: {e}")))
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let custom = read_env("IB_ROMAJI_BENCH_WORDS");
+    let custom = custom.as_deref().map(bench::parse_words);
+    let corpus = read_env("IB_ROMAJI_BENCH_CORPUS");
+    let corpus = corpus.as_deref().unwrap_or(SAMPLE_CORPUS);
+
+    let build = |dictionary: &str| match dictionary {
+        "embedded" => HepburnRomanizer::default(),
+        "none" => HepburnRomanizer::builder().kana(true).kanji(true).build(),
+        "custom" => HepburnRomanizer::build_from_words(custom.clone().unwrap()),
+        _ => unreachable!(),
+    };
+    let dictionaries: &[&str] = match custom {
+        Some(_) => &["embedded", "none", "custom"],
+        None => &["embedded", "none"],
+    };
+
+    let mut group = c.benchmark_group("build");
+    for &dictionary in dictionaries {
+        group.bench_function(dictionary, |b| b.iter(|| build(black_box(dictionary))));
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("romanize_corpus");
+    group.throughput(Throughput::Bytes(corpus.len() as u64));
+    for &dictionary in dictionaries {
+        let romanizer = build(dictionary);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(dictionary),
+            corpus,
+            |b, corpus| b.iter(|| bench::romanize_corpus(&romanizer, black_box(corpus))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
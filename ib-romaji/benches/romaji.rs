@@ -16,6 +16,19 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("build_word", |b| {
         b.iter(|| HepburnRomanizer::builder().word(true).build())
     });
+
+    let data = HepburnRomanizer::default();
+    let near_miss = format!("{}a", "日".repeat(50));
+    for (name, s) in [
+        ("japanese", "この素晴らしい世界に祝福を"),
+        ("mixed", "この素晴らしい世界に祝福をiPhone"),
+        ("latin", "iPhoneこの素晴らしい世界に祝福を"),
+        ("near_miss", near_miss.as_str()),
+    ] {
+        c.bench_function(&format!("is_romanizable_{name}"), |b| {
+            b.iter(|| data.is_romanizable(black_box(s)))
+        });
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
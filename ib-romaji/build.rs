@@ -0,0 +1,101 @@
+//! Generates the data derived from the romaji tables in `src/data`:
+//! - `kunrei`: the Kunrei-shiki tables, converted from the Hepburn ones by `hepburn_to_kunrei()`.
+//! - `compress-romajis`: the zstd-compressed blobs, see `src/data/compressed.rs`.
+#[cfg(feature = "kunrei")]
+extern crate alloc;
+
+#[cfg(feature = "kunrei")]
+#[allow(dead_code)]
+#[path = "src/convert/kunrei.rs"]
+mod kunrei;
+
+#[cfg(any(feature = "kunrei", feature = "compress-romajis"))]
+use std::{env, fs, path::PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(any(feature = "kunrei", feature = "compress-romajis"))]
+    generate();
+}
+
+#[cfg(any(feature = "kunrei", feature = "compress-romajis"))]
+#[cfg_attr(not(feature = "compress-romajis"), allow(unused_variables))]
+fn generate() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let tables = [
+        ("kanji".to_string(), read("src/data/kanjis.rs"), true),
+        ("word".to_string(), read("src/data/word_kanas.rs"), false),
+    ];
+
+    #[cfg(feature = "kunrei")]
+    let tables: Vec<_> = {
+        println!("cargo:rerun-if-changed=src/convert/kunrei.rs");
+        tables
+            .into_iter()
+            .flat_map(|(name, src, kanji)| {
+                let kunrei = convert_literals(&src, kunrei::hepburn_to_kunrei);
+                fs::write(out_dir.join(format!("kunrei_{name}_romajis.rs")), &kunrei).unwrap();
+                [
+                    (format!("kunrei_{name}"), kunrei, kanji),
+                    (name, src, kanji),
+                ]
+            })
+            .collect()
+    };
+
+    #[cfg(feature = "compress-romajis")]
+    for (name, src, kanji) in &tables {
+        let blob = zstd::encode_all(to_lines(src, *kanji).as_bytes(), 22).unwrap();
+        fs::write(out_dir.join(format!("{name}_romajis.zst")), blob).unwrap();
+    }
+}
+
+#[cfg(any(feature = "kunrei", feature = "compress-romajis"))]
+fn read(path: &str) -> String {
+    println!("cargo:rerun-if-changed={path}");
+    fs::read_to_string(path).unwrap()
+}
+
+/// Apply `f` to the content of every string literal in `src`.
+#[cfg(feature = "kunrei")]
+fn convert_literals(src: &str, f: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+    while let Some(start) = rest.find('"') {
+        out.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        let end = rest.find('"').unwrap();
+        out.push_str(&f(&rest[..end]));
+        out.push('"');
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `'日'=>&["nichi","jitsu"` -> `日 nichi jitsu`, `&["kyou"` -> `kyou`
+#[cfg(feature = "compress-romajis")]
+fn to_lines(src: &str, kanji: bool) -> String {
+    let mut lines = Vec::new();
+    for item in src.split("],") {
+        // e.g. `_ => &[]`
+        let Some(start) = item.find("&[\"") else {
+            continue;
+        };
+        let mut line = String::new();
+        if kanji {
+            let kanji = item[..start].split('\'').nth(1).unwrap();
+            line.push_str(kanji);
+        }
+        for romaji in item[start..].split('"').skip(1).step_by(2) {
+            assert!(!romaji.is_empty() && !romaji.contains([' ', '\n']));
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(romaji);
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
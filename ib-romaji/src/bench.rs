@@ -0,0 +1,58 @@
+/*!
+Entry points for benchmarking custom word lists (feature `bench`).
+
+No version compatibility guarantee.
+
+The embedded dictionary trades binary size for build time and speed, see the [crate docs](crate#binary-size).
+To measure the same tradeoffs with your own word list:
+- Build time: [`HepburnRomanizer::build_from_words()`] with the words from [`parse_words()`].
+- Throughput: [`romanize_corpus()`] on a corpus.
+
+`benches/dictionary.rs` is a [criterion](https://docs.rs/criterion) bench doing both,
+with the word list and corpus given by environment variables:
+```sh
+IB_ROMAJI_BENCH_WORDS=words.txt IB_ROMAJI_BENCH_CORPUS=corpus.txt cargo bench -p ib-romaji --bench dictionary
+```
+
+## Example
+```
+use ib_romaji::{bench, HepburnRomanizer};
+
+let words = bench::parse_words("日本語\tnihongo\tnippongo\n今日\tkyou\n");
+assert_eq!(words[0], ("日本語", vec!["nihongo", "nippongo"]));
+
+let romanizer = HepburnRomanizer::build_from_words(words);
+assert_eq!(romanizer.romanize_vec("今日")[0], (6, "kyou"));
+// "ka" and "na"
+assert_eq!(bench::romanize_corpus(&romanizer, "かな"), 2);
+```
+*/
+use alloc::vec::Vec;
+
+use crate::{HepburnRomanizer, Input};
+
+/// Parse a word list with one word per line, and the word and its romajis separated by `\t`,
+/// e.g. `日本語\tnihongo\tnippongo`.
+///
+/// Empty lines and `\r\n` line endings are accepted. Words without romajis are skipped.
+pub fn parse_words(s: &str) -> Vec<(&str, Vec<&str>)> {
+    s.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let word = fields.next()?;
+            let romajis: Vec<&str> = fields.filter(|romaji| !romaji.is_empty()).collect();
+            (!word.is_empty() && !romajis.is_empty()).then_some((word, romajis))
+        })
+        .collect()
+}
+
+/// Romanize `corpus` at every char by [`HepburnRomanizer::romanize_vec()`],
+/// with the text before it as the context.
+///
+/// Returns the total number of romanizations, e.g. for checking different word lists give the same results.
+pub fn romanize_corpus(romanizer: &HepburnRomanizer, corpus: &str) -> usize {
+    corpus
+        .char_indices()
+        .map(|(i, _)| romanizer.romanize_vec(Input::new(corpus, i)).len())
+        .sum()
+}
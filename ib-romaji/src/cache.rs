@@ -1,17 +1,125 @@
 /*!
 Serialization/deserialization of romanizers for caching initialization state.
+
+## Format
+All integers are little-endian.
+- Magic `IBROMAJI`
+- Format version, [`HepburnRomanizer::CACHE_VERSION`]
+- Crate version, `u8` length followed by the string
+- Kanji flag, romanization system, long vowel style, `tch` style, kana normalization flag, each in a `u8`
+- Words fingerprint, a `u64` checksum of the kana and word flags and the extra words given to the builder,
+  which can't be recovered from the cache otherwise. `0` if the romanizer is not built by the builder.
+- Punctuation mode, a `u8` of `0`..=`3` for [`PunctuationMode`](crate::punctuation::PunctuationMode) variants.
+  `3` (map) is followed by a `u32` entry count, then for each entry a `u32` char
+  and the romaji as a `u32` length followed by the string.
+- Embedded word romajis flag, `0` if the embedded dictionary is used, or `1` if not (e.g. [runtime dictionaries](crate::dictionary)).
+- Extra word romajis, a `u32` word count, then for each word a `u32` romaji count
+  and each romaji as a `u32` length followed by the string.
+  See [`HepburnRomanizerBuilder::extra_words()`](crate::HepburnRomanizerBuilder::extra_words).
+  This also includes the words of runtime dictionaries.
+- The Aho-Corasick automaton, a `u64` length followed by its bytes
+- A `u64` checksum of all the above bytes
+
+[`HepburnRomanizerBuilder::build_cached()`] rebuilds the romanizer if any of the settings or the fingerprint differs from the builder's.
+
+## Versioning
+A cache is rejected on deserialization if either the format version or the crate version mismatches.
+The latter is because the automaton depends on the embedded dictionary,
+which may change between crate versions even if the format doesn't.
+
+## Validation
+The automaton can only be deserialized without validation,
+so the whole cache is validated by its length and checksum first.
+This detects truncated and corrupted caches, but not deliberately crafted ones,
+so caches should not be loaded from untrusted sources.
+
+## Memory
+All the deserialized data is owned by the romanizer, so caches can be loaded repeatedly without leaking.
 */
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+
 use daachorse::CharwiseDoubleArrayAhoCorasick;
 
-use crate::HepburnRomanizer;
+use crate::{
+    HepburnRomanizer, RomanizationSystem,
+    convert::{hepburn_ime::TchStyle, long_vowel::LongVowelStyle},
+    punctuation::PunctuationMode,
+    words::WordRomajis,
+};
 #[cfg(feature = "std")]
 use crate::{HepburnRomanizerBuilder, hepburn_romanizer_builder};
 
+/// A cursor over the cached bytes.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.take(8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+    }
+
+    /// See [`write_str()`].
+    fn str(&mut self) -> Option<&'a str> {
+        let len = self.u32()? as usize;
+        core::str::from_utf8(self.take(len)?).ok()
+    }
+}
+
+/// Write a length as a `u32`.
+///
+/// ## Panics
+/// If `len` doesn't fit in a `u32`, instead of writing a truncated length.
+fn write_len(buf: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("cache length overflows u32");
+    buf.extend_from_slice(&len.to_le_bytes());
+}
+
+/// Write a string as a `u32` length followed by the string.
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    write_len(buf, s.len());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// A fast non-cryptographic checksum, processing 8 bytes at a time.
+fn checksum(data: &[u8]) -> u64 {
+    const K: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut chunks = data.chunks_exact(8);
+    let mut hash = data.len() as u64;
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        hash = (hash.rotate_left(5) ^ word).wrapping_mul(K);
+    }
+    let mut last = [0; 8];
+    last[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+    hash = (hash.rotate_left(5) ^ u64::from_le_bytes(last)).wrapping_mul(K);
+    hash ^ (hash >> 32)
+}
+
 impl HepburnRomanizer {
     /// Header magic bytes for cache file validation
     const CACHE_MAGIC: &'static [u8] = b"IBROMAJI";
     /// Cache format version
-    const CACHE_VERSION: u8 = 2;
+    pub const CACHE_VERSION: u8 = 12;
+    /// The crate version, since the embedded dictionary may change between versions
+    const CACHE_CRATE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
     /// Serialize the HepburnRomanizer to bytes for caching.
     ///
@@ -19,6 +127,8 @@ impl HepburnRomanizer {
     /// [`deserialize_from_slice`](Self::deserialize_from_slice) to avoid the expensive
     /// initialization cost.
     ///
+    /// See [`cache`](crate::cache) for the format.
+    ///
     /// ## Example
     /// ```ignore
     /// use ib_romaji::HepburnRomanizer;
@@ -32,19 +142,59 @@ impl HepburnRomanizer {
         // Serialize the Aho-Corasick automaton first to get its size
         let ac_bytes = self.ac.serialize();
 
-        let mut buf = Vec::with_capacity(10 + ac_bytes.len());
+        let mut buf = Vec::with_capacity(32 + ac_bytes.len());
         // Write header
         buf.extend_from_slice(Self::CACHE_MAGIC);
         buf.push(Self::CACHE_VERSION);
+        buf.push(Self::CACHE_CRATE_VERSION.len() as u8);
+        buf.extend_from_slice(Self::CACHE_CRATE_VERSION.as_bytes());
         // Write kanji flag
         buf.push(self.kanji as u8);
+        // Write romanization system
+        buf.push(Self::system_to_u8(self.system));
+        // Write long vowel style
+        buf.push(self.long_vowel as u8);
+        // Write tch style
+        buf.push(self.sokuon_before_ch as u8);
+        // Write kana normalization flag
+        buf.push(self.normalize_kana as u8);
+        // Write words fingerprint
+        buf.extend_from_slice(&self.words_fingerprint.to_le_bytes());
+        // Write punctuation mode
+        match &self.punctuation {
+            PunctuationMode::Comma => buf.push(0),
+            PunctuationMode::Keep => buf.push(1),
+            PunctuationMode::Drop => buf.push(2),
+            PunctuationMode::Map(map) => {
+                buf.push(3);
+                write_len(&mut buf, map.len());
+                for (c, romaji) in map {
+                    buf.extend_from_slice(&(*c as u32).to_le_bytes());
+                    write_str(&mut buf, romaji);
+                }
+            }
+        }
+        // Write embedded word romajis flag
+        debug_assert!(
+            self.word_romajis.is_empty()
+                || core::ptr::eq(self.word_romajis, Self::builtin_word_romajis(self.system))
+        );
+        buf.push(self.word_romajis.is_empty() as u8);
+        // Write extra word romajis
+        Self::serialize_word_romajis(self.extra_word_romajis.iter(), &mut buf);
         // Append serialized Aho-Corasick automaton
+        buf.extend_from_slice(&(ac_bytes.len() as u64).to_le_bytes());
         buf.extend(ac_bytes);
+        // Append checksum
+        let checksum = checksum(&buf);
+        buf.extend_from_slice(&checksum.to_le_bytes());
         buf
     }
 
     /// Deserialize a HepburnRomanizer from cached bytes.
     ///
+    /// `data` can also be memory-mapped from a cache file.
+    ///
     /// Returns `None` if the cache is invalid, corrupted, or has an incompatible version.
     ///
     /// ## Example
@@ -58,41 +208,187 @@ impl HepburnRomanizer {
     /// ```
     ///
     /// ## Safety
-    /// This function is safe to call with any input data. Invalid or corrupted data
-    /// will result in `None` being returned. The underlying deserialization uses
-    /// `unsafe` code but is protected by the header validation.
+    /// Truncated or corrupted data will result in `None` being returned,
+    /// since the data is validated by its checksum before the automaton is deserialized.
+    /// But deliberately crafted data can pass the validation, see [`cache`](crate::cache).
     pub fn deserialize_from_slice(data: &[u8]) -> Option<Self> {
-        // Validate minimum size: magic (8) + version (1) + kanji flag (1) = 10 bytes
-        if data.len() < 10 {
+        // Validate checksum
+        let (body, sum) = data.split_at(data.len().checked_sub(8)?);
+        if checksum(body).to_le_bytes() != sum {
             return None;
         }
+        let mut r = Reader(body);
 
         // Validate magic header
-        if &data[0..8] != Self::CACHE_MAGIC {
+        if r.take(Self::CACHE_MAGIC.len())? != Self::CACHE_MAGIC {
             return None;
         }
 
         // Validate version
-        if data[8] != Self::CACHE_VERSION {
+        if r.u8()? != Self::CACHE_VERSION {
+            return None;
+        }
+        let crate_version_len = r.u8()? as usize;
+        if r.take(crate_version_len)? != Self::CACHE_CRATE_VERSION.as_bytes() {
             return None;
         }
 
         // Read kanji flag
-        let kanji = data[9] != 0;
+        let kanji = r.u8()? != 0;
+
+        // Read romanization system
+        let system = Self::system_from_u8(r.u8()?)?;
+
+        // Read long vowel style
+        let long_vowel = match r.u8()? {
+            0 => LongVowelStyle::Spelled,
+            1 => LongVowelStyle::Macron,
+            2 => LongVowelStyle::Circumflex,
+            _ => return None,
+        };
+
+        // Read tch style
+        let sokuon_before_ch = match r.u8()? {
+            0 => TchStyle::Tch,
+            1 => TchStyle::Cch,
+            _ => return None,
+        };
+
+        // Read kana normalization flag
+        let normalize_kana = r.u8()? != 0;
+
+        // Read words fingerprint
+        let words_fingerprint = r.u64()?;
+
+        // Read punctuation mode
+        let punctuation = match r.u8()? {
+            0 => PunctuationMode::Comma,
+            1 => PunctuationMode::Keep,
+            2 => PunctuationMode::Drop,
+            3 => Self::deserialize_punctuation_map(&mut r)?,
+            _ => return None,
+        };
+
+        // Read embedded word romajis flag
+        let word_romajis = match r.u8()? {
+            0 => Self::builtin_word_romajis(system),
+            1 => &[],
+            _ => return None,
+        };
+
+        // Read extra word romajis
+        let extra_word_romajis = Self::deserialize_word_romajis(&mut r)?;
 
         // Deserialize the Aho-Corasick automaton
-        // SAFETY: The header validation ensures this is data we serialized.
-        // The deserialize_unchecked function may panic or produce incorrect
-        // results if given invalid data, but we've validated the header.
-        let (ac, _remaining) =
-            unsafe { CharwiseDoubleArrayAhoCorasick::deserialize_unchecked(&data[10..]) };
+        let ac_len = usize::try_from(r.u64()?).ok()?;
+        let ac_bytes = r.take(ac_len)?;
+        if !r.0.is_empty() {
+            return None;
+        }
+        // SAFETY: The checksum and lengths ensure this is the automaton we serialized.
+        let (ac, remaining) =
+            unsafe { CharwiseDoubleArrayAhoCorasick::deserialize_unchecked(ac_bytes) };
+        if !remaining.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            words_fingerprint,
+            ..Self::with_ac(
+                ac,
+                kanji,
+                system,
+                long_vowel,
+                sokuon_before_ch,
+                normalize_kana,
+                punctuation,
+                word_romajis,
+                extra_word_romajis,
+            )
+        })
+    }
+
+    /// See [`cache`](crate::cache).
+    pub(crate) fn words_fingerprint(
+        kana: bool,
+        word: bool,
+        extra_words: &BTreeMap<String, Vec<String>>,
+    ) -> u64 {
+        let mut buf = vec![kana as u8, word as u8];
+        for (word, romajis) in extra_words {
+            write_str(&mut buf, word);
+            write_len(&mut buf, romajis.len());
+            for romaji in romajis {
+                write_str(&mut buf, romaji);
+            }
+        }
+        // Never `0`
+        checksum(&buf) | 1
+    }
+
+    fn serialize_word_romajis<'a, R: AsRef<str> + 'a>(
+        word_romajis: impl ExactSizeIterator<Item = &'a [R]>,
+        buf: &mut Vec<u8>,
+    ) {
+        write_len(buf, word_romajis.len());
+        for romajis in word_romajis {
+            write_len(buf, romajis.len());
+            for romaji in romajis {
+                write_str(buf, romaji.as_ref());
+            }
+        }
+    }
+
+    fn deserialize_word_romajis(r: &mut Reader) -> Option<WordRomajis> {
+        let word_count = r.u32()? as usize;
+        let mut word_romajis = WordRomajis::default();
+        for _ in 0..word_count {
+            let romaji_count = r.u32()? as usize;
+            let mut romajis = Vec::with_capacity(romaji_count.min(r.0.len()));
+            for _ in 0..romaji_count {
+                romajis.push(r.str()?);
+            }
+            word_romajis.push(romajis);
+        }
+        word_romajis.shrink_to_fit();
+        Some(word_romajis)
+    }
+
+    fn deserialize_punctuation_map(r: &mut Reader) -> Option<PunctuationMode> {
+        let count = r.u32()? as usize;
+        let mut map = Vec::with_capacity(count.min(r.0.len()));
+        for _ in 0..count {
+            let c = char::from_u32(r.u32()?)?;
+            map.push((c, r.str()?));
+        }
+        Some(PunctuationMode::map(map))
+    }
+
+    fn system_to_u8(system: RomanizationSystem) -> u8 {
+        match system {
+            RomanizationSystem::Hepburn => 0,
+            #[cfg(feature = "kunrei")]
+            RomanizationSystem::Kunrei => 1,
+        }
+    }
 
-        Some(Self { ac, kanji })
+    fn system_from_u8(b: u8) -> Option<RomanizationSystem> {
+        Some(match b {
+            0 => RomanizationSystem::Hepburn,
+            #[cfg(feature = "kunrei")]
+            1 => RomanizationSystem::Kunrei,
+            _ => return None,
+        })
     }
 }
 
 #[cfg(feature = "std")]
 impl HepburnRomanizer {
+    /// [`serialize_to_vec()`](Self::serialize_to_vec), but write to `writer`.
+    pub fn serialize_to(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&self.serialize_to_vec())
+    }
+
     /// Load a HepburnRomanizer from a cache file.
     ///
     /// Returns `None` if the file doesn't exist, is unreadable, or contains invalid cache data.
@@ -123,29 +419,30 @@ impl HepburnRomanizer {
     /// romanizer.to_cache("romanizer.cache").unwrap();
     /// ```
     pub fn to_cache<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
-        let data = self.serialize_to_vec();
         // Create parent directories if they don't exist
         if let Some(parent) = path.as_ref().parent() {
             if !parent.as_os_str().is_empty() {
                 std::fs::create_dir_all(parent)?;
             }
         }
-        std::fs::write(path, data)
+        self.serialize_to(std::fs::File::create(path)?)
     }
 }
 
-/// Extension trait for `HepburnRomanizerBuilder` to support cached builds.
+/// Cached builds of [`HepburnRomanizer`].
 #[cfg(feature = "std")]
 impl<S: hepburn_romanizer_builder::State> HepburnRomanizerBuilder<S>
 where
     S::Kana: hepburn_romanizer_builder::IsSet,
     S::Kanji: hepburn_romanizer_builder::IsSet,
     S::Word: hepburn_romanizer_builder::IsSet,
+    S::CachePath: hepburn_romanizer_builder::IsUnset,
 {
     /// Build a HepburnRomanizer with caching support.
     ///
-    /// This method attempts to load from the cache file first. If the cache is invalid
-    /// or doesn't exist, it builds the romanizer from scratch and saves it to the cache.
+    /// This method attempts to load from the cache file first. If the cache is invalid,
+    /// doesn't exist, or is built with a different config (any of the builder's options),
+    /// it builds the romanizer from scratch and saves it to the cache.
     ///
     /// This is an alternative to `build()` that adds caching. Use it when initialization
     /// time is a concern.
@@ -161,27 +458,174 @@ where
     ///     .build_cached("romanizer.cache");
     /// ```
     pub fn build_cached<P: AsRef<std::path::Path>>(self, cache_path: P) -> HepburnRomanizer {
-        // Get the builder parameters for cache validation
-        // Note: kana and word are encoded in the AC automaton structure,
-        // while kanji is stored as a separate flag
-        let _kana = self.get_kana().copied().unwrap_or(false);
-        let kanji = self.get_kanji().copied().unwrap_or(false);
-        let _word = self.get_word().copied().unwrap_or(false);
-
-        // Try to load from cache first
-        if let Some(romanizer) = HepburnRomanizer::from_cache(&cache_path) {
-            // Verify that the cached romanizer has matching kanji setting
-            if romanizer.kanji == kanji {
-                return romanizer;
-            }
+        self.cache_path(cache_path.as_ref().to_owned()).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .long_vowel(LongVowelStyle::Macron)
+            .sokuon_before_ch(TchStyle::Cch)
+            .build();
+        let data = romanizer.serialize_to_vec();
+        let cached = HepburnRomanizer::deserialize_from_slice(&data).unwrap();
+        assert_eq!(cached.long_vowel(), LongVowelStyle::Macron);
+        assert_eq!(cached.sokuon_before_ch(), TchStyle::Cch);
+        assert_eq!(cached.punctuation(), &PunctuationMode::Comma);
+        assert_eq!(cached.romanize_vec("日本"), romanizer.romanize_vec("日本"));
+        assert!(core::ptr::eq(cached.word_romajis, romanizer.word_romajis));
+
+        for punctuation in [
+            PunctuationMode::Keep,
+            PunctuationMode::Drop,
+            PunctuationMode::map([('。', "."), ('「', "\"")]),
+        ] {
+            let romanizer = HepburnRomanizer::builder()
+                .kana(true)
+                .punctuation(punctuation.clone())
+                .build();
+            let cached =
+                HepburnRomanizer::deserialize_from_slice(&romanizer.serialize_to_vec()).unwrap();
+            assert_eq!(cached.punctuation(), &punctuation);
         }
 
-        // Build from scratch
-        let romanizer = self.build();
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .word(true)
+            .extra_words([("葬送", ["sousou"])])
+            .build();
+        let cached =
+            HepburnRomanizer::deserialize_from_slice(&romanizer.serialize_to_vec()).unwrap();
+        assert_eq!(cached.romanize_vec("葬送"), vec![(6, "sousou")]);
+        assert_eq!(cached.romanize_vec("日本"), romanizer.romanize_vec("日本"));
+
+        // More than `u8::MAX` romajis
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .word(true)
+            .extra_words([("葬送", (0..300).map(|i| format!("sousou{i}")))])
+            .build();
+        let cached =
+            HepburnRomanizer::deserialize_from_slice(&romanizer.serialize_to_vec()).unwrap();
+        assert_eq!(cached.extra_word_romajis.get(0).unwrap().len(), 300);
 
-        // Save to cache (ignore errors)
-        let _ = romanizer.to_cache(&cache_path);
+        // Longer than `u16::MAX`
+        let long = "a".repeat(70000);
+        let romanizer = HepburnRomanizer::builder()
+            .extra_words([("葬送", [&long])])
+            .punctuation(PunctuationMode::map([('。', &*long)]))
+            .build();
+        let cached =
+            HepburnRomanizer::deserialize_from_slice(&romanizer.serialize_to_vec()).unwrap();
+        assert_eq!(cached.romanize_vec("葬送"), vec![(6, long.as_str())]);
+        assert_eq!(cached.punctuation(), romanizer.punctuation());
+
+        // Runtime dictionaries
+        let romanizer = HepburnRomanizer::from_reader("日本語\tにほんご\n".as_bytes()).unwrap();
+        let cached =
+            HepburnRomanizer::deserialize_from_slice(&romanizer.serialize_to_vec()).unwrap();
+        assert!(cached.word_romajis.is_empty());
+        assert_eq!(cached.romanize_vec("日本語")[0], (9, "nihongo"));
+
+        // Stale or corrupted caches
+        let mut stale = data.clone();
+        stale[HepburnRomanizer::CACHE_MAGIC.len()] -= 1;
+        assert!(HepburnRomanizer::deserialize_from_slice(&stale).is_none());
+        let mut stale = data.clone();
+        stale[HepburnRomanizer::CACHE_MAGIC.len() + 2] ^= 1;
+        assert!(HepburnRomanizer::deserialize_from_slice(&stale).is_none());
+        assert!(HepburnRomanizer::deserialize_from_slice(&data[..12]).is_none());
+        assert!(HepburnRomanizer::deserialize_from_slice(&data[..data.len() - 1]).is_none());
+        assert!(HepburnRomanizer::deserialize_from_slice(&[]).is_none());
+        for i in [data.len() / 2, data.len() - 20, data.len() - 1] {
+            let mut corrupted = data.clone();
+            corrupted[i] ^= 0x10;
+            assert!(
+                HepburnRomanizer::deserialize_from_slice(&corrupted).is_none(),
+                "{i}"
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn build_cached() {
+        let path = std::env::temp_dir().join(format!(
+            "ib-romaji-build-cached-{}.cache",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(false)
+            .word(true)
+            .extra_words([("葬送", ["sousou"])])
+            .build_cached(&path);
+        assert_eq!(romanizer.romanize_vec("葬送")[0], (6, "sousou"));
+        let data = std::fs::read(&path).unwrap();
+
+        // Same config
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(false)
+            .word(true)
+            .extra_words([("葬送", ["sousou"])])
+            .build_cached(&path);
+        assert_eq!(romanizer.romanize_vec("葬送")[0], (6, "sousou"));
+        assert_eq!(std::fs::read(&path).unwrap(), data);
+
+        // Different configs are rebuilt
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(false)
+            .word(true)
+            .extra_words([("葬送", ["soosoo"])])
+            .build_cached(&path);
+        assert_eq!(romanizer.romanize_vec("葬送")[0], (6, "soosoo"));
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(false)
+            .word(false)
+            .build_cached(&path);
+        assert!(romanizer.romanize_vec("葬送").is_empty());
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(false)
+            .word(false)
+            .long_vowel(LongVowelStyle::Macron)
+            .build_cached(&path);
+        assert_eq!(romanizer.long_vowel(), LongVowelStyle::Macron);
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(false)
+            .word(false)
+            .punctuation(PunctuationMode::Drop)
+            .build_cached(&path);
+        assert_eq!(romanizer.long_vowel(), LongVowelStyle::default());
+        assert_eq!(romanizer.punctuation(), &PunctuationMode::Drop);
+
+        std::fs::remove_file(&path).unwrap();
+    }
 
-        romanizer
+    #[cfg(feature = "std")]
+    #[test]
+    fn roundtrip_dictionary() {
+        let romanizer =
+            HepburnRomanizer::from_reader("日本語\tにほんご\tにっぽんご\n".as_bytes()).unwrap();
+        let mut data = Vec::new();
+        romanizer.serialize_to(&mut data).unwrap();
+        let cached = HepburnRomanizer::deserialize_from_slice(&data).unwrap();
+        assert_eq!(
+            cached.romanize_vec("日本語")[..2],
+            [(9, "nihongo"), (9, "nippongo")]
+        );
     }
 }
@@ -4,10 +4,62 @@ its convenient IME variant on the fly.
 
 - `n'` can be alternatively written as `nn`.
 - `tch*` can be alternatively written as `cch*`.
+
+`tch*` only comes from sokuon (`っ`) before `ch*`, e.g. `matcha` for `まっちゃ`,
+while sokuon before other consonants always doubles the consonant (e.g. `kka`, `sshi`, `ttsu`, `jji`).
+The output romajis can be written with `cch*` by [`TchStyle::Cch`].
 */
+use alloc::{borrow::Cow, string::String};
 
 pub const APOSTROPHE_ALT: char = 'n';
 
+/// How sokuon (`っ`) before `ch*` is written in romajis.
+///
+/// Matching always accepts both, see [`starts_with_ignore_hepburn_ime()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TchStyle {
+    /// e.g. `matcha`, `kotchi`, following Hepburn.
+    #[default]
+    Tch,
+    /// e.g. `maccha`, `kocchi`, following the IME variant.
+    Cch,
+}
+
+impl TchStyle {
+    /// Convert `tch*` in a romaji to this style in place.
+    ///
+    /// The byte length of the romaji is kept.
+    pub fn convert_in_place(self, romaji: &mut String) {
+        if self == TchStyle::Tch {
+            return;
+        }
+        let mut i = 0;
+        while let Some(j) = romaji[i..].find("tch") {
+            i += j;
+            romaji.replace_range(i..i + 1, "c");
+            i += 3;
+        }
+    }
+
+    /// Convert `tch*` in a romaji to this style.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::convert::hepburn_ime::TchStyle;
+    ///
+    /// assert_eq!(TchStyle::Cch.convert("matcha"), "maccha");
+    /// assert_eq!(TchStyle::Tch.convert("matcha"), "matcha");
+    /// ```
+    pub fn convert(self, romaji: &str) -> Cow<'_, str> {
+        if self == TchStyle::Tch || !romaji.contains("tch") {
+            return Cow::Borrowed(romaji);
+        }
+        let mut romaji = String::from(romaji);
+        self.convert_in_place(&mut romaji);
+        Cow::Owned(romaji)
+    }
+}
+
 const fn hepburn_ime_map() -> [u8; 128] {
     let mut map = [0; 128];
 
@@ -33,7 +85,7 @@ unsafe fn map_hepburn_ime_c(romaji: u8) -> u8 {
 }
 
 #[inline]
-fn eq_ignore_hepburn_ime_c(s: u8, r: u8, r_next: u8) -> bool {
+pub(crate) fn eq_ignore_hepburn_ime_c(s: u8, r: u8, r_next: u8) -> bool {
     s == r || s == unsafe { map_hepburn_ime_c(r) } && (r != b't' || r_next == b'c')
 }
 
@@ -148,6 +200,15 @@ pub fn romaji_starts_with_ignore_hepburn_ime(romaji: &str, s: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn tch_style() {
+        let mut romaji = String::from("kotchitcha");
+        TchStyle::Cch.convert_in_place(&mut romaji);
+        assert_eq!(romaji, "kocchiccha");
+        assert_eq!(TchStyle::Cch.convert("chitose"), "chitose");
+        assert_eq!(TchStyle::Tch.convert("kotchi"), "kotchi");
+    }
+
     #[test]
     fn starts_with() {
         assert!(starts_with_ignore_hepburn_ime("kotchidayo", "kotchi"));
@@ -0,0 +1,97 @@
+/*!
+This module contains functions for converting Hepburn romajis to
+[Kunrei-shiki romanization](https://en.wikipedia.org/wiki/Kunrei-shiki_romanization).
+
+- `shi`, `chi`, `tsu`, `fu`, `ji` are written as `si`, `ti`, `tu`, `hu`, `zi`.
+- `sh*`, `ch*`, `j*` are written as `sy*`, `ty*`, `zy*`, e.g. `sha` as `sya`.
+- `wo` is written as `o`.
+- Long consonants follow the converted kana, e.g. `tchi` as `tti`, `sshi` as `ssi`.
+
+Long vowels and `n'` are kept as is.
+*/
+
+use alloc::string::String;
+
+/// Convert the syllable at the start of `s`, and return the number of consumed bytes and the converted romaji.
+fn convert_syllable(s: &[u8]) -> Option<(usize, &'static str)> {
+    Some(match s {
+        [b's', b'h', b'i', ..] => (3, "si"),
+        [b's', b'h', ..] => (2, "sy"),
+        [b'c', b'h', b'i', ..] => (3, "ti"),
+        [b'c', b'h', ..] => (2, "ty"),
+        [b't', b's', b'u', ..] => (3, "tu"),
+        [b'f', b'u', ..] => (2, "hu"),
+        [b'j', b'i', ..] => (2, "zi"),
+        [b'j', b'y', ..] => (2, "zy"),
+        [b'j', ..] => (1, "zy"),
+        [b'w', b'o', ..] => (2, "o"),
+        _ => return None,
+    })
+}
+
+/// Convert a Hepburn romaji string to Kunrei-shiki.
+///
+/// ## Example
+/// ```
+/// use ib_romaji::convert::kunrei::hepburn_to_kunrei;
+///
+/// assert_eq!(hepburn_to_kunrei("tsuki"), "tuki");
+/// assert_eq!(hepburn_to_kunrei("matcha"), "mattya");
+/// ```
+pub fn hepburn_to_kunrei(romaji: &str) -> String {
+    let s = romaji.as_bytes();
+    let mut buf = String::with_capacity(romaji.len() + 1);
+    let mut i = 0;
+    while i < s.len() {
+        let rest = &s[i..];
+        // Long consonants: tch*, ss*, tts*, ff*, jj*
+        let next = match rest {
+            [b't', b'c', b'h', ..] => Some(1),
+            [c, next, ..] if c == next && c.is_ascii_lowercase() && !b"aeioun".contains(c) => {
+                Some(1)
+            }
+            _ => None,
+        };
+        if let Some((_, romaji)) = next.and_then(|n| convert_syllable(&rest[n..])) {
+            buf.push(romaji.as_bytes()[0] as char);
+            i += 1;
+            continue;
+        }
+        match convert_syllable(rest) {
+            Some((len, romaji)) => {
+                buf.push_str(romaji);
+                i += len;
+            }
+            None => {
+                let c = romaji[i..].chars().next().unwrap();
+                buf.push(c);
+                i += c.len_utf8();
+            }
+        }
+    }
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert() {
+        assert_eq!(hepburn_to_kunrei("tsuki"), "tuki");
+        assert_eq!(hepburn_to_kunrei("shinjuku"), "sinzyuku");
+        assert_eq!(hepburn_to_kunrei("chiisai"), "tiisai");
+        assert_eq!(hepburn_to_kunrei("fuji"), "huzi");
+        assert_eq!(hepburn_to_kunrei("sha"), "sya");
+        assert_eq!(hepburn_to_kunrei("jojo"), "zyozyo");
+        assert_eq!(hepburn_to_kunrei("kotchi"), "kotti");
+        assert_eq!(hepburn_to_kunrei("matcha"), "mattya");
+        assert_eq!(hepburn_to_kunrei("zasshi"), "zassi");
+        assert_eq!(hepburn_to_kunrei("mittsu"), "mittu");
+        assert_eq!(hepburn_to_kunrei("jjya"), "zzya");
+        assert_eq!(hepburn_to_kunrei("sekaiwo"), "sekaio");
+        assert_eq!(hepburn_to_kunrei("botan'yuki"), "botan'yuki");
+        assert_eq!(hepburn_to_kunrei("kyou"), "kyou");
+        assert_eq!(hepburn_to_kunrei("ffa"), "ffa");
+    }
+}
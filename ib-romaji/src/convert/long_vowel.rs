@@ -0,0 +1,220 @@
+/*!
+This module contains functions for converting/matching long vowels in Hepburn romajis
+to other [long vowel styles](LongVowelStyle) on the fly.
+
+The romaji data always spells long vowels out (e.g. `kou`, `kuu`).
+- `aa`, `uu`, `ee`, `oo`, `ou` can be alternatively written as `ā`, `ū`, `ē`, `ō`, `ō` (macron)
+  or `â`, `û`, `ê`, `ô`, `ô` (circumflex).
+- `ii` and `ei` are kept as is, following modern Hepburn.
+  But `ii` lengthened by the katakana long vowel mark `ー` is written as `ī` or `î` (e.g. `furī` for `フリー`),
+  and `ī`/`î` are always accepted when matching.
+
+## UTF-8 length
+Macron and circumflex vowels are all 2 bytes in UTF-8,
+the same as the spelled long vowels they replace.
+So the byte length of a romaji is kept after conversion, but the char count is not,
+e.g. `kou` is 3 bytes and 3 chars, while `kō` is 3 bytes and 2 chars.
+*/
+use alloc::{borrow::Cow, string::String};
+
+use crate::convert::hepburn_ime::eq_ignore_hepburn_ime_c;
+
+/// How long vowels are written in romajis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LongVowelStyle {
+    /// Spell long vowels out, e.g. `kou`, `kuu`.
+    #[default]
+    Spelled,
+    /// e.g. `kō`, `kū`.
+    Macron,
+    /// e.g. `kô`, `kû`.
+    Circumflex,
+}
+
+impl LongVowelStyle {
+    /// Get the styled vowel of a spelled long vowel, e.g. `ou` to `ō`.
+    ///
+    /// Returns `None` for [`LongVowelStyle::Spelled`] or if `spelled` is not a long vowel.
+    pub const fn map(self, spelled: [u8; 2]) -> Option<&'static str> {
+        let i = match spelled {
+            [b'a', b'a'] => 0,
+            [b'u', b'u'] => 1,
+            [b'e', b'e'] => 2,
+            [b'o', b'o'] | [b'o', b'u'] => 3,
+            _ => return None,
+        };
+        match self {
+            LongVowelStyle::Spelled => None,
+            LongVowelStyle::Macron => Some(["ā", "ū", "ē", "ō"][i]),
+            LongVowelStyle::Circumflex => Some(["â", "û", "ê", "ô"][i]),
+        }
+    }
+
+    /// [`map()`](Self::map), but `ii` is also mapped (e.g. to `ī`),
+    /// which is used for vowels lengthened by the katakana long vowel mark `ー`.
+    pub const fn map_lengthened(self, spelled: [u8; 2]) -> Option<&'static str> {
+        match (self, spelled) {
+            (LongVowelStyle::Macron, [b'i', b'i']) => Some("ī"),
+            (LongVowelStyle::Circumflex, [b'i', b'i']) => Some("î"),
+            _ => self.map(spelled),
+        }
+    }
+
+    /// Convert long vowels in a romaji to this style, and push the result to `buf`.
+    pub fn convert_into(self, romaji: &str, buf: &mut String) {
+        let s = romaji.as_bytes();
+        let mut start = 0;
+        let mut i = 0;
+        while i + 1 < s.len() {
+            if let Some(vowel) = self.map([s[i], s[i + 1]]) {
+                buf.push_str(&romaji[start..i]);
+                buf.push_str(vowel);
+                i += 2;
+                start = i;
+            } else {
+                i += 1;
+            }
+        }
+        buf.push_str(&romaji[start..]);
+    }
+
+    /// [`convert_into()`](Self::convert_into) for a romaji ending with a vowel lengthened by `ー`,
+    /// where a trailing `ii` is also converted, e.g. `rii` to `rī` for `リー`.
+    pub fn convert_lengthened_into(self, romaji: &str, buf: &mut String) {
+        match romaji.strip_suffix("ii").zip(self.map_lengthened(*b"ii")) {
+            Some((stem, vowel)) => {
+                self.convert_into(stem, buf);
+                buf.push_str(vowel);
+            }
+            None => self.convert_into(romaji, buf),
+        }
+    }
+
+    /// Convert long vowels in a romaji to this style.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::convert::long_vowel::LongVowelStyle;
+    ///
+    /// assert_eq!(LongVowelStyle::Macron.convert("toukyou"), "tōkyō");
+    /// assert_eq!(LongVowelStyle::Circumflex.convert("kuuki"), "kûki");
+    /// assert_eq!(LongVowelStyle::Spelled.convert("toukyou"), "toukyou");
+    /// ```
+    pub fn convert(self, romaji: &str) -> Cow<'_, str> {
+        if self == LongVowelStyle::Spelled {
+            return Cow::Borrowed(romaji);
+        }
+        let mut buf = String::with_capacity(romaji.len());
+        self.convert_into(romaji, &mut buf);
+        Cow::Owned(buf)
+    }
+}
+
+/// Hepburn IME variant is also ignored, see [`hepburn_ime`](super::hepburn_ime).
+fn eq_ignore_long_vowel_equisized(s: &[u8], romaji: &[u8], style: LongVowelStyle) -> bool {
+    debug_assert_eq!(s.len(), romaji.len());
+    let mut i = 0;
+    while i < s.len() {
+        if !s[i].is_ascii() {
+            // Styled vowels are all 2 bytes
+            match (s.get(i..i + 2), romaji.get(i..i + 2)) {
+                (Some(vowel), Some(&[a, b]))
+                    if style.map_lengthened([a, b]).map(str::as_bytes) == Some(vowel) =>
+                {
+                    i += 2;
+                    continue;
+                }
+                _ => return false,
+            }
+        }
+        let r_next = romaji.get(i + 1).copied().unwrap_or(0);
+        if !eq_ignore_hepburn_ime_c(s[i], romaji[i], r_next) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// [`starts_with_ignore_hepburn_ime()`](super::hepburn_ime::starts_with_ignore_hepburn_ime),
+/// but `s` can also use `style` long vowels.
+pub fn starts_with_ignore_long_vowel(s: &str, romaji: &str, style: LongVowelStyle) -> bool {
+    if let Some(s) = s.as_bytes().get(..romaji.len()) {
+        eq_ignore_long_vowel_equisized(s, romaji.as_bytes(), style)
+    } else {
+        false
+    }
+}
+
+/// [`romaji_starts_with_ignore_hepburn_ime()`](super::hepburn_ime::romaji_starts_with_ignore_hepburn_ime),
+/// but `s` can also use `style` long vowels.
+pub fn romaji_starts_with_ignore_long_vowel(romaji: &str, s: &str, style: LongVowelStyle) -> bool {
+    if let Some(romaji) = romaji.as_bytes().get(..s.len()) {
+        eq_ignore_long_vowel_equisized(s.as_bytes(), romaji, style)
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert() {
+        let m = LongVowelStyle::Macron;
+        assert_eq!(m.convert("kou"), "kō");
+        assert_eq!(m.convert("too"), "tō");
+        assert_eq!(m.convert("kuuki"), "kūki");
+        assert_eq!(m.convert("oneesan"), "onēsan");
+        assert_eq!(m.convert("obaasan"), "obāsan");
+        assert_eq!(m.convert("oniisan"), "oniisan");
+        assert_eq!(m.convert("sensei"), "sensei");
+        assert_eq!(m.convert("kouu"), "kōu");
+        assert_eq!(LongVowelStyle::Circumflex.convert("toukyou"), "tôkyô");
+        for romaji in ["kou", "toukyou", "kuuki", "a"] {
+            assert_eq!(m.convert(romaji).len(), romaji.len());
+        }
+
+        let lengthened = |style: LongVowelStyle, romaji| {
+            let mut buf = String::new();
+            style.convert_lengthened_into(romaji, &mut buf);
+            assert_eq!(buf.len(), romaji.len());
+            buf
+        };
+        assert_eq!(lengthened(m, "kaa"), "kā");
+        assert_eq!(lengthened(m, "rii"), "rī");
+        assert_eq!(lengthened(m, "kouhii"), "kōhī");
+        assert_eq!(lengthened(LongVowelStyle::Circumflex, "rii"), "rî");
+        assert_eq!(lengthened(LongVowelStyle::Spelled, "rii"), "rii");
+    }
+
+    #[test]
+    fn starts_with() {
+        let m = LongVowelStyle::Macron;
+        assert!(starts_with_ignore_long_vowel("kō", "kou", m));
+        assert!(starts_with_ignore_long_vowel("kou", "kou", m));
+        assert!(starts_with_ignore_long_vowel("tōkyō", "toukyou", m));
+        assert!(starts_with_ignore_long_vowel("kōhai", "kou", m));
+        assert!(!starts_with_ignore_long_vowel("kô", "kou", m));
+        assert!(!starts_with_ignore_long_vowel(
+            "kō",
+            "kou",
+            LongVowelStyle::Spelled
+        ));
+        assert!(!starts_with_ignore_long_vowel("kō", "ko", m));
+        assert!(!starts_with_ignore_long_vowel("kā", "kou", m));
+        assert!(starts_with_ignore_long_vowel("furīren", "furii", m));
+        // Hepburn IME
+        assert!(starts_with_ignore_long_vowel("kocchō", "kotchou", m));
+    }
+
+    #[test]
+    fn romaji_starts_with() {
+        let m = LongVowelStyle::Macron;
+        assert!(romaji_starts_with_ignore_long_vowel("kouhai", "kō", m));
+        assert!(romaji_starts_with_ignore_long_vowel("kouhai", "kōh", m));
+        assert!(romaji_starts_with_ignore_long_vowel("kouhai", "kou", m));
+        assert!(!romaji_starts_with_ignore_long_vowel("ko", "kō", m));
+    }
+}
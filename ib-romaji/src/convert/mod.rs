@@ -1 +1,3 @@
 pub mod hepburn_ime;
+pub mod kunrei;
+pub mod long_vowel;
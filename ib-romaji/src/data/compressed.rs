@@ -0,0 +1,127 @@
+/*!
+The kanji and word romajis stored as zstd-compressed blobs, see the `compress-romajis` feature.
+
+Each line of a blob is a kanji or word's romajis separated by ` `,
+with kanji lines prefixed by the kanji.
+The blobs are generated from the uncompressed data by `build.rs`, so they never drift from it.
+
+On first use, a blob is decompressed, and all its romajis are collected into a single slice,
+which each kanji or word then refers to by offsets.
+All of them are leaked, since they are needed for the rest of the program anyway.
+*/
+use std::sync::OnceLock;
+
+type Romajis = &'static [&'static str];
+
+/// Decompress a blob generated by `build.rs`.
+macro_rules! blob {
+    ($name:literal) => {
+        include_bytes_zstd::decode(include_bytes!(concat!(env!("OUT_DIR"), "/", $name)))
+    };
+}
+
+fn leak_str(bytes: Vec<u8>) -> &'static str {
+    // Generated by ourselves
+    unsafe { String::from_utf8_unchecked(bytes) }.leak()
+}
+
+/// Split each line into its romajis.
+fn split_lines(lines: impl Iterator<Item = &'static str>) -> Vec<Romajis> {
+    let mut romajis = Vec::new();
+    let mut ends = Vec::new();
+    for line in lines {
+        romajis.extend(line.split(' ').filter(|romaji| !romaji.is_empty()));
+        ends.push(romajis.len());
+    }
+
+    let romajis: Romajis = romajis.leak();
+    let mut start = 0;
+    ends.into_iter()
+        .map(|end| {
+            let line = &romajis[start..end];
+            start = end;
+            line
+        })
+        .collect()
+}
+
+fn word_table(blob: Vec<u8>) -> &'static [Romajis] {
+    split_lines(leak_str(blob).split('\n')).leak()
+}
+
+/// Sorted by kanji.
+fn kanji_table(blob: Vec<u8>) -> Vec<(char, Romajis)> {
+    let mut kanjis = Vec::new();
+    let romajis = split_lines(leak_str(blob).split('\n').map(|line| {
+        let mut chars = line.chars();
+        kanjis.push(chars.next().unwrap());
+        chars.as_str()
+    }));
+    let mut table: Vec<_> = kanjis.into_iter().zip(romajis).collect();
+    table.sort_unstable_by_key(|&(kanji, _)| kanji);
+    table
+}
+
+fn find_kanji(table: &[(char, Romajis)], kanji: char) -> Romajis {
+    match table.binary_search_by_key(&kanji, |&(kanji, _)| kanji) {
+        Ok(i) => table[i].1,
+        Err(_) => &[],
+    }
+}
+
+pub(crate) fn word_romajis() -> &'static [Romajis] {
+    static WORD_ROMAJIS: OnceLock<&'static [Romajis]> = OnceLock::new();
+    WORD_ROMAJIS.get_or_init(|| word_table(blob!("word_romajis.zst")))
+}
+
+pub(crate) fn kanji_romajis(kanji: char) -> Romajis {
+    static KANJI_ROMAJIS: OnceLock<Vec<(char, Romajis)>> = OnceLock::new();
+    let table = KANJI_ROMAJIS.get_or_init(|| kanji_table(blob!("kanji_romajis.zst")));
+    find_kanji(table, kanji)
+}
+
+#[cfg(feature = "kunrei")]
+pub(crate) fn kunrei_word_romajis() -> &'static [Romajis] {
+    static WORD_ROMAJIS: OnceLock<&'static [Romajis]> = OnceLock::new();
+    WORD_ROMAJIS.get_or_init(|| word_table(blob!("kunrei_word_romajis.zst")))
+}
+
+#[cfg(feature = "kunrei")]
+pub(crate) fn kunrei_kanji_romajis(kanji: char) -> Romajis {
+    static KANJI_ROMAJIS: OnceLock<Vec<(char, Romajis)>> = OnceLock::new();
+    let table = KANJI_ROMAJIS.get_or_init(|| kanji_table(blob!("kunrei_kanji_romajis.zst")));
+    find_kanji(table, kanji)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split() {
+        let table = kanji_table(b"\xce\x92 be-ta vita\n\xce\x91 arufa".to_vec());
+        assert_eq!(table, [('Α', &["arufa"][..]), ('Β', &["be-ta", "vita"])]);
+        assert_eq!(find_kanji(&table, 'Β'), ["be-ta", "vita"]);
+        assert_eq!(find_kanji(&table, 'a'), [""; 0]);
+
+        let table = word_table(b"kyou\nkonnichi konnichiha".to_vec());
+        assert_eq!(table, [&["kyou"][..], &["konnichi", "konnichiha"]]);
+    }
+
+    #[test]
+    fn same_as_uncompressed() {
+        let uncompressed: &[&[&str]] = include!("word_kanas.rs");
+        assert_eq!(word_romajis(), uncompressed);
+        assert!(core::ptr::eq(word_romajis(), word_romajis()));
+
+        fn uncompressed_kanji_romajis(kanji: char) -> Romajis {
+            include!("kanjis.rs")
+        }
+        let table = kanji_table(blob!("kanji_romajis.zst"));
+        for (kanji, romajis) in table {
+            assert_eq!(romajis, uncompressed_kanji_romajis(kanji), "{kanji}");
+        }
+        assert_eq!(kanji_romajis('日'), uncompressed_kanji_romajis('日'));
+        assert_eq!(kanji_romajis('a'), [""; 0]);
+    }
+}
@@ -1,9 +1,15 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
+//! Kana data. See [`data`](super#stability) for the stability of the constants.
 
+/// The minimum length of a kana.
 pub const KANA_MIN_LEN: usize = 3;
+/// The maximum length of a kana, including combined ones like `きゃ`.
+///
 /// i.e. ｯｳﾞｧ, ...
 pub const KANA_MAX_LEN: usize = 12;
 
+/// The maximum length of a kana's romaji.
+///
 /// i.e. kkya, ...
 pub const KANA_ROMAJI_MAX_LEN: usize = 4;
 
@@ -205,4 +211,105 @@ pub(crate) static HEPBURN_ROMAJIS: &[&str] = &[
 "n'ya","n'yu","n'yo",
 "wi","we","wo",
 "wi","we","wo"
+];
+
+#[cfg(feature = "kunrei")]
+pub(crate) static KUNREI_ROMAJIS: &[&str] = &[
+"a","a","i","i","u","u","e","e",
+"o","o","ka","ga","ki","kya","kyu","kyo",
+"gi","gya","gyu","gyo","ku","gu","ke","ge",
+"ko","go","sa","za","si","sya","syu","syo",
+"zi","zya","zyu","zyo","su","zu","se","ze",
+"so","zo","ta","da","ti","tye","tya","tyu",
+"tyo","zi","zya","zyu","zyo","tu","kka","gga",
+"kki","kkya","kkyu","kkyo","ggi","ggya","ggyu","ggyo",
+"kku","ggu","kke","gge","kko","ggo","ssa","zza",
+"ssi","ssya","ssyu","ssyo","zzi","zzya","zzyu","zzyo",
+"ssu","zzu","sse","sso","zzo","tta","dda","tti",
+"ttya","ttyu","ttyo","zzi","zzya","zzyu","zzyo","ttu",
+"zzu","tte","dde","tto","ddo","hha","bba","ppa",
+"hhi","hhya","hhyu","hhyo","bbi","bbya","bbyu","bbyo",
+"ppi","ppya","ppyu","ppyo","hhu","ffa","ffi","ffe",
+"ffo","bbu","ppu","hhe","bbe","ppe","hho","bbo",
+"ppo","yya","yyu","yyo","rra","rri","rrya","rryu",
+"rryo","rru","rre","rro","vvu","vva","vvi","vve",
+"vvo","tu","zu","te","de","di","to","do",
+"na","ni","nya","nyu","nyo","nu","ne","no",
+"ha","ba","pa","hi","hya","hyu","hyo","bi",
+"bya","byu","byo","pi","pya","pyu","pyo","hu",
+"fa","fi","fe","fo","bu","pu","he","be",
+"pe","ho","bo","po","ma","mi","mya","myu",
+"myo","mu","me","mo","ya","ya","yu","yu",
+"yo","yo","ra","ri","rya","ryu","ryo","ru",
+"re","ro","wa","wa","i","e","o","n",
+"n'a","n'i","n'u","n'e","n'o",
+"n'ya","n'yu","n'yo","n'a","n'i","n'u","n'e","n'o","n'ya","n'yu","n'yo","n'a","n'i","n'u","n'e","n'o","n'ya","n'yu","n'yo",
+"vu","va","vi",
+"ve","vo","ka","ke","\"","=","a","a",
+"i","i","u","u","e","e","o","o",
+"ka","ga","ki","kya","kyu","kyo","kya","kyu",
+"kyo","gi","gyu","gyo","ku","gu","gya","ke",
+"ge","ko","go","sa","za","si","sya","syu",
+"syo","zi","zya","zyu","zyo","su","zu","se",
+"ze","so","zo","ta","da","ti","tye","tya",
+"tyu","tyo","zi","zya","zyu","zyo","tu","kka",
+"gga","kki","kkya","kkyu","kkyo","ggi","ggya","ggyu",
+"ggyo","kku","ggu","kke","gge","kko","ggo","ssa",
+"zza","ssi","ssya","ssyu","ssyo","zzi","zzya","zzyu",
+"zzyo","ssu","zzu","sse","sso","zzo","tta","dda",
+"tti","ttya","ttyu","ttyo","zzi","zzya","zzyu","zzyo",
+"ttu","zzu","tte","dde","tto","ddo","hha","bba",
+"ppa","hhi","hhya","hhyu","hhyo","bbi","bbya","bbyu",
+"bbyo","ppi","ppya","ppyu","ppyo","hhu","ffa","ffi",
+"ffe","ffo","bbu","ppu","hhe","bbe","ppe","hho",
+"bbo","ppo","yya","yyu","yyo","rra","rri","rrya",
+"rryu","rryo","rru","rre","rro","vvu","vva","vvi",
+"vve","vvo","tu","zu","te","de","di","to",
+"do","na","ni","nya","nyu","nyo","nu","ne",
+"no","ha","ba","pa","hi","hya","hyu","hyo",
+"bi","bya","byu","byo","pi","pya","pyu","pyo",
+"hu","fa","fi","fe","fo","bu","pu","he",
+"be","pe","ho","bo","po","ma","mi","mya",
+"myu","myo","mu","me","mo","ya","ya","yu",
+"yu","yo","yo","ra","ri","rya","ryu","ryo",
+"ru","re","ro","wa","wa","i","e","o",
+"n",
+"n'a","n'i","n'u","n'e","n'o","n'ya","n'yu","n'yo",
+"n'a","n'i","n'u","n'e","n'o",
+"n'ya","n'yu","n'yo","n'a","n'i","n'u","n'e","n'o","n'ya","n'yu","n'yo",
+"vu","va",
+"vi","ve","vo","ka","ke","va","vi","ve",
+"vo",".","-","o","a","i","u","e",
+"o","ya","yu","yo","tu","vvu","vva","vvi",
+"vve","vvo","kka","gga","kki","kkya","kkyu","kkyo",
+"ggi","ggya","ggyu","ggyo","kku","ggu","kke","gge",
+"kko","ggo","ssa","zza","ssi","ssya","ssyu","ssyo",
+"zzi","zzya","zzyu","zzyo","ssu","zzu","sse","sso",
+"zzo","tta","dda","tti","ttya","ttyu","ttyo","zzi",
+"zzya","zzyu","zzyo","ttu","zzu","tte","dde","tto",
+"ddo","hha","bba","ppa","hhi","hhya","hhyu","hhyo",
+"bbi","bbya","bbyu","bbyo","ppi","ppya","ppyu","ppyo",
+"hhu","ffa","ffi","ffe","ffo","bbu","ppu","hhe",
+"bbe","ppe","hho","bbo","ppo","yya","yyu","yyo",
+"rra","rri","rrya","rryu","rryo","rru","rre","rro",
+"a","i","u","vu","va","vi","ve","vo",
+"e","o","ka","ga","ki","kya","kyu","kyo",
+"kya","kyu","kyo","gi","gyu","gyo","ku","gu",
+"gya","ke","ge","ko","go","sa","za","si",
+"sya","syu","syo","zi","zya","zyu","zyo","su",
+"zu","se","ze","so","zo","ta","da","ti",
+"tye","tya","tyu","tyo","zi","zya","zyu","zyo",
+"tu","zu","te","de","di","to","do","na",
+"ni","nya","nyu","nyo","nu","ne","no","ha",
+"ba","pa","hi","hya","hyu","hyo","bi","bya",
+"byu","byo","pi","pya","pyu","pyo","hu","fa",
+"fi","fe","fo","bu","pu","he","be","pe",
+"ho","bo","po","ma","mi","mya","myu","myo",
+"mu","me","mo","ya","yu","yo","ra","ri",
+"rya","ryu","ryo","ru","re","ro","wa","n",
+"n'a","n'i","n'u","n'e","n'o","n'ya","n'yu","n'yo","n'a","n'i","n'u","n'e","n'o","n'ya","n'yu","n'yo",
+"n'a","n'i","n'u","n'e","n'o",
+"n'ya","n'yu","n'yo",
+"wi","we","o",
+"wi","we","o"
 ];
\ No newline at end of file
@@ -0,0 +1,38 @@
+match kanji {
+'上'=>(0xC0080,0x73FF7F,0x3B74),
+'下'=>(0x3,0x7FFC,0x4800),
+'中'=>(0x4,0x7B,0x1A),
+'井'=>(0x30,0xF,0xD),
+'人'=>(0x50,0x1AF,0x2B),
+'佐'=>(0x1,0x2,0x2),
+'北'=>(0x1,0x3E,0x2E),
+'南'=>(0x50,0x2F,0x27),
+'原'=>(0x4,0xFB,0xF3),
+'和'=>(0x48010,0x7FB7FEF,0x67B67EE),
+'大'=>(0x8001,0xF7FFE,0xF6AFE),
+'子'=>(0x3800,0xC7FF,0xC5DF),
+'学'=>(0x1,0xE,0xC),
+'小'=>(0x800,0x7FF,0x72D),
+'山'=>(0xC,0xF3,0xE3),
+'川'=>(0x40,0x3F,0x37),
+'日'=>(0x4080,0x3BF7F,0x3BE5B),
+'明'=>(0xD0000,0x72FFFF,0x72F8A0),
+'春'=>(0x10,0x6F,0x6D),
+'月'=>(0x3,0x7C,0x5C),
+'木'=>(0x21,0x5E,0x52),
+'本'=>(0x4,0x3FB,0x3DB),
+'村'=>(0x10,0xF,0xD),
+'東'=>(0x4000,0x3FFF,0x3FDF),
+'林'=>(0x2,0x5,0x4),
+'森'=>(0x4,0x3,0x2),
+'生'=>(0x180000000,0x7FE7FFFFFFF,0x46E6E576A3F),
+'田'=>(0x2,0xFD,0xED),
+'石'=>(0xB0,0x34F,0x34D),
+'美'=>(0x5,0x7A,0x6A),
+'花'=>(0x6,0x9,0x8),
+'藤'=>(0x11,0x2E,0x2C),
+'西'=>(0xB0,0x4F,0x47),
+'野'=>(0x14,0x2B,0x2A),
+'高'=>(0x20,0x7DF,0x5F),
+_ => (0, 0, 0)
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+match kanji {
+'上'=>&[7,19,18,21,22,10,3,1,0,15,14,16,17,20,2,4,5,6,8,9,11,12,13],
+'下'=>&[1,0,13,12,6,10,9,3,2,5,4,8,7,11,14],
+'中'=>&[2,5,6,0,1,3,4],
+'井'=>&[4,5,1,0,2,3],
+'人'=>&[4,6,2,7,8,1,0,3,5],
+'佐'=>&[0,1],
+'北'=>&[0,4,1,2,3,5],
+'南'=>&[6,4,3,0,1,2,5],
+'原'=>&[2,3,0,1,4,5,6,7],
+'和'=>&[18,15,4,24,23,11,12,0,1,2,3,5,6,7,8,9,10,13,14,16,17,19,20,21,22,25,26],
+'大'=>&[0,15,8,12,10,1,2,3,4,5,6,7,9,11,13,14,16,17,18,19],
+'子'=>&[11,12,13,5,9,0,1,2,3,4,6,7,8,10,14,15],
+'学'=>&[0,1,3,2],
+'小'=>&[11,1,4,6,7,0,2,3,5,8,9,10],
+'山'=>&[2,3,4,0,1,5,6,7],
+'川'=>&[6,3,0,1,2,4,5],
+'日'=>&[14,7,5,2,8,0,1,3,4,6,9,10,11,12,13,15,16,17],
+'明'=>&[16,19,18,1,2,3,0,8,6,9,10,4,5,7,11,12,13,14,15,17,20,21,22],
+'春'=>&[4,1,0,2,3,5,6],
+'月'=>&[1,0,5,6,2,3,4],
+'木'=>&[0,5,2,3,1,4,6],
+'本'=>&[2,5,0,1,3,4,6,7,8,9],
+'村'=>&[4,1,0,2,3],
+'東'=>&[14,5,0,1,2,3,4,6,7,8,9,10,11,12,13],
+'林'=>&[1,0,2],
+'森'=>&[2,0,1],
+'生'=>&[31,32,12,8,10,40,39,41,28,6,7,15,21,23,24,19,36,0,1,2,3,4,5,9,11,13,14,16,17,18,20,22,25,26,27,29,30,33,34,35,37,38,42],
+'田'=>&[1,4,0,2,3,5,6,7],
+'石'=>&[5,7,4,1,0,2,3,6,8,9],
+'美'=>&[0,2,4,1,3,5,6],
+'花'=>&[1,2,0,3],
+'藤'=>&[4,0,1,2,3,5],
+'西'=>&[5,4,7,3,0,1,2,6],
+'野'=>&[4,2,0,1,3,5],
+'高'=>&[5,8,7,9,10,0,1,2,3,4,6],
+_ => &[]
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+/*!
+Kana romajis lengthened by the long vowel mark `ー` (chōonpu), e.g. `ka` to `kaa` for `カー`.
+
+Generated from the kana romajis at compile time, so that they can be `'static` like other romajis.
+*/
+use super::kana::{self, KANA_ROMAJI_MAX_LEN};
+
+type Lengthened = ([u8; KANA_ROMAJI_MAX_LEN + 1], u8);
+
+const fn lengthen<const N: usize>(romajis: &[&str]) -> [Lengthened; N] {
+    let mut lengthened = [([0; KANA_ROMAJI_MAX_LEN + 1], 0); N];
+    let mut i = 0;
+    while i < N {
+        let romaji = romajis[i].as_bytes();
+        if let [.., vowel @ (b'a' | b'i' | b'u' | b'e' | b'o')] = romaji {
+            let mut j = 0;
+            while j < romaji.len() {
+                lengthened[i].0[j] = romaji[j];
+                j += 1;
+            }
+            lengthened[i].0[j] = *vowel;
+            lengthened[i].1 = j as u8 + 1;
+        }
+        i += 1;
+    }
+    lengthened
+}
+
+static HEPBURN_LENGTHENED: [Lengthened; kana::HEPBURN_ROMAJIS.len()] =
+    lengthen(kana::HEPBURN_ROMAJIS);
+
+#[cfg(feature = "kunrei")]
+static KUNREI_LENGTHENED: [Lengthened; kana::KUNREI_ROMAJIS.len()] = lengthen(kana::KUNREI_ROMAJIS);
+
+fn get(lengthened: &'static [Lengthened], i: usize) -> Option<&'static str> {
+    let (romaji, len) = lengthened.get(i)?;
+    // SAFETY: ASCII romaji plus an ASCII vowel
+    (*len != 0).then(|| unsafe { str::from_utf8_unchecked(&romaji[..*len as usize]) })
+}
+
+/// The `i`-th kana romaji in [`HEPBURN_ROMAJIS`](kana::HEPBURN_ROMAJIS) lengthened,
+/// or `None` if it doesn't end with a vowel (e.g. `n`).
+pub(crate) fn hepburn(i: usize) -> Option<&'static str> {
+    get(&HEPBURN_LENGTHENED, i)
+}
+
+/// [`hepburn()`] for [`KUNREI_ROMAJIS`](kana::KUNREI_ROMAJIS).
+#[cfg(feature = "kunrei")]
+pub(crate) fn kunrei(i: usize) -> Option<&'static str> {
+    get(&KUNREI_LENGTHENED, i)
+}
@@ -1,21 +1,53 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
+/*!
+The embedded romanization data and its length limits.
+
+## Stability
+All lengths are in UTF-8 bytes.
+The constants are part of the public API and won't be removed in minor versions.
+But their values may change in minor versions when the embedded data is updated,
+so they should not be relied on being exact, e.g. to size a fixed buffer across versions of this crate.
+
+## See also
+- [`HepburnRomanizer::max_word_len()`](crate::HepburnRomanizer::max_word_len)
+*/
 use core::ops::Range;
 
 pub mod kana;
+pub(crate) mod lengthened;
+#[cfg(feature = "compress-romajis")]
+mod compressed;
+
+#[cfg(feature = "compress-romajis")]
+pub(crate) use compressed::{kanji_romajis, word_romajis};
+#[cfg(all(feature = "compress-romajis", feature = "kunrei"))]
+pub(crate) use compressed::{kunrei_kanji_romajis, kunrei_word_romajis};
 
 /// The minimum length of bytes that can be romanized.
 pub const MIN_LEN: usize = KANJI_MIN_LEN;
 
+/// The minimum length of a kanji, i.e. Greek letters like Α.
 pub const KANJI_MIN_LEN: usize = 2;
+/// The maximum length of a kanji, i.e. CJK Unified Ideographs Extension B and later (e.g. 𠮟).
 pub const KANJI_MAX_LEN: usize = 4;
+/// [`KANJI_MIN_LEN`]..=[`KANJI_MAX_LEN`]
 pub const KANJI_LEN: Range<usize> = KANJI_MIN_LEN..KANJI_MAX_LEN+1;
 
+/// The maximum length of a kanji's romaji.
+///
 /// i.e. 娍: suraritoshitemimeyoi (すらりとしてみめよい)
 pub const KANJI_ROMAJI_MAX_LEN: usize = 22;
 
+/// The maximum length of a word in the embedded dictionary.
+///
+/// Input beyond this length is never looked at when romanizing a word,
+/// see [`HepburnRomanizer::max_word_len()`](crate::HepburnRomanizer::max_word_len).
+///
 /// i.e. 身体髪膚これを父母に受くあえて毀傷せざるは孝の始めなり
 pub const WORD_MAX_LEN: usize = 81;
 
+/// The maximum length of a word's romaji.
+///
 /// i.e.
 /// - 身体髪膚これを父母に受くあえて毀傷せざるは孝の始め?なり: shintaihappukorewofuboniukuaetekishousezaruhakounohajimenari
 /// - 山中の賊を破るは易く心中の賊を破るは難し: sanchuunozokuwoyaburuhayasukushinchuunozokuwoyaburuhakatashi
@@ -28,8 +60,50 @@ pub const WORD_ROMAJI_MAX_LEN: usize = 60;
 pub(crate) static WORDS: &str = include_str!("words.in.txt");
 
 // pub static WORD_ROMAJIS: &[&[&str]] = &[&["onaji", "onajiku"], &["dou"]];
-pub(crate) static WORD_ROMAJIS: &[&[&str]] = include!("word_kanas.rs");
+#[cfg(not(feature = "compress-romajis"))]
+static WORD_ROMAJIS: &[&[&str]] = include!("word_kanas.rs");
 
+#[cfg(not(feature = "compress-romajis"))]
+pub(crate) fn word_romajis() -> &'static [&'static [&'static str]] {
+    WORD_ROMAJIS
+}
+
+#[cfg(not(feature = "compress-romajis"))]
 pub(crate) fn kanji_romajis(kanji: char) -> &'static [&'static str] {
     include!("kanjis.rs")
-}
\ No newline at end of file
+}
+
+/// [`kanji_reading_masks()`] of the kanji not in `data/kanjidic-tagged.tsv`.
+///
+/// No romaji is either on'yomi or kun'yomi, which never happens for a tagged kanji.
+pub(crate) const UNTAGGED_KANJI_MASKS: (u64, u64, u64) = (0, 0, 0);
+
+/// `(on'yomi mask, kun'yomi mask, nanori mask)` of [`kanji_romajis()`], where bit `i` is for the `i`-th romaji.
+///
+/// Nanori are also kun'yomi. [`UNTAGGED_KANJI_MASKS`] if the kanji is not tagged.
+/// The masks also apply to `kunrei_kanji_romajis()`.
+pub(crate) fn kanji_reading_masks(kanji: char) -> (u64, u64, u64) {
+    include!("kanji_kinds.rs")
+}
+
+/// Indices of [`kanji_romajis()`] from the most common to the least.
+///
+/// Empty if and only if the kanji is not tagged, see [`kanji_reading_masks()`].
+pub(crate) fn kanji_reading_ranks(kanji: char) -> &'static [u8] {
+    include!("kanji_ranks.rs")
+}
+
+#[cfg(all(feature = "kunrei", not(feature = "compress-romajis")))]
+static KUNREI_WORD_ROMAJIS: &[&[&str]] = include!(concat!(env!("OUT_DIR"), "/kunrei_word_romajis.rs"));
+
+/// Generated from [`word_romajis()`] by [`hepburn_to_kunrei()`](crate::convert::kunrei::hepburn_to_kunrei) in `build.rs`.
+#[cfg(all(feature = "kunrei", not(feature = "compress-romajis")))]
+pub(crate) fn kunrei_word_romajis() -> &'static [&'static [&'static str]] {
+    KUNREI_WORD_ROMAJIS
+}
+
+/// Generated from [`kanji_romajis()`] by [`hepburn_to_kunrei()`](crate::convert::kunrei::hepburn_to_kunrei) in `build.rs`.
+#[cfg(all(feature = "kunrei", not(feature = "compress-romajis")))]
+pub(crate) fn kunrei_kanji_romajis(kanji: char) -> &'static [&'static str] {
+    include!(concat!(env!("OUT_DIR"), "/kunrei_kanji_romajis.rs"))
+}
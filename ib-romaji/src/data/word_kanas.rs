@@ -100,7 +100,7 @@
 &["obon'yasumi"],&["obon'ake"],&["omenikakaru"],&["omenikakeru"],&["omenitomaru"],&["ometsuke"],&["ometsukeyaku"],&["omedetai"],&["omekoboshi"],&["omedamawokurau"],&["omemie"],&["omeza"],&["omezamashi"],&["omedoori"],&["osumousan"],&["onemu"],
 &["omegane"],&["onemu"],
 &["oshirase"],&["oshiraseme-ru"],
-&["onrei"],&["oreinihaoyobimasen"],&["oreinomoushiyou"],&["oreinomoushiyou"],&["oreiboukou"],&["oreigoe"],&["oreigoe"],&["omatsuri"],&["oinori"],&["oinorime-ru"],&["oharai"],&["obaasama"],&["oba-san","obaasan"],&["obaachama","obachama"],&["obaachan"],&["obaachankko"],&["obaachanko"],&["obaasama"],&["ojiisan"],&["ojiichama"],&["ojiichan"],&["oiwai"],&["omiwatari"],&["omikiagaranukamihanai"],&["omatsuri"],&["omatsurisawagi"],&["omatsurikibun"],&["omatsurisawagi"],&["omatsurisawagi"],
+&["onrei"],&["oreinihaoyobimasen"],&["oreinomoushiyou"],&["oreinomoushiyou"],&["oreiboukou"],&["oreigoe"],&["oreigoe"],&["omatsuri"],&["oinori"],&["oinorime-ru"],&["oharai"],&["obaasama"],&["oba-san","obaasan"],&["obaachama","obachama"],&["obaachan"],&["obaachankko"],&["obaachanko"],&["obaasama"],&["ojiisan"],&["ojiichama"],&["ojiichan"],&["oiwai"],&["omiwatari"],&["omiki"],&["omikiagaranukamihanai"],&["omatsuri"],&["omatsurisawagi"],&["omatsurikibun"],&["omatsurisawagi"],&["omatsurisawagi"],
 &["otsumori"],&["otsumori"],
 &["okamawookosu"],&["okamawookosu"],&["otachi"],&["otachidai"],
 &["osekkai"],&["osekkaiya"],&["osekkaiyaki"],
@@ -578,7 +578,7 @@
 &["ichirittorudaki"],&["ikkanen"],&["ikkasho"],&["ikkagetsu"],&["ikko"],&["ikkanen"],&["ikkasho"],&["ikkagetsu"],&["ikkagetsukenshin"],
 &["juuichi","toichi","tooichi"],&["hyakutooban"],&["hyakujuukyuuban"],&["hyakujuuhachiban"],&["juuichinichi"],&["juuichigatsu"],&["itchou"],&["itchoagari","itchouagari"],&["itchoumae"],&["itchouji","itteiji"],&["itchoumeichibanchi"],&["itchoura"],&["juunana","juushichi"],&["hitonanuka","isshichinichi","juunananichi","juushichinichi"],&["juusan"],&["juusannichi"],&["ikka"],&["isse","issei"],&["isseinoyuu"],&["isseiwofuubisuru"],&["isseichidai","isseiichidai"],&["isseiichigen"],&["isseiki"],&["isseifuubi"],
 &["itchuubushi"],&["juukyuu"],&["juukunichi"],
-&["juuni"],&["ichininioyobazu"],&["ichiniwoarasou"],&["juuninichi"],&["juunigatsu"],&["juugo","togo"],&["juugonichi"],&["hitorikkiri"],&["hitorikko"],&["hitorikkoseisaku"],&["hitoridemoooku"],&["hitoribitori"],&["hitoripotchi"],&["hitoribotchi"],&["hitoribitori"],&["hitoriippyou","ichinin'ippyou"],&["ichininnori"],&["ichiningaisha"],&["hitorizumai"],&["hitorikiri"],&["hitorigachi"],&["hitorikatte"],&["hitoriuranai"],&["hitorijime"],&["hitorigaten","hitorigatten"],&["hitoriyogari"],&["hitoridenka"],&["hitoritengu"],&["hitorigo","hitorikko"],&["hitoriatari"],&["hitoriate"],&["ichinintouzen"],&["hitorimusuko"],&["hitorietsuniiru"],&["hitorigurashi"],&["hitorigurashi"],&["hitoriaruki"],&["hitorinokorazu"],&["hitorigime"],&["hitoriyakiniku"],&["hitorizumou"],&["hitorigeiko"],&["hitoridachi"],&["hitoribara"],&["hitorigoto"],&["hitorimake"],&["hitoriasobi"],&["ikkai"],&["ichidaitoshiyori"],&["ichidaizasshu"],
+&["juuni"],&["ichininioyobazu"],&["ichiniwoarasou"],&["juuninichi"],&["juunigatsu"],&["juugo","togo"],&["juugonichi"],&["hitori"],&["hitorikkiri"],&["hitorikko"],&["hitorikkoseisaku"],&["hitoridemoooku"],&["hitoribitori"],&["hitoripotchi"],&["hitoribotchi"],&["hitoribitori"],&["hitoriippyou","ichinin'ippyou"],&["ichininnori"],&["ichiningaisha"],&["hitorizumai"],&["hitorikiri"],&["hitorigachi"],&["hitorikatte"],&["hitoriuranai"],&["hitorijime"],&["hitorigaten","hitorigatten"],&["hitoriyogari"],&["hitoridenka"],&["hitoritengu"],&["hitorigo","hitorikko"],&["hitoriatari"],&["hitoriate"],&["ichinintouzen"],&["hitorimusuko"],&["hitorietsuniiru"],&["hitorigurashi"],&["hitorigurashi"],&["hitoriaruki"],&["hitorinokorazu"],&["hitorigime"],&["hitoriyakiniku"],&["hitorizumou"],&["hitorigeiko"],&["hitoridachi"],&["hitoribara"],&["hitorigoto"],&["hitorimake"],&["hitoriasobi"],&["ikkai"],&["ichidaitoshiyori"],&["ichidaizasshu"],
 &["ikken"],&["ikkenshorui"],&["ikkenrakuchaku"],&["hitoyasumi"],
 &["issa"],&["ittai"],&["ittaitonaru"],&["ittaini"],&["ittaizentai"],&["ittaika"],&["ittaigata"],&["ittaigatamonita","ittaigatamonita-"],&["ittaigatamonita","ittaigatamonita-"],&["ittaisei"],&["ittaikan"],&["ittaiteki"],&["ikkaaruko-ru"],&["ikkafuhouwashibousan"],&["ikkakansuu"],
 &["ippyou"],&["ikko"],&["ikkoatari"],&["ikkoikko"],&["ikkojin"],&["ikkouri"],&["ikkoatari"],&["ikkoatari"],
@@ -904,7 +904,7 @@
 &["agedoko"],&["agezoko"],&["kamizanisueru"],&["jouzabubukkyou"],
 &["uwamawaru"],&["uwabari"],
 &["jouigedatsu"],&["jouiuchi"],&["jouiuchi"],
-&["jo-gu","jo-gu-","jougo","jouguu"],&["jougohadokuwoshirazugekohakusuriwoshirazu"],&["umai","uttsui"],&["umaiguaini"],&["umaihanashinihauragaaru"],&["umaku"],&["umakuiku"],&["umakuiku"],&["jouzuniatsukau"],&["jouzunierabu"],&["jouzunotekaramizugamoreru"],&["uwatewoiku"],&["uwatedashinage"],&["uwatenage"],&["uwatehineri"],
+&["jo-gu","jo-gu-","jougo","jouguu"],&["jougohadokuwoshirazugekohakusuriwoshirazu"],&["jouzu"],&["umai","uttsui"],&["umaiguaini"],&["umaihanashinihauragaaru"],&["umaku"],&["umakuiku"],&["umakuiku"],&["jouzuniatsukau"],&["jouzunierabu"],&["jouzunotekaramizugamoreru"],&["uwatewoiku"],&["uwatedashinage"],&["uwatenage"],&["uwatehineri"],
 &["uebure","uwabure"],
 &["uwagake","uwakake"],&["uwagakesuisha"],
 &["uesoroe","uezoroe"],
@@ -1360,7 +1360,7 @@
 &["noru"],
 &["otsunisumasu"],
 &["itchuu","otchuu"],
-&["otomege-"],&["otomege"],&["otomego"],&["otomezakura"],&["otomeyuri"],
+&["otome"],&["otomege-"],&["otomege"],&["otomego"],&["otomezakura"],&["otomeyuri"],
 &["otoutoyome"],
 &["otogo"],
 &["isshi","osshi"],
@@ -1527,7 +1527,7 @@
 &["nikakokugo"],&["nikanen"],&["nikasho"],&["ninirokujiken"],&["nigoshinhou"],
 &["nijuuichinichi"],&["nichounage"],&["nichougama"],&["nijuunana","nijuushichi"],&["nijuushichinente-ze"],&["futananuka","nijuunananichi","nijuushichinichi"],&["nijuusannichi"],&["nisenokatame"],&["nisenochigiri"],
 &["nijuukyuu"],&["nijuukunichi","nijuukyuunichi"],
-&["nijuuninichi"],&["nijuugo"],&["nijuugonichi"],&["futarikkiri"],&["futarihahanryo、sanninhanakamaware"],&["futarihahanryo、sanninhanakamaware"],&["futarinori"],&["futarikiri"],&["futarigurashi"],&["futarigoroshi"],&["nininbaori"],&["futaribakama"],&["futarizure"],
+&["nijuuninichi"],&["nijuugo"],&["nijuugonichi"],&["futari"],&["futarikkiri"],&["futarihahanryo、sanninhanakamaware"],&["futarihahanryo、sanninhanakamaware"],&["futarinori"],&["futarikiri"],&["futarigurashi"],&["futarigoroshi"],&["nininbaori"],&["futaribakama"],&["futarizure"],
 &["nitowoou"],&["nitowooumonohaittowomoezu"],&["nitowooumonohaittowomoezu"],&["nijuuhachi","nippachi"],&["nijuuhachinichi"],&["nihachisoba"],&["nijuuroku"],&["nijuurokunichi"],
 &["nibuntansakugi"],&["nibungi"],&["nibun'onpu","nibuonpu"],
 &["hata","hatachi","nijissai","nijussai"],&["nijuuisseiki"],&["nijuuikkajouyoukyuu"],&["nisannichi"],&["nijisseiki","nijusseiki"],&["nijuuhasshuku"],&["nijuuyahoshitentou"],&["nijuuyokka"],&["nijuushisekki"],&["hatatose"],&["hatsuka"],&["hatsukausagi"],&["hatsukadaikon"],&["hatsukashougatsu"],&["hatsukanezumi"],&["hatsukanezumitoningen"],&["hatachi","hatatose","nijissai","nijussai"],&["hatae"],
@@ -1592,7 +1592,7 @@
 &["goshinihairu"],
 &["gosekke"],
 &["goasatte"],&["gojihakkyou"],
-&["gogatsunosekku"],&["satsukibare"],&["urusai","wazurai"],&["sabae"],&["urusai","wazurai"],&["satsukitsutsuji"],&["samidare"],&["samidareshiki"],&["samidaregumo"],
+&["satsuki"],&["gogatsunosekku"],&["satsukibare"],&["urusai","wazurai"],&["sabae"],&["urusai","wazurai"],&["satsukitsutsuji"],&["samidare"],&["samidareshiki"],&["samidaregumo"],
 &["gohonnoyubinihairu"],&["gohonbashi"],
 &["itsusenomikoto"],
 &["gomokunarabe"],&["gomokuzushi"],&["gomokusoba"],&["gomokuzushi"],&["utsubo"],
@@ -1953,7 +1953,7 @@
 &["karishussho"],&["karishusshosha"],
 &["karisome","kousho"],&["karisomenimo"],&["karisome","kousho"],&["karisomenisuru"],&["karisomenimo"],&["karizuri"],
 &["karigenbunkugirikigou"],&["kariukekin"],
-&["kanawofuru"],&["kanamajiri"],&["kanamajiribun"],&["kanazukai"],&["kanadehon"],&["kanabumi"],&["kanagaki"],&["kanabon"],&["kanazoushi"],&["kanazoushi"],&["kanazukai"],
+&["kana"],&["kanawofuru"],&["kanamajiri"],&["kanamajiribun"],&["kanazukai"],&["kanadehon"],&["kanabumi"],&["kanagaki"],&["kanabon"],&["kanazoushi"],&["kanazoushi"],&["kanazukai"],
 &["karigakoi"],
 &["kariume"],
 &["karishikkou"],
@@ -2078,7 +2078,7 @@
 &["denbou","denpou"],&["denboukanjou"],&["denbouhada"],
 &["denpyou"],&["denpyoubangou"],
 &["dentouchuugokuigaku"],
-&["tenmabune"],
+&["tenmabune","tenmasen"],
 &["hakkei"],
 &["burajiru"],
 &["mozu"],
@@ -2602,7 +2602,7 @@
 &["motouke"],&["motoukenin"],&["motoukeoinin"],
 &["motogome"],&["motogomejuu"],
 &["motodoori"],
-&["keitarigatakuteitarigatashi"],&["anchan"],
+&["niisan"],&["keitarigatakuteitarigatashi"],&["anchan"],
 &["kyoudai"],&["kyoudaigenka"],&["kyoudaikouhai"],&["kyoudaigenka"],&["kyoudai"],
 &["kyoudaihataninnohajimari"],&["kyoudaigaisha"],&["kyoudaigenka"],&["keiteikakinisemegu"],
 &["kippou"],
@@ -2801,7 +2801,7 @@
 &["hasshoudou"],
 &["yatsugo"],&["hattan"],
 &["hatchin"],
-&["happaku"],&["happyaku"],&["happyakuman"],&["yaoyorozunokami"],&["happyakuyadera"],&["happyakuyabashi"],&["happyakuyachou"],&["happyakubikuni","obikuni","yaobikuni"],&["yaochoujiai"],
+&["happaku"],&["happyaku"],&["happyakuman"],&["yaoyorozunokami"],&["happyakuyadera"],&["happyakuyabashi"],&["happyakuyachou"],&["yaoya"],&["happyakubikuni","obikuni","yaobikuni"],&["yaochou"],&["yaochoujiai"],
 &["hasshou"],
 &["hasshukyougi"],
 &["hachidan","hattan"],&["hattan'ori"],&["hattan'ori"],
@@ -3138,7 +3138,7 @@
 &["magamagashii"],
 &["kyoudannitaoreru"],&["kyoudannitaoreru"],
 &["kyoujoumochi"],
-&["dakuboku"],
+&["dakuboku","dekoboko"],
 &["totsumachi"],
 &["ponkan"],
 &["toppan"],&["toppan'insatsu"],
@@ -4322,7 +4322,7 @@
 &["tomobune"],
 &["tomouchi"],
 &["yuugikokka"],
-&["tomodachizukiai"],&["tomodachizukiai"],&["tomodachiijoukoibitomiman"],&["tomodachigai"],&["tomodachikotoba"],
+&["tomodachi"],&["tomodachizukiai"],&["tomodachizukiai"],&["tomodachiijoukoibitomiman"],&["tomodachigai"],&["tomodachikotoba"],
 &["tomozuri"],&["tomozuri"],
 &["narabu"],&["naraberu"],
 &["shanpon"],&["shanponmachi"],
@@ -6331,7 +6331,7 @@
 &["futomomowodasu"],&["futorijishi"],&["futorisugi"],
 &["daijoutennou","dajoutennou","ookisumeramikoto","taijoutennou"],&["daijouhouou","dajouhouou"],
 &["futomani"],
-&["daitou"],&["tachidori","tachitori"],&["tachiuchi"],&["tachiuchidekiru"],&["tachiuchidekiru"],&["tachimochi"],&["tachisabaki"],&["tachibukuro"],
+&["daitou","tachi"],&["tachidori","tachitori"],&["tachiuchi"],&["tachiuchidekiru"],&["tachiuchidekiru"],&["tachimochi"],&["tachisabaki"],&["tachibukuro"],
 &["futomani"],
 &["futebuteshii"],&["daidaikagura"],&["daibu"],
 &["futomaki"],&["futomakizushi"],&["futomakizushi"],&["futomakizushi"],
@@ -6575,7 +6575,7 @@
 &["tsumaguro"],
 &["mekakeboukou"],
 &["mekakebara"],
-&["anesankaburi","neesankaburi"],
+&["neesan"],&["anesankaburi","neesankaburi"],
 &["kyoudai"],&["kyoudaigenka"],&["shimaiyuukoutoshikankei"],&["shimaigaisha"],&["kyoudaigenka"],&["kyoudaiai"],
 &["anegawanotatakai"],
 &["kyoudai"],
@@ -7845,8 +7845,8 @@
 &["kanchan"],&["kanchanmachi"],
 &["arashiwoyobu"],
 &["koujiru"],&["kouzuru"],&["kasanikakaru"],&["kasanikiru"],&["kasaage"],&["kasabaru"],&["kewashii"],&["rinshanpai"],&["rinshankaiho-","rinshankaihou"],&["minezakura"],
-&["ganpeki"],&["kawappuchi"],&["kawamo","kawazura"],&["kawamo","kawatsura"],&["kawabuchi"],&["kawawokudaru"],&["kawawokoeru"],&["kawakudari"],&["kawazutai"],&["kawachidori"],&["kawaramatsuba"],&["kawarage"],
-&["kawaguchi"],&["kawaguchitankidaigaku"],
+&["ganpeki"],&["kawappuchi"],&["kawamo","kawazura"],&["kawamo","kawatsura"],&["kawabuchi"],&["kawawokudaru"],&["kawawokoeru"],&["kawakudari"],&["kawazutai"],&["kawachidori"],&["kawara"],&["kawaramatsuba"],
+&["kawarage"],&["kawaguchi"],&["kawaguchitankidaigaku"],
 &["kawamukai"],&["kawamukou"],
 &["gaataro","gataro","gatarou"],
 &["kawajiri"],
@@ -8585,6 +8585,7 @@
 &["misa"],
 &["iyasaka","yasaka"],
 &["yajirobee"],&["yajiumakonjou"],
+&["yayoi"],
 &["iyodatsu","yodatsu"],
 &["bibousaku"],
 &["gengakugassou"],&["gengakki"],
@@ -10844,9 +10845,9 @@
 &["kazutori"],
 &["kazuawase"],&["kazuawase"],
 &["kazuzakiyomogi"],&["kazuzakiyomogi"],
-&["amata"],&["kazuooi"],&["kazuooku"],&["sakki"],&["sukiyazukuri"],
+&["amata"],&["kazuooi"],&["kazuooku"],&["sakki"],&["sukiya"],&["sukiyazukuri"],
 &["suujiasobi"],&["suugakukyouikugakkai"],
-&["sukiyatabi"],&["sukiyazukuri"],
+&["sukiya"],&["sukiyatabi"],&["sukiyazukuri"],
 &["kazusukunai"],
 &["kazu","shibashiba","suu"],
 &["kazuhagondou"],
@@ -11130,7 +11131,7 @@
 &["hinata","hyuuga"],&["nichinan'yougogakkou"],
 &["nissan"],&["nisshuu"],&["hidori"],&["nittai"],
 &["hinata","hyuuga"],&["hinatanikoori"],&["hinataboko"],&["hinatabokori"],&["hinatabokko"],&["hyuuganatsu"],&["hinatamizu"],&["hinatakusai"],
-&["nisshuu"],&["nisshuurizumu"],&["nisshuushisa"],&["nisshuuundou"],&["hiyoru"],
+&["nisshuu"],&["nisshuurizumu"],&["nisshuushisa"],&["nisshuuundou"],&["hiyori"],&["hiyoru"],
 &["higarakasa"],
 &["nisshou"],&["nisshouerekutoronikusu"],&["nisshouiwai"],&["nisshoubokikentei"],
 &["hitsugi"],&["hitsuginomiko"],&["hitsuginomiko"],
@@ -11237,6 +11238,7 @@
 &["hae-","hashikkoi","hashikoi","haya-i","hayya-i","hayyai"],&["hayaitokoro"],&["hayaitoko"],&["hayaimonogachi"],&["hayaimonogachi"],&["hayaimonogachi"],&["hayakareosokare"],&["hayakuieba"],&["hayakerebahayaihodoyoi"],&["hayakerebahayaihodoii"],&["hayakerebahayaihodoii","hayakerebahayaihodoyoi"],&["hayakerebahayaihodoyoi"],&["hayakerebahayaihodoii","hayakerebahayaihodoyoi"],&["hayanehayaoki"],&["hayanomikomi"],
 &["hayamegusuri"],&["hayo"],
 &["hayaagari"],&["hayaagari"],
+&["saotome"],
 &["hayajimai"],&["hayatsukegi"],&["hayatsukegi"],
 &["hayawakari"],&["hayawakari"],
 &["hayaguchi"],&["hayakuchikotoba"],
@@ -11265,7 +11267,7 @@
 &["wasada","waseda"],&["wasedagaigosenmongakkou"],&["wasedadaigaku"],&["wasedadaigakukawaguchigeijutsugakkou"],&["wasedadaigakugeijutsugakkou"],&["wasedajitsugyougakkou"],&["wasedabiyousenmongakkou"],&["wasedadenshisenmongakkou"],
 &["hayadachi"],
 &["hayabune"],
-&["sanaburi"],&["sanaedori"],
+&["sanae"],&["sanaburi"],&["sanaedori"],
 &["hayayude"],
 &["hayamaki"],
 &["hayaoki"],&["hayaokihasanmonnotoku"],&["hayaokihasanmonnotoku"],&["sokuho"],
@@ -11444,7 +11446,7 @@
 &["tokiedabunpou"],
 &["tokinashidaikon"],
 &["tokishirazu","tokizake"],
-&["jimeishou"],&["tokeime-ka-"],&["tokeijikake"],&["tokeimawari"],&["tokeizara"],
+&["jimeishou","tokei"],&["tokeime-ka-"],&["tokeijikake"],&["tokeimawari"],&["tokeizara"],
 &["tokigane"],
 &["jikangakakaru"],&["jikangaaku"],&["jikangatatsu"],&["jikangatatsunitsurete"],&["jikangatatsunitsurete"],&["jikangaaku"],&["jikandoori"],&["jikanniowareru"],&["jikanniokureru"],&["jikannohizumi"],&["jikannoyurusukagiri"],&["jikannookure"],&["jikanwotsukuru"],&["jikanwosaku"],&["jikanwotorareru"],&["jikanwotoru"],&["jikanwomamoru"],&["jikanwooshimu"],&["jikanwokakeru"],&["jikanwotsubusu"],&["jikanwokasegu"],&["jikanwoakeru"],&["jikangakarishinpan"],&["jikangire"],&["jikanwari"],&["jikangaiteate"],&["jikanmachi"],&["jikantsubushi"],&["jikanhatten"],&["jikankasegi"],&["jikankashi"],&["jikandoori"],
 &["jigenrippou"],
@@ -11808,7 +11810,7 @@
 &["kibera"],&["mokkan"],&["mokkangakki"],&["kibako"],
 &["mokkan"],
 &["kigumi"],
-&["yuu"],&["momendoufu"],&["momenbari"],
+&["momen","yuu"],&["momendoufu"],&["momenbari"],
 &["kikurage"],
 &["mokuikuhiroba"],
 &["mimizuku","tsuku","zuku"],&["tsukutori"],
@@ -12389,6 +12391,7 @@
 &["sandawara"],
 &["sankarado"],
 &["sando"],
+&["sajiki"],
 &["sanbashi"],
 &["sangawara"],
 &["santomejima"],
@@ -13043,8 +13046,8 @@
 &["kaasama"],&["kaasan"],&["kaachan"],
 &["oyako"],&["oyakodon","oyakodonburi"],&["oyakodon","oyakodonburi"],
 &["oyako"],
-&["muya"],
-&["muya"],&["moyageta"],
+&["muya","omoya"],
+&["muya","omoya"],&["moyageta"],
 &["oyayubitaikousei"],
 &["kamoji"],
 &["kaasama"],
@@ -13486,7 +13489,7 @@
 &["habamu"],
 &["kawanokami","kawarou"],
 &["hanoi"],&["kawachiondo"],
-&["kawarakojiki"],&["kawaraninjin"],&["kawarabato"],&["kawaguchi"],&["kawaguchikobijutsukan"],
+&["kawara"],&["kawarakojiki"],&["kawaraninjin"],&["kawarabato"],&["kawaguchi"],&["kawaguchikobijutsukan"],
 &["gaataro","gataro","gatarou"],
 &["kashi","kawagishi"],&["kashiwokaeru"],&["kashiwokaeru"],&["kashiwokaeru"],
 &["kawagondou"],
@@ -13870,11 +13873,11 @@
 &["umigame"],
 &["ama","kaishi","uminchu"],&["amaobunegai"],&["makuri"],&["makuri"],
 &["hainanji-fan"],
-&["iriko"],
+&["unabara"],&["iriko"],
 &["kaiingakkou"],
 &["tsunami"],
 &["haichi"],
-&["kaijin","uminchu"],
+&["ama","kaijin","uminchu"],
 &["kaigaishutchou"],&["kaigaigurashi","kaigaikurashi"],&["kaigaikeizaikyouryokukikin"],&["umitengu"],&["ama"],
 &["kaiganzutai"],&["kaigandoori"],
 &["unazoko"],&["haiteiraoyue"],&["kaiteidani"],
@@ -14076,7 +14079,7 @@
 &["kiyomoto"],&["kiyomotobushi"],
 &["seirenkeppaku"],
 &["kiyobuki"],
-&["kiyomizu"],&["kiyomizunobutai"],&["kiyomizunobutaikaratobioriru"],&["kiyomizunobutaikaratobioriru"],&["kiyomizunobutaikaratobioriru"],&["sen","sumashi"],
+&["kiyomizu","shimizu"],&["kiyomizunobutai"],&["kiyomizunobutaikaratobioriru"],&["kiyomizunobutaikaratobioriru"],&["kiyomizunobutaikaratobioriru"],&["sen","sumashi"],
 &["seijoukeppaku"],
 &["seigaiha"],&["seirouden"],
 &["harebare"],
@@ -17078,7 +17081,7 @@
 &["kamikakete"],
 &["shinmeizukuri"],
 &["jinji","kamigoto","kamukoto","shinji"],
-&["kagurajishi"],&["kagurabue"],&["kagurakoumori"],&["kagurazame"],
+&["kagura"],&["kagurajishi"],&["kagurabue"],&["kagurakoumori"],&["kagurazame"],
 &["jinmukonokata"],&["jinmutennousai"],&["jinmuirai","jinmukonokata"],
 &["kamigoroshi","kamikoroshi"],
 &["jinzui","shinzui"],
@@ -17864,6 +17867,7 @@
 &["kizukiageru"],&["kizukiageru"],&["kizuku"],
 &["tsuigaki","tsukigaki"],
 &["tsuiji","tsukiji"],
+&["tsukiyama"],
 &["chikuasa"],
 &["chikkou"],
 &["tsuigaki","tsukigaki"],
@@ -19969,6 +19973,7 @@
 &["shibaikke"],&["shibaiwoutsu"],&["shibaizuki"],&["shibaigoya"],&["shibaigakaru"],&["shibaigakaru"],&["shibaijaya"],
 &["shibazakura"],
 &["shibayaki"],
+&["shibafu"],
 &["chi-ma-chu-","chi-ma-kao"],&["chi-ma-jan","ji-ma-jan"],
 &["karashi"],&["karashimiso"],&["karashiyu"],&["karashidei"],&["karashizuke"],&["karashiiro"],&["karashina"],&["karashisumiso"],
 &["gomitame"],
@@ -23481,6 +23486,7 @@
 &["tokoro"],&["tokorozura"],
 &["nosodachi"],
 &["nobushi"],&["nobuseri"],&["nobusuma"],
+&["nora"],
 &["nogeshi"],&["nobana"],
 &["egonoki"],
 &["nogiku"],&["yasaiwotsukuru"],&["yasaijiru"],&["yasaiitame"],
@@ -23855,7 +23861,7 @@
 &["tsubazeriai"],&["tsubazeriai"],
 &["tsubazeriai"],
 &["kitae"],&["kitaeageru"],&["kitaenuku"],&["kitaeru"],&["kitaeageru"],&["kitaenuku"],
-&["kanuchi"],
+&["kaji","kanuchi"],
 &["kanuchi","tan'ya"],
 &["kuwaire"],&["kuwaireshiki"],
 &["kuwahajime"],
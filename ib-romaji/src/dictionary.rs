@@ -0,0 +1,227 @@
+/*!
+Loading word dictionaries at runtime, instead of using the embedded one.
+
+This can be used to ship the dictionary separately or let users update it.
+
+## Format
+UTF-8 text, one word per line, with the word and its kana readings separated by `\t`:
+```text
+日本語\tにほんご
+今日\tきょう\tこんにち
+```
+i.e. `words.in.txt` with the readings appended, or the `jmdict.csv` used to generate the embedded dictionary.
+The readings are romanized with the [`RomanizationSystem`](crate::RomanizationSystem) of the romanizer on loading.
+
+Empty lines are ignored. `\r\n` line endings are also accepted.
+
+## Memory
+The romajis of a loaded dictionary are owned by the romanizer like [extra words](crate::HepburnRomanizerBuilder::extra_words),
+so they are freed with it and a dictionary can be reloaded.
+
+## Caching
+The word romajis are also included in the [cache](crate::cache),
+so a dictionary only needs to be parsed once.
+*/
+use std::{fmt, fs, io, path::Path};
+
+use crate::{
+    HepburnRomanizer, RomanizationSystem,
+    convert::{hepburn_ime::TchStyle, long_vowel::LongVowelStyle},
+    data,
+    punctuation::PunctuationMode,
+    words::WordRomajis,
+};
+
+/// Error when loading a dictionary.
+#[derive(Debug)]
+pub enum DictionaryError {
+    /// Failed to read the dictionary, or it is not valid UTF-8.
+    Io(io::Error),
+    /// A line of the dictionary is malformed.
+    Malformed {
+        /// 1-based line number.
+        line: usize,
+        reason: &'static str,
+    },
+    /// Failed to build the automaton, e.g. there are duplicate words.
+    Build(daachorse::errors::DaachorseError),
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DictionaryError::Io(e) => write!(f, "failed to read dictionary: {e}"),
+            DictionaryError::Malformed { line, reason } => {
+                write!(f, "malformed dictionary at line {line}: {reason}")
+            }
+            DictionaryError::Build(e) => write!(f, "failed to build dictionary: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DictionaryError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DictionaryError {
+    fn from(e: io::Error) -> Self {
+        DictionaryError::Io(e)
+    }
+}
+
+impl HepburnRomanizer {
+    /// [`HepburnRomanizer::default()`], but with the words from a dictionary
+    /// instead of the embedded one.
+    ///
+    /// See [`dictionary`](crate::dictionary) for the format.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::from_reader("日本語\tにほんご\n".as_bytes()).unwrap();
+    /// assert_eq!(romanizer.romanize_vec("日本語")[0], (9, "nihongo"));
+    /// ```
+    pub fn from_reader(mut reader: impl io::Read) -> Result<Self, DictionaryError> {
+        let mut s = String::new();
+        reader.read_to_string(&mut s)?;
+        Self::from_dictionary_str(&s, RomanizationSystem::default())
+    }
+
+    /// [`from_reader()`](Self::from_reader) with a file.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DictionaryError> {
+        Self::from_reader(io::BufReader::new(fs::File::open(path)?))
+    }
+
+    fn from_dictionary_str(s: &str, system: RomanizationSystem) -> Result<Self, DictionaryError> {
+        let kana_romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .system(system)
+            .build();
+
+        let mut words = Vec::new();
+        let mut word_romajis = WordRomajis::default();
+        let mut romajis: Vec<String> = Vec::new();
+        for (i, line) in s.split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() {
+                continue;
+            }
+            let malformed = |reason| DictionaryError::Malformed {
+                line: i + 1,
+                reason,
+            };
+
+            let mut fields = line.split('\t');
+            let word = fields.next().unwrap();
+            if word.is_empty() {
+                return Err(malformed("empty word"));
+            }
+            if word.len() > data::WORD_MAX_LEN {
+                return Err(malformed("word too long"));
+            }
+
+            for kana in fields {
+                let romaji = kana_romanizer
+                    .romanize_kana_str_all(kana)
+                    .filter(|romaji| !romaji.is_empty())
+                    .ok_or_else(|| malformed("reading is not romanizable kana"))?;
+                if !romajis.contains(&romaji) {
+                    romajis.push(romaji);
+                }
+            }
+            if romajis.is_empty() {
+                return Err(malformed("no reading"));
+            }
+
+            words.push(word);
+            word_romajis.push(romajis.drain(..));
+        }
+        word_romajis.shrink_to_fit();
+
+        // The words are indexed as extra words, with no embedded ones
+        let ac =
+            Self::build_ac(true, words.into_iter().enumerate()).map_err(DictionaryError::Build)?;
+
+        Ok(Self::with_ac(
+            ac,
+            true,
+            system,
+            LongVowelStyle::default(),
+            TchStyle::default(),
+            false,
+            PunctuationMode::default(),
+            &[],
+            word_romajis,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_reader() {
+        let romanizer = HepburnRomanizer::from_reader(
+            "日本語\tにほんご\tにっぽんご\r\n\n今日\tきょう\tこんにち\tきょう\n".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            romanizer.romanize_vec("日本語")[..2],
+            [(9, "nihongo"), (9, "nippongo")]
+        );
+        assert_eq!(
+            romanizer.romanize_vec("今日")[..2],
+            [(6, "kyou"), (6, "konnichi")]
+        );
+        assert_eq!(romanizer.romanize_vec("今日")[2].0, 3);
+        // Not in the dictionary
+        assert!(
+            romanizer
+                .romanize_vec("東京")
+                .iter()
+                .all(|&(len, _)| len == 3)
+        );
+        // Kana
+        assert_eq!(romanizer.romanize_vec("かな"), [(3, "ka")]);
+
+        // Reloading
+        for reading in ["にほんご", "にっぽんご"] {
+            let romanizer =
+                HepburnRomanizer::from_reader(format!("日本語\t{reading}").as_bytes()).unwrap();
+            let romaji = romanizer.romanize_kana_str_all(reading).unwrap();
+            assert_eq!(romanizer.romanize_vec("日本語")[0], (9, romaji.as_str()));
+        }
+    }
+
+    #[test]
+    fn malformed() {
+        let err = |s: &str| HepburnRomanizer::from_reader(s.as_bytes()).err().unwrap();
+        assert!(matches!(
+            err("日本語\tにほんご\n今日\n"),
+            DictionaryError::Malformed { line: 2, .. }
+        ));
+        assert!(matches!(
+            err("日本語\tnihongo"),
+            DictionaryError::Malformed { line: 1, .. }
+        ));
+        assert!(matches!(
+            err("\tにほんご"),
+            DictionaryError::Malformed { line: 1, .. }
+        ));
+        assert!(matches!(
+            err("日本語\tにほんご\n日本語\tにっぽんご"),
+            DictionaryError::Build(_)
+        ));
+        assert!(matches!(
+            HepburnRomanizer::from_reader(&[0xff][..]).err().unwrap(),
+            DictionaryError::Io(_)
+        ));
+    }
+}
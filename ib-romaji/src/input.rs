@@ -1,37 +1,134 @@
+use core::ops::{Bound, Range, RangeBounds};
+
 /**
+The input of romanization, i.e. a range of a haystack.
+
 Unfortunately, Japanese is highly contextual, surrounding charcaters
 are needed for accurate romanization.
 This struct can keep surrounding charcaters by storing the entire haystack
-and the start offset.
+and the range to romanize, like [`regex_automata::Input`](https://docs.rs/regex-automata/latest/regex_automata/struct.Input.html).
+
+Only the range is romanized, and the romanized lengths are relative to [`Input::start()`],
+while the haystack before and after the range is only used as the context,
+e.g. `々` at the start repeats the kanji before it.
+
+## Example
+```
+use ib_romaji::{HepburnRomanizer, Input};
+
+let romanizer = HepburnRomanizer::default();
+let haystack = "時々は日本語";
+
+// Without slicing the haystack
+let input = Input::new(haystack, 3).range(3..9);
+assert_eq!(input.as_ref(), "々は");
+assert!(romanizer.romanize_vec(input).contains(&(3, "toki")));
+// Words are not romanized beyond the range
+let romajis = romanizer.romanize_vec(Input::new(haystack, 9).range(..15));
+assert!(romajis.iter().all(|&(len, _)| len <= 6));
+
+// `&str` is the whole haystack
+let input = Input::from(haystack);
+assert_eq!((input.start(), input.end()), (0, haystack.len()));
+assert!(romanizer.is_romanizable(Input::new(haystack, 9)));
+```
 */
 #[derive(Clone, Copy, Debug)]
 pub struct Input<'h> {
     haystack: &'h str,
     start: usize,
+    end: usize,
 }
 
 impl<'h> Input<'h> {
+    /// `haystack[start..]`, with the haystack before `start` as the context.
+    ///
+    /// ## Panics
+    /// Panics if `start` is greater than the length of `haystack` or not at a char boundary.
     #[inline]
+    #[track_caller]
     pub fn new<H: ?Sized + AsRef<str>>(haystack: &'h H, start: usize) -> Self {
+        let haystack = haystack.as_ref();
+        Self::new_range(haystack, start..haystack.len())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn new_range(haystack: &'h str, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end
+                && haystack.is_char_boundary(range.start)
+                && haystack.is_char_boundary(range.end),
+            "invalid input range {range:?} for haystack of length {}",
+            haystack.len()
+        );
         Self {
-            haystack: haystack.as_ref(),
-            start,
+            haystack,
+            start: range.start,
+            end: range.end,
         }
     }
 
+    /// Set the range of the haystack to romanize, like [`regex_automata::Input::range()`](https://docs.rs/regex-automata/latest/regex_automata/struct.Input.html#method.range).
+    ///
+    /// The range is in the whole haystack, not relative to the current range.
+    ///
+    /// ## Panics
+    /// Panics if the range is out of the haystack or not at char boundaries.
+    #[inline]
+    #[track_caller]
+    pub fn range<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1).unwrap(),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).unwrap(),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.haystack.len(),
+        };
+        Self::new_range(self.haystack, start..end)
+    }
+
+    /// The whole haystack, including the context around the range.
     #[inline]
     pub fn haystack(&self) -> &'h str {
         self.haystack
     }
 
+    /// The start of the range in [`Input::haystack()`].
     #[inline]
     pub fn start(&self) -> usize {
         self.start
     }
 
+    /// The end (exclusive) of the range in [`Input::haystack()`].
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The range in [`Input::haystack()`].
+    #[inline]
+    pub fn get_range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Set the start of the range, keeping the end.
+    ///
+    /// ## Panics
+    /// Panics if `start` is greater than [`Input::end()`] or not at a char boundary.
+    #[inline]
+    #[track_caller]
+    pub fn set_start(&mut self, start: usize) {
+        *self = Self::new_range(self.haystack, start..self.end);
+    }
+
+    /// Whether the range is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.as_ref().is_empty()
+        self.start == self.end
     }
 }
 
@@ -42,7 +139,8 @@ impl<'h, H: ?Sized + AsRef<str>> From<&'h H> for Input<'h> {
 }
 
 impl<'h> AsRef<str> for Input<'h> {
+    /// The range of the haystack to romanize.
     fn as_ref(&self) -> &'h str {
-        &self.haystack[self.start..]
+        &self.haystack[self.start..self.end]
     }
 }
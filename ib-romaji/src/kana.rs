@@ -1,6 +1,93 @@
+/*!
+Kana utilities, including Japanese character classification.
+
+## Example
+```
+use ib_romaji::kana::{classify, is_kanji, KanaClass};
+
+assert_eq!(classify('か'), KanaClass::Hiragana);
+assert_eq!(classify('カ'), KanaClass::Katakana);
+assert_eq!(classify('ｶ'), KanaClass::HalfWidthKana);
+assert_eq!(classify('日'), KanaClass::Kanji);
+assert_eq!(classify('。'), KanaClass::Punctuation);
+assert_eq!(classify('a'), KanaClass::Other);
+assert!(is_kanji('々'));
+```
+*/
 use logos::Logos;
 
-use crate::HepburnRomanizer;
+use crate::{HepburnRomanizer, punctuation::PUNCTUATIONS, width};
+
+/// The class of a Japanese character, see [`classify()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KanaClass {
+    /// See [`is_hiragana()`].
+    Hiragana,
+    /// See [`is_katakana()`].
+    Katakana,
+    /// See [`is_half_width_kana()`](width::is_half_width_kana), excluding half-width punctuation.
+    HalfWidthKana,
+    /// See [`is_kanji()`].
+    Kanji,
+    /// Punctuation in [`PUNCTUATIONS`] and `・`.
+    Punctuation,
+    /// Not a Japanese character, or one not covered by the other classes (e.g. the sound marks `゛`, `゜`).
+    Other,
+}
+
+/// Whether `c` is a hiragana, including small ones (e.g. `ぁ`, `\u{1B150}`) and iteration marks (`ゝ`, `ゞ`).
+#[inline]
+pub const fn is_hiragana(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ゝ'..='ゟ' | '\u{1B132}' | '\u{1B150}'..='\u{1B152}')
+}
+
+/// Whether `c` is a full-width katakana, including small ones (e.g. `ァ`, `ㇰ`),
+/// the long vowel mark `ー` and iteration marks (`ヽ`, `ヾ`).
+///
+/// `・` is not included, see [`KanaClass::Punctuation`].
+/// For half-width katakana, see [`is_half_width_kana()`](width::is_half_width_kana).
+#[inline]
+pub const fn is_katakana(c: char) -> bool {
+    matches!(
+        c,
+        'ァ'..='ヺ' | 'ー'..='ヿ' | 'ㇰ'..='ㇿ' | '\u{1B155}' | '\u{1B164}'..='\u{1B167}'
+    )
+}
+
+/// Whether `c` is a kanji, i.e. a CJK unified or compatibility ideograph,
+/// or one of `々`, `〆` and `〇`.
+///
+/// Whether the kanji can be romanized depends on the embedded dictionary.
+#[inline]
+pub const fn is_kanji(c: char) -> bool {
+    matches!(c,
+        '々'..='〇'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}'
+        | '\u{30000}'..='\u{323AF}'
+    )
+}
+
+/// Classify a Japanese character.
+///
+/// Half-width punctuation (e.g. `｡`) is classified as [`KanaClass::Punctuation`].
+pub fn classify(c: char) -> KanaClass {
+    if is_hiragana(c) {
+        KanaClass::Hiragana
+    } else if is_katakana(c) {
+        KanaClass::Katakana
+    } else if is_kanji(c) {
+        KanaClass::Kanji
+    } else if c == '・' || PUNCTUATIONS.iter().any(|p| p.starts_with(c)) {
+        KanaClass::Punctuation
+    } else if width::is_half_width_kana(c) {
+        KanaClass::HalfWidthKana
+    } else {
+        KanaClass::Other
+    }
+}
 
 #[derive(Logos, Clone, Copy, Debug, PartialEq)]
 #[logos(utf8 = false)]
@@ -20,6 +107,14 @@ enum RomajiToken {
         |tcha|tchi|tcho|tchu
         |te|to|tsu|tta|tte|tto|ttsu|u|va|ve|vi|vo|vu|vva|vve|vvi|vvo|vvu|wa|we|wi|wo|ya|yo|yu|yya|yyo|yyu|za|ze|zo|zu|zza|zzo|zzu"
     )]
+    // Kunrei-shiki kanas that are different from Hepburn ones
+    #[cfg_attr(
+        feature = "kunrei",
+        regex(
+            "(?x)hhu|hu|si|ssi|ssya|ssyo|ssyu|sya|syo|syu|ti|tti|ttu|ttya|ttyo|ttyu|tu|tya|tye|tyo|tyu
+            |zi|zya|zyo|zyu|zzi|zzya|zzyo|zzyu"
+        )
+    )]
     Kana,
 
     #[token("'")]
@@ -36,7 +131,7 @@ impl HepburnRomanizer {
     pub const APOSTROPHE_STR: &str = "'";
 
     #[inline]
-    fn is_romaji_n_suffix(next: u8) -> bool {
+    pub(crate) fn is_romaji_n_suffix(next: u8) -> bool {
         matches!(next, b'a' | b'e' | b'i' | b'o' | b'u' | b'y')
     }
 
@@ -80,7 +175,7 @@ impl HepburnRomanizer {
     /// although maybe not fully.
     #[inline]
     pub fn is_romaji_kana_boundary(s: impl AsRef<[u8]>, index: usize) -> bool {
-        use std::cmp::Ordering;
+        use core::cmp::Ordering;
 
         let s = s.as_ref();
         debug_assert!(index < s.len());
@@ -241,4 +336,41 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn classify() {
+        use super::classify;
+
+        for c in "ぁあゖゝゞゟ\u{1B150}".chars() {
+            assert_eq!(classify(c), KanaClass::Hiragana, "{c}");
+        }
+        for c in "ァアヺーヽヾヿㇰㇿ\u{1B167}".chars() {
+            assert_eq!(classify(c), KanaClass::Katakana, "{c}");
+        }
+        for c in "ｦｧｱﾝﾞﾟｰ".chars() {
+            assert_eq!(classify(c), KanaClass::HalfWidthKana, "{c}");
+        }
+        for c in "日本語々〆〇㐀𠮟丽".chars() {
+            assert_eq!(classify(c), KanaClass::Kanji, "{c}");
+        }
+        for c in "、。・「」　｡｢､･".chars() {
+            assert_eq!(classify(c), KanaClass::Punctuation, "{c}");
+        }
+        for c in "a1 ゛゠〃Α".chars() {
+            assert_eq!(classify(c), KanaClass::Other, "{c}");
+        }
+
+        // All the embedded kanas
+        for kana in crate::data::kana::HEPBURN_KANAS {
+            for c in kana.chars() {
+                assert!(
+                    matches!(
+                        classify(c),
+                        KanaClass::Hiragana | KanaClass::Katakana | KanaClass::HalfWidthKana
+                    ) || matches!(c, '・' | '゛' | '゠'),
+                    "{kana}"
+                );
+            }
+        }
+    }
 }
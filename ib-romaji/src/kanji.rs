@@ -11,8 +11,56 @@ v0.2 added full support for single 々.
 Besides supporting more 々 usage, this also reduced the word dictionary size
 by 591 (0.69%) words and some word kanas.
 The word dictionary is still kept for 連濁 words (292) and words containing two 々 (only 9).
+
+### Rendaku
+々 often voices the repeated reading (連濁, rendaku), e.g. 人々 (`hitobito`) and 時々 (`tokidoki`).
+Common ones are covered by the word dictionary or the voiced readings in KANJIDIC/JMdict,
+but others (e.g. `teradera` for 寺々) are not.
+[`HepburnRomanizer::romanize_compound()`] additionally tries the [`rendaku()`] of each reading for 々.
+Other functions (e.g. [`HepburnRomanizer::romanize_and_try_for_each()`] and [`HepburnRomanizer::is_romanizable_to()`])
+only yield the readings as is, since they borrow the romajis instead of allocating voiced ones;
+ib-matcher can try the voiced ones with its `rendaku` option of the romaji match config.
+
+This is best-effort: whether rendaku happens can't be decided from the readings alone
+(e.g. 山々 is `yamayama`, not `yamazama`), so both are yielded.
+
+## On'yomi and kun'yomi
+Kanji readings can be filtered by their kinds with [`ReadingKind`],
+e.g. to only match kun'yomi in Japanese personal names.
+
+The kinds are tagged by the script of the readings in KANJIDIC:
+katakana ones as on'yomi (音読み), and hiragana ones (kun'yomi, nanori and readings from JMdict) as kun'yomi (訓読み).
+
+At the moment only the common kanji in `data/kanjidic-tagged.tsv` (e.g. 日, 人 and 月) are tagged,
+see [untagged kanji](#untagged-kanji).
+
+## Nanori
+Nanori (名乗り) are readings only used in names, e.g. `kusa` and `tachi` for 日.
+They are essential when searching names, but can cause odd matches in ordinary text.
+They can be excluded with [`ReadingFilter::nanori`].
+
+Readings that are also normal readings in KANJIDIC or JMdict are not nanori, and are never excluded.
+
+Like the kinds, only nanori of the kanji in `data/kanjidic-tagged-nanori.tsv` are tagged at the moment.
+
+## Reading ranks
+Common kanji can have many readings, e.g. 18 for 日, most of which are rare nanori (名乗り).
+[`ReadingFilter::max`] can limit the readings to the most common ones to reduce false positives and the search space.
+
+The readings are ranked by their order in KANJIDIC: on'yomi and kun'yomi first, then readings from JMdict, and nanori last.
+
+Like the kinds, only the kanji in `data/kanjidic-tagged.tsv` are ranked at the moment.
+
+## Untagged kanji
+The kinds, nanori and ranks are only tagged for the kanji in `data/kanjidic-tagged.tsv`,
+which are copied from the full KANJIDIC data.
+All readings of other kanji (e.g. 奈) pass any [`ReadingFilter`]:
+they are never filtered by [`ReadingKind`], [`ReadingFilter::nanori`] or [`ReadingFilter::max`].
+This keeps them matchable rather than dropping readings whose kinds are unknown.
 */
 
+use alloc::string::String;
+
 use ib_unicode::str::RoundCharBoundaryExt;
 
 use crate::{HepburnRomanizer, Input, data};
@@ -21,44 +69,207 @@ pub const NOMA: char = '々';
 pub const NOMA_STR: &str = "々";
 pub const NOMA_ROMAJI: &str = "noma";
 
+/// Voice the first consonant of a romaji as in rendaku (連濁), e.g. `toki` to `doki` and `hito` to `bito`.
+///
+/// Returns `None` if the romaji starts with a vowel or a consonant that can't be voiced.
+/// Both Hepburn and Kunrei-shiki romajis are supported, e.g. `shima` to `jima` and `sima` to `zima`.
+/// See [`kanji`](self#rendaku) for details.
+///
+/// ## Example
+/// ```
+/// use ib_romaji::kanji::rendaku;
+///
+/// assert_eq!(rendaku("saki").as_deref(), Some("zaki"));
+/// assert_eq!(rendaku("tsuki").as_deref(), Some("zuki"));
+/// assert_eq!(rendaku("fumi").as_deref(), Some("bumi"));
+/// assert_eq!(rendaku("yama"), None);
+/// ```
+pub fn rendaku(romaji: &str) -> Option<String> {
+    let (voiced, rest) = if let Some(rest) = romaji.strip_prefix("sh") {
+        ("j", rest)
+    } else if let Some(rest) = romaji.strip_prefix("ch") {
+        ("j", rest)
+    } else if let Some(rest) = romaji.strip_prefix("ts") {
+        ("z", rest)
+    } else if let [b't', b'i' | b'u' | b'y', ..] = romaji.as_bytes() {
+        // Kunrei-shiki ち, つ and ちゃ, whose voiced ones are ぢ `zi`, づ `zu` and ぢゃ `zya`
+        ("z", &romaji[1..])
+    } else {
+        let voiced = match romaji.as_bytes().first()? {
+            b'k' => "g",
+            b's' => "z",
+            b't' => "d",
+            b'h' | b'f' => "b",
+            _ => return None,
+        };
+        (voiced, &romaji[1..])
+    };
+    // A lone consonant, e.g. of a sokuon
+    if rest.is_empty() {
+        return None;
+    }
+    let mut buf = String::with_capacity(romaji.len());
+    buf.push_str(voiced);
+    buf.push_str(rest);
+    Some(buf)
+}
+
+/// The kinds of kanji readings to romanize.
+///
+/// See [`kanji`](self#onyomi-and-kunyomi) for details.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ReadingKind {
+    /// Both on'yomi and kun'yomi.
+    #[default]
+    All,
+    /// Only on'yomi (音読み), e.g. `nichi` and `jitsu` for 日.
+    OnOnly,
+    /// Only kun'yomi (訓読み), including nanori (名乗り), e.g. `hi` and `ka` for 日.
+    KunOnly,
+}
+
+/// Which kanji readings to romanize.
+///
+/// A [`ReadingKind`] can be converted into a filter with nanori and without the limit.
+///
+/// Readings of [untagged kanji](self#untagged-kanji) are never filtered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReadingFilter {
+    /// See [`kanji`](self#onyomi-and-kunyomi) for details.
+    pub kind: ReadingKind,
+    /// Only romanize the top `max` most common readings (of [`kind`](Self::kind)) of each kanji.
+    ///
+    /// `None` for all readings. See [`kanji`](self#reading-ranks) for details.
+    pub max: Option<usize>,
+    /// Whether to romanize nanori, i.e. readings only used in names.
+    ///
+    /// See [`kanji`](self#nanori) for details.
+    pub nanori: bool,
+}
+
+impl Default for ReadingFilter {
+    /// All readings, including nanori.
+    fn default() -> Self {
+        ReadingKind::All.into()
+    }
+}
+
+impl From<ReadingKind> for ReadingFilter {
+    fn from(kind: ReadingKind) -> Self {
+        Self {
+            kind,
+            max: None,
+            nanori: true,
+        }
+    }
+}
+
+impl ReadingFilter {
+    /// Bit `i` is set if the `i`-th romaji of `kanji` passes the filter.
+    ///
+    /// All bits are set for [untagged kanji](self#untagged-kanji).
+    fn mask(self, kanji: char) -> u64 {
+        let masks = data::kanji_reading_masks(kanji);
+        if masks == data::UNTAGGED_KANJI_MASKS {
+            return u64::MAX;
+        }
+        let mask = match (self.kind, masks) {
+            (ReadingKind::All, (_, _, nanori)) if !self.nanori => !nanori,
+            (ReadingKind::All, _) => u64::MAX,
+            (ReadingKind::OnOnly, (on, _, _)) => on,
+            (ReadingKind::KunOnly, (_, kun, nanori)) if !self.nanori => kun & !nanori,
+            (ReadingKind::KunOnly, (_, kun, _)) => kun,
+        };
+        let ranks = data::kanji_reading_ranks(kanji);
+        match self.max {
+            Some(max) => ranks
+                .iter()
+                .map(|&i| 1u64 << i)
+                .filter(|bit| mask & bit != 0)
+                .take(max)
+                .fold(0, |top, bit| top | bit),
+            None => mask,
+        }
+    }
+}
+
 impl HepburnRomanizer {
-    pub(crate) fn romanize_kanji_and_try_for_each<'h, S: Into<Input<'h>>, T>(
+    /// The romajis of `kanji` that pass the filter.
+    fn kanji_readings(
+        &self,
+        kanji: char,
+        readings: ReadingFilter,
+    ) -> impl Iterator<Item = &'static str> {
+        let mask = readings.mask(kanji);
+        self.kanji_romajis(kanji)
+            .iter()
+            .enumerate()
+            .filter(move |&(i, _)| mask >> i & 1 != 0)
+            .map(|(_, &romaji)| romaji)
+    }
+
+    /// Romanize the first kanji in the string, and yield its length and each of its readings.
+    ///
+    /// The length is always the UTF-8 length of the kanji.
+    /// Nothing is yielded if the first char is not a kanji (e.g. a kana).
+    /// This doesn't go through kanas or words, and works even if [`kanji`](crate::HepburnRomanizerBuilder::kanji) is not enabled,
+    /// which can be used to build custom segmenters.
+    ///
+    /// 々(noma) yields the readings of the preceding kanji in [`Input::haystack()`], followed by [`NOMA_ROMAJI`].
+    /// See [`kanji`](self#handling-of-noma) for details.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::{HepburnRomanizer, Input};
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// let readings: Vec<_> = romanizer.romanize_kanji("時間").collect();
+    /// assert!(readings.contains(&(3, "toki")));
+    /// assert!(readings.contains(&(3, "ji")));
+    /// assert_eq!(romanizer.romanize_kanji("じかん").next(), None);
+    ///
+    /// assert!(romanizer.romanize_kanji(Input::new("時々", 3)).any(|r| r == (3, "toki")));
+    /// ```
+    pub fn romanize_kanji<'h, S: Into<Input<'h>>>(
         &self,
         input: S,
-        mut f: impl FnMut(usize, &'static str) -> Option<T>,
-    ) -> Option<T> {
-        let input = input.into();
-        let s = input.as_ref();
-
-        // let s = unsafe { str::from_utf8_unchecked(s) };
-        if let Some(kanji) = s.chars().next() {
-            if kanji != NOMA {
-                // TODO: Binary search
-                for romaji in data::kanji_romajis(kanji) {
-                    // TODO: Always 3?
-                    if let Some(result) = f(kanji.len_utf8(), romaji) {
-                        return Some(result);
-                    }
-                }
-            } else {
-                // Noma is only used for kanji
-                if input.start() >= data::KANJI_MIN_LEN {
-                    let h = input.haystack();
-                    let i = h.floor_char_boundary_ib(input.start() - 1);
-                    let kanji = h[i..].chars().next().unwrap();
-                    for romaji in data::kanji_romajis(kanji) {
-                        if let Some(result) = f(NOMA.len_utf8(), romaji) {
-                            return Some(result);
-                        }
-                    }
-                }
-                if let Some(result) = f(NOMA.len_utf8(), NOMA_ROMAJI) {
-                    return Some(result);
-                }
+    ) -> impl Iterator<Item = (usize, &'static str)> {
+        self.romanize_kanji_readings(input.into(), ReadingKind::All.into())
+    }
+
+    fn romanize_kanji_readings(
+        &self,
+        input: Input,
+        readings: ReadingFilter,
+    ) -> impl Iterator<Item = (usize, &'static str)> {
+        let first = input.as_ref().chars().next();
+        let (kanji, noma) = match first {
+            // Noma is only used for kanji
+            Some(NOMA) if input.start() >= data::KANJI_MIN_LEN => {
+                let h = input.haystack();
+                let i = h.floor_char_boundary_ib(input.start() - 1);
+                (h[i..].chars().next(), true)
             }
-        }
+            Some(NOMA) => (None, true),
+            kanji => (kanji, false),
+        };
+        let len = first.map_or(0, char::len_utf8);
+        // TODO: Binary search
+        kanji
+            .into_iter()
+            .flat_map(move |kanji| self.kanji_readings(kanji, readings))
+            .chain(noma.then_some(NOMA_ROMAJI))
+            .map(move |romaji| (len, romaji))
+    }
 
-        None
+    pub(crate) fn romanize_kanji_and_try_for_each<'s, 'h, S: Into<Input<'h>>, T>(
+        &self,
+        input: S,
+        readings: ReadingFilter,
+        mut f: impl FnMut(usize, &'s str) -> Option<T>,
+    ) -> Option<T> {
+        self.romanize_kanji_readings(input.into(), readings)
+            .find_map(|(len, romaji)| f(len, romaji))
     }
 }
 
@@ -160,6 +371,189 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rendaku() {
+        assert_eq!(super::rendaku("kami").as_deref(), Some("gami"));
+        assert_eq!(super::rendaku("sato").as_deref(), Some("zato"));
+        assert_eq!(super::rendaku("shima").as_deref(), Some("jima"));
+        assert_eq!(super::rendaku("sima").as_deref(), Some("zima"));
+        assert_eq!(super::rendaku("tikara").as_deref(), Some("zikara"));
+        assert_eq!(super::rendaku("tuki").as_deref(), Some("zuki"));
+        assert_eq!(super::rendaku("tyawan").as_deref(), Some("zyawan"));
+        assert_eq!(super::rendaku("tera").as_deref(), Some("dera"));
+        assert_eq!(super::rendaku("chi").as_deref(), Some("ji"));
+        assert_eq!(super::rendaku("tsuki").as_deref(), Some("zuki"));
+        assert_eq!(super::rendaku("hito").as_deref(), Some("bito"));
+        assert_eq!(super::rendaku("fune").as_deref(), Some("bune"));
+        assert_eq!(super::rendaku("yama"), None);
+        assert_eq!(super::rendaku("gen"), None);
+        assert_eq!(super::rendaku("t"), None);
+        assert_eq!(super::rendaku(""), None);
+
+        let data = HepburnRomanizer::default();
+        let compound = |s, i| data.romanize_compound(Input::new(s, i));
+        assert!(!data.is_romanizable_to("寺々", "teradera"));
+        let romajis = compound("寺々", 3);
+        assert!(romajis.contains(&(3, "tera".into())));
+        assert!(romajis.contains(&(3, "dera".into())));
+        assert!(romajis.contains(&(3, NOMA_ROMAJI.into())));
+
+        // Both are yielded
+        let romajis = compound("山々", 3);
+        assert!(romajis.contains(&(3, "yama".into())));
+        assert!(romajis.contains(&(3, "san".into())));
+        assert!(romajis.contains(&(3, "zan".into())));
+
+        // With okurigana
+        assert!(compound("寺々は", 3).contains(&(6, "deraha".into())));
+
+        // Not 々
+        assert!(!compound("寺", 0).contains(&(3, "dera".into())));
+        assert_eq!(compound("々", 0), vec![(3, NOMA_ROMAJI.into())]);
+    }
+
+    fn romanize_readings<'s, 'h>(
+        data: &'s HepburnRomanizer,
+        s: impl Into<Input<'h>>,
+        readings: impl Into<ReadingFilter>,
+    ) -> Vec<(usize, &'s str)> {
+        let mut romajis = Vec::new();
+        data.romanize_readings_and_try_for_each(s, readings, |len, romaji| {
+            romajis.push((len, romaji));
+            None::<()>
+        });
+        romajis
+    }
+
+    #[test]
+    fn readings() {
+        let data = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        let romanize = |s, readings: ReadingKind| romanize_readings(&data, s, readings);
+
+        assert_eq!(romanize("日", ReadingKind::All), data.romanize_vec("日"));
+        assert_eq!(
+            romanize("日", ReadingKind::OnOnly),
+            vec![(3, "jitsu"), (3, "nichi")]
+        );
+        let kun = romanize("日", ReadingKind::KunOnly);
+        assert!(kun.contains(&(3, "hi")));
+        assert!(!kun.contains(&(3, "nichi")));
+        assert_eq!(kun.len() + 2, data.romanize_vec("日").len());
+        assert_eq!(
+            romanize("月", ReadingKind::OnOnly),
+            vec![(3, "gatsu"), (3, "getsu")]
+        );
+        assert!(!romanize("月", ReadingKind::KunOnly).contains(&(3, "getsu")));
+
+        // Untagged kanji are never filtered
+        assert_eq!(romanize("奈", ReadingKind::OnOnly), data.romanize_vec("奈"));
+
+        // 々
+        assert_eq!(
+            romanize_readings(&data, Input::new("山々", 3), ReadingKind::OnOnly),
+            vec![(3, "san"), (3, "sen"), (3, NOMA_ROMAJI)]
+        );
+
+        // Kanas are not filtered
+        assert_eq!(romanize("ひ", ReadingKind::OnOnly), vec![(3, "hi")]);
+    }
+
+    #[test]
+    fn max_readings() {
+        let data = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        let romanize = |s, kind, max| {
+            romanize_readings(
+                &data,
+                s,
+                ReadingFilter {
+                    kind,
+                    max,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            romanize("日", ReadingKind::All, None),
+            data.romanize_vec("日")
+        );
+        assert_eq!(
+            romanize("日", ReadingKind::All, Some(2)),
+            vec![(3, "jitsu"), (3, "nichi")]
+        );
+        assert_eq!(
+            romanize("日", ReadingKind::KunOnly, Some(2)),
+            vec![(3, "bi"), (3, "hi")]
+        );
+        assert_eq!(romanize("日", ReadingKind::All, Some(0)), vec![]);
+
+        assert_eq!(data.romanize_vec("学").len(), 4);
+        assert_eq!(
+            romanize("学", ReadingKind::All, Some(2)),
+            vec![(3, "gaku"), (3, "manabu")]
+        );
+        assert_eq!(
+            romanize("学", ReadingKind::KunOnly, Some(1)),
+            vec![(3, "manabu")]
+        );
+
+        // Untagged kanji are never limited
+        assert_eq!(
+            romanize("奈", ReadingKind::All, Some(1)),
+            data.romanize_vec("奈")
+        );
+    }
+
+    #[test]
+    fn nanori() {
+        let data = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        let romanize = |kind| {
+            romanize_readings(
+                &data,
+                "日",
+                ReadingFilter {
+                    kind,
+                    nanori: false,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let all = romanize(ReadingKind::All);
+        assert!(all.contains(&(3, "nichi")));
+        assert!(all.contains(&(3, "hi")));
+        assert!(!all.contains(&(3, "kusa")));
+        assert!(!all.contains(&(3, "tachi")));
+        assert_eq!(
+            romanize(ReadingKind::OnOnly),
+            vec![(3, "jitsu"), (3, "nichi")]
+        );
+        assert_eq!(
+            romanize(ReadingKind::KunOnly),
+            vec![(3, "bi"), (3, "hi"), (3, "ka")]
+        );
+    }
+
+    #[test]
+    fn romanize_kanji() {
+        let data = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        let kanji = |input| data.romanize_kanji(input).collect::<Vec<_>>();
+        assert_eq!(kanji("日本".into()), data.romanize_vec("日"));
+        assert_eq!(
+            kanji(Input::new("奈々", 3)),
+            data.romanize_vec(Input::new("奈々", 3))
+        );
+        assert_eq!(kanji("々".into()), vec![(3, NOMA_ROMAJI)]);
+        assert_eq!(kanji("𠮟る".into())[0].0, 4);
+        assert_eq!(kanji("にほん".into()), vec![]);
+        assert_eq!(kanji("".into()), vec![]);
+
+        // Not affected by the word dictionary or `kanji`
+        let data = HepburnRomanizer::builder().kana(true).word(true).build();
+        assert_eq!(data.romanize_kanji("日本").next(), Some((3, "a")));
+        assert_eq!(data.romanize_vec("日本"), vec![(6, "nippon")]);
+    }
+
     #[test]
     fn noma_word() {
         let data = HepburnRomanizer::default();
@@ -9,8 +9,13 @@ See [Romanization of Japanese](https://en.wikipedia.org/wiki/Romanization_of_Jap
   - [Hepburn romanization system](https://en.wikipedia.org/wiki/Hepburn_romanization)
   - Hepburn's [convenient IME variant](convert::hepburn_ime):
     `n'` and `tch*` can be alternatively written as `nn` and `cch*` respectively.
+  - [Kunrei-shiki romanization system](https://en.wikipedia.org/wiki/Kunrei-shiki_romanization)
+    (feature `kunrei`, see [`RomanizationSystem`])
 - Support handling of `n'` (n apostrophe, e.g. `n'ya` for `んや`).
 - Support [handling of 々(noma)](kanji#handling-of-々noma).
+- Support [filtering kanji readings](kanji#onyomi-and-kunyomi) by on'yomi and kun'yomi.
+- Support [reverse romanization](reverse) from romajis to kanas (feature `reverse`).
+- Support [normalization of half-width katakana](width) (e.g. `ｶﾀｶﾅ`).
 */
 //! ## Usage
 //! ```rust
@@ -31,6 +36,8 @@ See [Romanization of Japanese](https://en.wikipedia.org/wiki/Romanization_of_Jap
 //! ## Binary size
 //! The dictionary will take ~4.8 MiB (5.5 MiB without compression) in the binary at the moment.
 //!
+//! The dictionary can also be [loaded at runtime](dictionary), e.g. to update it without rebuilding.
+//!
 //! ## Design
 //! `&[&str]` will cause each str to occupy 16 extra bytes to store the pointer and length. While CStr only needs 1 byte for each str.
 //! - For words, this can save 3.14 MiB (actually 3.54 MiB).
@@ -38,30 +45,87 @@ See [Romanization of Japanese](https://en.wikipedia.org/wiki/Romanization_of_Jap
 //!   - `build()` time: `split()`/memchr +10%
 //! - And this way the str can also be compressed and then streamly decompressed.
 //!
+//! The same applies to kanji and word romajis, which can be stored as compressed blobs by the `compress-romajis` feature.
+//! Besides the pointers, each of them also needs a relocation entry in position-independent executables
+//! (24 bytes on x86_64 Linux), so `&[&str]` can take much more space than the strs themselves.
+//!
 //! ## Crate features
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(feature = "doc", doc = document_features::document_features!())]
+extern crate alloc;
+
+use alloc::{borrow::ToOwned, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::ops::Range;
+
 use bon::bon;
 use daachorse::{CharwiseDoubleArrayAhoCorasick, CharwiseDoubleArrayAhoCorasickBuilder, MatchKind};
 
 use ib_unicode::str::RoundCharBoundaryExt;
 
+use crate::{
+    convert::{hepburn_ime::TchStyle, long_vowel::LongVowelStyle},
+    kanji::{ReadingFilter, ReadingKind},
+    punctuation::PunctuationMode,
+    words::WordRomajis,
+};
+
+#[cfg(feature = "bench")]
+pub mod bench;
 #[cfg(feature = "cache")]
 pub mod cache;
 pub mod convert;
 pub mod data;
+#[cfg(feature = "std")]
+pub mod dictionary;
 mod input;
 pub mod kana;
 pub mod kanji;
+pub mod punctuation;
+#[cfg(feature = "reverse")]
+pub mod reverse;
+pub mod width;
+mod words;
 
+pub use daachorse;
 pub use input::Input;
 
+/// The romanization system used by [`HepburnRomanizer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RomanizationSystem {
+    /// [Hepburn romanization](https://en.wikipedia.org/wiki/Hepburn_romanization),
+    /// e.g. `shi`, `tsu`, `ji`.
+    #[default]
+    Hepburn,
+    /// [Kunrei-shiki romanization](https://en.wikipedia.org/wiki/Kunrei-shiki_romanization),
+    /// e.g. `si`, `tu`, `zi`.
+    ///
+    /// Kanji and word romajis are generated from Hepburn ones by [`convert::kunrei`],
+    /// so this doubles the size of the kanji and word romaji data in the binary.
+    #[cfg(feature = "kunrei")]
+    Kunrei,
+}
+
 /// [Hepburn romanization](https://en.wikipedia.org/wiki/Hepburn_romanization)
+///
+/// Other romanization systems can be used via [`HepburnRomanizerBuilder::system()`].
 #[derive(Clone)]
 pub struct HepburnRomanizer {
     // ac: AhoCorasick,
     ac: CharwiseDoubleArrayAhoCorasick<u32>,
     kanji: bool,
+    system: RomanizationSystem,
+    long_vowel: LongVowelStyle,
+    sokuon_before_ch: TchStyle,
+    normalize_kana: bool,
+    punctuation: PunctuationMode,
+    word_romajis: &'static [&'static [&'static str]],
+    extra_word_romajis: WordRomajis,
+    #[cfg(feature = "reverse")]
+    reverse: reverse::KanaIndex,
+    /// See [`cache`].
+    #[cfg(feature = "cache")]
+    words_fingerprint: u64,
 }
 
 #[bon]
@@ -72,6 +136,81 @@ impl HepburnRomanizer {
         #[builder(default = false, getter(vis = "pub(crate)"))] kana: bool,
         #[builder(default = false, getter(vis = "pub(crate)"))] kanji: bool,
         #[builder(default = false, getter(vis = "pub(crate)"))] word: bool,
+        /// The romanization system of the output romajis.
+        #[builder(default)]
+        system: RomanizationSystem,
+        /// How long vowels are written in the output romajis.
+        ///
+        /// Only affects [`romanize_styled_and_try_for_each()`](Self::romanize_styled_and_try_for_each).
+        /// See [`LongVowelStyle`] for details.
+        #[builder(default)]
+        long_vowel: LongVowelStyle,
+        /// How sokuon (`っ`) before `ch*` is written in the output romajis, e.g. `matcha` or `maccha` for `まっちゃ`.
+        ///
+        /// Only affects [`romanize_styled_and_try_for_each()`](Self::romanize_styled_and_try_for_each).
+        /// See [`TchStyle`] for details.
+        #[builder(default)]
+        sokuon_before_ch: TchStyle,
+        /// Normalize half-width katakana (e.g. `ｶﾀｶﾅ`) to full-width ones (e.g. `カタカナ`) before romanization,
+        /// so that words containing them can also be romanized.
+        ///
+        /// No allocation is needed if the input doesn't contain half-width katakana.
+        /// See [`width`] for details.
+        #[builder(default = false)]
+        normalize_kana: bool,
+        /// How punctuation is handled by [`romanize_kana_iter()`](Self::romanize_kana_iter) and the functions based on it.
+        ///
+        /// Default: only `、` is kept. See [`PunctuationMode`] for details.
+        #[builder(default)]
+        punctuation: PunctuationMode,
+        /// Extra words and their romajis, in addition to the embedded word dictionary,
+        /// e.g. domain-specific terms like product names.
+        ///
+        /// The romajis should be in the [`system`](HepburnRomanizerBuilder::system) of the romanizer.
+        /// They are added even if [`word`](HepburnRomanizerBuilder::word) is not enabled.
+        /// If a word is also in the embedded dictionary (or given multiple times),
+        /// its romajis are appended to the existing ones.
+        /// Words longer than [`data::WORD_MAX_LEN`] are ignored,
+        /// and so are kanas (e.g. `きょ`) if [`kana`](HepburnRomanizerBuilder::kana) is enabled,
+        /// since a kana is always romanized as its own romaji.
+        ///
+        /// The romajis are owned by the romanizer, so they borrow from it when romanized.
+        ///
+        /// ## Example
+        /// ```
+        /// use ib_romaji::HepburnRomanizer;
+        ///
+        /// let romanizer = HepburnRomanizer::builder()
+        ///     .kana(true)
+        ///     .kanji(true)
+        ///     .word(true)
+        ///     .extra_words([("葬送", ["sousou"]), ("日本語", ["nihongo"])])
+        ///     .build();
+        /// assert_eq!(romanizer.romanize_vec("葬送")[0], (6, "sousou"));
+        /// assert_eq!(romanizer.romanize_vec("日本語")[..2], [(9, "nippongo"), (9, "nihongo")]);
+        /// ```
+        #[builder(default, with = |words: impl IntoIterator<Item = (impl AsRef<str>, impl IntoIterator<Item = impl AsRef<str>>)>| {
+            let mut map = BTreeMap::<String, Vec<String>>::new();
+            for (word, romajis) in words {
+                let word = word.as_ref();
+                if word.is_empty() || word.len() > data::WORD_MAX_LEN {
+                    continue;
+                }
+                let entry = map.entry(word.to_owned()).or_default();
+                for romaji in romajis {
+                    let romaji = romaji.as_ref();
+                    if !entry.iter().any(|r| r == romaji) {
+                        entry.push(romaji.to_owned());
+                    }
+                }
+            }
+            map
+        })]
+        extra_words: BTreeMap<String, Vec<String>>,
+        /// See [`build_cached()`](HepburnRomanizerBuilder::build_cached).
+        #[cfg(all(feature = "cache", feature = "std"))]
+        #[builder(setters(vis = ""))]
+        cache_path: Option<std::path::PathBuf>,
     ) -> Self {
         // // let start = UnsafeCell::new(0);
         // let mut start = 0;
@@ -108,94 +247,487 @@ impl HepburnRomanizer {
         // }
         // .unwrap();
 
-        let ac =
-            CharwiseDoubleArrayAhoCorasickBuilder::new().match_kind(MatchKind::LeftmostLongest);
-        let ac = match (kana, word) {
-            (true, true) => ac.build(data::kana::HEPBURN_KANAS.iter().cloned().chain(words)),
-            (true, false) => ac.build(data::kana::HEPBURN_KANAS),
-            (false, true) => ac.build(words),
-            (false, false) => ac.build([] as [&str; 0]),
+        #[cfg(feature = "cache")]
+        let words_fingerprint = Self::words_fingerprint(kana, word, &extra_words);
+        #[cfg(all(feature = "cache", feature = "std"))]
+        if let Some(romanizer) = cache_path.as_ref().and_then(Self::from_cache) {
+            // Only use the cache if it's built with the same config
+            if romanizer.words_fingerprint == words_fingerprint
+                && romanizer.kanji == kanji
+                && romanizer.system == system
+                && romanizer.long_vowel == long_vowel
+                && romanizer.sokuon_before_ch == sokuon_before_ch
+                && romanizer.normalize_kana == normalize_kana
+                && romanizer.punctuation == punctuation
+            {
+                return romanizer;
+            }
+        }
+        let word_romajis = Self::builtin_word_romajis(system);
+        // Sorted
+        let extra_words: Vec<(String, Vec<String>)> = extra_words
+            .into_iter()
+            // Kanas are already in the automaton
+            .filter(|(word, _)| !(kana && data::kana::HEPBURN_KANAS.contains(&word.as_str())))
+            .collect();
+        // The indices of the extra words in the embedded dictionary, which are excluded from it
+        let mut builtin_indices = vec![None; extra_words.len()];
+        let builtin_words = word.then_some(words).into_iter().flatten().enumerate();
+        let builtin_words = builtin_words.filter(|&(i, word)| {
+            match extra_words.binary_search_by(|(w, _)| w.as_str().cmp(word)) {
+                Ok(j) => {
+                    builtin_indices[j] = Some(i);
+                    false
+                }
+                Err(_) => true,
+            }
+        });
+        let extra = extra_words
+            .iter()
+            .enumerate()
+            .map(|(j, (word, _))| (word_romajis.len() + j, word.as_str()));
+        let ac = Self::build_ac(kana, builtin_words.chain(extra)).unwrap();
+
+        let mut extra_word_romajis = WordRomajis::default();
+        for ((_, romajis), i) in extra_words.into_iter().zip(builtin_indices) {
+            let builtin = i.map_or(&[][..], |i| word_romajis[i]);
+            let extra = romajis
+                .into_iter()
+                .filter(|romaji| !builtin.contains(&romaji.as_str()))
+                .map(String::into_boxed_str);
+            extra_word_romajis.push(builtin.iter().map(|&romaji| romaji.into()).chain(extra));
+        }
+        extra_word_romajis.shrink_to_fit();
+
+        let romanizer = Self {
+            #[cfg(feature = "cache")]
+            words_fingerprint,
+            ..Self::with_ac(
+                ac,
+                kanji,
+                system,
+                long_vowel,
+                sokuon_before_ch,
+                normalize_kana,
+                punctuation,
+                word_romajis,
+                extra_word_romajis,
+            )
+        };
+        // Decompress the kanji romajis on build rather than on the first romanization
+        #[cfg(feature = "compress-romajis")]
+        if kanji {
+            romanizer.kanji_romajis('一');
+        }
+        #[cfg(all(feature = "cache", feature = "std"))]
+        if let Some(cache_path) = cache_path {
+            // Ignore errors
+            let _ = romanizer.to_cache(cache_path);
+        }
+        romanizer
+    }
+
+    /// [`HepburnRomanizer::default()`], but with `words` and their romajis instead of the embedded word dictionary,
+    /// e.g. to use a custom word list or measure the cost of one (see the `bench` feature).
+    ///
+    /// The words are handled the same as [`extra_words`](HepburnRomanizerBuilder::extra_words),
+    /// i.e. duplicates are merged, and too long words and kanas are ignored.
+    /// To load words with kana readings instead of romajis, see [`dictionary`](crate::dictionary).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::build_from_words([("日本語", ["nihongo", "nippongo"])]);
+    /// assert_eq!(romanizer.romanize_vec("日本語")[..2], [(9, "nihongo"), (9, "nippongo")]);
+    /// // Not in the words
+    /// assert!(romanizer.romanize_vec("今日").iter().all(|&(len, _)| len == 3));
+    /// ```
+    pub fn build_from_words(
+        words: impl IntoIterator<Item = (impl AsRef<str>, impl IntoIterator<Item = impl AsRef<str>>)>,
+    ) -> Self {
+        Self::builder()
+            .kana(true)
+            .kanji(true)
+            .extra_words(words)
+            .build()
+    }
+
+    /// Build the automaton from kanas (if `kana`) and `words` with their indices.
+    ///
+    /// ## Indexing
+    /// The value of each pattern in the automaton indexes the romaji tables, with `K` being the number of kanas
+    /// and `W` being the length of `word_romajis` (the embedded dictionary, or empty for a [runtime one](crate::dictionary)):
+    /// - `0..K`: kanas, indexing [`kana_romajis()`](Self::kana_romajis).
+    ///   `K` is always the number of all kanas, even if `kana` is not enabled.
+    /// - `K..K+W`: words, indexing `word_romajis`.
+    ///   Words that are also extra words are excluded, and the indices of other words are kept.
+    /// - `K+W..`: extra words or the words of a runtime dictionary, indexing `extra_word_romajis`.
+    ///
+    /// See also [`automaton()`](Self::automaton).
+    ///
+    /// `words` yields `(index - K, word)`.
+    pub(crate) fn build_ac<'w>(
+        kana: bool,
+        words: impl IntoIterator<Item = (usize, &'w str)>,
+    ) -> Result<CharwiseDoubleArrayAhoCorasick<u32>, daachorse::errors::DaachorseError> {
+        let kanas = data::kana::HEPBURN_KANAS;
+        CharwiseDoubleArrayAhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build_with_values(
+                kana.then_some(kanas)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .map(|(i, &kana)| (kana, i as u32))
+                    .chain(
+                        words
+                            .into_iter()
+                            .map(|(i, word)| (word, (kanas.len() + i) as u32)),
+                    ),
+            )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn with_ac(
+        ac: CharwiseDoubleArrayAhoCorasick<u32>,
+        kanji: bool,
+        system: RomanizationSystem,
+        long_vowel: LongVowelStyle,
+        sokuon_before_ch: TchStyle,
+        normalize_kana: bool,
+        punctuation: PunctuationMode,
+        word_romajis: &'static [&'static [&'static str]],
+        extra_word_romajis: WordRomajis,
+    ) -> Self {
+        Self {
+            ac,
+            kanji,
+            system,
+            long_vowel,
+            sokuon_before_ch,
+            normalize_kana,
+            punctuation,
+            word_romajis,
+            extra_word_romajis,
+            #[cfg(feature = "reverse")]
+            reverse: reverse::KanaIndex::new(system),
+            #[cfg(feature = "cache")]
+            words_fingerprint: 0,
+        }
+    }
+
+    /// The romanization system of the output romajis.
+    pub fn system(&self) -> RomanizationSystem {
+        self.system
+    }
+
+    /// How long vowels are written in the output romajis.
+    pub fn long_vowel(&self) -> LongVowelStyle {
+        self.long_vowel
+    }
+
+    /// How sokuon (`っ`) before `ch*` is written in the output romajis.
+    pub fn sokuon_before_ch(&self) -> TchStyle {
+        self.sokuon_before_ch
+    }
+
+    /// Whether half-width katakana are normalized before romanization.
+    pub fn normalize_kana(&self) -> bool {
+        self.normalize_kana
+    }
+
+    /// How punctuation is handled when romanizing kanas.
+    pub fn punctuation(&self) -> &PunctuationMode {
+        &self.punctuation
+    }
+
+    /// The maximum length of input that can be romanized as a single word by
+    /// [`romanize_and_try_for_each()`](Self::romanize_and_try_for_each).
+    ///
+    /// This can be used to size a sliding window over a long text.
+    /// It is [`data::WORD_MAX_LEN`], or twice of it if [`normalize_kana`](HepburnRomanizerBuilder::normalize_kana)
+    /// is enabled since half-width kanas may be longer (e.g. `ｶﾞ`).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// let s = "日本語";
+    /// let window = &s[..s.len().min(romanizer.max_word_len())];
+    /// assert_eq!(romanizer.romanize_vec(window)[0], (9, "nippongo"));
+    /// ```
+    pub fn max_word_len(&self) -> usize {
+        if self.normalize_kana {
+            2 * data::WORD_MAX_LEN
+        } else {
+            data::WORD_MAX_LEN
+        }
+    }
+
+    /// The automaton of kanas (if [`kana`](HepburnRomanizerBuilder::kana)) and words,
+    /// for custom integrations, e.g. a tokenizer with its own match kind or anchoring.
+    ///
+    /// The automaton is built with [`MatchKind::LeftmostLongest`],
+    /// so only the `leftmost_*` methods can be used.
+    /// The value of each pattern can be mapped to its romajis by [`pattern_romajis()`](Self::pattern_romajis).
+    ///
+    /// ## Value space
+    /// With `K` being the number of all kanas (regardless of `kana`) and `W` the number of dictionary words:
+    /// - `0..K`: kanas.
+    /// - `K..K+W`: words in the embedded dictionary.
+    /// - `K+W..`: [extra words](HepburnRomanizerBuilder::extra_words) or the words of a [runtime dictionary](crate::dictionary).
+    ///
+    /// Kanjis are not in the automaton, see [`kanji`](crate::kanji).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// let romajis: Vec<_> = romanizer
+    ///     .automaton()
+    ///     .leftmost_find_iter("あの日本語")
+    ///     .map(|m| (m.start(), romanizer.pattern_romajis(m.value()).collect::<Vec<_>>()))
+    ///     .collect();
+    /// assert_eq!(romajis, [(0, vec!["a"]), (3, vec!["no"]), (6, vec!["nippongo"])]);
+    /// ```
+    pub fn automaton(&self) -> &CharwiseDoubleArrayAhoCorasick<u32> {
+        &self.ac
+    }
+
+    /// The romajis of the pattern with `value` in the [`automaton()`](Self::automaton).
+    ///
+    /// A kana has only one romaji. Vowels lengthened by a following `ー` are not handled,
+    /// unlike [`romanize_kana()`](Self::romanize_kana).
+    /// Values not from the automaton yield no romajis.
+    pub fn pattern_romajis(&self, value: u32) -> impl Iterator<Item = &str> + Clone {
+        let pattern = value as usize;
+        let kana_romajis = self.kana_romajis();
+        let (romajis, extra_romajis): (&[&str], &[Box<str>]) = match kana_romajis.get(pattern) {
+            Some(romaji) => (core::slice::from_ref(romaji), &[]),
+            None => {
+                let i = pattern - kana_romajis.len();
+                match self.word_romajis.get(i) {
+                    Some(romajis) => (romajis, &[]),
+                    None => (
+                        &[],
+                        self.extra_word_romajis
+                            .get(i - self.word_romajis.len())
+                            .unwrap_or_default(),
+                    ),
+                }
+            }
+        };
+        romajis
+            .iter()
+            .copied()
+            .chain(extra_romajis.iter().map(|romaji| &**romaji))
+    }
+
+    #[inline]
+    fn kana_romajis(&self) -> &'static [&'static str] {
+        match self.system {
+            RomanizationSystem::Hepburn => data::kana::HEPBURN_ROMAJIS,
+            #[cfg(feature = "kunrei")]
+            RomanizationSystem::Kunrei => data::kana::KUNREI_ROMAJIS,
+        }
+    }
+
+    /// The `i`-th kana romaji lengthened by a following `ー`.
+    #[inline]
+    fn kana_lengthened_romaji(&self, i: usize) -> Option<&'static str> {
+        match self.system {
+            RomanizationSystem::Hepburn => data::lengthened::hepburn(i),
+            #[cfg(feature = "kunrei")]
+            RomanizationSystem::Kunrei => data::lengthened::kunrei(i),
+        }
+    }
+
+    pub(crate) fn builtin_word_romajis(
+        system: RomanizationSystem,
+    ) -> &'static [&'static [&'static str]] {
+        match system {
+            RomanizationSystem::Hepburn => data::word_romajis(),
+            #[cfg(feature = "kunrei")]
+            RomanizationSystem::Kunrei => data::kunrei_word_romajis(),
         }
-        .unwrap();
+    }
 
-        Self { ac, kanji }
+    #[inline]
+    pub(crate) fn kanji_romajis(&self, kanji: char) -> &'static [&'static str] {
+        match self.system {
+            RomanizationSystem::Hepburn => data::kanji_romajis(kanji),
+            #[cfg(feature = "kunrei")]
+            RomanizationSystem::Kunrei => data::kunrei_kanji_romajis(kanji),
+        }
     }
 
     /// Romanize the first kana in the string, and return the length of the kana and the romaji.
     ///
     /// ## Notes
     /// - n apostrophe is properly handled in this function.
+    /// - The long vowel mark `ー` following the kana is included and lengthens its vowel, e.g. `カー` to `kaa`.
+    ///   A standalone `ー` is romanized as `-`.
     ///
     /// ## Example
     /// ```
     /// use ib_romaji::HepburnRomanizer;
     ///
-    /// assert_eq!(HepburnRomanizer::builder().kana(true).build().romanize_kana("あ"), Some((3, "a")));
+    /// let romanizer = HepburnRomanizer::builder().kana(true).build();
+    /// assert_eq!(romanizer.romanize_kana("あ"), Some((3, "a")));
+    /// assert_eq!(romanizer.romanize_kana("カー"), Some((6, "kaa")));
     /// ```
-    /// TODO: Iter
     pub fn romanize_kana<S: ?Sized + AsRef<str>>(&self, s: &S) -> Option<(usize, &'static str)> {
         let s = s.as_ref();
-        let s = &s[..s.floor_char_boundary_ib(data::kana::KANA_MAX_LEN)];
         // let m = self.ac.find(Input::new(s).anchored(Anchored::Yes))?;
         // let pattern = m.pattern().as_usize();
         let m = self
             .ac
-            .leftmost_find_iter(s)
+            .leftmost_find_iter(&s[..s.floor_char_boundary_ib(data::kana::KANA_MAX_LEN)])
             .next()
             .filter(|m| m.start() == 0)?;
         let pattern = m.value() as usize;
         let len = m.end() - m.start();
-        data::kana::HEPBURN_ROMAJIS
+        if let Some(romaji) = self.lengthen_kana(&s[len..], pattern) {
+            return Some((len + Self::LONG_VOWEL_MARK.len_utf8(), romaji));
+        }
+        self.kana_romajis()
             .get(pattern)
             .map(|&romaji| (len, romaji))
     }
 
+    /// Romanize kanas from the beginning of the string until a non-kana character, and yield the length of each kana and its romaji.
+    ///
+    /// Punctuation is handled according to [`punctuation`](HepburnRomanizerBuilder::punctuation),
+    /// by default only `、` is yielded as is.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::builder().kana(true).build();
+    /// assert_eq!(
+    ///     romanizer.romanize_kana_iter("ジョジョ日").collect::<Vec<_>>(),
+    ///     vec![(6, "jo"), (6, "jo")]
+    /// );
+    /// ```
+    pub fn romanize_kana_iter<'s, S: ?Sized + AsRef<str>>(
+        &'s self,
+        s: &'s S,
+    ) -> impl Iterator<Item = (usize, &'s str)> + 's {
+        self.romanize_kana_iter_with(s.as_ref(), Some(&self.punctuation))
+    }
+
+    /// [`romanize_kana_iter()`](Self::romanize_kana_iter), but stops at any punctuation if `punctuation` is `None`.
+    fn romanize_kana_iter_with<'s>(
+        &'s self,
+        mut s: &'s str,
+        punctuation: Option<&'s PunctuationMode>,
+    ) -> impl Iterator<Item = (usize, &'s str)> + 's {
+        core::iter::from_fn(move || {
+            if s.is_empty() {
+                return None;
+            }
+            let (len, romaji) = self.romanize_kana(s).or_else(|| punctuation?.romanize(s))?;
+            s = &s[len..];
+            Some((len, romaji))
+        })
+    }
+
     /// Romanize kanas from the beginning of the string until a non-kana character, and return the length of the kanas and the romajis.
+    ///
+    /// ## See also
+    /// - [`romanize_kana_iter()`](Self::romanize_kana_iter) for a version that doesn't allocate.
+    /// - [`romanize_kana_str_into()`](Self::romanize_kana_str_into) for a version that reuses a buffer.
     pub fn romanize_kana_str<S: ?Sized + AsRef<str>>(&self, s: &S) -> Option<(usize, String)> {
-        let s = s.as_ref();
-        let mut len = 0;
         let mut buf = String::new();
-        while let Some((l, romaji)) = self.romanize_kana(&s[len..]).or_else(|| {
-            if s[len..].starts_with("、") {
-                Some((3, "、"))
-            } else {
-                None
-            }
-        }) {
+        self.romanize_kana_str_into(s, &mut buf)
+            .map(|len| (len, buf))
+    }
+
+    /// [`romanize_kana_str()`](Self::romanize_kana_str), but append the romajis to `buf` and only return the length of the kanas,
+    /// so that one buffer can be reused across calls.
+    ///
+    /// `buf` is not changed if `None` is returned.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::builder().kana(true).build();
+    /// let mut buf = String::new();
+    /// for s in ["ジョジョ", "かな"] {
+    ///     buf.clear();
+    ///     romanizer.romanize_kana_str_into(s, &mut buf).unwrap();
+    ///     println!("{buf}");
+    /// }
+    /// assert_eq!(romanizer.romanize_kana_str_into("ひらがな日", &mut buf), Some(12));
+    /// assert_eq!(buf, "kanahiragana");
+    /// ```
+    pub fn romanize_kana_str_into<S: ?Sized + AsRef<str>>(
+        &self,
+        s: &S,
+        buf: &mut String,
+    ) -> Option<usize> {
+        let mut len = 0;
+        for (l, romaji) in self.romanize_kana_iter(s) {
             len += l;
             buf.push_str(romaji);
-            if len >= s.len() {
-                return Some((len, buf));
-            }
         }
-        if len == 0 { None } else { Some((len, buf)) }
+        if len == 0 { None } else { Some(len) }
     }
 
-    /// Romanize kana text to romajis. Returns `None` if there is any non-kana character in the string.
+    /// Romanize kana text to romajis. Returns `None` if there is any non-kana character in the string,
+    /// except punctuation handled by [`punctuation`](HepburnRomanizerBuilder::punctuation).
+    ///
+    /// ## See also
+    /// [`romanize_kana_str_all_into()`](Self::romanize_kana_str_all_into) for a version that reuses a buffer.
     pub fn romanize_kana_str_all<S: ?Sized + AsRef<str>>(&self, s: &S) -> Option<String> {
+        let mut buf = String::new();
+        self.romanize_kana_str_all_into(s, &mut buf).then_some(buf)
+    }
+
+    /// [`romanize_kana_str_all()`](Self::romanize_kana_str_all), but append the romajis to `buf`.
+    ///
+    /// Returns `false` and leaves `buf` unchanged if there is any non-kana character in the string.
+    pub fn romanize_kana_str_all_into<S: ?Sized + AsRef<str>>(
+        &self,
+        s: &S,
+        buf: &mut String,
+    ) -> bool {
         let s = s.as_ref();
-        match self.romanize_kana_str(s) {
-            Some((len, buf)) if len == s.len() => Some(buf),
-            _ => None,
+        let buf_len = buf.len();
+        match self.romanize_kana_str_into(s, buf) {
+            Some(len) if len == s.len() => true,
+            _ => {
+                buf.truncate(buf_len);
+                false
+            }
         }
     }
 
     /// Romanize the first word in the string, and call `f` for each possible romanization.
     ///
     /// `f` can return `Some(_)` to stop the iteration, or `None` to continue.
+    /// The romajis borrow from the romanizer, since [extra words](HepburnRomanizerBuilder::extra_words) are owned by it.
     ///
     /// ## Notes
     /// - While n apostrophe (e.g. `n'a`) in words is handled,
     ///   n apostrophe at start/end is not handled in this function for performance reason.
     ///
     ///   You should call [HepburnRomanizer::need_apostrophe(last_romaji, romaji)](HepburnRomanizer::need_apostrophe) to check and insert the apostrophe.
+    /// - A kana followed by the long vowel mark `ー` is first romanized with its vowel lengthened (e.g. `(6, "kaa")` for `カー`),
+    ///   and then as the kana alone (`(3, "ka")`), after which `ー` is romanized as `-`.
     ///
     /// ## Example
     /// ```
     /// use ib_romaji::HepburnRomanizer;
     ///
+    /// let romanizer = HepburnRomanizer::default();
     /// let mut romajis = Vec::new();
-    /// HepburnRomanizer::default().romanize_and_try_for_each("日本語", |len, romaji| {
+    /// romanizer.romanize_and_try_for_each("日本語", |len, romaji| {
     ///     romajis.push((len, romaji));
     ///     None::<()>
     /// });
@@ -204,47 +736,192 @@ impl HepburnRomanizer {
     ///
     /// ## See also
     /// [`romanize_vec()`](Self::romanize_vec) for a version that returns a `Vec` of all possible romanizations.
-    pub fn romanize_and_try_for_each<'h, S: Into<Input<'h>>, T>(
-        &self,
+    pub fn romanize_and_try_for_each<'s, 'h, S: Into<Input<'h>>, T>(
+        &'s self,
+        input: S,
+        f: impl FnMut(usize, &'s str) -> Option<T>,
+    ) -> Option<T> {
+        self.romanize_readings_and_try_for_each(input, ReadingKind::All, f)
+    }
+
+    /// [`romanize_and_try_for_each()`](Self::romanize_and_try_for_each),
+    /// but only kanji readings that pass the filter are romanized,
+    /// e.g. of the given [`ReadingKind`] or only the most common ones.
+    ///
+    /// Kanas and words are not affected. See [`kanji`](kanji#onyomi-and-kunyomi) for details.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::{kanji::{ReadingFilter, ReadingKind}, HepburnRomanizer};
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// let mut romajis = Vec::new();
+    /// romanizer.romanize_readings_and_try_for_each("日", ReadingKind::OnOnly, |len, romaji| {
+    ///     romajis.push((len, romaji));
+    ///     None::<()>
+    /// });
+    /// assert_eq!(romajis, vec![(3, "jitsu"), (3, "nichi")]);
+    ///
+    /// let mut romajis = Vec::new();
+    /// romanizer.romanize_readings_and_try_for_each(
+    ///     "日",
+    ///     ReadingFilter { max: Some(3), ..Default::default() },
+    ///     |len, romaji| {
+    ///         romajis.push((len, romaji));
+    ///         None::<()>
+    ///     },
+    /// );
+    /// assert_eq!(romajis, vec![(3, "hi"), (3, "jitsu"), (3, "nichi")]);
+    /// ```
+    pub fn romanize_readings_and_try_for_each<'s, 'h, S: Into<Input<'h>>, T>(
+        &'s self,
         input: S,
-        mut f: impl FnMut(usize, &'static str) -> Option<T>,
+        readings: impl Into<ReadingFilter>,
+        mut f: impl FnMut(usize, &'s str) -> Option<T>,
     ) -> Option<T> {
+        let readings = readings.into();
         let input = input.into();
-        let s = input.as_ref();
-        let s = &s[..s.floor_char_boundary_ib(data::WORD_MAX_LEN)];
+        if let Some(result) = self.romanize_kana_or_word_and_try_for_each(input.as_ref(), &mut f) {
+            return Some(result);
+        }
+
+        if self.kanji {
+            if let Some(result) = self.romanize_kanji_and_try_for_each(input, readings, f) {
+                return Some(result);
+            }
+        }
+
+        None
+    }
+
+    /// Romanize the first kana or word in `s`, normalizing half-width kanas if enabled.
+    fn romanize_kana_or_word_and_try_for_each<'s, T>(
+        &'s self,
+        s: &str,
+        f: &mut impl FnMut(usize, &'s str) -> Option<T>,
+    ) -> Option<T> {
+        // Half-width kanas are at most 2 times longer than full-width ones (e.g. ｶﾞ)
+        let window = &s[..s.floor_char_boundary_ib(self.max_word_len())];
+        if self.normalize_kana && window.chars().any(width::is_half_width_kana) {
+            let mut normalized = String::new();
+            let mut ends = Vec::new();
+            width::normalize_into(window, data::WORD_MAX_LEN, &mut normalized, &mut ends);
+            let normalized = &normalized[..normalized.floor_char_boundary_ib(data::WORD_MAX_LEN)];
+            if let Some(result) =
+                self.romanize_word_and_try_for_each(normalized, &mut |len, romaji| {
+                    let i = ends.binary_search_by_key(&len, |&(end, _)| end).unwrap();
+                    f(ends[i].1, romaji)
+                })
+            {
+                return Some(result);
+            }
+        } else if let Some(result) = self
+            .romanize_word_and_try_for_each(&s[..s.floor_char_boundary_ib(data::WORD_MAX_LEN)], f)
+        {
+            return Some(result);
+        }
+        None
+    }
 
+    /// Romanize the first kana or word in `s` by the automaton.
+    fn romanize_word_and_try_for_each<'s, T>(
+        &'s self,
+        s: &str,
+        f: &mut impl FnMut(usize, &'s str) -> Option<T>,
+    ) -> Option<T> {
         // self.ac.find(Input::new(s).anchored(Anchored::Yes))
-        if let Some(m) = self
+        let m = self
             .ac
             .leftmost_find_iter(s)
             .next()
-            .filter(|m| m.start() == 0)
-        {
-            // let pattern = m.pattern().as_usize();
-            let pattern = m.value() as usize;
-            let len = m.end() - m.start();
-            if pattern < data::kana::HEPBURN_ROMAJIS.len() {
-                let romaji = data::kana::HEPBURN_ROMAJIS[pattern];
+            .filter(|m| m.start() == 0)?;
+        // let pattern = m.pattern().as_usize();
+        let pattern = m.value() as usize;
+        let len = m.end() - m.start();
+        let kana_romajis = self.kana_romajis();
+        if pattern < kana_romajis.len() {
+            if let Some(result) = self
+                .lengthen_kana(&s[len..], pattern)
+                .and_then(|romaji| f(len + Self::LONG_VOWEL_MARK.len_utf8(), romaji))
+            {
+                return Some(result);
+            }
+            let romaji = kana_romajis[pattern];
+            if let Some(result) = f(len, romaji) {
+                return Some(result);
+            }
+        } else {
+            // TODO: Binary search
+            for romaji in self.pattern_romajis(m.value()) {
                 if let Some(result) = f(len, romaji) {
                     return Some(result);
                 }
-            } else if pattern < data::kana::HEPBURN_ROMAJIS.len() + data::WORD_ROMAJIS.len() {
-                // TODO: Binary search
-                for romaji in data::WORD_ROMAJIS[pattern - data::kana::HEPBURN_ROMAJIS.len()] {
-                    if let Some(result) = f(len, romaji) {
-                        return Some(result);
-                    }
-                }
             }
         }
+        None
+    }
 
-        if self.kanji {
-            if let Some(result) = self.romanize_kanji_and_try_for_each(input, f) {
-                return Some(result);
-            }
+    /// The katakana long vowel mark (chōonpu).
+    const LONG_VOWEL_MARK: char = 'ー';
+
+    /// The lengthened romaji of the `pattern`-th kana if `rest` after it starts with [`Self::LONG_VOWEL_MARK`].
+    #[inline]
+    fn lengthen_kana(&self, rest: &str, pattern: usize) -> Option<&'static str> {
+        if rest.starts_with(Self::LONG_VOWEL_MARK) {
+            self.kana_lengthened_romaji(pattern)
+        } else {
+            None
         }
+    }
 
-        None
+    /// [`romanize_and_try_for_each()`](Self::romanize_and_try_for_each),
+    /// but long vowels in romajis are converted to the [`long_vowel`](HepburnRomanizerBuilder::long_vowel) style,
+    /// and `tch*` to the [`sokuon_before_ch`](HepburnRomanizerBuilder::sokuon_before_ch) style.
+    ///
+    /// The romajis only live during the call of `f`, since they may be converted on the fly.
+    /// The byte lengths of them are kept, see [`LongVowelStyle`] for details.
+    ///
+    /// Vowels lengthened by `ー` are always converted, including `ii` (e.g. `フリー` to `fu`, `rī`).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::{convert::long_vowel::LongVowelStyle, HepburnRomanizer};
+    ///
+    /// let romanizer = HepburnRomanizer::builder()
+    ///     .kana(true)
+    ///     .kanji(true)
+    ///     .long_vowel(LongVowelStyle::Macron)
+    ///     .build();
+    /// let mut romajis = Vec::new();
+    /// romanizer.romanize_styled_and_try_for_each("校", |len, romaji| {
+    ///     romajis.push((len, romaji.to_owned()));
+    ///     None::<()>
+    /// });
+    /// assert!(romajis.contains(&(3, "kō".to_owned())));
+    /// ```
+    pub fn romanize_styled_and_try_for_each<'h, S: Into<Input<'h>>, T>(
+        &self,
+        input: S,
+        mut f: impl FnMut(usize, &str) -> Option<T>,
+    ) -> Option<T> {
+        let style = self.long_vowel;
+        if style == LongVowelStyle::Spelled && self.sokuon_before_ch == TchStyle::Tch {
+            return self.romanize_and_try_for_each(input, f);
+        }
+        let input = input.into();
+        let s = input.as_ref();
+        let mut buf = String::new();
+        self.romanize_and_try_for_each(input, |len, romaji| {
+            buf.clear();
+            // Half-width `ｰ` if normalized
+            if s[..len].ends_with([Self::LONG_VOWEL_MARK, 'ｰ']) {
+                style.convert_lengthened_into(romaji, &mut buf);
+            } else {
+                style.convert_into(romaji, &mut buf);
+            }
+            self.sokuon_before_ch.convert_in_place(&mut buf);
+            f(len, &buf)
+        })
     }
 
     /// Romanize the first word in the string, and return a `Vec` for all possible romanization.
@@ -257,7 +934,7 @@ impl HepburnRomanizer {
     ///
     /// assert_eq!(HepburnRomanizer::default().romanize_vec("日本語"), vec![(9, "nippongo"), (3, "a"), (3, "aki"), (3, "bi"), (3, "chi"), (3, "he"), (3, "hi"), (3, "iru"), (3, "jitsu"), (3, "ka"), (3, "kou"), (3, "ku"), (3, "kusa"), (3, "nchi"), (3, "ni"), (3, "nichi"), (3, "nitsu"), (3, "su"), (3, "tachi")]);
     /// ```
-    pub fn romanize_vec<'h, S: Into<Input<'h>>>(&self, s: S) -> Vec<(usize, &'static str)> {
+    pub fn romanize_vec<'s, 'h, S: Into<Input<'h>>>(&'s self, s: S) -> Vec<(usize, &'s str)> {
         let mut results = Vec::new();
         self.romanize_and_try_for_each(s, |len, romaji| {
             results.push((len, romaji));
@@ -266,35 +943,360 @@ impl HepburnRomanizer {
         results
     }
 
-    /// Check if the string can be fully romanized.
+    /// Romanize the first word in the string like [`romanize_vec()`](Self::romanize_vec),
+    /// but also continue with the following kanas (i.e. okurigana), and return the total length and the concatenated romajis.
     ///
-    /// This function can be used to test if the string is a possible Japanese text or not.
-    pub fn is_romanizable<'h, S: Into<Input<'h>>>(&self, s: S) -> bool {
-        let s = s.into();
-        if s.is_empty() {
-            return true;
-        }
-        self.romanize_and_try_for_each(s, |len, _| {
-            self.is_romanizable(Input::new(s.haystack(), s.start() + len))
-                .then_some(())
-        })
-        .is_some()
+    /// This can produce reasonable readings for words not in the dictionary, e.g. conjugations.
+    /// Since kanji readings usually include okurigana (e.g. 食: `taberu`, 書: `kaku`), for kanjis the following stems are also tried:
+    /// - The reading before the first following kana, e.g. `ta` for 食べた (`tabeta`).
+    /// - The reading without the last kana if it ends with `u`, i.e. godan conjugation, e.g. `ka` for 書いた (`kaita`).
+    ///
+    /// For 々 (with the preceding kanji in [`Input::haystack()`]), the [rendaku](kanji#rendaku) of each reading is also tried.
+    ///
+    /// There may be many false candidates (e.g. `shokubeta`).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::{HepburnRomanizer, Input};
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// assert_eq!(romanizer.romanize_compound("食べる")[0], (9, "taberu".to_string()));
+    /// assert!(romanizer.romanize_compound("食べた").contains(&(9, "tabeta".to_string())));
+    ///
+    /// // 寺々
+    /// let romajis = romanizer.romanize_compound(Input::new("寺々", 3));
+    /// assert!(romajis.contains(&(3, "tera".to_string())));
+    /// assert!(romajis.contains(&(3, "dera".to_string())));
+    /// ```
+    pub fn romanize_compound<'h, S: Into<Input<'h>>>(&self, s: S) -> Vec<(usize, String)> {
+        let input = s.into();
+        let s = input.as_ref();
+        let first = s.chars().next();
+        let mut results: Vec<(usize, String)> = Vec::new();
+        let mut push = |len: usize, stem: &str, kanas: &str| {
+            let mut buf = String::with_capacity(stem.len() + 1 + kanas.len());
+            buf.push_str(stem);
+            if !kanas.is_empty() && Self::need_apostrophe(stem, kanas) {
+                buf.push(Self::APOSTROPHE);
+            }
+            buf.push_str(kanas);
+            if !results.iter().any(|(l, r)| *l == len && *r == buf) {
+                results.push((len, buf));
+            }
+        };
+        self.romanize_and_try_for_each(input, |len, romaji| {
+            let mut kana_len = 0;
+            let mut kanas = String::new();
+            let mut first_kana = "";
+            for (l, kana) in self.romanize_kana_iter_with(&s[len..], None) {
+                if kanas.is_empty() {
+                    first_kana = kana;
+                }
+                kana_len += l;
+                kanas.push_str(kana);
+            }
+            let total_len = len + kana_len;
+
+            let is_kanji = first.is_some_and(|c| {
+                c.len_utf8() == len && self.kanji && self.kanji_romajis(c).contains(&romaji)
+            });
+            if is_kanji && !kanas.is_empty() {
+                // Okurigana is included in the reading, e.g. 食(taberu)べた
+                let okurigana = romaji
+                    .get(1..)
+                    .and_then(|r| r.find(first_kana))
+                    .map(|p| p + 1)
+                    .filter(|&p| Self::is_romaji_kana_boundary(romaji, p));
+                if let Some(p) = okurigana {
+                    push(total_len, &romaji[..p], &kanas);
+                } else if romaji.ends_with('u') {
+                    // Godan conjugation, e.g. 書(kaku)いた
+                    if let Some(p) = (1..romaji.len())
+                        .rev()
+                        .find(|&p| Self::is_romaji_kana_boundary(romaji, p))
+                    {
+                        push(total_len, &romaji[..p], &kanas);
+                    }
+                }
+            }
+            push(total_len, romaji, &kanas);
+            // Best-effort rendaku of 々, e.g. 寺々 (teradera). `noma` itself can't be voiced.
+            if let Some(voiced) = (first == Some(kanji::NOMA))
+                .then_some(romaji)
+                .and_then(kanji::rendaku)
+            {
+                push(total_len, &voiced, &kanas);
+            }
+            None::<()>
+        });
+        results
     }
 
-    fn is_romanizable_to_with_last(&self, s: Input, last_romaji: &str, romaji: &str) -> bool {
-        if s.is_empty() {
-            return romaji.is_empty();
-        }
-        self.romanize_and_try_for_each(s, |len, word_romaji| {
-            let romaji = if Self::need_apostrophe(last_romaji, word_romaji) {
-                romaji.strip_prefix(Self::APOSTROPHE)?
-            } else {
-                romaji
-            };
+    /// Segment the whole text into tokens greedily, and return the range and all possible romajis of each token.
+    ///
+    /// At each position, the longest romanizable word/kana/kanji is taken as a token.
+    /// Tokens that can't be romanized have no romajis and should be kept verbatim (i.e. `&s[range]`), including:
+    /// - ASCII runs, which are returned as one token.
+    /// - Other chars that can't be romanized, which are returned char by char.
+    ///
+    /// ## Notes
+    /// - n apostrophe between tokens is not handled, see [`need_apostrophe()`](Self::need_apostrophe).
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// assert_eq!(
+    ///     romanizer.romanize_text("iPhone5す"),
+    ///     vec![(0..7, vec![]), (7..10, vec!["su"])]
+    /// );
+    /// ```
+    pub fn romanize_text<S: ?Sized + AsRef<str>>(&self, s: &S) -> Vec<(Range<usize>, Vec<&str>)> {
+        let s = s.as_ref();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < s.len() {
+            let rest = &s[i..];
+            let ascii_len = rest
+                .bytes()
+                .position(|b| !b.is_ascii())
+                .unwrap_or(rest.len());
+            if ascii_len != 0 {
+                tokens.push((i..i + ascii_len, Vec::new()));
+                i += ascii_len;
+                continue;
+            }
+
+            let mut len = 0;
+            let mut romajis = Vec::new();
+            self.romanize_and_try_for_each(Input::new(s, i), |l, romaji| {
+                if l > len {
+                    len = l;
+                    romajis.clear();
+                }
+                if l == len && !romajis.contains(&romaji) {
+                    romajis.push(romaji);
+                }
+                None::<()>
+            });
+            if len == 0 {
+                len = rest.chars().next().unwrap().len_utf8();
+            }
+            tokens.push((i..i + len, romajis));
+            i += len;
+        }
+        tokens
+    }
+
+    /// Check if the string can be fully romanized.
+    ///
+    /// This function can be used to test if the string is a possible Japanese text or not.
+    ///
+    /// Each offset is romanized at most once, and the check stops at the first char that can't be romanized
+    /// (e.g. the first Latin letter in mixed-language text), so it is linear in the length of the string.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// assert!(romanizer.is_romanizable("日本語の文章"));
+    /// assert!(romanizer.is_romanizable("α-ヘリックス"));
+    /// assert!(!romanizer.is_romanizable("日本語のiPhone"));
+    /// ```
+    pub fn is_romanizable<'h, S: Into<Input<'h>>>(&self, s: S) -> bool {
+        Self::is_segmentable(s.into(), |s, f| self.romanize_and_try_for_each(s, f))
+    }
+
+    /// Whether `s` can be fully segmented into the prefixes romanized by `romanize`.
+    ///
+    /// Instead of trying every segmentation recursively, which is exponential for near-miss strings
+    /// (e.g. many kanjis followed by a Latin letter), this marks the offsets reachable from the start in order,
+    /// and bails at the first char that no segmentation reaches.
+    fn is_segmentable(
+        s: Input,
+        mut romanize: impl FnMut(Input, &mut dyn FnMut(usize, &str) -> Option<()>) -> Option<()>,
+    ) -> bool {
+        let len = s.end() - s.start();
+        if len == 0 {
+            return true;
+        }
+        let mut reachable = vec![false; len + 1];
+        reachable[0] = true;
+        let mut max_reached = 0;
+        for (i, _) in s.as_ref().char_indices() {
+            if i > max_reached {
+                return false;
+            }
+            if !reachable[i] {
+                continue;
+            }
+            let found = romanize(s.range(s.start() + i..s.end()), &mut |l, _| {
+                reachable[i + l] = true;
+                max_reached = max_reached.max(i + l);
+                (i + l == len).then_some(())
+            });
+            if found.is_some() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// [`is_romanizable()`](Self::is_romanizable), but without falling back to per-kanji readings,
+    /// i.e. the string must consist of kanas and words (in the dictionary), even if [`kanji`](HepburnRomanizerBuilder::kanji) is enabled.
+    ///
+    /// This is intended for language detection heuristics.
+    /// Most CJK text is romanizable by kanji readings (e.g. Chinese `我是学生`), so [`is_romanizable()`](Self::is_romanizable)
+    /// gives many false positives of "this is Japanese",
+    /// while kanas and known words are much stronger evidence.
+    /// This is the same as [`is_romanizable()`](Self::is_romanizable) with `kanji(false)`,
+    /// but without building another romanizer.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// assert!(romanizer.is_romanizable("我是学生"));
+    /// assert!(!romanizer.is_romanizable_strict("我是学生"));
+    ///
+    /// assert!(romanizer.is_romanizable_strict("にほんご"));
+    /// assert!(romanizer.is_romanizable_strict("日本語を"));
+    /// ```
+    pub fn is_romanizable_strict<'h, S: Into<Input<'h>>>(&self, s: S) -> bool {
+        Self::is_segmentable(s.into(), |s, f| {
+            self.romanize_kana_or_word_and_try_for_each(s.as_ref(), &mut |len, romaji| {
+                f(len, romaji)
+            })
+        })
+    }
+
+    /// Romanize the whole string in all possible ways, i.e. every segmentation of it into words/kanas/kanjis
+    /// and every romaji of them, and return at most `max` distinct romanizations.
+    ///
+    /// Returns an empty `Vec` if the string can't be fully romanized, or `vec![String::new()]` if it is empty.
+    ///
+    /// The number of romanizations grows exponentially with the length of the string (heteronyms are common in kanjis),
+    /// so `max` should be small, e.g. 100.
+    /// Only the segmentations that can be fully romanized are explored,
+    /// so the time is bounded by `max` instead of the number of all segmentations.
+    ///
+    /// ## Notes
+    /// - n apostrophe is properly handled in this function, e.g. `hon'ya` for 本屋.
+    /// - Like [`romanize_and_try_for_each()`](Self::romanize_and_try_for_each), only the longest word at each position is romanized,
+    ///   e.g. 今日 (`kyou`) is not tried for 今日は, since 今日は (`konnichiwa`) is a word.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// let romajis = romanizer.romanize_all("今日は", 100);
+    /// assert!(romajis.contains(&"konnichiwa".to_string()));
+    /// // 今(kon) 日(nichi) は(ha)
+    /// assert!(romajis.contains(&"konnichiha".to_string()));
+    ///
+    /// assert_eq!(romanizer.romanize_all("今日は", 3).len(), 3);
+    /// assert!(romanizer.romanize_all("今日はiPhone", 100).is_empty());
+    /// ```
+    pub fn romanize_all<'h, S: Into<Input<'h>>>(&self, s: S, max: usize) -> Vec<String> {
+        let input = s.into();
+        let s = input.as_ref();
+
+        // Whether the rest of the string from each offset can be fully romanized
+        let mut romanizable = vec![false; s.len() + 1];
+        romanizable[s.len()] = true;
+        for (i, _) in s.char_indices().rev() {
+            romanizable[i] = self
+                .romanize_and_try_for_each(input.range(input.start() + i..input.end()), |len, _| {
+                    romanizable[i + len].then_some(())
+                })
+                .is_some();
+        }
+
+        let mut results = Vec::new();
+        if max != 0 && romanizable[0] {
+            self.romanize_all_into(
+                input,
+                &romanizable,
+                "",
+                &mut String::new(),
+                max,
+                &mut results,
+            );
+        }
+        results
+    }
+
+    /// Returns `Some(())` if `max` is reached.
+    fn romanize_all_into(
+        &self,
+        s: Input,
+        romanizable: &[bool],
+        last_romaji: &str,
+        buf: &mut String,
+        max: usize,
+        results: &mut Vec<String>,
+    ) -> Option<()> {
+        if s.is_empty() {
+            if !results.contains(buf) {
+                results.push(buf.clone());
+            }
+            return (results.len() >= max).then_some(());
+        }
+        self.romanize_and_try_for_each(s, |len, romaji| {
+            if !romanizable[len] {
+                return None;
+            }
+            let buf_len = buf.len();
+            if Self::need_apostrophe(last_romaji, romaji) {
+                buf.push(Self::APOSTROPHE);
+            }
+            buf.push_str(romaji);
+            let result = self.romanize_all_into(
+                s.range(s.start() + len..s.end()),
+                &romanizable[len..],
+                romaji,
+                buf,
+                max,
+                results,
+            );
+            buf.truncate(buf_len);
+            result
+        })
+    }
+
+    /// If `prefix`, `romaji` only needs to be a prefix of the romanization of `s`.
+    fn is_romanizable_to_with_last(
+        &self,
+        s: Input,
+        last_romaji: &str,
+        romaji: &str,
+        prefix: bool,
+    ) -> bool {
+        if prefix && romaji.is_empty() {
+            return true;
+        }
+        if s.is_empty() {
+            return romaji.is_empty();
+        }
+        self.romanize_and_try_for_each(s, |len, word_romaji| {
+            let romaji = if Self::need_apostrophe(last_romaji, word_romaji) {
+                romaji.strip_prefix(Self::APOSTROPHE)?
+            } else {
+                romaji
+            };
+            // The prefix ends in the middle of the word
+            if prefix && word_romaji.starts_with(romaji) {
+                return Some(());
+            }
             self.is_romanizable_to_with_last(
-                Input::new(s.haystack(), s.start() + len),
+                s.range(s.start() + len..s.end()),
                 word_romaji,
                 romaji.strip_prefix(word_romaji)?,
+                prefix,
             )
             .then_some(())
         })
@@ -318,108 +1320,561 @@ impl HepburnRomanizer {
         }
         self.romanize_and_try_for_each(s, |len, word_romaji| {
             self.is_romanizable_to(
-                Input::new(s.haystack(), s.start() + len),
+                s.range(s.start() + len..s.end()),
                 romaji.strip_prefix(word_romaji)?,
             )
             .then_some(())
         })
         .is_some()
         */
-        self.is_romanizable_to_with_last(s, "", romaji)
+        self.is_romanizable_to_with_last(s, "", romaji, false)
+    }
+
+    /// Check if the string can be romanized to something starting with the given romaji prefix,
+    /// e.g. for incremental search.
+    ///
+    /// The check stops as soon as the prefix is consumed,
+    /// so the rest of the string doesn't need to be romanizable.
+    /// An empty prefix always matches.
+    ///
+    /// ## Notes
+    /// - n apostrophe is properly handled in this function.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::default();
+    /// assert!(romanizer.is_romanizable_to_prefix("今日", "kyo"));
+    /// assert!(romanizer.is_romanizable_to_prefix("今日の", "kyouno"));
+    /// assert!(romanizer.is_romanizable_to_prefix("今日の", "kyouna") == false);
+    /// assert!(romanizer.is_romanizable_to("今日", "kyo") == false);
+    /// ```
+    pub fn is_romanizable_to_prefix<'h, S: Into<Input<'h>>>(
+        &self,
+        s: S,
+        prefix: &(impl ?Sized + AsRef<str>),
+    ) -> bool {
+        self.is_romanizable_to_with_last(s.into(), "", prefix.as_ref(), true)
+    }
+}
+
+impl Default for HepburnRomanizer {
+    fn default() -> Self {
+        Self::builder().kana(true).kanji(true).word(true).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs, io::Write};
+
+    use indexmap::{IndexMap, IndexSet};
+
+    use super::*;
+
+    #[test]
+    fn min_len() {
+        let min_len = data::kana::HEPBURN_KANAS
+            .iter()
+            .inspect(|kana| {
+                if kana.len() == data::kana::KANA_MIN_LEN {
+                    println!("{}", kana);
+                }
+            })
+            .map(|s| s.len())
+            .min()
+            .unwrap();
+        assert_eq!(data::kana::KANA_MIN_LEN, min_len);
+
+        assert!(data::MIN_LEN <= data::kana::KANA_MIN_LEN);
+        assert!(data::MIN_LEN <= data::KANJI_MIN_LEN);
+    }
+
+    #[test]
+    fn kana_max_len() {
+        let max_len = data::kana::HEPBURN_KANAS
+            .iter()
+            .inspect(|kana| {
+                if kana.len() == data::kana::KANA_MAX_LEN {
+                    println!("{}", kana);
+                }
+            })
+            .map(|s| s.len())
+            .max()
+            .unwrap();
+        assert_eq!(data::kana::KANA_MAX_LEN, max_len);
+
+        let max_len = data::kana::HEPBURN_ROMAJIS
+            .iter()
+            .inspect(|romaji| {
+                if romaji.len() == data::kana::KANA_ROMAJI_MAX_LEN {
+                    println!("{}", romaji);
+                }
+            })
+            .map(|s| s.len())
+            .max()
+            .unwrap();
+        assert_eq!(data::kana::KANA_ROMAJI_MAX_LEN, max_len);
+    }
+
+    #[test]
+    fn kana() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        assert_eq!(data.romanize_kana("は"), Some((3, "ha")));
+        assert_eq!(data.romanize_kana("ハハハ"), Some((3, "ha")));
+        assert_eq!(data.romanize_kana("ジョジョ"), Some((6, "jo")));
+        assert_eq!(data.romanize_kana("って"), Some((6, "tte")));
+        assert_eq!(data.romanize_kana("日は"), None);
+    }
+
+    #[test]
+    fn kana_str() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        assert_eq!(data.romanize_kana_str("は"), Some((3, "ha".into())));
+        assert_eq!(data.romanize_kana_str("ハハハ"), Some((9, "hahaha".into())));
+        assert_eq!(
+            data.romanize_kana_str("ジョジョ"),
+            Some((12, "jojo".into()))
+        );
+        assert_eq!(data.romanize_kana_str("って"), Some((6, "tte".into())));
+        assert_eq!(data.romanize_kana_str("日は"), None);
+
+        // 平仮名-apostrophe-平仮名
+        assert_eq!(
+            data.romanize_kana_str("ぼたんゆき"),
+            Some((15, "botan'yuki".into()))
+        );
+        // 片仮名-apostrophe-平仮名
+        assert_eq!(
+            data.romanize_kana_str("ボタンゆき"),
+            Some((15, "botan'yuki".into()))
+        );
+    }
+
+    #[test]
+    fn kana_str_into() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        let mut buf = String::from("x");
+        assert_eq!(data.romanize_kana_str_into("ハハ日", &mut buf), Some(6));
+        assert_eq!(buf, "xhaha");
+        assert_eq!(data.romanize_kana_str_into("日は", &mut buf), None);
+        assert_eq!(buf, "xhaha");
+
+        buf.clear();
+        assert!(data.romanize_kana_str_all_into("ふじさん", &mut buf));
+        assert_eq!(buf, "fujisan");
+        assert!(!data.romanize_kana_str_all_into("ふじ山", &mut buf));
+        assert_eq!(buf, "fujisan");
+        assert_eq!(data.romanize_kana_str_all(""), None);
+        assert_eq!(data.romanize_kana_str_all("ふじ山"), None);
+    }
+
+    #[test]
+    fn kana_iter() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        assert_eq!(
+            data.romanize_kana_iter("ボタンゆき").collect::<Vec<_>>(),
+            vec![(3, "bo"), (3, "ta"), (6, "n'yu"), (3, "ki")]
+        );
+        assert_eq!(
+            data.romanize_kana_iter("はい、日は").collect::<Vec<_>>(),
+            vec![(3, "ha"), (3, "i"), (3, "、")]
+        );
+        assert_eq!(data.romanize_kana_iter("日は").next(), None);
+        assert_eq!(data.romanize_kana_iter("").next(), None);
+        assert_eq!(
+            data.romanize_kana_iter("ハハハ")
+                .take_while(|&(_, romaji)| romaji == "ha")
+                .map(|(len, _)| len)
+                .sum::<usize>(),
+            9
+        );
+    }
+
+    #[test]
+    fn punctuation() {
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .punctuation(PunctuationMode::Keep)
+            .build();
+        assert_eq!(
+            data.romanize_kana_iter("「はい。」日").collect::<Vec<_>>(),
+            vec![(3, "「"), (3, "ha"), (3, "i"), (3, "。"), (3, "」")]
+        );
+        assert_eq!(
+            data.romanize_kana_str_all("はい、そうです。").unwrap(),
+            "hai、soudesu。"
+        );
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .punctuation(PunctuationMode::Drop)
+            .build();
+        assert_eq!(
+            data.romanize_kana_str("はい！　いいえ？"),
+            Some((24, "haiiie".into()))
+        );
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .punctuation(PunctuationMode::map([('。', "."), ('、', ",")]))
+            .build();
+        assert_eq!(
+            data.romanize_kana_str_all("はい、そう。").unwrap(),
+            "hai,sou."
+        );
+        assert_eq!(data.romanize_kana_str_all("はい！"), None);
+
+        // Okurigana stops at any punctuation
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .punctuation(PunctuationMode::Keep)
+            .build();
+        assert!(
+            data.romanize_compound("食べた。")
+                .contains(&(9, "tabeta".to_string()))
+        );
+    }
+
+    #[test]
+    fn long_vowel() {
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .long_vowel(LongVowelStyle::Macron)
+            .build();
+        let mut romajis = Vec::new();
+        data.romanize_styled_and_try_for_each("今日", |len, romaji| {
+            romajis.push((len, romaji.to_owned()));
+            None::<()>
+        });
+        assert_eq!(romajis[0], (6, "kyō".to_owned()));
+        // Not affected
+        assert_eq!(data.romanize_vec("今日")[0], (6, "kyou"));
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .long_vowel(LongVowelStyle::Circumflex)
+            .build();
+        let mut romajis = Vec::new();
+        data.romanize_styled_and_try_for_each("あ", |len, romaji| {
+            romajis.push((len, romaji.to_owned()));
+            None::<()>
+        });
+        assert_eq!(romajis, vec![(3, "a".to_owned())]);
+    }
+
+    #[test]
+    fn sokuon() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        for (kana, romaji) in [
+            ("っか", "kka"),
+            ("っが", "gga"),
+            ("っさ", "ssa"),
+            ("っしゃ", "ssha"),
+            ("った", "tta"),
+            ("っつ", "ttsu"),
+            ("っち", "tchi"),
+            ("っちゃ", "tcha"),
+            ("っじ", "jji"),
+            ("っぱ", "ppa"),
+            ("っふ", "ffu"),
+            ("まっちゃ", "matcha"),
+            ("マッチャ", "matcha"),
+            // Not followed by a consonant
+            ("っ", "tsu"),
+            ("あっ", "atsu"),
+            ("っあ", "tsua"),
+            ("っっか", "tsukka"),
+        ] {
+            assert_eq!(data.romanize_kana_str_all(kana).unwrap(), romaji, "{kana}");
+        }
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .sokuon_before_ch(TchStyle::Cch)
+            .long_vowel(LongVowelStyle::Macron)
+            .build();
+        assert_eq!(data.sokuon_before_ch(), TchStyle::Cch);
+        let mut romajis = Vec::new();
+        data.romanize_styled_and_try_for_each("っちゃ", |len, romaji| {
+            romajis.push((len, romaji.to_owned()));
+            None::<()>
+        });
+        assert_eq!(romajis, vec![(9, "ccha".to_owned())]);
+        let mut romajis = Vec::new();
+        data.romanize_styled_and_try_for_each("抹茶", |len, romaji| {
+            romajis.push((len, romaji.to_owned()));
+            None::<()>
+        });
+        assert!(romajis.contains(&(6, "maccha".to_owned())));
+        // Only in the styled output
+        assert_eq!(data.romanize_kana_str_all("まっちゃ").unwrap(), "matcha");
+    }
+
+    #[test]
+    fn long_vowel_mark() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        assert_eq!(data.romanize_kana("カー"), Some((6, "kaa")));
+        assert_eq!(data.romanize_kana("ー"), Some((3, "-")));
+        assert_eq!(data.romanize_kana_str("フリー"), Some((9, "furii".into())));
+        assert_eq!(data.romanize_kana_str("カーー"), Some((9, "kaa-".into())));
+        assert_eq!(data.romanize_kana_str("ンー"), Some((6, "n-".into())));
+        assert_eq!(data.romanize_kana_str("ジョー"), Some((9, "joo".into())));
+        assert_eq!(data.romanize_vec("カー"), vec![(6, "kaa"), (3, "ka")]);
+        assert!(data.is_romanizable_to("フリーレン", "furiiren"));
+        assert!(data.is_romanizable_to("フリーレン", "furi-ren"));
+
+        let styled = |data: &HepburnRomanizer, s: &str| {
+            let mut buf = String::new();
+            let mut i = 0;
+            while i < s.len() {
+                let (len, romaji) = data
+                    .romanize_styled_and_try_for_each(&s[i..], |len, romaji| {
+                        Some((len, romaji.to_owned()))
+                    })
+                    .unwrap();
+                buf.push_str(&romaji);
+                i += len;
+            }
+            buf
+        };
+        assert_eq!(styled(&data, "カー"), "kaa");
+        assert_eq!(styled(&data, "フリー"), "furii");
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .long_vowel(LongVowelStyle::Macron)
+            .build();
+        assert_eq!(styled(&data, "カー"), "kā");
+        assert_eq!(styled(&data, "フリー"), "furī");
+        assert_eq!(styled(&data, "フリーレン"), "furīren");
+        // Not `ー`
+        assert_eq!(styled(&data, "にい"), "nii");
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .long_vowel(LongVowelStyle::Circumflex)
+            .build();
+        assert_eq!(styled(&data, "フリー"), "furî");
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .normalize_kana(true)
+            .long_vowel(LongVowelStyle::Macron)
+            .build();
+        assert_eq!(styled(&data, "ﾌﾘｰ"), "furī");
     }
-}
 
-impl Default for HepburnRomanizer {
-    fn default() -> Self {
-        Self::builder().kana(true).kanji(true).word(true).build()
+    #[test]
+    fn normalize_kana() {
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .normalize_kana(true)
+            .build();
+        let romaji = data.romanize_vec("いす取りゲーム")[0].1;
+        assert_eq!(
+            data.romanize_vec("いす取りｹﾞｰﾑ")[0],
+            ("いす取りｹﾞｰﾑ".len(), romaji)
+        );
+        assert_eq!(data.romanize_vec("ｶﾞｯｺｳ"), vec![(6, "ga")]);
+        assert_eq!(data.romanize_vec("ｯｶ"), vec![(6, "kka")]);
+        assert_eq!(
+            data.romanize_vec("日本"),
+            HepburnRomanizer::default().romanize_vec("日本")
+        );
+
+        // Half-width kanas are already romanizable, but not in words
+        let data = HepburnRomanizer::default();
+        assert_eq!(data.romanize_vec("ｶﾞｯｺｳ"), vec![(6, "ga")]);
+        assert_ne!(data.romanize_vec("いす取りｹﾞｰﾑ")[0].1, romaji);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::{fs, io::Write};
+    #[test]
+    fn extra_words() {
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .extra_words([
+                ("葬送", vec!["sousou"]),
+                ("今日", vec!["kyou", "konnichiha"]),
+                ("葬送", vec!["sousou", "hoomuru"]),
+                ("", vec!["empty"]),
+            ])
+            .build();
+        assert_eq!(
+            data.romanize_vec("葬送")[..2],
+            [(6, "sousou"), (6, "hoomuru")]
+        );
+        // Merged with the embedded dictionary
+        let romajis = data.romanize_vec("今日");
+        assert_eq!(romajis[..2], [(6, "kyou"), (6, "konnichiha")]);
+        assert_eq!(romajis.iter().filter(|&&r| r == (6, "kyou")).count(), 1);
+        // Other words are not affected
+        assert_eq!(data.romanize_vec("日本語")[0], (9, "nippongo"));
+        assert_eq!(data.romanize_vec("かな")[0], (3, "ka"));
+
+        // Kanas are ignored
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .extra_words([
+                ("か", vec!["ka", "ga"]),
+                ("きょ", vec!["kyo"]),
+                ("かな", vec!["kana"]),
+            ])
+            .build();
+        assert_eq!(data.romanize_vec("か"), vec![(3, "ka")]);
+        assert_eq!(data.romanize_vec("きょう"), vec![(6, "kyo")]);
+        assert_eq!(data.romanize_vec("かな"), vec![(6, "kana")]);
+        // Unless kanas are not enabled
+        let data = HepburnRomanizer::builder()
+            .extra_words([("か", ["ka", "ga"])])
+            .build();
+        assert_eq!(data.romanize_vec("か"), vec![(3, "ka"), (3, "ga")]);
 
-    use indexmap::IndexSet;
+        // Without the embedded dictionary or kanas
+        let data = HepburnRomanizer::builder()
+            .extra_words([("葬送", ["sousou"])])
+            .build();
+        assert_eq!(data.romanize_vec("葬送"), vec![(6, "sousou")]);
+        assert_eq!(data.romanize_vec("日本語"), vec![]);
 
-    use super::*;
+        // Words without kanas
+        let data = HepburnRomanizer::builder().word(true).build();
+        assert_eq!(data.romanize_vec("日本語"), vec![(9, "nippongo")]);
+        assert_eq!(data.romanize_vec("かな"), vec![]);
+    }
 
     #[test]
-    fn min_len() {
-        let min_len = data::kana::HEPBURN_KANAS
-            .iter()
-            .inspect(|kana| {
-                if kana.len() == data::kana::KANA_MIN_LEN {
-                    println!("{}", kana);
-                }
-            })
-            .map(|s| s.len())
-            .min()
-            .unwrap();
-        assert_eq!(data::kana::KANA_MIN_LEN, min_len);
+    fn compound() {
+        let data = HepburnRomanizer::default();
+        assert_eq!(data.romanize_compound("食べる")[0], (9, "taberu".into()));
+        assert!(
+            data.romanize_compound("食べた")
+                .contains(&(9, "tabeta".into()))
+        );
+        assert!(
+            data.romanize_compound("食べました")
+                .contains(&(15, "tabemashita".into()))
+        );
+        assert!(
+            data.romanize_compound("書いた")
+                .contains(&(9, "kaita".into()))
+        );
+        assert!(
+            data.romanize_compound("読んで")
+                .contains(&(9, "yonde".into()))
+        );
+        // n apostrophe
+        assert!(
+            data.romanize_compound("本いる")
+                .contains(&(9, "hon'iru".into()))
+        );
+        // Stop at non-kana
+        assert!(
+            data.romanize_compound("書いた日")
+                .iter()
+                .all(|&(len, _)| len <= 9)
+        );
+        assert!(data.romanize_compound("").is_empty());
+    }
 
-        assert!(data::MIN_LEN <= data::kana::KANA_MIN_LEN);
-        assert!(data::MIN_LEN <= data::KANJI_MIN_LEN);
+    #[test]
+    fn text() {
+        let data = HepburnRomanizer::default();
+        let tokens = data.romanize_text("日本語はiPhone5で★です");
+        let ranges = tokens.iter().map(|(r, _)| r.clone()).collect::<Vec<_>>();
+        assert_eq!(ranges[0], 0..9);
+        assert!(tokens[0].1.contains(&"nippongo"));
+        assert_eq!(tokens[1], (9..12, vec!["ha"]));
+        assert_eq!(tokens[2], (12..19, vec![]));
+        assert_eq!(tokens[3], (19..22, vec!["de"]));
+        assert_eq!(tokens[4], (22..25, vec![]));
+        assert_eq!(ranges.last().unwrap().end, "日本語はiPhone5で★です".len());
+        assert!(ranges.windows(2).all(|w| w[0].end == w[1].start));
+
+        assert!(data.romanize_text("").is_empty());
+        assert_eq!(data.romanize_text("abc"), vec![(0..3, vec![])]);
     }
 
     #[test]
-    fn kana_max_len() {
-        let max_len = data::kana::HEPBURN_KANAS
-            .iter()
-            .inspect(|kana| {
-                if kana.len() == data::kana::KANA_MAX_LEN {
-                    println!("{}", kana);
-                }
-            })
-            .map(|s| s.len())
-            .max()
-            .unwrap();
-        assert_eq!(data::kana::KANA_MAX_LEN, max_len);
+    fn romanize_all() {
+        let data = HepburnRomanizer::default();
+        let romajis = data.romanize_all("今日は", usize::MAX);
+        for romaji in ["konnichiwa", "konnichiha", "imahiha", "konjitsuha"] {
+            assert!(romajis.contains(&romaji.into()), "{romaji}");
+        }
+        // Distinct
+        let mut sorted = romajis.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), romajis.len());
+        // Capped
+        assert_eq!(data.romanize_all("今日は", 5), romajis[..5]);
+        assert!(data.romanize_all("今日は", 0).is_empty());
 
-        let max_len = data::kana::HEPBURN_ROMAJIS
-            .iter()
-            .inspect(|romaji| {
-                if romaji.len() == data::kana::KANA_ROMAJI_MAX_LEN {
-                    println!("{}", romaji);
-                }
-            })
-            .map(|s| s.len())
-            .max()
-            .unwrap();
-        assert_eq!(data::kana::KANA_ROMAJI_MAX_LEN, max_len);
+        // n apostrophe
+        assert!(data.romanize_all("本屋", 100).contains(&"hon'ya".into()));
+        assert!(data.romanize_all("今日だ", 100).contains(&"kyouda".into()));
+        assert_eq!(data.romanize_all("かな", 100), vec!["kana"]);
+        assert_eq!(data.romanize_all("", 100), vec![""]);
+        assert!(data.romanize_all("かなa", 100).is_empty());
+        assert!(data.romanize_all("★かな", 100).is_empty());
+
+        // Heteronym-heavy strings are bounded
+        let s = "日".repeat(20);
+        assert_eq!(data.romanize_all(&s, 1000).len(), 1000);
     }
 
     #[test]
-    fn kana() {
-        let data = HepburnRomanizer::builder().kana(true).build();
-        assert_eq!(data.romanize_kana("は"), Some((3, "ha")));
-        assert_eq!(data.romanize_kana("ハハハ"), Some((3, "ha")));
-        assert_eq!(data.romanize_kana("ジョジョ"), Some((6, "jo")));
-        assert_eq!(data.romanize_kana("って"), Some((6, "tte")));
-        assert_eq!(data.romanize_kana("日は"), None);
+    fn is_romanizable() {
+        let data = HepburnRomanizer::default();
+        assert!(data.is_romanizable(""));
+        assert!(data.is_romanizable("ハハハ"));
+        assert!(data.is_romanizable("今日は良い天気ですね"));
+        assert!(data.is_romanizable("α-ヘリックス"));
+        assert!(!data.is_romanizable("今日はiPhone"));
+        assert!(!data.is_romanizable("iPhone今日は"));
+        assert!(!data.is_romanizable("★"));
+
+        // Input context
+        assert!(data.is_romanizable(Input::new("iPhoneの今日", 6)));
+        assert!(!data.is_romanizable(Input::new("iPhoneの今日", 5)));
+
+        // Near-miss strings with many heteronyms
+        let s = "日".repeat(100);
+        assert!(data.is_romanizable(s.as_str()));
+        assert!(!data.is_romanizable(format!("{s}a").as_str()));
+        assert!(!data.is_romanizable(format!("{s}a{s}").as_str()));
+        assert!(!data.is_romanizable_strict(format!("{s}a").as_str()));
     }
 
     #[test]
-    fn kana_str() {
-        let data = HepburnRomanizer::builder().kana(true).build();
-        assert_eq!(data.romanize_kana_str("は"), Some((3, "ha".into())));
-        assert_eq!(data.romanize_kana_str("ハハハ"), Some((9, "hahaha".into())));
-        assert_eq!(
-            data.romanize_kana_str("ジョジョ"),
-            Some((12, "jojo".into()))
-        );
-        assert_eq!(data.romanize_kana_str("って"), Some((6, "tte".into())));
-        assert_eq!(data.romanize_kana_str("日は"), None);
+    fn is_romanizable_strict() {
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .normalize_kana(true)
+            .build();
+        assert!(data.is_romanizable_strict(""));
+        assert!(data.is_romanizable_strict("ハハハ"));
+        assert!(data.is_romanizable_strict("ｶﾀｶﾅ"));
+        assert!(data.is_romanizable_strict("今日は"));
+        assert!(data.is_romanizable("日は"));
+        assert!(!data.is_romanizable_strict("日は"));
+        assert!(!data.is_romanizable_strict("はiPhone"));
 
-        // 平仮名-apostrophe-平仮名
-        assert_eq!(
-            data.romanize_kana_str("ぼたんゆき"),
-            Some((15, "botan'yuki".into()))
-        );
-        // 片仮名-apostrophe-平仮名
-        assert_eq!(
-            data.romanize_kana_str("ボタンゆき"),
-            Some((15, "botan'yuki".into()))
-        );
+        let data = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        assert!(!data.is_romanizable_strict("今日"));
     }
 
     #[test]
@@ -438,6 +1893,144 @@ mod tests {
         assert!(data.is_romanizable_to("ぼたん雪", "botan'yuki"));
     }
 
+    #[test]
+    fn is_romanizable_to_prefix() {
+        let data = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        assert!(!data.is_romanizable_to_prefix("今日", "kyou"));
+        assert!(data.is_romanizable_to_prefix("今日", "ima"));
+        assert!(data.is_romanizable_to_prefix("今日", "i"));
+        assert!(data.is_romanizable_to_prefix("今日", "imaku"));
+        assert!(data.is_romanizable_to_prefix("今日", "imakusa"));
+        assert!(!data.is_romanizable_to_prefix("今日", "imakusaha"));
+        assert!(data.is_romanizable_to_prefix("今日", ""));
+        assert!(data.is_romanizable_to_prefix("", ""));
+        assert!(!data.is_romanizable_to_prefix("", "a"));
+        // The rest doesn't need to be romanizable
+        assert!(data.is_romanizable_to_prefix("はabc", "ha"));
+        assert!(!data.is_romanizable_to_prefix("はabc", "haa"));
+
+        // n apostrophe
+        assert!(data.is_romanizable_to_prefix("ぼたん雪", "botan"));
+        assert!(data.is_romanizable_to_prefix("ぼたん雪", "botan'"));
+        assert!(data.is_romanizable_to_prefix("ぼたん雪", "botan'yu"));
+        assert!(!data.is_romanizable_to_prefix("ぼたん雪", "botanyu"));
+
+        let data = HepburnRomanizer::default();
+        assert!(data.is_romanizable_to_prefix("今日", "kyo"));
+        assert!(data.is_romanizable_to_prefix("今日", "kyou"));
+    }
+
+    /// Romaji -> `(on'yomi, kun'yomi, nanori only)`
+    type RomajiKinds = IndexMap<String, (bool, bool, bool)>;
+
+    /// The romajis of a kanji's `kanas` in `data/kanjidic.csv` (sorted), with `(on'yomi, kun'yomi, nanori only)` of each,
+    /// and the indices of the romajis from the most common to the least.
+    ///
+    /// Shared by `codegen_kanji()` and `kanji_tables()`.
+    fn kanjidic_romajis(
+        romanizer: &HepburnRomanizer,
+        kanas: &str,
+        nanoris: &[&str],
+    ) -> (RomajiKinds, Vec<usize>) {
+        let mut kanas_set = RomajiKinds::new();
+        for kana in kanas.split('\t') {
+            let romaji = match romanizer.romanize_kana_str_all(kana) {
+                Some(romaji) => romaji,
+                None => {
+                    println!("Failed to romanize kana: {kana}");
+                    kana.into()
+                }
+            };
+            // On'yomi are in katakana in KANJIDIC
+            let on = kana.chars().any(|c| matches!(c, 'ァ'..='ヺ'));
+            let nanori = nanoris.contains(&kana);
+            let kind = kanas_set.entry(romaji).or_insert((false, false, true));
+            kind.0 |= on;
+            kind.1 |= !on;
+            kind.2 &= nanori;
+        }
+        // Readings are ordered by commonness in kanjidic.csv
+        let ranks: Vec<String> = kanas_set.keys().cloned().collect();
+        kanas_set.sort_unstable_keys();
+        let ranks = ranks
+            .iter()
+            .map(|romaji| kanas_set.get_index_of(romaji).unwrap())
+            .collect();
+        (kanas_set, ranks)
+    }
+
+    /// `(on'yomi mask, kun'yomi mask, nanori mask)` of `kinds`, see [`data::kanji_reading_masks()`].
+    fn kanji_masks(kinds: impl Iterator<Item = (bool, bool, bool)>) -> (u64, u64, u64) {
+        let (mut on, mut kun, mut nanori) = (0u64, 0u64, 0u64);
+        for (i, (is_on, is_kun, is_nanori)) in kinds.enumerate() {
+            on |= (is_on as u64) << i;
+            kun |= (is_kun as u64) << i;
+            nanori |= (is_nanori as u64) << i;
+        }
+        (on, kun, nanori)
+    }
+
+    /// `data/kanjidic.csv` and `data/kanjidic-nanori.csv`, which are written by `data/dict.ipynb` and not checked in.
+    fn read_kanjidic() -> (String, String) {
+        let read = |path| {
+            fs::read_to_string(path)
+                .unwrap_or_else(|e| panic!("{path}: {e}, run `data/dict.ps1` and `data/dict.ipynb` first"))
+        };
+        (read("data/kanjidic.csv"), read("data/kanjidic-nanori.csv"))
+    }
+
+    /// `data/kanjidic-nanori.csv`: kanji -> nanori that are not other readings.
+    fn read_kanjidic_nanoris(nanoris: &str) -> HashMap<&str, Vec<&str>> {
+        nanoris
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(kanji, kanas)| (kanji, kanas.split('\t').collect()))
+            .collect()
+    }
+
+    /// `src/data/kanji_kinds.rs` and `src/data/kanji_ranks.rs` of the kanjis in `kanjidic`,
+    /// in the format of `data/kanjidic.csv` and `data/kanjidic-nanori.csv`.
+    fn kanji_kinds_tables(kanjidic: &str, nanoris: &str) -> (String, String) {
+        use std::fmt::Write as _;
+
+        let romanizer = HepburnRomanizer::builder().kana(true).build();
+        let nanoris = read_kanjidic_nanoris(nanoris);
+        let mut out_kinds = String::from("match kanji {\n");
+        let mut out_ranks = String::from("match kanji {\n");
+        for (kanji, kanas) in kanjidic.lines().filter_map(|line| line.split_once('\t')) {
+            if matches!(kanji, kanji::NOMA_STR) {
+                continue;
+            }
+            let kanji_nanoris = nanoris.get(kanji).map(Vec::as_slice).unwrap_or_default();
+            let (kanas_set, ranks) = kanjidic_romajis(&romanizer, kanas, kanji_nanoris);
+            assert!(kanas_set.len() <= 64, "{kanji} {}", kanas_set.len());
+            let (on, kun, nanori) = kanji_masks(kanas_set.values().copied());
+            writeln!(out_kinds, "'{kanji}'=>({on:#X},{kun:#X},{nanori:#X}),").unwrap();
+            writeln!(
+                out_ranks,
+                "'{kanji}'=>&[{}],",
+                ranks
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+            .unwrap();
+        }
+        out_kinds.push_str("_ => (0, 0, 0)\n}");
+        out_ranks.push_str("_ => &[]\n}");
+        (out_kinds, out_ranks)
+    }
+
+    /// Generate `src/data/kanjis.rs` from `data/kanjidic.csv`, which is written by `data/dict.ipynb`,
+    /// and `src/data/kanji_kinds.rs` and `src/data/kanji_ranks.rs`
+    /// from `data/kanjidic-tagged.tsv` and `data/kanjidic-tagged-nanori.tsv`.
+    ///
+    /// The kinds and ranks only cover the kanjis in `data/kanjidic-tagged.tsv` at the moment,
+    /// whose lines are copied from `data/kanjidic.csv` and `data/kanjidic-nanori.csv`.
+    /// Unlike the CSVs, they are checked in and `kanji_tables()` verifies the tables against them.
+    ///
+    /// `cargo test --package ib-romaji --lib -r -- tests::codegen_kanji --exact --no-capture --ignored`
     #[ignore]
     #[test]
     fn codegen_kanji() {
@@ -446,11 +2039,11 @@ mod tests {
         let mut dup_count = 0;
         let mut romaji_max_len = 0;
 
-        let kanjidic = fs::read_to_string("data/kanjidic.csv").unwrap();
+        let (kanjidic, _) = read_kanjidic();
         let mut out_kanjis = fs::File::create("src/data/kanjis.rs").unwrap();
         writeln!(out_kanjis, "match kanji {{").unwrap();
         let mut range = 0;
-        for (_i, line) in kanjidic.lines().enumerate() {
+        for line in kanjidic.lines() {
             let (kanji, kanas) = match line.split_once('\t') {
                 Some(v) => v,
                 None => continue,
@@ -462,17 +2055,7 @@ mod tests {
             write!(out_kanjis, "'{kanji}'=>").unwrap();
 
             let kanas_count = kanas.split('\t').count();
-            let mut kanas_set: IndexSet<String> = kanas
-                .split('\t')
-                .map(|kana| match romanizer.romanize_kana_str_all(kana) {
-                    Some(romaji) => format!("\"{}\"", romaji),
-                    None => {
-                        println!("Failed to romanize kana: {kana}");
-                        kana.into()
-                    }
-                })
-                .collect();
-            kanas_set.sort_unstable();
+            let (kanas_set, _) = kanjidic_romajis(&romanizer, kanas, &[]);
             if kanas_set.len() != kanas_count {
                 // println!("Duplicated romajis: {kanji}\t{kanas}");
                 dup_count += 1;
@@ -484,7 +2067,7 @@ mod tests {
                 kanji.len()
             );
             {
-                let max_len = kanas_set.iter().map(|s| s.len()).max().unwrap();
+                let max_len = kanas_set.keys().map(|s| s.len()).max().unwrap();
                 if max_len > romaji_max_len {
                     romaji_max_len = max_len;
                 }
@@ -496,7 +2079,11 @@ mod tests {
             write!(
                 out_kanjis,
                 "&[{}],",
-                kanas_set.into_iter().collect::<Vec<_>>().join(",")
+                kanas_set
+                    .into_keys()
+                    .map(|romaji| format!("\"{romaji}\""))
+                    .collect::<Vec<_>>()
+                    .join(",")
             )
             .unwrap();
 
@@ -510,13 +2097,149 @@ mod tests {
         }
         write!(out_kanjis, "_ => &[]\n}}").unwrap();
 
+        let (kinds, ranks) = kanji_kinds_tables(
+            &fs::read_to_string("data/kanjidic-tagged.tsv").unwrap(),
+            &fs::read_to_string("data/kanjidic-tagged-nanori.tsv").unwrap(),
+        );
+        fs::write("src/data/kanji_kinds.rs", kinds).unwrap();
+        fs::write("src/data/kanji_ranks.rs", ranks).unwrap();
+
         println!("Kanjis with duplicated romajis: {dup_count}");
         println!("Romaji max len: {romaji_max_len}");
         assert_eq!(romaji_max_len, data::KANJI_ROMAJI_MAX_LEN);
     }
 
+    /// Ties `kanji_kinds.rs` and `kanji_ranks.rs` to their source `data/kanjidic-tagged.tsv`,
+    /// to [`data::kanji_romajis()`], and to `data/kanjidic.csv` if it exists (see `codegen_kanji()`).
+    #[test]
+    fn kanji_tables() {
+        // `'上'=>(0xC0080,0x73FF7F,0x3B74),` / `'上'=>&[7,19,18],`
+        fn entries(table: &str) -> impl Iterator<Item = (char, &str)> {
+            table.lines().filter_map(|line| {
+                let (kanji, value) = line.split_once("=>")?;
+                let kanji = kanji.strip_prefix('\'')?.strip_suffix('\'')?;
+                Some((kanji.parse().unwrap(), value.trim_end_matches(',')))
+            })
+        }
+
+        let kinds: Vec<char> = entries(include_str!("data/kanji_kinds.rs"))
+            .map(|(kanji, _)| kanji)
+            .collect();
+        let ranked: Vec<char> = entries(include_str!("data/kanji_ranks.rs"))
+            .map(|(kanji, _)| kanji)
+            .collect();
+        assert_eq!(kinds, ranked);
+        for &kanji in &kinds {
+            let len = data::kanji_romajis(kanji).len();
+            assert!(len > 0 && len <= 64, "{kanji}");
+            #[cfg(feature = "kunrei")]
+            assert_eq!(data::kunrei_kanji_romajis(kanji).len(), len, "{kanji}");
+
+            // Every romaji is either on'yomi or kun'yomi, and nanori are only kun'yomi
+            let all = u64::MAX >> (64 - len);
+            let (on, kun, nanori) = data::kanji_reading_masks(kanji);
+            assert_eq!(on | kun, all, "{kanji}");
+            assert_eq!(nanori & !(kun & !on), 0, "{kanji}");
+
+            let mut ranks: Vec<usize> = data::kanji_reading_ranks(kanji)
+                .iter()
+                .map(|&i| i as usize)
+                .collect();
+            ranks.sort_unstable();
+            assert_eq!(ranks, (0..len).collect::<Vec<_>>(), "{kanji}");
+        }
+
+        let tagged = include_str!("../data/kanjidic-tagged.tsv");
+        let tagged_nanoris = include_str!("../data/kanjidic-tagged-nanori.tsv");
+        let (kinds_table, ranks_table) = kanji_kinds_tables(tagged, tagged_nanoris);
+        assert!(
+            kinds_table == include_str!("data/kanji_kinds.rs")
+                && ranks_table == include_str!("data/kanji_ranks.rs"),
+            "The tables are outdated, run `codegen_kanji()`"
+        );
+        // `kanjis.rs` keeps ー as `-` (e.g. ぺー `pe-`), while it is romanized as the vowel now
+        fn lengthen(romaji: &str) -> String {
+            let mut s = String::new();
+            for c in romaji.chars() {
+                match c {
+                    '-' => s.push(s.chars().last().unwrap()),
+                    c => s.push(c),
+                }
+            }
+            s
+        }
+        let romanizer = HepburnRomanizer::builder().kana(true).build();
+        for (kanji, kanas) in tagged.lines().filter_map(|line| line.split_once('\t')) {
+            let (kanas_set, _) = kanjidic_romajis(&romanizer, kanas, &[]);
+            let c = kanji.chars().next().unwrap();
+            assert_eq!(
+                data::kanji_romajis(c)
+                    .iter()
+                    .map(|romaji| lengthen(romaji))
+                    .collect::<Vec<_>>(),
+                kanas_set.into_keys().collect::<Vec<_>>(),
+                "{kanji}"
+            );
+        }
+        // Common kanjis must not fall back to untagged
+        for kanji in ['人', '学', '月', '日', '大', '山'] {
+            assert_ne!(
+                data::kanji_reading_masks(kanji),
+                data::UNTAGGED_KANJI_MASKS,
+                "{kanji}"
+            );
+            assert!(!data::kanji_reading_ranks(kanji).is_empty(), "{kanji}");
+        }
+
+        // The data of `dict.ipynb` is not checked in
+        if !fs::exists("data/kanjidic.csv").unwrap() {
+            return;
+        }
+        let (kanjidic, nanoris) = read_kanjidic();
+        let kanjidic = read_kanjidic_nanoris(&kanjidic);
+        let nanoris = read_kanjidic_nanoris(&nanoris);
+        let tagged_nanoris = read_kanjidic_nanoris(tagged_nanoris);
+        for (kanji, kanas) in tagged.lines().filter_map(|line| line.split_once('\t')) {
+            assert_eq!(
+                kanjidic.get(kanji),
+                Some(&kanas.split('\t').collect()),
+                "{kanji}"
+            );
+            assert_eq!(nanoris.get(kanji), tagged_nanoris.get(kanji), "{kanji}");
+        }
+    }
+
+    /// The nanori of every kanji in `data/kanjidic-tagged-nanori.tsv` are excluded by the filter.
+    #[test]
+    fn kanjidic_nanoris() {
+        let kanjidic = read_kanjidic_nanoris(include_str!("../data/kanjidic-tagged.tsv"));
+        let nanoris = read_kanjidic_nanoris(include_str!("../data/kanjidic-tagged-nanori.tsv"));
+
+        let romanizer = HepburnRomanizer::builder().kana(true).kanji(true).build();
+        let filter = ReadingFilter {
+            nanori: false,
+            ..Default::default()
+        };
+        for (kanji, kanji_nanoris) in &nanoris {
+            let kanas = kanjidic[kanji].join("\t");
+            let (kinds, _) = kanjidic_romajis(&romanizer, &kanas, kanji_nanoris);
+            let mut romajis = Vec::new();
+            romanizer.romanize_readings_and_try_for_each(*kanji, filter, |_, romaji| {
+                romajis.push(romaji);
+                None::<()>
+            });
+            for (romaji, (_, _, nanori)) in kinds {
+                assert_eq!(romajis.contains(&romaji.as_str()), !nanori, "{kanji} {romaji}");
+            }
+        }
+    }
+
     /// `codegen_kanji()` should be run first.
     ///
+    /// Words whose readings can all be romanized from their kanji are filtered out,
+    /// except the irregular readings in `data/joyo_fuhyo.tsv` (the appendix of the Jōyō kanji table, e.g. 一人 ひとり),
+    /// which are kept so that they are preferred over the concatenations of kanji readings.
+    ///
     /// `cargo test --package ib-romaji --lib -r -- tests::codegen_word --exact --no-capture --ignored > data/word.txt`
     #[ignore]
     #[test]
@@ -533,6 +2256,14 @@ mod tests {
         let mut romaji_max_len = 0;
 
         let jmdict = fs::read_to_string("data/jmdict.csv").unwrap();
+        let fuhyo = fs::read_to_string("data/joyo_fuhyo.tsv").unwrap();
+        let irregular_romajis: HashMap<&str, String> = fuhyo
+            .lines()
+            .map(|line| {
+                let (word, kana) = line.split_once('\t').unwrap();
+                (word, romanizer.romanize_kana_str_all(kana).unwrap())
+            })
+            .collect();
         let mut out_words = fs::File::create("src/data/words.in.txt").unwrap();
         let mut out_kanas = fs::File::create("src/data/word_kanas.rs").unwrap();
         // writeln!(out_words, "&[").unwrap();
@@ -570,11 +2301,15 @@ mod tests {
             // Source file: 2.52+3.59=6.11 MiB -> 1.07+1.45=2.52 MiB
             // Binary: -10.01 MiB
             // TODO: What if the dependent word is in words?
+            let irregular = irregular_romajis.get(word);
             let mut romajis = if kanji_romanizer.is_romanizable(word) {
                 let romajis = kanas_set
                     .iter()
                     .cloned()
-                    .filter(|romaji| !kanji_romanizer.is_romanizable_to(word, romaji))
+                    .filter(|romaji| {
+                        irregular == Some(romaji)
+                            || !kanji_romanizer.is_romanizable_to(word, romaji)
+                    })
                     .collect::<Vec<_>>();
                 if romajis.len() != kanas_set.len() {
                     if romajis.is_empty() {
@@ -676,6 +2411,59 @@ mod tests {
         assert_eq!(data::WORD_ROMAJI_MAX_LEN, romaji_max_len);
     }
 
+    #[cfg(feature = "kunrei")]
+    #[test]
+    fn kunrei() {
+        for (&hepburn, &kunrei) in data::kana::HEPBURN_ROMAJIS
+            .iter()
+            .zip(data::kana::KUNREI_ROMAJIS)
+        {
+            assert_eq!(convert::kunrei::hepburn_to_kunrei(hepburn), kunrei);
+        }
+        assert_eq!(
+            data::kana::HEPBURN_ROMAJIS.len(),
+            data::kana::KUNREI_ROMAJIS.len()
+        );
+        assert_eq!(
+            data::word_romajis().len(),
+            data::kunrei_word_romajis().len()
+        );
+        for (hepburn, kunrei) in data::word_romajis().iter().zip(data::kunrei_word_romajis()) {
+            let converted: Vec<_> = hepburn
+                .iter()
+                .map(|romaji| convert::kunrei::hepburn_to_kunrei(romaji))
+                .collect();
+            assert_eq!(&converted, kunrei);
+        }
+        for kanji in ['日', '茶', '字', '𠮟', 'Α'] {
+            let converted: Vec<_> = data::kanji_romajis(kanji)
+                .iter()
+                .map(|romaji| convert::kunrei::hepburn_to_kunrei(romaji))
+                .collect();
+            assert_eq!(converted, data::kunrei_kanji_romajis(kanji), "{kanji}");
+        }
+
+        let data = HepburnRomanizer::builder()
+            .kana(true)
+            .kanji(true)
+            .word(true)
+            .system(RomanizationSystem::Kunrei)
+            .build();
+        assert_eq!(data.romanize_kana("し"), Some((3, "si")));
+        assert_eq!(data.romanize_kana("ツ"), Some((3, "tu")));
+        assert_eq!(data.romanize_kana("じゃ"), Some((6, "zya")));
+        assert_eq!(
+            data.romanize_kana_str("ふじさん"),
+            Some((12, "huzisan".into()))
+        );
+        assert!(data.romanize_vec("月").contains(&(3, "tuki")));
+        assert!(!data.romanize_vec("月").contains(&(3, "tsuki")));
+        assert!(data.is_romanizable_to("しんじゅく", "sinzyuku"));
+        assert!(data.is_romanizable_to("ちいさい", "tiisai"));
+        assert_eq!(data.romanize_kana_str("シー"), Some((6, "sii".into())));
+        assert!(!data.is_romanizable_to("ちいさい", "chiisai"));
+    }
+
     #[test]
     fn kanji() {
         assert_eq!(
@@ -725,4 +2513,71 @@ mod tests {
             vec![(6, "kyou"), (3, "ima"), (3, "kin"), (3, "kon"), (3, "na")]
         );
     }
+
+    #[test]
+    fn build_from_words() {
+        // Kanas
+        let romanizer = HepburnRomanizer::build_from_words([("きょ", ["kyo"]), ("今日", ["kyou"])]);
+        assert_eq!(romanizer.romanize_vec("きょう"), vec![(6, "kyo")]);
+        assert_eq!(romanizer.romanize_vec("今日")[0], (6, "kyou"));
+
+        // Rebuilding doesn't leak, and the romajis borrow from the romanizer
+        let romaji = |i: usize| {
+            let romanizer =
+                HepburnRomanizer::build_from_words([("日本語", [format!("nihongo{i}")])]);
+            romanizer.romanize_vec("日本語")[0].1.to_owned()
+        };
+        for i in 0..3 {
+            assert_eq!(romaji(i), format!("nihongo{i}"));
+        }
+    }
+
+    #[test]
+    fn automaton() {
+        let romanizer = HepburnRomanizer::builder()
+            .kana(true)
+            .word(true)
+            .extra_words([("日本語", ["nihon"])])
+            .build();
+        let romajis: Vec<_> = romanizer
+            .automaton()
+            .leftmost_find_iter("にほん日本語")
+            .map(|m| {
+                let romajis: Vec<_> = romanizer.pattern_romajis(m.value()).collect();
+                (m.end() - m.start(), romajis)
+            })
+            .collect();
+        assert_eq!(
+            romajis,
+            [
+                (3, vec!["ni"]),
+                (3, vec!["ho"]),
+                (3, vec!["n"]),
+                (9, vec!["nippongo", "nihon"])
+            ]
+        );
+        assert_eq!(romanizer.pattern_romajis(u32::MAX).next(), None);
+    }
+
+    #[test]
+    fn word_irregular() {
+        let data = HepburnRomanizer::builder().kana(true).word(true).build();
+        assert_eq!(data.romanize_vec("一人"), vec![(6, "hitori")]);
+        assert_eq!(data.romanize_vec("二人"), vec![(6, "futari")]);
+        assert_eq!(data.romanize_vec("明日"), vec![(6, "ashita"), (6, "asu")]);
+        assert_eq!(data.romanize_vec("大人"), vec![(6, "otona"), (6, "ushi")]);
+        assert_eq!(data.romanize_vec("上手"), vec![(6, "jouzu")]);
+        assert!(data.romanize_vec("清水").contains(&(6, "shimizu")));
+
+        let kana = HepburnRomanizer::builder().kana(true).build();
+        for line in include_str!("../data/joyo_fuhyo.tsv").lines() {
+            let (word, reading) = line.split_once('\t').unwrap();
+            let romaji = kana.romanize_kana_str_all(reading).unwrap();
+            assert!(
+                data.romanize_vec(word)
+                    .contains(&(word.len(), romaji.as_str())),
+                "{word} {romaji}"
+            );
+        }
+    }
 }
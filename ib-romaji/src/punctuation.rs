@@ -0,0 +1,107 @@
+/*!
+Punctuation handling when romanizing kanas.
+
+Japanese punctuation (e.g. `。`, `「」`) can't be romanized,
+so by default [`HepburnRomanizer::romanize_kana_iter()`](crate::HepburnRomanizer::romanize_kana_iter)
+stops at them, except `、`.
+See [`HepburnRomanizerBuilder::punctuation()`](crate::HepburnRomanizerBuilder::punctuation) for other modes.
+
+`・` is romanized as `.` like a kana regardless of the mode.
+*/
+use alloc::boxed::Box;
+
+/// Punctuation recognized by [`PunctuationMode::Keep`] and [`PunctuationMode::Drop`],
+/// including full-width spaces and half-width punctuation like `｡`.
+pub static PUNCTUATIONS: &[&str] = &[
+    "、", "。", "「", "」", "『", "』", "（", "）", "［", "］", "｛", "｝", "【", "】", "〈", "〉",
+    "《", "》", "〔", "〕", "？", "！", "，", "．", "：", "；", "…", "‥", "〜", "～", "　", "｡",
+    "｢", "｣", "､", "･",
+];
+
+/// How punctuation is handled when romanizing kanas.
+///
+/// ## Example
+/// ```
+/// use ib_romaji::{punctuation::PunctuationMode, HepburnRomanizer};
+///
+/// let romanizer = HepburnRomanizer::builder().kana(true).build();
+/// assert_eq!(romanizer.romanize_kana_str_all("「はい。」"), None);
+///
+/// let romanizer = HepburnRomanizer::builder()
+///     .kana(true)
+///     .punctuation(PunctuationMode::Drop)
+///     .build();
+/// assert_eq!(romanizer.romanize_kana_str_all("「はい。」").unwrap(), "hai");
+///
+/// let romanizer = HepburnRomanizer::builder()
+///     .kana(true)
+///     .punctuation(PunctuationMode::map([('。', "."), ('「', "\""), ('」', "\"")]))
+///     .build();
+/// assert_eq!(romanizer.romanize_kana_str_all("「はい。」").unwrap(), "\"hai.\"");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PunctuationMode {
+    /// Only `、` is kept as is. Other punctuation ends the kanas.
+    #[default]
+    Comma,
+    /// Punctuation in [`PUNCTUATIONS`] is kept as is.
+    Keep,
+    /// Punctuation in [`PUNCTUATIONS`] is romanized as an empty string.
+    Drop,
+    /// Punctuation in the map is transliterated, e.g. `('。', ".")`.
+    /// Other punctuation (including `、` if not in the map) ends the kanas.
+    ///
+    /// See also [`PunctuationMode::map()`].
+    Map(Box<[(char, Box<str>)]>),
+}
+
+impl PunctuationMode {
+    /// [`PunctuationMode::Map`] of `(punctuation, romaji)` pairs.
+    pub fn map<S: Into<Box<str>>>(map: impl IntoIterator<Item = (char, S)>) -> Self {
+        PunctuationMode::Map(map.into_iter().map(|(c, romaji)| (c, romaji.into())).collect())
+    }
+
+    /// Romanize the punctuation at the start of `s`, and return its length and romaji.
+    pub(crate) fn romanize(&self, s: &str) -> Option<(usize, &str)> {
+        let find = || PUNCTUATIONS.iter().find(|&&p| s.starts_with(p));
+        match self {
+            PunctuationMode::Comma => s.starts_with("、").then_some((3, "、")),
+            PunctuationMode::Keep => find().map(|&p| (p.len(), p)),
+            PunctuationMode::Drop => find().map(|p| (p.len(), "")),
+            PunctuationMode::Map(map) => {
+                let c = s.chars().next()?;
+                map.iter()
+                    .find(|&&(p, _)| p == c)
+                    .map(|(p, romaji)| (p.len_utf8(), &**romaji))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romanize() {
+        assert_eq!(PunctuationMode::Comma.romanize("、は"), Some((3, "、")));
+        assert_eq!(PunctuationMode::Comma.romanize("。"), None);
+        assert_eq!(PunctuationMode::Keep.romanize("。は"), Some((3, "。")));
+        assert_eq!(PunctuationMode::Keep.romanize("｢"), Some((3, "｢")));
+        assert_eq!(PunctuationMode::Keep.romanize("は"), None);
+        assert_eq!(PunctuationMode::Drop.romanize("　"), Some((3, "")));
+        assert_eq!(PunctuationMode::Drop.romanize(""), None);
+        let map = PunctuationMode::map([('。', "."), ('!', "!")]);
+        assert_eq!(map.romanize("。"), Some((3, ".")));
+        assert_eq!(map.romanize("!"), Some((1, "!")));
+        assert_eq!(map.romanize("、"), None);
+        assert_eq!(map.romanize(""), None);
+    }
+
+    #[test]
+    fn punctuations() {
+        for p in PUNCTUATIONS {
+            assert_eq!(p.chars().count(), 1, "{p}");
+        }
+    }
+}
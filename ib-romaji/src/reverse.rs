@@ -0,0 +1,141 @@
+/*!
+Reverse romanization, i.e. romaji to kana.
+
+Only hiragana are produced. Historical kanas (ゐ, ゑ) and standalone small kanas are excluded.
+*/
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use daachorse::{CharwiseDoubleArrayAhoCorasick, CharwiseDoubleArrayAhoCorasickBuilder, MatchKind};
+use ib_unicode::str::RoundCharBoundaryExt;
+
+use crate::{HepburnRomanizer, RomanizationSystem, data};
+
+/// Romaji to kana index.
+#[derive(Clone)]
+pub(crate) struct KanaIndex {
+    ac: CharwiseDoubleArrayAhoCorasick<u32>,
+    kanas: Vec<Vec<&'static str>>,
+}
+
+impl KanaIndex {
+    fn is_reversible(kana: &str) -> bool {
+        let mut chars = kana.chars();
+        let first = chars.next().unwrap();
+        if !kana.chars().all(|c| matches!(c, 'ぁ'..='ゖ')) {
+            return false;
+        }
+        if chars.next().is_none() {
+            !"ぁぃぅぇぉっゃゅょゎゕゖゐゑ".contains(first)
+        } else {
+            true
+        }
+    }
+
+    pub(crate) fn new(system: RomanizationSystem) -> Self {
+        let romajis = match system {
+            RomanizationSystem::Hepburn => data::kana::HEPBURN_ROMAJIS,
+            #[cfg(feature = "kunrei")]
+            RomanizationSystem::Kunrei => data::kana::KUNREI_ROMAJIS,
+        };
+        let mut map: BTreeMap<&'static str, Vec<&'static str>> = BTreeMap::new();
+        for (&kana, &romaji) in data::kana::HEPBURN_KANAS.iter().zip(romajis) {
+            if Self::is_reversible(kana) {
+                map.entry(romaji).or_default().push(kana);
+            }
+        }
+        let (romajis, kanas): (Vec<_>, Vec<_>) = map.into_iter().unzip();
+        let ac = CharwiseDoubleArrayAhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .build(romajis)
+            .unwrap();
+        Self { ac, kanas }
+    }
+}
+
+impl HepburnRomanizer {
+    /// Convert a romaji string back to all possible kana strings.
+    ///
+    /// Returns an empty `Vec` if the romaji cannot be fully converted.
+    ///
+    /// ## Notes
+    /// - `n` before vowels and `y` is not treated as `ん`, i.e. n apostrophe (e.g. `n'a`) is needed.
+    /// - Only hiragana are produced, see [`reverse`](crate::reverse) for details.
+    ///
+    /// ## Example
+    /// ```
+    /// use ib_romaji::HepburnRomanizer;
+    ///
+    /// let romanizer = HepburnRomanizer::builder().kana(true).build();
+    /// assert_eq!(romanizer.romaji_to_kana("nihon"), vec!["にほん"]);
+    /// assert_eq!(romanizer.romaji_to_kana("ji"), vec!["じ", "ぢ"]);
+    /// ```
+    pub fn romaji_to_kana(&self, romaji: &str) -> Vec<String> {
+        let mut kanas = Vec::new();
+        if romaji.is_empty() {
+            return kanas;
+        }
+        self.romaji_to_kana_with(romaji, 0, &mut String::new(), &mut kanas);
+        kanas.dedup();
+        kanas
+    }
+
+    fn romaji_to_kana_with(
+        &self,
+        romaji: &str,
+        start: usize,
+        buf: &mut String,
+        kanas: &mut Vec<String>,
+    ) {
+        if start == romaji.len() {
+            kanas.push(buf.clone());
+            return;
+        }
+        let s = &romaji[start..];
+        let s = &s[..s.floor_char_boundary_ib(data::kana::KANA_ROMAJI_MAX_LEN)];
+        let index = &self.reverse;
+        for m in index.ac.find_overlapping_iter(s).filter(|m| m.start() == 0) {
+            let end = start + m.end();
+            if m.end() == 1
+                && s.as_bytes()[0] == Self::POSSIBLE_PREFIX as u8
+                && romaji
+                    .as_bytes()
+                    .get(end)
+                    .is_some_and(|&next| Self::is_romaji_n_suffix(next))
+            {
+                continue;
+            }
+            for kana in &index.kanas[m.value() as usize] {
+                let len = buf.len();
+                buf.push_str(kana);
+                self.romaji_to_kana_with(romaji, end, buf, kanas);
+                buf.truncate(len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romaji_to_kana() {
+        let data = HepburnRomanizer::builder().kana(true).build();
+        assert_eq!(data.romaji_to_kana("nihon"), vec!["にほん"]);
+        assert_eq!(data.romaji_to_kana("ji"), vec!["じ", "ぢ"]);
+        assert_eq!(data.romaji_to_kana("zu"), vec!["ず", "づ"]);
+        assert_eq!(data.romaji_to_kana("kyou"), vec!["きょう"]);
+        assert_eq!(data.romaji_to_kana("kotchi"), vec!["こっち"]);
+
+        // n
+        assert_eq!(data.romaji_to_kana("kani"), vec!["かに"]);
+        assert_eq!(data.romaji_to_kana("kanni"), vec!["かんに"]);
+        assert_eq!(data.romaji_to_kana("kan'i"), vec!["かんい"]);
+        assert_eq!(data.romaji_to_kana("hon'ya"), vec!["ほんや"]);
+        assert_eq!(data.romaji_to_kana("honya"), vec!["ほにゃ"]);
+
+        assert!(data.romaji_to_kana("").is_empty());
+        assert!(data.romaji_to_kana("xyz").is_empty());
+        assert!(data.romaji_to_kana("日本").is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+/*!
+Half-width katakana (半角カナ) normalization.
+
+Half-width katakana (e.g. `ｶﾀｶﾅ`) are normalized to full-width ones (e.g. `カタカナ`),
+and the half-width (semi-)voiced sound marks (`ﾞ`, `ﾟ`) are combined into the preceding kana
+(e.g. `ｶﾞ` to `ガ`).
+
+Although half-width kanas can already be romanized, words containing them (e.g. `いす取りｹﾞｰﾑ`)
+can only be romanized after normalization.
+See [`HepburnRomanizerBuilder::normalize_kana()`](crate::HepburnRomanizerBuilder::normalize_kana).
+
+Hiragana and katakana are romanized identically, so they are not normalized into each other.
+*/
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+/// `｡`..=`ﾟ` -> `。`..=`゜`
+static FULL_WIDTHS: [char; 63] = [
+    '。', '「', '」', '、', '・', 'ヲ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ッ', 'ー',
+    'ア', 'イ', 'ウ', 'エ', 'オ', 'カ', 'キ', 'ク', 'ケ', 'コ', 'サ', 'シ', 'ス', 'セ', 'ソ', 'タ',
+    'チ', 'ツ', 'テ', 'ト', 'ナ', 'ニ', 'ヌ', 'ネ', 'ノ', 'ハ', 'ヒ', 'フ', 'ヘ', 'ホ', 'マ', 'ミ',
+    'ム', 'メ', 'モ', 'ヤ', 'ユ', 'ヨ', 'ラ', 'リ', 'ル', 'レ', 'ロ', 'ワ', 'ン', '゛', '゜',
+];
+
+const VOICED_MARK: char = 'ﾞ';
+const SEMI_VOICED_MARK: char = 'ﾟ';
+
+/// Whether `c` is a half-width katakana, including punctuations and sound marks.
+#[inline]
+pub const fn is_half_width_kana(c: char) -> bool {
+    matches!(c, '｡'..='ﾟ')
+}
+
+/// Convert a half-width katakana to the full-width one, or return `c` as is.
+///
+/// The sound marks are converted to the standalone full-width ones (`゛`, `゜`).
+#[inline]
+pub fn to_full_width_kana(c: char) -> char {
+    if is_half_width_kana(c) {
+        FULL_WIDTHS[(c as u32 - '｡' as u32) as usize]
+    } else {
+        c
+    }
+}
+
+/// Combine a full-width katakana with a half-width (semi-)voiced sound mark, e.g. `カ` + `ﾞ` to `ガ`.
+pub fn combine_sound_mark(kana: char, mark: char) -> Option<char> {
+    match (kana, mark) {
+        ('ウ', VOICED_MARK) => Some('ヴ'),
+        ('ワ', VOICED_MARK) => Some('ヷ'),
+        ('ヲ', VOICED_MARK) => Some('ヺ'),
+        (
+            'カ' | 'キ' | 'ク' | 'ケ' | 'コ' | 'サ' | 'シ' | 'ス' | 'セ' | 'ソ' | 'タ' | 'チ'
+            | 'ツ' | 'テ' | 'ト',
+            VOICED_MARK,
+        ) => char::from_u32(kana as u32 + 1),
+        ('ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ', VOICED_MARK) => char::from_u32(kana as u32 + 1),
+        ('ハ' | 'ヒ' | 'フ' | 'ヘ' | 'ホ', SEMI_VOICED_MARK) => {
+            char::from_u32(kana as u32 + 2)
+        }
+        _ => None,
+    }
+}
+
+/// Normalize half-width katakana in `s` and push the result to `buf`,
+/// until `buf` is at least `max_len` bytes.
+///
+/// For each normalized char, `(end in buf, end in s)` is pushed to `ends`.
+///
+/// Returns the length of `s` that has been normalized.
+pub(crate) fn normalize_into(
+    s: &str,
+    max_len: usize,
+    buf: &mut String,
+    ends: &mut Vec<(usize, usize)>,
+) -> usize {
+    let mut chars = s.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if buf.len() >= max_len {
+            break;
+        }
+        let mut c = to_full_width_kana(c);
+        if let Some(combined) = chars
+            .peek()
+            .and_then(|&(_, mark)| combine_sound_mark(c, mark))
+        {
+            c = combined;
+            chars.next();
+        }
+        buf.push(c);
+        let end = chars.peek().map_or(s.len(), |&(i, _)| i);
+        ends.push((buf.len(), end));
+    }
+    ends.last().map_or(0, |&(_, end)| end)
+}
+
+/// Normalize half-width katakana in `s`.
+///
+/// Returns [`Cow::Borrowed`] without allocation if there are no half-width katakana.
+///
+/// ## Example
+/// ```
+/// use ib_romaji::width::normalize_half_width_kana;
+///
+/// assert_eq!(normalize_half_width_kana("ｶﾀｶﾅ"), "カタカナ");
+/// assert_eq!(normalize_half_width_kana("ｶﾞｯｺｳ､ﾊﾟﾝ"), "ガッコウ、パン");
+/// assert_eq!(normalize_half_width_kana("いす取りｹﾞｰﾑ"), "いす取りゲーム");
+/// ```
+pub fn normalize_half_width_kana(s: &str) -> Cow<'_, str> {
+    match s.char_indices().find(|&(_, c)| is_half_width_kana(c)) {
+        Some((i, _)) => {
+            let mut buf = String::with_capacity(s.len());
+            buf.push_str(&s[..i]);
+            normalize_into(&s[i..], usize::MAX, &mut buf, &mut Vec::new());
+            Cow::Owned(buf)
+        }
+        None => Cow::Borrowed(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_width() {
+        assert_eq!(to_full_width_kana('ｱ'), 'ア');
+        assert_eq!(to_full_width_kana('ﾝ'), 'ン');
+        assert_eq!(to_full_width_kana('ｰ'), 'ー');
+        assert_eq!(to_full_width_kana('ﾞ'), '゛');
+        assert_eq!(to_full_width_kana('a'), 'a');
+    }
+
+    #[test]
+    fn sound_mark() {
+        assert_eq!(normalize_half_width_kana("ｶﾞｷﾞｸﾞｹﾞｺﾞ"), "ガギグゲゴ");
+        assert_eq!(normalize_half_width_kana("ﾂﾞﾃﾞﾄﾞ"), "ヅデド");
+        assert_eq!(normalize_half_width_kana("ﾊﾞﾋﾟﾌﾞﾍﾟﾎﾞ"), "バピブペボ");
+        assert_eq!(normalize_half_width_kana("ｳﾞｧ"), "ヴァ");
+        assert_eq!(normalize_half_width_kana("ｱﾞ"), "ア゛");
+        assert_eq!(normalize_half_width_kana("ﾟ"), "゜");
+    }
+
+    #[test]
+    fn borrowed() {
+        assert!(matches!(
+            normalize_half_width_kana("カタカナ"),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(normalize_half_width_kana("ｶﾀｶﾅ"), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn ends() {
+        let mut buf = String::new();
+        let mut ends = Vec::new();
+        let len = normalize_into("ｶﾞｯｺ", 6, &mut buf, &mut ends);
+        assert_eq!(buf, "ガッ");
+        assert_eq!(ends, [(3, 6), (6, 9)]);
+        assert_eq!(len, 9);
+    }
+}
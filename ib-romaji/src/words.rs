@@ -0,0 +1,42 @@
+//! Word romajis owned by a romanizer, i.e. of [extra words](crate::HepburnRomanizerBuilder::extra_words).
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::Range;
+
+/// The romajis of each word, stored in two flat tables.
+///
+/// Unlike the embedded `&'static [&'static [&'static str]]`, these are freed with the romanizer,
+/// so romanizers can be built (and dropped) repeatedly.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct WordRomajis {
+    romajis: Vec<Box<str>>,
+    /// The range in `romajis` of each word.
+    words: Vec<Range<u32>>,
+}
+
+impl WordRomajis {
+    /// The romajis of the `i`-th word.
+    pub fn get(&self, i: usize) -> Option<&[Box<str>]> {
+        self.words
+            .get(i)
+            .map(|r| &self.romajis[r.start as usize..r.end as usize])
+    }
+
+    /// The romajis of each word.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[Box<str>]> {
+        self.words
+            .iter()
+            .map(|r| &self.romajis[r.start as usize..r.end as usize])
+    }
+
+    /// Add a word with its romajis.
+    pub fn push<R: Into<Box<str>>>(&mut self, romajis: impl IntoIterator<Item = R>) {
+        let start = self.romajis.len() as u32;
+        self.romajis.extend(romajis.into_iter().map(Into::into));
+        self.words.push(start..self.romajis.len() as u32);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.romajis.shrink_to_fit();
+        self.words.shrink_to_fit();
+    }
+}
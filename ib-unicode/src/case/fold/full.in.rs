@@ -0,0 +1,106 @@
+&[
+('ß',"ss"),
+('İ',"i\u{307}"),
+('ŉ',"ʼn"),
+('ǰ',"j\u{30c}"),
+('ΐ',"ι\u{308}\u{301}"),
+('ΰ',"υ\u{308}\u{301}"),
+('և',"եւ"),
+('ẖ',"h\u{331}"),
+('ẗ',"t\u{308}"),
+('ẘ',"w\u{30a}"),
+('ẙ',"y\u{30a}"),
+('ẚ',"aʾ"),
+('ẞ',"ss"),
+('ὐ',"υ\u{313}"),
+('ὒ',"υ\u{313}\u{300}"),
+('ὔ',"υ\u{313}\u{301}"),
+('ὖ',"υ\u{313}\u{342}"),
+('ᾀ',"ἀι"),
+('ᾁ',"ἁι"),
+('ᾂ',"ἂι"),
+('ᾃ',"ἃι"),
+('ᾄ',"ἄι"),
+('ᾅ',"ἅι"),
+('ᾆ',"ἆι"),
+('ᾇ',"ἇι"),
+('ᾈ',"ἀι"),
+('ᾉ',"ἁι"),
+('ᾊ',"ἂι"),
+('ᾋ',"ἃι"),
+('ᾌ',"ἄι"),
+('ᾍ',"ἅι"),
+('ᾎ',"ἆι"),
+('ᾏ',"ἇι"),
+('ᾐ',"ἠι"),
+('ᾑ',"ἡι"),
+('ᾒ',"ἢι"),
+('ᾓ',"ἣι"),
+('ᾔ',"ἤι"),
+('ᾕ',"ἥι"),
+('ᾖ',"ἦι"),
+('ᾗ',"ἧι"),
+('ᾘ',"ἠι"),
+('ᾙ',"ἡι"),
+('ᾚ',"ἢι"),
+('ᾛ',"ἣι"),
+('ᾜ',"ἤι"),
+('ᾝ',"ἥι"),
+('ᾞ',"ἦι"),
+('ᾟ',"ἧι"),
+('ᾠ',"ὠι"),
+('ᾡ',"ὡι"),
+('ᾢ',"ὢι"),
+('ᾣ',"ὣι"),
+('ᾤ',"ὤι"),
+('ᾥ',"ὥι"),
+('ᾦ',"ὦι"),
+('ᾧ',"ὧι"),
+('ᾨ',"ὠι"),
+('ᾩ',"ὡι"),
+('ᾪ',"ὢι"),
+('ᾫ',"ὣι"),
+('ᾬ',"ὤι"),
+('ᾭ',"ὥι"),
+('ᾮ',"ὦι"),
+('ᾯ',"ὧι"),
+('ᾲ',"ὰι"),
+('ᾳ',"αι"),
+('ᾴ',"άι"),
+('ᾶ',"α\u{342}"),
+('ᾷ',"α\u{342}ι"),
+('ᾼ',"αι"),
+('ῂ',"ὴι"),
+('ῃ',"ηι"),
+('ῄ',"ήι"),
+('ῆ',"η\u{342}"),
+('ῇ',"η\u{342}ι"),
+('ῌ',"ηι"),
+('ῒ',"ι\u{308}\u{300}"),
+('ΐ',"ι\u{308}\u{301}"),
+('ῖ',"ι\u{342}"),
+('ῗ',"ι\u{308}\u{342}"),
+('ῢ',"υ\u{308}\u{300}"),
+('ΰ',"υ\u{308}\u{301}"),
+('ῤ',"ρ\u{313}"),
+('ῦ',"υ\u{342}"),
+('ῧ',"υ\u{308}\u{342}"),
+('ῲ',"ὼι"),
+('ῳ',"ωι"),
+('ῴ',"ώι"),
+('ῶ',"ω\u{342}"),
+('ῷ',"ω\u{342}ι"),
+('ῼ',"ωι"),
+('ﬀ',"ff"),
+('ﬁ',"fi"),
+('ﬂ',"fl"),
+('ﬃ',"ffi"),
+('ﬄ',"ffl"),
+('ﬅ',"st"),
+('ﬆ',"st"),
+('ﬓ',"մն"),
+('ﬔ',"մե"),
+('ﬕ',"մի"),
+('ﬖ',"վն"),
+('ﬗ',"մխ"),
+]
\ No newline at end of file
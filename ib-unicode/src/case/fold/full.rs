@@ -0,0 +1,38 @@
+/// `(c, full folding)` of the chars whose full case folding differs from the simple one, sorted by `c`.
+static FULL: &[(char, &str)] = include!("full.in.rs");
+
+/// Returns `None` if the full case folding of `c` is the same as the simple one.
+pub fn fold(c: char) -> Option<&'static str> {
+    // All are non-ASCII, the first one is 'ß'
+    if c < 'ß' {
+        return None;
+    }
+    FULL.binary_search_by_key(&c, |&(c, _)| c)
+        .ok()
+        .map(|i| FULL[i].1)
+}
+
+/// CaseFolding.txt from https://www.unicode.org/Public/16.0.0/ucd/
+#[cfg(all(not(feature = "doc"), feature = "_test_data"))]
+mod codegen {
+    use std::{fmt::Write, fs};
+
+    #[test]
+    fn codegen() {
+        let data = fs::read_to_string("data/ucd-16.0.0/CaseFolding.txt").unwrap();
+        let mut s = String::new();
+        write!(s, "&[\n").unwrap();
+        for line in data.lines() {
+            let line = line.split('#').next().unwrap();
+            let fields: Vec<_> = line.split(';').map(str::trim).collect();
+            // <code>; <status>; <mapping>;
+            if let [code, "F", mapping, ..] = fields[..] {
+                let c = |hex| char::from_u32(u32::from_str_radix(hex, 16).unwrap()).unwrap();
+                let mapping: String = mapping.split(' ').map(c).collect();
+                write!(s, "({:?},{mapping:?}),\n", c(code)).unwrap();
+            }
+        }
+        write!(s, "]").unwrap();
+        fs::write("src/case/fold/full.in.rs", s).unwrap();
+    }
+}
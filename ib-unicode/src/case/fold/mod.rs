@@ -1,3 +1,5 @@
+#[cfg(feature = "case-fold-full")]
+pub mod full;
 #[cfg(feature = "perf-case-fold")]
 pub mod map;
 #[cfg(any(not(feature = "perf-case-fold"), feature = "bench"))]
@@ -2,7 +2,7 @@
 ## Case folding
 > Case folding, i.e. mapping strings to a canonical form for string comparison, typically results in lowercase characters; however, characters in the Cherokee script resolve to uppercase characters. Case folding isn't context-, language-, or locale-sensitive; however, you can specify whether to use mappings for languages like Turkish.
 
-Currently, only simple [case folding](https://www.unicode.org/Public/16.0.0/ucd/CaseFolding.txt) is supported. Simple case folding does not handle some special letter cases that have multiple characters, like `Maße` cannot match `MASSE`.
+By default, only simple [case folding](https://www.unicode.org/Public/16.0.0/ucd/CaseFolding.txt) is supported. Simple case folding does not handle some special letter cases that have multiple characters, like `Maße` cannot match `MASSE`. See [full case folding](#full-case-folding) for that.
 
 The API is [`CharCaseExt::to_simple_fold_case()`] and [`StrCaseExt::to_simple_fold_case()`], for example:
 ```
@@ -18,6 +18,25 @@ assert_eq!("ΒΊΟΣ".to_simple_fold_case(), "βίοσ");
 
 Simple case folding is also used by the [`regex`](https://docs.rs/regex/) crate.
 
+### Full case folding
+Full case folding additionally maps some characters to multiple characters, e.g. `ß` and `ẞ` to `ss`, `ﬁ` to `fi`. It requires the `case-fold-full` feature.
+
+The API is [`StrCaseExt::to_full_fold_case()`], for example:
+```
+# #[cfg(feature = "case-fold-full")]
+# {
+use ib_unicode::case::StrCaseExt;
+
+assert_eq!("Maße".to_full_fold_case(), "masse");
+assert_eq!("MASSE".to_full_fold_case(), "masse");
+assert_eq!("ﬁle".to_full_fold_case(), "file");
+# }
+```
+
+- `İ` is mapped to `i\u{307}` (`i̇`), not `i`. The Turkic-specific mappings (`T` status in `CaseFolding.txt`) are not used.
+- Characters without multi-char mappings are mapped the same as simple case folding, so the results are the same as the `regex` crate's for them.
+- Performance: Full case folding is slower than simple case folding. Each non-ASCII character needs an extra binary search in the full mapping table (104 entries), and the result may be longer than the input, so it can only be done on strings instead of chars. Byte offsets in the folded string also no longer correspond to the original string.
+
 ## Mono lowercase
 The "mono lowercase" mentioned in this module refers to the single-char lowercase mapping of a Unicode character. This is different from Unicode's [simple case folding](#case-folding) in that it always results in lowercase characters, and does not normalize different lower cases of a character to the same one (e.g. `σ` and `ς` are kept).
 
@@ -40,8 +59,30 @@ assert_eq!("ΒΊΟΣ".to_mono_lowercase(), "βίοσ");
   - ΐ, ΐ
   - ΰ, ΰ
   - ﬅ, ﬆ
+
+## Mono uppercase and title case
+Similarly, "mono uppercase" refers to the single-char uppercase mapping of a Unicode character. The multi-char uppercase mappings only keep the first char, e.g. `ß` is mapped to `S` instead of `SS`, like `İ` for [mono lowercase](#mono-lowercase).
+
+"Mono title case" is the same as mono uppercase, except for the title case digraphs (e.g. `ǆ` to `ǅ`), Greek letters with ypogegrammeni (e.g. `ᾳ` to `ᾼ`), and Georgian letters (not changed).
+
+For example:
+```
+use ib_unicode::case::{CharCaseExt, StrCaseExt};
+
+assert_eq!("βίος".to_mono_uppercase(), "ΒΊΟΣ");
+assert_eq!("Maße".to_mono_uppercase(), "MASE");
+assert_eq!('ǆ'.to_mono_titlecase(), 'ǅ');
+assert_eq!("hello, wORLD!".to_title_case(), "Hello, World!");
+```
+
+- Unicode version: 16.0.0.
+- Compared to [`char::to_uppercase()`]/[`str::to_uppercase()`] in `std`: the same, except for the multi-char mappings.
+  - [`to_mono_uppercase()`](CharCaseExt::to_mono_uppercase) is also much faster if `perf-case-map` feature is enabled.
+- [`StrCaseExt::to_title_case()`] splits words by non-alphanumeric chars. There is no special handling for apostrophes, e.g. `they're` is mapped to `They'Re`.
 */
 
+use alloc::string::String;
+
 use crate::Sealed;
 
 #[cfg(feature = "case-fold")]
@@ -55,6 +96,14 @@ pub trait CharCaseExt: Sealed {
     /// See [mono lowercase](super::case#mono-lowercase) for details.
     fn to_mono_lowercase(self) -> char;
 
+    /// The multi-char uppercase mappings (e.g. 'ß' -> "SS") only keep the first char.
+    ///
+    /// See [mono uppercase](super::case#mono-uppercase-and-title-case) for details.
+    fn to_mono_uppercase(self) -> char;
+
+    /// See [mono title case](super::case#mono-uppercase-and-title-case) for details.
+    fn to_mono_titlecase(self) -> char;
+
     /// A convenient method for feature-gated case folding.
     /// If `case-fold` feature is enabled, it uses simple case folding; otherwise it uses `to_ascii_lowercase()`.
     fn to_simple_or_ascii_fold_case(self) -> char;
@@ -83,6 +132,30 @@ impl CharCaseExt for char {
         map::to_mono_lowercase(self)
     }
 
+    fn to_mono_uppercase(self) -> char {
+        #[cfg(not(feature = "perf-case-map"))]
+        return self.to_uppercase().next().unwrap();
+        #[cfg(feature = "perf-case-map")]
+        map::to_mono_uppercase(self)
+    }
+
+    fn to_mono_titlecase(self) -> char {
+        match self {
+            '\u{1c4}'..='\u{1c6}' => '\u{1c5}',
+            '\u{1c7}'..='\u{1c9}' => '\u{1c8}',
+            '\u{1ca}'..='\u{1cc}' => '\u{1cb}',
+            '\u{1f1}'..='\u{1f3}' => '\u{1f2}',
+            // Georgian Mkhedruli letters have no title case
+            '\u{10d0}'..='\u{10fa}' | '\u{10fd}'..='\u{10ff}' => self,
+            // ᾀ..=ᾇ -> ᾈ..=ᾏ, ᾐ..=ᾗ -> ᾘ..=ᾟ, ᾠ..=ᾧ -> ᾨ..=ᾯ
+            '\u{1f80}'..='\u{1faf}' => unsafe { char::from_u32_unchecked(self as u32 | 8) },
+            '\u{1fb3}' | '\u{1fbc}' => '\u{1fbc}',
+            '\u{1fc3}' | '\u{1fcc}' => '\u{1fcc}',
+            '\u{1ff3}' | '\u{1ffc}' => '\u{1ffc}',
+            c => c.to_mono_uppercase(),
+        }
+    }
+
     fn to_simple_or_ascii_fold_case(self) -> char {
         #[cfg(not(feature = "case-fold"))]
         return self.to_ascii_lowercase();
@@ -113,6 +186,15 @@ pub trait StrCaseExt: Sealed {
     /// See [mono lowercase](super::case#mono-lowercase) for details.
     fn to_mono_lowercase(&self) -> String;
 
+    /// See [mono uppercase](super::case#mono-uppercase-and-title-case) for details.
+    fn to_mono_uppercase(&self) -> String;
+
+    /// Map the first char of each word to [mono title case](super::case#mono-uppercase-and-title-case)
+    /// and the rest to [mono lowercase](super::case#mono-lowercase).
+    ///
+    /// Words are split by non-alphanumeric chars.
+    fn to_title_case(&self) -> String;
+
     /// A convenient method for feature-gated case folding.
     /// If `case-fold` feature is enabled, it uses simple case folding; otherwise it uses `to_ascii_lowercase()`.
     fn to_simple_or_ascii_fold_case(&self) -> String;
@@ -120,6 +202,10 @@ pub trait StrCaseExt: Sealed {
     /// See [case folding](super::case#case-folding) for details.
     #[cfg(feature = "case-fold")]
     fn to_simple_fold_case(&self) -> String;
+
+    /// See [full case folding](super::case#full-case-folding) for details.
+    #[cfg(feature = "case-fold-full")]
+    fn to_full_fold_case(&self) -> String;
 }
 
 impl StrCaseExt for str {
@@ -127,6 +213,24 @@ impl StrCaseExt for str {
         self.chars().map(|c| c.to_mono_lowercase()).collect()
     }
 
+    fn to_mono_uppercase(&self) -> String {
+        self.chars().map(|c| c.to_mono_uppercase()).collect()
+    }
+
+    fn to_title_case(&self) -> String {
+        let mut word_start = true;
+        self.chars()
+            .map(|c| {
+                let mapped = match word_start {
+                    true => c.to_mono_titlecase(),
+                    false => c.to_mono_lowercase(),
+                };
+                word_start = !c.is_alphanumeric();
+                mapped
+            })
+            .collect()
+    }
+
     fn to_simple_or_ascii_fold_case(&self) -> String {
         self.chars()
             .map(|c| c.to_simple_or_ascii_fold_case())
@@ -137,6 +241,18 @@ impl StrCaseExt for str {
     fn to_simple_fold_case(&self) -> String {
         self.chars().map(|c| c.to_simple_fold_case()).collect()
     }
+
+    #[cfg(feature = "case-fold-full")]
+    fn to_full_fold_case(&self) -> String {
+        let mut s = String::with_capacity(self.len());
+        for c in self.chars() {
+            match fold::full::fold(c) {
+                Some(full) => s.push_str(full),
+                None => s.push(c.to_simple_fold_case()),
+            }
+        }
+        s
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +274,50 @@ mod tests {
         chars
     }
 
+    #[test]
+    fn titlecase() {
+        assert_eq!('a'.to_mono_titlecase(), 'A');
+        assert_eq!('ß'.to_mono_titlecase(), 'S');
+        assert_eq!('Ǆ'.to_mono_titlecase(), 'ǅ');
+        assert_eq!('ǆ'.to_mono_titlecase(), 'ǅ');
+        assert_eq!('ǳ'.to_mono_titlecase(), 'ǲ');
+        assert_eq!('ᾀ'.to_mono_titlecase(), 'ᾈ');
+        assert_eq!('ᾈ'.to_mono_titlecase(), 'ᾈ');
+        assert_eq!('ᾳ'.to_mono_titlecase(), 'ᾼ');
+        assert_eq!('ა'.to_mono_titlecase(), 'ა');
+        assert_eq!('う'.to_mono_titlecase(), 'う');
+
+        assert_eq!("la vie est drôle".to_title_case(), "La Vie Est Drôle");
+        assert_eq!("ΒΊΟΣ ǆungla".to_title_case(), "Βίοσ ǅungla");
+        assert_eq!("straße".to_title_case(), "Straße");
+        assert_eq!("ßtraße 1st".to_title_case(), "Straße 1st");
+        assert_eq!("".to_title_case(), "");
+    }
+
+    #[cfg(feature = "case-fold-full")]
+    #[test]
+    fn full_fold() {
+        assert_eq!("Maße".to_full_fold_case(), "MASSE".to_full_fold_case());
+        assert_eq!("ẞ".to_full_fold_case(), "ss");
+        assert_eq!("ﬃ".to_full_fold_case(), "ffi");
+        assert_eq!("İ".to_full_fold_case(), "i\u{307}");
+        assert_eq!("ΐ".to_full_fold_case(), "ι\u{308}\u{301}");
+        assert_eq!("ΒΊΟΣ".to_full_fold_case(), "βίοσ");
+
+        for c in (0..=char::MAX as u32).filter_map(char::from_u32) {
+            let full = c.encode_utf8(&mut [0; 4]).to_full_fold_case();
+            match fold::full::fold(c) {
+                // The same as simple case folding (and `regex`)
+                None => assert_eq!(full, c.to_simple_fold_case().to_string()),
+                // Multi-char and already folded
+                Some(_) => {
+                    assert!(full.chars().count() > 1);
+                    assert_eq!(full.to_full_fold_case(), full, "{c:?}");
+                }
+            }
+        }
+    }
+
     #[test]
     fn mono() {
         let mono = mono_set();
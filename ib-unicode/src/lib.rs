@@ -2,15 +2,19 @@
 Fast Unicode utils.
 
 ## Features
-- [Simple case folding](case#case-folding)
-- [Mono `to_lowercase()`](case#mono-lowercase)
+- [Simple case folding](case#case-folding) and [full case folding](case#full-case-folding)
+- [Mono `to_lowercase()`](case#mono-lowercase), [`to_uppercase()` and title case](case#mono-uppercase-and-title-case)
 - [ASCII](ascii) search utils
 - `floor_char_boundary()` and `ceil_char_boundary()` polyfill
 
 ## Crate features
 */
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(feature = "doc", doc = document_features::document_features!())]
+
+extern crate alloc;
+
 pub mod ascii;
 pub mod case;
 pub mod str;